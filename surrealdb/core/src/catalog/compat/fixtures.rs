@@ -136,6 +136,7 @@ pub fn table_basic() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -170,6 +171,7 @@ pub fn table_with_view() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -196,6 +198,7 @@ pub fn table_schemafull() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -230,6 +233,7 @@ pub fn table_relation() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -257,6 +261,7 @@ pub fn table_with_materialized_view() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -280,6 +285,7 @@ pub fn table_any_type() -> TableDefinition {
 		cache_indexes_ts: UuidExt::nil(),
 		graphql_alias: None,
 		graphql_deprecated: None,
+		ttl: None,
 	}
 }
 
@@ -1012,6 +1018,7 @@ pub fn index_basic() -> IndexDefinition {
 		index: Index::Idx,
 		comment: None,
 		prepare_remove: false,
+		cond: None,
 	}
 }
 
@@ -1025,6 +1032,7 @@ pub fn index_unique() -> IndexDefinition {
 		index: Index::Uniq,
 		comment: Some("Unique email constraint".to_string()),
 		prepare_remove: false,
+		cond: None,
 	}
 }
 
@@ -1046,9 +1054,11 @@ pub fn index_hnsw() -> IndexDefinition {
 			extend_candidates: false,
 			keep_pruned_connections: true,
 			use_hashed_vector: false,
+			quantization: None,
 		}),
 		comment: Some("Vector similarity search index".to_string()),
 		prepare_remove: false,
+		cond: None,
 	}
 }
 
@@ -1066,9 +1076,11 @@ pub fn index_fulltext() -> IndexDefinition {
 				k1: 1.2,
 				b: 0.75,
 			},
+			fuzzy: None,
 		}),
 		comment: Some("Full-text search on articles".to_string()),
 		prepare_remove: false,
+		cond: None,
 	}
 }
 
@@ -1084,6 +1096,7 @@ pub fn index_count() -> IndexDefinition {
 		)))))),
 		comment: None,
 		prepare_remove: true,
+		cond: None,
 	}
 }
 
@@ -496,6 +496,24 @@ pub(crate) trait TableProvider: ProviderFutureSendRequirement {
 		version: Option<u64>,
 	) -> BoxProviderFut<'a, Result<Arc<[catalog::SubscriptionDefinition]>>>;
 
+	/// Retrieve all sink definitions for a specific table.
+	fn all_tb_sinks<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<[catalog::SinkDefinition]>>>;
+
+	/// Retrieve all consumer definitions for a specific table.
+	fn all_tb_consumers<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<[catalog::ConsumerDefinition]>>>;
+
 	/// Retrieve a specific table definition.
 	fn get_tb<'a>(
 		&'a self,
@@ -532,6 +550,26 @@ pub(crate) trait TableProvider: ProviderFutureSendRequirement {
 		version: Option<u64>,
 	) -> BoxProviderFut<'a, Result<Arc<catalog::EventDefinition>>>;
 
+	/// Retrieve a sink for a table.
+	fn get_tb_sink<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		sk: &'a str,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<catalog::SinkDefinition>>>;
+
+	/// Retrieve a consumer for a table.
+	fn get_tb_consumer<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		cs: &'a str,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<catalog::ConsumerDefinition>>>;
+
 	/// Retrieve a field for a table.
 	fn get_tb_field<'a>(
 		&'a self,
@@ -67,6 +67,8 @@ fn tokenizer_structure_strand(t: Tokenizer) -> Strand {
 		Tokenizer::Camel => Strand::new_static("CAMEL"),
 		Tokenizer::Class => Strand::new_static("CLASS"),
 		Tokenizer::Punct => Strand::new_static("PUNCT"),
+		Tokenizer::Unicode => Strand::new_static("UNICODE"),
+		Tokenizer::Cjk => Strand::new_static("CJK"),
 	}
 }
 
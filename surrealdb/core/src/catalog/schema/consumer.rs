@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use revision::revisioned;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::DefineKind;
+use crate::sql::{self};
+use crate::types::PublicDuration;
+use crate::val::{Datetime, TableName, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct ConsumerDefinition {
+	pub(crate) name: Strand,
+	pub(crate) target_table: TableName,
+	/// The number of partitions the changes of the table are split into.
+	pub(crate) partitions: u16,
+	/// How long a member can go without reading before its partitions are
+	/// reassigned to the other members of the group.
+	pub(crate) timeout: Duration,
+	pub(crate) comment: Option<String>,
+}
+
+/// The position up to which a partition of a consumer has been acknowledged.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct ConsumerOffset {
+	/// The versionstamp of the last acknowledged change set.
+	pub(crate) versionstamp: u128,
+}
+
+/// A member of a consumer group, and the partitions it last read from.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct ConsumerMember {
+	/// The time at which the member last read changes.
+	pub(crate) last_seen: Datetime,
+	/// The partitions which were assigned to the member when it last read.
+	pub(crate) partitions: Vec<u16>,
+}
+
+/// Changes whenever a member joins or leaves a consumer group, so that
+/// concurrent changes to the membership conflict with each other.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct ConsumerGroup {
+	/// The number of membership changes of the group.
+	pub(crate) generation: u64,
+}
+
+impl_kv_value_revisioned!(ConsumerDefinition);
+impl_kv_value_revisioned!(ConsumerGroup);
+impl_kv_value_revisioned!(ConsumerOffset);
+impl_kv_value_revisioned!(ConsumerMember);
+
+impl ConsumerDefinition {
+	pub(crate) const DEFAULT_PARTITIONS: u16 = 1;
+	pub(crate) const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+	pub(crate) fn to_sql_definition(&self) -> sql::DefineConsumerStatement {
+		sql::DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			target_table: sql::Expr::Table(self.target_table.clone()),
+			partitions: self.partitions,
+			timeout: sql::Expr::Literal(sql::Literal::Duration(PublicDuration::from_std(
+				self.timeout,
+			))),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v.into())))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+}
+
+impl InfoStructure for ConsumerDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name" => self.name.clone().into(),
+			"what" => self.target_table.clone().into(),
+			"partitions" => self.partitions.into(),
+			"timeout" => Value::Duration(self.timeout.into()),
+			"comment", if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for ConsumerDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
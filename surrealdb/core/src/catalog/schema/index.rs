@@ -1,5 +1,6 @@
 use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
 
 use anyhow::Result;
 use revision::{
@@ -12,6 +13,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use crate::err::Error;
 use crate::expr::statements::info::InfoStructure;
 use crate::expr::{Cond, Idiom};
+use crate::fnc::util::math::vector::Covariance;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::sql::statements::define::DefineKind;
@@ -71,7 +73,7 @@ impl From<u32> for IndexId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 #[non_exhaustive]
 pub struct IndexDefinition {
@@ -85,6 +87,10 @@ pub struct IndexDefinition {
 	/// Indexes marked for removal are excluded from query planning and document
 	/// indexing, and any in-progress index builds are cancelled.
 	pub(crate) prepare_remove: bool,
+	/// The predicate of a partial index. Only the records matching this
+	/// condition are indexed.
+	#[revision(start = 2)]
+	pub(crate) cond: Option<Cond>,
 }
 
 impl_kv_value_revisioned!(IndexDefinition);
@@ -102,10 +108,21 @@ impl IndexDefinition {
 				.clone()
 				.map(|x| sql::Expr::Literal(sql::Literal::String(x.into())))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			cond: self.cond.clone().map(Into::into),
 			concurrently: false,
 		}
 	}
 
+	/// Whether this index can serve a query with the condition `cond`. A
+	/// partial index only holds the records matching its predicate, so it can
+	/// only serve a query whose condition implies that predicate.
+	pub(crate) fn serves(&self, cond: Option<&Cond>) -> bool {
+		match &self.cond {
+			Some(predicate) => cond.is_some_and(|cond| cond.implies(predicate)),
+			None => true,
+		}
+	}
+
 	/// Checks if this index has been marked for removal and returns an error if so.
 	///
 	/// This method is used during index building to detect when an index has been
@@ -133,6 +150,7 @@ impl InfoStructure for IndexDefinition {
 			"table" => Value::String(self.table_name.into()),
 			"cols" => Value::Array(Array(self.cols.into_iter().map(|x| x.structure()).collect())),
 			"index" => self.index.structure(),
+			"cond", if let Some(v) = self.cond => v.0.structure(),
 			"comment", if let Some(v) = self.comment => v.into(),
 			"prepare_remove", if self.prepare_remove => self.prepare_remove.into()
 		})
@@ -145,7 +163,7 @@ impl ToSql for IndexDefinition {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub(crate) enum Index {
 	/// (Basic) non unique
@@ -162,6 +180,9 @@ pub(crate) enum Index {
 	/// DiskANN index for distance-based metrics
 	#[revision(start = 2)]
 	DiskAnn(DiskAnnParams),
+	/// Geohash-cell based spatial index for geometry fields
+	#[revision(start = 3)]
+	Spatial(SpatialParams),
 }
 
 impl Index {
@@ -173,6 +194,7 @@ impl Index {
 			Self::DiskAnn(params) => sql::index::Index::DiskAnn(params.clone().into()),
 			Self::FullText(params) => sql::index::Index::FullText(params.clone().into()),
 			Self::Count(cond) => sql::index::Index::Count(cond.clone().map(Into::into)),
+			Self::Spatial(params) => sql::index::Index::Spatial(params.clone().into()),
 		}
 	}
 
@@ -196,7 +218,7 @@ impl ToSql for Index {
 }
 
 /// Full-Text search parameters.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct FullTextParams {
 	/// The analyzer to use.
@@ -205,6 +227,9 @@ pub struct FullTextParams {
 	pub highlight: bool,
 	/// The scoring to use.
 	pub scoring: Scoring,
+	/// The maximum edit distance of fuzzy term matching, if enabled.
+	#[revision(start = 2)]
+	pub fuzzy: Option<u8>,
 }
 
 /// Scoring for Full-Text search.
@@ -269,8 +294,17 @@ impl Default for Scoring {
 	}
 }
 
+/// Spatial index parameters.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct SpatialParams {
+	/// The finest geohash precision (number of base32 characters) used to
+	/// cover indexed geometries.
+	pub precision: u8,
+}
+
 /// Distance metric for calculating distances between vectors.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash)]
 pub(crate) enum Distance {
 	/// Chebyshev distance.
@@ -312,6 +346,17 @@ pub(crate) enum Distance {
 	/// Inner product similarity, transformed as a distance score.
 	#[revision(start = 2)]
 	InnerProduct,
+	/// Spearman rank correlation.
+	///
+	/// <https://en.wikipedia.org/wiki/Spearman%27s_rank_correlation_coefficient>
+	#[revision(start = 3)]
+	Spearman,
+	/// Mahalanobis distance for the given covariance matrix. Only supported
+	/// by brute-force KNN.
+	///
+	/// <https://en.wikipedia.org/wiki/Mahalanobis_distance>
+	#[revision(start = 3)]
+	Mahalanobis(Vec<Vec<Number>>),
 }
 
 impl Distance {
@@ -319,8 +364,8 @@ impl Distance {
 		use crate::fnc::util::math::ToFloat;
 		use crate::fnc::util::math::vector::{
 			ChebyshevDistance, CosineDistance, EuclideanDistance, HammingDistance,
-			JaccardSimilarity, ManhattanDistance, MinkowskiDistance, PearsonSimilarity,
-			check_same_dimension,
+			JaccardSimilarity, MahalanobisDistance, ManhattanDistance, MinkowskiDistance,
+			PearsonSimilarity, SpearmanSimilarity, check_same_dimension,
 		};
 		match self {
 			Self::Cosine => v1.cosine_distance(v2),
@@ -349,6 +394,53 @@ impl Distance {
 			Self::Manhattan => v1.manhattan_distance(v2),
 			Self::Minkowski(r) => v1.minkowski_distance(v2, r),
 			Self::Pearson => v1.pearson_similarity(v2),
+			Self::Spearman => v1.spearman_similarity(v2),
+			Self::Mahalanobis(c) => v1.mahalanobis_distance(v2, c),
+		}
+	}
+}
+
+impl Distance {
+	/// Turns a value computed with this metric into a score which is higher
+	/// for more similar vectors, preserving the order of the values.
+	pub(crate) fn similarity(&self, value: f64) -> f64 {
+		match self {
+			// These metrics compute a similarity rather than a distance
+			Self::Jaccard | Self::Pearson | Self::Spearman => value,
+			_ => -value,
+		}
+	}
+
+	/// Prepares the distance to be computed against many vectors, like the
+	/// candidates of a brute-force KNN search. The covariance matrix of a
+	/// Mahalanobis distance is factorised once, failing if it is not valid.
+	pub(crate) fn prepare(&self) -> Result<PreparedDistance> {
+		Ok(match self {
+			Self::Mahalanobis(c) => PreparedDistance::Mahalanobis(Arc::new(Covariance::new(c)?)),
+			d => PreparedDistance::Distance(d.clone()),
+		})
+	}
+}
+
+/// A [`Distance`] prepared by [`Distance::prepare`].
+#[derive(Clone, Debug)]
+pub(crate) enum PreparedDistance {
+	Distance(Distance),
+	Mahalanobis(Arc<Covariance>),
+}
+
+impl PreparedDistance {
+	pub(crate) fn compute(&self, v1: &Vec<Number>, v2: &Vec<Number>) -> Result<Number> {
+		use crate::fnc::util::math::ToFloat;
+		use crate::fnc::util::math::vector::check_same_dimension;
+		match self {
+			Self::Distance(d) => d.compute(v1, v2),
+			Self::Mahalanobis(c) => {
+				check_same_dimension("vector::distance::mahalanobis", v1, v2)?;
+				let diff: Vec<f64> =
+					v1.iter().zip(v2.iter()).map(|(a, b)| a.to_float() - b.to_float()).collect();
+				Ok(c.mahalanobis(&diff)?.into())
+			}
 		}
 	}
 }
@@ -366,6 +458,8 @@ impl ToSql for Distance {
 			Self::Manhattan => f.push_str("MANHATTAN"),
 			Self::Minkowski(order) => write_sql!(f, fmt, "MINKOWSKI {}", order),
 			Self::Pearson => f.push_str("PEARSON"),
+			Self::Spearman => f.push_str("SPEARMAN"),
+			Self::Mahalanobis(_) => sql::index::Distance::from(self.clone()).fmt_sql(f, fmt),
 		}
 	}
 }
@@ -412,7 +506,7 @@ impl Display for VectorType {
 }
 
 /// HNSW index parameters.
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct HnswParams {
 	/// The dimension of the index.
@@ -436,10 +530,13 @@ pub(crate) struct HnswParams {
 	/// Whether to use vector hash for vector retrieval.
 	#[revision(start = 2)]
 	pub use_hashed_vector: bool,
+	/// The quantization of the vectors traversed by the graph, if any.
+	#[revision(start = 3)]
+	pub quantization: Option<VectorQuantization>,
 }
 
 /// DiskANN index parameters.
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub(crate) struct DiskAnnParams {
 	/// The dimension of the index.
@@ -456,6 +553,25 @@ pub(crate) struct DiskAnnParams {
 	pub alpha: Number,
 	/// Whether to use vector hashes for vector retrieval.
 	pub use_hashed_vector: bool,
+	/// The quantization of the vectors traversed by the graph, if any.
+	#[revision(start = 2)]
+	pub quantization: Option<VectorQuantization>,
+}
+
+/// Quantization of the vectors traversed by an ANN graph.
+///
+/// The graph is searched on the compact codes, while the full vectors remain
+/// stored and are used to re-rank the final candidates.
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum VectorQuantization {
+	/// Each dimension is encoded as a signed byte.
+	Scalar,
+	/// Each dimension is encoded as a single bit: its sign.
+	Binary,
+	/// The vector is split into the given number of subspaces, each encoded
+	/// as the nearest of the centroids trained for that subspace.
+	Product(u16),
 }
 
 #[cfg(test)]
@@ -7,6 +7,7 @@ mod api;
 pub(crate) mod base;
 mod bucket;
 mod config;
+mod consumer;
 mod event;
 mod field;
 mod function;
@@ -15,6 +16,7 @@ mod ml;
 mod module;
 mod param;
 mod sequence;
+mod sink;
 mod user;
 use std::fmt::{Display, Formatter};
 
@@ -23,6 +25,7 @@ pub use analyzer::*;
 pub use api::*;
 pub use bucket::*;
 pub use config::*;
+pub use consumer::*;
 pub use event::*;
 pub use field::*;
 pub use function::*;
@@ -31,6 +34,7 @@ pub use ml::*;
 pub use module::*;
 pub(crate) use param::*;
 pub use sequence::*;
+pub use sink::*;
 pub use user::*;
 
 use crate::expr::Expr;
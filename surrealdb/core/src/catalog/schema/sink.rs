@@ -0,0 +1,201 @@
+use std::time::Duration;
+
+use revision::revisioned;
+use surrealdb_strand::Strand;
+use surrealdb_types::{SqlFormat, ToSql};
+use url::Url;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::sql::statements::define::DefineKind;
+use crate::sql::{self};
+use crate::types::PublicDuration;
+use crate::val::{Datetime, TableName, Value};
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct SinkDefinition {
+	pub(crate) name: Strand,
+	pub(crate) target_table: TableName,
+	/// The transport used to deliver the changes.
+	pub(crate) kind: SinkKind,
+	/// The endpoint of the webhook, NATS server, or Kafka bootstrap broker.
+	pub(crate) url: String,
+	/// The NATS subject or Kafka topic the changes are published to.
+	pub(crate) topic: Option<String>,
+	/// Maximum retry count for a failed delivery (0 disables retries).
+	pub(crate) retry: u16,
+	/// The initial delay between retries, doubled after each attempt.
+	pub(crate) backoff: Duration,
+	/// The table which receives changes which could not be delivered.
+	pub(crate) dead_letter: Option<TableName>,
+	pub(crate) comment: Option<String>,
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum SinkKind {
+	/// Changes are sent as a JSON `POST` request to an HTTP endpoint.
+	Webhook,
+	/// Changes are published to a subject on a NATS server.
+	Nats,
+	/// Changes are produced to a topic on a Kafka protocol broker.
+	Kafka,
+}
+
+impl SinkKind {
+	pub(crate) fn as_str(self) -> &'static str {
+		match self {
+			SinkKind::Webhook => "WEBHOOK",
+			SinkKind::Nats => "NATS",
+			SinkKind::Kafka => "KAFKA",
+		}
+	}
+
+	/// Returns true if the sink publishes to a named subject or topic.
+	pub(crate) fn requires_topic(self) -> bool {
+		!matches!(self, SinkKind::Webhook)
+	}
+
+	/// The port used when the sink URL does not specify one.
+	pub(crate) fn default_port(self) -> Option<u16> {
+		match self {
+			SinkKind::Webhook => None,
+			SinkKind::Nats => Some(4222),
+			SinkKind::Kafka => Some(9092),
+		}
+	}
+
+	/// Returns true if the URL scheme can be used with this sink.
+	pub(crate) fn supports_scheme(self, scheme: &str) -> bool {
+		match self {
+			SinkKind::Webhook => matches!(scheme, "http" | "https"),
+			SinkKind::Nats => scheme == "nats",
+			SinkKind::Kafka => scheme == "kafka",
+		}
+	}
+}
+
+/// The position up to which changes have been delivered by a sink.
+#[revisioned(revision = 3)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct SinkCursor {
+	/// The versionstamp of the last delivered change set.
+	pub(crate) versionstamp: u128,
+	/// The number of failed attempts to deliver the next change set.
+	#[revision(start = 2)]
+	pub(crate) failures: u32,
+	/// The earliest time at which the next change set is delivered again,
+	/// after a failed attempt.
+	#[revision(start = 2)]
+	pub(crate) retry_at: Option<Datetime>,
+	/// The time of the first failed attempt to deliver the next change set.
+	#[revision(start = 3)]
+	pub(crate) failing_since: Option<Datetime>,
+}
+
+/// A change set which a sink without a dead letter table failed to deliver
+/// for too long, and which was set aside so that the cursor can advance.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct SinkParked {
+	/// The change set, as it would have been sent to the remote endpoint.
+	pub(crate) payload: Value,
+	/// The error returned by the last delivery attempt.
+	pub(crate) error: String,
+	/// The number of failed delivery attempts.
+	pub(crate) attempts: u32,
+}
+
+impl_kv_value_revisioned!(SinkDefinition);
+impl_kv_value_revisioned!(SinkCursor);
+impl_kv_value_revisioned!(SinkParked);
+
+impl SinkDefinition {
+	pub(crate) const DEFAULT_RETRY: u16 = 3;
+	pub(crate) const DEFAULT_BACKOFF: Duration = Duration::from_secs(1);
+
+	pub(crate) fn to_sql_definition(&self) -> sql::DefineSinkStatement {
+		// Credentials in the URL are never displayed
+		self.sql_definition(self.redacted_url())
+	}
+
+	/// Returns the statement which recreates this sink, including any
+	/// credentials in the URL, for exports.
+	pub(crate) fn to_export_definition(&self) -> sql::DefineSinkStatement {
+		self.sql_definition(self.url.clone())
+	}
+
+	fn sql_definition(&self, url: String) -> sql::DefineSinkStatement {
+		sql::DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
+			target_table: sql::Expr::Table(self.target_table.clone()),
+			sink_kind: self.kind,
+			url: sql::Expr::Literal(sql::Literal::String(url.into())),
+			topic: self
+				.topic
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v.into())))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			retry: self.retry,
+			backoff: sql::Expr::Literal(sql::Literal::Duration(PublicDuration::from_std(
+				self.backoff,
+			))),
+			dead_letter: self
+				.dead_letter
+				.clone()
+				.map(sql::Expr::Table)
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			comment: self
+				.comment
+				.clone()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(v.into())))
+				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+		}
+	}
+
+	/// Returns the sink URL with any username and password replaced, for
+	/// display.
+	pub(crate) fn redacted_url(&self) -> String {
+		let Ok(mut url) = Url::parse(&self.url) else {
+			return self.url.clone();
+		};
+		if url.username().is_empty() && url.password().is_none() {
+			return self.url.clone();
+		}
+		// A username on its own can be a secret too, such as a NATS token
+		let redacted = url.set_username("REDACTED").and_then(|_| match url.password() {
+			Some(_) => url.set_password(Some("REDACTED")),
+			None => Ok(()),
+		});
+		match redacted {
+			Ok(()) => url.to_string(),
+			Err(()) => "REDACTED".to_owned(),
+		}
+	}
+}
+
+impl InfoStructure for SinkDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name" => self.name.clone().into(),
+			"what" => self.target_table.clone().into(),
+			"kind" => self.kind.as_str().to_lowercase().into(),
+			"url" => self.redacted_url().into(),
+			"topic", if let Some(v) = self.topic => v.into(),
+			"retry" => self.retry.into(),
+			"backoff" => Value::Duration(self.backoff.into()),
+			"deadletter", if let Some(v) = self.dead_letter => v.into(),
+			"comment", if let Some(v) = self.comment => v.into(),
+		})
+	}
+}
+
+impl ToSql for SinkDefinition {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		self.to_sql_definition().fmt_sql(f, fmt)
+	}
+}
@@ -6,7 +6,7 @@ use uuid::Uuid;
 
 use crate::catalog::{DatabaseId, NamespaceId, Permissions, ViewDefinition};
 use crate::expr::statements::info::InfoStructure;
-use crate::expr::{ChangeFeed, Kind};
+use crate::expr::{ChangeFeed, Kind, Ttl};
 use crate::fmt::EscapeKwFreeIdent;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
@@ -60,7 +60,7 @@ impl revision::WalkRevisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 2)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -94,6 +94,11 @@ pub struct TableDefinition {
 	/// auto-generated Query/Mutation field that targets this table.
 	#[revision(start = 2)]
 	pub(crate) graphql_deprecated: Option<String>,
+
+	/// The time to live of the records in this table, after which they are
+	/// deleted by the record expiry task.
+	#[revision(start = 3)]
+	pub(crate) ttl: Option<Ttl>,
 }
 
 impl_kv_value_revisioned!(TableDefinition);
@@ -124,6 +129,7 @@ impl TableDefinition {
 			cache_indexes_ts: now,
 			graphql_alias: None,
 			graphql_deprecated: None,
+			ttl: None,
 		}
 	}
 
@@ -136,7 +142,7 @@ impl TableDefinition {
 		matches!(self.table_type, TableType::Relation(_) | TableType::Any)
 	}
 
-	fn to_sql_definition(&self) -> DefineTableStatement {
+	pub(crate) fn to_sql_definition(&self) -> DefineTableStatement {
 		DefineTableStatement {
 			id: Some(self.table_id.0),
 			name: sql::Expr::Table(self.name.clone()),
@@ -145,6 +151,7 @@ impl TableDefinition {
 			view: self.view.clone().map(|v| v.to_sql_definition()),
 			permissions: self.permissions.clone().into(),
 			changefeed: self.changefeed.map(|v| v.into()),
+			ttl: self.ttl.clone().map(|v| v.into()),
 			comment: self
 				.comment
 				.clone()
@@ -173,6 +180,7 @@ impl InfoStructure for TableDefinition {
 			"kind" => self.table_type.structure(),
 			"view", if let Some(v) = self.view => v.structure(),
 			"changefeed", if let Some(v) = self.changefeed => v.structure(),
+			"ttl", if let Some(v) = self.ttl => v.structure(),
 			"permissions" => self.permissions.structure(),
 			"comment", if let Some(v) = self.comment => v.into(),
 			"graphql_alias", if let Some(v) = self.graphql_alias => v.into(),
@@ -66,7 +66,8 @@ use crate::val::{Datetime, TableName, Value};
 	cache_indexes_ts: Uuid::default(),
 	graphql_alias: None,
 	graphql_deprecated: None,
-}, 151)]
+	ttl: None,
+}, 152)]
 #[case::subscription(SubscriptionDefinition {
 	id: Uuid::default(),
 	node: Uuid::default(),
@@ -213,7 +214,8 @@ use crate::val::{Datetime, TableName, Value};
 	index: Index::Idx,
 	comment: Some("comment".to_string()),
 	prepare_remove: false,
-}, 34)]
+	cond: None,
+}, 35)]
 #[case::model(MlModelDefinition {
 	name: "model".into(),
 	hash: "hash".into(),
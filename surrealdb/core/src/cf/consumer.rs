@@ -0,0 +1,327 @@
+//! Durable change feed consumers.
+//!
+//! A consumer ([`ConsumerDefinition`]) reads the change feed of a single table
+//! on behalf of a group of members, and stores the position up to which the
+//! changes have been acknowledged on the server. The changes of the table are
+//! split into a fixed number of partitions by record id, and each partition has
+//! its own [`ConsumerOffset`]. The partitions are spread across the members of
+//! the group which have read within the consumer timeout, so that each member
+//! only receives the changes in its own partitions.
+//!
+//! Offsets only advance when changes are acknowledged. Any changes which were
+//! read but not acknowledged before a member stopped reading are received again
+//! by the member its partitions are reassigned to, giving at-least-once
+//! delivery semantics. The change feed garbage collector never removes changes
+//! which have not yet been acknowledged by every consumer ([`min_offset`]).
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{
+	ConsumerDefinition, ConsumerGroup, ConsumerMember, ConsumerOffset, DatabaseId, NamespaceId,
+	TableDefinition,
+};
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutation, TableMutations};
+use crate::err::Error;
+use crate::key::change;
+use crate::key::table::{cg, cm, co};
+use crate::kvs::{KVKey, KVValue, Transaction};
+use crate::val::{Datetime, RecordId};
+
+/// The maximum number of partitions of a consumer.
+pub(crate) const MAX_PARTITIONS: u16 = 1024;
+
+/// The maximum number of change feed entries scanned in one batch.
+const BATCH_SIZE: u32 = 1000;
+
+/// Returns the partition which the changes of a record belong to.
+pub(crate) fn partition(id: &RecordId, partitions: u16) -> u16 {
+	// A 64-bit FNV-1a hash, which is stable across versions and platforms
+	let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+	for b in id.key.to_sql().bytes() {
+		hash ^= b as u64;
+		hash = hash.wrapping_mul(0x0100_0000_01b3);
+	}
+	(hash % partitions.max(1) as u64) as u16
+}
+
+/// Returns the partition which a table mutation belongs to.
+fn mutation_partition(m: &TableMutation, partitions: u16) -> u16 {
+	match m {
+		TableMutation::Set(id, _)
+		| TableMutation::Del(id)
+		| TableMutation::SetWithDiff(id, _, _)
+		| TableMutation::DelWithOriginal(id, _) => partition(id, partitions),
+		// Changes to the table definition belong to the first partition
+		TableMutation::Def(_) => 0,
+	}
+}
+
+/// Returns the partitions assigned to the member at `index` in a group.
+fn assign(index: usize, members: usize, partitions: u16) -> Vec<u16> {
+	(0..partitions).filter(|p| *p as usize % members == index).collect()
+}
+
+/// Returns true if a member has read within the timeout of the consumer.
+fn is_active(cs: &ConsumerDefinition, member: &ConsumerMember, now: &Datetime) -> bool {
+	match (now.0 - member.last_seen.0).to_std() {
+		Ok(elapsed) => elapsed <= cs.timeout,
+		// The member was seen by a node with a clock ahead of this one
+		Err(_) => true,
+	}
+}
+
+/// Fetch the acknowledged offset of each partition of a consumer.
+pub(crate) async fn offsets(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	cs: &ConsumerDefinition,
+) -> Result<BTreeMap<u16, u128>> {
+	let beg = co::prefix(ns, db, &cs.target_table, &cs.name)?;
+	let end = co::suffix(ns, db, &cs.target_table, &cs.name)?;
+	let mut res = BTreeMap::new();
+	for (k, v) in txn.getr(beg..end, None).await? {
+		let key = co::Co::decode_key(&k)?;
+		let val = ConsumerOffset::kv_decode_value(&v, ())?;
+		res.insert(key.partition, val.versionstamp);
+	}
+	Ok(res)
+}
+
+/// Returns the lowest acknowledged offset of the consumers defined on any of
+/// the given tables, or `None` if none of the tables have a consumer.
+pub(crate) async fn min_offset(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tbs: &[TableDefinition],
+) -> Result<Option<u128>> {
+	let mut min: Option<u128> = None;
+	for tb in tbs {
+		for cs in txn.all_tb_consumers(ns, db, &tb.name, None).await?.iter() {
+			for vs in offsets(txn, ns, db, cs).await?.into_values() {
+				min = Some(min.map_or(vs, |m| m.min(vs)));
+			}
+		}
+	}
+	Ok(min)
+}
+
+/// Record the heartbeat of a member, returning the partitions assigned to it.
+///
+/// The active members of the group are ordered by name, and partition `p` is
+/// assigned to the member at position `p % members`. Members which have not
+/// read within the consumer timeout are removed from the group.
+///
+/// Any change to the membership bumps the generation of the group, which
+/// every join reads first, so that concurrent joins conflict instead of each
+/// computing an assignment which leaves out the other member.
+async fn join(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	cs: &ConsumerDefinition,
+	member: &str,
+) -> Result<Vec<u16>> {
+	let now = Datetime::now();
+	let group_key = cg::new(ns, db, &cs.target_table, &cs.name);
+	let group = txn.get(&group_key, None).await?;
+	let beg = cm::prefix(ns, db, &cs.target_table, &cs.name)?;
+	let end = cm::suffix(ns, db, &cs.target_table, &cs.name)?;
+	let mut members = Vec::new();
+	let mut joined = true;
+	let mut changed = false;
+	for (k, v) in txn.getr(beg..end, None).await? {
+		let key = cm::Cm::decode_key(&k)?;
+		let val = ConsumerMember::kv_decode_value(&v, ())?;
+		if key.member == member {
+			// A member which timed out rejoins the group
+			joined = !is_active(cs, &val, &now);
+			continue;
+		}
+		if is_active(cs, &val, &now) {
+			members.push(key.member.into_owned());
+		} else {
+			txn.del(&cm::new(ns, db, &cs.target_table, &cs.name, &key.member)).await?;
+			changed = true;
+		}
+	}
+	if joined || changed {
+		let generation = match group {
+			Some(group) => group.generation + 1,
+			None => 1,
+		};
+		txn.set(
+			&group_key,
+			&ConsumerGroup {
+				generation,
+			},
+		)
+		.await?;
+	}
+	// The scan returns the members ordered by name
+	let index = members.partition_point(|m| m.as_str() < member);
+	let partitions = assign(index, members.len() + 1, cs.partitions);
+	let key = cm::new(ns, db, &cs.target_table, &cs.name, member);
+	let val = ConsumerMember {
+		last_seen: now,
+		partitions: partitions.clone(),
+	};
+	txn.set(&key, &val).await?;
+	Ok(partitions)
+}
+
+/// Read the unacknowledged changes of a consumer.
+///
+/// When a member is specified, the heartbeat of the member is recorded, and
+/// only the changes in the partitions assigned to it are returned. Otherwise
+/// the changes in every partition are returned. Change sets only contain the
+/// mutations which have not yet been acknowledged in their partition.
+pub(crate) async fn read(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	cs: &ConsumerDefinition,
+	member: Option<&str>,
+	limit: Option<u32>,
+) -> Result<Vec<ChangeSet>> {
+	let ts_impl = txn.timestamp_impl();
+	// Work out which partitions to read from
+	let partitions = match member {
+		Some(member) => join(txn, ns, db, cs, member).await?,
+		None => (0..cs.partitions).collect(),
+	};
+	let offsets = offsets(txn, ns, db, cs).await?;
+	let assigned: BTreeMap<u16, u128> =
+		partitions.into_iter().map(|p| (p, offsets.get(&p).copied().unwrap_or_default())).collect();
+	// Members without any partitions have nothing to read
+	let Some(from) = assigned.values().min().copied() else {
+		return Ok(Vec::new());
+	};
+	// Only changes at or below the safe watermark are final
+	let safe_vs = txn.safe_timestamp().await?.as_versionstamp();
+	// Limit the changefeed results with a default
+	let limit = limit.unwrap_or(100).min(1000) as usize;
+	// Scan the change feed from the lowest offset onwards
+	let beg_ts = ts_impl.create_from_versionstamp(from).unwrap_or_else(|| ts_impl.earliest());
+	let mut buf = [0u8; _];
+	let mut beg = change::prefix_ts(ns, db, beg_ts.encode(&mut buf)).encode_key()?;
+	let end = change::suffix(ns, db).encode_key()?;
+	let mut res = Vec::new();
+	loop {
+		let entries = txn.scan(beg.clone()..end.clone(), BATCH_SIZE, 0, None).await?;
+		let complete = (entries.len() as u32) < BATCH_SIZE;
+		// Continue the next batch after the last entry
+		if let Some((k, _)) = entries.last() {
+			beg.clone_from(k);
+			beg.push(0x00);
+		}
+		for (k, v) in entries {
+			let key = change::Cf::decode_key(&k)?;
+			let vs = ts_impl.decode(key.ts.as_ref())?.as_versionstamp();
+			if vs <= from {
+				continue;
+			}
+			if vs > safe_vs {
+				return Ok(res);
+			}
+			// Check the change is for the table of this consumer
+			if *key.tb != cs.target_table {
+				continue;
+			}
+			// Keep the mutations which are unacknowledged in their partition
+			let TableMutations(tb, muts) = TableMutations::kv_decode_value(&v, ())?;
+			let muts: Vec<_> = muts
+				.into_iter()
+				.filter(|m| {
+					let p = mutation_partition(m, cs.partitions);
+					assigned.get(&p).is_some_and(|offset| vs > *offset)
+				})
+				.collect();
+			if muts.is_empty() {
+				continue;
+			}
+			res.push(ChangeSet(vs, DatabaseMutation(vec![TableMutations(tb, muts)])));
+			if res.len() >= limit {
+				return Ok(res);
+			}
+		}
+		if complete {
+			return Ok(res);
+		}
+	}
+}
+
+/// Acknowledge the changes of a consumer up to and including a versionstamp.
+///
+/// When a member is specified, only the partitions which were assigned to the
+/// member when it last read are acknowledged, and the member must still be
+/// active. Otherwise every partition is acknowledged. Offsets never move
+/// backwards.
+pub(crate) async fn ack(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	cs: &ConsumerDefinition,
+	member: Option<&str>,
+	versionstamp: u128,
+) -> Result<()> {
+	let partitions = match member {
+		Some(member) => {
+			let key = cm::new(ns, db, &cs.target_table, &cs.name, member);
+			match txn.get(&key, None).await? {
+				Some(val) if is_active(cs, &val, &Datetime::now()) => val.partitions,
+				_ => bail!(Error::CsMemberNotActive {
+					name: cs.name.to_string(),
+					member: member.to_owned(),
+				}),
+			}
+		}
+		None => (0..cs.partitions).collect(),
+	};
+	// Changes above the safe watermark can not have been read yet
+	let safe_vs = txn.safe_timestamp().await?.as_versionstamp();
+	let versionstamp = versionstamp.min(safe_vs);
+	for p in partitions {
+		let key = co::new(ns, db, &cs.target_table, &cs.name, p);
+		let acked = match txn.get(&key, None).await? {
+			Some(offset) => offset.versionstamp,
+			None => 0,
+		};
+		if versionstamp > acked {
+			let offset = ConsumerOffset {
+				versionstamp,
+			};
+			txn.set(&key, &offset).await?;
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn partitions_are_stable() {
+		let id = RecordId::new("person".into(), "tobie".to_owned());
+		assert_eq!(partition(&id, 1), 0);
+		assert_eq!(partition(&id, 8), partition(&id.clone(), 8));
+		// The table of the record does not affect its partition
+		let other = RecordId::new("user".into(), "tobie".to_owned());
+		assert_eq!(partition(&id, 8), partition(&other, 8));
+		assert!((0..100).all(|i| partition(&RecordId::new("person".into(), i as i64), 8) < 8));
+	}
+
+	#[test]
+	fn partitions_are_spread_across_members() {
+		assert_eq!(assign(0, 1, 4), vec![0, 1, 2, 3]);
+		assert_eq!(assign(0, 2, 4), vec![0, 2]);
+		assert_eq!(assign(1, 2, 4), vec![1, 3]);
+		assert_eq!(assign(2, 3, 2), Vec::<u16>::new());
+	}
+}
@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::catalog::{DatabaseId, NamespaceId};
+use crate::cf::{consumer, sink};
 use crate::key::change;
 use crate::key::debug::Sprintable;
 use crate::kvs::tasklease::LeaseHandler;
@@ -50,7 +51,23 @@ pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
 
 			let ts = tx.timestamp().await?;
 			// Calculate the changefeed watermark cutoff time
-			let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			let mut watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			// Retain any changes which a consumer has not yet acknowledged, or
+			// which a sink has not yet delivered
+			let min_offset =
+				consumer::min_offset(tx, db.namespace_id, db.database_id, &tbs).await?;
+			let min_cursor = sink::min_cursor(tx, db.namespace_id, db.database_id, &tbs).await?;
+			let retained = match (min_offset, min_cursor) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, b) => a.or(b),
+			};
+			if let Some(vs) = retained
+				&& vs < watermark_ts.as_versionstamp()
+			{
+				watermark_ts = ts_impl
+					.create_from_versionstamp(vs.saturating_add(1))
+					.unwrap_or_else(|| ts_impl.earliest());
+			}
 			// Garbage collect all entries older than the watermark
 			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
 			// Possibly renew the lease
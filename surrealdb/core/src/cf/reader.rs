@@ -44,6 +44,9 @@ pub async fn read(
 				),
 			})?
 		}
+		ShowSince::Consumer {
+			..
+		} => fail!("Consumers are read with cf::consumer::read"),
 	};
 
 	let buf = &mut [0u8; _];
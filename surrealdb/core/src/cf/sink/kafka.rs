@@ -0,0 +1,286 @@
+//! Delivery of change sets to a Kafka topic.
+//!
+//! This implements the minimal subset of the Kafka wire protocol needed to
+//! produce a single record: a `Produce` (v3) request carrying a v2 record
+//! batch. The record is written to partition 0 of the topic, keyed by the name
+//! of the table, and acknowledged by all in-sync replicas (`acks=all`) before
+//! the change set is considered delivered. The URL must point at the leader of
+//! partition 0; TLS and SASL connections are not supported.
+
+use std::net::SocketAddr;
+
+use anyhow::{Result, bail};
+use chrono::Utc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::catalog::SinkDefinition;
+
+/// The API key of a Produce request.
+const PRODUCE_API_KEY: i16 = 0;
+/// The version of the Produce request, the first supporting v2 record batches.
+const PRODUCE_API_VERSION: i16 = 3;
+/// Require acknowledgement from all in-sync replicas.
+const ACKS_ALL: i16 = -1;
+/// The client id sent with each request.
+const CLIENT_ID: &str = "surrealdb";
+/// The partition which change sets are written to.
+const PARTITION: i32 = 0;
+/// The largest response accepted from the broker.
+const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// Produce a change set to the topic of a Kafka sink, connecting to the
+/// first reachable address of the broker.
+pub(super) async fn send(sk: &SinkDefinition, addrs: &[SocketAddr], body: Vec<u8>) -> Result<()> {
+	let topic = match sk.topic.as_deref() {
+		Some(t) if !t.is_empty() && t.len() <= i16::MAX as usize => t,
+		t => bail!("the Kafka topic `{}` is invalid", t.unwrap_or("")),
+	};
+	let timestamp = Utc::now().timestamp_millis();
+	let batch = record_batch(sk.target_table.as_bytes(), &body, timestamp);
+	let request = produce_request(1, topic, &batch, super::DELIVERY_TIMEOUT.as_millis() as i32);
+	super::with_timeout(async {
+		let mut stream = TcpStream::connect(addrs).await?;
+		stream.write_all(&request).await?;
+		stream.flush().await?;
+		// Read the size-prefixed response
+		let size = stream.read_i32().await?;
+		if size < 0 || size as usize > MAX_RESPONSE_SIZE {
+			bail!("the Kafka broker returned an invalid response size: {size}");
+		}
+		let mut response = vec![0u8; size as usize];
+		stream.read_exact(&mut response).await?;
+		check_produce_response(1, &response)
+	})
+	.await
+}
+
+/// Encode a size-prefixed Produce request for a single partition.
+fn produce_request(correlation_id: i32, topic: &str, batch: &[u8], timeout_ms: i32) -> Vec<u8> {
+	let mut req = Vec::with_capacity(batch.len() + topic.len() + 64);
+	// The size of the request, filled in below
+	req.extend_from_slice(&0i32.to_be_bytes());
+	// The request header
+	req.extend_from_slice(&PRODUCE_API_KEY.to_be_bytes());
+	req.extend_from_slice(&PRODUCE_API_VERSION.to_be_bytes());
+	req.extend_from_slice(&correlation_id.to_be_bytes());
+	put_string(&mut req, CLIENT_ID);
+	// The transactional id is null
+	req.extend_from_slice(&(-1i16).to_be_bytes());
+	req.extend_from_slice(&ACKS_ALL.to_be_bytes());
+	req.extend_from_slice(&timeout_ms.to_be_bytes());
+	// A single topic with a single partition
+	req.extend_from_slice(&1i32.to_be_bytes());
+	put_string(&mut req, topic);
+	req.extend_from_slice(&1i32.to_be_bytes());
+	req.extend_from_slice(&PARTITION.to_be_bytes());
+	req.extend_from_slice(&(batch.len() as i32).to_be_bytes());
+	req.extend_from_slice(batch);
+	// Fill in the size of the request
+	let size = (req.len() - 4) as i32;
+	req[..4].copy_from_slice(&size.to_be_bytes());
+	req
+}
+
+/// Encode a v2 record batch containing a single record.
+fn record_batch(key: &[u8], value: &[u8], timestamp: i64) -> Vec<u8> {
+	// The record itself, prefixed by its length
+	let mut record = Vec::with_capacity(key.len() + value.len() + 16);
+	// Attributes
+	record.push(0);
+	// Timestamp delta
+	put_varint(&mut record, 0);
+	// Offset delta
+	put_varint(&mut record, 0);
+	put_varint(&mut record, key.len() as i64);
+	record.extend_from_slice(key);
+	put_varint(&mut record, value.len() as i64);
+	record.extend_from_slice(value);
+	// No headers
+	put_varint(&mut record, 0);
+	// The part of the batch covered by the checksum
+	let mut body = Vec::with_capacity(record.len() + 48);
+	// Attributes: no compression, create time, not transactional
+	body.extend_from_slice(&0i16.to_be_bytes());
+	// Last offset delta
+	body.extend_from_slice(&0i32.to_be_bytes());
+	// First and max timestamp
+	body.extend_from_slice(&timestamp.to_be_bytes());
+	body.extend_from_slice(&timestamp.to_be_bytes());
+	// Producer id, producer epoch, and base sequence: not idempotent
+	body.extend_from_slice(&(-1i64).to_be_bytes());
+	body.extend_from_slice(&(-1i16).to_be_bytes());
+	body.extend_from_slice(&(-1i32).to_be_bytes());
+	// The number of records
+	body.extend_from_slice(&1i32.to_be_bytes());
+	put_varint(&mut body, record.len() as i64);
+	body.extend_from_slice(&record);
+	// The batch header
+	let mut batch = Vec::with_capacity(body.len() + 21);
+	// Base offset
+	batch.extend_from_slice(&0i64.to_be_bytes());
+	// Batch length: everything after this field
+	batch.extend_from_slice(&((body.len() + 9) as i32).to_be_bytes());
+	// Partition leader epoch
+	batch.extend_from_slice(&(-1i32).to_be_bytes());
+	// Magic
+	batch.push(2);
+	batch.extend_from_slice(&crc32c(&body).to_be_bytes());
+	batch.extend_from_slice(&body);
+	batch
+}
+
+/// Check the response to a Produce request for a single partition.
+fn check_produce_response(correlation_id: i32, res: &[u8]) -> Result<()> {
+	let mut rd = Reader(res);
+	if rd.i32()? != correlation_id {
+		bail!("the Kafka broker returned a response for another request");
+	}
+	for _ in 0..rd.i32()? {
+		let topic_len = rd.i16()?;
+		rd.skip(topic_len.max(0) as usize)?;
+		for _ in 0..rd.i32()? {
+			let partition = rd.i32()?;
+			let error_code = rd.i16()?;
+			if error_code != 0 {
+				bail!(
+					"the Kafka broker rejected the record for partition {partition} with error code {error_code}"
+				);
+			}
+			// Base offset and log append time
+			rd.skip(16)?;
+		}
+	}
+	Ok(())
+}
+
+/// A cursor over a Kafka response.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+	fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+		let Some((head, tail)) = self.0.split_first_chunk::<N>() else {
+			bail!("the Kafka broker returned a truncated response");
+		};
+		self.0 = tail;
+		Ok(*head)
+	}
+
+	fn skip(&mut self, n: usize) -> Result<()> {
+		if self.0.len() < n {
+			bail!("the Kafka broker returned a truncated response");
+		}
+		self.0 = &self.0[n..];
+		Ok(())
+	}
+
+	fn i16(&mut self) -> Result<i16> {
+		Ok(i16::from_be_bytes(self.take()?))
+	}
+
+	fn i32(&mut self) -> Result<i32> {
+		Ok(i32::from_be_bytes(self.take()?))
+	}
+}
+
+/// Write a string prefixed by its length as an `i16`.
+fn put_string(buf: &mut Vec<u8>, s: &str) {
+	buf.extend_from_slice(&(s.len() as i16).to_be_bytes());
+	buf.extend_from_slice(s.as_bytes());
+}
+
+/// Write a zigzag-encoded variable length integer.
+fn put_varint(buf: &mut Vec<u8>, v: i64) {
+	let mut v = ((v << 1) ^ (v >> 63)) as u64;
+	while v >= 0x80 {
+		buf.push((v as u8) | 0x80);
+		v >>= 7;
+	}
+	buf.push(v as u8);
+}
+
+/// Compute the CRC-32C (Castagnoli) checksum used by record batches.
+fn crc32c(data: &[u8]) -> u32 {
+	let mut crc = !0u32;
+	for &b in data {
+		crc ^= b as u32;
+		for _ in 0..8 {
+			let mask = (crc & 1).wrapping_neg();
+			crc = (crc >> 1) ^ (0x82F6_3B78 & mask);
+		}
+	}
+	!crc
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn crc32c_check_value() {
+		assert_eq!(crc32c(b""), 0);
+		assert_eq!(crc32c(b"123456789"), 0xE306_9283);
+	}
+
+	#[test]
+	fn varint_zigzag() {
+		let enc = |v| {
+			let mut buf = Vec::new();
+			put_varint(&mut buf, v);
+			buf
+		};
+		assert_eq!(enc(0), [0x00]);
+		assert_eq!(enc(-1), [0x01]);
+		assert_eq!(enc(1), [0x02]);
+		assert_eq!(enc(63), [0x7e]);
+		assert_eq!(enc(64), [0x80, 0x01]);
+		assert_eq!(enc(-65), [0x81, 0x01]);
+	}
+
+	#[test]
+	fn record_batch_layout() {
+		let batch = record_batch(b"person", b"{}", 1_700_000_000_000);
+		// The batch length covers everything after the length field
+		let len = i32::from_be_bytes(batch[8..12].try_into().unwrap());
+		assert_eq!(len as usize, batch.len() - 12);
+		// The magic byte identifies a v2 record batch
+		assert_eq!(batch[16], 2);
+		// The checksum covers everything from the attributes onwards
+		let crc = u32::from_be_bytes(batch[17..21].try_into().unwrap());
+		assert_eq!(crc, crc32c(&batch[21..]));
+		// The record key and value are stored verbatim
+		assert!(batch.ends_with(b"person\x04{}\x00"));
+	}
+
+	#[test]
+	fn produce_request_layout() {
+		let req = produce_request(7, "changes", b"batch", 1000);
+		let size = i32::from_be_bytes(req[..4].try_into().unwrap());
+		assert_eq!(size as usize, req.len() - 4);
+		assert_eq!(&req[4..6], &PRODUCE_API_KEY.to_be_bytes());
+		assert_eq!(&req[6..8], &PRODUCE_API_VERSION.to_be_bytes());
+		assert_eq!(&req[8..12], &7i32.to_be_bytes());
+		assert!(req.ends_with(b"\x00\x00\x00\x05batch"));
+	}
+
+	#[test]
+	fn produce_response_errors() {
+		let response = |error_code: i16| {
+			let mut res = Vec::new();
+			res.extend_from_slice(&1i32.to_be_bytes());
+			res.extend_from_slice(&1i32.to_be_bytes());
+			put_string(&mut res, "changes");
+			res.extend_from_slice(&1i32.to_be_bytes());
+			res.extend_from_slice(&0i32.to_be_bytes());
+			res.extend_from_slice(&error_code.to_be_bytes());
+			res.extend_from_slice(&0i64.to_be_bytes());
+			res.extend_from_slice(&(-1i64).to_be_bytes());
+			res.extend_from_slice(&0i32.to_be_bytes());
+			res
+		};
+		check_produce_response(1, &response(0)).unwrap();
+		check_produce_response(1, &response(3)).unwrap_err();
+		check_produce_response(2, &response(0)).unwrap_err();
+		check_produce_response(1, &response(0)[..10]).unwrap_err();
+	}
+}
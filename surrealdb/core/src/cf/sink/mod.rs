@@ -0,0 +1,471 @@
+//! Delivery of change feed mutations to external sinks.
+//!
+//! A sink ([`SinkDefinition`]) tails the change feed of a single table and
+//! pushes each change set to a webhook, a NATS subject, or a Kafka topic. The
+//! position up to which changes have been delivered is stored durably in a
+//! [`SinkCursor`], which is only advanced once a change set has been
+//! acknowledged by the remote endpoint (or written to the dead letter table).
+//! A crash between delivery and the cursor update therefore causes the change
+//! set to be delivered again, giving at-least-once delivery semantics. The
+//! change feed garbage collector never removes changes which a sink has not
+//! yet delivered ([`min_cursor`]).
+//!
+//! Delivery runs as a lease-guarded background task, so only a single node in
+//! the cluster delivers changes at any one time
+//! ([`Datastore::sink_delivery`]). Several sinks are processed at once, and a
+//! failed delivery is not retried in place: the number of failed attempts and
+//! the time of the next attempt are stored in the cursor, and the change set is
+//! retried by the first pass after that time.
+//!
+//! Once the retries are exhausted, a change set is written to the dead letter
+//! table of the sink. A sink without a dead letter table keeps retrying, until
+//! the change set has been failing for [`PARK_AFTER`]. It is then parked under
+//! the sink ([`crate::key::table::sp`]), so that the cursor, and with it the
+//! change feed garbage collector, can move past it.
+
+#[cfg(not(target_family = "wasm"))]
+mod kafka;
+#[cfg(not(target_family = "wasm"))]
+mod nats;
+#[cfg(feature = "http")]
+mod webhook;
+
+#[cfg(not(target_family = "wasm"))]
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use chrono::{TimeDelta, Utc};
+use futures::StreamExt;
+use url::Url;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{
+	DatabaseDefinition, DatabaseId, NamespaceDefinition, NamespaceId, SinkCursor, SinkDefinition,
+	SinkKind, SinkParked, TableDefinition,
+};
+use crate::cf::{ChangeSet, DatabaseMutation, TableMutations};
+use crate::dbs::Session;
+use crate::dbs::capabilities::NetTarget;
+use crate::err::Error;
+use crate::key::change;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, KVKey, KVValue, Transaction};
+use crate::types::PublicVariables;
+use crate::val::{Array, Datetime, Object, RecordId, Value};
+
+/// The maximum number of change feed entries read for a sink in one pass.
+const BATCH_SIZE: u32 = 1000;
+
+/// The maximum time allowed for a single delivery attempt.
+#[cfg(not(target_family = "wasm"))]
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The upper bound for the delay between two delivery attempts.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// How long a change set is retried by a sink without a dead letter table,
+/// before it is parked.
+const PARK_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The maximum number of sinks which deliver changes at the same time.
+const MAX_CONCURRENT_SINKS: usize = 8;
+
+/// A change set which is ready to be delivered to a sink.
+struct Delivery {
+	/// The versionstamp of the change set
+	versionstamp: u128,
+	/// The change set, as sent to the remote endpoint
+	payload: Value,
+}
+
+/// Run one delivery pass over every sink defined in the datastore.
+///
+/// Each sink is processed independently, and concurrently with the others: an
+/// unreachable endpoint only holds back the sink which delivers to it. Errors
+/// are logged, and the sink resumes from its durable cursor on the next pass.
+pub(crate) async fn process(ds: &Datastore, lh: &LeaseHandler) -> Result<()> {
+	// Collect all sinks in a short-lived read transaction
+	let txn = ds.transaction(Read, Optimistic).await?;
+	let mut sinks = Vec::new();
+	for ns in txn.all_ns(None).await?.iter() {
+		for db in txn.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in txn.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				let defs =
+					txn.all_tb_sinks(db.namespace_id, db.database_id, &tb.name, None).await?;
+				for sk in defs.iter() {
+					sinks.push((ns.clone(), db.clone(), sk.clone()));
+				}
+			}
+		}
+	}
+	txn.cancel().await?;
+	// Deliver the pending changes for each sink
+	let mut tasks = futures::stream::iter(sinks)
+		.map(|(ns, db, sk)| async move {
+			if let Err(e) = process_sink(ds, &ns, &db, &sk).await {
+				warn!(
+					"Failed to deliver changes to sink '{}' on {}:{}:{}: {e}",
+					sk.name, ns.name, db.name, sk.target_table
+				);
+			}
+		})
+		.buffer_unordered(MAX_CONCURRENT_SINKS);
+	while tasks.next().await.is_some() {
+		// Possibly renew the lease
+		lh.try_maintain_lease().await?;
+		// Yield execution
+		yield_now!();
+	}
+	Ok(())
+}
+
+/// Returns the lowest cursor of the sinks defined on any of the given tables,
+/// or `None` if none of the tables have a sink.
+pub(crate) async fn min_cursor(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tbs: &[TableDefinition],
+) -> Result<Option<u128>> {
+	let mut min: Option<u128> = None;
+	for tb in tbs {
+		for sk in txn.all_tb_sinks(ns, db, &tb.name, None).await?.iter() {
+			let key = crate::key::table::sc::new(ns, db, &tb.name, &sk.name);
+			if let Some(cursor) = txn.get(&key, None).await? {
+				let vs = cursor.versionstamp;
+				min = Some(min.map_or(vs, |m| m.min(vs)));
+			}
+		}
+	}
+	Ok(min)
+}
+
+/// Deliver the pending change sets of a single sink, and advance its cursor.
+async fn process_sink(
+	ds: &Datastore,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+) -> Result<()> {
+	let (nsid, dbid) = (db.namespace_id, db.database_id);
+	let cursor_key = crate::key::table::sc::new(nsid, dbid, &sk.target_table, &sk.name);
+	// Read the pending changes in a short-lived read transaction
+	let txn = ds.transaction(Read, Optimistic).await?;
+	let ts_impl = txn.timestamp_impl();
+	// The cursor is created with the sink, and removed along with it
+	let Some(state) = catch!(txn, txn.get(&cursor_key, None).await) else {
+		txn.cancel().await?;
+		return Ok(());
+	};
+	// Wait for the next attempt of a failed delivery to be due
+	if let Some(retry_at) = &state.retry_at
+		&& *retry_at > Datetime::now()
+	{
+		txn.cancel().await?;
+		return Ok(());
+	}
+	let cursor = state.versionstamp;
+	// Only changes at or below the safe watermark are final
+	let safe_vs = txn.safe_timestamp().await?.as_versionstamp();
+	if safe_vs <= cursor {
+		txn.cancel().await?;
+		return Ok(());
+	}
+	// Scan the change feed from the cursor onwards. Entries at exactly the
+	// cursor were already delivered, and are skipped below.
+	let beg_ts = ts_impl.create_from_versionstamp(cursor).unwrap_or_else(|| ts_impl.earliest());
+	let mut buf = [0u8; _];
+	let beg = change::prefix_ts(nsid, dbid, beg_ts.encode(&mut buf)).encode_key()?;
+	let end = change::suffix(nsid, dbid).encode_key()?;
+	let entries = txn.scan(beg..end, BATCH_SIZE, 0, None).await?;
+	// Check whether the scan reached the end of the safe range
+	let mut complete = (entries.len() as u32) < BATCH_SIZE;
+	let mut last_seen = cursor;
+	let mut pending = Vec::new();
+	for (k, v) in entries {
+		let key = change::Cf::decode_key(&k)?;
+		let vs = ts_impl.decode(key.ts.as_ref())?.as_versionstamp();
+		if vs <= cursor {
+			continue;
+		}
+		if vs > safe_vs {
+			complete = true;
+			break;
+		}
+		last_seen = vs;
+		// Check the change is for the table of this sink
+		if *key.tb != sk.target_table {
+			continue;
+		}
+		let muts = TableMutations::kv_decode_value(&v, ())?;
+		pending.push(Delivery {
+			versionstamp: vs,
+			payload: payload(ns, db, sk, ChangeSet(vs, DatabaseMutation(vec![muts])))?,
+		});
+	}
+	txn.cancel().await?;
+	// The position which can be reached once all pending changes are delivered.
+	// When the scan stopped early, later entries may share the last versionstamp.
+	let floor = if complete {
+		safe_vs
+	} else {
+		last_seen.saturating_sub(1)
+	};
+	// Deliver each change set in versionstamp order
+	let mut next = SinkCursor {
+		retry_at: None,
+		..state.clone()
+	};
+	let mut failed = false;
+	for delivery in pending {
+		if let Err(err) = deliver(ds, sk, &delivery.payload).await {
+			next.failures += 1;
+			let since = next.failing_since.get_or_insert_with(Datetime::now).clone();
+			// Once the retries are exhausted the change set is dead-lettered, or
+			// parked once it has been failing for too long
+			let handled = next.failures > u32::from(sk.retry)
+				&& (dead_letter(ds, ns, db, sk, &delivery, &err, next.failures).await?
+					|| park(ds, db, sk, &delivery, &err, next.failures, &since).await?);
+			if !handled {
+				let backoff = backoff(sk, next.failures);
+				debug!(
+					"Delivery of versionstamp {} to sink '{}' failed (attempt {}), retrying in {backoff:?}: {err}",
+					delivery.versionstamp, sk.name, next.failures
+				);
+				next.retry_at = Some(Datetime(Utc::now() + TimeDelta::from_std(backoff)?));
+				failed = true;
+				break;
+			}
+		}
+		next.versionstamp = delivery.versionstamp;
+		next.failures = 0;
+		next.failing_since = None;
+	}
+	// Advance the cursor past everything which has been handled
+	if !failed {
+		next.versionstamp = next.versionstamp.max(floor);
+	}
+	if next != state {
+		let txn = ds.transaction(Write, Optimistic).await?;
+		// Don't recreate the cursor of a sink removed in the meantime
+		let sink_key = crate::key::table::sk::new(nsid, dbid, &sk.target_table, &sk.name);
+		if catch!(txn, txn.exists(&sink_key, None).await) {
+			catch!(txn, txn.set(&cursor_key, &next).await);
+		}
+		catch!(txn, txn.commit().await);
+	}
+	Ok(())
+}
+
+/// Returns the delay before the next attempt to deliver a change set, which
+/// doubles with every failed attempt.
+fn backoff(sk: &SinkDefinition, failures: u32) -> Duration {
+	let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+	sk.backoff.saturating_mul(factor).min(MAX_BACKOFF)
+}
+
+/// Build the document which is sent to the remote endpoint for a change set.
+fn payload(
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+	cs: ChangeSet,
+) -> Result<Value> {
+	let Value::Object(mut obj) = cs.into_value()? else {
+		bail!(Error::unreachable("change sets are converted into objects"));
+	};
+	obj.insert("sink", Value::from(sk.name.as_str()));
+	obj.insert("ns", Value::from(ns.name.as_str()));
+	obj.insert("db", Value::from(db.name.as_str()));
+	obj.insert("table", Value::from(sk.target_table.as_str()));
+	Ok(Value::Object(obj))
+}
+
+/// Write a change set which could not be delivered to the dead letter table.
+///
+/// Returns `true` once the change set has been handled by writing it to the
+/// dead letter table. Returns `false` if the sink has no dead letter table, or
+/// if the write failed, in which case the delivery is retried later.
+async fn dead_letter(
+	ds: &Datastore,
+	ns: &NamespaceDefinition,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+	delivery: &Delivery,
+	err: &anyhow::Error,
+	attempts: u32,
+) -> Result<bool> {
+	warn!(
+		"Delivery of versionstamp {} to sink '{}' failed after {attempts} attempts: {err}",
+		delivery.versionstamp, sk.name
+	);
+	// Without a dead letter table the change set is retried, or parked
+	let Some(dead_letter) = &sk.dead_letter else {
+		return Ok(false);
+	};
+	// Record the failed change set in the dead letter table
+	let id = RecordId::new(
+		dead_letter.clone(),
+		Array(vec![Value::from(sk.name.as_str()), Value::try_from(delivery.versionstamp)?]),
+	);
+	let mut content = Object::default();
+	content.insert("sink", Value::from(sk.name.as_str()));
+	content.insert("table", Value::from(sk.target_table.as_str()));
+	content.insert("versionstamp", Value::try_from(delivery.versionstamp)?);
+	content.insert("payload", delivery.payload.clone());
+	content.insert("error", Value::from(err.to_string()));
+	content.insert("attempts", Value::from(attempts as i64));
+	let mut vars = PublicVariables::new();
+	vars.insert("id", crate::val::convert_value_to_public_value(Value::RecordId(id))?);
+	vars.insert("content", crate::val::convert_value_to_public_value(Value::Object(content))?);
+	let sess = Session::owner().with_ns(ns.name.as_str()).with_db(db.name.as_str());
+	let res = ds
+		.execute("UPSERT $id CONTENT $content RETURN NONE", &sess, Some(vars))
+		.await
+		.map_err(anyhow::Error::new)?;
+	for r in res {
+		if let Err(e) = r.result {
+			warn!("Failed to write to the dead letter table of sink '{}': {e}", sk.name);
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
+/// Park a change set which has been failing for longer than [`PARK_AFTER`].
+///
+/// Returns `true` once the change set has been handled by parking it under the
+/// sink. Returns `false` if the change set has not been failing for long
+/// enough, in which case the delivery is retried later.
+async fn park(
+	ds: &Datastore,
+	db: &DatabaseDefinition,
+	sk: &SinkDefinition,
+	delivery: &Delivery,
+	err: &anyhow::Error,
+	attempts: u32,
+	since: &Datetime,
+) -> Result<bool> {
+	if Utc::now() - since.0 < TimeDelta::from_std(PARK_AFTER)? {
+		return Ok(false);
+	}
+	let (nsid, dbid) = (db.namespace_id, db.database_id);
+	let txn = ds.transaction(Write, Optimistic).await?;
+	// Don't park changes for a sink removed in the meantime
+	let sink_key = crate::key::table::sk::new(nsid, dbid, &sk.target_table, &sk.name);
+	if catch!(txn, txn.exists(&sink_key, None).await) {
+		let key = crate::key::table::sp::new(
+			nsid,
+			dbid,
+			&sk.target_table,
+			&sk.name,
+			delivery.versionstamp,
+		);
+		let parked = SinkParked {
+			payload: delivery.payload.clone(),
+			error: err.to_string(),
+			attempts,
+		};
+		catch!(txn, txn.set(&key, &parked).await);
+	}
+	catch!(txn, txn.commit().await);
+	warn!(
+		"Parked versionstamp {} of sink '{}', which has been failing since {since}",
+		delivery.versionstamp, sk.name
+	);
+	Ok(true)
+}
+
+/// Deliver a change set to the endpoint of a sink, once.
+async fn deliver(ds: &Datastore, sk: &SinkDefinition, payload: &Value) -> Result<()> {
+	let url = Url::parse(&sk.url).map_err(|_| Error::InvalidUrl(sk.redacted_url()))?;
+	check_allowed_net(ds, sk, &url)?;
+	let json = crate::val::convert_value_to_public_value(payload.clone())?.into_json_value();
+	let body = serde_json::to_vec(&json)?;
+	let res = match sk.kind {
+		#[cfg(feature = "http")]
+		SinkKind::Webhook => webhook::send(ds, &url, body).await,
+		#[cfg(not(target_family = "wasm"))]
+		SinkKind::Nats => {
+			let addrs = resolve_allowed(ds, sk, &url).await?;
+			nats::send(sk, &addrs, &url, body).await
+		}
+		#[cfg(not(target_family = "wasm"))]
+		SinkKind::Kafka => {
+			let addrs = resolve_allowed(ds, sk, &url).await?;
+			kafka::send(sk, &addrs, body).await
+		}
+		#[allow(unreachable_patterns)]
+		kind => Err(anyhow::anyhow!("{} sinks are not supported on this platform", kind.as_str())),
+	};
+	res.map_err(|e| {
+		Error::SinkDelivery {
+			name: sk.name.to_string(),
+			message: e.to_string(),
+		}
+		.into()
+	})
+}
+
+/// Check that the endpoint of a sink is allowed by the network capabilities.
+///
+/// The capabilities are checked again on every delivery, as they may have
+/// changed since the sink was defined. The addresses which the host resolves
+/// to are checked when connecting: by the HTTP client of the datastore for
+/// webhooks, and by [`resolve_allowed`] for other sinks.
+fn check_allowed_net(ds: &Datastore, sk: &SinkDefinition, url: &Url) -> Result<()> {
+	let Some(host) = url.host() else {
+		bail!(Error::InvalidUrl(sk.redacted_url()));
+	};
+	let target =
+		NetTarget::Host(host.to_owned(), url.port_or_known_default().or(sk.kind.default_port()));
+	if !ds.get_capabilities().allows_network_target(&target) {
+		bail!(Error::NetTargetNotAllowed(target.to_string()));
+	}
+	Ok(())
+}
+
+/// Resolve the endpoint of a sink which is reached over a plain TCP
+/// connection, checking every address against the deny list.
+///
+/// The connection is made to the returned addresses rather than to the host,
+/// so that the host can not resolve to a denied address after the check.
+#[cfg(not(target_family = "wasm"))]
+async fn resolve_allowed(
+	ds: &Datastore,
+	sk: &SinkDefinition,
+	url: &Url,
+) -> Result<Vec<SocketAddr>> {
+	let Some(port) = url.port().or(sk.kind.default_port()) else {
+		bail!(Error::InvalidUrl(sk.redacted_url()));
+	};
+	let addrs: Vec<SocketAddr> = match url.host() {
+		Some(url::Host::Domain(host)) => tokio::net::lookup_host((host, port)).await?.collect(),
+		Some(url::Host::Ipv4(ip)) => vec![SocketAddr::new(ip.into(), port)],
+		Some(url::Host::Ipv6(ip)) => vec![SocketAddr::new(ip.into(), port)],
+		None => bail!(Error::InvalidUrl(sk.redacted_url())),
+	};
+	let caps = ds.get_capabilities();
+	for addr in &addrs {
+		let target = NetTarget::IPNet(addr.ip().into());
+		if caps.matches_any_deny_net(&target) {
+			bail!(Error::NetTargetNotAllowed(target.to_string()));
+		}
+	}
+	if addrs.is_empty() {
+		bail!("the host `{}` did not resolve to any address", url.host_str().unwrap_or(""));
+	}
+	Ok(addrs)
+}
+
+/// Run a future, failing if it does not complete within the delivery timeout.
+#[cfg(not(target_family = "wasm"))]
+async fn with_timeout<T>(fut: impl Future<Output = Result<T>>) -> Result<T> {
+	match tokio::time::timeout(DELIVERY_TIMEOUT, fut).await {
+		Ok(res) => res,
+		Err(_) => bail!("the delivery timed out after {DELIVERY_TIMEOUT:?}"),
+	}
+}
@@ -0,0 +1,32 @@
+//! Delivery of change sets to an HTTP endpoint.
+
+use anyhow::{Result, bail};
+use reqwest::Method;
+use reqwest::header::CONTENT_TYPE;
+use url::Url;
+
+use crate::err::Error;
+use crate::kvs::Datastore;
+
+/// Send a change set as a JSON `POST` request.
+///
+/// Any response with a success status code acknowledges the change set.
+pub(super) async fn send(ds: &Datastore, url: &Url, body: Vec<u8>) -> Result<()> {
+	let cli = ds.http_client();
+	let req =
+		cli.request(Method::POST, url.clone()).header(CONTENT_TYPE, "application/json").body(body);
+	#[cfg(not(target_family = "wasm"))]
+	let req = req.timeout(super::DELIVERY_TIMEOUT);
+	let res = req.send().await.map_err(Error::from)?;
+	if let Err(err) = res.error_for_status() {
+		match err.status() {
+			Some(s) => bail!(Error::Http(format!(
+				"{} {}",
+				s.as_u16(),
+				s.canonical_reason().unwrap_or_default(),
+			))),
+			None => bail!(Error::Http(err.to_string())),
+		}
+	}
+	Ok(())
+}
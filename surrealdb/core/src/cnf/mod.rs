@@ -608,6 +608,51 @@ pub static DISKANN_FILTER_PREFETCH_MAX_CHUNK: LazyLock<usize> = LazyLock::new(||
 		.unwrap_or(4096)
 });
 
+/// Number of vectors sampled to train the codebook of an ANN index using
+/// product quantization (`QUANTIZATION PQ`). The codebook is trained once the
+/// index holds that many vectors; until then the graph is traversed with the
+/// full vectors. Read once at first use.
+pub static VECTOR_PQ_TRAINING_SIZE: LazyLock<usize> = LazyLock::new(|| {
+	std::env::var("SURREAL_VECTOR_PQ_TRAINING_SIZE")
+		.ok()
+		.and_then(|s| s.parse::<usize>().ok())
+		.filter(|n| *n > 0)
+		.unwrap_or(1024)
+});
+
+/// Maximum number of records matching the non-vector condition of a filtered
+/// KNN query for which an exact brute-force search over those records is
+/// preferred to a graph traversal. Only applies when the condition can be
+/// served by another index. Read once at first use.
+pub static KNN_FILTER_BRUTE_FORCE_LIMIT: LazyLock<usize> = LazyLock::new(|| {
+	std::env::var("SURREAL_KNN_FILTER_BRUTE_FORCE_LIMIT")
+		.ok()
+		.and_then(|s| s.parse::<usize>().ok())
+		.unwrap_or(1000)
+});
+
+/// Maximum number of records matching the non-vector condition of a filtered
+/// KNN query counted to estimate the selectivity of that condition. Read once
+/// at first use.
+pub static KNN_FILTER_SELECTIVITY_SAMPLE: LazyLock<usize> = LazyLock::new(|| {
+	std::env::var("SURREAL_KNN_FILTER_SELECTIVITY_SAMPLE")
+		.ok()
+		.and_then(|s| s.parse::<usize>().ok())
+		.filter(|n| *n > 0)
+		.unwrap_or(10_000)
+});
+
+/// Upper bound of the search list size (`ef`) a filtered KNN query expands to
+/// in order to compensate for the candidates rejected by a selective
+/// condition. Read once at first use.
+pub static KNN_FILTER_MAX_EF: LazyLock<usize> = LazyLock::new(|| {
+	std::env::var("SURREAL_KNN_FILTER_MAX_EF")
+		.ok()
+		.and_then(|s| s.parse::<usize>().ok())
+		.filter(|n| *n > 0)
+		.unwrap_or(4096)
+});
+
 // Used in a lot of surrealql functions which randomly access this limit as well as casting
 // functions Both of which cannot be changed without massive restructuring.
 
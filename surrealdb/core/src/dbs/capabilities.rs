@@ -986,7 +986,7 @@ impl Capabilities {
 		self.allow_net.matches(target)
 	}
 
-	#[cfg(feature = "http")]
+	#[cfg(any(feature = "http", not(target_family = "wasm")))]
 	pub(crate) fn matches_any_deny_net(&self, target: &NetTarget) -> bool {
 		self.deny_net.matches(target)
 	}
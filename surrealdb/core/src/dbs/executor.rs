@@ -919,6 +919,14 @@ impl Executor {
 				ctx_mut!().set_transaction(txn);
 				s.compute(&self.ctx, &self.opt, None).await.map_err(ControlFlow::Err)
 			}
+			TopLevelExpr::Ack(s) => {
+				ctx_mut!().set_transaction(txn);
+				self.stack
+					.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None))
+					.finish()
+					.await
+					.map_err(ControlFlow::Err)
+			}
 			TopLevelExpr::Access(s) => {
 				ctx_mut!().set_transaction(txn);
 				self.stack.enter(|stk| s.compute(stk, &self.ctx, &self.opt, None)).finish().await
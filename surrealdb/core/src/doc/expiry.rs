@@ -0,0 +1,54 @@
+use anyhow::Result;
+
+use crate::ctx::FrozenContext;
+use crate::doc::Document;
+use crate::key::table::te;
+use crate::val::Value;
+
+impl Document {
+	/// Keeps the expiry entry of this record in step with its TTL field.
+	///
+	/// Tables with a TTL hold an entry for every record whose TTL field is a
+	/// datetime, ordered by that datetime, so that the expiry task only reads
+	/// the records which are due. When the field changes, the entry for the
+	/// previous datetime is removed, and one for the new datetime is written.
+	pub(super) async fn store_expiry_data(&self, ctx: &FrozenContext) -> Result<()> {
+		// Get the document table
+		let tb = self.doc_ctx.tb()?;
+		// Check if the table has a TTL
+		let Some(ttl) = &tb.ttl else {
+			return Ok(());
+		};
+		// Get the datetimes before and after the change
+		let before = match self.initial.doc.as_ref().pick(&ttl.field) {
+			Value::Datetime(v) => Some(v),
+			_ => None,
+		};
+		let after = match self.current.doc.as_ref().pick(&ttl.field) {
+			Value::Datetime(v) => Some(v),
+			_ => None,
+		};
+		// Check if the field changed
+		if before == after {
+			return Ok(());
+		}
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the record id
+		let rid = self.id()?;
+		// Get the namespace id
+		let ns = self.doc_ctx.ns().namespace_id;
+		// Get the database id
+		let db = self.doc_ctx.db().database_id;
+		// Remove the previous entry
+		if let Some(dt) = before {
+			txn.del(&te::new(ns, db, &rid.table, &dt, &rid.key)).await?;
+		}
+		// Store the new entry
+		if let Some(dt) = after {
+			txn.set(&te::new(ns, db, &rid.table, &dt, &rid.key), &()).await?;
+		}
+		// Carry on
+		Ok(())
+	}
+}
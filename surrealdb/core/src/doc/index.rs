@@ -154,6 +154,8 @@ impl Document {
 	/// Eg. If the index is composed of the columns `name` and `instrument`
 	/// Given this doc: { "id": 1, "instrument": "piano", "name": "Tobie" }
 	/// It will return: ["Tobie", "piano"]
+	/// A document not matching the predicate of a partial index has no values,
+	/// as if it did not exist.
 	pub(crate) async fn build_opt_values(
 		stk: &mut Stk,
 		ctx: &FrozenContext,
@@ -164,6 +166,16 @@ impl Document {
 		if doc.doc.as_ref().is_nullish() {
 			return Ok(None);
 		}
+		if let Some(cond) = &ix.cond {
+			let matches = stk
+				.run(|stk| cond.0.compute(stk, ctx, opt, Some(doc)))
+				.await
+				.catch_return()?
+				.is_truthy();
+			if !matches {
+				return Ok(None);
+			}
+		}
 		let mut o = Vec::with_capacity(ix.cols.len());
 		for i in ix.cols.iter() {
 			let v = i.compute(stk, ctx, opt, Some(doc)).await.catch_return()?;
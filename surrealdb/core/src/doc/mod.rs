@@ -25,6 +25,7 @@ mod check; // Data and condition checking for this document
 pub(crate) mod compute; // Compute computed fields for this document
 mod edges; // Attempts to store the edge data for this document
 mod event; // Processes any table events relevant for this document
+mod expiry; // Maintains the expiry entry of this document for tables with a TTL
 mod field; // Processes any schema-defined fields for this document
 mod index; // Attempts to store the index data for this document
 mod live_events; // Captures live-query events for this document (Router engine)
@@ -57,6 +57,8 @@ impl Document {
 			let db = self.doc_ctx.db().database_id;
 			// Purge the record data
 			txn.del_record(ns, db, &rid.table, &rid.key).await?;
+			// Purge the expiry entry of the record
+			self.store_expiry_data(ctx).await?;
 			// Mark this row as having mutated the KV store so the
 			// iterator bumps the per-statement affected-row counter.
 			self.mutated = true;
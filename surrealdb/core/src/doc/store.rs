@@ -137,6 +137,8 @@ impl Document {
 			// Let's update the stored value for the specified key
 			_ => ctx.tx().set_record(ns, db, &rid.table, &rid.key, doc).await,
 		}?;
+		// Store the expiry entry of the record
+		self.store_expiry_data(ctx).await?;
 		// KV write succeeded; mark the document as mutated so the
 		// per-statement affected-row counter (bumped from
 		// `Document::process`) reflects this row.
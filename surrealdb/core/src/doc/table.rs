@@ -660,6 +660,7 @@ impl Document {
 				what: vec![Expr::Table(table_name.clone())],
 				// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
 				cond: condition.map(Cond),
+				fusion: None,
 				// GROUP ALL
 				group: Some(Groups(Vec::new())),
 				omit: vec![],
@@ -1070,6 +1071,7 @@ impl Document {
 				what: vec![Expr::Table(table_name.clone())],
 				// WHERE group_expr1 = group_value1 && group_expr2 = group_value2 && ..
 				cond: condition.map(Cond),
+				fusion: None,
 				// GROUP ALL
 				group: Some(Groups(Vec::new())),
 				omit: vec![],
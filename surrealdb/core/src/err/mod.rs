@@ -191,7 +191,6 @@ pub(crate) enum Error {
 
 	/// The URL is invalid
 	#[error("The URL `{0}` is invalid")]
-	#[cfg_attr(not(any(feature = "http", feature = "jwks")), expect(dead_code))]
 	InvalidUrl(String),
 
 	/// The size of the vector is incorrect
@@ -279,6 +278,25 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested sink does not exist
+	#[error("The sink '{name}' does not exist")]
+	SkNotFound {
+		name: String,
+	},
+
+	/// The requested consumer does not exist
+	#[error("The consumer '{name}' does not exist")]
+	CsNotFound {
+		name: String,
+	},
+
+	/// The member of a consumer group has not read within the consumer timeout
+	#[error("The member '{member}' of consumer '{name}' is not active")]
+	CsMemberNotActive {
+		name: String,
+		member: String,
+	},
+
 	/// The requested function does not exist
 	#[error("The function '{name}' does not exist")]
 	FcNotFound {
@@ -685,6 +703,13 @@ pub(crate) enum Error {
 	#[cfg_attr(not(any(feature = "http", feature = "jwks")), expect(dead_code))]
 	Http(String),
 
+	/// A change set could not be delivered to the endpoint of a sink
+	#[error("Failed to deliver changes to sink '{name}': {message}")]
+	SinkDelivery {
+		name: String,
+		message: String,
+	},
+
 	/// There was an error processing a value in parallel
 	#[error("There was an error processing a value in parallel: {0}")]
 	Channel(String),
@@ -716,6 +741,33 @@ pub(crate) enum Error {
 		exp: String,
 	},
 
+	/// The only indexes able to support the given expression are partial
+	/// indexes whose predicate the query condition does not imply
+	#[error(
+		"There was no suitable index supporting the expression: {exp}. The index '{index}' only holds the records matching '{cond}', which the query condition does not imply"
+	)]
+	PartialIndexNotImplied {
+		exp: String,
+		index: String,
+		cond: String,
+	},
+
+	/// A full-text query used a phrase or proximity clause against an index
+	/// which does not store the term positions
+	#[error(
+		"The full-text query '{query}' uses phrase or proximity matching, which requires the index to be defined with HIGHLIGHTS"
+	)]
+	FtPositionsRequired {
+		query: String,
+	},
+
+	/// A full-text query used a phrase or proximity clause against an index
+	/// which was built before term positions were stored
+	#[error(
+		"The full-text index was built without term positions, which phrase or proximity matching requires. Rebuild it with REBUILD INDEX"
+	)]
+	FtPositionsOutdated,
+
 	/// Represents an error when analyzing a value
 	#[error("A value can't be analyzed: {0}")]
 	AnalyzerError(String),
@@ -784,7 +836,6 @@ pub(crate) enum Error {
 
 	/// Network target is not allowed
 	#[error("Access to network target '{0}' is not allowed")]
-	#[cfg_attr(not(feature = "http"), expect(dead_code))]
 	NetTargetNotAllowed(String),
 
 	//
@@ -866,6 +917,18 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The requested sink already exists
+	#[error("The sink '{name}' already exists")]
+	SkAlreadyExists {
+		name: String,
+	},
+
+	/// The requested consumer already exists
+	#[error("The consumer '{name}' already exists")]
+	CsAlreadyExists {
+		name: String,
+	},
+
 	/// The requested field already exists
 	#[error("The field '{name}' already exists")]
 	FdAlreadyExists {
@@ -1132,6 +1195,12 @@ pub(crate) enum Error {
 		actual: u16,
 	},
 
+	/// A backup could not be written or restored
+	#[error("Invalid backup: {message}")]
+	InvalidBackup {
+		message: String,
+	},
+
 	#[error("Size of query script exceeded maximum supported size of 4,294,967,295 bytes.")]
 	QueryTooLarge,
 
@@ -1296,6 +1365,22 @@ pub(crate) enum Error {
 		field: String,
 		index: String,
 	},
+
+	#[error(
+		"Only deterministic expressions over the record's fields can be indexed. Index: '{index}' - Expression: '{expr}'"
+	)]
+	NonDeterministicIndexExpression {
+		expr: String,
+		index: String,
+	},
+
+	#[error(
+		"Only deterministic conditions over the record's fields can filter an index. Index: '{index}' - Condition: '{cond}'"
+	)]
+	NonDeterministicIndexCondition {
+		cond: String,
+		index: String,
+	},
 }
 
 impl Error {
@@ -250,6 +250,12 @@ pub fn into_types_error(error: Error) -> TypesError {
 		| SeqAlreadyExists {
 			..
 		}
+		| SkAlreadyExists {
+			..
+		}
+		| CsAlreadyExists {
+			..
+		}
 		| NtAlreadyExists {
 			..
 		}
@@ -285,7 +291,10 @@ pub fn into_types_error(error: Error) -> TypesError {
 		Thrown(..) => TypesError::thrown(message),
 
 		// Connection/transport (remote request failure)
-		Http(..) => TypesError::connection(message, ConnectionError::ConnectionFailed),
+		Http(..)
+		| SinkDelivery {
+			..
+		} => TypesError::connection(message, ConnectionError::ConnectionFailed),
 
 		// Not found (no record returned)
 		NoRecordFound => TypesError::not_found(message, None),
@@ -313,7 +322,8 @@ pub fn into_types_error(error: Error) -> TypesError {
 			| KvsError::Transaction(_)
 			| KvsError::TimestampInvalid(_)
 			| KvsError::Internal(_)
-			| KvsError::CompactionNotSupported => TypesError::internal(message),
+			| KvsError::CompactionNotSupported
+			| KvsError::Encryption(_) => TypesError::internal(message),
 		},
 
 		// Internal and everything else
@@ -325,6 +335,13 @@ pub fn into_types_error(error: Error) -> TypesError {
 		NoIndexFoundForMatch {
 			..
 		} => TypesError::internal(message),
+		FtPositionsRequired {
+			..
+		}
+		| PartialIndexNotImplied {
+			..
+		}
+		| FtPositionsOutdated => TypesError::configuration(message, None),
 		AnalyzerError(..) => TypesError::internal(message),
 		HighlightError(..) => TypesError::internal(message),
 		FstError(_) => TypesError::internal(message),
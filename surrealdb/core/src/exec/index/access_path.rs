@@ -5,6 +5,8 @@
 
 use std::sync::Arc;
 
+use geo::Rect;
+
 use super::IndexCandidate;
 use crate::catalog::IndexDefinition;
 use crate::expr::BinaryOperator;
@@ -111,6 +113,18 @@ pub enum AccessPath {
 		ef: u32,
 	},
 
+	/// Spatial search using a geohash-cell index.
+	///
+	/// Returns a superset of the matching records, so the original
+	/// predicate is always re-applied above the scan.
+	SpatialSearch {
+		index_ref: IndexRef,
+		/// The bounding rectangles of the query region
+		region: Vec<Rect<f64>>,
+		/// Whether records holding non-point geometries must be returned
+		non_points: bool,
+	},
+
 	/// Union of multiple index scans (OR-union, scalar IN-expansion,
 	/// or array-containment expansion).
 	///
@@ -193,6 +207,14 @@ pub enum BTreeAccess {
 		/// ANN search expansion factor
 		ef: u32,
 	},
+
+	/// Spatial search access via a geohash-cell index.
+	Spatial {
+		/// The bounding rectangles of the query region
+		region: Vec<Rect<f64>>,
+		/// Whether records holding non-point geometries must be returned
+		non_points: bool,
+	},
 }
 
 /// A bound for a range scan.
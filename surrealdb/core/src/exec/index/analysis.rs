@@ -3,17 +3,21 @@
 //! The [`IndexAnalyzer`] examines query conditions and ORDER BY clauses to find
 //! indexes that can accelerate the query.
 
+use std::borrow::Cow;
 use std::sync::Arc;
 
+use geo::Rect;
+
 use super::access_path::{AccessPath, BTreeAccess, IndexRef, RangeBound, select_access_path};
 use crate::catalog::{Index, IndexDefinition};
 use crate::exec::planner::util::try_literal_to_value;
 use crate::expr::operator::{MatchesOperator, NearestNeighbor, PrefixOperator};
 use crate::expr::order::Ordering;
 use crate::expr::with::With;
-use crate::expr::{BinaryOperator, Cond, Expr, Idiom};
+use crate::expr::{BinaryOperator, Cond, Expr, Function, Idiom, Literal};
 use crate::idx::planner::ScanDirection;
-use crate::val::{Number, Value};
+use crate::idx::spatial;
+use crate::val::{Geometry, Number, Value};
 
 /// Analyzes query conditions to find matching indexes.
 pub struct IndexAnalyzer<'a> {
@@ -61,6 +65,9 @@ impl<'a> IndexAnalyzer<'a> {
 			self.analyze_order(ordering, &mut candidates);
 		}
 
+		// Filter out partial indexes the condition does not imply
+		candidates.retain(|c| c.index_ref.definition().serves(cond));
+
 		// Filter out indexes not allowed by WITH hints
 		if let Some(With::Index(names)) = self.with_hints {
 			candidates.retain(|c| names.iter().any(|n| n.as_str() == c.index_ref.name.as_str()));
@@ -209,7 +216,7 @@ impl<'a> IndexAnalyzer<'a> {
 			let mut best: Option<(usize, usize)> = None; // (index idx, num cols)
 
 			for (idx, ix_def) in self.indexes.iter().enumerate() {
-				if ix_def.prepare_remove {
+				if ix_def.prepare_remove || !ix_def.serves(Some(cond)) {
 					continue;
 				}
 				if !matches!(ix_def.index, crate::catalog::Index::Idx | crate::catalog::Index::Uniq)
@@ -302,7 +309,7 @@ impl<'a> IndexAnalyzer<'a> {
 			}
 
 			for (idx, ix_def) in self.indexes.iter().enumerate() {
-				if ix_def.prepare_remove {
+				if ix_def.prepare_remove || !ix_def.serves(Some(cond)) {
 					continue;
 				}
 				if !matches!(ix_def.index, crate::catalog::Index::Idx | crate::catalog::Index::Uniq)
@@ -498,19 +505,13 @@ impl<'a> IndexAnalyzer<'a> {
 		right: &Expr,
 	) -> Option<SimpleCondition> {
 		let (idiom, value, position) = match (left, right) {
-			(Expr::Idiom(idiom), Expr::Literal(lit)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom.clone(), value, IdiomPosition::Left)
-				} else {
-					return None;
-				}
+			(_, Expr::Literal(lit)) => {
+				let idiom = index_key(left)?;
+				(idiom.into_owned(), try_literal_to_value(lit)?, IdiomPosition::Left)
 			}
-			(Expr::Literal(lit), Expr::Idiom(idiom)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom.clone(), value, IdiomPosition::Right)
-				} else {
-					return None;
-				}
+			(Expr::Literal(lit), _) => {
+				let idiom = index_key(right)?;
+				(idiom.into_owned(), try_literal_to_value(lit)?, IdiomPosition::Right)
 			}
 			_ => return None,
 		};
@@ -817,6 +818,17 @@ impl<'a> IndexAnalyzer<'a> {
 					BinaryOperator::Contain | BinaryOperator::Inside => {
 						self.try_match_containment(left, op, right, candidates);
 						self.try_match_comparison(left, op, right, candidates);
+						self.try_match_spatial(left, right, candidates);
+					}
+					BinaryOperator::Intersects => {
+						self.try_match_spatial(left, right, candidates);
+					}
+					BinaryOperator::LessThan
+					| BinaryOperator::LessThanEqual
+					| BinaryOperator::MoreThan
+					| BinaryOperator::MoreThanEqual => {
+						self.try_match_spatial_distance(left, op, right, candidates);
+						self.try_match_comparison(left, op, right, candidates);
 					}
 					_ => {
 						self.try_match_comparison(left, op, right, candidates);
@@ -846,20 +858,14 @@ impl<'a> IndexAnalyzer<'a> {
 	) {
 		// Extract idiom and value from the comparison
 		let (idiom, value, position) = match (left, right) {
-			(Expr::Idiom(idiom), Expr::Literal(lit)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom, value, IdiomPosition::Left)
-				} else {
-					return;
-				}
-			}
-			(Expr::Literal(lit), Expr::Idiom(idiom)) => {
-				if let Some(value) = try_literal_to_value(lit) {
-					(idiom, value, IdiomPosition::Right)
-				} else {
-					return;
-				}
-			}
+			(_, Expr::Literal(lit)) => match (index_key(left), try_literal_to_value(lit)) {
+				(Some(idiom), Some(value)) => (idiom, value, IdiomPosition::Left),
+				_ => return,
+			},
+			(Expr::Literal(lit), _) => match (index_key(right), try_literal_to_value(lit)) {
+				(Some(idiom), Some(value)) => (idiom, value, IdiomPosition::Right),
+				_ => return,
+			},
 			// Parameters are pre-folded into literals before the analyzer
 			// runs (see `resolve_condition_params` in the planner and dynamic
 			// scan). If a bare `Expr::Param` reaches the analyzer it means
@@ -877,7 +883,7 @@ impl<'a> IndexAnalyzer<'a> {
 
 			// Check if the idiom matches the first column of the index
 			if let Some(first_col) = ix_def.cols.first()
-				&& idiom_matches(idiom, first_col)
+				&& idiom_matches(&idiom, first_col)
 				&& let Some(access) =
 					self.match_operator_to_access(op, &value, position, &ix_def.index)
 			{
@@ -1131,6 +1137,100 @@ impl<'a> IndexAnalyzer<'a> {
 		}
 	}
 
+	/// Try to match a geometry predicate (`INSIDE`, `CONTAINS`,
+	/// `INTERSECTS`) to a spatial index.
+	///
+	/// All three operators can only hold when the bounding rectangles of
+	/// both geometries overlap, so the region is the bounding rectangle of
+	/// the geometry literal, whichever side the field is on.
+	fn try_match_spatial(&self, left: &Expr, right: &Expr, candidates: &mut Vec<IndexCandidate>) {
+		let (idiom, geometry) = match (left, right) {
+			(Expr::Idiom(idiom), Expr::Literal(Literal::Geometry(g)))
+			| (Expr::Literal(Literal::Geometry(g)), Expr::Idiom(idiom)) => (idiom, g),
+			_ => return,
+		};
+		let Some(rect) = spatial::bounding_rect(geometry) else {
+			return;
+		};
+		self.push_spatial_candidates(idiom, &[rect], false, candidates);
+	}
+
+	/// Try to match a distance predicate to a spatial index.
+	///
+	/// Handles `geo::distance(field, point) < r` (and `<=`), as well as the
+	/// mirrored `r > geo::distance(field, point)` (and `>=`).
+	fn try_match_spatial_distance(
+		&self,
+		left: &Expr,
+		op: &BinaryOperator,
+		right: &Expr,
+		candidates: &mut Vec<IndexCandidate>,
+	) {
+		let (call, limit) = match op {
+			BinaryOperator::LessThan | BinaryOperator::LessThanEqual => (left, right),
+			BinaryOperator::MoreThan | BinaryOperator::MoreThanEqual => (right, left),
+			_ => return,
+		};
+		let Expr::FunctionCall(call) = call else {
+			return;
+		};
+		if !matches!(&call.receiver, Function::Normal(name) if name == "geo::distance") {
+			return;
+		}
+		let (idiom, centre) = match call.arguments.as_slice() {
+			[Expr::Idiom(idiom), Expr::Literal(Literal::Geometry(Geometry::Point(p)))]
+			| [Expr::Literal(Literal::Geometry(Geometry::Point(p))), Expr::Idiom(idiom)] => (idiom, p.0),
+			_ => return,
+		};
+		let meters = match limit {
+			Expr::Literal(lit) => match try_literal_to_value(lit) {
+				Some(Value::Number(n)) => n.to_float(),
+				_ => return,
+			},
+			_ => return,
+		};
+		if !meters.is_finite() {
+			return;
+		}
+		let rect = spatial::region_within_distance(centre, meters.max(0.0));
+		// `geo::distance` returns NONE for non-point geometries, which
+		// compares lower than any distance, so those records also match.
+		self.push_spatial_candidates(idiom, &[rect], true, candidates);
+	}
+
+	/// Add a spatial candidate for every spatial index on `idiom`.
+	fn push_spatial_candidates(
+		&self,
+		idiom: &Idiom,
+		region: &[Rect<f64>],
+		non_points: bool,
+		candidates: &mut Vec<IndexCandidate>,
+	) {
+		for (idx, ix_def) in self.indexes.iter().enumerate() {
+			if ix_def.prepare_remove {
+				continue;
+			}
+
+			// Only Spatial indexes support geometry predicates
+			if !matches!(ix_def.index, Index::Spatial(_)) {
+				continue;
+			}
+
+			if let Some(first_col) = ix_def.cols.first()
+				&& idiom_matches(idiom, first_col)
+			{
+				let index_ref = IndexRef::new(Arc::clone(&self.indexes), idx);
+				candidates.push(IndexCandidate::new(
+					index_ref,
+					BTreeAccess::Spatial {
+						region: region.to_vec(),
+						non_points,
+					},
+				));
+			}
+		}
+	}
+
 	/// Try to match a KNN expression to an ANN index.
 	fn try_match_knn(
 		&self,
@@ -1402,6 +1502,12 @@ impl IndexCandidate {
 			} => {
 				score += 800;
 			}
+			// A spatial scan returns a superset of the matching records
+			BTreeAccess::Spatial {
+				..
+			} => {
+				score += 600;
+			}
 		}
 
 		if self.covers_order {
@@ -1435,6 +1541,14 @@ impl IndexCandidate {
 				k: *k,
 				ef: *ef,
 			},
+			BTreeAccess::Spatial {
+				region,
+				non_points,
+			} => AccessPath::SpatialSearch {
+				index_ref: self.index_ref.clone(),
+				region: region.clone(),
+				non_points: *non_points,
+			},
 			_ => AccessPath::BTreeScan {
 				index_ref: self.index_ref.clone(),
 				access: self.access.clone(),
@@ -1462,6 +1576,30 @@ struct SimpleCondition {
 	position: IdiomPosition,
 }
 
+/// The index column a side of a comparison may match.
+///
+/// A field path matches a field column, while an expression over the record's
+/// fields (like `string::lowercase(email)`) matches an expression column, which
+/// is stored as an idiom starting with the expression.
+fn index_key(expr: &Expr) -> Option<Cow<'_, Idiom>> {
+	use crate::expr::Part;
+
+	match expr {
+		Expr::Idiom(idiom) => Some(Cow::Borrowed(idiom)),
+		Expr::FunctionCall(_)
+		| Expr::Binary {
+			..
+		}
+		| Expr::Prefix {
+			..
+		}
+		| Expr::Postfix {
+			..
+		} => Some(Cow::Owned(Idiom(vec![Part::Start(expr.clone())]))),
+		_ => None,
+	}
+}
+
 /// Check if an idiom matches an index column.
 ///
 /// Idioms containing `Part::All` (flattened field paths like `marks.*.mark`)
@@ -1600,6 +1738,7 @@ mod tests {
 			index: kind,
 			comment: None,
 			prepare_remove: false,
+			cond: None,
 		}
 	}
 
@@ -2487,4 +2626,242 @@ mod tests {
 			assert!(make(true).score() > make(false).score());
 		}
 	}
+
+	// ------------------------------------------------------------------
+	// 11. Spatial predicates
+	// ------------------------------------------------------------------
+	mod spatial {
+		use super::*;
+		use crate::catalog::SpatialParams;
+
+		fn idx_spatial(id: u32, name: &str, col: &str) -> IndexDefinition {
+			idx_def(
+				id,
+				name,
+				&[col],
+				Index::Spatial(SpatialParams {
+					precision: 8,
+				}),
+			)
+		}
+
+		fn spatial_access(cand: &IndexCandidate) -> (&[Rect<f64>], bool) {
+			match &cand.access {
+				BTreeAccess::Spatial {
+					region,
+					non_points,
+				} => (region, *non_points),
+				other => panic!("expected Spatial access, got {other:?}"),
+			}
+		}
+
+		#[test]
+		fn geometry_operators_use_spatial_index() {
+			let a = analyzer(
+				vec![idx_spatial(1, "ix_loc", "loc"), idx_basic(2, "ix_b", &["loc"])],
+				None,
+			);
+			for snippet in
+				["loc INTERSECTS (1.5, 2.5)", "loc CONTAINS (1.5, 2.5)", "(1.5, 2.5) INSIDE loc"]
+			{
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				let cand = find_for(&cands, "ix_loc").unwrap_or_else(|| panic!("{snippet}"));
+				let (region, non_points) = spatial_access(cand);
+				assert_eq!(region.len(), 1);
+				assert_eq!(
+					region[0].min(),
+					geo::Coord {
+						x: 1.5,
+						y: 2.5
+					}
+				);
+				assert!(!non_points);
+			}
+		}
+
+		#[test]
+		fn distance_predicate_uses_spatial_index() {
+			let a = analyzer(vec![idx_spatial(1, "ix_loc", "loc")], None);
+			for snippet in [
+				"geo::distance(loc, (-0.1278, 51.5074)) < 1000",
+				"geo::distance((-0.1278, 51.5074), loc) <= 1000",
+				"1000 > geo::distance(loc, (-0.1278, 51.5074))",
+			] {
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				let cand = find_for(&cands, "ix_loc").unwrap_or_else(|| panic!("{snippet}"));
+				let (region, non_points) = spatial_access(cand);
+				assert!(region[0].min().y < 51.5074 && region[0].max().y > 51.5074);
+				assert!(non_points, "non-point geometries compare below any distance");
+			}
+		}
+
+		#[test]
+		fn unbounded_distance_is_not_indexed() {
+			let a = analyzer(vec![idx_spatial(1, "ix_loc", "loc")], None);
+			for snippet in [
+				"geo::distance(loc, (0.0, 0.0)) > 1000",
+				"1000 < geo::distance(loc, (0.0, 0.0))",
+				"geo::distance(loc, other) < 1000",
+			] {
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				assert_no_candidate(&cands, "ix_loc");
+			}
+		}
+
+		#[test]
+		fn spatial_search_access_path() {
+			let a = analyzer(vec![idx_spatial(1, "ix_loc", "loc")], None);
+			let cands = a.analyze(Some(&parse_cond("loc INTERSECTS (1.5, 2.5)")), None);
+			let path = select_access_path(cands, None, ScanDirection::Forward);
+			assert!(matches!(path, AccessPath::SpatialSearch { .. }), "got {path:?}");
+		}
+	}
+
+	// ------------------------------------------------------------------
+	// 12. Partial indexes
+	// ------------------------------------------------------------------
+	mod partial {
+		use super::*;
+
+		fn idx_partial(id: u32, name: &str, cols: &[&str], predicate: &str) -> IndexDefinition {
+			IndexDefinition {
+				cond: Some(parse_cond(predicate)),
+				..idx_uniq(id, name, cols)
+			}
+		}
+
+		#[test]
+		fn used_when_the_condition_implies_the_predicate() {
+			let a = analyzer(vec![idx_partial(1, "ix_email", &["email"], "!deleted")], None);
+			for snippet in
+				["email = 'a@b.c' AND !deleted", "!deleted AND age > 18 AND email = 'a@b.c'"]
+			{
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				assert!(find_for(&cands, "ix_email").is_some(), "{snippet}");
+			}
+		}
+
+		#[test]
+		fn skipped_when_the_condition_does_not_imply_the_predicate() {
+			let a = analyzer(vec![idx_partial(1, "ix_email", &["email"], "!deleted")], None);
+			for snippet in
+				["email = 'a@b.c'", "email = 'a@b.c' OR !deleted", "email = 'a@b.c' AND deleted"]
+			{
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				assert_no_candidate(&cands, "ix_email");
+			}
+			// An ordered scan would miss the records outside of the index
+			let (_, order, _) = parse_select_parts("ORDER BY email");
+			let cands = a.analyze(None, order.as_ref());
+			assert_no_candidate(&cands, "ix_email");
+		}
+
+		#[test]
+		fn conjunctive_and_disjunctive_predicates() {
+			let a = analyzer(
+				vec![
+					idx_partial(1, "ix_and", &["email"], "!deleted AND verified"),
+					idx_partial(2, "ix_or", &["email"], "status = 'active' OR status = 'new'"),
+				],
+				None,
+			);
+			let cands = a.analyze(Some(&parse_cond("verified AND email = 'x' AND !deleted")), None);
+			assert!(find_for(&cands, "ix_and").is_some());
+			assert_no_candidate(&cands, "ix_or");
+			let cands = a.analyze(Some(&parse_cond("email = 'x' AND status = 'new'")), None);
+			assert!(find_for(&cands, "ix_or").is_some());
+			assert_no_candidate(&cands, "ix_and");
+		}
+
+		#[test]
+		fn union_branches_check_their_own_condition() {
+			let a = analyzer(
+				vec![
+					idx_partial(1, "ix_email", &["email"], "!deleted"),
+					idx_basic(2, "ix_name", &["name"]),
+				],
+				None,
+			);
+			let cond = parse_cond("(email = 'x' AND !deleted) OR name = 'y'");
+			let path = a.try_or_union(Some(&cond), ScanDirection::Forward);
+			assert!(matches!(path, Some(AccessPath::Union { .. })), "got {path:?}");
+			let cond = parse_cond("email = 'x' OR name = 'y'");
+			assert!(a.try_or_union(Some(&cond), ScanDirection::Forward).is_none());
+		}
+
+		#[test]
+		fn in_expansion_requires_the_predicate() {
+			let a = analyzer(vec![idx_partial(1, "ix_email", &["email"], "!deleted")], None);
+			let cond = parse_cond("email IN ['a', 'b']");
+			assert!(a.try_in_expansion(Some(&cond), ScanDirection::Forward).is_none());
+			let cond = parse_cond("email IN ['a', 'b'] AND !deleted");
+			assert!(a.try_in_expansion(Some(&cond), ScanDirection::Forward).is_some());
+		}
+	}
+
+	// ------------------------------------------------------------------
+	// 13. Expression indexes
+	// ------------------------------------------------------------------
+	mod expression {
+		use super::*;
+		use crate::expr::Part;
+
+		fn idx_expression(id: u32, name: &str, cols: &[&str]) -> IndexDefinition {
+			IndexDefinition {
+				cols: cols
+					.iter()
+					.map(|c| match parse_cond(c).0 {
+						Expr::Idiom(idiom) => idiom,
+						expr => Idiom(vec![Part::Start(expr)]),
+					})
+					.collect(),
+				..idx_basic(id, name, &[])
+			}
+		}
+
+		#[test]
+		fn matches_the_same_expression() {
+			let a =
+				analyzer(vec![idx_expression(1, "ix_email", &["string::lowercase(email)"])], None);
+			for snippet in
+				["string::lowercase(email) = 'a@b.c'", "'a@b.c' = string::lowercase(email)"]
+			{
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				let c = find_for(&cands, "ix_email").expect("ix_email candidate");
+				assert!(matches!(c.access, BTreeAccess::Equality(_)), "{snippet}");
+			}
+			let cands = a.analyze(Some(&parse_cond("string::lowercase(email) > 'm'")), None);
+			let c = find_for(&cands, "ix_email").expect("ix_email candidate");
+			assert!(matches!(c.access, BTreeAccess::Range { .. }));
+		}
+
+		#[test]
+		fn other_expressions_do_not_match() {
+			let a =
+				analyzer(vec![idx_expression(1, "ix_email", &["string::lowercase(email)"])], None);
+			for snippet in [
+				"email = 'a@b.c'",
+				"string::uppercase(email) = 'A@B.C'",
+				"string::lowercase(name) = 'a@b.c'",
+			] {
+				let cands = a.analyze(Some(&parse_cond(snippet)), None);
+				assert_no_candidate(&cands, "ix_email");
+			}
+		}
+
+		#[test]
+		fn compound_with_a_field_column() {
+			let a = analyzer(
+				vec![idx_expression(1, "ix_day", &["time::day(created_at)", "status"])],
+				None,
+			);
+			let cond = parse_cond("status = 'open' AND time::day(created_at) = 3");
+			let cands = a.analyze(Some(&cond), None);
+			let found = cands.iter().any(|c| {
+				c.index_ref.name.as_str() == "ix_day"
+					&& matches!(&c.access, BTreeAccess::Compound { prefix, .. } if prefix.len() == 2)
+			});
+			assert!(found, "expected a two column prefix, got {cands:?}");
+		}
+	}
 }
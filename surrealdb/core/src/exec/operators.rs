@@ -9,6 +9,7 @@ pub(crate) mod fetch;
 mod filter;
 mod foreach;
 mod graph;
+mod hybrid;
 mod ifelse;
 mod info;
 mod join;
@@ -20,12 +21,14 @@ mod limit;
 // compiled only with the feature on.
 #[cfg(feature = "gql")]
 mod mutate;
+mod otherwise;
 mod project;
 mod project_value;
 pub(crate) mod recursion;
 mod r#return;
 pub(crate) mod scan;
 mod sequence;
+mod set_op;
 mod sleep;
 mod sort;
 mod source_expr;
@@ -34,6 +37,7 @@ mod timeout;
 mod union;
 mod unwrap_exactly_one;
 mod version_scope;
+mod window;
 
 #[cfg(test)]
 pub(crate) mod test_util;
@@ -60,6 +64,7 @@ pub use graph::{
 	DistinctEdges, EdgeBinding, EndpointBind, EndpointField, Expand, ExpandDir, PathExpand,
 	PathMode, ShortestPathExpand, ShortestSelector,
 };
+pub use hybrid::HybridSearch;
 pub use ifelse::IfElsePlan;
 pub use info::{
 	DatabaseInfoPlan, IndexInfoPlan, NamespaceInfoPlan, RootInfoPlan, TableInfoPlan, UserInfoPlan,
@@ -73,6 +78,8 @@ pub use limit::Limit;
 pub use mutate::{DeleteBinding, DrainSink, InsertGraph, SingleRowScan, UpdateBinding};
 #[cfg(feature = "gql")]
 pub(crate) use mutate::{InsertEdgeOp, InsertNodeOp};
+#[cfg_attr(not(feature = "gql"), allow(unused_imports))]
+pub use otherwise::Otherwise;
 pub use project::{FieldSelection, Project, Projection, SelectProject};
 pub use project_value::ProjectValue;
 pub use recursion::RecursionOp;
@@ -81,9 +88,12 @@ pub use r#return::ReturnPlan;
 pub use scan::CountScan;
 pub use scan::{
 	DynamicScan, EdgeTableSpec, EmptyScan, FullTextScan, GraphEdgeScan, GraphScanOutput, IndexScan,
-	KnnScan, RecordIdScan, ReferenceScan, ReferenceScanOutput, TableScan, UnionIndexScan,
+	KnnScan, RecordIdScan, ReferenceScan, ReferenceScanOutput, SpatialScan, TableScan,
+	UnionIndexScan,
 };
 pub use sequence::SequencePlan;
+#[cfg_attr(not(feature = "gql"), allow(unused_imports))]
+pub use set_op::{SetOp, SetOpKind};
 pub use sleep::SleepPlan;
 #[cfg(all(storage, not(target_family = "wasm")))]
 pub use sort::{ExternalSort, ExternalSortByKey};
@@ -97,6 +107,8 @@ pub use timeout::Timeout;
 pub use union::Union;
 pub use unwrap_exactly_one::UnwrapExactlyOne;
 pub use version_scope::VersionScope;
+pub use window::{Window, WindowField, WindowFunctionKind};
+pub(crate) use window::default_frame;
 
 use crate::exec::{ExecutionContext, FlowResult};
 
@@ -0,0 +1,329 @@
+//! HybridSearch operator - fuses a full-text and a vector ranking.
+//!
+//! A `SELECT ... WHERE text @@ 'query' OR embedding <|k, ef|> $vector FUSION ...`
+//! reads the `k` most relevant full-text hits and the `k` nearest neighbours
+//! concurrently, then merges both rankings into a single one, either by
+//! reciprocal rank fusion or by a weighted sum of the normalized scores.
+//!
+//! This operator consumes both input streams entirely (pipeline-breaking,
+//! like `Sort`) and emits the fused records by descending fused score.
+//!
+//! Pipeline shape:
+//! ```text
+//! FullTextScan(top k) ─┐
+//!                      ├─> HybridSearch(RRF | LINEAR) -> Project
+//! KnnScan(k) ──────────┘
+//! ```
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use crate::catalog::Distance;
+use crate::err::Error;
+use crate::exec::function::KnnContext;
+use crate::exec::{
+	AccessMode, CardinalityHint, CombineAccessModes, ContextLevel, ExecOperator, ExecutionContext,
+	FlowResult, OperatorMetrics, ValueBatch, ValueBatchStream, buffer_stream, monitor_stream,
+};
+use crate::expr::{ControlFlow, Fusion, FusionNorm};
+use crate::val::{RecordId, Value};
+
+/// Hybrid search operator - fuses the rankings of a full-text and a KNN scan.
+#[derive(Debug)]
+pub struct HybridSearch {
+	/// The full-text scan, returning its hits by descending relevance.
+	pub(crate) fulltext: Arc<dyn ExecOperator>,
+	/// The KNN scan, returning its neighbours nearest first.
+	pub(crate) knn: Arc<dyn ExecOperator>,
+	/// How both rankings are combined.
+	pub(crate) fusion: Fusion,
+	/// Relevance scores recorded by the full-text scan.
+	pub(crate) scores: Arc<KnnContext>,
+	/// Distances recorded by the KNN scan.
+	pub(crate) distances: Arc<KnnContext>,
+	/// The metric of the vector index, which the distances were computed with.
+	pub(crate) metric: Distance,
+	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl HybridSearch {
+	pub(crate) fn new(
+		fulltext: Arc<dyn ExecOperator>,
+		knn: Arc<dyn ExecOperator>,
+		fusion: Fusion,
+		scores: Arc<KnnContext>,
+		distances: Arc<KnnContext>,
+		metric: Distance,
+	) -> Self {
+		Self {
+			fulltext,
+			knn,
+			fusion,
+			scores,
+			distances,
+			metric,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for HybridSearch {
+	fn name(&self) -> &'static str {
+		"HybridSearch"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		let fusion = match &self.fusion {
+			Fusion::Rrf(constant) => format!("RRF({constant})"),
+			Fusion::Linear {
+				fulltext,
+				vector,
+				norm,
+			} => {
+				let norm = match norm {
+					FusionNorm::MinMax => "MINMAX",
+					FusionNorm::ZScore => "ZSCORE",
+				};
+				format!("LINEAR({fulltext}, {vector}, {norm})")
+			}
+		};
+		vec![("fusion".to_string(), fusion)]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.fulltext.required_context().max(self.knn.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		[self.fulltext.access_mode(), self.knn.access_mode()].into_iter().combine_all()
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		match (self.fulltext.cardinality_hint(), self.knn.cardinality_hint()) {
+			(CardinalityHint::Bounded(a), CardinalityHint::Bounded(b)) => {
+				CardinalityHint::Bounded(a + b)
+			}
+			_ => CardinalityHint::Unbounded,
+		}
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.fulltext, &self.knn]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let buffer_size = ctx.root().ctx.config.operator_buffer_size;
+		let fulltext = buffer_stream(
+			self.fulltext.execute(ctx)?,
+			self.fulltext.access_mode(),
+			self.fulltext.cardinality_hint(),
+			buffer_size,
+		);
+		let knn = buffer_stream(
+			self.knn.execute(ctx)?,
+			self.knn.access_mode(),
+			self.knn.cardinality_hint(),
+			buffer_size,
+		);
+		let fusion = self.fusion.clone();
+		let scores = Arc::clone(&self.scores);
+		let distances = Arc::clone(&self.distances);
+		let metric = self.metric.clone();
+		let ctx = ctx.clone();
+
+		let result_stream = futures::stream::once(async move {
+			// Pull both rankings concurrently
+			let (fulltext, knn) =
+				futures::try_join!(collect_ranked(&ctx, fulltext), collect_ranked(&ctx, knn))?;
+
+			let (fulltext_scores, vector_scores) = match fusion {
+				Fusion::Rrf(constant) => (rrf(fulltext.len(), constant), rrf(knn.len(), constant)),
+				Fusion::Linear {
+					fulltext: fulltext_weight,
+					vector: vector_weight,
+					norm,
+				} => {
+					let mut fulltext_scores = Vec::with_capacity(fulltext.len());
+					for (rid, _) in &fulltext {
+						let score = scores.get(rid).await.map(|s| s.to_float());
+						fulltext_scores.push(score.unwrap_or(0.0));
+					}
+					// Distances are turned into similarities by an order
+					// preserving transform, which the normalization rescales
+					let mut similarities = Vec::with_capacity(knn.len());
+					for (rid, _) in &knn {
+						let distance = distances.get(rid).await.map(|d| d.to_float());
+						similarities.push(distance.map(|d| metric.similarity(d)));
+					}
+					// A neighbour without a distance ranks as the least similar
+					let least =
+						similarities.iter().flatten().copied().fold(f64::INFINITY, f64::min);
+					let vector_scores = similarities
+						.into_iter()
+						.map(|s| match s {
+							Some(s) => s,
+							None if least.is_finite() => least,
+							None => 0.0,
+						})
+						.collect();
+					(
+						linear(fulltext_scores, fulltext_weight.to_float(), norm),
+						linear(vector_scores, vector_weight.to_float(), norm),
+					)
+				}
+			};
+			let values = fuse([(fulltext, fulltext_scores), (knn, vector_scores)]);
+
+			Ok(ValueBatch {
+				values,
+			})
+		});
+
+		// Filter out empty batches
+		let filtered = result_stream.filter_map(|result| async move {
+			match result {
+				Ok(batch) if batch.values.is_empty() => None,
+				other => Some(other),
+			}
+		});
+
+		Ok(monitor_stream(Box::pin(filtered), "HybridSearch", &self.metrics))
+	}
+}
+
+/// Drain a ranked input, keeping each record with its id, in rank order.
+async fn collect_ranked(
+	ctx: &ExecutionContext,
+	mut stream: ValueBatchStream,
+) -> FlowResult<Vec<(RecordId, Value)>> {
+	let mut ranked = Vec::new();
+	while let Some(batch) = stream.next().await {
+		if ctx.cancellation().is_cancelled() {
+			return Err(ControlFlow::Err(anyhow::anyhow!(Error::QueryCancelled)));
+		}
+		for value in batch?.values {
+			if let Value::Object(obj) = &value
+				&& let Some(Value::RecordId(rid)) = obj.get("id")
+			{
+				ranked.push((rid.clone(), value));
+			}
+		}
+	}
+	Ok(ranked)
+}
+
+/// The records found by a ranking, in rank order, with the contribution of
+/// each record to its fused score.
+type RankedList = (Vec<(RecordId, Value)>, Vec<f64>);
+
+/// Merge the per-record contributions of each ranking, returning the
+/// records by descending fused score. Records found by a single ranking get
+/// no contribution from the other one.
+fn fuse(lists: [RankedList; 2]) -> Vec<Value> {
+	let mut fused: Vec<(f64, Value)> = Vec::new();
+	let mut positions: HashMap<RecordId, usize> = HashMap::new();
+	for (list, contributions) in lists {
+		for ((rid, value), contribution) in list.into_iter().zip(contributions) {
+			match positions.entry(rid) {
+				Entry::Occupied(e) => fused[*e.get()].0 += contribution,
+				Entry::Vacant(e) => {
+					e.insert(fused.len());
+					fused.push((contribution, value));
+				}
+			}
+		}
+	}
+	// A stable sort keeps equally scored records in first-seen order
+	fused.sort_by(|a, b| b.0.total_cmp(&a.0));
+	fused.into_iter().map(|(_, value)| value).collect()
+}
+
+/// Reciprocal rank fusion contributions, `1 / (constant + rank)` with a
+/// 1-based rank, of a ranking of `len` records.
+fn rrf(len: usize, constant: u64) -> Vec<f64> {
+	(1..=len).map(|rank| 1.0 / (constant as f64 + rank as f64)).collect()
+}
+
+/// Weighted linear contributions of the normalized scores of a ranking.
+fn linear(scores: Vec<f64>, weight: f64, norm: FusionNorm) -> Vec<f64> {
+	normalize(scores, norm).into_iter().map(|s| weight * s).collect()
+}
+
+/// Normalize the scores of one ranking, as `search::linear` does.
+fn normalize(scores: Vec<f64>, norm: FusionNorm) -> Vec<f64> {
+	if scores.is_empty() {
+		return scores;
+	}
+	let (offset, scale) = match norm {
+		FusionNorm::MinMax => {
+			let min = scores.iter().copied().fold(f64::INFINITY, f64::min);
+			let max = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+			(min, max - min)
+		}
+		FusionNorm::ZScore => {
+			let len = scores.len() as f64;
+			let mean = scores.iter().sum::<f64>() / len;
+			let variance = scores.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / len;
+			(mean, variance.sqrt())
+		}
+	};
+	let scale = if scale > 0.0 {
+		scale
+	} else {
+		1.0
+	};
+	scores.into_iter().map(|s| (s - offset) / scale).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn ranked(ids: &[i64]) -> Vec<(RecordId, Value)> {
+		ids.iter()
+			.map(|id| {
+				let rid = RecordId::new("doc".into(), *id);
+				(rid.clone(), Value::RecordId(rid))
+			})
+			.collect()
+	}
+
+	fn values(ids: &[i64]) -> Vec<Value> {
+		ranked(ids).into_iter().map(|(_, v)| v).collect()
+	}
+
+	#[test]
+	fn fuse_favours_records_found_by_both_rankings() {
+		let fused = fuse([(ranked(&[1, 2, 3]), rrf(3, 60)), (ranked(&[3, 4]), rrf(2, 60))]);
+		// Records 2 and 4 tie, and keep their first-seen order
+		assert_eq!(fused, values(&[3, 1, 2, 4]));
+	}
+
+	#[test]
+	fn fuse_applies_linear_weights() {
+		let fulltext = linear(vec![4.0, 2.0], 1.0, FusionNorm::MinMax);
+		let vector = linear(vec![0.5, 0.25], 2.0, FusionNorm::MinMax);
+		let fused = fuse([(ranked(&[1, 2]), fulltext), (ranked(&[3, 4]), vector)]);
+		assert_eq!(fused, values(&[3, 1, 2, 4]));
+	}
+
+	#[test]
+	fn normalize_minmax() {
+		assert_eq!(normalize(vec![2.0, 4.0, 3.0], FusionNorm::MinMax), vec![0.0, 1.0, 0.5]);
+		assert_eq!(normalize(vec![5.0, 5.0], FusionNorm::MinMax), vec![0.0, 0.0]);
+	}
+
+	#[test]
+	fn normalize_zscore() {
+		assert_eq!(normalize(vec![1.0, 3.0], FusionNorm::ZScore), vec![-1.0, 1.0]);
+		assert_eq!(normalize(vec![5.0, 5.0], FusionNorm::ZScore), vec![0.0, 0.0]);
+	}
+}
@@ -154,17 +154,29 @@ async fn execute_table_info(
 	// Get the transaction
 	let txn = ctx.txn();
 
+	// Sinks and consumers are only listed when the table has any
+	let sinks = txn.all_tb_sinks(ns, db, &tb, version).await?;
+	let consumers = txn.all_tb_consumers(ns, db, &tb, version).await?;
 	// Create the result set
 	if structured {
 		Ok(Value::from(map! {
+			"consumers", if !consumers.is_empty() => process(&consumers),
 			"events" => process(&txn.all_tb_events(ns, db, &tb, version).await?),
 			"fields" => process(&txn.all_tb_fields(ns, db, &tb, version).await?),
 			"indexes" => process(&txn.all_tb_indexes(ns, db, &tb, version).await?),
 			"lives" => process(&txn.all_tb_lives(ns, db, &tb, version).await?),
+			"sinks", if !sinks.is_empty() => process(&sinks),
 			"tables" => process(&txn.all_tb_views(ns, db, &tb, version).await?),
 		}))
 	} else {
 		Ok(Value::from(map! {
+			"consumers", if !consumers.is_empty() => {
+				let mut out = Object::default();
+				for v in consumers.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"events" => {
 				let mut out = Object::default();
 				for v in txn.all_tb_events(ns, db, &tb, version).await?.iter() {
@@ -193,6 +205,13 @@ async fn execute_table_info(
 				}
 				out.into()
 			},
+			"sinks", if !sinks.is_empty() => {
+				let mut out = Object::default();
+				for v in sinks.iter() {
+					out.insert(v.name.clone(), v.to_sql().into());
+				}
+				out.into()
+			},
 			"tables" => {
 				let mut out = Object::default();
 				for v in txn.all_tb_views(ns, db, &tb, version).await?.iter() {
@@ -34,30 +34,30 @@ use crate::val::{Number, Value};
 /// min-heap of the **worst** (farthest) distances, matching the `SortTopK`
 /// pattern. When the heap is full, the worst entry is evicted when a closer
 /// record arrives.
-struct DistanceEntry {
+struct DistanceEntry<T> {
 	/// Computed distance from the query vector (sort key).
 	distance: Number,
-	/// The full record value.
-	value: Value,
+	/// The record (or record id) the distance was computed for.
+	value: T,
 	/// Insertion sequence number for stable tie-breaking.
 	seq: u64,
 }
 
-impl PartialEq for DistanceEntry {
+impl<T> PartialEq for DistanceEntry<T> {
 	fn eq(&self, other: &Self) -> bool {
 		self.cmp(other) == Ordering::Equal
 	}
 }
 
-impl Eq for DistanceEntry {}
+impl<T> Eq for DistanceEntry<T> {}
 
-impl PartialOrd for DistanceEntry {
+impl<T> PartialOrd for DistanceEntry<T> {
 	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
 		Some(self.cmp(other))
 	}
 }
 
-impl Ord for DistanceEntry {
+impl<T> Ord for DistanceEntry<T> {
 	fn cmp(&self, other: &Self) -> Ordering {
 		// Reversed: we want a min-heap of the worst (farthest) distances.
 		// `BinaryHeap::peek()` returns the largest element, so by reversing
@@ -71,6 +71,61 @@ impl Ord for DistanceEntry {
 	}
 }
 
+/// Bounded collection of the `k` nearest entries pushed so far.
+///
+/// Shared by [`KnnTopK`] and the brute-force fallback of filtered
+/// [`KnnScan`](super::KnnScan) searches.
+pub(crate) struct NearestK<T> {
+	/// Number of nearest entries to keep.
+	k: usize,
+	/// Min-heap of the worst (farthest) kept entries.
+	heap: BinaryHeap<std::cmp::Reverse<DistanceEntry<T>>>,
+	/// Next insertion sequence number.
+	seq: u64,
+}
+
+impl<T> NearestK<T> {
+	pub(crate) fn new(k: usize) -> Self {
+		Self {
+			k,
+			heap: BinaryHeap::with_capacity(k + 1),
+			seq: 0,
+		}
+	}
+
+	/// Keeps `value` if it is among the `k` nearest entries seen so far.
+	pub(crate) fn push(&mut self, distance: Number, value: T) {
+		let entry = DistanceEntry {
+			distance,
+			value,
+			seq: self.seq,
+		};
+		self.seq += 1;
+		if self.heap.len() >= self.k {
+			// Heap is full -- only insert if closer than the farthest
+			if let Some(worst) = self.heap.peek()
+				&& entry.distance < worst.0.distance
+			{
+				self.heap.push(std::cmp::Reverse(entry));
+				self.heap.pop();
+			}
+		} else {
+			self.heap.push(std::cmp::Reverse(entry));
+		}
+	}
+
+	/// Returns the kept entries ordered by distance (nearest first).
+	pub(crate) fn into_sorted_vec(mut self) -> Vec<(Number, T)> {
+		// Pop yields farthest-first, so reverse after collecting.
+		let mut entries = Vec::with_capacity(self.heap.len());
+		while let Some(std::cmp::Reverse(entry)) = self.heap.pop() {
+			entries.push((entry.distance, entry.value));
+		}
+		entries.reverse();
+		entries
+	}
+}
+
 /// Brute-force KNN operator: scans all input, computes vector distances,
 /// and returns the top-K nearest records.
 ///
@@ -169,14 +224,13 @@ impl ExecOperator for KnnTopK {
 		let field = self.field.clone();
 		let query_vector = self.query_vector.clone();
 		let k = self.k;
-		let distance = self.distance.clone();
+		// Factorise a Mahalanobis covariance matrix once for all records
+		let distance = self.distance.prepare()?;
 		let cancellation = ctx.cancellation().clone();
 		let knn_context = self.knn_context.clone();
 
 		let result_stream = futures::stream::once(async move {
-			let mut heap: BinaryHeap<std::cmp::Reverse<DistanceEntry>> =
-				BinaryHeap::with_capacity(k + 1);
-			let mut seq: u64 = 0;
+			let mut nearest = NearestK::new(k);
 
 			futures::pin_mut!(input_stream);
 			while let Some(batch_result) = input_stream.next().await {
@@ -203,49 +257,27 @@ impl ExecOperator for KnnTopK {
 						Err(_) => continue, // Skip on dimension mismatch etc.
 					};
 
-					let entry = DistanceEntry {
-						distance: dist,
-						value,
-						seq,
-					};
-					seq += 1;
-
-					if heap.len() >= k {
-						// Heap is full -- only insert if closer than the farthest
-						if let Some(worst) = heap.peek()
-							&& entry.distance < worst.0.distance
-						{
-							heap.push(std::cmp::Reverse(entry));
-							heap.pop();
-						}
-					} else {
-						heap.push(std::cmp::Reverse(entry));
-					}
+					nearest.push(dist, value);
 				}
 			}
 
 			// Extract results ordered by distance (nearest first).
-			// Pop yields farthest-first, so reverse after collecting.
-			let mut entries: Vec<DistanceEntry> = Vec::with_capacity(heap.len());
-			while let Some(std::cmp::Reverse(entry)) = heap.pop() {
-				entries.push(entry);
-			}
-			entries.reverse();
+			let entries = nearest.into_sorted_vec();
 
 			// Populate KNN distance context (if present) before yielding
 			// records. This makes distances available to
 			// vector::distance::knn() during downstream projection evaluation.
 			if let Some(ref knn_ctx) = knn_context {
-				for entry in &entries {
-					if let Value::Object(ref obj) = entry.value
+				for (distance, value) in &entries {
+					if let Value::Object(obj) = value
 						&& let Some(Value::RecordId(rid)) = obj.get("id")
 					{
-						knn_ctx.insert(rid.clone(), entry.distance).await;
+						knn_ctx.insert(rid.clone(), *distance).await;
 					}
 				}
 			}
 
-			let sorted: Vec<Value> = entries.into_iter().map(|e| e.value).collect();
+			let sorted: Vec<Value> = entries.into_iter().map(|(_, value)| value).collect();
 
 			Ok(ValueBatch {
 				values: sorted,
@@ -268,7 +300,7 @@ impl ExecOperator for KnnTopK {
 ///
 /// Returns `None` if the field is missing, None/Null, not an array,
 /// or contains non-numeric elements.
-fn extract_vector(value: &Value, field: &Idiom) -> Option<Vec<Number>> {
+pub(crate) fn extract_vector(value: &Value, field: &Idiom) -> Option<Vec<Number>> {
 	match value.pick(field) {
 		Value::Array(arr) if !arr.is_empty() => {
 			let mut nums = Vec::with_capacity(arr.len());
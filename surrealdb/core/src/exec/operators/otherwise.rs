@@ -0,0 +1,128 @@
+//! `Otherwise` — the GQL `OTHERWISE` query conjunction.
+//!
+//! Streams its first input; only when that input produced no row at all does
+//! it execute and stream the fallback input. The fallback is never executed
+//! otherwise, so its reads cost nothing when the first input matched.
+
+// Constructed only by the gql-gated planner (`Expr::Match` is
+// `#[cfg(feature = "gql")]`), so dead code when the feature is off — suppress
+// the lint there only.
+#![cfg_attr(not(feature = "gql"), allow(dead_code))]
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use crate::exec::{
+	AccessMode, CombineAccessModes, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
+	OperatorMetrics, ValueBatchStream, buffer_stream, monitor_stream,
+};
+
+/// Yields the rows of `input`, or the rows of `fallback` when `input` is
+/// empty.
+#[derive(Debug, Clone)]
+pub struct Otherwise {
+	pub(crate) input: Arc<dyn ExecOperator>,
+	pub(crate) fallback: Arc<dyn ExecOperator>,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl Otherwise {
+	/// Create a new `Otherwise` over `input`, falling back to `fallback`.
+	pub(crate) fn new(input: Arc<dyn ExecOperator>, fallback: Arc<dyn ExecOperator>) -> Self {
+		Self {
+			input,
+			fallback,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for Otherwise {
+	fn name(&self) -> &'static str {
+		"Otherwise"
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.input.required_context().max(self.fallback.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		[&self.input, &self.fallback].iter().map(|input| input.access_mode()).combine_all()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.input, &self.fallback]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let input_stream = buffer_stream(
+			self.input.execute(ctx)?,
+			self.input.access_mode(),
+			self.input.cardinality_hint(),
+			ctx.root().ctx.config.operator_buffer_size,
+		);
+		let fallback = Arc::clone(&self.fallback);
+		let ctx = ctx.clone();
+
+		let combined = async_stream::try_stream! {
+			let mut matched = false;
+			futures::pin_mut!(input_stream);
+			while let Some(batch_result) = input_stream.next().await {
+				let batch = batch_result?;
+				if !batch.values.is_empty() {
+					matched = true;
+					yield batch;
+				}
+			}
+			if !matched {
+				// Only now is the fallback executed.
+				let fallback_stream = buffer_stream(
+					fallback.execute(&ctx)?,
+					fallback.access_mode(),
+					fallback.cardinality_hint(),
+					ctx.root().ctx.config.operator_buffer_size,
+				);
+				futures::pin_mut!(fallback_stream);
+				while let Some(batch_result) = fallback_stream.next().await {
+					yield batch_result?;
+				}
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(combined), "Otherwise", &self.metrics))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::exec::operators::test_util::{ValuesOperator, collect, root_ctx};
+	use crate::val::Value;
+
+	fn rows(ns: &[i64]) -> Vec<Value> {
+		ns.iter().map(|n| Value::from(*n)).collect()
+	}
+
+	#[tokio::test]
+	async fn keeps_a_non_empty_input() {
+		let op: Arc<dyn ExecOperator> = Arc::new(Otherwise::new(
+			ValuesOperator::new(rows(&[1, 2])),
+			ValuesOperator::new(rows(&[9])),
+		));
+		assert_eq!(collect(&op, &root_ctx()).await, rows(&[1, 2]));
+	}
+
+	#[tokio::test]
+	async fn falls_back_on_an_empty_input() {
+		let op: Arc<dyn ExecOperator> = Arc::new(Otherwise::new(
+			ValuesOperator::new(Vec::new()),
+			ValuesOperator::new(rows(&[9, 8])),
+		));
+		assert_eq!(collect(&op, &root_ctx()).await, rows(&[9, 8]));
+	}
+}
@@ -19,6 +19,7 @@ pub(crate) mod pipeline;
 mod record_id;
 mod reference;
 pub(crate) mod resolved;
+mod spatial;
 mod table;
 mod union_index;
 
@@ -32,5 +33,6 @@ pub use knn::KnnScan;
 pub(crate) use pipeline::determine_scan_direction;
 pub use record_id::RecordIdScan;
 pub use reference::{ReferenceScan, ReferenceScanOutput};
+pub use spatial::SpatialScan;
 pub use table::TableScan;
 pub use union_index::UnionIndexScan;
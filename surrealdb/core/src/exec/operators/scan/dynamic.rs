@@ -6,7 +6,7 @@ use tracing::instrument;
 use super::pipeline::{
 	build_field_state, determine_scan_direction, eval_limit_expr, kv_scan_stream,
 };
-use super::{FullTextScan, IndexScan, KnnScan};
+use super::{FullTextScan, IndexScan, KnnScan, SpatialScan};
 use crate::catalog::{DatabaseId, NamespaceId, Permission};
 use crate::err::Error;
 use crate::exec::index::access_path::{AccessPath, select_access_path};
@@ -672,6 +672,9 @@ async fn resolve_table_scan_stream(
 			.get_table_indexes(&cfg.table_name, version_stamp)
 			.await
 			.context("Failed to fetch indexes")?;
+		if let Some(cond) = resolved_cond.as_ref() {
+			cond.check_partial_indexes(&indexes)?;
+		}
 
 		let analyzer = IndexAnalyzer::new(indexes, cfg.with.as_ref());
 		let candidates = analyzer.analyze(resolved_cond.as_ref(), cfg.order.as_ref());
@@ -765,6 +768,19 @@ async fn resolve_table_scan_stream(
 			Ok((stream, 0))
 		}
 
+		// Spatial search via a geohash-cell index. The scan only yields
+		// candidates; the predicate is re-applied by the pipeline above.
+		Some(AccessPath::SpatialSearch {
+			index_ref,
+			region,
+			non_points,
+		}) => {
+			let spatial_op =
+				SpatialScan::new(index_ref, region, non_points, cfg.table_name, cfg.version, None);
+			let stream = spatial_op.execute(ctx)?;
+			Ok((stream, 0))
+		}
+
 		// Multi-index union for OR conditions — delegate to UnionIndexScan.
 		// Permission handling is done by DynamicScan's ScanPipeline above.
 		// The `dedupe` flag is informational here: this fallback path uses
@@ -874,6 +890,18 @@ fn create_index_operator(
 				None,
 			))
 		}
+		AccessPath::SpatialSearch {
+			index_ref,
+			region,
+			non_points,
+		} => Arc::new(SpatialScan::new(
+			index_ref.clone(),
+			region.clone(),
+			*non_points,
+			cfg.table_name.clone(),
+			cfg.version.clone(),
+			None,
+		)),
 		// Provably empty: emit a single EmptyScan operator.
 		AccessPath::EmptyScan => Arc::new(super::EmptyScan::new()),
 		// TableScan and nested Union should not appear as sub-paths.
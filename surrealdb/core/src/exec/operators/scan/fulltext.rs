@@ -1,9 +1,12 @@
 //! Full-text search scan operator.
 //!
 //! This operator retrieves records using full-text search indexes,
-//! supporting the MATCHES operator with BM25 or VS scoring.
+//! supporting the MATCHES operator with BM25 or VS scoring. As part of a
+//! hybrid search it can instead return only the most relevant hits, in
+//! descending score order.
 
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashSet};
 use std::sync::Arc;
 
 use reblessive::TreeStack;
@@ -13,14 +16,15 @@ use super::pipeline::{ScanPipeline, build_field_state};
 use super::resolved::ResolvedTableContext;
 use crate::catalog::Index;
 use crate::err::Error;
+use crate::exec::function::KnnContext;
 use crate::exec::index::access_path::IndexRef;
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
 	validate_record_user_access,
 };
 use crate::exec::{
-	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
-	PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
+	AccessMode, CardinalityHint, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
+	OperatorMetrics, PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
 };
 use crate::expr::operator::MatchesOperator;
 use crate::expr::{ControlFlow, ControlFlowExt};
@@ -29,6 +33,7 @@ use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::FullTextIndex;
 use crate::idx::planner::iterators::MatchesHitsIterator;
 use crate::kvs::CachePolicy;
+use crate::val::{Number, RecordId};
 
 /// Batch size for full-text result batching.
 ///
@@ -62,6 +67,10 @@ pub struct FullTextScan {
 	pub(crate) needed_fields: Option<Option<HashSet<String>>>,
 	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
 	pub(crate) metrics: Arc<OperatorMetrics>,
+	/// When present, only the `k` most relevant hits are returned, ordered
+	/// by descending score. Their scores are recorded in the context, keyed
+	/// by record id, the same way `KnnScan` records its distances.
+	pub(crate) top_k: Option<(usize, Arc<KnnContext>)>,
 }
 
 impl FullTextScan {
@@ -82,9 +91,16 @@ impl FullTextScan {
 			resolved: None,
 			needed_fields,
 			metrics: Arc::new(OperatorMetrics::new()),
+			top_k: None,
 		}
 	}
 
+	/// Only return the `k` most relevant hits, recording their scores.
+	pub(crate) fn with_top_k(mut self, k: usize, scores: Arc<KnnContext>) -> Self {
+		self.top_k = Some((k, scores));
+		self
+	}
+
 	/// Set the plan-time resolved table context.
 	pub(crate) fn with_resolved(mut self, resolved: ResolvedTableContext) -> Self {
 		self.resolved = Some(resolved);
@@ -97,10 +113,14 @@ impl ExecOperator for FullTextScan {
 	}
 
 	fn attrs(&self) -> Vec<(String, String)> {
-		vec![
+		let mut attrs = vec![
 			("index".to_string(), self.index_ref.name.to_string()),
 			("query".to_string(), self.query.clone()),
-		]
+		];
+		if let Some((k, _)) = &self.top_k {
+			attrs.push(("k".to_string(), k.to_string()));
+		}
+		attrs
 	}
 
 	fn required_context(&self) -> ContextLevel {
@@ -111,6 +131,13 @@ impl ExecOperator for FullTextScan {
 		AccessMode::ReadOnly
 	}
 
+	fn cardinality_hint(&self) -> CardinalityHint {
+		match &self.top_k {
+			Some((k, _)) => CardinalityHint::Bounded(*k),
+			None => CardinalityHint::Unbounded,
+		}
+	}
+
 	fn metrics(&self) -> Option<&OperatorMetrics> {
 		Some(&self.metrics)
 	}
@@ -132,6 +159,7 @@ impl ExecOperator for FullTextScan {
 		let version_expr = self.version.clone();
 		let resolved = self.resolved.clone();
 		let needed_fields = self.needed_fields.clone();
+		let top_k = self.top_k.clone();
 		let ctx = ctx.clone();
 
 		let stream = async_stream::try_stream! {
@@ -267,9 +295,90 @@ impl ExecOperator for FullTextScan {
 				}
 			};
 
+			let mut hits_iter = hits_iter;
+
+			// Ranked mode: keep the best scoring hits, then fetch them by
+			// descending score until `k` records have passed the permission
+			// checks. An overflow of another `k` hits replaces the records
+			// which fail the checks, and should it run out too, the hits are
+			// scanned again for the ones ranked after those already fetched.
+			if let Some((k, scores)) = top_k {
+				let scorer = fti.new_scorer(frozen_ctx).await.context("Failed to create scorer")?;
+				let capacity = k.saturating_mul(2).max(1);
+				let mut remaining = k;
+				let mut after: Option<RankedHit> = None;
+				let mut next_iter = Some(hits_iter);
+				while let Some(mut hits_iter) = next_iter.take() {
+					// The worst of the hits kept is at the top of the heap
+					let mut heap = BinaryHeap::with_capacity(capacity + 1);
+					let mut truncated = false;
+					let mut position = 0;
+					while let Some((rid, doc_id)) = hits_iter.next(txn.as_ref()).await
+						.context("Failed to get next hit")?
+					{
+						position += 1;
+						let score = match &scorer {
+							Some(scorer) => scorer
+								.score(&fti, txn.as_ref(), &query_terms, doc_id)
+								.await
+								.context("Failed to score hit")? as f64,
+							None => 0.0,
+						};
+						let hit = RankedHit {
+							score,
+							position,
+							rid,
+						};
+						// Skip the hits which an earlier scan already fetched
+						if after.as_ref().is_some_and(|last| hit <= *last) {
+							continue;
+						}
+						heap.push(hit);
+						if heap.len() > capacity {
+							heap.pop();
+							truncated = true;
+						}
+					}
+					// The best scoring hit sorts first
+					let ranked = heap.into_sorted_vec();
+					for chunk in ranked.chunks(BATCH_SIZE) {
+						if remaining == 0 {
+							break;
+						}
+						let mut rid_batch = Vec::with_capacity(chunk.len());
+						for hit in chunk {
+							scores.insert(hit.rid.clone(), Number::Float(hit.score)).await;
+							rid_batch.push(hit.rid.clone());
+						}
+						let mut values = fetch_and_filter_records_batch(
+							&ctx,
+							&txn,
+							ns.namespace_id,
+							db.database_id,
+							&rid_batch,
+							&select_permission,
+							check_perms,
+							version,
+							CachePolicy::ReadOnly,
+						).await?;
+						pipeline.process_batch(&mut values, &ctx).await?;
+						values.truncate(remaining);
+						remaining -= values.len();
+						if !values.is_empty() {
+							yield ValueBatch { values };
+						}
+					}
+					// Scan again only if some hits were left out of the heap
+					if remaining > 0 && truncated {
+						after = ranked.into_iter().last();
+						next_iter = fti.new_hits_iterator(&query_terms, bool_op);
+					}
+				}
+				return;
+			}
+
 			// Iterate over hits, collecting record IDs into batches for
 			// batch-fetching via get_records.
-			let mut hits_iter = hits_iter;
 			let mut rid_batch = Vec::with_capacity(BATCH_SIZE);
 
 			loop {
@@ -330,3 +439,32 @@ impl ExecOperator for FullTextScan {
 		Ok(monitor_stream(Box::pin(stream), "FullTextScan", &self.metrics))
 	}
 }
+
+/// A hit of a ranked full-text scan. Hits are ordered by descending score,
+/// and then by their position in the hits iterator, so that a hit compares
+/// lower than the hits ranked after it.
+struct RankedHit {
+	score: f64,
+	position: usize,
+	rid: RecordId,
+}
+
+impl Ord for RankedHit {
+	fn cmp(&self, other: &Self) -> Ordering {
+		other.score.total_cmp(&self.score).then(self.position.cmp(&other.position))
+	}
+}
+
+impl PartialOrd for RankedHit {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl PartialEq for RankedHit {
+	fn eq(&self, other: &Self) -> bool {
+		self.cmp(other) == Ordering::Equal
+	}
+}
+
+impl Eq for RankedHit {}
@@ -175,14 +175,17 @@ impl ExecOperator for IndexScan {
 					format!("[{prefix_str}]")
 				}
 			}
-			// FullText and KNN should use dedicated operators
+			// FullText, KNN and Spatial should use dedicated operators
 			BTreeAccess::FullText {
 				..
 			}
 			| BTreeAccess::Knn {
 				..
+			}
+			| BTreeAccess::Spatial {
+				..
 			} => {
-				unreachable!("IndexScan does not support FullText or KNN access")
+				unreachable!("IndexScan does not support FullText, KNN or Spatial access")
 			}
 		};
 		let mut attrs = vec![
@@ -710,10 +713,12 @@ impl ExecOperator for IndexScan {
 					}
 				}
 
-				// FullText and KNN should use dedicated operators
-				(BTreeAccess::FullText { .. }, _) | (BTreeAccess::Knn { .. }, _) => {
+				// FullText, KNN and Spatial should use dedicated operators
+				(BTreeAccess::FullText { .. }, _)
+				| (BTreeAccess::Knn { .. }, _)
+				| (BTreeAccess::Spatial { .. }, _) => {
 					Err(ControlFlow::Err(anyhow::anyhow!(
-						"IndexScan does not support FullText or KNN access - use dedicated operators"
+						"IndexScan does not support FullText, KNN or Spatial access - use dedicated operators"
 					)))?
 				}
 			}
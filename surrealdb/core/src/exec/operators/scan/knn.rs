@@ -3,19 +3,29 @@
 //! This operator performs approximate nearest-neighbor search using an ANN
 //! index. It retrieves the top-K records closest to a query vector, ordered by
 //! distance (nearest first).
-
-use std::collections::HashSet;
+//!
+//! When the residual WHERE condition can be served by another index, the
+//! records matching it are counted first to estimate its selectivity. A
+//! selective condition either expands the ANN search list, or — when only a
+//! few records match — skips the graph entirely for an exact brute-force
+//! search over those records (see [`KnnFilterStrategy`]).
+
+use std::collections::{HashSet, VecDeque};
+use std::future::Future;
 use std::sync::Arc;
 
+use futures::StreamExt;
 use reblessive::TreeStack;
 use surrealdb_types::ToSql;
 
 use super::common::fetch_and_filter_records_batch;
 use super::pipeline::{ScanPipeline, build_field_state};
 use super::resolved::ResolvedTableContext;
-use crate::catalog::Index;
+use crate::catalog::{Distance, Index};
+use crate::cnf::{KNN_FILTER_BRUTE_FORCE_LIMIT, KNN_FILTER_SELECTIVITY_SAMPLE};
 use crate::err::Error;
 use crate::exec::index::access_path::IndexRef;
+use crate::exec::operators::knn_topk::{NearestK, extract_vector};
 use crate::exec::permission::{
 	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
 	validate_record_user_access,
@@ -24,10 +34,12 @@ use crate::exec::{
 	AccessMode, CardinalityHint, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
 	OperatorMetrics, PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
 };
-use crate::expr::{Cond, ControlFlow, ControlFlowExt};
+use crate::expr::{Cond, ControlFlow, ControlFlowExt, Idiom};
 use crate::iam::Action;
+use crate::idx::planner::iterators::KnnIteratorResult;
+use crate::idx::planner::knn::KnnFilterStrategy;
 use crate::kvs::CachePolicy;
-use crate::val::Number;
+use crate::val::{Number, Value};
 
 /// KNN scan operator using an ANN index.
 ///
@@ -68,6 +80,10 @@ pub struct KnnScan {
 	/// probe their field values. Preserve that ordering when touching
 	/// `is_record_truthy`; see the SECURITY note there for the threat model.
 	pub(crate) residual_cond: Option<Cond>,
+	/// Records matching the residual condition, read through another index.
+	/// When present, their count drives the [`KnnFilterStrategy`] of the
+	/// search.
+	pub(crate) candidates: Option<Arc<dyn ExecOperator>>,
 	/// Projection-aware field set for computed-field materialization.
 	/// Outer `None` = sub-operator mode (parent handles fields).
 	/// `Some(None)` = all fields, `Some(Some(set))` = specific fields.
@@ -98,10 +114,17 @@ impl KnnScan {
 			metrics: Arc::new(OperatorMetrics::new()),
 			knn_context,
 			residual_cond,
+			candidates: None,
 			needed_fields,
 		}
 	}
 
+	/// Set the source of the records matching the residual condition.
+	pub(crate) fn with_candidates(mut self, candidates: Option<Arc<dyn ExecOperator>>) -> Self {
+		self.candidates = candidates;
+		self
+	}
+
 	/// Set the plan-time resolved table context.
 	pub(crate) fn with_resolved(mut self, resolved: ResolvedTableContext) -> Self {
 		self.resolved = Some(resolved);
@@ -144,6 +167,10 @@ impl ExecOperator for KnnScan {
 		CardinalityHint::Bounded(self.k as usize)
 	}
 
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		self.candidates.iter().collect()
+	}
+
 	fn metrics(&self) -> Option<&OperatorMetrics> {
 		Some(&self.metrics)
 	}
@@ -166,6 +193,7 @@ impl ExecOperator for KnnScan {
 		let version_expr = self.version.clone();
 		let knn_context = self.knn_context.clone();
 		let residual_cond = self.residual_cond.clone();
+		let candidates = self.candidates.clone();
 		let resolved = self.resolved.clone();
 		let needed_fields = self.needed_fields.clone();
 		let ctx = ctx.clone();
@@ -273,32 +301,50 @@ impl ExecOperator for KnnScan {
 						.await
 						.context("Failed to check HNSW index state")?;
 
-					let cond_filter = match (residual_cond.clone(), ctx.options()) {
-						(Some(cond), Some(opt)) => Some((opt, Arc::new(cond))),
-						_ => None,
-					};
-
-					let mut stack = TreeStack::new();
-					stack
-						.enter(|stk| {
-							let hnsw_index = &hnsw_index;
-							let vector = &vector;
-							async move {
-								hnsw_index
-									.knn_search(
-										frozen_ctx,
-										stk,
-										vector,
-										k as usize,
-										ef as usize,
-										cond_filter,
-									)
-									.await
-							}
-						})
-						.finish()
-						.await
-						.context("HNSW KNN search failed")?
+					let (strategy, matching) = select_filter_strategy(
+						&ctx,
+						candidates.as_ref(),
+						ef as usize,
+						hnsw_index.indexed_count(&txn),
+					)
+					.await?;
+					if strategy == KnnFilterStrategy::BruteForce {
+						brute_force(
+							matching,
+							&index_def.cols[0],
+							&vector,
+							k as usize,
+							&hnsw_params.distance,
+						)
+					} else {
+						let ef = strategy.ef(ef as usize);
+						let cond_filter = match (residual_cond.clone(), ctx.options()) {
+							(Some(cond), Some(opt)) => Some((opt, Arc::new(cond))),
+							_ => None,
+						};
+
+						let mut stack = TreeStack::new();
+						stack
+							.enter(|stk| {
+								let hnsw_index = &hnsw_index;
+								let vector = &vector;
+								async move {
+									hnsw_index
+										.knn_search(
+											frozen_ctx,
+											stk,
+											vector,
+											k as usize,
+											ef,
+											cond_filter,
+										)
+										.await
+								}
+							})
+							.finish()
+							.await
+							.context("HNSW KNN search failed")?
+					}
 				}
 				#[cfg(diskann)]
 				Index::DiskAnn(diskann_params) => {
@@ -316,32 +362,50 @@ impl ExecOperator for KnnScan {
 
 					diskann_index.check_state().await.context("Failed to check DiskANN index state")?;
 
-					let cond_filter = match (residual_cond.clone(), ctx.options()) {
-						(Some(cond), Some(opt)) => Some((opt, Arc::new(cond))),
-						_ => None,
-					};
-
-					let mut stack = TreeStack::new();
-					stack
-						.enter(|stk| {
-							let diskann_index = &diskann_index;
-							let vector = &vector;
-							async move {
-								diskann_index
-									.knn_search(
-										frozen_ctx,
-										stk,
-										vector,
-										k as usize,
-										ef as usize,
-										cond_filter,
-									)
-									.await
-							}
-						})
-						.finish()
-						.await
-						.context("DiskANN KNN search failed")?
+					let (strategy, matching) = select_filter_strategy(
+						&ctx,
+						candidates.as_ref(),
+						ef as usize,
+						diskann_index.indexed_count(&txn),
+					)
+					.await?;
+					if strategy == KnnFilterStrategy::BruteForce {
+						brute_force(
+							matching,
+							&index_def.cols[0],
+							&vector,
+							k as usize,
+							&diskann_params.distance,
+						)
+					} else {
+						let ef = strategy.ef(ef as usize);
+						let cond_filter = match (residual_cond.clone(), ctx.options()) {
+							(Some(cond), Some(opt)) => Some((opt, Arc::new(cond))),
+							_ => None,
+						};
+
+						let mut stack = TreeStack::new();
+						stack
+							.enter(|stk| {
+								let diskann_index = &diskann_index;
+								let vector = &vector;
+								async move {
+									diskann_index
+										.knn_search(
+											frozen_ctx,
+											stk,
+											vector,
+											k as usize,
+											ef,
+											cond_filter,
+										)
+										.await
+								}
+							})
+							.finish()
+							.await
+							.context("DiskANN KNN search failed")?
+					}
 				}
 				#[cfg(not(diskann))]
 				Index::DiskAnn(_) => {
@@ -408,3 +472,73 @@ impl ExecOperator for KnnScan {
 		Ok(monitor_stream(Box::pin(stream), "KnnScan", &self.metrics))
 	}
 }
+
+/// Chooses how the ANN search is restricted by the residual condition.
+///
+/// Without a candidate source the graph is traversed with in-graph filtering.
+/// Otherwise the records matching the condition are read, keeping them while
+/// they are few enough for a brute-force search, and counted up to the
+/// selectivity sample. The number of indexed documents is only read then.
+async fn select_filter_strategy(
+	ctx: &ExecutionContext,
+	candidates: Option<&Arc<dyn ExecOperator>>,
+	ef: usize,
+	indexed: impl Future<Output = anyhow::Result<u64>>,
+) -> FlowResult<(KnnFilterStrategy, Vec<Value>)> {
+	let Some(candidates) = candidates else {
+		return Ok((KnnFilterStrategy::InGraph, Vec::new()));
+	};
+	let limit = *KNN_FILTER_BRUTE_FORCE_LIMIT;
+	let sample = (*KNN_FILTER_SELECTIVITY_SAMPLE).max(limit + 1);
+	let mut stream = candidates.execute(ctx)?;
+	let mut matching = Vec::new();
+	let mut count = 0;
+	let mut exhausted = true;
+	while let Some(batch) = stream.next().await {
+		if ctx.cancellation().is_cancelled() {
+			return Err(ControlFlow::Err(anyhow::anyhow!(Error::QueryCancelled)));
+		}
+		let batch = batch?;
+		count += batch.values.len();
+		if count <= limit {
+			matching.extend(batch.values);
+		} else {
+			// Too many records for a brute-force search: only count them
+			matching.clear();
+		}
+		if count >= sample {
+			exhausted = false;
+			break;
+		}
+	}
+	let indexed = indexed.await.context("Failed to count the indexed documents")?;
+	Ok((KnnFilterStrategy::select(ef, count, exhausted, indexed), matching))
+}
+
+/// Exact KNN search over the records matching the residual condition.
+fn brute_force(
+	matching: Vec<Value>,
+	field: &Idiom,
+	vector: &Vec<Number>,
+	k: usize,
+	distance: &Distance,
+) -> VecDeque<KnnIteratorResult> {
+	let mut nearest = NearestK::new(k);
+	for value in matching {
+		let Value::Object(obj) = &value else {
+			continue;
+		};
+		let Some(Value::RecordId(rid)) = obj.get("id") else {
+			continue;
+		};
+		let Some(v) = extract_vector(&value, field) else {
+			continue;
+		};
+		// Records whose vector does not match the index dimension are skipped,
+		// as they are not indexed either
+		if let Ok(dist) = distance.compute(&v, vector) {
+			nearest.push(dist, Arc::new(rid.clone()));
+		}
+	}
+	nearest.into_sorted_vec().into_iter().map(|(dist, rid)| (rid, dist.to_float(), None)).collect()
+}
@@ -0,0 +1,257 @@
+//! Spatial scan operator.
+//!
+//! This operator retrieves the candidate records of a geometry predicate
+//! using a geohash-cell spatial index.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use geo::Rect;
+
+use super::common::{DEFAULT_SCAN_BATCH_SIZE, fetch_and_filter_records_batch};
+use super::pipeline::{ScanPipeline, build_field_state};
+use super::resolved::ResolvedTableContext;
+use crate::catalog::Index;
+use crate::err::Error;
+use crate::exec::index::access_path::IndexRef;
+use crate::exec::permission::{
+	PhysicalPermission, convert_permission_to_physical_runtime, should_check_perms,
+	validate_record_user_access,
+};
+use crate::exec::{
+	AccessMode, ContextLevel, ExecOperator, ExecutionContext, FlowResult, OperatorMetrics,
+	PhysicalExpr, ValueBatch, ValueBatchStream, monitor_stream,
+};
+use crate::expr::{ControlFlow, ControlFlowExt};
+use crate::iam::Action;
+use crate::idx::IndexKeyBase;
+use crate::idx::spatial::SpatialIndex;
+use crate::kvs::CachePolicy;
+
+/// Spatial index scan operator.
+///
+/// Returns every record whose indexed geometry may overlap the query region.
+/// The result is a superset of the matching records, so the planner always
+/// keeps the original predicate as a filter above this operator.
+#[derive(Debug)]
+pub struct SpatialScan {
+	/// Reference to the index definition
+	pub index_ref: IndexRef,
+	/// The bounding rectangles of the query region
+	pub region: Vec<Rect<f64>>,
+	/// Whether records holding non-point geometries must be returned
+	pub non_points: bool,
+	/// Table name for record fetching
+	pub table_name: crate::val::TableName,
+	/// Optional VERSION timestamp for time-travel queries.
+	pub(crate) version: Option<Arc<dyn PhysicalExpr>>,
+	/// Plan-time resolved table context. When present, `execute()` skips
+	/// runtime table def + permission lookup.
+	pub(crate) resolved: Option<ResolvedTableContext>,
+	/// Projection-aware field set for computed-field materialization.
+	/// Outer `None` = sub-operator mode (parent handles fields).
+	/// `Some(None)` = all fields, `Some(Some(set))` = specific fields.
+	pub(crate) needed_fields: Option<Option<HashSet<String>>>,
+	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl SpatialScan {
+	pub(crate) fn new(
+		index_ref: IndexRef,
+		region: Vec<Rect<f64>>,
+		non_points: bool,
+		table_name: crate::val::TableName,
+		version: Option<Arc<dyn PhysicalExpr>>,
+		needed_fields: Option<Option<HashSet<String>>>,
+	) -> Self {
+		Self {
+			index_ref,
+			region,
+			non_points,
+			table_name,
+			version,
+			resolved: None,
+			needed_fields,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+
+	/// Set the plan-time resolved table context.
+	pub(crate) fn with_resolved(mut self, resolved: ResolvedTableContext) -> Self {
+		self.resolved = Some(resolved);
+		self
+	}
+}
+
+impl ExecOperator for SpatialScan {
+	fn name(&self) -> &'static str {
+		"SpatialScan"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		let region = self
+			.region
+			.iter()
+			.map(|r| format!("({}, {}, {}, {})", r.min().x, r.min().y, r.max().x, r.max().y))
+			.collect::<Vec<_>>()
+			.join(", ");
+		vec![("index".to_string(), self.index_ref.name.to_string()), ("region".to_string(), region)]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		ContextLevel::Database
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		AccessMode::ReadOnly
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let db_ctx = ctx.database()?.clone();
+
+		// Validate record user has access to this namespace/database
+		validate_record_user_access(&db_ctx)?;
+
+		// Check if we need to enforce permissions
+		let check_perms = should_check_perms(&db_ctx, Action::View)?;
+
+		// Clone for the async block
+		let index_ref = self.index_ref.clone();
+		let region = self.region.clone();
+		let non_points = self.non_points;
+		let table_name = self.table_name.clone();
+		let version_expr = self.version.clone();
+		let resolved = self.resolved.clone();
+		let needed_fields = self.needed_fields.clone();
+		let ctx = ctx.clone();
+
+		let stream = async_stream::try_stream! {
+			// Get namespace and database IDs
+			let db_ctx = ctx.database().context("SpatialScan requires database context")?;
+			let ns = Arc::clone(&db_ctx.ns_ctx.ns);
+			let db = Arc::clone(&db_ctx.db);
+			let txn = ctx.txn();
+
+			// Evaluate VERSION expression
+			let version: Option<u64> = match &version_expr {
+				Some(expr) => {
+					let eval_ctx = crate::exec::EvalContext::from_exec_ctx(&ctx);
+					let v = expr.evaluate(eval_ctx).await?;
+					Some(
+						v.cast_to::<crate::val::Datetime>()
+							.map_err(|e| anyhow::anyhow!("{e}"))?
+							.to_version_stamp(txn.timestamp_impl().as_ref())?,
+					)
+				}
+				None => ctx.version_stamp(),
+			};
+
+			// Resolve table permissions: plan-time fast path or runtime fallback
+			let select_permission = if let Some(ref res) = resolved {
+				res.select_permission(check_perms)
+			} else if check_perms {
+				let table_def = db_ctx
+					.get_table_def(&table_name, version)
+					.await
+					.context("Failed to get table")?;
+
+				if let Some(def) = &table_def {
+					convert_permission_to_physical_runtime(&def.permissions.select, ctx.ctx())
+						.await
+						.context("Failed to convert permission")?
+				} else {
+					Err(ControlFlow::Err(anyhow::Error::new(Error::TbNotFound {
+						name: table_name.clone(),
+					})))?
+				}
+			} else {
+				PhysicalPermission::Allow
+			};
+
+			// Early exit if denied
+			if matches!(select_permission, PhysicalPermission::Deny) {
+				return;
+			}
+
+			// Resolve field state for computed fields and field-level
+			// permissions. When needed_fields is None (sub-operator mode),
+			// the parent operator handles field processing.
+			let field_state = match &needed_fields {
+				Some(nf) => {
+					if let Some(ref res) = resolved {
+						res.field_state_for_projection(nf.as_ref())
+					} else {
+						build_field_state(
+							&ctx, &table_name, check_perms, nf.as_ref(),
+						).await?
+					}
+				}
+				None => super::pipeline::FieldState::empty(),
+			};
+
+			// Table-level permissions are handled by fetch_and_filter_records_batch.
+			// The pipeline handles computed fields and field-level permissions.
+			let mut pipeline = ScanPipeline::new(
+				PhysicalPermission::Allow,
+				None,
+				field_state,
+				check_perms,
+				None,
+				0,
+			);
+
+			// Get the Spatial index parameters from the index definition
+			let index_def = index_ref.definition();
+			let params = match &index_def.index {
+				Index::Spatial(params) => params,
+				_ => {
+					Err(ControlFlow::Err(anyhow::anyhow!(
+						"Index '{}' is not a spatial index",
+						index_def.name
+					)))?
+				}
+			};
+
+			// Create the index key base
+			let ikb = IndexKeyBase::new(ns.namespace_id, db.database_id, table_name.clone(), index_def.index_id);
+
+			// Stream the candidate records from the index, one batch of keys
+			// at a time
+			let si = SpatialIndex::new(ikb, params);
+			let mut search = si
+				.search(&region, non_points)
+				.context("Failed to search spatial index")?;
+			while let Some(rids) = search
+				.next_batch(txn.as_ref(), version)
+				.await
+				.context("Failed to search spatial index")?
+			{
+				// Fetch the records in batches
+				for rid_batch in rids.chunks(DEFAULT_SCAN_BATCH_SIZE) {
+					let mut values = fetch_and_filter_records_batch(
+						&ctx,
+						&txn,
+						ns.namespace_id,
+						db.database_id,
+						rid_batch,
+						&select_permission,
+						check_perms,
+						version,
+						CachePolicy::ReadOnly,
+					).await?;
+					pipeline.process_batch(&mut values, &ctx).await?;
+					if !values.is_empty() {
+						yield ValueBatch { values };
+					}
+				}
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(stream), "SpatialScan", &self.metrics))
+	}
+}
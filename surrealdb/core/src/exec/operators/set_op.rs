@@ -0,0 +1,320 @@
+//! `Except` and `Intersect` — the row-set difference and intersection of GQL
+//! composite queries (`EXCEPT [ALL|DISTINCT]`, `INTERSECT [ALL|DISTINCT]`).
+//!
+//! Both operators fully drain the right input into a counted multiset of its
+//! rows, then stream the left input, deciding each left row against the
+//! multiset (ISO/IEC 39075 §14.2):
+//!
+//! - `EXCEPT ALL` drops a left row while an equal right row is left to cancel it
+//!   (each right row cancels one), so a row appearing `m` times on the left and
+//!   `n` times on the right appears `max(m - n, 0)` times;
+//! - `INTERSECT ALL` keeps a left row while an equal right row is left to match
+//!   it, giving `min(m, n)` copies;
+//! - the `DISTINCT` forms keep the first occurrence of each left row that is
+//!   absent (`EXCEPT`) or present (`INTERSECT`) on the right.
+//!
+//! Surviving rows keep the left input's order. The multiset uses the same
+//! hash-keyed buckets as `Distinct`'s seen set (`distinct.rs`), and its size is
+//! bounded by `SURREAL_GQL_MAX_JOIN_BUILD_ROWS` (the shared GQL
+//! in-memory-build budget), counted in distinct rows.
+
+// The composite-query operators are constructed only by the gql-gated planner
+// (`Expr::Match` is `#[cfg(feature = "gql")]`), so they are dead code when the
+// feature is off — suppress the lint there only.
+#![cfg_attr(not(feature = "gql"), allow(dead_code))]
+
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use crate::exec::{
+	AccessMode, CombineAccessModes, ContextLevel, ExecOperator, ExecutionContext, FlowResult,
+	OperatorMetrics, OutputOrdering, ValueBatch, ValueBatchStream, buffer_stream, monitor_stream,
+};
+use crate::expr::ControlFlow;
+use crate::val::Value;
+
+/// Which set operation a [`SetOp`] computes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOpKind {
+	/// The left rows not matched by a right row.
+	Except,
+	/// The left rows matched by a right row.
+	Intersect,
+}
+
+/// Combines two row streams by set difference or intersection, with `ALL`
+/// (multiset) or `DISTINCT` (set) semantics.
+#[derive(Debug, Clone)]
+pub struct SetOp {
+	pub(crate) left: Arc<dyn ExecOperator>,
+	pub(crate) right: Arc<dyn ExecOperator>,
+	pub(crate) kind: SetOpKind,
+	pub(crate) distinct: bool,
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl SetOp {
+	/// Create a new set operation over `left` and `right`.
+	pub(crate) fn new(
+		left: Arc<dyn ExecOperator>,
+		right: Arc<dyn ExecOperator>,
+		kind: SetOpKind,
+		distinct: bool,
+	) -> Self {
+		Self {
+			left,
+			right,
+			kind,
+			distinct,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+impl ExecOperator for SetOp {
+	fn name(&self) -> &'static str {
+		match self.kind {
+			SetOpKind::Except => "Except",
+			SetOpKind::Intersect => "Intersect",
+		}
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		let quantifier = if self.distinct {
+			"DISTINCT"
+		} else {
+			"ALL"
+		};
+		vec![("quantifier".to_string(), quantifier.to_string())]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		self.left.required_context().max(self.right.required_context())
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		[&self.left, &self.right].iter().map(|input| input.access_mode()).combine_all()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.left, &self.right]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn output_ordering(&self) -> OutputOrdering {
+		// Surviving rows keep the left input's order.
+		self.left.output_ordering()
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let right_stream = buffer_stream(
+			self.right.execute(ctx)?,
+			self.right.access_mode(),
+			self.right.cardinality_hint(),
+			ctx.root().ctx.config.operator_buffer_size,
+		);
+		let left_stream = buffer_stream(
+			self.left.execute(ctx)?,
+			self.left.access_mode(),
+			self.left.cardinality_hint(),
+			ctx.root().ctx.config.operator_buffer_size,
+		);
+		let max_rows = ctx.root().ctx.config.gql_max_join_build_rows;
+		let kind = self.kind;
+		let distinct = self.distinct;
+		let ctx = ctx.clone();
+
+		let combined = async_stream::try_stream! {
+			// Build: the right input's rows, counted.
+			let mut right = RowCounts::new();
+			futures::pin_mut!(right_stream);
+			while let Some(batch_result) = right_stream.next().await {
+				crate::exec::operators::check_cancelled(&ctx)?;
+				for value in batch_result?.values {
+					right.add(value, max_rows)?;
+				}
+			}
+
+			// Probe: stream the left input against the multiset.
+			let mut emitted = RowCounts::new();
+			futures::pin_mut!(left_stream);
+			while let Some(batch_result) = left_stream.next().await {
+				crate::exec::operators::check_cancelled(&ctx)?;
+				let mut values = Vec::new();
+				for value in batch_result?.values {
+					let keep = if distinct {
+						let matched = right.contains(&value);
+						let wanted = match kind {
+							SetOpKind::Except => !matched,
+							SetOpKind::Intersect => matched,
+						};
+						wanted && emitted.add(value.clone(), max_rows)?
+					} else {
+						let matched = right.take(&value);
+						match kind {
+							SetOpKind::Except => !matched,
+							SetOpKind::Intersect => matched,
+						}
+					};
+					if keep {
+						values.push(value);
+					}
+				}
+				if !values.is_empty() {
+					yield ValueBatch { values };
+				}
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(combined), self.name(), &self.metrics))
+	}
+}
+
+/// A counted multiset of rows: hash-keyed buckets of `(row, count)` pairs,
+/// probed by `PartialEq` (the `Distinct` seen-set layout). Only distinct rows
+/// are stored, so the budget counts distinct rows.
+struct RowCounts {
+	buckets: HashMap<u64, Vec<(Value, usize)>>,
+	len: usize,
+}
+
+impl RowCounts {
+	fn new() -> Self {
+		Self {
+			buckets: HashMap::new(),
+			len: 0,
+		}
+	}
+
+	/// Count one more occurrence of `value`. Returns `Ok(true)` when it is the
+	/// first occurrence. Fails when a new distinct row would exceed `max_rows`.
+	fn add(&mut self, value: Value, max_rows: usize) -> Result<bool, ControlFlow> {
+		let bucket = self.buckets.entry(hash_value(&value)).or_default();
+		if let Some((_, count)) = bucket.iter_mut().find(|(seen, _)| *seen == value) {
+			*count += 1;
+			return Ok(false);
+		}
+		if self.len >= max_rows {
+			return Err(ControlFlow::Err(anyhow::anyhow!(crate::err::Error::InvalidStatement(
+				format!(
+					"GQL composite query exceeded the maximum of {max_rows} distinct rows \
+					 (configurable via SURREAL_GQL_MAX_JOIN_BUILD_ROWS)"
+				),
+			))));
+		}
+		bucket.push((value, 1));
+		self.len += 1;
+		Ok(true)
+	}
+
+	/// Whether at least one occurrence of `value` was counted.
+	fn contains(&self, value: &Value) -> bool {
+		self.buckets
+			.get(&hash_value(value))
+			.is_some_and(|bucket| bucket.iter().any(|(seen, count)| seen == value && *count > 0))
+	}
+
+	/// Consume one occurrence of `value`, returning whether one was left.
+	fn take(&mut self, value: &Value) -> bool {
+		let Some(bucket) = self.buckets.get_mut(&hash_value(value)) else {
+			return false;
+		};
+		match bucket.iter_mut().find(|(seen, count)| seen == value && *count > 0) {
+			Some((_, count)) => {
+				*count -= 1;
+				true
+			}
+			None => false,
+		}
+	}
+}
+
+/// Hash a single [`Value`] into a `u64` for bucket lookup, matching
+/// `distinct.rs`.
+fn hash_value(value: &Value) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	value.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::exec::operators::test_util::{ValuesOperator, collect, root_ctx};
+
+	fn rows(ns: &[i64]) -> Vec<Value> {
+		ns.iter().map(|n| Value::from(*n)).collect()
+	}
+
+	async fn run(left: &[i64], right: &[i64], kind: SetOpKind, distinct: bool) -> Vec<Value> {
+		let op: Arc<dyn ExecOperator> = Arc::new(SetOp::new(
+			ValuesOperator::new(rows(left)),
+			ValuesOperator::new(rows(right)),
+			kind,
+			distinct,
+		));
+		collect(&op, &root_ctx()).await
+	}
+
+	#[tokio::test]
+	async fn except_all_cancels_one_left_row_per_right_row() {
+		// 1 appears three times on the left and once on the right: two survive.
+		let out = run(&[1, 2, 1, 3, 1], &[1, 3, 4], SetOpKind::Except, false).await;
+		assert_eq!(out, rows(&[2, 1, 1]));
+	}
+
+	#[tokio::test]
+	async fn except_distinct_keeps_first_occurrences_absent_on_the_right() {
+		let out = run(&[2, 1, 2, 3, 1], &[1], SetOpKind::Except, true).await;
+		assert_eq!(out, rows(&[2, 3]));
+	}
+
+	#[tokio::test]
+	async fn intersect_all_keeps_the_smaller_multiplicity() {
+		let out = run(&[1, 2, 1, 1, 3], &[1, 1, 3, 3], SetOpKind::Intersect, false).await;
+		assert_eq!(out, rows(&[1, 1, 3]));
+	}
+
+	#[tokio::test]
+	async fn intersect_distinct_keeps_one_copy_of_shared_rows() {
+		let out = run(&[3, 1, 3, 2], &[3, 3, 2, 5], SetOpKind::Intersect, true).await;
+		assert_eq!(out, rows(&[3, 2]));
+	}
+
+	#[tokio::test]
+	async fn empty_right_side() {
+		assert_eq!(run(&[1, 1], &[], SetOpKind::Except, false).await, rows(&[1, 1]));
+		assert!(run(&[1, 1], &[], SetOpKind::Intersect, false).await.is_empty());
+	}
+
+	#[test]
+	fn row_counts_guard_names_the_knob() {
+		let mut counts = RowCounts::new();
+		assert!(counts.add(Value::from(1), 1).unwrap());
+		// A repeat only bumps the count; it is not a new distinct row.
+		assert!(!counts.add(Value::from(1), 1).unwrap());
+		let msg = match counts.add(Value::from(2), 1).unwrap_err() {
+			ControlFlow::Err(e) => e.to_string(),
+			other => panic!("expected error, got {other:?}"),
+		};
+		assert!(msg.contains("SURREAL_GQL_MAX_JOIN_BUILD_ROWS"), "{msg}");
+	}
+
+	#[test]
+	fn set_op_reports_name() {
+		let op = SetOp::new(
+			ValuesOperator::new(Vec::new()),
+			ValuesOperator::new(Vec::new()),
+			SetOpKind::Intersect,
+			true,
+		);
+		assert_eq!(op.name(), "Intersect");
+	}
+}
@@ -25,7 +25,8 @@ use crate::exec::{
 /// guarantees.
 ///
 /// This is used for `SELECT * FROM a, b, c` which fetches from a, b, and c
-/// in sequence, returning results in order a → b → c.
+/// in sequence, returning results in order a → b → c, and for the operands of
+/// a GQL `UNION` (topped by a `Distinct` unless `UNION ALL`).
 #[derive(Debug, Clone)]
 pub struct Union {
 	pub(crate) inputs: Vec<Arc<dyn ExecOperator>>,
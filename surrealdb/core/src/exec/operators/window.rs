@@ -0,0 +1,713 @@
+//! Window operator for `<function> OVER (...)` fields.
+//!
+//! Computes window functions (ranking, offset and framed aggregates) over
+//! partitions of the input. Every input row is kept: each window result is
+//! added to its row as a new field, and the rows are emitted in their input
+//! order. This is a pipeline-breaking operator: the entire input stream must
+//! be consumed before any output is produced.
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use futures::StreamExt;
+
+use super::sort::{OrderByField, compare_keys, compare_values};
+use crate::err::Error;
+use crate::exec::function::{Accumulator, AggregateFunction};
+use crate::exec::{
+	AccessMode, CardinalityHint, CombineAccessModes, ContextLevel, EvalContext, ExecOperator,
+	ExecutionContext, FlowResult, FlowResultExt as _, OperatorMetrics, PhysicalExpr, ValueBatch,
+	ValueBatchStream, buffer_stream, monitor_stream,
+};
+use crate::expr::{ControlFlow, WindowBound, WindowFrame};
+use crate::val::{Strand, Value};
+
+/// Computes window function fields over the input rows.
+#[derive(Debug, Clone)]
+pub struct Window {
+	pub(crate) input: Arc<dyn ExecOperator>,
+	/// The window fields to compute, in SELECT field order.
+	pub(crate) fields: Vec<WindowField>,
+	/// Per-operator runtime metrics for EXPLAIN ANALYZE.
+	pub(crate) metrics: Arc<OperatorMetrics>,
+}
+
+impl Window {
+	/// Create a new Window operator with fresh metrics.
+	pub(crate) fn new(input: Arc<dyn ExecOperator>, fields: Vec<WindowField>) -> Self {
+		Self {
+			input,
+			fields,
+			metrics: Arc::new(OperatorMetrics::new()),
+		}
+	}
+}
+
+/// A single window function call, written to each row as `name`.
+#[derive(Debug, Clone)]
+pub struct WindowField {
+	/// The synthetic field name the result is stored under (e.g. `_w0`).
+	pub name: Strand,
+	/// The original `<function> OVER (...)` text, for EXPLAIN output.
+	pub display: String,
+	/// The `PARTITION BY` expressions.
+	pub partition: Vec<Arc<dyn PhysicalExpr>>,
+	/// The `ORDER BY` fields, ordering rows within a partition.
+	pub order: Vec<OrderByField>,
+	/// The resolved row frame. Defaults to the whole partition without an
+	/// `ORDER BY`, and to `UNBOUNDED PRECEDING AND CURRENT ROW` with one.
+	pub frame: WindowFrame,
+	/// Whether the frame ends at the last peer of the current row rather
+	/// than the row itself. Set for the default frame with an `ORDER BY`,
+	/// which is `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`.
+	pub peers: bool,
+	/// The function computed over the window.
+	pub kind: WindowFunctionKind,
+}
+
+/// The function computed by a [`WindowField`].
+#[derive(Clone)]
+pub enum WindowFunctionKind {
+	/// `window::row_number()`: the 1-based position within the partition.
+	RowNumber,
+	/// `window::rank()`: the position of the first peer, leaving gaps.
+	Rank,
+	/// `window::dense_rank()`: the number of distinct peer groups so far.
+	DenseRank,
+	/// `window::lag(value, offset, default)` and
+	/// `window::lead(value, offset, default)`.
+	Offset {
+		/// The evaluated value of the row at the offset.
+		argument: Arc<dyn PhysicalExpr>,
+		/// The number of rows to look back (lag) or ahead (lead), evaluated
+		/// once per query.
+		offset: Option<Arc<dyn PhysicalExpr>>,
+		/// The value returned when the offset row does not exist, evaluated
+		/// once per query.
+		default: Option<Arc<dyn PhysicalExpr>>,
+		/// `true` for `window::lead`.
+		forward: bool,
+	},
+	/// `window::first_value(value)` and `window::last_value(value)` over the
+	/// frame.
+	Value {
+		argument: Arc<dyn PhysicalExpr>,
+		/// `true` for `window::last_value`.
+		last: bool,
+	},
+	/// A registered aggregate function evaluated over the frame, e.g.
+	/// `math::sum(amount) OVER (...)`.
+	Aggregate {
+		function: Arc<dyn AggregateFunction>,
+		/// The per-row value fed to the accumulator.
+		argument: Arc<dyn PhysicalExpr>,
+		/// Additional arguments, evaluated once per query.
+		extra_args: Vec<Arc<dyn PhysicalExpr>>,
+	},
+}
+
+impl std::fmt::Debug for WindowFunctionKind {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::RowNumber => f.write_str("RowNumber"),
+			Self::Rank => f.write_str("Rank"),
+			Self::DenseRank => f.write_str("DenseRank"),
+			Self::Offset {
+				forward,
+				..
+			} => f.debug_struct("Offset").field("forward", forward).finish(),
+			Self::Value {
+				last,
+				..
+			} => f.debug_struct("Value").field("last", last).finish(),
+			Self::Aggregate {
+				function,
+				..
+			} => f.debug_struct("Aggregate").field("function", &function.name()).finish(),
+		}
+	}
+}
+
+impl WindowField {
+	/// All physical expressions referenced by this field, with a role label.
+	fn expressions(&self) -> Vec<(&'static str, &Arc<dyn PhysicalExpr>)> {
+		let mut exprs = Vec::new();
+		for expr in &self.partition {
+			exprs.push(("partition_by", expr));
+		}
+		for field in &self.order {
+			exprs.push(("order_by", &field.expr));
+		}
+		match &self.kind {
+			WindowFunctionKind::RowNumber
+			| WindowFunctionKind::Rank
+			| WindowFunctionKind::DenseRank => {}
+			WindowFunctionKind::Offset {
+				argument,
+				offset,
+				default,
+				..
+			} => {
+				exprs.push(("window_arg", argument));
+				if let Some(offset) = offset {
+					exprs.push(("window_offset", offset));
+				}
+				if let Some(default) = default {
+					exprs.push(("window_default", default));
+				}
+			}
+			WindowFunctionKind::Value {
+				argument,
+				..
+			} => exprs.push(("window_arg", argument)),
+			WindowFunctionKind::Aggregate {
+				argument,
+				extra_args,
+				..
+			} => {
+				exprs.push(("window_arg", argument));
+				for extra in extra_args {
+					exprs.push(("window_extra", extra));
+				}
+			}
+		}
+		exprs
+	}
+}
+
+impl ExecOperator for Window {
+	fn name(&self) -> &'static str {
+		"Window"
+	}
+
+	fn attrs(&self) -> Vec<(String, String)> {
+		let fields_str = self
+			.fields
+			.iter()
+			.map(|f| format!("{} = {}", f.name, f.display))
+			.collect::<Vec<_>>()
+			.join(", ");
+		vec![("fields".to_string(), fields_str)]
+	}
+
+	fn required_context(&self) -> ContextLevel {
+		let expr_ctx = self
+			.fields
+			.iter()
+			.flat_map(|f| f.expressions())
+			.map(|(_, e)| e.required_context())
+			.max()
+			.unwrap_or(ContextLevel::Root);
+		self.input.required_context().max(expr_ctx)
+	}
+
+	fn access_mode(&self) -> AccessMode {
+		let expr_mode = self
+			.fields
+			.iter()
+			.flat_map(|f| f.expressions())
+			.map(|(_, e)| e.access_mode())
+			.combine_all();
+		self.input.access_mode().combine(expr_mode)
+	}
+
+	fn cardinality_hint(&self) -> CardinalityHint {
+		self.input.cardinality_hint()
+	}
+
+	fn children(&self) -> Vec<&Arc<dyn ExecOperator>> {
+		vec![&self.input]
+	}
+
+	fn metrics(&self) -> Option<&OperatorMetrics> {
+		Some(&self.metrics)
+	}
+
+	fn expressions(&self) -> Vec<(&str, &Arc<dyn PhysicalExpr>)> {
+		self.fields.iter().flat_map(|f| f.expressions()).collect()
+	}
+
+	fn output_ordering(&self) -> crate::exec::OutputOrdering {
+		// Rows are emitted in their input order.
+		self.input.output_ordering()
+	}
+
+	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
+		let input_stream = buffer_stream(
+			self.input.execute(ctx)?,
+			self.input.access_mode(),
+			self.input.cardinality_hint(),
+			ctx.root().ctx.config.operator_buffer_size,
+		);
+		let fields = self.fields.clone();
+		let ctx = ctx.clone();
+
+		let window_stream = async_stream::try_stream! {
+			let mut rows: Vec<Value> = Vec::new();
+			futures::pin_mut!(input_stream);
+			while let Some(batch_result) = input_stream.next().await {
+				super::check_cancelled(&ctx)?;
+				rows.extend(batch_result?.values);
+			}
+
+			if !rows.is_empty() {
+				let eval_ctx = EvalContext::from_exec_ctx(&ctx);
+				let mut results = Vec::with_capacity(fields.len());
+				for field in &fields {
+					super::check_cancelled(&ctx)?;
+					results.push(compute_window_field(field, &rows, eval_ctx.clone()).await?);
+				}
+
+				for (field, column) in fields.iter().zip(results) {
+					for (row, value) in rows.iter_mut().zip(column) {
+						if let Value::Object(obj) = row {
+							obj.insert(field.name.clone(), value);
+						}
+					}
+				}
+
+				yield ValueBatch {
+					values: rows,
+				};
+			}
+		};
+
+		Ok(monitor_stream(Box::pin(window_stream), "Window", &self.metrics))
+	}
+}
+
+/// Evaluate an expression across all rows, falling back to per-row
+/// evaluation (with ignorable errors mapped to `NONE`) if the batch fails.
+async fn evaluate_column(
+	expr: &Arc<dyn PhysicalExpr>,
+	rows: &[Value],
+	eval_ctx: EvalContext<'_>,
+) -> FlowResult<Vec<Value>> {
+	match expr.evaluate_batch(eval_ctx.clone(), rows).await {
+		Ok(values) => Ok(values),
+		Err(_) => {
+			let mut values = Vec::with_capacity(rows.len());
+			for row in rows {
+				values.push(expr.evaluate(eval_ctx.with_value(row)).await.or_none()?);
+			}
+			Ok(values)
+		}
+	}
+}
+
+/// Evaluate a once-per-query argument such as a `lag` offset.
+async fn evaluate_once(
+	expr: Option<&Arc<dyn PhysicalExpr>>,
+	eval_ctx: EvalContext<'_>,
+) -> FlowResult<Option<Value>> {
+	match expr {
+		Some(expr) => Ok(Some(expr.evaluate(eval_ctx).await.or_none()?)),
+		None => Ok(None),
+	}
+}
+
+/// Compute one window field for every row, returning the results in input
+/// row order.
+async fn compute_window_field(
+	field: &WindowField,
+	rows: &[Value],
+	eval_ctx: EvalContext<'_>,
+) -> FlowResult<Vec<Value>> {
+	// Evaluate the partition and order keys for every row.
+	let mut partition_columns = Vec::with_capacity(field.partition.len());
+	for expr in &field.partition {
+		partition_columns.push(evaluate_column(expr, rows, eval_ctx.clone()).await?);
+	}
+	let mut order_columns = Vec::with_capacity(field.order.len());
+	for order in &field.order {
+		order_columns.push(evaluate_column(&order.expr, rows, eval_ctx.clone()).await?);
+	}
+	let partition_keys: Vec<Vec<Value>> =
+		(0..rows.len()).map(|i| partition_columns.iter().map(|c| c[i].clone()).collect()).collect();
+	let order_keys: Vec<Vec<Value>> =
+		(0..rows.len()).map(|i| order_columns.iter().map(|c| c[i].clone()).collect()).collect();
+
+	// Evaluate the function arguments.
+	let argument = match &field.kind {
+		WindowFunctionKind::Offset {
+			argument,
+			..
+		}
+		| WindowFunctionKind::Value {
+			argument,
+			..
+		}
+		| WindowFunctionKind::Aggregate {
+			argument,
+			..
+		} => evaluate_column(argument, rows, eval_ctx.clone()).await?,
+		_ => Vec::new(),
+	};
+
+	// Order row indices by partition, then by the window ORDER BY. The sort is
+	// stable, so rows which compare equal keep their input order.
+	let mut indices: Vec<usize> = (0..rows.len()).collect();
+	indices.sort_by(|&a, &b| {
+		compare_partition(&partition_keys[a], &partition_keys[b])
+			.then_with(|| compare_keys(&order_keys[a], &order_keys[b], &field.order))
+	});
+
+	let mut output = vec![Value::None; rows.len()];
+	let mut start = 0;
+	while start < indices.len() {
+		let mut end = start + 1;
+		while end < indices.len()
+			&& compare_partition(&partition_keys[indices[start]], &partition_keys[indices[end]])
+				== Ordering::Equal
+		{
+			end += 1;
+		}
+		let partition = &indices[start..end];
+		let values =
+			compute_partition(field, partition, &order_keys, &argument, eval_ctx.clone()).await?;
+		for (&row, value) in partition.iter().zip(values) {
+			output[row] = value;
+		}
+		start = end;
+	}
+	Ok(output)
+}
+
+/// Partitions are grouped by plain value comparison.
+fn compare_partition(a: &[Value], b: &[Value]) -> Ordering {
+	for (a, b) in a.iter().zip(b) {
+		let ordering = compare_values(a, b, false, false);
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+	}
+	Ordering::Equal
+}
+
+/// Compute the window function for one ordered partition, returning a value
+/// per partition row.
+async fn compute_partition(
+	field: &WindowField,
+	partition: &[usize],
+	order_keys: &[Vec<Value>],
+	argument: &[Value],
+	eval_ctx: EvalContext<'_>,
+) -> FlowResult<Vec<Value>> {
+	let len = partition.len();
+	let is_peer = |a: usize, b: usize| {
+		compare_keys(&order_keys[partition[a]], &order_keys[partition[b]], &field.order)
+			== Ordering::Equal
+	};
+
+	let values = match &field.kind {
+		WindowFunctionKind::RowNumber => (0..len).map(|i| Value::from(i as i64 + 1)).collect(),
+		WindowFunctionKind::Rank => {
+			let mut rank = 1;
+			(0..len)
+				.map(|i| {
+					if i > 0 && !is_peer(i - 1, i) {
+						rank = i + 1;
+					}
+					Value::from(rank as i64)
+				})
+				.collect()
+		}
+		WindowFunctionKind::DenseRank => {
+			let mut rank = 1;
+			(0..len)
+				.map(|i| {
+					if i > 0 && !is_peer(i - 1, i) {
+						rank += 1;
+					}
+					Value::from(rank as i64)
+				})
+				.collect()
+		}
+		WindowFunctionKind::Offset {
+			offset,
+			default,
+			forward,
+			..
+		} => {
+			let name = if *forward {
+				"window::lead"
+			} else {
+				"window::lag"
+			};
+			let offset = match evaluate_once(offset.as_ref(), eval_ctx.clone()).await? {
+				None => 1,
+				Some(Value::Number(n)) => match n.as_int_lossless() {
+					Some(n) if n >= 0 => n as usize,
+					_ => return Err(invalid_offset(name)),
+				},
+				Some(_) => return Err(invalid_offset(name)),
+			};
+			let default = evaluate_once(default.as_ref(), eval_ctx).await?.unwrap_or(Value::None);
+			(0..len)
+				.map(|i| {
+					let target = if *forward {
+						i.checked_add(offset).filter(|&t| t < len)
+					} else {
+						i.checked_sub(offset)
+					};
+					match target {
+						Some(t) => argument[partition[t]].clone(),
+						None => default.clone(),
+					}
+				})
+				.collect()
+		}
+		WindowFunctionKind::Value {
+			last,
+			..
+		} => frame_ranges(field, len, is_peer)
+			.into_iter()
+			.map(|range| match range {
+				Some((start, end)) => {
+					let t = if *last {
+						end
+					} else {
+						start
+					};
+					argument[partition[t]].clone()
+				}
+				None => Value::None,
+			})
+			.collect(),
+		WindowFunctionKind::Aggregate {
+			function,
+			extra_args,
+			..
+		} => {
+			let mut args = Vec::with_capacity(extra_args.len());
+			for extra in extra_args {
+				args.push(extra.evaluate(eval_ctx.clone()).await.or_none()?);
+			}
+			let ranges = frame_ranges(field, len, is_peer);
+			aggregate_frames(function.as_ref(), &args, &field.frame, &ranges, partition, argument)
+				.map_err(ControlFlow::Err)?
+		}
+	};
+	Ok(values)
+}
+
+/// Resolve the frame of every row of a partition of `len` rows to an
+/// inclusive row range, extending it to the last peer of the row when the
+/// frame includes peers.
+fn frame_ranges(
+	field: &WindowField,
+	len: usize,
+	is_peer: impl Fn(usize, usize) -> bool,
+) -> Vec<Option<(usize, usize)>> {
+	let mut ranges: Vec<_> = (0..len).map(|i| field.frame.range(i, len)).collect();
+	if field.peers {
+		// The last peer of each row, found walking the partition backwards
+		let mut last = len.saturating_sub(1);
+		for i in (0..len).rev() {
+			if i + 1 < len && !is_peer(i, i + 1) {
+				last = i;
+			}
+			if let Some((_, end)) = &mut ranges[i] {
+				*end = last;
+			}
+		}
+	}
+	ranges
+}
+
+/// Evaluate an aggregate over the frame of every partition row.
+///
+/// Frames anchored at `UNBOUNDED PRECEDING` only ever grow, so a single
+/// accumulator is fed incrementally. Frames which run to `UNBOUNDED FOLLOWING`
+/// only ever shrink, so the rows are walked backwards, and the accumulator of
+/// the rows entering the frame is merged with the accumulator of the rest of
+/// the frame. Any other frame is accumulated afresh for each row.
+fn aggregate_frames(
+	function: &dyn AggregateFunction,
+	args: &[Value],
+	frame: &WindowFrame,
+	ranges: &[Option<(usize, usize)>],
+	partition: &[usize],
+	argument: &[Value],
+) -> anyhow::Result<Vec<Value>> {
+	let mut values = Vec::with_capacity(ranges.len());
+	if frame.start == WindowBound::UnboundedPreceding {
+		let mut acc: Box<dyn Accumulator> = function.create_accumulator_with_args(args);
+		let mut fed = 0;
+		for range in ranges {
+			match *range {
+				Some((_, end)) => {
+					while fed <= end {
+						acc.update(argument[partition[fed]].clone())?;
+						fed += 1;
+					}
+					values.push(acc.finalize()?);
+				}
+				None => values.push(function.create_accumulator_with_args(args).finalize()?),
+			}
+		}
+	} else if frame.end == WindowBound::UnboundedFollowing {
+		let mut acc: Box<dyn Accumulator> = function.create_accumulator_with_args(args);
+		// The first row which has been fed to the accumulator
+		let mut fed = partition.len();
+		for range in ranges.iter().rev() {
+			match *range {
+				Some((start, _)) => {
+					if start < fed {
+						// Keep the rows in order, with the new rows in front
+						let mut front = function.create_accumulator_with_args(args);
+						for &row in &partition[start..fed] {
+							front.update(argument[row].clone())?;
+						}
+						front.merge(acc)?;
+						acc = front;
+						fed = start;
+					}
+					values.push(acc.finalize()?);
+				}
+				None => values.push(function.create_accumulator_with_args(args).finalize()?),
+			}
+		}
+		values.reverse();
+	} else {
+		for range in ranges {
+			let mut acc = function.create_accumulator_with_args(args);
+			if let Some((start, end)) = *range {
+				for &row in &partition[start..=end] {
+					acc.update(argument[row].clone())?;
+				}
+			}
+			values.push(acc.finalize()?);
+		}
+	}
+	Ok(values)
+}
+
+fn invalid_offset(name: &str) -> ControlFlow {
+	ControlFlow::Err(anyhow::Error::new(Error::InvalidFunctionArguments {
+		name: name.to_string(),
+		message: "The offset must be a non-negative integer.".to_string(),
+	}))
+}
+
+/// The frame used when an `OVER` clause does not specify one.
+pub(crate) fn default_frame(ordered: bool) -> WindowFrame {
+	WindowFrame {
+		start: WindowBound::UnboundedPreceding,
+		end: if ordered {
+			WindowBound::CurrentRow
+		} else {
+			WindowBound::UnboundedFollowing
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn frame(start: WindowBound, end: WindowBound) -> WindowFrame {
+		WindowFrame {
+			start,
+			end,
+		}
+	}
+
+	#[test]
+	fn test_frame_range_running() {
+		let f = default_frame(true);
+		assert_eq!(f.range(0, 4), Some((0, 0)));
+		assert_eq!(f.range(3, 4), Some((0, 3)));
+	}
+
+	#[test]
+	fn test_frame_range_whole_partition() {
+		let f = default_frame(false);
+		assert_eq!(f.range(0, 4), Some((0, 3)));
+		assert_eq!(f.range(2, 4), Some((0, 3)));
+	}
+
+	#[test]
+	fn test_frame_range_sliding() {
+		let f = frame(WindowBound::Preceding(1), WindowBound::Following(1));
+		assert_eq!(f.range(0, 3), Some((0, 1)));
+		assert_eq!(f.range(1, 3), Some((0, 2)));
+		assert_eq!(f.range(2, 3), Some((1, 2)));
+	}
+
+	#[test]
+	fn test_frame_range_empty() {
+		let f = frame(WindowBound::Preceding(3), WindowBound::Preceding(2));
+		assert_eq!(f.range(0, 5), None);
+		assert_eq!(f.range(1, 5), None);
+		assert_eq!(f.range(3, 5), Some((0, 1)));
+		let f = frame(WindowBound::Following(1), WindowBound::UnboundedFollowing);
+		assert_eq!(f.range(4, 5), None);
+	}
+
+	#[test]
+	fn test_aggregate_frames_running_sum() {
+		let registry = crate::exec::function::FunctionRegistry::with_builtins();
+		let sum = registry.get_aggregate("math::sum").expect("math::sum is registered");
+		let argument: Vec<Value> = [1, 2, 3, 4].into_iter().map(Value::from).collect();
+		let partition = [0, 1, 2, 3];
+		let ranges = |f: WindowFrame| (0..4).map(|i| f.range(i, 4)).collect::<Vec<_>>();
+
+		let aggregate = |f: WindowFrame| {
+			aggregate_frames(sum.as_ref(), &[], &f, &ranges(f), &partition, &argument).unwrap()
+		};
+
+		let running = aggregate(default_frame(true));
+		assert_eq!(running, [1, 3, 6, 10].map(Value::from).to_vec());
+
+		let sliding = aggregate(frame(WindowBound::Preceding(1), WindowBound::CurrentRow));
+		assert_eq!(sliding, [1, 3, 5, 7].map(Value::from).to_vec());
+
+		let remaining = aggregate(frame(WindowBound::CurrentRow, WindowBound::UnboundedFollowing));
+		assert_eq!(remaining, [10, 9, 7, 4].map(Value::from).to_vec());
+
+		let ahead = aggregate(frame(WindowBound::Following(1), WindowBound::UnboundedFollowing));
+		assert_eq!(ahead, [9, 7, 4, 0].map(Value::from).to_vec());
+	}
+
+	#[test]
+	fn test_aggregate_frames_shrinking_keeps_order() {
+		let registry = crate::exec::function::FunctionRegistry::with_builtins();
+		let group = registry.get_aggregate("array::group").expect("array::group is registered");
+		let argument: Vec<Value> = [1, 2, 3].into_iter().map(Value::from).collect();
+		let partition = [0, 1, 2];
+		let f = frame(WindowBound::Preceding(1), WindowBound::UnboundedFollowing);
+		let ranges = (0..3).map(|i| f.range(i, 3)).collect::<Vec<_>>();
+		let values =
+			aggregate_frames(group.as_ref(), &[], &f, &ranges, &partition, &argument).unwrap();
+		let expected =
+			|v: &[i64]| Value::from(v.iter().copied().map(Value::from).collect::<Vec<_>>());
+		assert_eq!(values, vec![expected(&[1, 2, 3]), expected(&[1, 2, 3]), expected(&[2, 3])]);
+	}
+
+	#[test]
+	fn test_frame_ranges_include_peers() {
+		let mut field = WindowField {
+			name: "_w0".into(),
+			display: String::new(),
+			partition: Vec::new(),
+			order: Vec::new(),
+			frame: default_frame(true),
+			peers: true,
+			kind: WindowFunctionKind::RowNumber,
+		};
+		// Rows 1 and 2 are peers, as are rows 3 and 4
+		let keys = [1, 2, 2, 3, 3];
+		let is_peer = |a: usize, b: usize| keys[a] == keys[b];
+		assert_eq!(
+			frame_ranges(&field, 5, is_peer),
+			[Some((0, 0)), Some((0, 2)), Some((0, 2)), Some((0, 4)), Some((0, 4))]
+		);
+		// An explicit ROWS frame ends at the current row
+		field.peers = false;
+		assert_eq!(
+			frame_ranges(&field, 5, is_peer),
+			[Some((0, 0)), Some((0, 1)), Some((0, 2)), Some((0, 3)), Some((0, 4))]
+		);
+	}
+}
@@ -328,6 +328,7 @@ pub(crate) fn expr_required_context(expr: &Expr) -> ContextLevel {
 		// Function calls may be user-defined (stored in the database), so
 		// conservatively require database context.
 		Expr::FunctionCall(_) => ContextLevel::Database,
+		Expr::Window(_) => ContextLevel::Database,
 
 		// Return: delegate to inner expression
 		Expr::Return(stmt) => expr_required_context(&stmt.what),
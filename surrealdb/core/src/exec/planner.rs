@@ -44,6 +44,9 @@
 //! Aggregate (GROUP BY)
 //!     │
 //!     ▼
+//! Window (<function> OVER (...))
+//!     │
+//!     ▼
 //! Sort (ORDER BY)
 //!     │
 //!     ▼
@@ -70,6 +73,7 @@ mod row_scope;
 mod select;
 mod source;
 pub(crate) mod util;
+mod window;
 
 use std::sync::Arc;
 
@@ -580,6 +584,12 @@ impl<'ctx> Planner<'ctx> {
 				closure: *c,
 			})),
 
+			// Window calls are lifted out of the SELECT field list into a
+			// `Window` operator before the fields are planned.
+			Expr::Window(_) => Err(Error::InvalidStatement(
+				"Window functions can only be used in the fields of a SELECT statement".to_string(),
+			)),
+
 			// Compound expressions
 			Expr::IfElse(stmt) => Box::pin(self.physical_if_else(*stmt)).await,
 			Expr::Mock(m) => Ok(Arc::new(MockExpr(m))),
@@ -1206,6 +1216,7 @@ impl<'ctx> Planner<'ctx> {
 				Expr::Sleep(sleep_stmt) => self.plan_sleep_statement(*sleep_stmt),
 
 				expr @ (Expr::FunctionCall(_)
+				| Expr::Window(_)
 				| Expr::Closure(_)
 				| Expr::Literal(_)
 				| Expr::Param(_)
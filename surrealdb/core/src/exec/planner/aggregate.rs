@@ -330,6 +330,29 @@ impl<'a> AggregateExtractor<'a> {
 		}
 		false
 	}
+
+	/// Whether an expression is a (non-aggregate or `array::distinct`) function
+	/// call whose arguments are computed from an aggregate call, such as
+	/// `array::distinct(array::group(x))`. An aggregate function applied to such
+	/// a value runs once per group over the aggregated value, rather than being
+	/// a nested aggregate.
+	fn derives_from_aggregate(&self, expr: &Expr) -> bool {
+		let Expr::FunctionCall(func_call) = expr else {
+			return false;
+		};
+		let Function::Normal(name) = &func_call.receiver else {
+			return false;
+		};
+		if name.as_str() != "array::distinct"
+			&& self.registry.get_aggregate(name.as_str()).is_some()
+		{
+			return false;
+		}
+		func_call
+			.arguments
+			.iter()
+			.any(|arg| self.contains_aggregate_call(arg) || self.derives_from_aggregate(arg))
+	}
 }
 
 impl MutVisitor for AggregateExtractor<'_> {
@@ -350,6 +373,16 @@ impl MutVisitor for AggregateExtractor<'_> {
 				return expr.visit_mut(self);
 			}
 
+			// e.g. `math::sum(array::distinct(array::group(x)))`: the outer call
+			// sums the per-group distinct values, so only the inner aggregate
+			// is extracted.
+			if !self.inside_aggregate
+				&& self.registry.get_aggregate(name.as_str()).is_some()
+				&& func_call.arguments.first().is_some_and(|arg| self.derives_from_aggregate(arg))
+			{
+				return expr.visit_mut(self);
+			}
+
 			if self.registry.get_aggregate(name.as_str()).is_some() {
 				if self.inside_aggregate {
 					self.error = Some(Error::Query {
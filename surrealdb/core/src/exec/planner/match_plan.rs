@@ -24,7 +24,9 @@
 //! [`Planner::fold_optional`]. After the read body, the plan's
 //! `mutations` are folded into a chain of write operators
 //! (`UpdateBinding`/`DeleteBinding`/`InsertGraph`, see [`Planner::plan_mutations`]);
-//! a leading `INSERT` with no read body is seeded by a `SingleRowScan`. Still out
+//! a leading `INSERT` with no read body is seeded by a `SingleRowScan`. The
+//! operands of a composite query (`UNION`/`EXCEPT`/`INTERSECT`/`OTHERWISE`)
+//! each plan this way and are combined by the set operators. Still out
 //! of scope and rejected upstream in the lowering: label mutations, label
 //! expressions, undirected edges.
 //!
@@ -60,18 +62,19 @@ use super::Planner;
 use crate::err::Error;
 use crate::exec::ExecOperator;
 use crate::exec::operators::{
-	Aggregate, AggregateField, Bind, DeleteBinding, Distinct, DistinctEdges, DrainSink,
+	Aggregate, AggregateField, Bind, Compute, DeleteBinding, Distinct, DistinctEdges, DrainSink,
 	EdgeBinding, EndpointBind, EndpointField, Expand, ExpandDir, FieldSelection, Filter, HashJoin,
-	InsertEdgeOp, InsertGraph, InsertNodeOp, JoinType, Limit, OrderByField, PathExpand, PathMode,
-	Project, ShortestPathExpand, ShortestSelector, SingleRowScan, Sort, SortDirection,
-	UpdateBinding,
+	InsertEdgeOp, InsertGraph, InsertNodeOp, JoinType, Limit, OrderByField, Otherwise, PathExpand,
+	PathMode, Project, SetOp, SetOpKind, ShortestPathExpand, ShortestSelector, SingleRowScan, Sort,
+	SortDirection, Union, UpdateBinding,
 };
 use crate::expr::match_plan::{
-	BindingId, BindingKind, EdgeStep, ExpandDirection, MatchClausePlan, MatchOutput, MatchPlan,
-	MatchPredicate, MatchStage, MutationStage, NodeStep, PathMode as IrPathMode, PathPrefixPlan,
-	PathSearch, PatternPlan,
+	BindingId, BindingKind, EdgeStep, ExistsStage, ExpandDirection, MatchClausePlan, MatchOutput,
+	MatchPlan, MatchPredicate, MatchSetOp, MatchStage, MutationStage, NodeStep,
+	PathMode as IrPathMode, PathPrefixPlan, PathSearch, PatternPlan,
 };
-use crate::expr::{Cond, Expr, Function, Idiom, Literal, Part};
+use crate::expr::statements::IfelseStatement;
+use crate::expr::{Cond, Expr, Function, FunctionCall, Idiom, Literal, Part};
 use crate::val::TableName;
 
 /// How a quantified step's path-search selector routes to a physical operator.
@@ -155,12 +158,52 @@ struct PlannedClause {
 impl<'ctx> Planner<'ctx> {
 	/// Plan a GQL `MATCH` query into a streaming operator tree.
 	///
+	/// Each operand of a composite query plans on its own; the operands then
+	/// fold left to right, each combined with the rows before it: `Union`
+	/// (topped by a `Distinct` unless `ALL`), a `SetOp` for `EXCEPT` and
+	/// `INTERSECT`, or `Otherwise`.
+	pub(crate) async fn plan_match(
+		&self,
+		mut plan: MatchPlan,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		let compose = std::mem::take(&mut plan.compose);
+		let mut acc_op = self.plan_linear_match(plan).await?;
+		for composition in compose {
+			let operand = self.plan_linear_match(composition.plan).await?;
+			acc_op = match composition.op {
+				MatchSetOp::Union {
+					distinct,
+				} => {
+					let union =
+						Arc::new(Union::new(vec![acc_op, operand])) as Arc<dyn ExecOperator>;
+					if distinct {
+						Arc::new(Distinct::new(union)) as Arc<dyn ExecOperator>
+					} else {
+						union
+					}
+				}
+				MatchSetOp::Except {
+					distinct,
+				} => Arc::new(SetOp::new(acc_op, operand, SetOpKind::Except, distinct)),
+				MatchSetOp::Intersect {
+					distinct,
+				} => Arc::new(SetOp::new(acc_op, operand, SetOpKind::Intersect, distinct)),
+				MatchSetOp::Otherwise => Arc::new(Otherwise::new(acc_op, operand)),
+			};
+		}
+		Ok(acc_op)
+	}
+
+	/// Plan one linear `MATCH` query (a plan without composite operands).
+	///
 	/// Folds clauses left-to-right over an accumulator `(operator, bound)`: the
 	/// first clause seeds it; each subsequent (non-optional) clause is joined to
 	/// the accumulator with a `HashJoin` — Inner on the node-binding ids the two
 	/// sides share, or Cross when they share none (sequential `MATCH` joins
-	/// exactly like comma-separated patterns, R1).
-	pub(crate) async fn plan_match(&self, plan: MatchPlan) -> Result<Arc<dyn ExecOperator>, Error> {
+	/// exactly like comma-separated patterns, R1). A clause whose leading pattern
+	/// has no labelled element of its own instead extends the accumulator
+	/// directly, expanding off the node it reuses.
+	async fn plan_linear_match(&self, plan: MatchPlan) -> Result<Arc<dyn ExecOperator>, Error> {
 		// Group the ordered stages into fold units: each mandatory read clause is
 		// a unit; a run of read clauses sharing one `optional_group` id is one
 		// OPTIONAL block unit (the all-or-nothing left-join, R3); each write stage
@@ -190,6 +233,13 @@ impl<'ctx> Planner<'ctx> {
 						.to_string(),
 				));
 			}
+			StageUnit::Exists(_) => {
+				return Err(Error::Internal(
+					"GQL planning: a query cannot start with an EXISTS stage (it follows the \
+					 clause or RETURN that reads it)"
+						.to_string(),
+				));
+			}
 			StageUnit::Mutation(stage) => {
 				// A leading mutation (an `INSERT` with no preceding `MATCH`): seed a
 				// single empty row so it runs exactly once.
@@ -201,14 +251,29 @@ impl<'ctx> Planner<'ctx> {
 		// Fold the remaining units.
 		for unit in units {
 			let (op, bound) = match unit {
-				StageUnit::Mandatory(clause) => {
+				StageUnit::Mandatory(clause) if leads_self_rooted(clause) => {
 					let planned = self.plan_clause(&plan, clause, &acc_bound).await?;
 					self.fold_mandatory(&plan, acc_op, &acc_bound, planned).await?
 				}
+				StageUnit::Mandatory(clause) => {
+					// The leading pattern only reuses an accumulator binding: plan the
+					// clause ONTO the accumulator, so it expands off the bound rows.
+					let seed = (acc_op, acc_bound.clone());
+					let planned =
+						self.plan_clause_onto(&plan, clause, &acc_bound, Some(seed)).await?;
+					let op = self
+						.place_above(planned.operator, &planned.bound, planned.deferred)
+						.await?;
+					(op, planned.bound)
+				}
 				StageUnit::Optional(clauses) => {
 					self.fold_optional(&plan, acc_op, &acc_bound, &clauses).await?
 				}
 				StageUnit::Mutation(stage) => self.fold_mutation(&plan, acc_op, acc_bound, stage),
+				StageUnit::Exists(stage) => {
+					let op = self.fold_exists(&plan, acc_op, &acc_bound, stage).await?;
+					(op, acc_bound)
+				}
 			};
 			acc_op = op;
 			acc_bound = bound;
@@ -327,7 +392,9 @@ impl<'ctx> Planner<'ctx> {
 	///   the accumulator; and the `null_template` is every binding the block introduces. A block
 	///   matches all-or-nothing because the entire block is one subplan and one join unit: a
 	///   partial inner match never escapes the subplan, and an accumulator row the block could not
-	///   match at all is null-filled across every block binding.
+	///   match at all is null-filled across every block binding. When the block's leading pattern
+	///   has no labelled element of its own it cannot self-root, so the subplan is seeded from the
+	///   distinct values of the outer node it reuses (see [`Planner::correlated_seed`]).
 	async fn fold_optional(
 		&self,
 		plan: &MatchPlan,
@@ -346,8 +413,22 @@ impl<'ctx> Planner<'ctx> {
 		}
 
 		// The LeftJoin general path: plan the whole block as one standalone
-		// subplan, then left-join it onto the accumulator.
-		let subplan = self.plan_optional_block(plan, clauses).await?;
+		// subplan, then left-join it onto the accumulator. A block led by a
+		// pattern that only reuses an outer node is seeded from that node.
+		let seed = match clauses.first().and_then(|clause| clause.patterns.first()) {
+			Some(pattern) if !self_rootable(pattern) => {
+				let Some(anchor) = shared_node_anchor(plan, pattern, acc_bound) else {
+					return Err(Error::Internal(
+						"GQL MATCH planning: an OPTIONAL block's leading pattern is neither \
+						 self-anchorable nor reuses an outer binding; the lowering must reject it"
+							.to_string(),
+					));
+				};
+				Some(self.correlated_seed(plan, Arc::clone(&acc_op), &[anchor]).await?)
+			}
+			_ => None,
+		};
+		let subplan = self.plan_optional_block(plan, clauses, seed).await?;
 
 		// Keys = the node bindings the block shares with the accumulator (the
 		// correlation between the optional body and the rows it extends). With a
@@ -406,6 +487,103 @@ impl<'ctx> Planner<'ctx> {
 		Ok((join, merged_bound))
 	}
 
+	/// Build the seed of a correlated `OPTIONAL` block or `EXISTS` body: the
+	/// distinct values of the outer `anchors`, read from a second execution of
+	/// the accumulator.
+	///
+	/// The subplan expands off (or joins into) these rows and the left join then
+	/// keys on `anchors`, so each outer row is extended by exactly the matches
+	/// for its anchors. Projecting and deduplicating keeps the build side free of
+	/// the duplicates other outer bindings would cause. Re-reading the
+	/// accumulator is only sound while it carries no writes; the lowering
+	/// restricts a block that follows a write stage to the OptionalExpand fast
+	/// path, and rejects `EXISTS` after a write.
+	async fn correlated_seed(
+		&self,
+		plan: &MatchPlan,
+		acc_op: Arc<dyn ExecOperator>,
+		anchors: &[BindingId],
+	) -> Result<(Arc<dyn ExecOperator>, Vec<BindingId>), Error> {
+		let fields = self.binding_fields(plan, anchors).await?;
+		let project =
+			Arc::new(Project::new(acc_op, fields, Vec::new(), false)) as Arc<dyn ExecOperator>;
+		Ok((Arc::new(Distinct::new(project)) as Arc<dyn ExecOperator>, anchors.to_vec()))
+	}
+
+	/// One pass-through field selection per binding, in the given order.
+	async fn binding_fields(
+		&self,
+		plan: &MatchPlan,
+		ids: &[BindingId],
+	) -> Result<Vec<FieldSelection>, Error> {
+		let mut fields = Vec::with_capacity(ids.len());
+		for id in ids.iter() {
+			let name = binding_name(plan, *id);
+			let expr = self.physical_expr(Expr::Idiom(Idiom::field(name.to_string()))).await?;
+			fields.push(FieldSelection::new(name, expr));
+		}
+		Ok(fields)
+	}
+
+	/// Fold an `EXISTS` stage onto the accumulator: semi-join every subquery as
+	/// a mark, then filter on the conjuncts that read the marks.
+	///
+	/// Each body is planned like an `OPTIONAL` block seeded from the distinct
+	/// values of its correlated bindings, then reduced to one row per distinct
+	/// key carrying `mark = true`. A `Left` join on the correlated bindings adds
+	/// the mark to every accumulator row (null-filled on a miss), so the
+	/// accumulator keeps its cardinality and its bound set: the body's own
+	/// bindings are projected away before the join. An uncorrelated body joins
+	/// on the empty key, marking every row when it matched anywhere.
+	async fn fold_exists(
+		&self,
+		plan: &MatchPlan,
+		mut acc_op: Arc<dyn ExecOperator>,
+		acc_bound: &[BindingId],
+		stage: &ExistsStage,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		for subquery in stage.subqueries.iter() {
+			if !deps_subset(&subquery.correlated, acc_bound) {
+				return Err(Error::Internal(
+					"GQL MATCH planning: an EXISTS subquery correlates a binding the \
+					 accumulator does not carry"
+						.to_string(),
+				));
+			}
+			let seed = if subquery.correlated.is_empty() {
+				None
+			} else {
+				Some(self.correlated_seed(plan, Arc::clone(&acc_op), &subquery.correlated).await?)
+			};
+			let clauses = subquery.clauses.iter().collect::<Vec<_>>();
+			let body = self.plan_optional_block(plan, &clauses, seed).await?;
+			if !body.deferred.is_empty() {
+				return Err(Error::Internal(
+					"GQL MATCH planning: an EXISTS body predicate reads an outer binding missing \
+					 from its correlated set"
+						.to_string(),
+				));
+			}
+
+			let mut fields = self.binding_fields(plan, &subquery.correlated).await?;
+			let mark = self.physical_expr(Expr::Literal(Literal::Bool(true))).await?;
+			fields.push(FieldSelection::new(&subquery.mark, mark));
+			let project = Arc::new(Project::new(body.operator, fields, Vec::new(), false))
+				as Arc<dyn ExecOperator>;
+			let marks = Arc::new(Distinct::new(project)) as Arc<dyn ExecOperator>;
+
+			acc_op = Arc::new(HashJoin::new(
+				marks,
+				acc_op,
+				binding_names(plan, &subquery.correlated),
+				JoinType::Left,
+				vec![subquery.mark.clone()],
+				None,
+			)) as Arc<dyn ExecOperator>;
+		}
+		self.place_above(acc_op, acc_bound, stage.predicates.clone()).await
+	}
+
 	/// Try the OptionalExpand fast path for an `OPTIONAL` block. Returns `Some`
 	/// with the extended stage when the block is a single optional clause whose
 	/// single pattern is a single, non-quantified hop whose source node (its start,
@@ -521,13 +699,14 @@ impl<'ctx> Planner<'ctx> {
 	/// accumulator), so a multi-clause block is inner-joined internally and emits
 	/// only fully-matched rows. Inside-predicates are owned by their clause and
 	/// compile inside this subplan, so they evaluate pre-null (R3 structural). The
-	/// subplan is self-contained: it never expands off the OUTER accumulator (the
-	/// fast path handles the lone correlated single-hop shape; everything reaching
-	/// here is self-rootable by the lowering's optional-anchorability guarantee).
+	/// subplan never expands off the OUTER accumulator itself: a block whose
+	/// leading pattern cannot self-root expands off `seed`, the distinct outer
+	/// anchor values built by [`Planner::correlated_seed`].
 	async fn plan_optional_block(
 		&self,
 		plan: &MatchPlan,
 		clauses: &[&MatchClausePlan],
+		seed: Option<(Arc<dyn ExecOperator>, Vec<BindingId>)>,
 	) -> Result<PlannedClause, Error> {
 		let Some((first, rest)) = clauses.split_first() else {
 			return Err(Error::Internal(
@@ -536,10 +715,15 @@ impl<'ctx> Planner<'ctx> {
 		};
 
 		// The block's leading clause seeds the block accumulator with NO outer
-		// bindings: the subplan stands alone, so its first clause cannot share /
-		// expand off the outer accumulator (a lone correlated single-hop block is
-		// the fast path; a self-rootable block anchors on its own labelled element).
-		let seed = self.plan_clause(plan, first, &[]).await?;
+		// bindings beyond the correlated seed: a self-rootable block anchors on its
+		// own labelled element, any other block expands off the seeded anchor.
+		let seed_bound = match &seed {
+			Some((_, bound)) => bound.clone(),
+			// Without a correlated seed the block binds nothing before its
+			// leading clause
+			None => Vec::new(),
+		};
+		let seed = self.plan_clause_onto(plan, first, &seed_bound, seed).await?;
 		let mut block_op = seed.operator;
 		let mut block_bound = seed.bound;
 		// A predicate the lowering owns to a block clause but whose deps include an
@@ -871,7 +1055,7 @@ impl<'ctx> Planner<'ctx> {
 				.plan_step(plan, pattern, stage, edge, node, step_predicates, edge.direction, false)
 				.await?;
 		}
-		Ok(stage)
+		self.bind_path(plan, pattern, stage).await
 	}
 
 	/// Edge-anchored pattern: no labeled node but a labeled edge. Scan the edge
@@ -932,11 +1116,12 @@ impl<'ctx> Planner<'ctx> {
 			out_node.label.clone(),
 		)) as Arc<dyn ExecOperator>;
 
-		Ok(Some(ChainStage {
+		let stage = ChainStage {
 			operator: out_bind,
 			bound: vec![edge_binding, in_node.binding, out_node.binding],
 			tip: far_node.binding,
-		}))
+		};
+		Ok(Some(self.bind_path(plan, pattern, stage).await?))
 	}
 
 	/// Expand a bound-variable pattern off `input` (the accumulator's rows). The
@@ -977,7 +1162,7 @@ impl<'ctx> Planner<'ctx> {
 					)
 					.await?;
 			}
-			return Ok(stage);
+			return self.bind_path(plan, pattern, stage).await;
 		}
 
 		// Reverse: only the far node of a single-hop pattern is the shared anchor
@@ -994,7 +1179,7 @@ impl<'ctx> Planner<'ctx> {
 			};
 			let step_bound = step_bindings(&stage.bound, pattern, edge, &pattern.start);
 			let step_predicates = drain_satisfiable(pending, &step_bound);
-			return self
+			let stage = self
 				.plan_step(
 					plan,
 					pattern,
@@ -1005,7 +1190,8 @@ impl<'ctx> Planner<'ctx> {
 					edge.direction.reverse(),
 					true,
 				)
-				.await;
+				.await?;
+			return self.bind_path(plan, pattern, stage).await;
 		}
 
 		Err(Error::Internal(
@@ -1165,7 +1351,9 @@ impl<'ctx> Planner<'ctx> {
 			None => {
 				let edge_def = plan.binding(edge.binding);
 				let edge_name = edge_def.name.clone();
-				let edge_binding = if edge_def.user_named {
+				// A path value carries the full edge record (R5), so an anonymous
+				// edge on a path-variable pattern is fetched like a named one.
+				let edge_binding = if edge_def.user_named || pattern.path_var.is_some() {
 					EdgeBinding::Full(edge_name)
 				} else {
 					EdgeBinding::IdOnly(edge_name)
@@ -1200,7 +1388,16 @@ impl<'ctx> Planner<'ctx> {
 				// and `RETURN *` lists user-named bindings, so a `__e<n>` group is
 				// dropped before output.
 				let group_binding = Some(binding_name(plan, edge.binding).to_string());
-				let path_binding = pattern.path_var.map(|id| binding_name(plan, id).to_string());
+				// A lone quantified step emits the whole path under the path
+				// variable; on a longer pattern it emits its segment to a hidden
+				// field that `bind_path` splices into the full path.
+				let path_binding = match pattern.path_var {
+					Some(id) if path_bound_by_step(pattern) => {
+						Some(binding_name(plan, id).to_string())
+					}
+					Some(_) => Some(segment_path_name(edge)),
+					None => None,
+				};
 
 				// Path-search routing (V2_DESIGN path-search): `None`/`ALL` → every
 				// path (the `PathExpand` DFS); `ANY [k]` and the SHORTEST family →
@@ -1248,7 +1445,9 @@ impl<'ctx> Planner<'ctx> {
 						reversed,
 					)) as Arc<dyn ExecOperator>,
 				};
-				if let Some(id) = pattern.path_var {
+				if path_bound_by_step(pattern)
+					&& let Some(id) = pattern.path_var
+				{
 					bound.push(id);
 				}
 				self.maybe_filter(
@@ -1264,6 +1463,30 @@ impl<'ctx> Planner<'ctx> {
 		}
 	}
 
+	/// Bind a pattern's path variable after its last step when no step operator
+	/// emitted it (see [`path_bound_by_step`]): a `Compute` assembles the
+	/// alternating `[node, edge, …, node]` array from the bound elements (R5).
+	/// Passes the stage through unchanged otherwise.
+	async fn bind_path(
+		&self,
+		plan: &MatchPlan,
+		pattern: &PatternPlan,
+		mut stage: ChainStage,
+	) -> Result<ChainStage, Error> {
+		let Some(id) = pattern.path_var else {
+			return Ok(stage);
+		};
+		if path_bound_by_step(pattern) {
+			return Ok(stage);
+		}
+		let value = self.physical_expr(path_value(plan, pattern)).await?;
+		let name = crate::val::Strand::new(binding_name(plan, id));
+		stage.operator =
+			Arc::new(Compute::new(stage.operator, vec![(name, value)])) as Arc<dyn ExecOperator>;
+		stage.bound.push(id);
+		Ok(stage)
+	}
+
 	/// Wrap a stage in a `Filter` for the conjoined `exprs`, or pass the stage
 	/// through unchanged when there are none. Binding set and tip are preserved
 	/// (a Filter neither adds nor drops bindings).
@@ -1539,9 +1762,10 @@ fn drain_satisfiable(pending: &mut Vec<MatchPredicate>, bound: &[BindingId]) ->
 }
 
 /// The bindings a step introduces, given the bindings already in scope: prior
-/// bindings ∪ {edge, node} ∪ optional path var. Used to decide which conjuncts
-/// become satisfiable at a step BEFORE the step operator is built (so an Expand
-/// can carry its predicate). Mirrors the binding growth in `plan_step`.
+/// bindings ∪ {edge, node} ∪ the path var when the step binds it. Used to
+/// decide which conjuncts become satisfiable at a step BEFORE the step operator
+/// is built (so an Expand can carry its predicate). Mirrors the binding growth
+/// in `plan_step`.
 fn step_bindings(
 	prior: &[BindingId],
 	pattern: &PatternPlan,
@@ -1551,7 +1775,7 @@ fn step_bindings(
 	let mut bound = prior.to_vec();
 	bound.push(edge.binding);
 	bound.push(node.binding);
-	if edge.quantifier.is_some()
+	if path_bound_by_step(pattern)
 		&& let Some(id) = pattern.path_var
 	{
 		bound.push(id);
@@ -1559,6 +1783,49 @@ fn step_bindings(
 	bound
 }
 
+/// Whether the pattern's path variable (if any) is bound by its step operator:
+/// a pattern of exactly one quantified step, whose `PathExpand` /
+/// `ShortestPathExpand` emits the whole path. Any other pattern assembles its
+/// path after the last step (`bind_path`).
+fn path_bound_by_step(pattern: &PatternPlan) -> bool {
+	matches!(pattern.steps.as_slice(), [(edge, _)] if edge.quantifier.is_some())
+}
+
+/// The hidden row field a quantified step writes its segment path to when the
+/// segment is only part of a path variable's value. `__` names never collide
+/// with a user binding and never surface in the output.
+fn segment_path_name(edge: &EdgeStep) -> String {
+	format!("__path{}", edge.binding)
+}
+
+/// The R5 path value of a pattern assembled from the bound row fields: the
+/// start node, then per step either `[edge, node]` or the step's segment path
+/// without its (already present) first node, concatenated in written order.
+fn path_value(plan: &MatchPlan, pattern: &PatternPlan) -> Expr {
+	let field = |id: BindingId| Expr::Idiom(Idiom::field(binding_name(plan, id).to_string()));
+	let call = |name: &str, arguments: Vec<Expr>| {
+		Expr::FunctionCall(Box::new(crate::expr::FunctionCall {
+			receiver: Function::Normal(name.to_string()),
+			arguments,
+		}))
+	};
+	let mut path = Expr::Literal(Literal::Array(vec![field(pattern.start.binding)]));
+	for (edge, node) in &pattern.steps {
+		let segment = match edge.quantifier {
+			None => Expr::Literal(Literal::Array(vec![field(edge.binding), field(node.binding)])),
+			Some(_) => call(
+				"array::slice",
+				vec![
+					Expr::Idiom(Idiom::field(segment_path_name(edge))),
+					Expr::Literal(Literal::Integer(1)),
+				],
+			),
+		};
+		path = call("array::concat", vec![path, segment]);
+	}
+	path
+}
+
 /// Whether an expression contains a call to a registered aggregate function.
 /// Used to classify an aggregating query's columns: a non-key column with an
 /// aggregate folds via `extract_aggregate_info`, one without is a value
@@ -1595,6 +1862,14 @@ fn expr_has_aggregate(registry: &crate::exec::function::FunctionRegistry, expr:
 			Expr::Literal(Literal::Object(entries)) => {
 				stack.extend(entries.iter().map(|entry| &entry.value));
 			}
+			// The null guard the GQL lowering wraps a scalar function call in.
+			Expr::IfElse(stmt) => {
+				for (cond, then) in &stmt.exprs {
+					stack.push(cond);
+					stack.push(then);
+				}
+				stack.extend(stmt.close.iter());
+			}
 			_ => {}
 		}
 	}
@@ -1627,12 +1902,14 @@ enum StageUnit<'p> {
 	Mandatory(&'p MatchClausePlan),
 	Optional(Vec<&'p MatchClausePlan>),
 	Mutation(&'p MutationStage),
+	Exists(&'p ExistsStage),
 }
 
 /// Group a plan's stages (textual order) into fold units: each mandatory read
 /// clause is its own unit; consecutive read clauses sharing one `optional_group`
 /// id collapse into one [`StageUnit::Optional`] (the all-or-nothing block); each
-/// write stage is a [`StageUnit::Mutation`] and breaks any optional run. The
+/// write stage is a [`StageUnit::Mutation`] and each `EXISTS` stage a
+/// [`StageUnit::Exists`]; both break any optional run. The
 /// lowering mints block ids densely in textual order and an `OPTIONAL` block
 /// never contains a mutation, so a block's clauses are always adjacent and a
 /// simple run-grouping recovers the blocks exactly.
@@ -1663,11 +1940,34 @@ fn stage_units(plan: &MatchPlan) -> Vec<StageUnit<'_>> {
 				units.push(StageUnit::Mutation(mutation));
 				current_group = None;
 			}
+			MatchStage::Exists(stage) => {
+				units.push(StageUnit::Exists(stage));
+				current_group = None;
+			}
 		}
 	}
 	units
 }
 
+/// Whether a pattern self-roots: a labelled start node, or a single-hop,
+/// non-quantified labelled edge. Mirrors [`Planner::plan_pattern_selfrooted`],
+/// decided up front so a clause can choose how it combines with the
+/// accumulator before any of it is planned.
+fn self_rootable(pattern: &PatternPlan) -> bool {
+	if pattern.start.label.is_some() {
+		return true;
+	}
+	matches!(
+		pattern.steps.as_slice(),
+		[(edge, _)] if edge.label.is_some() && edge.quantifier.is_none()
+	)
+}
+
+/// Whether a clause's leading pattern self-roots (see [`self_rootable`]).
+fn leads_self_rooted(clause: &MatchClausePlan) -> bool {
+	clause.patterns.first().is_some_and(self_rootable)
+}
+
 /// The shared node binding a pattern would anchor on, if any: either its start
 /// node or (single-hop only) its far node is already in `visible`. Returns the
 /// shared binding id, or `None` when the pattern shares no node with `visible`
@@ -1784,8 +2084,10 @@ fn conjoin(mut exprs: Vec<Expr>) -> Option<Expr> {
 
 /// Rewrite a binding-row-scoped expression into a record-row-scoped one for
 /// scan pushdown: every idiom `Idiom[Field(binding), rest..]` becomes
-/// `Idiom[rest..]`. Returns `None` — bailing to a Filter — if any bare
-/// whole-record reference `Idiom[Field(binding)]` survives.
+/// `Idiom[rest..]`, descending through operators, function arguments,
+/// conditionals and array literals. Returns `None` — bailing to a Filter — if
+/// any bare whole-record reference `Idiom[Field(binding)]` survives, or the
+/// expression holds a composite this rewrite does not walk.
 fn prefix_strip(expr: &Expr, binding: &str) -> Option<Expr> {
 	match expr {
 		Expr::Idiom(idiom) => {
@@ -1821,7 +2123,43 @@ fn prefix_strip(expr: &Expr, binding: &str) -> Option<Expr> {
 			op: op.clone(),
 			expr: Box::new(prefix_strip(expr, binding)?),
 		}),
-		other => Some(other.clone()),
+		Expr::FunctionCall(call) => {
+			let arguments = call
+				.arguments
+				.iter()
+				.map(|arg| prefix_strip(arg, binding))
+				.collect::<Option<Vec<_>>>()?;
+			Some(Expr::FunctionCall(Box::new(FunctionCall {
+				receiver: call.receiver.clone(),
+				arguments,
+			})))
+		}
+		Expr::IfElse(stmt) => {
+			let exprs = stmt
+				.exprs
+				.iter()
+				.map(|(cond, then)| {
+					Some((prefix_strip(cond, binding)?, prefix_strip(then, binding)?))
+				})
+				.collect::<Option<Vec<_>>>()?;
+			let close = match &stmt.close {
+				Some(close) => Some(prefix_strip(close, binding)?),
+				None => None,
+			};
+			Some(Expr::IfElse(Box::new(IfelseStatement {
+				exprs,
+				close,
+			})))
+		}
+		Expr::Literal(Literal::Array(items)) => {
+			let items =
+				items.iter().map(|item| prefix_strip(item, binding)).collect::<Option<Vec<_>>>()?;
+			Some(Expr::Literal(Literal::Array(items)))
+		}
+		Expr::Literal(_) | Expr::Param(_) | Expr::Constant(_) | Expr::Table(_) => {
+			Some(expr.clone())
+		}
+		_ => None,
 	}
 }
 
@@ -1830,7 +2168,8 @@ mod tests {
 	use super::*;
 	use crate::ctx::Context;
 	use crate::expr::match_plan::{
-		BindingDef, BindingKind, EdgeQuantifier, MatchColumn, MatchOrder, MatchOutput,
+		BindingDef, BindingKind, EdgeQuantifier, MatchColumn, MatchComposition, MatchOrder,
+		MatchOutput,
 	};
 	use crate::expr::{BinaryOperator, Literal};
 	use crate::kvs::{Datastore, LockType, TransactionType};
@@ -1848,7 +2187,9 @@ mod tests {
 	fn clause_mut(plan: &mut MatchPlan, i: usize) -> &mut MatchClausePlan {
 		match &mut plan.stages[i] {
 			MatchStage::Read(c) => c,
-			MatchStage::Mutate(_) => panic!("stage {i} is not a read clause"),
+			MatchStage::Mutate(_) | MatchStage::Exists(_) => {
+				panic!("stage {i} is not a read clause")
+			}
 		}
 	}
 
@@ -1953,6 +2294,7 @@ mod tests {
 				col("a_name", field_path("a", "name")),
 				col("b_name", field_path("b", "name")),
 			]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -1998,6 +2340,7 @@ mod tests {
 				skip: None,
 				limit: None,
 			}),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2040,6 +2383,7 @@ mod tests {
 				predicates: Vec::new(),
 			}]),
 			output: out_columns(vec![col("a", var("a")), col("c", var("c"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2074,6 +2418,7 @@ mod tests {
 				predicates: Vec::new(),
 			}]),
 			output: out_columns(vec![col("a", var("a")), col("c", var("c"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2129,6 +2474,7 @@ mod tests {
 				predicates: Vec::new(),
 			}]),
 			output: out_columns(vec![col("b", var("b")), col("c", var("c"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2167,6 +2513,7 @@ mod tests {
 				},
 			]),
 			output: out_columns(vec![col("a", var("a")), col("c", var("c"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2243,6 +2590,45 @@ Project [ctx: Db]
 		assert_eq!(rendered, expected, "\n--- got ---\n{rendered}");
 	}
 
+	#[tokio::test]
+	async fn explain_composite_operands_fold_left_to_right() {
+		// `tree_i UNION tree_i EXCEPT ALL tree_i`: each operand plans on its own,
+		// the UNION is deduplicated, and the EXCEPT combines the result with the
+		// third operand.
+		let mut plan = plan_tree_i();
+		plan.compose = vec![
+			MatchComposition {
+				op: MatchSetOp::Union {
+					distinct: true,
+				},
+				plan: plan_tree_i(),
+			},
+			MatchComposition {
+				op: MatchSetOp::Except {
+					distinct: false,
+				},
+				plan: plan_tree_i(),
+			},
+		];
+		let rendered = explain(plan).await;
+		let operand = |indent: &str| {
+			format!(
+				"{indent}Project [ctx: Db]\n\
+				 {indent}    Expand [ctx: Db] [source: a, direction: ->, tables: knows, edge: k, target: b, target_label: person, predicate: k.since > 2020]\n\
+				 {indent}        Bind [ctx: Db] [binding: a]\n\
+				 {indent}            TableScan [ctx: Db] [table: person, direction: Forward]\n"
+			)
+		};
+		let expected = format!(
+			"Except [ctx: Db] [quantifier: ALL]\n    Distinct [ctx: Db]\n        Union [ctx: Db] \
+			 [inputs: 2]\n{}{}{}",
+			operand("            "),
+			operand("            "),
+			operand("    "),
+		);
+		assert_eq!(rendered, expected, "\n--- got ---\n{rendered}");
+	}
+
 	#[tokio::test]
 	async fn explain_tree_iv_quantified_path_order() {
 		let rendered = explain(plan_tree_iv()).await;
@@ -2256,6 +2642,36 @@ Project [ctx: Db]
 		assert_eq!(rendered, expected, "\n--- got ---\n{rendered}");
 	}
 
+	#[tokio::test]
+	async fn explain_unquantified_path_var_is_computed_after_the_step() {
+		// `p = (a:person)-[:knows]->(b:person)`: no step operator emits the path,
+		// so a `Compute` above the `Expand` assembles it from the bound elements.
+		let mut plan = plan_tree_iv();
+		plan.bindings[1] = hidden_edge("__e0");
+		clause_mut(&mut plan, 0).patterns[0].steps[0].0.quantifier = None;
+		let rendered = explain(plan).await;
+		assert!(rendered.contains("Compute"), "\n--- got ---\n{rendered}");
+		assert!(rendered.contains("array::concat"), "\n--- got ---\n{rendered}");
+		assert!(rendered.contains("Expand [ctx: Db] [source: a"), "\n--- got ---\n{rendered}");
+	}
+
+	#[tokio::test]
+	async fn explain_multi_step_path_var_splices_segment_paths() {
+		// `p = (a:person)-[:knows]->{1,3}(b:person)-[:knows]->(c:person)`: the
+		// quantified step writes its segment to a hidden field, and the path is
+		// assembled after the last step.
+		let mut plan = plan_tree_iv();
+		plan.bindings.push(hidden_edge("__e1"));
+		plan.bindings.push(node("c"));
+		clause_mut(&mut plan, 0).patterns[0]
+			.steps
+			.push((edgestep(4, Some("knows"), ExpandDirection::Out), nodestep(5, Some("person"))));
+		let rendered = explain(plan).await;
+		assert!(rendered.contains("path: __path1"), "\n--- got ---\n{rendered}");
+		assert!(rendered.contains("Compute"), "\n--- got ---\n{rendered}");
+		assert!(rendered.contains("array::slice(__path1, 1)"), "\n--- got ---\n{rendered}");
+	}
+
 	#[tokio::test]
 	async fn explain_shortest_routes_to_shortest_path_expand() {
 		// A SHORTEST selector routes the quantified step to `ShortestPathExpand`,
@@ -2414,6 +2830,7 @@ Project [ctx: Db]
 				col("a_name", field_path("a", "name")),
 				col("b_name", field_path("b", "name")),
 			]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2473,6 +2890,7 @@ Project [ctx: Db]
 				},
 			]),
 			output: out_columns(vec![col("a", var("a"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2534,6 +2952,7 @@ Project [ctx: Db]
 				},
 			]),
 			output: out_columns(vec![col("a", var("a"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2599,6 +3018,7 @@ Project [ctx: Db]
 				},
 			]),
 			output: out_columns(vec![col("a", var("a"))]),
+			compose: Vec::new(),
 		}
 	}
 
@@ -2738,6 +3158,7 @@ Project [ctx: Db]
 			bindings: vec![node("a"), hidden_edge("e"), node("b")],
 			stages: Vec::new(),
 			output: out_columns(Vec::new()),
+			compose: Vec::new(),
 		};
 		// Start node shared.
 		assert_eq!(shared_node_anchor(&plan, &pattern, &[0]), Some(0));
@@ -34,6 +34,7 @@ use super::util::{
 	resolve_projection_field_idioms, strip_fts_condition, strip_index_conditions,
 	strip_knn_from_condition, strip_union_index_conditions,
 };
+use super::window::fields_contain_window;
 use crate::catalog::Index;
 use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
 use crate::err::Error;
@@ -665,6 +666,7 @@ impl<'ctx> Planner<'ctx> {
 			mut what,
 			with,
 			cond,
+			fusion,
 			split,
 			group,
 			order,
@@ -679,6 +681,10 @@ impl<'ctx> Planner<'ctx> {
 
 		let version = extract_version(version, self).await?;
 
+		// Window calls see every row that passes the WHERE clause, so LIMIT
+		// and START must not be pushed below the `Window` operator.
+		let has_window = fields_contain_window(&fields);
+
 		// COUNT fast-path
 		if is_count_all_eligible(&fields, &group, &cond, &split, &order, &fetch, &omit, &what) {
 			use crate::exec::operators::CountScan;
@@ -769,6 +775,7 @@ impl<'ctx> Planner<'ctx> {
 			&& fetch.is_none()
 			&& with.is_none()
 			&& version.is_none()
+			&& !has_window
 		{
 			let needed_fields = Self::extract_needed_fields(
 				&fields,
@@ -933,6 +940,35 @@ impl<'ctx> Planner<'ctx> {
 			None => None,
 		};
 
+		// Hybrid search: the full-text and KNN rankings selected by the WHERE
+		// clause are read concurrently and fused into a single source.
+		if let Some(fusion) = fusion {
+			let source = pp
+				.plan_hybrid_search_source(
+					fusion,
+					&what,
+					cond.as_ref(),
+					with.as_ref(),
+					needed_fields,
+					version.clone(),
+				)
+				.await?;
+			let config = SelectPipelineConfig {
+				where_clause: WhereClauseState::None,
+				split,
+				group,
+				order,
+				limit,
+				start,
+				omit,
+				tempfiles,
+				topk_pushdown: None,
+			};
+			let projected = pp.plan_pipeline(source, Some(fields), config).await?;
+			let fetched = pp.plan_fetch(fetch, projected).await?;
+			return pp.wrap_select_tail(fetched, timeout, version, only, true).await;
+		}
+
 		// KNN handling
 		let has_knn = cond.as_ref().is_some_and(|c| has_knn_operator(&c.0));
 		let brute_force_knn = if has_knn {
@@ -999,14 +1035,16 @@ impl<'ctx> Planner<'ctx> {
 			&& !has_top_level_or(cond_for_filter.as_ref())
 			&& limit.is_some()
 			&& split.is_none()
-			&& group.is_none();
+			&& group.is_none()
+			&& !has_window;
 
 		let can_soft_push_limit = !can_push_limit
 			&& source_is_single_scan
 			&& brute_force_knn.is_none()
 			&& limit.is_some()
 			&& split.is_some()
-			&& group.is_none();
+			&& group.is_none()
+			&& !has_window;
 
 		let (scan_limit, scan_start) = if can_push_limit {
 			(
@@ -1061,7 +1099,7 @@ impl<'ctx> Planner<'ctx> {
 				&limit,
 				&fields,
 				tempfiles,
-				split.is_some() || group.is_some() || brute_force_knn.is_some(),
+				split.is_some() || group.is_some() || brute_force_knn.is_some() || has_window,
 				self.ctx.config.max_order_limit_priority_queue_size as usize,
 			)
 		} else {
@@ -1412,6 +1450,21 @@ impl<'ctx> Planner<'ctx> {
 							)
 							.await;
 					}
+					AccessPath::SpatialSearch {
+						index_ref,
+						region,
+						non_points,
+					} => {
+						return Ok(Self::plan_spatial_search_source(
+							table,
+							index_ref,
+							region,
+							non_points,
+							needed_fields,
+							version,
+							table_ctx,
+						));
+					}
 					AccessPath::TableScan => {
 						return self
 							.plan_table_scan_source(
@@ -1670,7 +1723,8 @@ impl<'ctx> Planner<'ctx> {
 	/// Build a `KnnScan` for [`AccessPath::KnnSearch`]. KNN operators are
 	/// stripped from the condition; the residual non-KNN predicates are
 	/// pushed into the HNSW search so non-matching rows don't consume
-	/// top-K slots.
+	/// top-K slots. When another index serves the residual predicates, the
+	/// records it yields let the scan estimate their selectivity.
 	#[allow(clippy::too_many_arguments)]
 	async fn plan_knn_search_source(
 		&self,
@@ -1688,6 +1742,13 @@ impl<'ctx> Planner<'ctx> {
 		use crate::exec::operators::KnnScan;
 
 		let residual_cond = cond.and_then(strip_knn_from_condition);
+		let candidates = match &residual_cond {
+			Some(residual) => {
+				self.plan_knn_candidates(&table, residual, version.as_ref(), table_ctx.as_ref())
+					.await?
+			}
+			None => None,
+		};
 		let mut scan = KnnScan::new(
 			index_ref,
 			vector,
@@ -1698,7 +1759,8 @@ impl<'ctx> Planner<'ctx> {
 			knn_ctx,
 			residual_cond,
 			Some(needed_fields),
-		);
+		)
+		.with_candidates(candidates);
 		if let Some(tc) = table_ctx {
 			scan = scan.with_resolved(tc);
 		}
@@ -1710,6 +1772,187 @@ impl<'ctx> Planner<'ctx> {
 		})
 	}
 
+	/// Plan the source of the records matching the residual predicates of a
+	/// KNN search, when a B-tree index narrows them. Returns `None` when the
+	/// predicates can only be evaluated during the ANN search.
+	async fn plan_knn_candidates(
+		&self,
+		table: &TableName,
+		residual: &Cond,
+		version: Option<&Arc<dyn crate::exec::PhysicalExpr>>,
+		table_ctx: Option<&ResolvedTableContext>,
+	) -> Result<Option<Arc<dyn ExecOperator>>, Error> {
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			return Ok(None);
+		};
+		// SECURITY: the number of records the index yields decides the search
+		// strategy, so an index over a restricted field would leak the
+		// cardinality of values the current user is not permitted to read.
+		if self.cond_touches_restricted_select_field_for_table(table, residual).await {
+			return Ok(None);
+		}
+		let access = self.resolve_access_path(txn, ns, db, table, Some(residual), None, None).await;
+		let path = match access {
+			Ok(Some((path, _))) if !path.is_full_range_scan() => path,
+			_ => return Ok(None),
+		};
+		if !matches!(path, AccessPath::BTreeScan { .. }) {
+			return Ok(None);
+		}
+		let scan =
+			self.build_union_sub_operator(path, table, None, version, table_ctx, None, None)?;
+		let predicate = self.physical_expr(residual.0.clone()).await?;
+		Ok(Some(Arc::new(Filter::new(scan, predicate))))
+	}
+
+	/// Build a `HybridSearch` for a SELECT with a FUSION clause. The WHERE
+	/// clause must be a full-text MATCHES condition OR a KNN condition, each
+	/// served by its own index. The `k` of the KNN condition bounds both
+	/// rankings.
+	async fn plan_hybrid_search_source(
+		&self,
+		fusion: crate::expr::Fusion,
+		what: &[Expr],
+		cond: Option<&Cond>,
+		with: Option<&With>,
+		needed_fields: Option<std::collections::HashSet<String>>,
+		version: Option<Arc<dyn crate::exec::PhysicalExpr>>,
+	) -> Result<Arc<dyn ExecOperator>, Error> {
+		use crate::exec::function::KnnContext;
+		use crate::exec::operators::{FullTextScan, HybridSearch, KnnScan};
+		use crate::expr::BinaryOperator;
+
+		let invalid = || Error::Query {
+			message: "The FUSION clause requires a single table source and a WHERE clause of \
+					  the form `<MATCHES condition> OR <KNN condition>`, each served by an index"
+				.to_string(),
+		};
+		let [Expr::Table(table)] = what else {
+			return Err(invalid());
+		};
+		let Some(Expr::Binary {
+			left,
+			op: BinaryOperator::Or,
+			right,
+		}) = cond.map(|c| &c.0)
+		else {
+			return Err(invalid());
+		};
+		let (Some(txn), Some(ns), Some(db)) = (&self.txn, &self.ns, &self.db) else {
+			return Err(Error::PlannerUnimplemented(
+				"Hybrid search requires plan-time index resolution".to_string(),
+			));
+		};
+
+		// Each side of the OR must be fully served by its index
+		let mut fulltext = None;
+		let mut knn = None;
+		for side in [left, right] {
+			let side = Cond(side.as_ref().clone());
+			match self.resolve_access_path(txn, ns, db, table, Some(&side), None, with).await? {
+				Some((
+					AccessPath::FullTextSearch {
+						index_ref,
+						query,
+						operator,
+					},
+					_,
+				)) if fulltext.is_none() && strip_fts_condition(&side).is_none() => {
+					fulltext = Some((index_ref, query, operator));
+				}
+				Some((
+					AccessPath::KnnSearch {
+						index_ref,
+						vector,
+						k,
+						ef,
+					},
+					_,
+				)) if knn.is_none() && strip_knn_from_condition(&side).is_none() => {
+					knn = Some((index_ref, vector, k, ef));
+				}
+				_ => return Err(invalid()),
+			}
+		}
+		let (Some((ft_index, query, operator)), Some((knn_index, vector, k, ef))) = (fulltext, knn)
+		else {
+			return Err(invalid());
+		};
+
+		// The vector scores are derived from the distances of the index metric
+		let metric = match &knn_index.definition().index {
+			Index::Hnsw(p) => p.distance.clone(),
+			Index::DiskAnn(p) => p.distance.clone(),
+			_ => return Err(invalid()),
+		};
+
+		let table_ctx = self.try_resolve_table_ctx(table).await;
+		let scores = Arc::new(KnnContext::new());
+		let distances = match self.ctx.get_knn_context() {
+			Some(distances) => Arc::clone(distances),
+			None => Arc::new(KnnContext::new()),
+		};
+
+		let mut fulltext = FullTextScan::new(
+			ft_index,
+			query,
+			operator,
+			table.clone(),
+			version.clone(),
+			Some(needed_fields.clone()),
+		)
+		.with_top_k(k as usize, Arc::clone(&scores));
+		let mut knn = KnnScan::new(
+			knn_index,
+			vector,
+			k,
+			ef,
+			table.clone(),
+			version,
+			Some(Arc::clone(&distances)),
+			None,
+			Some(needed_fields),
+		);
+		if let Some(tc) = table_ctx {
+			fulltext = fulltext.with_resolved(tc.clone());
+			knn = knn.with_resolved(tc);
+		}
+		Ok(Arc::new(HybridSearch::new(
+			Arc::new(fulltext),
+			Arc::new(knn),
+			fusion,
+			scores,
+			distances,
+			metric,
+		)))
+	}
+
+	/// Build a `SpatialScan` for [`AccessPath::SpatialSearch`]. The spatial
+	/// index only yields candidates, so the original WHERE clause is kept.
+	fn plan_spatial_search_source(
+		table: crate::val::TableName,
+		index_ref: crate::exec::index::access_path::IndexRef,
+		region: Vec<geo::Rect<f64>>,
+		non_points: bool,
+		needed_fields: Option<std::collections::HashSet<String>>,
+		version: Option<Arc<dyn crate::exec::PhysicalExpr>>,
+		table_ctx: Option<ResolvedTableContext>,
+	) -> PlannedSource {
+		use crate::exec::operators::SpatialScan;
+
+		let mut scan =
+			SpatialScan::new(index_ref, region, non_points, table, version, Some(needed_fields));
+		if let Some(tc) = table_ctx {
+			scan = scan.with_resolved(tc);
+		}
+		PlannedSource {
+			operator: Arc::new(scan) as Arc<dyn ExecOperator>,
+			filter_action: FilterAction::UseOriginal,
+			limit_pushed: false,
+			topk_pushdown: None,
+		}
+	}
+
 	/// Build a `EmptyScan` for [`AccessPath::EmptyScan`] — used when the
 	/// analyzer proved the WHERE clause cannot match any rows (e.g. a
 	/// contradictory range or empty `IN []`). Returns a `PlannedSource`
@@ -2017,7 +2260,7 @@ impl<'ctx> Planner<'ctx> {
 	/// `needed_fields` — those are handled at the union level.
 	///
 	/// `select_access_path` only emits `BTreeScan` / `FullTextSearch` /
-	/// `KnnSearch` as union sub-paths; anything else is a planner bug
+	/// `KnnSearch` / `SpatialSearch` as union sub-paths; anything else is a planner bug
 	/// and surfaces as `Error::Internal` rather than silently returning a
 	/// full table scan.
 	#[allow(clippy::too_many_arguments)]
@@ -2031,7 +2274,7 @@ impl<'ctx> Planner<'ctx> {
 		knn_ctx: Option<&Arc<crate::exec::function::KnnContext>>,
 		merge_batch_ceiling: Option<&Arc<dyn crate::exec::PhysicalExpr>>,
 	) -> Result<Arc<dyn ExecOperator>, Error> {
-		use crate::exec::operators::{FullTextScan, IndexScan, KnnScan};
+		use crate::exec::operators::{FullTextScan, IndexScan, KnnScan, SpatialScan};
 
 		match path {
 			AccessPath::BTreeScan {
@@ -2099,6 +2342,24 @@ impl<'ctx> Planner<'ctx> {
 				}
 				Ok(Arc::new(scan))
 			}
+			AccessPath::SpatialSearch {
+				index_ref,
+				region,
+				non_points,
+			} => {
+				let mut scan = SpatialScan::new(
+					index_ref,
+					region,
+					non_points,
+					table.clone(),
+					version.cloned(),
+					None,
+				);
+				if let Some(tc) = table_ctx {
+					scan = scan.with_resolved(tc.clone());
+				}
+				Ok(Arc::new(scan))
+			}
 			other => {
 				// Server-side log carries the Debug-formatted access path
 				// for diagnosis; the client-facing message stays opaque
@@ -2109,7 +2370,7 @@ impl<'ctx> Planner<'ctx> {
 				);
 				Err(Error::Internal(
 					"UnionIndexScan sub-path produced an unexpected access path; \
-					 only BTreeScan / FullTextSearch / KnnSearch are valid here"
+					 only BTreeScan / FullTextSearch / KnnSearch / SpatialSearch are valid here"
 						.into(),
 				))
 			}
@@ -2526,6 +2787,10 @@ impl<'ctx> Planner<'ctx> {
 		});
 		let analysis_cond = rewritten_cond.as_ref();
 
+		if let Some(cond) = analysis_cond {
+			cond.check_partial_indexes(&indexes)?;
+		}
+
 		let analyzer = IndexAnalyzer::new(indexes, with);
 		let candidates = analyzer.analyze(analysis_cond, order);
 
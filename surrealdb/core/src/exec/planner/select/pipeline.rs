@@ -1,4 +1,5 @@
-//! SELECT pipeline assembly: WHERE → SPLIT → GROUP → ORDER → LIMIT → projection.
+//! SELECT pipeline assembly: WHERE → SPLIT → GROUP → WINDOW → ORDER → LIMIT →
+//! projection.
 //!
 //! Owns the pipeline-state types ([`WhereClauseState`], [`FilterAction`],
 //! [`PlannedSource`], [`SelectPipelineConfig`]) and the orchestration
@@ -20,6 +21,7 @@ use std::sync::Arc;
 
 use super::super::Planner;
 use super::super::util::{check_forbidden_group_by_params, get_effective_limit_literal};
+use super::super::window::fields_contain_window;
 use crate::err::Error;
 use crate::exec::expression_registry::{ComputePoint, ExpressionRegistry, resolve_order_by_alias};
 use crate::exec::field_path::FieldPath;
@@ -322,6 +324,26 @@ impl<'ctx> Planner<'ctx> {
 
 		let fields = fields.unwrap_or_else(Fields::all);
 
+		// Window calls are computed over the filtered rows, before ORDER BY and
+		// LIMIT. They are not defined over grouped rows.
+		let (split_op, fields, window_omits) = if fields_contain_window(&fields) {
+			if group.is_some() {
+				return Err(Error::Query {
+					message: "Window functions can not be used together with GROUP BY".to_string(),
+				});
+			}
+			let has_all = fields.has_all_selection();
+			let (windowed, fields, names) = self.plan_window(split_op, fields).await?;
+			let omits = if has_all {
+				names
+			} else {
+				vec![]
+			};
+			(windowed, fields, omits)
+		} else {
+			(split_op, fields, vec![])
+		};
+
 		let (grouped, skip_projections) = if let Some(groups) = group {
 			let group_by: Vec<_> = groups.0.into_iter().map(|g| g.0).collect();
 			check_forbidden_group_by_params(&fields)?;
@@ -389,7 +411,7 @@ impl<'ctx> Planner<'ctx> {
 		};
 
 		let mut all_omit = omit;
-		for field_name in sort_only_omits {
+		for field_name in sort_only_omits.into_iter().chain(window_omits) {
 			all_omit.push(Expr::Idiom(Idiom::field(field_name)));
 		}
 
@@ -23,6 +23,10 @@ pub(crate) fn derive_field_name(expr: &Expr) -> String {
 			let idiom: crate::expr::idiom::Idiom = call.receiver.to_idiom();
 			idiom_to_field_name(&idiom)
 		}
+		Expr::Window(window) => {
+			let idiom: crate::expr::idiom::Idiom = window.call.receiver.to_idiom();
+			idiom_to_field_name(&idiom)
+		}
 		_ => {
 			use surrealdb_types::ToSql;
 			expr.to_sql()
@@ -0,0 +1,291 @@
+//! Window function planning for the planner.
+//!
+//! Lifts `<function> OVER (...)` calls out of the SELECT field list into a
+//! [`Window`] operator, replacing each call with a reference to the synthetic
+//! field (`_w0`, `_w1`, ...) that the operator adds to every row.
+
+use std::sync::Arc;
+
+use surrealdb_types::ToSql;
+
+use super::Planner;
+use super::util::derive_field_name;
+use crate::err::Error;
+use crate::exec::ExecOperator;
+use crate::exec::operators::{Window, WindowField, WindowFunctionKind, default_frame};
+use crate::expr::field::{Field, Fields};
+use crate::expr::visit::{MutVisitor, Visit, VisitMut, Visitor};
+use crate::expr::{Expr, Function, Idiom, WindowCall};
+
+/// Generate a synthetic field name for a window call at the given index.
+pub(crate) fn window_field_name(idx: usize) -> String {
+	format!("_w{idx}")
+}
+
+/// Check whether any SELECT field contains a window call.
+///
+/// Subqueries are not inspected: their window calls are planned with the
+/// subquery itself.
+pub(crate) fn fields_contain_window(fields: &Fields) -> bool {
+	let mut finder = WindowFinder {
+		found: false,
+	};
+	for selector in fields.iter_non_all_fields() {
+		let _ = finder.visit_expr(&selector.expr);
+	}
+	finder.found
+}
+
+// ============================================================================
+// impl Planner — Window functions
+// ============================================================================
+
+impl<'ctx> Planner<'ctx> {
+	/// Plan the window calls in the SELECT fields.
+	///
+	/// Returns the `Window` operator wrapping `input`, the fields rewritten to
+	/// reference the computed window values, and the synthetic field names so
+	/// that a `*` projection can omit them again.
+	pub(crate) async fn plan_window(
+		&self,
+		input: Arc<dyn ExecOperator>,
+		fields: Fields,
+	) -> Result<(Arc<dyn ExecOperator>, Fields, Vec<String>), Error> {
+		let mut extractor = WindowExtractor::default();
+		let fields = match fields {
+			Fields::Value(mut selector) => {
+				let _ = extractor.visit_mut_expr(&mut selector.expr);
+				Fields::Value(selector)
+			}
+			Fields::Select(field_list) => {
+				let mut rewritten = Vec::with_capacity(field_list.len());
+				for field in field_list {
+					match field {
+						Field::All => rewritten.push(Field::All),
+						Field::Single(mut selector) => {
+							let count = extractor.windows.len();
+							let original_name = derive_field_name(&selector.expr);
+							let _ = extractor.visit_mut_expr(&mut selector.expr);
+							// Keep the output name of the original expression rather
+							// than the synthetic field it now references.
+							if extractor.windows.len() > count && selector.alias.is_none() {
+								selector.alias = Some(Idiom::field(original_name));
+							}
+							rewritten.push(Field::Single(selector));
+						}
+					}
+				}
+				Fields::Select(rewritten)
+			}
+		};
+
+		if let Some(err) = extractor.error {
+			return Err(err);
+		}
+
+		let mut window_fields = Vec::with_capacity(extractor.windows.len());
+		let mut names = Vec::with_capacity(extractor.windows.len());
+		for (name, window) in extractor.windows {
+			window_fields.push(self.plan_window_field(name.clone(), window).await?);
+			names.push(name);
+		}
+
+		Ok((Arc::new(Window::new(input, window_fields)) as Arc<dyn ExecOperator>, fields, names))
+	}
+
+	/// Plan a single window call into a [`WindowField`].
+	async fn plan_window_field(
+		&self,
+		name: String,
+		window: WindowCall,
+	) -> Result<WindowField, Error> {
+		let display = window.to_sql();
+		let WindowCall {
+			call,
+			spec,
+		} = window;
+
+		let Function::Normal(function) = call.receiver else {
+			return Err(Error::Query {
+				message: format!("`{display}` is not a valid window function call"),
+			});
+		};
+		let arity = |min: usize, max: usize| {
+			let len = call.arguments.len();
+			if len < min || len > max {
+				let message = if min == max {
+					format!("The function expects {min} arguments.")
+				} else {
+					format!("The function expects {min} to {max} arguments.")
+				};
+				return Err(Error::InvalidFunctionArguments {
+					name: function.clone(),
+					message,
+				});
+			}
+			Ok(())
+		};
+
+		let registry = self.function_registry();
+		let kind = match function.as_str() {
+			"window::row_number" | "window::rank" | "window::dense_rank" => {
+				arity(0, 0)?;
+				match function.as_str() {
+					"window::row_number" => WindowFunctionKind::RowNumber,
+					"window::rank" => WindowFunctionKind::Rank,
+					_ => WindowFunctionKind::DenseRank,
+				}
+			}
+			"window::lag" | "window::lead" => {
+				arity(1, 3)?;
+				let mut args = call.arguments.into_iter();
+				let argument = self.physical_expr(args.next().expect("arity checked")).await?;
+				let offset = match args.next() {
+					Some(x) => Some(self.physical_expr(x).await?),
+					None => None,
+				};
+				let default = match args.next() {
+					Some(x) => Some(self.physical_expr(x).await?),
+					None => None,
+				};
+				WindowFunctionKind::Offset {
+					argument,
+					offset,
+					default,
+					forward: function == "window::lead",
+				}
+			}
+			"window::first_value" | "window::last_value" => {
+				arity(1, 1)?;
+				let argument = call.arguments.into_iter().next().expect("arity checked");
+				WindowFunctionKind::Value {
+					argument: self.physical_expr(argument).await?,
+					last: function == "window::last_value",
+				}
+			}
+			_ => {
+				let aggregate = if function == "count" {
+					registry.get_count_aggregate(!call.arguments.is_empty())
+				} else if let Some(aggregate) = registry.get_aggregate(&function) {
+					Arc::clone(aggregate)
+				} else {
+					return Err(Error::Query {
+						message: format!(
+							"The function {function}() can not be used as a window function, only \
+							 window::* and aggregate functions accept an OVER clause"
+						),
+					});
+				};
+				let mut args = call.arguments.into_iter();
+				let argument = match args.next() {
+					Some(x) => self.physical_expr(x).await?,
+					None => self.physical_expr(Expr::Literal(crate::expr::Literal::None)).await?,
+				};
+				let mut extra_args = Vec::new();
+				for arg in args {
+					extra_args.push(self.physical_expr(arg).await?);
+				}
+				WindowFunctionKind::Aggregate {
+					function: aggregate,
+					argument,
+					extra_args,
+				}
+			}
+		};
+
+		let mut partition = Vec::with_capacity(spec.partition.len());
+		for expr in spec.partition {
+			partition.push(self.physical_expr(expr).await?);
+		}
+		let order = match spec.order {
+			Some(order_list) => self.convert_order_list(order_list).await?,
+			None => Vec::new(),
+		};
+		// Without an explicit frame, an ordered window includes the peers of
+		// the current row, as `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`
+		let peers = spec.frame.is_none() && !order.is_empty();
+		let frame = spec.frame.unwrap_or_else(|| default_frame(!order.is_empty()));
+
+		Ok(WindowField {
+			name: name.into(),
+			display,
+			partition,
+			order,
+			frame,
+			peers,
+			kind,
+		})
+	}
+}
+
+// ============================================================================
+// Window visitors
+// ============================================================================
+
+/// Visitor that detects window calls outside of subqueries.
+struct WindowFinder {
+	found: bool,
+}
+
+impl Visitor for WindowFinder {
+	type Error = std::convert::Infallible;
+
+	fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+		if matches!(expr, Expr::Window(_)) {
+			self.found = true;
+			return Ok(());
+		}
+		expr.visit(self)
+	}
+
+	fn visit_select(
+		&mut self,
+		_s: &crate::expr::statements::SelectStatement,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
+
+/// Visitor that replaces window calls with references to synthetic fields.
+#[derive(Default)]
+struct WindowExtractor {
+	windows: Vec<(String, WindowCall)>,
+	error: Option<Error>,
+}
+
+impl MutVisitor for WindowExtractor {
+	type Error = std::convert::Infallible;
+
+	fn visit_mut_expr(&mut self, expr: &mut Expr) -> Result<(), Self::Error> {
+		if self.error.is_some() {
+			return Ok(());
+		}
+
+		if let Expr::Window(window) = expr {
+			let mut finder = WindowFinder {
+				found: false,
+			};
+			let _ = window.visit(&mut finder);
+			if finder.found {
+				self.error = Some(Error::Query {
+					message: "Nested window functions are not supported".to_string(),
+				});
+				return Ok(());
+			}
+
+			let name = window_field_name(self.windows.len());
+			self.windows.push((name.clone(), window.as_ref().clone()));
+			*expr = Expr::Idiom(Idiom::field(name));
+			return Ok(());
+		}
+
+		expr.visit_mut(self)
+	}
+
+	fn visit_mut_select(
+		&mut self,
+		_s: &mut crate::expr::statements::SelectStatement,
+	) -> Result<(), Self::Error> {
+		Ok(())
+	}
+}
@@ -157,6 +157,8 @@ impl Visitor for FieldDependencyExtractor {
 			// `$this` indirectly. Walk body for any obvious deps but assume
 			// incompleteness.
 			| Expr::Closure(_)
+			// Window calls read other rows of the result set.
+			| Expr::Window(_)
 			// DDL inside an expression should never appear in a permission
 			// clause, but if it does it is by definition opaque to static
 			// dependency analysis.
@@ -1,7 +1,10 @@
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
-use super::Expr;
+use super::operator::NearestNeighbor;
+use super::{BinaryOperator, Expr, Idiom};
+use crate::catalog::{Index, IndexDefinition};
+use crate::err::Error;
 
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -13,3 +16,134 @@ impl ToSql for Cond {
 		stmt.fmt_sql(f, fmt);
 	}
 }
+
+impl Cond {
+	/// Returns whether every record matching this condition also matches the
+	/// given predicate.
+	///
+	/// This is decided syntactically: each `AND` term of the predicate must be
+	/// an `AND` term of this condition, or an `OR` of which one branch is
+	/// itself implied.
+	pub(crate) fn implies(&self, predicate: &Cond) -> bool {
+		let mut terms = Vec::new();
+		conjuncts(&self.0, &mut terms);
+		implied_by(&predicate.0, &terms)
+	}
+
+	/// Checks that the full-text and approximate nearest neighbour expressions
+	/// of this condition, which can only be evaluated with an index, are not
+	/// left with partial indexes whose predicate this condition does not
+	/// imply.
+	pub(crate) fn check_partial_indexes(&self, indexes: &[IndexDefinition]) -> Result<(), Error> {
+		let mut exprs = Vec::new();
+		indexed_operators(&self.0, &mut exprs);
+		for (exp, idiom) in exprs {
+			let mut unimplied = None;
+			for ix in indexes {
+				if ix.prepare_remove
+					|| ix.cols.first() != Some(idiom)
+					|| !supports_operator(&ix.index, exp)
+				{
+					continue;
+				}
+				match &ix.cond {
+					Some(predicate) if !self.implies(predicate) => {
+						unimplied.get_or_insert((ix, predicate));
+					}
+					// An index serves the expression
+					_ => {
+						unimplied = None;
+						break;
+					}
+				}
+			}
+			if let Some((ix, predicate)) = unimplied {
+				return Err(Error::PartialIndexNotImplied {
+					exp: exp.to_sql(),
+					index: ix.name.to_string(),
+					cond: predicate.0.to_sql(),
+				});
+			}
+		}
+		Ok(())
+	}
+}
+
+/// Collect the expressions which can only be evaluated with an index, along
+/// with the field they apply to.
+fn indexed_operators<'a>(expr: &'a Expr, exprs: &mut Vec<(&'a Expr, &'a Idiom)>) {
+	match expr {
+		Expr::Binary {
+			left,
+			op,
+			right,
+		} => {
+			if let Expr::Idiom(idiom) = left.as_ref()
+				&& matches!(op, BinaryOperator::Matches(_) | BinaryOperator::NearestNeighbor(_))
+			{
+				exprs.push((expr, idiom));
+			}
+			indexed_operators(left, exprs);
+			indexed_operators(right, exprs);
+		}
+		Expr::Prefix {
+			expr,
+			..
+		} => indexed_operators(expr, exprs),
+		_ => {}
+	}
+}
+
+/// Whether an index can evaluate an expression collected by
+/// [`indexed_operators`].
+fn supports_operator(index: &Index, exp: &Expr) -> bool {
+	let Expr::Binary {
+		op,
+		..
+	} = exp
+	else {
+		return false;
+	};
+	match op {
+		BinaryOperator::Matches(_) => matches!(index, Index::FullText(_)),
+		BinaryOperator::NearestNeighbor(nn) => {
+			matches!(nn.as_ref(), NearestNeighbor::Approximate(..))
+				&& matches!(index, Index::Hnsw(_) | Index::DiskAnn(_))
+		}
+		_ => false,
+	}
+}
+
+/// Flatten nested AND expressions into a list of terms.
+fn conjuncts<'a>(expr: &'a Expr, terms: &mut Vec<&'a Expr>) {
+	match expr {
+		Expr::Binary {
+			left,
+			op: BinaryOperator::And,
+			right,
+		} => {
+			conjuncts(left, terms);
+			conjuncts(right, terms);
+		}
+		_ => terms.push(expr),
+	}
+}
+
+fn implied_by(predicate: &Expr, terms: &[&Expr]) -> bool {
+	if terms.contains(&predicate) {
+		return true;
+	}
+	match predicate {
+		Expr::Binary {
+			left,
+			op: BinaryOperator::And,
+			right,
+		} => implied_by(left, terms) && implied_by(right, terms),
+		Expr::Binary {
+			left,
+			op: BinaryOperator::Or,
+			right,
+		} => implied_by(left, terms) || implied_by(right, terms),
+		_ => false,
+	}
+}
@@ -19,7 +19,7 @@ use crate::expr::statements::{
 };
 use crate::expr::{
 	BinaryOperator, Block, Constant, ControlFlow, FlowResult, FunctionCall, Idiom, Literal, Mock,
-	ObjectEntry, Param, PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit,
+	ObjectEntry, Param, PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit, WindowCall,
 };
 use crate::fnc;
 use crate::types::PublicValue;
@@ -58,6 +58,9 @@ pub(crate) enum Expr {
 	},
 	// TODO: Factor out the call from the function expression.
 	FunctionCall(Box<FunctionCall>),
+	/// A function call evaluated over a window of rows. Only valid in the
+	/// field list of a `SELECT` statement, see [`WindowCall`].
+	Window(Box<WindowCall>),
 
 	Closure(Box<ClosureExpr>),
 
@@ -128,6 +131,9 @@ impl Expr {
 				..
 			} => left.read_only() && right.read_only(),
 			Expr::FunctionCall(function) => function.read_only(),
+			Expr::Window(window) => {
+				window.call.read_only() && window.spec.partition.iter().all(|x| x.read_only())
+			}
 			Expr::Return(expr) => expr.read_only(),
 			Expr::Throw(expr) => expr.read_only(),
 			Expr::IfElse(s) => s.read_only(),
@@ -369,6 +375,7 @@ impl Expr {
 			| Expr::Table(_)
 			| Expr::Mock(_)
 			| Expr::Block(_)
+			| Expr::Window(_)
 			| Expr::Closure(_)
 			| Expr::Break
 			| Expr::Continue
@@ -404,6 +411,7 @@ impl Expr {
 			Expr::Idiom(i) => i.simplify(),
 			Expr::Param(i) => Idiom::field(i.clone().into_strand()),
 			Expr::FunctionCall(x) => x.receiver.to_idiom(),
+			Expr::Window(x) => x.call.receiver.to_idiom(),
 			Expr::Literal(l) => match l {
 				Literal::String(s) => Idiom::field(s.clone()),
 				Literal::Datetime(d) => Idiom::field(d.to_string()),
@@ -493,6 +501,11 @@ impl Expr {
 				..
 			} => Self::compute_binary(stk, ctx, &opt, doc, self).await,
 			Expr::FunctionCall(function_call) => function_call.compute(stk, ctx, &opt, doc).await,
+			Expr::Window(_) => Err(ControlFlow::Err(anyhow::Error::new(Error::InvalidStatement(
+				"Window functions can only be used in the fields of a SELECT statement run by the \
+				 streaming execution engine"
+					.to_string(),
+			)))),
 			Expr::Closure(closure) => Ok(closure.compute(ctx).await?),
 			Expr::Break => Err(ControlFlow::Break),
 			Expr::Continue => Err(ControlFlow::Continue),
@@ -889,7 +902,8 @@ impl Expr {
 			| Expr::Binary {
 				..
 			}
-			| Expr::FunctionCall(_) => false,
+			| Expr::FunctionCall(_)
+			| Expr::Window(_) => false,
 		}
 	}
 }
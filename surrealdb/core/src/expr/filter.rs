@@ -2,8 +2,9 @@ use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::expr::language::Language;
+use crate::val::File;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Filter {
 	Ascii,
@@ -13,6 +14,20 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	#[revision(start = 2)]
+	StopWords(StopWords),
+	#[revision(start = 2)]
+	Synonyms(File),
+}
+
+/// The list of words removed by a `STOPWORDS` filter
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum StopWords {
+	/// The built-in list of a language
+	Language(Language),
+	/// A file of a bucket, with one word per line
+	File(File),
 }
 
 impl ToSql for Filter {
@@ -0,0 +1,30 @@
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::val::Number;
+
+/// The FUSION clause of a hybrid full-text and vector search.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum Fusion {
+	/// Reciprocal rank fusion with the given rank constant.
+	Rrf(u64),
+	/// Weighted linear combination of the normalized scores.
+	Linear {
+		fulltext: Number,
+		vector: Number,
+		norm: FusionNorm,
+	},
+}
+
+/// How the scores of each ranking are normalized before a linear fusion.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum FusionNorm {
+	MinMax,
+	ZScore,
+}
+
+impl ToSql for Fusion {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		let fusion: crate::sql::Fusion = self.clone().into();
+		fusion.fmt_sql(f, sql_fmt);
+	}
+}
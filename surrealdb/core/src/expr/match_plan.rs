@@ -45,6 +45,11 @@ pub(crate) struct MatchPlan {
 	/// The `RETURN` projection, or `None` for a mutation-only query (no
 	/// trailing `RETURN`).
 	pub(crate) output: Option<MatchOutput>,
+	/// The further operands of a composite query, in textual order: each
+	/// combines the result of everything before it with its own plan's rows.
+	/// Empty for a plain linear query. The operand plans are themselves linear
+	/// (their own `compose` is empty) and read-only.
+	pub(crate) compose: Vec<MatchComposition>,
 }
 
 impl MatchPlan {
@@ -53,9 +58,44 @@ impl MatchPlan {
 	/// transaction) — see `Expr::read_only`.
 	pub(crate) fn has_mutations(&self) -> bool {
 		self.stages.iter().any(|s| matches!(s, MatchStage::Mutate(_)))
+			|| self.compose.iter().any(|c| c.plan.has_mutations())
 	}
 }
 
+/// One further operand of a composite query (`UNION`, `EXCEPT`, `INTERSECT`,
+/// `OTHERWISE`) and the operator combining it with the rows before it.
+///
+/// Rows are compared whole: every operand returns the same column names (the
+/// lowering checks it), so two rows are equal when every column is.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct MatchComposition {
+	pub(crate) op: MatchSetOp,
+	pub(crate) plan: MatchPlan,
+}
+
+/// How a [`MatchComposition`] combines its operand with the rows before it.
+/// `distinct` is the set quantifier: `true` for `DISTINCT` (the default),
+/// `false` for `ALL`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum MatchSetOp {
+	/// Every row of both sides.
+	Union {
+		distinct: bool,
+	},
+	/// The rows before it without those of the operand; under `ALL` each
+	/// operand row cancels one equal row.
+	Except {
+		distinct: bool,
+	},
+	/// The rows before it that the operand also returns; under `ALL` each
+	/// operand row matches at most one equal row.
+	Intersect {
+		distinct: bool,
+	},
+	/// The rows before it if there are any, otherwise the operand's rows.
+	Otherwise,
+}
+
 /// One step of a lowered query, in textual order: a read clause that extends the
 /// binding table, or a write stage that mutates it. A read clause after a write
 /// re-reads the live (post-write) state in the same transaction.
@@ -65,6 +105,40 @@ pub(crate) enum MatchStage {
 	Read(MatchClausePlan),
 	/// A write stage applied to the binding table.
 	Mutate(MutationStage),
+	/// The `EXISTS` subqueries of the preceding read clause (or of the
+	/// `RETURN`), semi-joined onto the binding table, and the clause conjuncts
+	/// that test them.
+	Exists(ExistsStage),
+}
+
+/// A set of `EXISTS` subqueries evaluated against the binding table.
+///
+/// Each subquery marks every row it matches: the row gains a `mark` field that
+/// is `true` when the body matched the row's correlated bindings and `NULL`
+/// otherwise, so the subquery never multiplies or drops rows. The `EXISTS`
+/// expressions themselves lower to tests of their mark, so `NOT EXISTS` and
+/// an `EXISTS` inside a disjunction or a projection need no special casing.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ExistsStage {
+	pub(crate) subqueries: Vec<ExistsSubquery>,
+	/// Conjuncts of the preceding clause's predicate that read a mark, placed
+	/// once every subquery has marked the rows. Empty for the marks a `RETURN`
+	/// reads.
+	pub(crate) predicates: Vec<MatchPredicate>,
+}
+
+/// One `EXISTS` subquery.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ExistsSubquery {
+	/// The row field (`__x<n>`) the subquery marks.
+	pub(crate) mark: String,
+	/// The outer bindings the body reads, sorted: the semi-join keys. Empty
+	/// for an uncorrelated body, which marks every row or none.
+	pub(crate) correlated: Vec<BindingId>,
+	/// The body's clauses, inner-joined like a mandatory `MATCH` sequence. The
+	/// bindings they introduce are local to the body and never reach the
+	/// binding table.
+	pub(crate) clauses: Vec<MatchClausePlan>,
 }
 
 /// One write stage applied to the binding table, in textual order.
@@ -371,6 +445,41 @@ impl ExpandDirection {
 /// textually diffable. The rendering is single-line regardless of `fmt`.
 impl ToSql for MatchPlan {
 	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		self.render_linear(f);
+		for composition in &self.compose {
+			f.push_str(match composition.op {
+				MatchSetOp::Union {
+					..
+				} => " UNION",
+				MatchSetOp::Except {
+					..
+				} => " EXCEPT",
+				MatchSetOp::Intersect {
+					..
+				} => " INTERSECT",
+				MatchSetOp::Otherwise => " OTHERWISE",
+			});
+			match composition.op {
+				MatchSetOp::Union {
+					distinct: false,
+				}
+				| MatchSetOp::Except {
+					distinct: false,
+				}
+				| MatchSetOp::Intersect {
+					distinct: false,
+				} => f.push_str(" ALL "),
+				_ => f.push(' '),
+			}
+			composition.plan.render_linear(f);
+		}
+	}
+}
+
+impl MatchPlan {
+	/// Render the linear query — the steps and the `RETURN` — without the
+	/// composite operands.
+	fn render_linear(&self, f: &mut String) {
 		// Steps render in textual order, space-separated (reads and mutations
 		// interleaved as written).
 		for (i, stage) in self.stages.iter().enumerate() {
@@ -380,6 +489,7 @@ impl ToSql for MatchPlan {
 			match stage {
 				MatchStage::Read(clause) => self.render_clause(f, clause),
 				MatchStage::Mutate(mutation) => self.render_mutation(f, mutation),
+				MatchStage::Exists(stage) => self.render_exists(f, stage),
 			}
 		}
 
@@ -471,6 +581,35 @@ impl MatchPlan {
 		}
 	}
 
+	/// Render an `EXISTS` stage as `LET __x0 = EXISTS { MATCH … }, … [FILTER
+	/// <preds>]`: each mark bound to its subquery body, then the conjuncts that
+	/// read the marks.
+	fn render_exists(&self, f: &mut String, stage: &ExistsStage) {
+		f.push_str("LET");
+		for (i, subquery) in stage.subqueries.iter().enumerate() {
+			if i > 0 {
+				f.push(',');
+			}
+			f.push(' ');
+			f.push_str(&subquery.mark);
+			f.push_str(" = EXISTS {");
+			for clause in subquery.clauses.iter() {
+				f.push(' ');
+				self.render_clause(f, clause);
+			}
+			f.push_str(" }");
+		}
+		if !stage.predicates.is_empty() {
+			f.push_str(" FILTER ");
+			for (i, predicate) in stage.predicates.iter().enumerate() {
+				if i > 0 {
+					f.push_str(" AND ");
+				}
+				predicate.expr.fmt_sql(f, SqlFormat::SingleLine);
+			}
+		}
+	}
+
 	/// Render one mutation stage in a deterministic GQL-ish form (EXPLAIN /
 	/// logs). Never panics: out-of-range bindings render as `?` via
 	/// [`MatchPlan::binding_name`].
@@ -681,7 +820,9 @@ mod tests {
 	fn clause_mut(plan: &mut MatchPlan, i: usize) -> &mut MatchClausePlan {
 		match &mut plan.stages[i] {
 			MatchStage::Read(c) => c,
-			MatchStage::Mutate(_) => panic!("stage {i} is not a read clause"),
+			MatchStage::Mutate(_) | MatchStage::Exists(_) => {
+				panic!("stage {i} is not a read clause")
+			}
 		}
 	}
 
@@ -689,7 +830,9 @@ mod tests {
 	fn clause(plan: &MatchPlan, i: usize) -> &MatchClausePlan {
 		match &plan.stages[i] {
 			MatchStage::Read(c) => c,
-			MatchStage::Mutate(_) => panic!("stage {i} is not a read clause"),
+			MatchStage::Mutate(_) | MatchStage::Exists(_) => {
+				panic!("stage {i} is not a read clause")
+			}
 		}
 	}
 
@@ -775,6 +918,7 @@ mod tests {
 				skip: None,
 				limit: None,
 			}),
+			compose: Vec::new(),
 		}
 	}
 
@@ -23,6 +23,7 @@ pub(crate) mod fetch;
 pub(crate) mod field;
 pub(crate) mod filter;
 pub(crate) mod function;
+pub(crate) mod fusion;
 pub(crate) mod group;
 pub(crate) mod idiom;
 pub(crate) mod kind;
@@ -49,8 +50,10 @@ pub(crate) mod script;
 pub(crate) mod split;
 pub(crate) mod start;
 pub(crate) mod tokenizer;
+pub(crate) mod ttl;
 pub(crate) mod user;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub(crate) mod decimal;
@@ -77,6 +80,7 @@ pub(crate) use self::fetch::{Fetch, Fetchs};
 pub(crate) use self::field::{Field, Fields};
 pub(crate) use self::filter::Filter;
 pub(crate) use self::function::{Function, FunctionCall};
+pub(crate) use self::fusion::{Fusion, FusionNorm};
 pub(crate) use self::group::{Group, Groups};
 pub(crate) use self::idiom::Idiom;
 pub(crate) use self::kind::{Kind, KindLiteral};
@@ -106,7 +110,9 @@ pub(crate) use self::split::{Split, Splits};
 pub(crate) use self::start::Start;
 pub(crate) use self::statements::{DefineAnalyzerStatement, SelectStatement, SleepStatement};
 pub(crate) use self::tokenizer::Tokenizer;
+pub(crate) use self::ttl::Ttl;
 pub(crate) use self::view::View;
+pub(crate) use self::window::{WindowBound, WindowCall, WindowFrame, WindowSpec};
 pub(crate) use self::with::With;
 
 /// Result of functions which can impact the controlflow of query execution.
@@ -1,6 +1,7 @@
 use crate::expr::Expr;
 use crate::expr::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, AckStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement,
+	UseStatement,
 };
 
 #[derive(Clone, Debug)]
@@ -19,6 +20,7 @@ pub(crate) enum TopLevelExpr {
 	Option(OptionStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
+	Ack(AckStatement),
 	Expr(Expr),
 }
 
@@ -26,11 +28,10 @@ impl TopLevelExpr {
 	/// Check if we require a writeable transaction
 	pub(crate) fn read_only(&self) -> bool {
 		match self {
-			TopLevelExpr::Begin
-			| TopLevelExpr::Cancel
-			| TopLevelExpr::Commit
-			| TopLevelExpr::Show(_) => true,
-			TopLevelExpr::Kill(_)
+			TopLevelExpr::Begin | TopLevelExpr::Cancel | TopLevelExpr::Commit => true,
+			TopLevelExpr::Show(s) => s.read_only(),
+			TopLevelExpr::Ack(_)
+			| TopLevelExpr::Kill(_)
 			| TopLevelExpr::Live(_)
 			| TopLevelExpr::Option(_)
 			| TopLevelExpr::Use(_)
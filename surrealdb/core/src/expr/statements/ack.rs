@@ -0,0 +1,58 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::TableProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::{Base, Expr, FlowResultExt as _};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{TableName, Value};
+
+/// An ACK statement for acknowledging the changes read by a consumer.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct AckStatement {
+	pub consumer: String,
+	pub table: TableName,
+	pub member: Option<String>,
+	pub versionstamp: Expr,
+}
+
+impl AckStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "AckStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Acknowledging changes moves the offsets of the consumer
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Table, Base::Db)?;
+		// Compute the versionstamp
+		let versionstamp =
+			stk.run(|stk| self.versionstamp.compute(stk, ctx, opt, doc)).await.catch_return()?;
+		let versionstamp = match versionstamp {
+			Value::Number(v) => u128::try_from(v).map_err(|_| Value::Number(v)),
+			v => Err(v),
+		};
+		let versionstamp = match versionstamp {
+			Ok(v) => v,
+			Err(v) => bail!(Error::InvalidStatement(format!(
+				"The versionstamp `{}` is not a positive integer",
+				v.to_sql()
+			))),
+		};
+		// Get the transaction
+		let txn = ctx.tx();
+		// Acknowledge the changes
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+		let cs = txn.get_tb_consumer(ns, db, &self.table, &self.consumer, None).await?;
+		crate::cf::consumer::ack(&txn, ns, db, &cs, self.member.as_deref(), versionstamp).await?;
+		// Ok all good
+		Ok(Value::None)
+	}
+}
@@ -87,6 +87,10 @@ impl AlterAnalyzerStatement {
 			AlterKind::None => {}
 		}
 
+		if let AlterKind::Set(_) = self.filters {
+			ctx.get_index_stores().word_lists().load(ctx, ns, db, &az).await?;
+		}
+
 		let key = crate::key::database::az::new(ns, db, &name);
 		txn.set(&key, &az).await?;
 		txn.clear_cache();
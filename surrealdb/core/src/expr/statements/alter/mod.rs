@@ -114,7 +114,7 @@ pub(crate) enum AlterStatement {
 	System(AlterSystemStatement),
 	Namespace(AlterNamespaceStatement),
 	Database(AlterDatabaseStatement),
-	Table(AlterTableStatement),
+	Table(Box<AlterTableStatement>),
 	Api(AlterApiStatement),
 	Event(AlterEventStatement),
 	Index(AlterIndexStatement),
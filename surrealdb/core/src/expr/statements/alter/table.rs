@@ -14,7 +14,7 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::statements::DefineTableStatement;
-use crate::expr::{Base, ChangeFeed, Expr, Literal};
+use crate::expr::{Base, ChangeFeed, Expr, Literal, Ttl};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{TableName, Value};
 
@@ -25,6 +25,7 @@ use crate::val::{TableName, Value};
 /// - toggle `SCHEMAFULL`/`SCHEMALESS`
 /// - update `PERMISSIONS`
 /// - set/drop `CHANGEFEED`
+/// - set/drop record `TTL`
 /// - set/drop table `COMMENT`
 /// - change table `TYPE` (`NORMAL`/`RELATION`/`ANY`)
 /// - request a table-level storage `COMPACT`
@@ -44,6 +45,8 @@ pub(crate) struct AlterTableStatement {
 	pub permissions: Option<Permissions>,
 	/// Set/drop changefeed definition.
 	pub(crate) changefeed: AlterKind<ChangeFeed>,
+	/// Set/drop the time to live of the table records.
+	pub(crate) ttl: AlterKind<Ttl>,
 	/// Set/drop human‑readable comment.
 	pub(crate) comment: AlterKind<String>,
 	/// Request a compaction of the table’s keyspace.
@@ -60,6 +63,7 @@ impl Default for AlterTableStatement {
 			schemafull: AlterKind::None,
 			permissions: None,
 			changefeed: AlterKind::None,
+			ttl: AlterKind::None,
 			comment: AlterKind::None,
 			compact: false,
 			kind: None,
@@ -129,6 +133,23 @@ impl AlterTableStatement {
 			AlterKind::None => {}
 		}
 
+		let previous_ttl = dt.ttl.clone();
+		match self.ttl {
+			AlterKind::Set(ref x) => {
+				// Records of a view are managed by the view itself, so they can not expire
+				if dt.view.is_some() {
+					return Err(Error::InvalidStatement(format!(
+						"Unable to define a TTL on table `{}` as it is a view",
+						name.as_str()
+					))
+					.into());
+				}
+				dt.ttl = Some(x.clone())
+			}
+			AlterKind::Drop => dt.ttl = None,
+			AlterKind::None => {}
+		}
+
 		match self.comment {
 			AlterKind::Set(ref x) => dt.comment = Some(x.clone()),
 
@@ -158,6 +179,11 @@ impl AlterTableStatement {
 		// Set the table definition
 		txn.put_tb(ns_name, db_name, &dt).await?;
 
+		// Rebuild the expiry entries if the TTL field changed
+		if previous_ttl.as_ref().map(|t| &t.field) != dt.ttl.as_ref().map(|t| &t.field) {
+			crate::kvs::expiry::rebuild(&txn, ns, db, &name, dt.ttl.as_ref()).await?;
+		}
+
 		// Clear the cache
 		txn.clear_cache();
 		// Ok all good
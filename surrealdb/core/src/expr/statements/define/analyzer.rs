@@ -109,6 +109,7 @@ impl DefineAnalyzerStatement {
 		// Process the statement
 		let key = crate::key::database::az::new(ns, db, definition.name.as_str());
 		ctx.get_index_stores().mappers().load(&definition, &ctx.config.file_allowlist).await?;
+		ctx.get_index_stores().word_lists().load(ctx, ns, db, &definition).await?;
 		txn.set(&key, &definition).await?;
 		// Clear the cache
 		txn.clear_cache();
@@ -0,0 +1,132 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+
+use super::DefineKind;
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{ConsumerDefinition, ConsumerOffset};
+use crate::cf::consumer::{MAX_PARTITIONS, offsets};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Duration, TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub partitions: u16,
+	pub timeout: Expr,
+	pub comment: Expr,
+}
+
+impl DefineConsumerStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefineConsumerStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		let target_table = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.target_table, "target table").await?,
+		);
+
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Consumer, Base::Db)?;
+		// Get the NS and DB
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		if txn.get_tb_consumer(ns, db, &target_table, &name, None).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::CsAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => return Ok(Value::None),
+			}
+		}
+
+		// Check the number of partitions
+		if self.partitions == 0 || self.partitions > MAX_PARTITIONS {
+			bail!(Error::InvalidStatement(format!(
+				"The consumer `{name}` must have between 1 and {MAX_PARTITIONS} partitions"
+			)));
+		}
+		// Compute the member timeout
+		let timeout = stk
+			.run(|stk| self.timeout.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to::<Option<Duration>>()?
+			.map(|x| x.0)
+			.unwrap_or(ConsumerDefinition::DEFAULT_TIMEOUT);
+
+		// Ensure the table exists
+		let tb = txn.get_or_add_tb(Some(ctx), ns_name, db_name, &target_table, None).await?;
+		// Consumers read from the change feed of the table
+		if tb.changefeed.is_none() && ctx.get_db(opt).await?.changefeed.is_none() {
+			bail!(Error::InvalidStatement(format!(
+				"The consumer `{name}` requires a CHANGEFEED on table `{target_table}` or its database"
+			)));
+		}
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		// Process the statement
+		let cs = ConsumerDefinition {
+			name: name.clone().into(),
+			target_table: target_table.clone(),
+			partitions: self.partitions,
+			timeout,
+			comment,
+		};
+		let key = crate::key::table::cs::new(ns, db, &target_table, &name);
+		txn.set(&key, &cs).await?;
+		// Only changes made after the consumer was first defined are read. When
+		// the number of partitions changes, every partition resumes from the
+		// lowest existing offset, so that no unacknowledged changes are lost.
+		let existing = offsets(&txn, ns, db, &cs).await?;
+		if existing.len() != cs.partitions as usize {
+			let versionstamp = match existing.values().min() {
+				Some(vs) => *vs,
+				None => txn.timestamp().await?.as_versionstamp(),
+			};
+			let beg = crate::key::table::co::prefix(ns, db, &target_table, &name)?;
+			let end = crate::key::table::co::suffix(ns, db, &target_table, &name)?;
+			txn.delr(beg..end).await?;
+			for p in 0..cs.partitions {
+				let key = crate::key::table::co::new(ns, db, &target_table, &name, p);
+				txn.set(
+					&key,
+					&ConsumerOffset {
+						versionstamp,
+					},
+				)
+				.await?;
+			}
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
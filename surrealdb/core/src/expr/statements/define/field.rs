@@ -5,7 +5,7 @@ use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 use uuid::Uuid;
 
-use super::DefineKind;
+use super::{DefineKind, index_column_fields};
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
 	self, DatabaseId, FieldDefinition, NamespaceId, Permission, Permissions, Relation,
@@ -113,7 +113,7 @@ impl DefineFieldStatement {
 		if self.computed.is_some() {
 			let (ns, db) = ctx.get_ns_db_ids(opt).await?;
 			for ix in ctx.tx().all_tb_indexes(ns, db, &table, None).await?.iter() {
-				if ix.cols.iter().any(|col| col.starts_with(&name)) {
+				if ix.cols.iter().flat_map(index_column_fields).any(|col| col.starts_with(&name)) {
 					bail!(Error::ComputedFieldCannotBeIndexed {
 						index: ix.name.to_string(),
 						field: name.to_raw_string(),
@@ -1,6 +1,8 @@
+use std::convert::Infallible;
+use std::slice;
 use std::sync::Arc;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 use uuid::Uuid;
@@ -12,8 +14,10 @@ use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
 use crate::err::Error;
+use crate::exec::function::FunctionRegistry;
 use crate::expr::parameterize::{expr_to_ident, exprs_to_fields};
-use crate::expr::{Base, Expr, FlowResultExt, Idiom, Literal, Part};
+use crate::expr::visit::{Visit, Visitor};
+use crate::expr::{Base, Cond, Expr, FlowResultExt, Function, Idiom, Literal, Part};
 use crate::iam::{Action, ResourceKind};
 use crate::kvs::Transaction;
 use crate::kvs::index::{IndexBuilder, retire_durable_index};
@@ -26,6 +30,7 @@ pub(crate) struct DefineIndexStatement {
 	pub what: Expr,
 	pub cols: Vec<Expr>,
 	pub index: Index,
+	pub cond: Option<Cond>,
 	pub comment: Expr,
 	pub concurrently: bool,
 }
@@ -38,6 +43,7 @@ impl Default for DefineIndexStatement {
 			what: Expr::Literal(Literal::None),
 			cols: Vec::new(),
 			index: Index::Idx,
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		}
@@ -85,8 +91,40 @@ impl DefineIndexStatement {
 			}
 		}
 
-		// Compute columns
-		let cols = exprs_to_fields(stk, ctx, opt, doc, self.cols.as_slice()).await?;
+		// Only a deterministic condition over the record's fields can filter the
+		// index, as it is evaluated again whenever a record is written.
+		if let Some(cond) = &self.cond {
+			ensure!(
+				IndexExpression::new(&cond.0).is_deterministic(ctx.function_registry()),
+				Error::NonDeterministicIndexCondition {
+					cond: cond.0.to_sql(),
+					index: name,
+				}
+			);
+		}
+
+		// Compute columns. An expression over the record's fields is indexed
+		// as is, other columns are resolved to field names.
+		let mut cols = Vec::with_capacity(self.cols.len());
+		// The record's fields the index columns are computed from
+		let mut fields = Vec::new();
+		for expr in self.cols.iter() {
+			if let Some(expression) = IndexExpression::analyze(expr) {
+				ensure!(
+					expression.is_deterministic(ctx.function_registry()),
+					Error::NonDeterministicIndexExpression {
+						expr: expr.to_sql(),
+						index: name,
+					}
+				);
+				fields.extend(expression.fields);
+				cols.push(Idiom(vec![Part::Start(expr.clone())]));
+			} else {
+				let idioms = exprs_to_fields(stk, ctx, opt, doc, slice::from_ref(expr)).await?;
+				fields.extend(idioms.iter().cloned());
+				cols.extend(idioms);
+			}
+		}
 
 		// Validate each indexed field:
 		// 1. Computed fields cannot be indexed (regardless of schemafull/schemaless). This applies
@@ -97,7 +135,7 @@ impl DefineIndexStatement {
 		//    access — this includes `object`, `any`, literal object types (e.g. `{ key: string }`),
 		//    and union types where every non-none variant is object-like. A parent field with no
 		//    explicit type is also accepted, since it is unconstrained.
-		for idiom in cols.iter() {
+		for idiom in fields.iter() {
 			let fd = idiom.to_raw_string();
 			// Check if the exact field path (e.g. `document.visible`) is defined
 			if let Some(f) =
@@ -146,8 +184,13 @@ impl DefineIndexStatement {
 		if let Some(ix) = existing.as_ref()
 			&& self.kind == DefineKind::Default
 			&& opt.import
-			&& import_replay_can_reuse_index(ix, &table_name, &cols, &self.index)
-		{
+			&& import_replay_can_reuse_index(
+				ix,
+				&table_name,
+				&cols,
+				&self.index,
+				self.cond.as_ref(),
+			) {
 			// Import replays are idempotent when the physical index definition
 			// already matches. Preserve data and durable build state while still
 			// allowing metadata such as comments to be refreshed.
@@ -159,6 +202,7 @@ impl DefineIndexStatement {
 				index: self.index.clone(),
 				comment,
 				prepare_remove: false,
+				cond: self.cond.clone(),
 			};
 			txn.put_tb_index(tb.namespace_id, tb.database_id, &tb.name, &index_def).await?;
 			refresh_table_index_cache(ctx, &txn, ns, db, &tb).await?;
@@ -210,6 +254,7 @@ impl DefineIndexStatement {
 			index: self.index.clone(),
 			comment,
 			prepare_remove: false,
+			cond: self.cond.clone(),
 		};
 		txn.put_tb_index(tb.namespace_id, tb.database_id, &tb.name, &index_def).await?;
 
@@ -246,11 +291,135 @@ fn import_replay_can_reuse_index(
 	table_name: &TableName,
 	cols: &[Idiom],
 	index: &Index,
+	cond: Option<&Cond>,
 ) -> bool {
 	!ix.prepare_remove
 		&& &ix.table_name == table_name
 		&& ix.cols.as_slice() == cols
 		&& &ix.index == index
+		&& ix.cond.as_ref() == cond
+}
+
+/// The record's fields an index column reads: the column itself, or the
+/// fields an expression column is computed from.
+pub(crate) fn index_column_fields(col: &Idiom) -> Vec<Idiom> {
+	match col.first() {
+		Some(Part::Start(expr)) => IndexExpression::new(expr).fields,
+		_ => vec![col.clone()],
+	}
+}
+
+/// An index column computed from the record's fields, like
+/// `string::lowercase(email)` or `time::day(created_at)`.
+#[derive(Default)]
+struct IndexExpression {
+	/// The record's fields the expression reads.
+	fields: Vec<Idiom>,
+	/// The builtin functions the expression calls.
+	functions: Vec<String>,
+	/// Whether the expression reads more than the record, like parameters,
+	/// subqueries or other records.
+	opaque: bool,
+}
+
+impl IndexExpression {
+	fn new(expr: &Expr) -> Self {
+		let mut expression = Self::default();
+		let _ = expression.visit_expr(expr);
+		expression
+	}
+
+	/// Analyzes an index column, returning `None` when it is not an expression
+	/// over the record's fields and must be resolved to a field name instead.
+	fn analyze(expr: &Expr) -> Option<Self> {
+		let is_expression = match expr {
+			// `type::field` and `type::fields` name the indexed fields
+			Expr::FunctionCall(call) => !matches!(
+				&call.receiver,
+				Function::Normal(name) if matches!(name.as_str(), "type::field" | "type::fields")
+			),
+			Expr::Binary {
+				..
+			}
+			| Expr::Prefix {
+				..
+			}
+			| Expr::Postfix {
+				..
+			} => true,
+			_ => false,
+		};
+		if !is_expression {
+			return None;
+		}
+		let expression = Self::new(expr);
+		(!expression.fields.is_empty()).then_some(expression)
+	}
+
+	/// Whether the expression always computes the same value for the same
+	/// record, which is required to keep the index consistent.
+	fn is_deterministic(&self, registry: &FunctionRegistry) -> bool {
+		!self.opaque
+			&& self.functions.iter().all(|name| {
+				name != "rand"
+					&& !name.starts_with("rand::")
+					&& name != "time::now"
+					&& !name.ends_with("::generate")
+					&& registry.get(name).is_some_and(|f| f.is_pure() && !f.is_async())
+			})
+	}
+}
+
+impl Visitor for IndexExpression {
+	type Error = Infallible;
+
+	fn visit_expr(&mut self, expr: &Expr) -> Result<(), Self::Error> {
+		match expr {
+			Expr::Literal(_)
+			| Expr::Constant(_)
+			| Expr::Idiom(_)
+			| Expr::FunctionCall(_)
+			| Expr::Binary {
+				..
+			}
+			| Expr::Prefix {
+				..
+			}
+			| Expr::Postfix {
+				..
+			} => expr.visit(self),
+			_ => {
+				self.opaque = true;
+				Ok(())
+			}
+		}
+	}
+
+	fn visit_idiom(&mut self, idiom: &Idiom) -> Result<(), Self::Error> {
+		if let Some(Part::Field(_)) = idiom.first() {
+			self.fields.push(idiom.clone());
+		}
+		idiom.visit(self)
+	}
+
+	fn visit_part(&mut self, part: &Part) -> Result<(), Self::Error> {
+		match part {
+			Part::Lookup(_) | Part::Recurse(..) => {
+				self.opaque = true;
+				Ok(())
+			}
+			_ => part.visit(self),
+		}
+	}
+
+	fn visit_function(&mut self, function: &Function) -> Result<(), Self::Error> {
+		match function {
+			Function::Normal(name) => self.functions.push(name.clone()),
+			// Custom functions, scripts, models and modules may do anything
+			_ => self.opaque = true,
+		}
+		Ok(())
+	}
 }
 
 async fn refresh_table_index_cache(
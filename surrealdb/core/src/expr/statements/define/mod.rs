@@ -3,6 +3,7 @@ mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
@@ -13,6 +14,7 @@ mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,6 +24,7 @@ use anyhow::Result;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{
@@ -29,14 +32,15 @@ pub(crate) use field::{
 	validate_id_field_restrictions,
 };
 pub(crate) use function::DefineFunctionStatement;
-pub(crate) use index::DefineIndexStatement;
 pub(in crate::expr::statements) use index::run_indexing;
+pub(crate) use index::{DefineIndexStatement, index_column_fields};
 pub(crate) use model::DefineModelStatement;
 pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::DefineSinkStatement;
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
 
@@ -96,6 +100,8 @@ pub(crate) enum DefineStatement {
 	Api(DefineApiStatement),
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
+	Sink(DefineSinkStatement),
+	Consumer(DefineConsumerStatement),
 	Module(DefineModuleStatement),
 }
 
@@ -126,6 +132,8 @@ impl DefineStatement {
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(stk, ctx, opt, doc).await,
 		}
 	}
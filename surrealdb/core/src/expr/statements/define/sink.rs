@@ -0,0 +1,177 @@
+use anyhow::{Result, bail};
+use reblessive::tree::Stk;
+use url::Url;
+
+use super::DefineKind;
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{SinkCursor, SinkDefinition, SinkKind};
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::dbs::capabilities::NetTarget;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, FlowResultExt, Literal};
+use crate::iam::{Action, ResourceKind};
+use crate::val::{Duration, TableName, Value};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub sink_kind: SinkKind,
+	pub url: Expr,
+	pub topic: Expr,
+	pub retry: u16,
+	pub backoff: Expr,
+	pub dead_letter: Expr,
+	pub comment: Expr,
+}
+
+impl DefineSinkStatement {
+	/// Process this type returning a computed simple Value
+	#[instrument(level = "trace", name = "DefineSinkStatement::compute", skip_all)]
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sink name").await?;
+		let target_table = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.target_table, "target table").await?,
+		);
+
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Sink, Base::Db)?;
+		// Get the NS and DB
+		let (ns_name, db_name) = opt.ns_db()?;
+		let (ns, db) = ctx.get_ns_db_ids(opt).await?;
+		// Fetch the transaction
+		let txn = ctx.tx();
+		// Check if the definition exists
+		if txn.get_tb_sink(ns, db, &target_table, &name, None).await.is_ok() {
+			match self.kind {
+				DefineKind::Default => {
+					if !opt.import {
+						bail!(Error::SkAlreadyExists {
+							name: name.clone(),
+						});
+					}
+				}
+				DefineKind::Overwrite => {}
+				DefineKind::IfNotExists => return Ok(Value::None),
+			}
+		}
+
+		// Compute the endpoint and the topic
+		let url: String =
+			stk.run(|stk| self.url.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?;
+		let topic: Option<String> = stk
+			.run(|stk| self.topic.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+		let target = self.validate_endpoint(&url, topic.as_deref())?;
+		// Check the endpoint against the network capabilities
+		if !opt.import && !ctx.get_capabilities().allows_network_target(&target) {
+			bail!(Error::NetTargetNotAllowed(target.to_string()));
+		}
+		// Compute the retry backoff
+		let backoff = stk
+			.run(|stk| self.backoff.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to::<Option<Duration>>()?
+			.map(|x| x.0)
+			.unwrap_or(SinkDefinition::DEFAULT_BACKOFF);
+		// Compute the dead letter table
+		let dead_letter = match &self.dead_letter {
+			Expr::Literal(Literal::None) => None,
+			x => Some(TableName::new(
+				expr_to_ident(stk, ctx, opt, doc, x, "dead letter table").await?,
+			)),
+		};
+		if dead_letter.as_ref() == Some(&target_table) {
+			bail!(Error::InvalidStatement(format!(
+				"The dead letter table of sink `{name}` can not be the table it reads from"
+			)));
+		}
+
+		// Ensure the table exists
+		let tb = txn.get_or_add_tb(Some(ctx), ns_name, db_name, &target_table, None).await?;
+		// Sinks read from the change feed of the table
+		if tb.changefeed.is_none() && ctx.get_db(opt).await?.changefeed.is_none() {
+			bail!(Error::InvalidStatement(format!(
+				"The sink `{name}` requires a CHANGEFEED on table `{target_table}` or its database"
+			)));
+		}
+
+		let comment = stk
+			.run(|stk| self.comment.compute(stk, ctx, opt, doc))
+			.await
+			.catch_return()?
+			.cast_to()?;
+
+		// Process the statement
+		let key = crate::key::table::sk::new(ns, db, &target_table, &name);
+		txn.set(
+			&key,
+			&SinkDefinition {
+				name: name.clone().into(),
+				target_table: target_table.clone(),
+				kind: self.sink_kind,
+				url,
+				topic,
+				retry: self.retry,
+				backoff,
+				dead_letter,
+				comment,
+			},
+		)
+		.await?;
+		// Only changes made after the sink was first defined are delivered
+		let key = crate::key::table::sc::new(ns, db, &target_table, &name);
+		if !txn.exists(&key, None).await? {
+			let cursor = SinkCursor {
+				versionstamp: txn.timestamp().await?.as_versionstamp(),
+				..Default::default()
+			};
+			txn.set(&key, &cursor).await?;
+		}
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+
+	/// Checks that the endpoint can be used with this kind of sink,
+	/// returning the network target which the sink connects to.
+	fn validate_endpoint(&self, url: &str, topic: Option<&str>) -> Result<NetTarget> {
+		let kind = self.sink_kind.as_str();
+		let parsed = Url::parse(url).map_err(|_| Error::InvalidUrl(url.to_owned()))?;
+		if !self.sink_kind.supports_scheme(parsed.scheme()) {
+			bail!(Error::InvalidStatement(format!(
+				"The URL `{url}` can not be used with a {kind} sink"
+			)));
+		}
+		match (self.sink_kind.requires_topic(), topic) {
+			(true, None) => {
+				bail!(Error::InvalidStatement(format!("A {kind} sink requires a TOPIC")))
+			}
+			(false, Some(_)) => {
+				bail!(Error::InvalidStatement(format!("A {kind} sink does not support a TOPIC")))
+			}
+			_ => {}
+		}
+		let Some(host) = parsed.host() else {
+			bail!(Error::InvalidUrl(url.to_owned()));
+		};
+		Ok(NetTarget::Host(
+			host.to_owned(),
+			parsed.port_or_known_default().or(self.sink_kind.default_port()),
+		))
+	}
+}
@@ -25,7 +25,7 @@ use crate::expr::parameterize::expr_to_ident;
 use crate::expr::paths::{ID, IN, OUT};
 use crate::expr::{
 	Base, BinaryOperator, Cond, Expr, Field, Fields, FlowResultExt, Function, FunctionCall, Group,
-	Groups, Idiom, Kind, Literal, SelectStatement, View,
+	Groups, Idiom, Kind, Literal, SelectStatement, Ttl, View,
 };
 use crate::iam::{Action, ResourceKind};
 use crate::key;
@@ -42,6 +42,7 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub ttl: Option<Ttl>,
 	pub comment: Expr,
 	pub table_type: TableType,
 	pub graphql_alias: Option<String>,
@@ -59,6 +60,7 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::default(),
 			changefeed: None,
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 			graphql_alias: None,
@@ -87,6 +89,14 @@ impl DefineTableStatement {
 		let name =
 			TableName::new(expr_to_ident(stk, ctx, opt, doc, &self.name, "table name").await?);
 
+		// Records of a view are managed by the view itself, so they can not expire
+		if self.ttl.is_some() && self.view.is_some() {
+			bail!(Error::InvalidStatement(format!(
+				"Unable to define a TTL on table `{}` as it is a view",
+				name.as_str()
+			)));
+		}
+
 		// A PERMISSIONS clause must not perform writes (GHSA-66r2-5gwj-gxm2).
 		if self.permissions.has_direct_write() {
 			bail!(Error::PermissionClauseNotReadonly {
@@ -105,7 +115,7 @@ impl DefineTableStatement {
 		let db = txn.expect_db_by_name(ns_name, db_name).await?;
 
 		// Check if the definition exists
-		let table_id =
+		let (table_id, previous_ttl) =
 			if let Some(tb) = txn.get_tb(ns.namespace_id, db.database_id, &name, None).await? {
 				match self.kind {
 					DefineKind::Default => {
@@ -119,9 +129,9 @@ impl DefineTableStatement {
 					DefineKind::IfNotExists => return Ok(Value::None),
 				}
 
-				tb.table_id
+				(tb.table_id, tb.ttl.clone())
 			} else {
-				txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?
+				(txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?, None)
 			};
 
 		let comment = stk
@@ -144,6 +154,7 @@ impl DefineTableStatement {
 			permissions: self.permissions.clone(),
 			comment,
 			changefeed: self.changefeed,
+			ttl: self.ttl.clone(),
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
@@ -164,6 +175,18 @@ impl DefineTableStatement {
 		// Update the catalog
 		let tb = txn.put_tb(ns_name, db_name, &tb_def).await?;
 
+		// Rebuild the expiry entries if the TTL field changed
+		if previous_ttl.as_ref().map(|t| &t.field) != self.ttl.as_ref().map(|t| &t.field) {
+			crate::kvs::expiry::rebuild(
+				&txn,
+				ns.namespace_id,
+				db.database_id,
+				&name,
+				self.ttl.as_ref(),
+			)
+			.await?;
+		}
+
 		// Clear the cache
 		txn.clear_cache();
 
@@ -310,6 +333,7 @@ impl DefineTableStatement {
 			fields: init_fields,
 			what: tables.iter().map(|x| Expr::Table(x.clone())).collect(),
 			cond: condition.cloned().map(Cond),
+			fusion: None,
 			omit: vec![],
 			only: false,
 			with: None,
@@ -555,6 +579,7 @@ impl DefineTableStatement {
 			fields: Fields::Select(fields),
 			// WHERE cond
 			cond: condition.cloned().map(Cond),
+			fusion: None,
 			// GROUP BY g0,g1,..
 			group: Some(Groups(groups)),
 			what: tables.iter().map(|x| Expr::Table(x.clone())).collect(),
@@ -1,4 +1,5 @@
 pub(crate) mod access;
+pub(crate) mod ack;
 pub(crate) mod alter;
 pub(crate) mod create;
 // needs to be public because the RPC layer is accessing the kv store for api
@@ -25,13 +26,15 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::ack::AckStatement;
 pub(crate) use self::alter::AlterStatement;
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineModelStatement, DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
+	DefineIndexStatement, DefineModelStatement, DefineModuleStatement, DefineNamespaceStatement,
+	DefineParamStatement, DefineSinkStatement, DefineStatement, DefineTableStatement,
+	DefineUserStatement,
 };
 pub(crate) use self::delete::DeleteStatement;
 pub(crate) use self::foreach::ForeachStatement;
@@ -45,10 +48,11 @@ pub(crate) use self::output::OutputStatement;
 pub(crate) use self::rebuild::RebuildStatement;
 pub(crate) use self::relate::RelateStatement;
 pub(crate) use self::remove::{
-	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveConfigStatement, RemoveDatabaseStatement,
-	RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
-	RemoveModelStatement, RemoveModuleStatement, RemoveNamespaceStatement, RemoveParamStatement,
-	RemoveStatement, RemoveTableStatement, RemoveUserStatement,
+	RemoveAccessStatement, RemoveAnalyzerStatement, RemoveConfigStatement, RemoveConsumerStatement,
+	RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement,
+	RemoveIndexStatement, RemoveModelStatement, RemoveModuleStatement, RemoveNamespaceStatement,
+	RemoveParamStatement, RemoveSinkStatement, RemoveStatement, RemoveTableStatement,
+	RemoveUserStatement,
 };
 pub(crate) use self::select::SelectStatement;
 pub(crate) use self::set::SetStatement;
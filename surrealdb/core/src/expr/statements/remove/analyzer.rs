@@ -77,6 +77,7 @@ impl RemoveAnalyzerStatement {
 		// Cleanup in-memory mappers if not used anymore
 		let azs = txn.all_db_analyzers(ns, db, None).await?;
 		ctx.get_index_stores().mappers().cleanup(&azs);
+		ctx.get_index_stores().word_lists().cleanup(ns, db, &azs);
 		// Ok all good
 		Ok(Value::None)
 	}
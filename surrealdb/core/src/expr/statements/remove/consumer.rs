@@ -0,0 +1,89 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::catalog::providers::TableProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveConsumerStatement {
+	pub name: Expr,
+	pub table_name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveConsumerStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			table_name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveConsumerStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Consumer, Base::Db)?;
+		// Compute the table name
+		let table_name = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.table_name, "table name").await?,
+		);
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "consumer name").await?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let cs = match txn.get_tb_consumer(ns, db, &table_name, &name, None).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::CsNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the definition
+		let key = crate::key::table::cs::new(ns, db, &cs.target_table, &cs.name);
+		txn.del(&key).await?;
+		// Delete the partition offsets
+		let beg = crate::key::table::co::prefix(ns, db, &cs.target_table, &cs.name)?;
+		let end = crate::key::table::co::suffix(ns, db, &cs.target_table, &cs.name)?;
+		txn.delr(beg..end).await?;
+		// Delete the group members
+		let beg = crate::key::table::cm::prefix(ns, db, &cs.target_table, &cs.name)?;
+		let end = crate::key::table::cm::suffix(ns, db, &cs.target_table, &cs.name)?;
+		txn.delr(beg..end).await?;
+		let key = crate::key::table::cg::new(ns, db, &cs.target_table, &cs.name);
+		txn.del(&key).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for RemoveConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::remove::RemoveConsumerStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -3,6 +3,7 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
@@ -13,6 +14,7 @@ mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,6 +24,7 @@ use anyhow::Result;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::RemoveConfigStatement;
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
@@ -33,6 +36,7 @@ pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 use reblessive::tree::Stk;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -62,6 +66,8 @@ pub(crate) enum RemoveStatement {
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Sink(RemoveSinkStatement),
+	Consumer(RemoveConsumerStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
 }
@@ -91,6 +97,8 @@ impl RemoveStatement {
 			Self::Api(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Bucket(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Sequence(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Sink(v) => v.compute(stk, ctx, opt, doc).await,
+			Self::Consumer(v) => v.compute(stk, ctx, opt, doc).await,
 			Self::Module(v) => v.compute(ctx, opt).await,
 			Self::Config(v) => v.compute(ctx, opt).await,
 		}
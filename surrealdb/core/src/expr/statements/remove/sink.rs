@@ -0,0 +1,86 @@
+use anyhow::Result;
+use reblessive::tree::Stk;
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::catalog::providers::TableProvider;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::doc::CursorDoc;
+use crate::err::Error;
+use crate::expr::parameterize::expr_to_ident;
+use crate::expr::{Base, Expr, Literal, Value};
+use crate::iam::{Action, ResourceKind};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RemoveSinkStatement {
+	pub name: Expr,
+	pub table_name: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveSinkStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			table_name: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl RemoveSinkStatement {
+	/// Process this type returning a computed simple Value
+	pub(crate) async fn compute(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		doc: Option<&CursorDoc>,
+	) -> Result<Value> {
+		// Allowed to run?
+		ctx.is_allowed(opt, Action::Edit, ResourceKind::Sink, Base::Db)?;
+		// Compute the table name
+		let table_name = TableName::new(
+			expr_to_ident(stk, ctx, opt, doc, &self.table_name, "table name").await?,
+		);
+		// Compute the name
+		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "sink name").await?;
+		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+
+		// Get the transaction
+		let txn = ctx.tx();
+		// Get the definition
+		let sk = match txn.get_tb_sink(ns, db, &table_name, &name, None).await {
+			Ok(x) => x,
+			Err(e) => {
+				if self.if_exists && matches!(e.downcast_ref(), Some(Error::SkNotFound { .. })) {
+					return Ok(Value::None);
+				} else {
+					return Err(e);
+				}
+			}
+		};
+		// Delete the definition
+		let key = crate::key::table::sk::new(ns, db, &sk.target_table, &sk.name);
+		txn.del(&key).await?;
+		// Delete the delivery cursor
+		let key = crate::key::table::sc::new(ns, db, &sk.target_table, &sk.name);
+		txn.del(&key).await?;
+		// Delete the parked change sets
+		let beg = crate::key::table::sp::prefix(ns, db, &sk.target_table, &sk.name)?;
+		let end = crate::key::table::sp::suffix(ns, db, &sk.target_table, &sk.name)?;
+		txn.delr(beg..end).await?;
+		// Clear the cache
+		txn.clear_cache();
+		// Ok all good
+		Ok(Value::None)
+	}
+}
+
+impl ToSql for RemoveSinkStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let stmt: crate::sql::statements::remove::RemoveSinkStatement = self.clone().into();
+		stmt.fmt_sql(f, fmt);
+	}
+}
@@ -11,7 +11,8 @@ use crate::doc::{CursorDoc, NsDbCtx};
 use crate::err::Error;
 use crate::expr::order::Ordering;
 use crate::expr::{
-	Cond, Explain, Expr, Fetchs, Fields, FlowResultExt as _, Groups, Limit, Splits, Start, With,
+	Cond, Explain, Expr, Fetchs, Fields, FlowResultExt as _, Fusion, Groups, Limit, Splits, Start,
+	With,
 };
 use crate::idx::planner::{QueryPlanner, RecordStrategy, StatementContext};
 use crate::val::{Datetime, Value};
@@ -38,6 +39,10 @@ pub(crate) struct SelectStatement {
 	pub with: Option<With>,
 	/// The WHERE clause.
 	pub cond: Option<Cond>,
+	/// The FUSION clause.
+	///
+	/// This combines the full-text and vector rankings of a hybrid search.
+	pub fusion: Option<Fusion>,
 	/// The SPLIT clause.
 	///
 	/// This is used to produce the cartesian product of the values in split fields.
@@ -72,6 +77,15 @@ impl SelectStatement {
 	) -> Result<Value> {
 		// Valid options?
 		opt.valid_for_db()?;
+		// Hybrid search is only planned by the streaming execution engine
+		ensure!(
+			self.fusion.is_none(),
+			Error::InvalidStatement(
+				"The FUSION clause can only be used in a SELECT statement run by the streaming \
+				 execution engine"
+					.to_string(),
+			)
+		);
 		// Assign the statement
 		let stm = Statement::from_select(stk, ctx, opt, parent_doc, self).await?;
 		// Create a new iterator
@@ -1,8 +1,10 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 
+use crate::catalog::providers::TableProvider;
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
+use crate::err::Error;
 use crate::expr::{Base, Value};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{Datetime, TableName};
@@ -11,6 +13,12 @@ use crate::val::{Datetime, TableName};
 pub enum ShowSince {
 	Timestamp(Datetime),
 	Versionstamp(u64),
+	/// The unacknowledged changes of a consumer, optionally as a member of
+	/// its consumer group.
+	Consumer {
+		name: String,
+		member: Option<String>,
+	},
 }
 
 /// A SHOW CHANGES statement for displaying changes made to a table or database.
@@ -23,6 +31,18 @@ pub(crate) struct ShowStatement {
 }
 
 impl ShowStatement {
+	/// Check if the statement only reads from the datastore. Reading as a
+	/// member of a consumer group records the heartbeat of the member.
+	pub(crate) fn read_only(&self) -> bool {
+		!matches!(
+			self.since,
+			ShowSince::Consumer {
+				member: Some(_),
+				..
+			}
+		)
+	}
+
 	/// Process this type returning a computed simple Value
 	#[instrument(level = "trace", name = "ShowStatement::compute", skip_all)]
 	pub(crate) async fn compute(
@@ -37,8 +57,24 @@ impl ShowStatement {
 		let txn = ctx.tx();
 		// Process the show query
 		let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
-		let r = crate::cf::read(&txn, ns, db, self.table.as_ref(), self.since.clone(), self.limit)
-			.await?;
+		let r = match &self.since {
+			ShowSince::Consumer {
+				name,
+				member,
+			} => {
+				let Some(tb) = &self.table else {
+					bail!(Error::InvalidStatement(
+						"A consumer can only show the changes of a table".to_owned()
+					));
+				};
+				let cs = txn.get_tb_consumer(ns, db, tb, name, None).await?;
+				crate::cf::consumer::read(&txn, ns, db, &cs, member.as_deref(), self.limit).await?
+			}
+			since => {
+				crate::cf::read(&txn, ns, db, self.table.as_ref(), since.clone(), self.limit)
+					.await?
+			}
+		};
 		// Return the changes
 		let a = r.iter().cloned().map(|x| x.into_value()).collect::<Result<Vec<Value>>>()?;
 		Ok(a.into())
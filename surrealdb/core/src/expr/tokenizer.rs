@@ -3,13 +3,19 @@ use std::fmt::Display;
 
 use revision::revisioned;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum Tokenizer {
 	Blank,
 	Camel,
 	Class,
 	Punct,
+	/// Unicode word segmentation (UAX #29)
+	#[revision(start = 2)]
+	Unicode,
+	/// Overlapping bigrams of Chinese, Japanese, Korean and Thai text
+	#[revision(start = 2)]
+	Cjk,
 }
 
 impl Display for Tokenizer {
@@ -19,6 +25,8 @@ impl Display for Tokenizer {
 			Self::Camel => "CAMEL",
 			Self::Class => "CLASS",
 			Self::Punct => "PUNCT",
+			Self::Unicode => "UNICODE",
+			Self::Cjk => "CJK",
 		})
 	}
 }
@@ -0,0 +1,29 @@
+use std::time;
+
+use revision::revisioned;
+use surrealdb_types::ToSql;
+
+use crate::expr::Idiom;
+use crate::expr::statements::info::InfoStructure;
+use crate::val::{Duration, Value};
+
+/// The time to live of the records in a table.
+///
+/// A record expires once `duration` has passed since the datetime stored in
+/// its `field`, after which it is deleted by a background task. Records where
+/// the field is not a datetime never expire.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct Ttl {
+	pub duration: time::Duration,
+	pub field: Idiom,
+}
+
+impl InfoStructure for Ttl {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"duration" => Duration(self.duration).into(),
+			"field" => self.field.to_sql().into(),
+		})
+	}
+}
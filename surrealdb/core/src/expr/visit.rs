@@ -22,14 +22,16 @@ use crate::expr::statements::define::config::ConfigInner;
 use crate::expr::statements::define::config::api::ApiConfig;
 use crate::expr::statements::define::config::defaults::DefaultConfig;
 use crate::expr::statements::define::{
-	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineDefault, DefineSequenceStatement,
+	ApiAction, DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement,
+	DefineDefault, DefineSequenceStatement, DefineSinkStatement,
 };
 use crate::expr::statements::rebuild::RebuildStatement;
 use crate::expr::statements::remove::{
-	RemoveApiStatement, RemoveBucketStatement, RemoveSequenceStatement,
+	RemoveApiStatement, RemoveBucketStatement, RemoveConsumerStatement, RemoveSequenceStatement,
+	RemoveSinkStatement,
 };
 use crate::expr::statements::{
-	AccessStatement, AlterStatement, CreateStatement, DefineAccessStatement,
+	AccessStatement, AckStatement, AlterStatement, CreateStatement, DefineAccessStatement,
 	DefineAnalyzerStatement, DefineApiStatement, DefineDatabaseStatement, DefineEventStatement,
 	DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement, DefineModelStatement,
 	DefineModuleStatement, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
@@ -45,7 +47,7 @@ use crate::expr::statements::{
 use crate::expr::{
 	AccessType, Block, ClosureExpr, Data, Expr, Field, Fields, Function, FunctionCall, Idiom,
 	JwtAccess, Kind, KindLiteral, Literal, Lookup, Model, Output, Param, Part, RecordAccess,
-	RecordIdKeyLit, RecordIdKeyRangeLit, RecordIdLit, TopLevelExpr, View,
+	RecordIdKeyLit, RecordIdKeyRangeLit, RecordIdLit, TopLevelExpr, View, WindowCall,
 };
 
 macro_rules! implement_visitor{
@@ -140,6 +142,7 @@ implement_visitor! {
 		TopLevelExpr::Option(s) =>{ this.visit_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_show(s)?; },
+		TopLevelExpr::Ack(s) => {this.visit_ack(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_expr(e)?; },
 		}
 		Ok(())
@@ -228,6 +231,11 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_ack(this, s: &AckStatement){
+		this.visit_expr(&s.versionstamp)?;
+		Ok(())
+	}
+
 	fn visit_expr(this, s: &Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -260,6 +268,9 @@ implement_visitor! {
 			Expr::FunctionCall(f) => {
 				this.visit_function_call(f)?;
 			},
+			Expr::Window(w) => {
+				this.visit_window(w)?;
+			},
 			Expr::Closure(c) => {
 				this.visit_closure(c)?;
 			},
@@ -304,48 +315,66 @@ implement_visitor! {
 		},
 		#[cfg(feature = "gql")]
 		Expr::Match(plan) => {
-			use crate::expr::match_plan::{MatchStage, MutationStage, UpdateData};
+			use crate::expr::match_plan::{MatchPlan, MatchStage, MutationStage, UpdateData};
 			// Walk every reachable Expr across the steps (clause predicates and
-			// mutation-stage values), then the output columns/ORDER BY/SKIP/LIMIT.
-			for stage in plan.stages.iter(){
-				match stage {
-					MatchStage::Read(clause) => {
-						for predicate in clause.predicates.iter(){
-							this.visit_expr(&predicate.expr)?;
-						}
-					}
-					MatchStage::Mutate(MutationStage::Update { data, .. }) => match data {
-						UpdateData::Set(assignments) => {
-							for (_, value) in assignments.iter(){
-								this.visit_expr(value)?;
+			// mutation-stage values), then the output columns/ORDER BY/SKIP/LIMIT,
+			// of the plan and of each composite operand in turn.
+			let MatchPlan { stages, output, compose, .. } = &**plan;
+			let operands = std::iter::once((stages, output))
+				.chain(compose.iter().map(|c| (&c.plan.stages, &c.plan.output)));
+			for (stages, output) in operands {
+				for stage in stages.iter(){
+					match stage {
+						MatchStage::Read(clause) => {
+							for predicate in clause.predicates.iter(){
+								this.visit_expr(&predicate.expr)?;
 							}
 						}
-						UpdateData::Unset(_) => {}
-						UpdateData::Content(expr) => this.visit_expr(expr)?,
-					},
-					MatchStage::Mutate(MutationStage::Delete { .. }) => {}
-					MatchStage::Mutate(MutationStage::Insert(insert)) => {
-						for node in insert.nodes.iter(){
-							this.visit_expr(&node.props)?;
+						MatchStage::Mutate(MutationStage::Update { data, .. }) => match data {
+							UpdateData::Set(assignments) => {
+								for (_, value) in assignments.iter(){
+									this.visit_expr(value)?;
+								}
+							}
+							UpdateData::Unset(_) => {}
+							UpdateData::Content(expr) => this.visit_expr(expr)?,
+						},
+						MatchStage::Mutate(MutationStage::Delete { .. }) => {}
+						MatchStage::Mutate(MutationStage::Insert(insert)) => {
+							for node in insert.nodes.iter(){
+								this.visit_expr(&node.props)?;
+							}
+							for edge in insert.edges.iter(){
+								this.visit_expr(&edge.props)?;
+							}
 						}
-						for edge in insert.edges.iter(){
-							this.visit_expr(&edge.props)?;
+						MatchStage::Exists(stage) => {
+							for subquery in stage.subqueries.iter(){
+								for clause in subquery.clauses.iter(){
+									for predicate in clause.predicates.iter(){
+										this.visit_expr(&predicate.expr)?;
+									}
+								}
+							}
+							for predicate in stage.predicates.iter(){
+								this.visit_expr(&predicate.expr)?;
+							}
 						}
 					}
 				}
-			}
-			if let Some(output) = plan.output.as_ref(){
-				for column in output.columns.iter(){
-					this.visit_expr(&column.expr)?;
-				}
-				for order in output.order.iter(){
-					this.visit_expr(&order.expr)?;
-				}
-				if let Some(skip) = output.skip.as_ref(){
-					this.visit_expr(skip)?;
-				}
-				if let Some(limit) = output.limit.as_ref(){
-					this.visit_expr(limit)?;
+				if let Some(output) = output.as_ref(){
+					for column in output.columns.iter(){
+						this.visit_expr(&column.expr)?;
+					}
+					for order in output.order.iter(){
+						this.visit_expr(&order.expr)?;
+					}
+					if let Some(skip) = output.skip.as_ref(){
+						this.visit_expr(skip)?;
+					}
+					if let Some(limit) = output.limit.as_ref(){
+						this.visit_expr(limit)?;
+					}
 				}
 			}
 		},
@@ -719,6 +748,12 @@ implement_visitor! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_remove_sequence(r)?;
 			},
+			RemoveStatement::Sink(r) => {
+				this.visit_remove_sink(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_remove_consumer(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_remove_module(r)?;
 			},
@@ -811,6 +846,18 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_remove_sink(this, r: &RemoveSinkStatement){
+		this.visit_expr(&r.name)?;
+		this.visit_expr(&r.table_name)?;
+		Ok(())
+	}
+
+	fn visit_remove_consumer(this, r: &RemoveConsumerStatement){
+		this.visit_expr(&r.name)?;
+		this.visit_expr(&r.table_name)?;
+		Ok(())
+	}
+
 	fn visit_relate(this, o: &RelateStatement){
 		this.visit_expr(&o.through)?;
 		this.visit_expr(&o.from)?;
@@ -958,6 +1005,12 @@ implement_visitor! {
 			DefineStatement::Sequence(d) => {
 				this.visit_define_sequence(d)?;
 			},
+			DefineStatement::Sink(d) => {
+				this.visit_define_sink(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_define_consumer(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_define_module(d)?;
 			},
@@ -973,6 +1026,25 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_define_sink(this, d: &DefineSinkStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.target_table)?;
+		this.visit_expr(&d.url)?;
+		this.visit_expr(&d.topic)?;
+		this.visit_expr(&d.backoff)?;
+		this.visit_expr(&d.dead_letter)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
+	fn visit_define_consumer(this, d: &DefineConsumerStatement) {
+		this.visit_expr(&d.name)?;
+		this.visit_expr(&d.target_table)?;
+		this.visit_expr(&d.timeout)?;
+		this.visit_expr(&d.comment)?;
+		Ok(())
+	}
+
 	fn visit_define_bucket(this, d: &DefineBucketStatement) {
 		this.visit_expr(&d.name)?;
 		if let Some(expr) = d.backend.as_ref(){
@@ -1131,6 +1203,9 @@ implement_visitor! {
 		for c in d.cols.iter(){
 			this.visit_expr(c)?;
 		}
+		if let Some(c) = d.cond.as_ref(){
+			this.visit_expr(&c.0)?;
+		}
 		this.visit_expr(&d.comment)?;
 		Ok(())
 	}
@@ -1391,6 +1466,19 @@ implement_visitor! {
 		Ok(())
 	}
 
+	fn visit_window(this, w: &WindowCall){
+		this.visit_function_call(&w.call)?;
+		for p in w.spec.partition.iter(){
+			this.visit_expr(p)?;
+		}
+		if let Some(order) = w.spec.order.as_ref(){
+			for o in order.0.iter(){
+				this.visit_idiom(&o.value)?;
+			}
+		}
+		Ok(())
+	}
+
 
 	fn visit_block(this, value: &Block){
 		for v in value.0.iter(){
@@ -1711,6 +1799,7 @@ implement_visitor_mut! {
 		TopLevelExpr::Option(s) =>{ this.visit_mut_option(s)?; },
 		TopLevelExpr::Use(s) => {this.visit_mut_use(s)?; },
 		TopLevelExpr::Show(s) => {this.visit_mut_show(s)?; },
+		TopLevelExpr::Ack(s) => {this.visit_mut_ack(s)?; },
 		TopLevelExpr::Expr(e) => {this.visit_mut_expr(e)?; },
 		}
 		Ok(())
@@ -1799,6 +1888,11 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_ack(this, s: &mut AckStatement){
+		this.visit_mut_expr(&mut s.versionstamp)?;
+		Ok(())
+	}
+
 	fn visit_mut_expr(this, s: &mut Expr){
 		match s {
 			Expr::Literal(literal) => {
@@ -1831,6 +1925,9 @@ implement_visitor_mut! {
 			Expr::FunctionCall(f) => {
 				this.visit_mut_function_call(f)?;
 			},
+			Expr::Window(w) => {
+				this.visit_mut_window(w)?;
+			},
 			Expr::Closure(c) => {
 				this.visit_mut_closure(c)?;
 			},
@@ -1875,48 +1972,66 @@ implement_visitor_mut! {
 		},
 		#[cfg(feature = "gql")]
 		Expr::Match(plan) => {
-			use crate::expr::match_plan::{MatchStage, MutationStage, UpdateData};
+			use crate::expr::match_plan::{MatchPlan, MatchStage, MutationStage, UpdateData};
 			// Walk every reachable Expr across the steps (clause predicates and
-			// mutation-stage values), then the output columns/ORDER BY/SKIP/LIMIT.
-			for stage in plan.stages.iter_mut(){
-				match stage {
-					MatchStage::Read(clause) => {
-						for predicate in clause.predicates.iter_mut(){
-							this.visit_mut_expr(&mut predicate.expr)?;
-						}
-					}
-					MatchStage::Mutate(MutationStage::Update { data, .. }) => match data {
-						UpdateData::Set(assignments) => {
-							for (_, value) in assignments.iter_mut(){
-								this.visit_mut_expr(value)?;
+			// mutation-stage values), then the output columns/ORDER BY/SKIP/LIMIT,
+			// of the plan and of each composite operand in turn.
+			let MatchPlan { stages, output, compose, .. } = &mut **plan;
+			let operands = std::iter::once((stages, output))
+				.chain(compose.iter_mut().map(|c| (&mut c.plan.stages, &mut c.plan.output)));
+			for (stages, output) in operands {
+				for stage in stages.iter_mut(){
+					match stage {
+						MatchStage::Read(clause) => {
+							for predicate in clause.predicates.iter_mut(){
+								this.visit_mut_expr(&mut predicate.expr)?;
 							}
 						}
-						UpdateData::Unset(_) => {}
-						UpdateData::Content(expr) => this.visit_mut_expr(expr)?,
-					},
-					MatchStage::Mutate(MutationStage::Delete { .. }) => {}
-					MatchStage::Mutate(MutationStage::Insert(insert)) => {
-						for node in insert.nodes.iter_mut(){
-							this.visit_mut_expr(&mut node.props)?;
+						MatchStage::Mutate(MutationStage::Update { data, .. }) => match data {
+							UpdateData::Set(assignments) => {
+								for (_, value) in assignments.iter_mut(){
+									this.visit_mut_expr(value)?;
+								}
+							}
+							UpdateData::Unset(_) => {}
+							UpdateData::Content(expr) => this.visit_mut_expr(expr)?,
+						},
+						MatchStage::Mutate(MutationStage::Delete { .. }) => {}
+						MatchStage::Mutate(MutationStage::Insert(insert)) => {
+							for node in insert.nodes.iter_mut(){
+								this.visit_mut_expr(&mut node.props)?;
+							}
+							for edge in insert.edges.iter_mut(){
+								this.visit_mut_expr(&mut edge.props)?;
+							}
 						}
-						for edge in insert.edges.iter_mut(){
-							this.visit_mut_expr(&mut edge.props)?;
+						MatchStage::Exists(stage) => {
+							for subquery in stage.subqueries.iter_mut(){
+								for clause in subquery.clauses.iter_mut(){
+									for predicate in clause.predicates.iter_mut(){
+										this.visit_mut_expr(&mut predicate.expr)?;
+									}
+								}
+							}
+							for predicate in stage.predicates.iter_mut(){
+								this.visit_mut_expr(&mut predicate.expr)?;
+							}
 						}
 					}
 				}
-			}
-			if let Some(output) = plan.output.as_mut(){
-				for column in output.columns.iter_mut(){
-					this.visit_mut_expr(&mut column.expr)?;
-				}
-				for order in output.order.iter_mut(){
-					this.visit_mut_expr(&mut order.expr)?;
-				}
-				if let Some(skip) = output.skip.as_mut(){
-					this.visit_mut_expr(skip)?;
-				}
-				if let Some(limit) = output.limit.as_mut(){
-					this.visit_mut_expr(limit)?;
+				if let Some(output) = output.as_mut(){
+					for column in output.columns.iter_mut(){
+						this.visit_mut_expr(&mut column.expr)?;
+					}
+					for order in output.order.iter_mut(){
+						this.visit_mut_expr(&mut order.expr)?;
+					}
+					if let Some(skip) = output.skip.as_mut(){
+						this.visit_mut_expr(skip)?;
+					}
+					if let Some(limit) = output.limit.as_mut(){
+						this.visit_mut_expr(limit)?;
+					}
 				}
 			}
 		},
@@ -2290,6 +2405,12 @@ implement_visitor_mut! {
 			RemoveStatement::Sequence(r) => {
 				this.visit_mut_remove_sequence(r)?;
 			},
+			RemoveStatement::Sink(r) => {
+				this.visit_mut_remove_sink(r)?;
+			},
+			RemoveStatement::Consumer(r) => {
+				this.visit_mut_remove_consumer(r)?;
+			},
 			RemoveStatement::Module(r) => {
 				this.visit_mut_remove_module(r)?;
 			},
@@ -2382,6 +2503,18 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_remove_sink(this, r: &mut RemoveSinkStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		this.visit_mut_expr(&mut r.table_name)?;
+		Ok(())
+	}
+
+	fn visit_mut_remove_consumer(this, r: &mut RemoveConsumerStatement){
+		this.visit_mut_expr(&mut r.name)?;
+		this.visit_mut_expr(&mut r.table_name)?;
+		Ok(())
+	}
+
 	fn visit_mut_relate(this, o: &mut RelateStatement){
 		this.visit_mut_expr(&mut o.through)?;
 		this.visit_mut_expr(&mut o.from)?;
@@ -2529,6 +2662,12 @@ implement_visitor_mut! {
 			DefineStatement::Sequence(d) => {
 				this.visit_mut_define_sequence(d)?;
 			},
+			DefineStatement::Sink(d) => {
+				this.visit_mut_define_sink(d)?;
+			},
+			DefineStatement::Consumer(d) => {
+				this.visit_mut_define_consumer(d)?;
+			},
 			DefineStatement::Module(d) => {
 				this.visit_mut_define_module(d)?;
 			},
@@ -2545,6 +2684,25 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_define_sink(this, d: &mut DefineSinkStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.target_table)?;
+		this.visit_mut_expr(&mut d.url)?;
+		this.visit_mut_expr(&mut d.topic)?;
+		this.visit_mut_expr(&mut d.backoff)?;
+		this.visit_mut_expr(&mut d.dead_letter)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
+	fn visit_mut_define_consumer(this, d: &mut DefineConsumerStatement) {
+		this.visit_mut_expr(&mut d.name)?;
+		this.visit_mut_expr(&mut d.target_table)?;
+		this.visit_mut_expr(&mut d.timeout)?;
+		this.visit_mut_expr(&mut d.comment)?;
+		Ok(())
+	}
+
 	fn visit_mut_define_bucket(this, d: &mut DefineBucketStatement) {
 		this.visit_mut_expr(&mut d.name)?;
 		if let Some(expr) = d.backend.as_mut(){
@@ -2701,6 +2859,9 @@ implement_visitor_mut! {
 		for c in d.cols.iter_mut(){
 			this.visit_mut_expr(c)?;
 		}
+		if let Some(c) = d.cond.as_mut(){
+			this.visit_mut_expr(&mut c.0)?;
+		}
 		this.visit_mut_expr(&mut d.comment)?;
 		Ok(())
 	}
@@ -2973,6 +3134,19 @@ implement_visitor_mut! {
 		Ok(())
 	}
 
+	fn visit_mut_window(this, w: &mut WindowCall){
+		this.visit_mut_function_call(&mut w.call)?;
+		for p in w.spec.partition.iter_mut(){
+			this.visit_mut_expr(p)?;
+		}
+		if let Some(order) = w.spec.order.as_mut(){
+			for o in order.0.iter_mut(){
+				this.visit_mut_idiom(&mut o.value)?;
+			}
+		}
+		Ok(())
+	}
+
 
 	fn visit_mut_block(this, value: &mut Block){
 		for v in value.0.iter_mut(){
@@ -0,0 +1,80 @@
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::expr::order::OrderList;
+use crate::expr::{Expr, FunctionCall};
+
+/// A function call evaluated over a window of rows.
+///
+/// Window calls only have a meaning inside the field list of a `SELECT`
+/// statement, where the streaming planner lifts them into a `Window`
+/// operator. They can not be evaluated against a single document.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct WindowCall {
+	pub(crate) call: FunctionCall,
+	pub(crate) spec: WindowSpec,
+}
+
+impl ToSql for WindowCall {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let sql_window: crate::sql::window::WindowCall = self.clone().into();
+		sql_window.fmt_sql(f, fmt);
+	}
+}
+
+/// The `PARTITION BY`, `ORDER BY` and frame of a window.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct WindowSpec {
+	pub(crate) partition: Vec<Expr>,
+	pub(crate) order: Option<OrderList>,
+	pub(crate) frame: Option<WindowFrame>,
+}
+
+impl ToSql for WindowSpec {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let sql_spec: crate::sql::window::WindowSpec = self.clone().into();
+		sql_spec.fmt_sql(f, fmt);
+	}
+}
+
+/// A `ROWS BETWEEN <start> AND <end>` frame.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct WindowFrame {
+	pub(crate) start: WindowBound,
+	pub(crate) end: WindowBound,
+}
+
+impl WindowFrame {
+	/// Resolve the frame to an inclusive row range within a partition of
+	/// `len` rows, for the row at position `idx`.
+	///
+	/// Returns `None` when the frame is empty, e.g. `3 PRECEDING AND 2
+	/// PRECEDING` for the first row of a partition.
+	pub(crate) fn range(&self, idx: usize, len: usize) -> Option<(usize, usize)> {
+		let start = match self.start {
+			WindowBound::UnboundedPreceding => 0,
+			WindowBound::Preceding(n) => idx.saturating_sub(n as usize),
+			WindowBound::CurrentRow => idx,
+			WindowBound::Following(n) => idx.saturating_add(n as usize),
+			WindowBound::UnboundedFollowing => len,
+		};
+		let end = match self.end {
+			WindowBound::UnboundedPreceding => return None,
+			WindowBound::Preceding(n) => idx.checked_sub(n as usize)?,
+			WindowBound::CurrentRow => idx,
+			WindowBound::Following(n) => idx.saturating_add(n as usize),
+			WindowBound::UnboundedFollowing => len.saturating_sub(1),
+		};
+		let end = end.min(len.checked_sub(1)?);
+		(start <= end).then_some((start, end))
+	}
+}
+
+/// One end of a [`WindowFrame`], relative to the current row.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) enum WindowBound {
+	UnboundedPreceding,
+	Preceding(u64),
+	CurrentRow,
+	Following(u64),
+	UnboundedFollowing,
+}
@@ -154,6 +154,7 @@ impl ToSql for CoverStmts<'_> {
 				..
 			}
 			| sql::Expr::FunctionCall(_)
+			| sql::Expr::Window(_)
 			| sql::Expr::Closure(_)
 			| sql::Expr::Break
 			| sql::Expr::Continue
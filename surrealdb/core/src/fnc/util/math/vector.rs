@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::collections::HashSet;
 
 use anyhow::{Result, ensure};
@@ -146,6 +147,170 @@ impl PearsonSimilarity for Vec<Number> {
 	}
 }
 
+pub trait SpearmanSimilarity {
+	fn spearman_similarity(&self, other: &Self) -> Result<Number>;
+}
+
+impl SpearmanSimilarity for Vec<Number> {
+	fn spearman_similarity(&self, other: &Self) -> Result<Number> {
+		check_same_dimension("vector::similarity::spearman", self, other)?;
+		Ok(spearman(self.iter().map(ToFloat::to_float), other.iter().map(ToFloat::to_float)).into())
+	}
+}
+
+/// Buffers reused by [`spearman`], which is called for every candidate of a
+/// KNN search.
+#[derive(Default)]
+struct SpearmanBuffers {
+	a: Vec<f64>,
+	b: Vec<f64>,
+	ranks_a: Vec<f64>,
+	ranks_b: Vec<f64>,
+	order: Vec<usize>,
+}
+
+thread_local! {
+	static SPEARMAN_BUFFERS: RefCell<SpearmanBuffers> = RefCell::default();
+}
+
+/// Spearman rank correlation: the Pearson correlation of the ranks of the
+/// values, where tied values share the average of their ranks.
+pub(crate) fn spearman(a: impl IntoIterator<Item = f64>, b: impl IntoIterator<Item = f64>) -> f64 {
+	SPEARMAN_BUFFERS.with_borrow_mut(|buf| {
+		buf.a.clear();
+		buf.a.extend(a);
+		buf.b.clear();
+		buf.b.extend(b);
+		if buf.a.iter().chain(&buf.b).any(|x| x.is_nan()) {
+			return f64::NAN;
+		}
+		fractional_ranks(&buf.a, &mut buf.order, &mut buf.ranks_a);
+		fractional_ranks(&buf.b, &mut buf.order, &mut buf.ranks_b);
+		// Both rank vectors share the same mean
+		let mean = (buf.a.len() + 1) as f64 / 2.0;
+		let (covar, var_a, var_b) = buf.ranks_a.iter().zip(buf.ranks_b.iter()).fold(
+			(0.0, 0.0, 0.0),
+			|(c, va, vb), (x, y)| {
+				let (dx, dy) = (x - mean, y - mean);
+				(c + dx * dy, va + dx * dx, vb + dy * dy)
+			},
+		);
+		covar / (var_a * var_b).sqrt()
+	})
+}
+
+fn fractional_ranks(v: &[f64], order: &mut Vec<usize>, ranks: &mut Vec<f64>) {
+	order.clear();
+	order.extend(0..v.len());
+	order.sort_unstable_by(|&x, &y| v[x].total_cmp(&v[y]));
+	ranks.clear();
+	ranks.resize(v.len(), 0.0);
+	let mut start = 0;
+	while start < order.len() {
+		let mut end = start + 1;
+		while end < order.len() && v[order[end]] == v[order[start]] {
+			end += 1;
+		}
+		// Average of the 1-based ranks `start + 1..=end`
+		let rank = (start + end + 1) as f64 / 2.0;
+		for &i in &order[start..end] {
+			ranks[i] = rank;
+		}
+		start = end;
+	}
+}
+
+pub trait MahalanobisDistance {
+	/// Mahalanobis distance between two vectors, given the covariance matrix
+	/// of their distribution
+	fn mahalanobis_distance(&self, other: &Self, covariance: &[Vec<Number>]) -> Result<Number>;
+}
+
+impl MahalanobisDistance for Vec<Number> {
+	fn mahalanobis_distance(&self, other: &Self, covariance: &[Vec<Number>]) -> Result<Number> {
+		check_same_dimension("vector::distance::mahalanobis", self, other)?;
+		let diff: Vec<f64> =
+			self.iter().zip(other.iter()).map(|(a, b)| a.to_float() - b.to_float()).collect();
+		Ok(Covariance::new(covariance)?.mahalanobis(&diff)?.into())
+	}
+}
+
+/// The Cholesky factorisation `S = L L^T` of a covariance matrix `S`.
+///
+/// The factorisation is computed once, so that the Mahalanobis distance of
+/// many pairs of vectors, like the candidates of a KNN search, is computed in
+/// `O(n²)` each.
+#[derive(Clone, Debug)]
+pub(crate) struct Covariance(Vec<Vec<f64>>);
+
+impl Covariance {
+	pub(crate) fn new(covariance: &[Vec<Number>]) -> Result<Self> {
+		let n = covariance.len();
+		ensure!(
+			covariance.iter().all(|row| row.len() == n),
+			Error::InvalidFunctionArguments {
+				name: String::from("vector::distance::mahalanobis"),
+				message: String::from(
+					"The covariance matrix must be a square matrix of the same dimension as the vectors.",
+				),
+			}
+		);
+		let s: Vec<Vec<f64>> =
+			covariance.iter().map(|row| row.iter().map(ToFloat::to_float).collect()).collect();
+		let mut l = vec![vec![0.0; n]; n];
+		for j in 0..n {
+			for i in j..n {
+				ensure!(
+					(s[i][j] - s[j][i]).abs() <= 1e-9 * s[i][j].abs().max(s[j][i].abs()),
+					Error::InvalidFunctionArguments {
+						name: String::from("vector::distance::mahalanobis"),
+						message: String::from(
+							"The covariance matrix must be symmetric and positive definite.",
+						),
+					}
+				);
+				let sum = s[i][j] - (0..j).map(|k| l[i][k] * l[j][k]).sum::<f64>();
+				if i == j {
+					ensure!(
+						sum > f64::EPSILON,
+						Error::InvalidFunctionArguments {
+							name: String::from("vector::distance::mahalanobis"),
+							message: String::from(
+								"The covariance matrix must be symmetric and positive definite.",
+							),
+						}
+					);
+					l[j][j] = sum.sqrt();
+				} else {
+					l[i][j] = sum / l[j][j];
+				}
+			}
+		}
+		Ok(Self(l))
+	}
+
+	/// Computes `sqrt(d^T S^-1 d)` for the difference `d` of two vectors, as
+	/// the norm of `y` solving `L y = d` by forward substitution.
+	pub(crate) fn mahalanobis(&self, diff: &[f64]) -> Result<f64> {
+		let l = &self.0;
+		ensure!(
+			diff.len() == l.len(),
+			Error::InvalidFunctionArguments {
+				name: String::from("vector::distance::mahalanobis"),
+				message: String::from(
+					"The covariance matrix must be a square matrix of the same dimension as the vectors.",
+				),
+			}
+		);
+		let mut y = Vec::with_capacity(diff.len());
+		for (i, d) in diff.iter().enumerate() {
+			let sum: f64 = (0..i).map(|k| l[i][k] * y[k]).sum();
+			y.push((d - sum) / l[i][i]);
+		}
+		Ok(y.iter().map(|y| y * y).sum::<f64>().sqrt())
+	}
+}
+
 pub trait ManhattanDistance {
 	fn manhattan_distance(&self, other: &Self) -> Result<Number>;
 }
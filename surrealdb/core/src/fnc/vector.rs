@@ -55,11 +55,11 @@ pub mod distance {
 
 	use crate::ctx::FrozenContext;
 	use crate::doc::CursorDoc;
-	use crate::err::Error;
 	use crate::fnc::args::Optional;
 	use crate::fnc::get_execution_context;
 	use crate::fnc::util::math::vector::{
-		ChebyshevDistance, EuclideanDistance, HammingDistance, ManhattanDistance, MinkowskiDistance,
+		ChebyshevDistance, EuclideanDistance, HammingDistance, MahalanobisDistance,
+		ManhattanDistance, MinkowskiDistance,
 	};
 	use crate::idx::planner::IterationStage;
 	use crate::val::{Number, Value};
@@ -100,10 +100,8 @@ pub mod distance {
 		Ok(Value::None)
 	}
 
-	pub fn mahalanobis((_, _): (Vec<Number>, Vec<Number>)) -> Result<Value> {
-		Err(anyhow::Error::new(Error::Unimplemented(
-			"vector::distance::mahalanobis() function".to_string(),
-		)))
+	pub fn mahalanobis((a, b, c): (Vec<Number>, Vec<Number>, Vec<Vec<Number>>)) -> Result<Value> {
+		Ok(a.mahalanobis_distance(&b, &c)?.into())
 	}
 
 	pub fn manhattan((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value> {
@@ -119,8 +117,9 @@ pub mod similarity {
 
 	use anyhow::Result;
 
-	use crate::err::Error;
-	use crate::fnc::util::math::vector::{CosineSimilarity, JaccardSimilarity, PearsonSimilarity};
+	use crate::fnc::util::math::vector::{
+		CosineSimilarity, JaccardSimilarity, PearsonSimilarity, SpearmanSimilarity,
+	};
 	use crate::val::{Number, Value};
 
 	pub fn cosine((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value> {
@@ -135,10 +134,8 @@ pub mod similarity {
 		Ok(a.pearson_similarity(&b)?.into())
 	}
 
-	pub fn spearman((_, _): (Vec<Number>, Vec<Number>)) -> Result<Value> {
-		Err(anyhow::Error::new(Error::Unimplemented(
-			"vector::similarity::spearman() function".to_string(),
-		)))
+	pub fn spearman((a, b): (Vec<Number>, Vec<Number>)) -> Result<Value> {
+		Ok(a.spearman_similarity(&b)?.into())
 	}
 }
 
@@ -16,10 +16,43 @@
 
 use crate::syn::token::Span;
 
-/// A complete GQL query: a linear program.
+/// A complete GQL query: a linear program, optionally composed with further
+/// linear programs (`compositeQueryExpression`, GQL.g4:504).
 #[derive(Clone, Debug, PartialEq)]
 pub struct GqlQuery {
 	pub program: LinearQuery,
+	/// The further operands of a composite query, in textual order; each
+	/// combines with the result of everything before it (the grammar is
+	/// left-recursive). Empty for a plain linear query.
+	pub composite: Vec<CompositeOperand>,
+}
+
+/// One further operand of a composite query and the conjunction joining it
+/// to the operands before it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompositeOperand {
+	pub conjunction: QueryConjunction,
+	pub query: LinearQuery,
+}
+
+/// A query conjunction (`queryConjunction`, GQL.g4:509): a set operator with
+/// its optional quantifier, or `OTHERWISE`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QueryConjunction {
+	pub kind: ConjunctionKind,
+	/// `ALL` or `DISTINCT`; always `None` for `OTHERWISE`. Absent means
+	/// `DISTINCT`.
+	pub quantifier: Option<SetQuantifier>,
+	pub span: Span,
+}
+
+/// The kind of a [`QueryConjunction`].
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ConjunctionKind {
+	Union,
+	Except,
+	Intersect,
+	Otherwise,
 }
 
 /// A linear query: a sequence of data-accessing steps — `MATCH`/`OPTIONAL`
@@ -479,7 +512,7 @@ pub struct GqlGroupItem {
 	pub span: Span,
 }
 
-/// The set quantifier of a `RETURN` clause (GQL.g4:2405).
+/// The set quantifier of a `RETURN` clause or a set operator (GQL.g4:2405).
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
 pub enum SetQuantifier {
 	Distinct,
@@ -580,6 +613,91 @@ pub enum GqlExpr {
 	List(Vec<GqlExpr>, Span),
 	/// A record literal: `{k: v, …}`.
 	Map(Vec<(Ident, GqlExpr)>, Span),
+	/// A `CASE` expression (`caseSpecification`, GQL.g4:2493). The simple
+	/// form `CASE x WHEN v THEN …` carries its `operand`, and each `WHEN`
+	/// lists the values compared against it; the searched form `CASE WHEN c
+	/// THEN …` has no operand, and each `WHEN` holds exactly one condition.
+	Case {
+		operand: Option<Box<GqlExpr>>,
+		whens: Vec<CaseWhen>,
+		else_result: Option<Box<GqlExpr>>,
+		span: Span,
+	},
+	/// `CAST(expr AS type)` (`castSpecification`, GQL.g4:2528).
+	Cast {
+		expr: Box<GqlExpr>,
+		ty: GqlType,
+		span: Span,
+	},
+	/// A value type test: `expr IS [NOT] TYPED type` (GQL.g4:2054).
+	IsTyped {
+		expr: Box<GqlExpr>,
+		ty: GqlType,
+		negated: bool,
+		span: Span,
+	},
+	/// A label test: `expr IS [NOT] LABELED label-expression` (GQL.g4:2084).
+	IsLabeled {
+		expr: Box<GqlExpr>,
+		label: LabelExpr,
+		negated: bool,
+		span: Span,
+	},
+	/// An endpoint test: `node IS [NOT] SOURCE|DESTINATION OF edge`
+	/// (GQL.g4:2092). `source` is `true` for `SOURCE OF`.
+	IsEndpoint {
+		expr: Box<GqlExpr>,
+		edge: Ident,
+		source: bool,
+		negated: bool,
+		span: Span,
+	},
+	/// An `EXISTS` subquery (`existsPredicate`, GQL.g4:2024): the braced or
+	/// parenthesized graph pattern, or `MATCH` statement block, it tests for a
+	/// match. A bare graph pattern is held as a single [`MatchItem::Match`].
+	Exists {
+		items: Vec<MatchItem>,
+		span: Span,
+	},
+}
+
+/// One `WHEN … THEN …` arm of a [`GqlExpr::Case`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CaseWhen {
+	/// The searched form's condition, or the simple form's comparison values.
+	pub operands: Vec<GqlExpr>,
+	pub result: GqlExpr,
+}
+
+/// A value type named by `CAST` or `IS TYPED`: the subset of the predefined
+/// GQL value types (GQL.g4:1340) with a SurrealDB counterpart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GqlType {
+	pub kind: GqlTypeKind,
+	pub span: Span,
+}
+
+/// The kind of a [`GqlType`].
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum GqlTypeKind {
+	/// `BOOL`, `BOOLEAN`
+	Bool,
+	/// `STRING`, `CHAR`, `VARCHAR`
+	String,
+	/// `INT`, `INTEGER`, `SMALLINT`, `BIGINT` and the sized signed forms.
+	Int,
+	/// `FLOAT`, `REAL`, `DOUBLE [PRECISION]` and the sized forms.
+	Float,
+	/// `DECIMAL`, `DEC`
+	Decimal,
+	/// `DATETIME`, `ZONED DATETIME`, `TIMESTAMP`
+	Datetime,
+	/// `DURATION`
+	Duration,
+	/// `LIST`, `ARRAY`
+	List,
+	/// `RECORD`
+	Record,
 }
 
 impl GqlExpr {
@@ -618,6 +736,30 @@ impl GqlExpr {
 			} => *span,
 			GqlExpr::List(_, span) => *span,
 			GqlExpr::Map(_, span) => *span,
+			GqlExpr::Case {
+				span,
+				..
+			}
+			| GqlExpr::Cast {
+				span,
+				..
+			}
+			| GqlExpr::IsTyped {
+				span,
+				..
+			}
+			| GqlExpr::IsLabeled {
+				span,
+				..
+			}
+			| GqlExpr::IsEndpoint {
+				span,
+				..
+			}
+			| GqlExpr::Exists {
+				span,
+				..
+			} => *span,
 		}
 	}
 
@@ -630,7 +772,27 @@ impl GqlExpr {
 				..
 			}
 			| GqlExpr::Variable(_) => false,
+			// An `EXISTS` body nests through the object-recursion budget, so
+			// its depth is bounded and the derived drop is safe.
+			GqlExpr::Exists {
+				..
+			} => false,
 			GqlExpr::Property(..)
+			| GqlExpr::Case {
+				..
+			}
+			| GqlExpr::Cast {
+				..
+			}
+			| GqlExpr::IsTyped {
+				..
+			}
+			| GqlExpr::IsLabeled {
+				..
+			}
+			| GqlExpr::IsEndpoint {
+				..
+			}
 			| GqlExpr::Unary {
 				..
 			}
@@ -668,12 +830,31 @@ impl GqlExpr {
 			| GqlExpr::Param {
 				..
 			}
-			| GqlExpr::Variable(_) => {}
+			| GqlExpr::Variable(_)
+			| GqlExpr::Exists {
+				..
+			} => {}
 			GqlExpr::Property(base, _, _) => take(base, stack),
 			GqlExpr::Unary {
 				expr,
 				..
 			}
+			| GqlExpr::Cast {
+				expr,
+				..
+			}
+			| GqlExpr::IsTyped {
+				expr,
+				..
+			}
+			| GqlExpr::IsLabeled {
+				expr,
+				..
+			}
+			| GqlExpr::IsEndpoint {
+				expr,
+				..
+			}
 			| GqlExpr::IsBool {
 				expr,
 				..
@@ -698,6 +879,26 @@ impl GqlExpr {
 			GqlExpr::Map(fields, _) => {
 				stack.extend(fields.drain(..).map(|(_, value)| value).filter(GqlExpr::has_children))
 			}
+			GqlExpr::Case {
+				operand,
+				whens,
+				else_result,
+				..
+			} => {
+				for child in operand.iter_mut().chain(else_result.iter_mut()) {
+					take(child, stack);
+				}
+				for when in whens.drain(..) {
+					let CaseWhen {
+						operands,
+						result,
+					} = when;
+					stack.extend(operands.into_iter().filter(GqlExpr::has_children));
+					if result.has_children() {
+						stack.push(result);
+					}
+				}
+			}
 		}
 	}
 }
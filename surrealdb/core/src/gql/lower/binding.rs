@@ -31,6 +31,14 @@
 //! Anchorability (V2_DESIGN §0) is validated per pattern: every pattern needs
 //! ≥1 labelled element OR ≥1 variable already bound by an earlier pattern /
 //! clause. Name validation reuses [`naming`] verbatim.
+//!
+//! An `EXISTS` body is walked with the same rules, between
+//! [`Analyzer::read_exists`] and [`Analyzer::retire_exists`]: a body variable
+//! that names an outer binding correlates with it (it resolves to the outer
+//! id), everything the body declares is local to it and leaves scope once the
+//! body is lowered. A body chain expanding off an outer node may also pass
+//! through further outer nodes — each is rewritten to a hidden binding plus an
+//! `id`-equality, exactly like a within-pattern repeat.
 
 use crate::expr::match_plan::{BindingDef, BindingId, BindingKind};
 use crate::gql::ast::{
@@ -51,6 +59,10 @@ pub(super) struct BindingInfo {
 	/// `OPTIONAL` operand. Consulted by the `nullable()` amendment (V2_DESIGN §8):
 	/// a bare `Variable(v)` is nullable iff `optional_depth(v) > 0`.
 	pub(super) optional_depth: u32,
+	/// `false` once the binding's `EXISTS` body has been lowered: the binding
+	/// stays in the table (its id is still referenced by the body's plan) but
+	/// no longer resolves, so a later clause may reuse the name.
+	pub(super) in_scope: bool,
 }
 
 /// The bindings of a whole MATCH query, indexed by [`BindingId`].
@@ -70,6 +82,17 @@ pub(super) struct Registry {
 	/// `OPTIONAL`). Stamped onto each binding at first declaration so the
 	/// `nullable()` amendment can tell optional-bound variables apart.
 	current_depth: u32,
+	/// Whether a mutation stage precedes the item currently being walked. The
+	/// outer accumulator of a later `OPTIONAL` then cannot be re-read (that would
+	/// re-run its writes), which narrows the block's bound-variable anchors (see
+	/// [`AnchorContext::OptionalAfterWrite`]).
+	after_write: bool,
+	/// The first id declared by the `EXISTS` body being walked, or `None`
+	/// outside a body. Every lower id is an outer binding the body correlates
+	/// with.
+	exists_floor: Option<BindingId>,
+	/// `__x<n>` counter for the `EXISTS` marks.
+	exists_marks: u32,
 }
 
 impl Registry {
@@ -79,6 +102,9 @@ impl Registry {
 			hidden_edges: 0,
 			hidden_nodes: 0,
 			current_depth: 0,
+			after_write: false,
+			exists_floor: None,
+			exists_marks: 0,
 		}
 	}
 
@@ -130,8 +156,19 @@ impl Registry {
 		self.bindings[id as usize].optional_depth
 	}
 
+	/// Whether a mutation stage precedes the item currently being walked.
+	pub(super) fn follows_write(&self) -> bool {
+		self.after_write
+	}
+
+	/// Whether a binding is an outer binding of the `EXISTS` body being
+	/// walked (always `false` outside a body).
+	pub(super) fn is_outer(&self, id: BindingId) -> bool {
+		self.exists_floor.is_some_and(|floor| id < floor)
+	}
+
 	fn lookup(&self, name: &str) -> Option<BindingId> {
-		self.bindings.iter().position(|b| b.name == name).map(|i| i as BindingId)
+		self.bindings.iter().position(|b| b.in_scope && b.name == name).map(|i| i as BindingId)
 	}
 
 	/// Declares a fresh binding at the current `OPTIONAL` depth and returns its
@@ -143,6 +180,7 @@ impl Registry {
 			kind,
 			user_named,
 			optional_depth: self.current_depth,
+			in_scope: true,
 		});
 		id
 	}
@@ -322,6 +360,34 @@ impl Analyzer {
 		Ok(out)
 	}
 
+	/// Declares the bindings of an `EXISTS` body, returning the id of its first
+	/// binding (the floor every outer id sits below) and one
+	/// [`ClauseBindings`] per body clause. The body's bindings stay in scope —
+	/// so its clauses can be lowered against [`Analyzer::registry`] — until
+	/// [`Analyzer::retire_exists`].
+	pub(super) fn read_exists<'ast>(
+		&mut self,
+		items: &'ast [MatchItem],
+	) -> Result<(BindingId, Vec<ClauseBindings<'ast>>), SyntaxError> {
+		let floor = self.registry.bindings.len() as BindingId;
+		self.registry.exists_floor = Some(floor);
+		let mut out = Vec::new();
+		analyze_items(&mut self.registry, items, None, &mut self.groups, &mut out)?;
+		Ok((floor, out))
+	}
+
+	/// Takes the bindings of the `EXISTS` body read at `floor` out of scope and
+	/// returns the name of the fresh `__x<n>` mark the body will set.
+	pub(super) fn retire_exists(&mut self, floor: BindingId) -> String {
+		for binding in self.registry.bindings.iter_mut().skip(floor as usize) {
+			binding.in_scope = false;
+		}
+		self.registry.exists_floor = None;
+		let mark = format!("__x{}", self.registry.exists_marks);
+		self.registry.exists_marks += 1;
+		mark
+	}
+
 	/// The registry built so far (read by clause and output lowering).
 	pub(super) fn registry(&self) -> &Registry {
 		&self.registry
@@ -332,6 +398,12 @@ impl Analyzer {
 		&mut self.registry
 	}
 
+	/// Records that a mutation step has been lowered, so every later read item
+	/// is analysed as following a write.
+	pub(super) fn record_write(&mut self) {
+		self.registry.after_write = true;
+	}
+
 	/// Consumes the analyzer, yielding the completed registry (for the output
 	/// spec and the final [`BindingDef`] list).
 	pub(super) fn into_registry(self) -> Registry {
@@ -356,12 +428,19 @@ fn analyze_items<'ast>(
 				let optional = group.is_some();
 				// The leading clause of an OPTIONAL block is the first clause emitted
 				// for that block id; its leading pattern, when bound-variable
-				// anchored, expands off the OUTER accumulator and the planner realises
-				// it only via the single-hop OptionalExpand fast path (see
-				// `AnchorContext::OptionalBlockLeading`). A later clause of the same
+				// anchored, expands off the OUTER bindings. After a write those can
+				// only be extended by the single-hop OptionalExpand fast path (see
+				// `AnchorContext::OptionalAfterWrite`). A later clause of the same
 				// block expands off the block's own subplan accumulator instead.
 				let block_leading = optional && out.last().map(|c| c.optional_group) != Some(group);
-				let patterns = analyze_clause(registry, clause, optional, block_leading)?;
+				let leading = if block_leading && registry.after_write {
+					Some(AnchorContext::OptionalAfterWrite {
+						lone: items.len() == 1 && clause.patterns.len() == 1,
+					})
+				} else {
+					None
+				};
+				let patterns = analyze_clause(registry, clause, leading)?;
 				out.push(ClauseBindings {
 					clause,
 					optional_group: group,
@@ -392,16 +471,13 @@ fn analyze_optional<'ast>(
 }
 
 /// Walks one clause's patterns, declaring their bindings and validating
-/// anchorability for each. `optional` is whether the clause is the body of an
-/// `OPTIONAL` operand; `block_leading` is whether this clause leads its OPTIONAL
-/// block (its leading pattern expands off the OUTER accumulator via the single-
-/// hop OptionalExpand fast path, so its bound-variable anchor is single-hop only
-/// — see [`AnchorContext`]).
+/// anchorability for each. `leading` overrides the [`AnchorContext`] of the
+/// clause's leading pattern (an OPTIONAL block's leading clause after a write);
+/// every other pattern uses [`AnchorContext::General`].
 fn analyze_clause(
 	registry: &mut Registry,
 	clause: &MatchClause,
-	optional: bool,
-	block_leading: bool,
+	leading: Option<AnchorContext>,
 ) -> Result<Vec<PatternBindings>, SyntaxError> {
 	if clause.patterns.is_empty() {
 		return Err(syntax_error!(
@@ -413,19 +489,16 @@ fn analyze_clause(
 	for (index, pattern) in clause.patterns.iter().enumerate() {
 		// Anchor context for this pattern (mirrors the planner's anchor selection,
 		// `exec/planner/match_plan.rs`):
-		// - the leading pattern of a MANDATORY clause is planned as a standalone self-rooted
-		//   sub-tree — no bound-variable anchor;
-		// - the leading pattern of an OPTIONAL block's LEADING clause expands off the OUTER
-		//   accumulator via OptionalExpand, which is single-hop only;
-		// - everything else (a later pattern in a clause, or any pattern of a later clause of an
-		//   optional block) expands off the within-subplan accumulator with the full bound-variable
-		//   anchor set (any chain forward, single-hop reverse).
-		let context = if index == 0 && !optional {
-			AnchorContext::MandatoryLeading
-		} else if index == 0 && block_leading {
-			AnchorContext::OptionalBlockLeading
-		} else {
-			AnchorContext::Inner
+		// - the leading pattern of an OPTIONAL block's LEADING clause, once a write stage has run,
+		//   can only expand off the OUTER accumulator via the single-hop OptionalExpand (the
+		//   correlated block subplan would re-read the accumulator, re-running its writes);
+		// - everything else expands off the rows bound before it with the full bound-variable
+		//   anchor set (any chain forward, single-hop reverse): a later mandatory clause extends
+		//   the accumulator directly, and an optional block is seeded from the outer bindings it
+		//   reuses.
+		let context = match leading {
+			Some(context) if index == 0 => context,
+			_ => AnchorContext::General,
 		};
 		patterns.push(analyze_pattern(registry, pattern, context)?);
 	}
@@ -479,12 +552,29 @@ fn analyze_pattern(
 	let mut used: Vec<BindingId> = Vec::new();
 	let mut node_equalities: Vec<(BindingId, BindingId)> = Vec::new();
 
+	// An `EXISTS` body chain that expands off an outer start node binds its
+	// step nodes from the traversal, so a step node naming another outer
+	// binding must not overwrite it: it becomes a hidden node plus an
+	// `id`-equality with the outer binding (the correlation).
+	let correlates_targets = registry.exists_floor.is_some()
+		&& !self_rootable(pattern)
+		&& node_reuses_bound_var(registry, &pattern.start);
+
 	let start = declare_node(registry, &pattern.start, &mut used, &mut node_equalities)?;
 
 	let mut steps = Vec::with_capacity(pattern.steps.len());
 	for step in &pattern.steps {
 		let edge = declare_edge(registry, &step.edge)?;
-		let node = declare_node(registry, &step.node, &mut used, &mut node_equalities)?;
+		let node = match outer_node(registry, &step.node) {
+			Some((ident, outer)) if correlates_targets => {
+				let outer = reuse_as(registry, ident, outer, BindingKind::Node)?;
+				let hidden = registry.declare_hidden_node();
+				node_equalities.push((outer, hidden));
+				used.push(hidden);
+				hidden
+			}
+			_ => declare_node(registry, &step.node, &mut used, &mut node_equalities)?,
+		};
 		steps.push((edge, node));
 	}
 
@@ -585,19 +675,22 @@ fn pattern_is_anchorable(registry: &Registry, pattern: &PathPattern) -> bool {
 /// selection, `exec/planner/match_plan.rs`).
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum AnchorContext {
-	/// The leading pattern of a mandatory clause: planned as a standalone
-	/// self-rooted sub-tree, so no bound-variable anchor is available.
-	MandatoryLeading,
-	/// The leading pattern of an OPTIONAL block's leading clause: it expands off
-	/// the OUTER accumulator, but only via the single-hop `OptionalExpand` fast
-	/// path, so a bound-variable anchor is realisable ONLY as a single,
-	/// non-quantified hop. (A self-rootable shape — labelled start, single labelled
-	/// edge — is fine too; that becomes the LeftJoin general path.)
-	OptionalBlockLeading,
-	/// Any pattern that expands off a WITHIN-subplan accumulator (a later pattern
-	/// of a clause, or any pattern of a non-leading clause of an optional block):
-	/// the full bound-variable anchor set (any chain forward, single-hop reverse).
-	Inner,
+	/// The leading pattern of an OPTIONAL block's leading clause when a write
+	/// stage precedes the block. The planner seeds a correlated block from a
+	/// second read of the outer accumulator, which would re-run those writes, so
+	/// a bound-variable anchor is realisable ONLY through the single-hop
+	/// `OptionalExpand` fast path: a single, non-quantified hop without a path
+	/// variable, as the `lone` pattern of a single-clause block. (A self-rootable
+	/// shape — labelled start, single labelled edge — is fine too; that becomes
+	/// the LeftJoin general path.)
+	OptionalAfterWrite {
+		lone: bool,
+	},
+	/// Any other pattern: it expands off the rows bound before it — the
+	/// accumulator of earlier clauses, the running sub-tree of its own clause or
+	/// block, or the outer bindings seeding an OPTIONAL block — with the full
+	/// bound-variable anchor set (any chain forward, single-hop reverse).
+	General,
 }
 
 /// Whether the planner can physically realise a pattern, given the bindings
@@ -612,15 +705,14 @@ enum AnchorContext {
 ///   quantified or multi-hop edge anchor is not realised);
 /// - a **bound start node** (forward) / **single-hop bound far node** (reverse) expansion off the
 ///   accumulator — its availability and chain length depend on the context:
-///   - [`AnchorContext::MandatoryLeading`]: not available (the sub-tree must be standalone);
-///   - [`AnchorContext::OptionalBlockLeading`]: a SINGLE non-quantified hop only (the
+///   - [`AnchorContext::OptionalAfterWrite`]: a SINGLE non-quantified hop only (the
 ///     `OptionalExpand` fast path off the outer accumulator);
-///   - [`AnchorContext::Inner`]: the full set (any chain forward, single-hop reverse).
+///   - [`AnchorContext::General`]: the full set (any chain forward, single-hop reverse).
 ///
 /// Anything else (a label only mid-chain or on a far node, a multi-hop edge-only
 /// anchor, a quantified edge anchor, a multi-hop reverse bound anchor, or a
-/// multi-hop / quantified bound-variable anchor leading an optional block) is
-/// anchorable in the abstract but out of planner scope.
+/// multi-hop / quantified bound-variable anchor leading an optional block after a
+/// write) is anchorable in the abstract but out of planner scope.
 ///
 /// A bound-variable expansion is additionally only realisable when the nodes it
 /// *introduces* are not already bound by an earlier pattern / clause: the
@@ -649,26 +741,24 @@ fn pattern_is_realisable(
 	// Bound-variable anchors, restricted by context.
 	//
 	// `forward_ok` decides whether a bound-START forward expansion of THIS chain is
-	// realisable from the bound variable alone:
-	// - within a subplan ([`AnchorContext::Inner`]) the planner walks any chain off the
-	//   accumulator's rows, quantified or not;
-	// - an optional block's leading clause expands off the OUTER accumulator only via the
-	//   single-hop, non-quantified `OptionalExpand` (a quantified hop is a `PathExpand`, which the
-	//   fast path does not build, and the standalone subplan cannot self-root an unlabelled
-	//   quantified start);
-	// - a mandatory leading clause has no bound-variable anchor.
-	let forward_ok = match context {
-		AnchorContext::MandatoryLeading => false,
-		AnchorContext::OptionalBlockLeading => matches!(
-			pattern.steps.as_slice(),
-			[step] if step.edge.quantifier.is_none()
-		),
-		AnchorContext::Inner => true,
+	// realisable from the bound variable alone, and `reverse_ok` the same for a
+	// single-hop bound-FAR reverse expansion:
+	// - in general the planner walks any chain off the rows bound before the pattern, quantified
+	//   or not, and a single hop (quantified or not) in reverse;
+	// - an optional block leading after a write expands off the OUTER accumulator only via the
+	//   single-hop, non-quantified `OptionalExpand`, which binds no path variable and handles only
+	//   a lone pattern.
+	let (forward_ok, reverse_ok) = match context {
+		AnchorContext::OptionalAfterWrite {
+			lone,
+		} => {
+			let fast_path = lone
+				&& pattern.path_var.is_none()
+				&& matches!(pattern.steps.as_slice(), [step] if step.edge.quantifier.is_none());
+			(fast_path, fast_path)
+		}
+		AnchorContext::General => (true, true),
 	};
-	// A reverse bound-far anchor is always a single hop; it is available off a
-	// within-subplan accumulator and, single-hop and unquantified, off the outer
-	// accumulator (OptionalExpand reverse). A mandatory leading clause has none.
-	let reverse_ok = !matches!(context, AnchorContext::MandatoryLeading);
 
 	// Bound start node ⇒ forward expansion off the accumulator. The step
 	// (introduced) nodes must not already be bound.
@@ -679,12 +769,9 @@ fn pattern_is_realisable(
 		return true;
 	}
 	// Single-hop bound far node ⇒ reverse expansion off the accumulator. The
-	// start (introduced) node must not already be bound. A reverse anchor off the
-	// outer accumulator (OptionalExpand) is also single-hop, non-quantified.
+	// start (introduced) node must not already be bound.
 	if reverse_ok
 		&& let [step] = pattern.steps.as_slice()
-		&& (!matches!(context, AnchorContext::OptionalBlockLeading)
-			|| step.edge.quantifier.is_none())
 		&& node_reuses_bound_var(registry, &step.node)
 		&& expansion_targets_are_fresh(registry, pattern, ExpandFrom::Far)
 	{
@@ -728,7 +815,11 @@ fn expansion_targets_are_fresh(
 		// Forward: every step node is introduced by the expansion.
 		ExpandFrom::Start => {
 			for step in &pattern.steps {
-				if overwrites_cross_pattern_binding(registry, &step.node, &earlier) {
+				// An outer node inside an `EXISTS` body is rewritten to a hidden
+				// binding (see `analyze_pattern`), so it is never overwritten.
+				if overwrites_cross_pattern_binding(registry, &step.node, &earlier)
+					&& outer_node(registry, &step.node).is_none()
+				{
 					return false;
 				}
 				if let Some(name) = step.node.var.as_ref().map(|i| i.name.as_str()) {
@@ -757,6 +848,25 @@ fn overwrites_cross_pattern_binding(
 	})
 }
 
+/// Whether a pattern self-roots on its own labelled element (a labelled start
+/// node, or a single-hop, non-quantified labelled edge) rather than expanding
+/// off a bound node. Mirrors the planner's `self_rootable`.
+fn self_rootable(pattern: &PathPattern) -> bool {
+	pattern.start.label.is_some()
+		|| matches!(
+			pattern.steps.as_slice(),
+			[step] if step.edge.label.is_some() && step.edge.quantifier.is_none()
+		)
+}
+
+/// The variable and outer binding a node element names, when it names an
+/// outer binding of the `EXISTS` body being walked.
+fn outer_node<'a>(registry: &Registry, node: &'a NodePattern) -> Option<(&'a Ident, BindingId)> {
+	let ident = node.var.as_ref()?;
+	let id = registry.lookup(&ident.name)?;
+	registry.is_outer(id).then_some((ident, id))
+}
+
 /// Whether a node element names a variable already present in the registry.
 fn node_reuses_bound_var(registry: &Registry, node: &NodePattern) -> bool {
 	node.var.as_ref().is_some_and(|ident| registry.lookup(&ident.name).is_some())
@@ -797,8 +907,12 @@ fn declare_node(
 					// so a shallower pattern cannot anchor / equi-join on it. A
 					// reuse at the same-or-deeper depth is a fine correlated /
 					// shared binding; a mandatory binding reused inside an optional
-					// is fine too (its depth 0 ≤ current).
-					if registry.optional_depth(existing) > registry.current_depth {
+					// is fine too (its depth 0 ≤ current). An `EXISTS` body
+					// only correlates with an outer binding, so it may read an
+					// optional one.
+					if registry.optional_depth(existing) > registry.current_depth
+						&& !registry.is_outer(existing)
+					{
 						return Err(optional_rebind(ident));
 					}
 					if used.contains(&existing) {
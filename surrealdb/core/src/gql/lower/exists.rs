@@ -0,0 +1,230 @@
+//! `EXISTS` subquery lowering.
+//!
+//! An `EXISTS { … }` predicate is planned as a semi-join rather than evaluated
+//! per row: its body lowers to read clauses of their own, resolved against the
+//! bindings declared before it, and the planner runs it once per distinct
+//! value of the outer bindings the body reads (its correlated bindings). Every
+//! outer row the body matched gains the subquery's `__x<n>` mark, and the
+//! `EXISTS` expression only reads that mark — so `NOT EXISTS`, an `EXISTS`
+//! under `OR` and an `EXISTS` in a `RETURN` item all lower through the
+//! ordinary expression rules.
+//!
+//! The subqueries of a mandatory `MATCH` clause's predicates form the
+//! [`ExistsStage`] that follows the clause, together with the conjuncts that
+//! read their marks; the subqueries of the `RETURN` items, `GROUP BY` and
+//! `ORDER BY` keys form a last stage with no conjuncts. An `EXISTS` inside an
+//! `OPTIONAL`, after a write, nested in another `EXISTS`, or whose body holds
+//! an `OPTIONAL` is rejected.
+//!
+//! [`ExistsStage`]: crate::expr::match_plan::ExistsStage
+
+use reblessive::Stk;
+
+use crate::expr::match_plan::{BindingId, ExistsSubquery};
+use crate::gql::ast::{
+	ElementPredicate, GqlExpr, MatchClause, MatchItem, ReturnClause, ReturnItems,
+};
+use crate::gql::lower::binding::Analyzer;
+use crate::gql::lower::pattern;
+use crate::syn::error::{SyntaxError, bail, syntax_error};
+use crate::syn::token::Span;
+
+/// Lowers the `EXISTS` subqueries of one position (a clause's predicates, or
+/// the output), in textual order, returning them with the marks the
+/// position's expressions read, keyed by the span of each `EXISTS`.
+pub(super) async fn lower_subqueries(
+	stk: &mut Stk,
+	analyzer: &mut Analyzer,
+	subqueries: &[&GqlExpr],
+) -> Result<(Vec<ExistsSubquery>, Vec<(Span, String)>), SyntaxError> {
+	let mut lowered = Vec::with_capacity(subqueries.len());
+	let mut marks = Vec::with_capacity(subqueries.len());
+	for subquery in subqueries {
+		let GqlExpr::Exists {
+			items,
+			span,
+		} = subquery
+		else {
+			return Err(syntax_error!("Internal error: not an EXISTS subquery", @subquery.span()));
+		};
+		// The semi-join re-reads the binding table, which would re-run the
+		// writes before it.
+		if analyzer.registry().follows_write() {
+			bail!(
+				"`EXISTS` after a write statement is not supported yet",
+				@*span => "test for the pattern before the first write"
+			);
+		}
+		for item in items {
+			match item {
+				MatchItem::Optional(block) => bail!(
+					"OPTIONAL inside an `EXISTS` subquery is not supported yet",
+					@block.span => "an OPTIONAL never changes whether the subquery matches; drop it"
+				),
+				MatchItem::Match(clause) => {
+					if let Some(nested) = clause_exists(clause).first() {
+						bail!("Nested `EXISTS` subqueries are not supported yet", @nested.span());
+					}
+				}
+			}
+		}
+
+		let (floor, bodies) = analyzer.read_exists(items)?;
+		let mut clauses = Vec::with_capacity(bodies.len());
+		let mut correlated: Vec<BindingId> = Vec::new();
+		for body in &bodies {
+			let (clause, _) = pattern::lower_clause(stk, body, analyzer.registry(), &[]).await?;
+			// The outer bindings the body reads: those its patterns reuse and
+			// those its predicates depend on.
+			for pattern in &clause.patterns {
+				correlated.push(pattern.start.binding);
+				for (edge, node) in &pattern.steps {
+					correlated.push(edge.binding);
+					correlated.push(node.binding);
+				}
+			}
+			for predicate in &clause.predicates {
+				correlated.extend(predicate.deps.iter().copied());
+			}
+			clauses.push(clause);
+		}
+		correlated.retain(|id| *id < floor);
+		correlated.sort_unstable();
+		correlated.dedup();
+
+		let mark = analyzer.retire_exists(floor);
+		marks.push((*span, mark.clone()));
+		lowered.push(ExistsSubquery {
+			mark,
+			correlated,
+			clauses,
+		});
+	}
+	Ok((lowered, marks))
+}
+
+/// The `EXISTS` subqueries in a clause's predicate sources (its `WHERE`, the
+/// inline element `WHERE`s and property maps), in textual order.
+pub(super) fn clause_exists(clause: &MatchClause) -> Vec<&GqlExpr> {
+	let mut out = Vec::new();
+	if let Some(where_clause) = &clause.where_clause {
+		collect_exists(where_clause, &mut out);
+	}
+	for pattern in &clause.patterns {
+		let predicates = std::iter::once(&pattern.start.predicate).chain(
+			pattern.steps.iter().flat_map(|step| [&step.edge.predicate, &step.node.predicate]),
+		);
+		for predicate in predicates {
+			match predicate {
+				Some(ElementPredicate::Where(expr)) => collect_exists(expr, &mut out),
+				Some(ElementPredicate::Props(props)) => {
+					for (_, value) in props {
+						collect_exists(value, &mut out);
+					}
+				}
+				None => {}
+			}
+		}
+	}
+	out
+}
+
+/// The `EXISTS` subqueries in a `RETURN` clause's items, `GROUP BY` and
+/// `ORDER BY` keys, in textual order.
+pub(super) fn output_exists(ret: &ReturnClause) -> Vec<&GqlExpr> {
+	let mut out = Vec::new();
+	if let ReturnItems::Items(items) = &ret.items {
+		for item in items {
+			collect_exists(&item.expr, &mut out);
+		}
+	}
+	for item in &ret.group_by {
+		collect_exists(&item.expr, &mut out);
+	}
+	for item in &ret.order_by {
+		collect_exists(&item.expr, &mut out);
+	}
+	out
+}
+
+/// Whether an expression holds an `EXISTS` subquery.
+pub(super) fn contains_exists(expr: &GqlExpr) -> bool {
+	let mut out = Vec::new();
+	collect_exists(expr, &mut out);
+	!out.is_empty()
+}
+
+/// Pushes the `EXISTS` subqueries of an expression onto `out`, in textual
+/// order, without descending into their bodies. Iterative: the parser builds
+/// arbitrarily deep linear chains.
+fn collect_exists<'a>(expr: &'a GqlExpr, out: &mut Vec<&'a GqlExpr>) {
+	let mut stack = vec![expr];
+	while let Some(e) = stack.pop() {
+		match e {
+			GqlExpr::Exists {
+				..
+			} => out.push(e),
+			GqlExpr::Property(inner, _, _) => stack.push(inner),
+			GqlExpr::Unary {
+				expr,
+				..
+			}
+			| GqlExpr::IsBool {
+				expr,
+				..
+			}
+			| GqlExpr::IsNull {
+				expr,
+				..
+			}
+			| GqlExpr::Cast {
+				expr,
+				..
+			}
+			| GqlExpr::IsTyped {
+				expr,
+				..
+			}
+			| GqlExpr::IsLabeled {
+				expr,
+				..
+			}
+			| GqlExpr::IsEndpoint {
+				expr,
+				..
+			} => stack.push(expr),
+			GqlExpr::Binary {
+				left,
+				right,
+				..
+			} => {
+				stack.push(right);
+				stack.push(left);
+			}
+			GqlExpr::FunctionCall {
+				args,
+				..
+			} => stack.extend(args.iter().rev()),
+			GqlExpr::List(items, _) => stack.extend(items.iter().rev()),
+			GqlExpr::Map(fields, _) => stack.extend(fields.iter().rev().map(|(_, value)| value)),
+			GqlExpr::Case {
+				operand,
+				whens,
+				else_result,
+				..
+			} => {
+				stack.extend(else_result.as_deref());
+				for when in whens.iter().rev() {
+					stack.push(&when.result);
+					stack.extend(when.operands.iter().rev());
+				}
+				stack.extend(operand.as_deref());
+			}
+			GqlExpr::Literal(..)
+			| GqlExpr::Param {
+				..
+			}
+			| GqlExpr::Variable(_) => {}
+		}
+	}
+}
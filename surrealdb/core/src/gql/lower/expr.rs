@@ -20,6 +20,12 @@
 //! spines, property and `NOT` chains) without consuming its nesting budget,
 //! so the lowering must not recurse on the machine stack either.
 //!
+//! `CASE`, `CAST` and the `IS TYPED`/`IS LABELED`/`IS SOURCE|DESTINATION OF`
+//! tests lower onto conditionals, casts and `type::is_*`/`record::tb` calls,
+//! guarded so a null operand yields `NULL` (UNKNOWN in predicate position). An
+//! `EXISTS` subquery is planned as a semi-join that marks the rows it matches
+//! (see `exists`); the expression itself only reads its `__x<n>` mark.
+//!
 //! Expressions are still built as [`crate::sql::Expr`] here and converted to
 //! [`crate::expr::Expr`] per slot by the caller; the guard and NNF machinery is
 //! unchanged from v1, only the variable-addressing leaf differs.
@@ -27,12 +33,16 @@
 use reblessive::Stk;
 
 use crate::expr::match_plan::{BindingId, BindingKind};
-use crate::gql::ast::{BinaryOp, GqlExpr, GqlLiteral, Ident, SetQuantifier, TruthValue, UnaryOp};
+use crate::gql::ast::{
+	BinaryOp, CaseWhen, GqlExpr, GqlLiteral, GqlType, GqlTypeKind, Ident, LabelExpr, SetQuantifier,
+	TruthValue, UnaryOp,
+};
 use crate::gql::lower::binding::Registry;
 use crate::gql::lower::naming;
 use crate::sql::function::{Function, FunctionCall};
 use crate::sql::literal::ObjectEntry;
-use crate::sql::{BinaryOperator, Expr, Idiom, Literal, Param, Part, PrefixOperator};
+use crate::sql::statements::IfelseStatement;
+use crate::sql::{BinaryOperator, Expr, Idiom, Kind, Literal, Param, Part, PrefixOperator};
 use crate::syn::error::{SyntaxError, bail, syntax_error};
 use crate::syn::token::Span;
 
@@ -51,6 +61,11 @@ pub(super) struct Scope<'a> {
 	/// `GROUP BY` keys, and an aggregate's own arguments — so nested aggregates
 	/// are rejected).
 	pub(super) allow_aggregates: bool,
+	/// The `EXISTS` subqueries planned for this position, each keyed by the
+	/// span of its expression and naming the `__x<n>` mark it sets. Empty where
+	/// `EXISTS` is not supported (pattern property maps of an `OPTIONAL`,
+	/// mutations).
+	pub(super) exists: &'a [(Span, String)],
 }
 
 impl<'a> Scope<'a> {
@@ -60,6 +75,7 @@ impl<'a> Scope<'a> {
 		Scope {
 			registry: self.registry,
 			allow_aggregates: false,
+			exists: self.exists,
 		}
 	}
 }
@@ -111,6 +127,35 @@ impl Scope<'_> {
 		parts.extend(props.iter().map(|p| Part::Field(p.name.clone().into())));
 		Ok(Expr::Idiom(Idiom(parts)))
 	}
+
+	/// The mark an `EXISTS` subquery sets on the binding row: `true` when the
+	/// subquery matched the row, `NULL` otherwise.
+	fn exists_mark(&self, span: Span) -> Result<Expr, SyntaxError> {
+		match self.exists.iter().find(|(exists, _)| *exists == span) {
+			Some((_, mark)) => Ok(Expr::Idiom(Idiom(vec![Part::Field(mark.clone().into())]))),
+			None => bail!(
+				"`EXISTS` is only supported in MATCH WHERE clauses and RETURN items",
+				@span => "move the subquery into the WHERE clause of a MATCH"
+			),
+		}
+	}
+
+	/// Resolves the variable an element test (`IS LABELED`, `IS SOURCE OF`, …)
+	/// reads, which must be a bare node or edge variable of the given `kinds`.
+	fn element_variable<'e>(
+		&self,
+		expr: &'e GqlExpr,
+		kinds: &[BindingKind],
+		message: &str,
+	) -> Result<&'e Ident, SyntaxError> {
+		if let GqlExpr::Variable(ident) = expr {
+			let id = self.registry.resolve(ident)?;
+			if kinds.contains(&self.registry.kind(id)) {
+				return Ok(ident);
+			}
+		}
+		bail!("{message}", @expr.span() => "test a pattern variable")
+	}
 }
 
 /// Lowers a GQL expression in value position (projections, sort keys,
@@ -146,6 +191,30 @@ pub(super) async fn lower_value(
 				expr: Box::new(operand),
 			})
 		}
+		GqlExpr::Binary {
+			left,
+			op: BinaryOp::Xor,
+			right,
+			..
+		} => {
+			// Two booleans differ exactly when one of them is true; a null
+			// operand makes the result null.
+			let left = stk.run(|stk| lower_value(stk, left, scope)).await?;
+			let right = stk.run(|stk| lower_value(stk, right, scope)).await?;
+			let any_null = Expr::Binary {
+				left: Box::new(null_test(left.clone(), false)),
+				op: BinaryOperator::Or,
+				right: Box::new(null_test(right.clone(), false)),
+			};
+			Ok(Expr::IfElse(Box::new(IfelseStatement {
+				exprs: vec![(any_null, Expr::Literal(Literal::Null))],
+				close: Some(Expr::Binary {
+					left: Box::new(left),
+					op: BinaryOperator::NotEqual,
+					right: Box::new(right),
+				}),
+			})))
+		}
 		GqlExpr::Binary {
 			left,
 			op,
@@ -192,6 +261,39 @@ pub(super) async fn lower_value(
 			}
 			Ok(Expr::Literal(Literal::Object(entries)))
 		}
+		GqlExpr::Case {
+			operand,
+			whens,
+			else_result,
+			span,
+		} => lower_case(stk, operand.as_deref(), whens, else_result.as_deref(), *span, scope).await,
+		GqlExpr::Cast {
+			expr: operand,
+			ty,
+			..
+		} => {
+			// `<kind> NULL` is an error in SurrealQL, while a GQL cast of null is
+			// null.
+			let value = stk.run(|stk| lower_value(stk, operand, scope)).await?;
+			let cast = Expr::Prefix {
+				op: PrefixOperator::Cast(cast_kind(ty)),
+				expr: Box::new(value.clone()),
+			};
+			Ok(null_guarded(&[value], cast))
+		}
+		GqlExpr::IsTyped {
+			..
+		}
+		| GqlExpr::IsLabeled {
+			..
+		}
+		| GqlExpr::IsEndpoint {
+			..
+		} => lower_element_test(stk, expr, false, false, scope).await,
+		GqlExpr::Exists {
+			span,
+			..
+		} => Ok(equality(scope.exists_mark(*span)?, Literal::Bool(true), false)),
 	}
 }
 
@@ -232,9 +334,34 @@ pub(super) async fn lower_predicate(
 		}
 		GqlExpr::Binary {
 			op: BinaryOp::Xor,
-			span,
+			left,
+			right,
 			..
-		} => reject_xor(*span),
+		} => {
+			// `a XOR b` is TRUE exactly when one side is TRUE and the other
+			// FALSE; negated, when both are TRUE or both FALSE. A side lowered
+			// with `negated` is TRUE exactly when it is FALSE, so an UNKNOWN side
+			// satisfies neither branch and excludes the row.
+			let left_true = stk.run(|stk| lower_predicate(stk, left, false, scope)).await?;
+			let left_false = stk.run(|stk| lower_predicate(stk, left, true, scope)).await?;
+			let right_true = stk.run(|stk| lower_predicate(stk, right, false, scope)).await?;
+			let right_false = stk.run(|stk| lower_predicate(stk, right, true, scope)).await?;
+			let (first, second) = if negated {
+				((left_true, right_true), (left_false, right_false))
+			} else {
+				((left_true, right_false), (left_false, right_true))
+			};
+			let both = |(left, right): (Expr, Expr)| Expr::Binary {
+				left: Box::new(left),
+				op: BinaryOperator::And,
+				right: Box::new(right),
+			};
+			Ok(Expr::Binary {
+				left: Box::new(both(first)),
+				op: BinaryOperator::Or,
+				right: Box::new(both(second)),
+			})
+		}
 		GqlExpr::Binary {
 			op:
 				op @ (BinaryOp::Eq
@@ -253,6 +380,21 @@ pub(super) async fn lower_predicate(
 		| GqlExpr::IsNull {
 			..
 		} => lower_truth_test(stk, expr, negated, scope).await,
+		GqlExpr::IsTyped {
+			..
+		}
+		| GqlExpr::IsLabeled {
+			..
+		}
+		| GqlExpr::IsEndpoint {
+			..
+		} => lower_element_test(stk, expr, negated, true, scope).await,
+		// A row the subquery did not match carries no mark, so `NOT EXISTS`
+		// (`mark != true`) keeps it.
+		GqlExpr::Exists {
+			span,
+			..
+		} => Ok(equality(scope.exists_mark(*span)?, Literal::Bool(true), negated)),
 		GqlExpr::Literal(GqlLiteral::Bool(b), _) => Ok(Expr::Literal(Literal::Bool(*b != negated))),
 		// Any other expression in predicate position is tested against an
 		// explicit boolean, so that a NULL/NONE value excludes the row:
@@ -477,6 +619,269 @@ fn equality(left: Expr, literal: Literal, negated: bool) -> Expr {
 	}
 }
 
+/// `IF a = NULL OR a = NONE OR … THEN NULL ELSE result END`: `result`, or
+/// `NULL` when any of `atoms` is null.
+fn null_guarded(atoms: &[Expr], result: Expr) -> Expr {
+	let Some(any_null) =
+		atoms.iter().map(|atom| null_test(atom.clone(), false)).reduce(|left, right| {
+			Expr::Binary {
+				left: Box::new(left),
+				op: BinaryOperator::Or,
+				right: Box::new(right),
+			}
+		})
+	else {
+		return result;
+	};
+	Expr::IfElse(Box::new(IfelseStatement {
+		exprs: vec![(any_null, Expr::Literal(Literal::Null))],
+		close: Some(result),
+	}))
+}
+
+/// Lowers a `CASE` expression onto an `IF … ELSE IF … END` chain. A searched
+/// `WHEN` condition lowers in predicate position, so an UNKNOWN condition falls
+/// through like FALSE; a simple `CASE x WHEN a, b` tests the guarded equalities
+/// `x = a OR x = b`. A missing `ELSE` yields `NULL`.
+async fn lower_case(
+	stk: &mut Stk,
+	operand: Option<&GqlExpr>,
+	whens: &[CaseWhen],
+	else_result: Option<&GqlExpr>,
+	span: Span,
+	scope: &Scope<'_>,
+) -> Result<Expr, SyntaxError> {
+	let mut exprs = Vec::with_capacity(whens.len());
+	for when in whens {
+		let condition = match operand {
+			None => {
+				let [condition] = when.operands.as_slice() else {
+					return Err(syntax_error!(
+						"Internal error: a searched CASE arm has one condition",
+						@span
+					));
+				};
+				stk.run(|stk| lower_predicate(stk, condition, false, scope)).await?
+			}
+			Some(operand) => {
+				let mut tests = Vec::with_capacity(when.operands.len());
+				for value in &when.operands {
+					tests.push(
+						lower_comparison(stk, BinaryOp::Eq, false, operand, value, span, scope)
+							.await?,
+					);
+				}
+				tests
+					.into_iter()
+					.reduce(|left, right| Expr::Binary {
+						left: Box::new(left),
+						op: BinaryOperator::Or,
+						right: Box::new(right),
+					})
+					.ok_or_else(
+						|| syntax_error!("Internal error: a simple CASE arm has no operand", @span),
+					)?
+			}
+		};
+		let result = stk.run(|stk| lower_value(stk, &when.result, scope)).await?;
+		exprs.push((condition, result));
+	}
+	let close = match else_result {
+		Some(else_result) => stk.run(|stk| lower_value(stk, else_result, scope)).await?,
+		None => Expr::Literal(Literal::Null),
+	};
+	Ok(Expr::IfElse(Box::new(IfelseStatement {
+		exprs,
+		close: Some(close),
+	})))
+}
+
+/// The SurrealQL kind a `CAST` converts to. A GQL record is a SurrealQL
+/// object.
+fn cast_kind(ty: &GqlType) -> Kind {
+	match ty.kind {
+		GqlTypeKind::Bool => Kind::Bool,
+		GqlTypeKind::String => Kind::String,
+		GqlTypeKind::Int => Kind::Int,
+		GqlTypeKind::Float => Kind::Float,
+		GqlTypeKind::Decimal => Kind::Decimal,
+		GqlTypeKind::Datetime => Kind::Datetime,
+		GqlTypeKind::Duration => Kind::Duration,
+		GqlTypeKind::List => Kind::Array(Box::new(Kind::Any), None),
+		GqlTypeKind::Record => Kind::Object,
+	}
+}
+
+/// The `type::is_*` builtin that tests a value against a GQL value type.
+fn type_test(ty: &GqlType) -> &'static str {
+	match ty.kind {
+		GqlTypeKind::Bool => "type::is_bool",
+		GqlTypeKind::String => "type::is_string",
+		GqlTypeKind::Int => "type::is_int",
+		GqlTypeKind::Float => "type::is_float",
+		GqlTypeKind::Decimal => "type::is_decimal",
+		GqlTypeKind::Datetime => "type::is_datetime",
+		GqlTypeKind::Duration => "type::is_duration",
+		GqlTypeKind::List => "type::is_array",
+		GqlTypeKind::Record => "type::is_object",
+	}
+}
+
+/// Lowers an `IS [NOT] TYPED`, `IS [NOT] LABELED` or `IS [NOT] SOURCE|DESTINATION
+/// OF` test. Each builds a two-valued test of its operand plus the operands
+/// that may be null: a typed test reads any value, while a label or endpoint
+/// test reads node/edge variables, which are only null when optional-bound.
+///
+/// In predicate position (`predicate`), a null operand excludes the row: a
+/// negated typed test and every test over an optional variable is prefixed
+/// with the §4 guards (which also keep `record::tb` from seeing a null). In
+/// value position the test yields `NULL` for a null operand.
+async fn lower_element_test(
+	stk: &mut Stk,
+	expr: &GqlExpr,
+	outer_negated: bool,
+	predicate: bool,
+	scope: &Scope<'_>,
+) -> Result<Expr, SyntaxError> {
+	let (test, negated, mut atoms) = match expr {
+		GqlExpr::IsTyped {
+			expr: operand,
+			ty,
+			negated,
+			..
+		} => {
+			let value = stk.run(|stk| lower_value(stk, operand, scope)).await?;
+			let test = function_call(type_test(ty), vec![value.clone()]);
+			// `type::is_*` is false for a null, which already excludes the row
+			// from a positive predicate.
+			let negated = outer_negated != *negated;
+			let atoms = if predicate && !negated {
+				Vec::new()
+			} else {
+				vec![value]
+			};
+			(test, negated, atoms)
+		}
+		GqlExpr::IsLabeled {
+			expr: operand,
+			label,
+			negated,
+			..
+		} => {
+			let ident = scope.element_variable(
+				operand,
+				&[BindingKind::Node, BindingKind::Edge],
+				"`IS LABELED` expects a node or edge variable",
+			)?;
+			let id = Ident {
+				name: "id".to_owned(),
+				span: ident.span,
+			};
+			let table = function_call("record::tb", vec![scope.binding_expr(ident, &[&id])?]);
+			let test = stk.run(|stk| lower_label_test(stk, label, table)).await;
+			let atoms = optional_atoms(&[ident], scope)?;
+			(test, outer_negated != *negated, atoms)
+		}
+		GqlExpr::IsEndpoint {
+			expr: operand,
+			edge,
+			source,
+			negated,
+			..
+		} => {
+			let message = "`IS SOURCE OF` and `IS DESTINATION OF` test a node variable against an \
+			               edge variable";
+			let node = scope.element_variable(operand, &[BindingKind::Node], message)?;
+			let edge_expr = GqlExpr::Variable(edge.clone());
+			let edge = scope.element_variable(&edge_expr, &[BindingKind::Edge], message)?;
+			// A SurrealDB edge record runs from its `in` to its `out` node.
+			let endpoint = Ident {
+				name: if *source {
+					"in"
+				} else {
+					"out"
+				}
+				.to_owned(),
+				span: edge.span,
+			};
+			let id = Ident {
+				name: "id".to_owned(),
+				span: node.span,
+			};
+			let test = Expr::Binary {
+				left: Box::new(scope.binding_expr(edge, &[&endpoint])?),
+				op: BinaryOperator::Equal,
+				right: Box::new(scope.binding_expr(node, &[&id])?),
+			};
+			let atoms = optional_atoms(&[node, edge], scope)?;
+			(test, outer_negated != *negated, atoms)
+		}
+		other => {
+			return Err(syntax_error!("Internal error: not an element test", @other.span()));
+		}
+	};
+	let test = if negated {
+		Expr::Prefix {
+			op: PrefixOperator::Not,
+			expr: Box::new(test),
+		}
+	} else {
+		test
+	};
+	atoms.dedup();
+	if predicate {
+		Ok(and_chain(guard_conjuncts(&atoms).into_iter().chain([test]))
+			.expect("the chain holds at least the test"))
+	} else {
+		Ok(null_guarded(&atoms, test))
+	}
+}
+
+/// The binding-row values of the optional-bound variables among `idents`:
+/// the operands of an element test that may be null.
+fn optional_atoms(idents: &[&Ident], scope: &Scope<'_>) -> Result<Vec<Expr>, SyntaxError> {
+	let mut atoms = Vec::new();
+	for ident in idents {
+		if scope.variable_is_optional(ident) {
+			atoms.push(scope.binding_expr(ident, &[])?);
+		}
+	}
+	Ok(atoms)
+}
+
+/// Lowers a label expression over an element's table name (`table`, the
+/// `record::tb` of the element): a label name is a table-name equality, `%`
+/// matches every element (each record has a table) and `!`/`&`/`|` lower to
+/// the boolean operators.
+async fn lower_label_test(stk: &mut Stk, label: &LabelExpr, table: Expr) -> Expr {
+	match label {
+		LabelExpr::Name(name) => Expr::Binary {
+			left: Box::new(table),
+			op: BinaryOperator::Equal,
+			right: Box::new(Expr::Literal(Literal::String(name.name.clone().into()))),
+		},
+		LabelExpr::Wildcard(_) => Expr::Literal(Literal::Bool(true)),
+		LabelExpr::Negation(inner, _) => Expr::Prefix {
+			op: PrefixOperator::Not,
+			expr: Box::new(stk.run(|stk| lower_label_test(stk, inner, table)).await),
+		},
+		LabelExpr::Conjunction(left, right, _) | LabelExpr::Disjunction(left, right, _) => {
+			let op = if matches!(label, LabelExpr::Conjunction(..)) {
+				BinaryOperator::And
+			} else {
+				BinaryOperator::Or
+			};
+			let left = stk.run(|stk| lower_label_test(stk, left, table.clone())).await;
+			let right = stk.run(|stk| lower_label_test(stk, right, table)).await;
+			Expr::Binary {
+				left: Box::new(left),
+				op,
+				right: Box::new(right),
+			}
+		}
+	}
+}
+
 /// Collects the §4 guard atoms of a comparison operand: the property
 /// accesses and parameters which can evaluate to `NULL`/`NONE`, reachable
 /// through arithmetic, concatenation and sign operators. Literals, containers
@@ -491,9 +896,17 @@ fn nullable_atoms<'a>(expr: &'a GqlExpr, scope: &Scope<'_>) -> Vec<&'a GqlExpr>
 	let mut stack = vec![expr];
 	while let Some(e) = stack.pop() {
 		match e {
+			// A `CAST` or `CASE` yields `NULL` for a null operand or an
+			// unmatched arm, so it is guarded as a whole.
 			GqlExpr::Property(..)
 			| GqlExpr::Param {
 				..
+			}
+			| GqlExpr::Cast {
+				..
+			}
+			| GqlExpr::Case {
+				..
 			} => out.push(e),
 			GqlExpr::Variable(ident) if scope.variable_is_optional(ident) => out.push(e),
 			GqlExpr::Unary {
@@ -528,6 +941,12 @@ fn nullable(expr: &GqlExpr, scope: &Scope<'_>) -> bool {
 			| GqlExpr::Param {
 				..
 			}
+			| GqlExpr::Cast {
+				..
+			}
+			| GqlExpr::Case {
+				..
+			}
 			| GqlExpr::Literal(GqlLiteral::Null, _) => return true,
 			GqlExpr::Variable(ident) if scope.variable_is_optional(ident) => return true,
 			GqlExpr::Unary {
@@ -564,11 +983,14 @@ fn complement(op: BinaryOp) -> BinaryOp {
 }
 
 /// Maps a GQL binary operator onto its SurrealQL counterpart. `XOR` has no
-/// exactly-equivalent three-valued lowering and is rejected (§4, §7).
+/// single-operator counterpart: [`lower_value`] and [`lower_predicate`] lower
+/// it before reaching here.
 fn binary_op(op: BinaryOp, span: Span) -> Result<BinaryOperator, SyntaxError> {
 	Ok(match op {
 		BinaryOp::Or => BinaryOperator::Or,
-		BinaryOp::Xor => return reject_xor(span),
+		BinaryOp::Xor => {
+			return Err(syntax_error!("Internal error: `XOR` has no single operator", @span));
+		}
 		BinaryOp::And => BinaryOperator::And,
 		BinaryOp::Eq => BinaryOperator::Equal,
 		BinaryOp::Neq => BinaryOperator::NotEqual,
@@ -584,13 +1006,6 @@ fn binary_op(op: BinaryOp, span: Span) -> Result<BinaryOperator, SyntaxError> {
 	})
 }
 
-fn reject_xor<T>(span: Span) -> Result<T, SyntaxError> {
-	bail!(
-		"`XOR` is not supported yet",
-		@span => "rewrite `a XOR b` as `(a OR b) AND NOT (a AND b)`"
-	);
-}
-
 fn lower_param(name: &str, span: Span) -> Result<Expr, SyntaxError> {
 	naming::validate_param_name(name, span)?;
 	Ok(Expr::Param(Param::new(name)))
@@ -665,17 +1080,51 @@ fn aggregate_target(name: &str) -> Option<AggregateTarget> {
 		"min" => AggregateTarget::Mapped("math::min"),
 		"max" => AggregateTarget::Mapped("math::max"),
 		"avg" => AggregateTarget::Mapped("math::mean"),
+		"stddev_samp" => AggregateTarget::Mapped("math::stddev"),
 		_ => return None,
 	})
 }
 
 /// GQL aggregate names that are recognised but not implemented yet — reported
 /// with the aggregate-specific message rather than the generic one.
-const UNSUPPORTED_AGGREGATES: &[&str] =
-	&["percentile_cont", "percentile_disc", "stddev_pop", "stddev_samp"];
+const UNSUPPORTED_AGGREGATES: &[&str] = &["percentile_cont", "percentile_disc", "stddev_pop"];
+
+/// §5: resolves a (lowercased) GQL scalar function name to the SurrealDB
+/// builtin it lowers to and the number of arguments it takes, or `None` if the
+/// name is not a supported scalar function. Every builtin here is pure, so a
+/// call over grouping keys is itself determined by the keys.
+fn scalar_target(name: &str) -> Option<(&'static str, usize)> {
+	Some(match name {
+		"upper" => ("string::uppercase", 1),
+		"lower" => ("string::lowercase", 1),
+		"trim" => ("string::trim", 1),
+		"char_length" | "character_length" => ("string::len", 1),
+		"cardinality" | "size" => ("array::len", 1),
+		"abs" => ("math::abs", 1),
+		"ceil" | "ceiling" => ("math::ceil", 1),
+		"floor" => ("math::floor", 1),
+		"round" => ("math::round", 1),
+		"sign" => ("math::sign", 1),
+		"sqrt" => ("math::sqrt", 1),
+		"ln" => ("math::ln", 1),
+		"log10" => ("math::log10", 1),
+		"power" => ("math::pow", 2),
+		"sin" => ("math::sin", 1),
+		"cos" => ("math::cos", 1),
+		"tan" => ("math::tan", 1),
+		"cot" => ("math::cot", 1),
+		"asin" => ("math::asin", 1),
+		"acos" => ("math::acos", 1),
+		"atan" => ("math::atan", 1),
+		"degrees" => ("math::rad2deg", 1),
+		"radians" => ("math::deg2rad", 1),
+		_ => return None,
+	})
+}
 
-/// §5: lowers a function call. The only functions supported are the aggregates
-/// (in `RETURN`/aggregating-`ORDER BY` position); every other call is rejected.
+/// §5: lowers a function call: an aggregate (in `RETURN`/aggregating-`ORDER BY`
+/// position) or a scalar function from [`scalar_target`] (anywhere); every
+/// other call is rejected.
 async fn lower_function(
 	stk: &mut Stk,
 	name: &Ident,
@@ -687,6 +1136,16 @@ async fn lower_function(
 ) -> Result<Expr, SyntaxError> {
 	let lowered = name.name.to_ascii_lowercase();
 	let Some(target) = aggregate_target(&lowered) else {
+		if let Some((surreal_name, arity)) = scalar_target(&lowered) {
+			if star.is_some() || quantifier.is_some() {
+				bail!(
+					"`{}` is not an aggregate function",
+					name.name,
+					@span => "`*` and DISTINCT/ALL only apply to aggregates"
+				);
+			}
+			return lower_scalar_function(stk, name, surreal_name, arity, args, span, scope).await;
+		}
 		// `*` and a `DISTINCT`/`ALL` set quantifier are aggregate-only syntax, so
 		// flag such calls as aggregates even when the name is unknown.
 		if star.is_some()
@@ -704,12 +1163,8 @@ async fn lower_function(
 			@span => "an aggregate cannot appear in WHERE, GROUP BY, or inside another aggregate"
 		);
 	}
-	if quantifier.is_some() {
-		bail!(
-			"DISTINCT/ALL inside an aggregate is not supported yet",
-			@span => "remove the set quantifier"
-		);
-	}
+	// `ALL` is the default (every value is aggregated) and lowers as if absent.
+	let distinct = quantifier == Some(SetQuantifier::Distinct);
 
 	match target {
 		AggregateTarget::Count => {
@@ -721,6 +1176,11 @@ async fn lower_function(
 			let arg = single_arg(name, args, span)?;
 			let arg_scope = scope.no_aggregates();
 			let lowered = stk.run(|stk| lower_value(stk, arg, &arg_scope)).await?;
+			if distinct {
+				// The number of distinct non-null values in the group.
+				let values = present_values(distinct_values(lowered));
+				return Ok(function_call("array::len", vec![values]));
+			}
 			// GQL `count(x)` counts rows where `x` is not null. SurrealDB's
 			// `count(<arg>)` counts truthy arguments, so feed it the non-null
 			// guard: `x != NONE AND x != NULL` is `true` exactly when `x` is
@@ -738,11 +1198,92 @@ async fn lower_function(
 			let arg = single_arg(name, args, span)?;
 			let arg_scope = scope.no_aggregates();
 			let lowered = stk.run(|stk| lower_value(stk, arg, &arg_scope)).await?;
-			Ok(function_call(surreal_name, vec![lowered]))
+			Ok(match surreal_name {
+				_ if !distinct => function_call(surreal_name, vec![lowered]),
+				// The extreme of the distinct values is the extreme of all values.
+				"math::min" | "math::max" => function_call(surreal_name, vec![lowered]),
+				"array::group" => distinct_values(lowered),
+				// The numeric aggregates run once per group over the distinct
+				// numbers: non-numbers are mapped to NONE before collecting, then
+				// dropped, which keeps the "ignore non-numeric values" contract.
+				_ => {
+					let number = Expr::IfElse(Box::new(IfelseStatement {
+						exprs: vec![(
+							function_call("type::is_number", vec![lowered.clone()]),
+							lowered,
+						)],
+						close: None,
+					}));
+					function_call(surreal_name, vec![present_values(distinct_values(number))])
+				}
+			})
 		}
 	}
 }
 
+/// The distinct values of `value` across the group, in first-seen order:
+/// `array::distinct(array::group(value))`. The planner extracts the inner
+/// `array::group` as the accumulator and applies `array::distinct` to its
+/// result.
+fn distinct_values(value: Expr) -> Expr {
+	function_call("array::distinct", vec![function_call("array::group", vec![value])])
+}
+
+/// Removes the `NONE`/`NULL` entries from an array expression.
+fn present_values(values: Expr) -> Expr {
+	function_call(
+		"array::complement",
+		vec![
+			values,
+			Expr::Literal(Literal::Array(vec![
+				Expr::Literal(Literal::None),
+				Expr::Literal(Literal::Null),
+			])),
+		],
+	)
+}
+
+/// Lowers a scalar function call onto its SurrealDB builtin. GQL scalar
+/// functions return `NULL` when any argument is null, whereas the builtins
+/// reject a `NULL`/`NONE` argument, so the call is guarded:
+/// `IF x = NULL OR x = NONE THEN NULL ELSE f(x) END`.
+async fn lower_scalar_function(
+	stk: &mut Stk,
+	name: &Ident,
+	surreal_name: &str,
+	arity: usize,
+	args: &[GqlExpr],
+	span: Span,
+	scope: &Scope<'_>,
+) -> Result<Expr, SyntaxError> {
+	if args.len() != arity {
+		bail!(
+			"`{}` takes {} argument{}",
+			name.name,
+			arity,
+			if arity == 1 { "" } else { "s" },
+			@span
+		);
+	}
+	let mut lowered = Vec::with_capacity(args.len());
+	for arg in args {
+		lowered.push(stk.run(|stk| lower_value(stk, arg, scope)).await?);
+	}
+	let any_null = lowered
+		.iter()
+		.map(|arg| null_test(arg.clone(), false))
+		.reduce(|left, right| Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::Or,
+			right: Box::new(right),
+		})
+		.expect("every scalar function takes at least one argument");
+	Ok(Expr::IfElse(Box::new(IfelseStatement {
+		exprs: vec![(any_null, Expr::Literal(Literal::Null))],
+		close: Some(function_call(surreal_name, lowered)),
+	})))
+}
+
 /// Returns the single argument of an aggregate call, rejecting any other arity.
 fn single_arg<'a>(
 	name: &Ident,
@@ -799,6 +1340,22 @@ pub(super) fn gql_contains_aggregate(expr: &GqlExpr) -> bool {
 			| GqlExpr::IsNull {
 				expr,
 				..
+			}
+			| GqlExpr::Cast {
+				expr,
+				..
+			}
+			| GqlExpr::IsTyped {
+				expr,
+				..
+			}
+			| GqlExpr::IsLabeled {
+				expr,
+				..
+			}
+			| GqlExpr::IsEndpoint {
+				expr,
+				..
 			} => stack.push(expr),
 			GqlExpr::Binary {
 				left,
@@ -810,11 +1367,29 @@ pub(super) fn gql_contains_aggregate(expr: &GqlExpr) -> bool {
 			}
 			GqlExpr::List(items, _) => stack.extend(items.iter()),
 			GqlExpr::Map(fields, _) => stack.extend(fields.iter().map(|(_, v)| v)),
+			GqlExpr::Case {
+				operand,
+				whens,
+				else_result,
+				..
+			} => {
+				stack.extend(operand.as_deref());
+				stack.extend(else_result.as_deref());
+				for when in whens {
+					stack.extend(when.operands.iter());
+					stack.push(&when.result);
+				}
+			}
+			// An `EXISTS` body is a separate query: an aggregate inside it does
+			// not aggregate the outer rows.
 			GqlExpr::Literal(..)
 			| GqlExpr::Param {
 				..
 			}
-			| GqlExpr::Variable(_) => {}
+			| GqlExpr::Variable(_)
+			| GqlExpr::Exists {
+				..
+			} => {}
 		}
 	}
 	false
@@ -10,12 +10,15 @@
 //! whole query (the binding registry, hidden bindings, anchorability, and the
 //! node-variable-reuse-as-join-key / repeated-edge / kind-mismatch rules),
 //! [`pattern`] emits each clause's [`PatternPlan`]s and NNF-split predicates
-//! with their dependency sets, [`expr`] lowers expressions with uniform binding
-//! addressing and the three-valued-logic guards, [`naming`] owns the
-//! column-name and reserved-name rules, and this module dispatches and
-//! assembles the output spec (R7/R8) and the final [`LogicalPlan`].
+//! with their dependency sets, [`exists`] lowers `EXISTS` subqueries into the
+//! semi-join stages that mark their rows, [`expr`] lowers expressions with
+//! uniform binding addressing and the three-valued-logic guards, [`naming`]
+//! owns the column-name and reserved-name rules, and this module dispatches,
+//! assembles the output spec (R7/R8), composes the operands of a composite
+//! query and builds the final [`LogicalPlan`].
 
 mod binding;
+mod exists;
 mod expr;
 mod mutation;
 mod naming;
@@ -29,12 +32,15 @@ use surrealdb_types::ToSql;
 
 use self::binding::Registry;
 use self::expr::Scope;
-use crate::expr::match_plan::{MatchColumn, MatchOrder, MatchOutput, MatchPlan, MatchStage};
+use crate::expr::match_plan::{
+	ExistsStage, MatchColumn, MatchComposition, MatchOrder, MatchOutput, MatchPlan, MatchSetOp,
+	MatchStage,
+};
 use crate::expr::plan::{LogicalPlan, TopLevelExpr};
 use crate::expr::{Expr, Idiom, Literal, Param};
 use crate::gql::ast::{
-	GqlExpr, GqlGroupItem, GqlLiteral, GqlQuery, GqlStep, LinearQuery, MatchItem, OrderItem,
-	ReturnClause, ReturnItems, SetQuantifier,
+	CompositeOperand, ConjunctionKind, GqlExpr, GqlGroupItem, GqlLiteral, GqlQuery, GqlStep,
+	LinearQuery, MatchItem, MutationStatement, OrderItem, ReturnClause, ReturnItems, SetQuantifier,
 };
 use crate::syn::error::{SyntaxError, bail, syntax_error};
 use crate::syn::token::Span;
@@ -45,18 +51,121 @@ use crate::syn::token::Span;
 /// Runs on a [`reblessive`] stack: the GQL AST contains arbitrarily deep
 /// expression chains which the machine stack must not recurse over.
 pub(super) fn lower(query: GqlQuery) -> Result<LogicalPlan, SyntaxError> {
-	let LinearQuery {
-		steps,
-		ret,
-		span,
-	} = query.program;
+	let GqlQuery {
+		program,
+		composite,
+	} = query;
 	let mut stack = Stack::new();
-	let plan = stack.enter(|stk| lower_program(stk, &steps, ret.as_ref(), span)).finish()?;
+	let plan = stack.enter(|stk| lower_composite(stk, &program, &composite)).finish()?;
 	Ok(LogicalPlan {
 		expressions: vec![TopLevelExpr::Expr(Expr::Match(Box::new(plan)))],
 	})
 }
 
+/// Lowers a query's first linear program and then each composite operand, on
+/// its own binding registry, into the first plan's [`MatchComposition`]s.
+///
+/// Every operand of a composite query is a read-only query ending in a
+/// `RETURN` (`compositeQueryPrimary` is a `linearQueryStatement`), and all
+/// operands return the same column names: rows are combined by comparing them
+/// whole, column by column.
+async fn lower_composite(
+	stk: &mut Stk,
+	program: &LinearQuery,
+	composite: &[CompositeOperand],
+) -> Result<MatchPlan, SyntaxError> {
+	if composite.is_empty() {
+		return lower_linear(stk, program).await;
+	}
+	check_composite_operand(program)?;
+	let mut plan = lower_linear(stk, program).await?;
+	let columns = returned_columns(&plan);
+	for operand in composite {
+		check_composite_operand(&operand.query)?;
+		let operand_plan = lower_linear(stk, &operand.query).await?;
+		let operand_columns = returned_columns(&operand_plan);
+		if operand_columns != columns {
+			let span = operand.query.ret.as_ref().map(|ret| ret.span).unwrap_or(operand.query.span);
+			bail!(
+				"The operands of a composite query must return the same columns",
+				@span => "this operand returns {}, the first returns {}",
+				render_columns(&operand_columns),
+				render_columns(&columns)
+			);
+		}
+		let distinct = operand.conjunction.quantifier != Some(SetQuantifier::All);
+		let op = match operand.conjunction.kind {
+			ConjunctionKind::Union => MatchSetOp::Union {
+				distinct,
+			},
+			ConjunctionKind::Except => MatchSetOp::Except {
+				distinct,
+			},
+			ConjunctionKind::Intersect => MatchSetOp::Intersect {
+				distinct,
+			},
+			ConjunctionKind::Otherwise => MatchSetOp::Otherwise,
+		};
+		plan.compose.push(MatchComposition {
+			op,
+			plan: operand_plan,
+		});
+	}
+	Ok(plan)
+}
+
+/// Lowers one linear query into a [`MatchPlan`] without composite operands.
+async fn lower_linear(stk: &mut Stk, query: &LinearQuery) -> Result<MatchPlan, SyntaxError> {
+	lower_program(stk, &query.steps, query.ret.as_ref(), query.span).await
+}
+
+/// Rejects a composite query operand that modifies data or has no `RETURN`.
+fn check_composite_operand(query: &LinearQuery) -> Result<(), SyntaxError> {
+	for step in &query.steps {
+		if let GqlStep::Mutate(stmt) = step {
+			let span = match stmt {
+				MutationStatement::Insert(stmt) => stmt.span,
+				MutationStatement::Set(stmt) => stmt.span,
+				MutationStatement::Remove(stmt) => stmt.span,
+				MutationStatement::Delete(stmt) => stmt.span,
+			};
+			bail!(
+				"The operands of a composite query cannot modify data",
+				@span => "run the write as its own query"
+			);
+		}
+	}
+	if query.ret.is_none() {
+		bail!(
+			"Each operand of a composite query must end with a RETURN clause",
+			@query.span => "add a `RETURN …` clause"
+		);
+	}
+	Ok(())
+}
+
+/// The sorted names of the columns a plan returns (hidden sort-only columns
+/// are dropped before the rows leave the plan).
+fn returned_columns(plan: &MatchPlan) -> Vec<String> {
+	let mut names: Vec<String> = plan
+		.output
+		.iter()
+		.flat_map(|output| &output.columns)
+		.filter(|column| !column.hidden)
+		.map(|column| column.name.clone())
+		.collect();
+	names.sort_unstable();
+	names
+}
+
+/// Renders column names for an error label: `` `a`, `b` ``, or `no columns`.
+fn render_columns(columns: &[String]) -> String {
+	if columns.is_empty() {
+		return "no columns".to_string();
+	}
+	columns.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ")
+}
+
 /// Lowers a whole linear query — `MATCH`/`OPTIONAL` reads and
 /// `INSERT`/`SET`/`REMOVE`/`DELETE` mutations interleaved in textual order, plus
 /// an optional `RETURN` — into a [`MatchPlan`].
@@ -83,14 +192,35 @@ async fn lower_program(
 				// `read` declares the item's bindings (anchorability checked
 				// against the registry built so far, which includes earlier
 				// INSERT-created variables) and returns one [`ClauseBindings`] per
-				// flattened clause; each lowers against the registry so far.
+				// flattened clause; each lowers against the registry so far. The
+				// `EXISTS` subqueries of a clause lower first (their bodies see
+				// the clause's bindings) and follow it as one stage.
 				for clause_bindings in analyzer.read(item)? {
-					let clause =
-						pattern::lower_clause(stk, &clause_bindings, analyzer.registry()).await?;
+					let subqueries = exists::clause_exists(clause_bindings.clause);
+					if let Some(first) = subqueries.first()
+						&& clause_bindings.optional_group.is_some()
+					{
+						bail!(
+							"`EXISTS` inside an OPTIONAL clause is not supported yet",
+							@first.span() => "move the test into a mandatory MATCH or the RETURN"
+						);
+					}
+					let (subqueries, marks) =
+						exists::lower_subqueries(stk, &mut analyzer, &subqueries).await?;
+					let (clause, predicates) =
+						pattern::lower_clause(stk, &clause_bindings, analyzer.registry(), &marks)
+							.await?;
 					stages.push(MatchStage::Read(clause));
+					if !subqueries.is_empty() {
+						stages.push(MatchStage::Exists(ExistsStage {
+							subqueries,
+							predicates,
+						}));
+					}
 				}
 			}
 			GqlStep::Mutate(stmt) => {
+				analyzer.record_write();
 				for mutation in
 					mutation::lower_statement(stk, analyzer.registry_mut(), stmt).await?
 				{
@@ -100,8 +230,6 @@ async fn lower_program(
 		}
 	}
 
-	let registry = analyzer.into_registry();
-
 	// A query needs at least one read clause or mutation (a bare `RETURN` has no
 	// `MATCH`).
 	if stages.is_empty() {
@@ -111,8 +239,24 @@ async fn lower_program(
 		);
 	}
 
+	// The `EXISTS` subqueries the output reads mark the final binding rows.
+	let subqueries = match ret {
+		Some(ret) => exists::output_exists(ret),
+		// A mutation-only query has no output to read subqueries from
+		None => Vec::new(),
+	};
+	let (subqueries, marks) = exists::lower_subqueries(stk, &mut analyzer, &subqueries).await?;
+	if !subqueries.is_empty() {
+		stages.push(MatchStage::Exists(ExistsStage {
+			subqueries,
+			predicates: Vec::new(),
+		}));
+	}
+
+	let registry = analyzer.into_registry();
+
 	let output = match ret {
-		Some(ret) => Some(lower_output(stk, ret, &registry).await?),
+		Some(ret) => Some(lower_output(stk, ret, &registry, &marks).await?),
 		None => {
 			// A read-only query must project a RETURN; a mutation-only query
 			// need not (it runs for its side effects and returns nothing).
@@ -130,6 +274,7 @@ async fn lower_program(
 		bindings: registry.into_defs(),
 		stages,
 		output,
+		compose: Vec::new(),
 	})
 }
 
@@ -161,17 +306,20 @@ struct Column {
 
 /// Lowers the RETURN clause into the [`MatchOutput`] spec: the projected
 /// columns (R8), the DISTINCT flag, the resolved ORDER BY keys (R7) and the
-/// SKIP/LIMIT counts.
+/// SKIP/LIMIT counts. `exists` names the marks of the output's `EXISTS`
+/// subqueries.
 async fn lower_output(
 	stk: &mut Stk,
 	ret: &ReturnClause,
 	registry: &Registry,
+	exists: &[(Span, String)],
 ) -> Result<MatchOutput, SyntaxError> {
 	// GROUP BY keys lower first (no aggregates permitted in a key) so the
 	// RETURN-item lowering can validate every non-aggregate column against them.
-	let group_keys = lower_group_keys(stk, &ret.group_by, registry).await?;
+	let group_keys = lower_group_keys(stk, &ret.group_by, registry, exists).await?;
 
-	let (mut columns, aggregating) = lower_return_items(stk, ret, registry, &group_keys).await?;
+	let (mut columns, aggregating) =
+		lower_return_items(stk, ret, registry, exists, &group_keys).await?;
 	let distinct = matches!(ret.quantifier, Some(SetQuantifier::Distinct));
 
 	// ORDER BY resolution may append hidden sort-only columns (an aggregating
@@ -179,8 +327,17 @@ async fn lower_output(
 	let mut order = Vec::with_capacity(ret.order_by.len());
 	for item in &ret.order_by {
 		order.push(
-			lower_order_item(stk, item, &mut columns, &group_keys, distinct, aggregating, registry)
-				.await?,
+			lower_order_item(
+				stk,
+				item,
+				&mut columns,
+				&group_keys,
+				distinct,
+				aggregating,
+				registry,
+				exists,
+			)
+			.await?,
 		);
 	}
 
@@ -220,10 +377,12 @@ async fn lower_group_keys(
 	stk: &mut Stk,
 	items: &[GqlGroupItem],
 	registry: &Registry,
+	exists: &[(Span, String)],
 ) -> Result<Vec<Expr>, SyntaxError> {
 	let scope = Scope {
 		registry,
 		allow_aggregates: false,
+		exists,
 	};
 	let mut keys = Vec::with_capacity(items.len());
 	for item in items {
@@ -234,18 +393,21 @@ async fn lower_group_keys(
 
 /// Lowers the RETURN items into named columns (R8): explicit aliases win,
 /// unaliased items are named by their verbatim source text, `RETURN *` expands
-/// to the user-named bindings (incl. group and path variables) in alphabetical
-/// order, and duplicate column names are rejected.
+/// to the user-named bindings in scope (incl. group and path variables, but
+/// not the variables local to an `EXISTS` body) in alphabetical order, and
+/// duplicate column names are rejected.
 async fn lower_return_items(
 	stk: &mut Stk,
 	ret: &ReturnClause,
 	registry: &Registry,
+	exists: &[(Span, String)],
 	group_keys: &[Expr],
 ) -> Result<(Vec<Column>, bool), SyntaxError> {
 	// Aggregates are permitted in RETURN value position.
 	let scope = Scope {
 		registry,
 		allow_aggregates: true,
+		exists,
 	};
 	let mut columns: Vec<Column> = Vec::new();
 	match &ret.items {
@@ -262,7 +424,7 @@ async fn lower_return_items(
 			let mut names: Vec<&str> = registry
 				.bindings()
 				.iter()
-				.filter(|b| b.user_named)
+				.filter(|b| b.user_named && b.in_scope)
 				.map(|b| b.name.as_str())
 				.collect();
 			names.sort_unstable();
@@ -335,6 +497,7 @@ async fn lower_return_items(
 ///   is materialised as a hidden sort-only column (dropped before output).
 /// - plain: Sort runs pre-projection over the binding rows, so any binding-row expression is valid;
 ///   a key naming a RETURN column sorts on that column's underlying expression.
+#[allow(clippy::too_many_arguments)]
 async fn lower_order_item(
 	stk: &mut Stk,
 	item: &OrderItem,
@@ -343,6 +506,7 @@ async fn lower_order_item(
 	distinct: bool,
 	aggregating: bool,
 	registry: &Registry,
+	exists: &[(Span, String)],
 ) -> Result<MatchOrder, SyntaxError> {
 	if item.nulls_first.is_some() {
 		bail!("`NULLS FIRST`/`NULLS LAST` ordering is not supported yet", @item.span);
@@ -352,6 +516,7 @@ async fn lower_order_item(
 	let scope = Scope {
 		registry,
 		allow_aggregates: aggregating,
+		exists,
 	};
 
 	if distinct {
@@ -457,9 +622,10 @@ async fn order_output_column(
 
 /// Whether an aggregating-query value expression is determined by the GROUP BY
 /// keys: it equals a key, or every leaf is a constant or an idiom prefixed by a
-/// grouping-key idiom (so the value is constant within each group and the
-/// `Aggregate`'s first-value fold is exact). Walks on an explicit stack so a deep
-/// operator spine cannot overflow.
+/// grouping-key idiom, combined by operators and scalar function calls (so the
+/// value is constant within each group and the `Aggregate`'s first-value fold
+/// is exact). Walks on an explicit stack so a deep operator spine cannot
+/// overflow.
 fn column_is_grouped(expr: &Expr, keys: &[Expr]) -> bool {
 	let mut stack = vec![expr];
 	while let Some(e) = stack.pop() {
@@ -497,8 +663,18 @@ fn column_is_grouped(expr: &Expr, keys: &[Expr]) -> bool {
 				expr,
 				..
 			} => stack.push(expr),
-			// Anything else (function calls, subqueries, tables, …) is not a value
-			// determined by the grouping keys.
+			// A non-aggregate call here is a pure scalar builtin (and its null
+			// guard), so it is determined by its arguments.
+			Expr::FunctionCall(call) => stack.extend(call.arguments.iter()),
+			Expr::IfElse(stmt) => {
+				for (cond, then) in &stmt.exprs {
+					stack.push(cond);
+					stack.push(then);
+				}
+				stack.extend(stmt.close.iter());
+			}
+			// Anything else (subqueries, tables, …) is not a value determined by
+			// the grouping keys.
 			_ => return false,
 		}
 	}
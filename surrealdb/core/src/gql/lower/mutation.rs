@@ -86,6 +86,7 @@ async fn lower_set(
 	let scope = Scope {
 		registry,
 		allow_aggregates: false,
+		exists: &[],
 	};
 	let mut i = 0;
 	while i < set.items.len() {
@@ -258,6 +259,7 @@ async fn lower_insert(
 	let scope = Scope {
 		registry,
 		allow_aggregates: false,
+		exists: &[],
 	};
 	let mut nodes = Vec::with_capacity(new_nodes.len());
 	for (binding, label, props) in &new_nodes {
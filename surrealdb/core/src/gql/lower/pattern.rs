@@ -31,6 +31,7 @@ use crate::gql::ast::{
 	PathMode, PathPattern, PathPatternPrefix, PathSearchKind, Quantifier, QuantifierKind, UnaryOp,
 };
 use crate::gql::lower::binding::{ClauseBindings, PatternBindings, Registry};
+use crate::gql::lower::exists;
 use crate::gql::lower::expr::{self, Scope};
 use crate::syn::error::{SyntaxError, bail};
 use crate::syn::token::Span;
@@ -52,18 +53,25 @@ use crate::val::TableName;
 /// an optional binding is owned by THAT later clause (post-null). The lowering
 /// only records the deps; the planner places each predicate at the earliest
 /// stage that binds its deps within its owning clause's subplan.
+///
+/// `exists` names the marks of the clause's `EXISTS` subqueries (see
+/// [`exists`]). The conjuncts that read a mark are returned apart from the
+/// clause plan: they can only be placed once the subqueries have marked the
+/// clause's rows, in the `EXISTS` stage that follows the clause.
 pub(super) async fn lower_clause(
 	stk: &mut Stk,
 	clause_bindings: &ClauseBindings<'_>,
 	registry: &Registry,
-) -> Result<MatchClausePlan, SyntaxError> {
+	exists: &[(Span, String)],
+) -> Result<(MatchClausePlan, Vec<MatchPredicate>), SyntaxError> {
 	let clause = clause_bindings.clause;
 	let pattern_bindings = clause_bindings.patterns.as_slice();
 	let mut patterns = Vec::with_capacity(clause.patterns.len());
 	for (pattern, bindings) in clause.patterns.iter().zip(pattern_bindings.iter()) {
 		patterns.push(build_pattern_plan(pattern, bindings)?);
 	}
-	let mut predicates = lower_predicates(stk, clause, registry, pattern_bindings).await?;
+	let (mut predicates, exists_predicates) =
+		lower_predicates(stk, clause, registry, pattern_bindings, exists).await?;
 	// A node variable repeated within a single pattern (e.g. the self-loop
 	// `(a)-[…]->(a)`) was rewritten to a fresh hidden binding by `binding`; emit
 	// the implied `id`-equality so the planner enforces it (the chain has no join
@@ -80,11 +88,12 @@ pub(super) async fn lower_clause(
 	// Property access on group/path vars is already rejected upstream; this guards
 	// the residual bare-reference forms.
 	reject_selective_group_predicates(clause, pattern_bindings, &predicates)?;
-	Ok(MatchClausePlan {
+	let plan = MatchClausePlan {
 		optional_group: clause_bindings.optional_group,
 		patterns,
 		predicates,
-	})
+	};
+	Ok((plan, exists_predicates))
 }
 
 /// Rejects a predicate that reads the edge-group or path binding of a pattern
@@ -298,13 +307,15 @@ enum Conjunct<'a> {
 /// property-map equalities of every pattern — splits each into NNF conjuncts,
 /// and lowers each into a [`MatchPredicate`] with its dependency set. A
 /// conjunct may reference bindings from several patterns (a cross-pattern
-/// predicate); the planner places it post-join.
+/// predicate); the planner places it post-join. The conjuncts reading an
+/// `EXISTS` mark are returned second.
 async fn lower_predicates(
 	stk: &mut Stk,
 	clause: &MatchClause,
 	registry: &Registry,
 	pattern_bindings: &[PatternBindings],
-) -> Result<Vec<MatchPredicate>, SyntaxError> {
+	exists: &[(Span, String)],
+) -> Result<(Vec<MatchPredicate>, Vec<MatchPredicate>), SyntaxError> {
 	let conjuncts = collect_conjuncts(
 		clause.where_clause.as_ref(),
 		&clause.patterns,
@@ -315,8 +326,10 @@ async fn lower_predicates(
 		registry,
 		// Aggregates are not allowed in WHERE predicates or property equalities.
 		allow_aggregates: false,
+		exists,
 	};
 	let mut out = Vec::with_capacity(conjuncts.len());
+	let mut exists_out = Vec::new();
 	for conjunct in &conjuncts {
 		let deps = conjunct_deps(conjunct, registry)?;
 		// Cross-variable references inside a quantified edge's inline predicate
@@ -334,13 +347,28 @@ async fn lower_predicates(
 				value,
 			} => expr::lower_prop_equality(stk, *binding, binding_name, key, value, &scope).await?,
 		};
-		out.push(MatchPredicate {
+		let reads_mark = match conjunct {
+			Conjunct::Expr {
+				expr,
+				..
+			} => exists::contains_exists(expr),
+			Conjunct::Prop {
+				value,
+				..
+			} => exists::contains_exists(value),
+		};
+		let predicate = MatchPredicate {
 			// The lowering builds `sql::Expr`; the IR is binding-row `expr::Expr`.
 			expr: lowered.into(),
 			deps,
-		});
+		};
+		if reads_mark {
+			exists_out.push(predicate);
+		} else {
+			out.push(predicate);
+		}
 	}
-	Ok(out)
+	Ok((out, exists_out))
 }
 
 /// Merges and NNF-splits the clause's predicate sources into conjuncts: the
@@ -558,7 +586,27 @@ fn walk_variables<'a>(
 			| GqlExpr::IsNull {
 				expr,
 				..
+			}
+			| GqlExpr::Cast {
+				expr,
+				..
+			}
+			| GqlExpr::IsTyped {
+				expr,
+				..
+			}
+			| GqlExpr::IsLabeled {
+				expr,
+				..
 			} => stack.push(expr),
+			GqlExpr::IsEndpoint {
+				expr,
+				edge,
+				..
+			} => {
+				visit(edge, true)?;
+				stack.push(expr);
+			}
 			GqlExpr::Binary {
 				left,
 				right,
@@ -573,9 +621,28 @@ fn walk_variables<'a>(
 			} => stack.extend(args.iter()),
 			GqlExpr::List(items, _) => stack.extend(items.iter()),
 			GqlExpr::Map(fields, _) => stack.extend(fields.iter().map(|(_, value)| value)),
+			GqlExpr::Case {
+				operand,
+				whens,
+				else_result,
+				..
+			} => {
+				stack.extend(else_result.as_deref());
+				for when in whens.iter().rev() {
+					stack.push(&when.result);
+					stack.extend(when.operands.iter().rev());
+				}
+				stack.extend(operand.as_deref());
+			}
+			// The variables an `EXISTS` body shares with the outer query are the
+			// semi-join keys of its stage, not deps of the conjunct reading its
+			// mark.
 			GqlExpr::Literal(..)
 			| GqlExpr::Param {
 				..
+			}
+			| GqlExpr::Exists {
+				..
 			} => {}
 		}
 	}
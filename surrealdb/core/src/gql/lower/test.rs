@@ -12,8 +12,9 @@ use surrealdb_types::{SqlFormat, ToSql};
 
 use crate::expr::Expr;
 use crate::expr::match_plan::{
-	BindingDef, BindingId, BindingKind, DetachMode, EdgeQuantifier, ExpandDirection,
-	MatchClausePlan, MatchPlan, MatchStage, MutationStage, NodeStep, PatternPlan, UpdateData,
+	BindingDef, BindingId, BindingKind, DetachMode, EdgeQuantifier, ExistsStage, ExpandDirection,
+	MatchClausePlan, MatchPlan, MatchSetOp, MatchStage, MutationStage, NodeStep, PatternPlan,
+	UpdateData,
 };
 use crate::expr::plan::TopLevelExpr;
 use crate::gql::{GqlParserSettings, lower, parse_str, parse_to_plan_with_settings};
@@ -72,13 +73,68 @@ fn render_plan(plan: &MatchPlan) -> String {
 		match stage {
 			MatchStage::Read(clause) => render_clause(plan, clause, &mut out),
 			MatchStage::Mutate(mutation) => render_mutation(plan, mutation, &mut out),
+			MatchStage::Exists(stage) => render_exists(plan, stage, &mut out),
 		}
 	}
 
 	render_output(plan, &mut out);
+	for composition in &plan.compose {
+		out.push('\n');
+		out.push_str(match composition.op {
+			MatchSetOp::Union {
+				distinct: true,
+			} => "UNION",
+			MatchSetOp::Union {
+				distinct: false,
+			} => "UNION ALL",
+			MatchSetOp::Except {
+				distinct: true,
+			} => "EXCEPT",
+			MatchSetOp::Except {
+				distinct: false,
+			} => "EXCEPT ALL",
+			MatchSetOp::Intersect {
+				distinct: true,
+			} => "INTERSECT",
+			MatchSetOp::Intersect {
+				distinct: false,
+			} => "INTERSECT ALL",
+			MatchSetOp::Otherwise => "OTHERWISE",
+		});
+		out.push('\n');
+		out.push_str(&render_plan(&composition.plan));
+	}
 	out
 }
 
+/// Renders an `EXISTS` stage: each subquery's mark and correlated bindings,
+/// its body clauses indented beneath, then the conjuncts reading the marks.
+fn render_exists(plan: &MatchPlan, stage: &ExistsStage, out: &mut String) {
+	for subquery in &stage.subqueries {
+		out.push_str(&format!(
+			"EXISTS {} ON [{}]\n",
+			subquery.mark,
+			render_deps(plan, &subquery.correlated)
+		));
+		let mut body = String::new();
+		for clause in &subquery.clauses {
+			render_clause(plan, clause, &mut body);
+		}
+		for line in body.lines() {
+			out.push_str("  ");
+			out.push_str(line);
+			out.push('\n');
+		}
+	}
+	for predicate in &stage.predicates {
+		out.push_str("  FILTER [");
+		out.push_str(&render_deps(plan, &predicate.deps));
+		out.push_str("] ");
+		out.push_str(&render_expr(&predicate.expr));
+		out.push('\n');
+	}
+}
+
 /// Renders one mutation stage as a compact, single-line step so interleaved
 /// read/write programs stay diffable in the lowering snapshots.
 fn render_mutation(plan: &MatchPlan, mutation: &MutationStage, out: &mut String) {
@@ -1851,14 +1907,47 @@ fn lowers_count_field_with_non_null_guard() {
 }
 
 #[test]
-fn rejects_distinct_in_aggregate() {
-	assert_rejects(
-		"MATCH (n:person) RETURN count(DISTINCT n)",
-		"DISTINCT/ALL inside an aggregate is not supported yet",
-		"count(DISTINCT n)",
+fn lowers_count_distinct_over_present_values() {
+	// `count(DISTINCT x)` counts the group's distinct non-null values.
+	let rendered = render("MATCH (n:person) RETURN count(DISTINCT n.city) AS c");
+	assert!(
+		rendered.contains(
+			"array::len(array::complement(array::distinct(array::group(n.city)), [NONE, NULL]))"
+		),
+		"{rendered}"
+	);
+	assert!(rendered.contains("GROUP ALL"), "{rendered}");
+}
+
+#[test]
+fn lowers_collect_distinct() {
+	let rendered = render("MATCH (n:person) RETURN collect_list(DISTINCT n.city) AS c");
+	assert!(rendered.contains("array::distinct(array::group(n.city)) AS c"), "{rendered}");
+}
+
+#[test]
+fn lowers_sum_distinct_over_distinct_numbers() {
+	// The numeric aggregate runs per group over the distinct numbers; a
+	// non-number collects as NONE and is dropped.
+	let rendered = render("MATCH (n:person) RETURN sum(DISTINCT n.age) AS s");
+	assert!(
+		rendered.contains("math::sum(array::complement(array::distinct(array::group(IF type::is_number(n.age) THEN n.age END))"),
+		"{rendered}"
 	);
 }
 
+#[test]
+fn lowers_min_distinct_as_plain_min() {
+	let rendered = render("MATCH (n:person) RETURN min(DISTINCT n.age) AS m");
+	assert!(rendered.contains("math::min(n.age) AS m"), "{rendered}");
+}
+
+#[test]
+fn lowers_all_quantifier_as_plain_aggregate() {
+	let rendered = render("MATCH (n:person) RETURN sum(ALL n.age) AS s");
+	assert!(rendered.contains("math::sum(n.age) AS s"), "{rendered}");
+}
+
 #[test]
 fn rejects_aggregate_in_where() {
 	assert_rejects(
@@ -1925,20 +2014,80 @@ fn rejects_unsupported_aggregate() {
 }
 
 #[test]
-fn rejects_non_aggregate_functions() {
+fn lowers_stddev_samp() {
+	let rendered = render("MATCH (n:person) RETURN stddev_samp(n.age) AS sd");
+	assert!(rendered.contains("math::stddev(n.age) AS sd"), "{rendered}");
+}
+
+#[test]
+fn rejects_unknown_functions() {
 	assert_rejects(
-		"MATCH (n:person) RETURN upper(n.name)",
-		"The function `upper` is not supported yet",
-		"upper(n.name)",
+		"MATCH (n:person) RETURN soundex(n.name)",
+		"The function `soundex` is not supported yet",
+		"soundex(n.name)",
+	);
+}
+
+#[test]
+fn lowers_scalar_function_with_null_guard() {
+	// A GQL scalar function returns NULL on a null argument; the builtin would
+	// reject it, so the call is guarded.
+	let rendered = render("MATCH (n:person) RETURN UPPER(n.name) AS u");
+	assert!(
+		rendered.contains(
+			"IF n.name = NULL OR n.name = NONE THEN NULL ELSE string::uppercase(n.name) END AS u"
+		),
+		"{rendered}"
 	);
 }
 
 #[test]
-fn rejects_function_calls_in_where() {
+fn lowers_scalar_function_in_where() {
+	let rendered = render("MATCH (n:person) WHERE char_length(n.name) = 1 RETURN n");
+	assert!(rendered.contains("string::len(n.name)"), "{rendered}");
+}
+
+#[test]
+fn lowers_two_argument_scalar_function() {
+	let rendered = render("MATCH (n:person) RETURN power(n.age, 2) AS p");
+	assert!(
+		rendered.contains(
+			"IF n.age = NULL OR n.age = NONE OR 2 = NULL OR 2 = NONE THEN NULL ELSE math::pow(n.age, 2) END"
+		),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_scalar_function_over_grouping_key() {
+	// A scalar function of a grouping key is determined by the key.
+	let rendered =
+		render("MATCH (n:person) RETURN lower(n.city) AS c, count(*) AS k GROUP BY n.city");
+	assert!(rendered.contains("string::lowercase(n.city)"), "{rendered}");
+	assert!(rendered.contains("GROUP BY n.city"), "{rendered}");
+}
+
+#[test]
+fn lowers_scalar_function_over_aggregate() {
+	let rendered = render("MATCH (n:person) RETURN abs(sum(n.age)) AS s");
+	assert!(rendered.contains("math::abs(math::sum(n.age))"), "{rendered}");
+}
+
+#[test]
+fn rejects_scalar_function_arity() {
 	assert_rejects(
-		"MATCH (n:person) WHERE upper(n.name) = 'A' RETURN n",
-		"The function `upper` is not supported yet",
-		"upper(n.name)",
+		"MATCH (n:person) RETURN power(n.age)",
+		"`power` takes 2 arguments",
+		"power(n.age)",
+	);
+}
+
+#[test]
+fn rejects_set_quantifier_on_scalar_function() {
+	assert_rejects(
+		"MATCH (n:person) RETURN upper(DISTINCT n.name)",
+		"`upper` is not an aggregate function",
+		"upper(DISTINCT n.name)",
 	);
 }
 
@@ -2020,20 +2169,187 @@ fn rejects_engine_reserved_parameters() {
 }
 
 #[test]
-fn rejects_xor_in_predicates() {
+fn lowers_xor_in_predicates() {
+	// TRUE exactly when one side is TRUE and the other FALSE, so an UNKNOWN
+	// side excludes the row.
+	let rendered = render("MATCH (n:person) WHERE n.flag XOR n.old RETURN n");
+	assert!(rendered.contains("n.flag = true AND n.old = false"), "{rendered}");
+	assert!(rendered.contains("n.flag = false AND n.old = true"), "{rendered}");
+	let rendered = render("MATCH (n:person) WHERE NOT (n.flag XOR n.old) RETURN n");
+	assert!(rendered.contains("n.flag = true AND n.old = true"), "{rendered}");
+	assert!(rendered.contains("n.flag = false AND n.old = false"), "{rendered}");
+}
+
+#[test]
+fn lowers_xor_in_value_position() {
+	let rendered = render("MATCH (n:person) RETURN n.flag XOR true AS x");
+	assert!(
+		rendered.contains(
+			"IF n.flag = NULL OR n.flag = NONE OR true = NULL OR true = NONE THEN NULL ELSE n.flag != true END AS x"
+		),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_searched_case() {
+	// A WHEN condition lowers in predicate position: an UNKNOWN condition falls
+	// through to the next arm.
+	let rendered =
+		render("MATCH (n:person) RETURN CASE WHEN n.age >= 18 THEN 'adult' ELSE 'minor' END AS c");
+	assert!(
+		rendered.contains(
+			"IF n.age != NONE AND n.age != NULL AND n.age >= 18 THEN 'adult' ELSE 'minor' END AS c"
+		),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_simple_case_without_else() {
+	let rendered =
+		render("MATCH (n:person) RETURN CASE n.city WHEN 'London', 'Paris' THEN 1 END AS c");
+	assert!(
+		rendered.contains("IF n.city = 'London' OR n.city = 'Paris' THEN 1 ELSE NULL END AS c"),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_cast_with_null_guard() {
+	let rendered = render("MATCH (n:person) RETURN CAST(n.age AS STRING) AS s");
+	assert!(
+		rendered.contains("IF n.age = NULL OR n.age = NONE THEN NULL ELSE <string> n.age END AS s"),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_typed_predicates() {
+	let rendered = render("MATCH (n:person) WHERE n.age IS TYPED INT RETURN n");
+	assert!(rendered.contains("WHERE [n] type::is_int(n.age)\n"), "{rendered}");
+	// A null is neither typed nor not typed: the negated test is guarded.
+	let rendered = render("MATCH (n:person) WHERE n.tags IS NOT TYPED LIST RETURN n");
+	assert!(
+		rendered.contains("n.tags != NONE AND n.tags != NULL AND !type::is_array(n.tags)"),
+		"{rendered}"
+	);
+}
+
+#[test]
+fn lowers_label_and_endpoint_predicates() {
+	let rendered = render(
+		"MATCH (a:person)-[k]->(b) WHERE b IS LABELED person | robot AND a IS SOURCE OF k \
+		 AND b IS DESTINATION OF k RETURN a",
+	);
+	assert!(
+		rendered.contains("record::tb(b.id) = 'person' OR record::tb(b.id) = 'robot'"),
+		"{rendered}"
+	);
+	assert!(rendered.contains("WHERE [a k] k.in = a.id"), "{rendered}");
+	assert!(rendered.contains("WHERE [k b] k.out = b.id"), "{rendered}");
+}
+
+#[test]
+fn guards_label_predicate_on_optional_variable() {
+	let rendered =
+		render("MATCH (a:person) OPTIONAL MATCH (a)-[:knows]->(b) WHERE b IS LABELED % RETURN a");
+	assert!(rendered.contains("b != NONE AND b != NULL AND true"), "{rendered}");
+}
+
+#[test]
+fn rejects_label_test_on_non_variable() {
+	assert_rejects(
+		"MATCH (n:person) WHERE n.name IS LABELED person RETURN n",
+		"`IS LABELED` expects a node or edge variable",
+		"n.name",
+	);
+	assert_rejects(
+		"MATCH (a:person)-[k]->(b) WHERE k IS SOURCE OF a RETURN a",
+		"`IS SOURCE OF` and `IS DESTINATION OF` test a node variable against an edge variable",
+		"k",
+	);
+}
+
+#[test]
+fn lowers_exists_as_semi_join_stage() {
+	assert_eq!(
+		render("MATCH (a:person) WHERE EXISTS { MATCH (a)-[:knows]->(b:person) } RETURN a"),
+		"bindings: a:Node __e0:Edge* b:Node\n\
+		 MATCH (a:person)\n\
+		 EXISTS __x0 ON [a]\n\
+		 \x20\x20MATCH (a)-[__e0:knows]->(b:person)\n\
+		 \x20\x20FILTER [] __x0 = true\n\
+		 RETURN a AS a"
+	);
+}
+
+#[test]
+fn lowers_not_exists_correlated_on_two_outer_nodes() {
+	// The body chain expands off `a`; the outer `b` it reaches becomes a hidden
+	// node equated with `b`, so the semi-join keys on both.
+	let rendered =
+		render("MATCH (a:person), (b:person) WHERE NOT EXISTS { (a)-[:knows]->(b) } RETURN a, b");
+	assert!(rendered.contains("EXISTS __x0 ON [a b]"), "{rendered}");
+	assert!(rendered.contains("MATCH (a)-[__e0:knows]->(__v0)"), "{rendered}");
+	assert!(rendered.contains("WHERE [b __v0] b.id = __v0.id"), "{rendered}");
+	assert!(rendered.contains("FILTER [] __x0 != true"), "{rendered}");
+}
+
+#[test]
+fn lowers_exists_in_return_items() {
+	let rendered = render(
+		"MATCH (a:person) WHERE a.age > 30 OR EXISTS { (a)-[:knows]->() } \
+		 RETURN a.name AS name, EXISTS { (a)<-[:knows]-() } AS known",
+	);
+	assert!(rendered.contains("EXISTS __x0 ON [a]"), "{rendered}");
+	assert!(rendered.contains("FILTER [a] a.age != NONE"), "{rendered}");
+	assert!(rendered.contains("EXISTS __x1 ON [a]"), "{rendered}");
+	assert!(rendered.contains("__x1 = true AS known"), "{rendered}");
+}
+
+#[test]
+fn exists_body_variables_are_local() {
 	assert_rejects(
-		"MATCH (n:person) WHERE n.flag XOR n.old RETURN n",
-		"`XOR` is not supported yet",
-		"n.flag XOR n.old",
+		"MATCH (a:person) WHERE EXISTS { (a)-[:knows]->(b) } RETURN b",
+		"Unknown variable `b`",
+		"b",
 	);
+	// A later clause may reuse the name for a binding of its own.
+	let rendered =
+		render("MATCH (a:person) WHERE EXISTS { (a)-[:knows]->(b) } MATCH (b:person) RETURN *");
+	assert!(rendered.contains("RETURN a AS a, b AS b"), "{rendered}");
 }
 
 #[test]
-fn rejects_xor_in_value_position() {
+fn rejects_unsupported_exists_positions() {
+	assert_rejects(
+		"MATCH (a:person) OPTIONAL MATCH (a)-[:knows]->(b) WHERE EXISTS { (b)-[:knows]->() } \
+		 RETURN a",
+		"`EXISTS` inside an OPTIONAL clause is not supported yet",
+		"EXISTS { (b)-[:knows]->() }",
+	);
+	assert_rejects(
+		"MATCH (a:person) WHERE EXISTS { MATCH (a)-[:knows]->(b) WHERE EXISTS { (b)-[:knows]->() } } \
+		 RETURN a",
+		"Nested `EXISTS` subqueries are not supported yet",
+		"EXISTS { (b)-[:knows]->() }",
+	);
+	assert_rejects(
+		"MATCH (a:person) WHERE EXISTS { MATCH (a)-[:knows]->(b) OPTIONAL MATCH (b)-[:knows]->(c) } \
+		 RETURN a",
+		"OPTIONAL inside an `EXISTS` subquery is not supported yet",
+		"OPTIONAL MATCH (b)-[:knows]->(c)",
+	);
+	assert_rejects(
+		"MATCH (a:person) SET a.seen = true RETURN EXISTS { (a)-[:knows]->() } AS k",
+		"`EXISTS` after a write statement is not supported yet",
+		"EXISTS { (a)-[:knows]->() }",
+	);
 	assert_rejects(
-		"MATCH (n:person) RETURN n.flag XOR true AS x",
-		"`XOR` is not supported yet",
-		"n.flag XOR true",
+		"MATCH (a:person) SET a.social = EXISTS { (a)-[:knows]->() } RETURN a",
+		"`EXISTS` is only supported in MATCH WHERE clauses and RETURN items",
+		"EXISTS { (a)-[:knows]->() }",
 	);
 }
 
@@ -2128,33 +2444,72 @@ fn rejects_query_leading_with_optional() {
 }
 
 #[test]
-fn rejects_optional_leading_multi_hop_bound_var_expansion() {
+fn optional_leading_multi_hop_bound_var_expansion_lowers() {
 	// An OPTIONAL block's leading clause whose only anchor is the bound `a` (no
-	// labelled element) is realised off the OUTER accumulator by the single-hop
-	// `OptionalExpand` only. A MULTI-hop bound-variable expansion off the outer
-	// accumulator has no operator (it is neither a self-rootable subplan nor a
-	// single-hop fast path), so it is rejected at lowering rather than reaching a
-	// planner internal error.
-	assert_rejects(
-		"MATCH (a:person) OPTIONAL MATCH (a)-[k1]->(x)-[k2]->(b) RETURN a",
-		"This MATCH pattern shape is not supported yet",
-		"(a)",
+	// labelled element) cannot self-root; the planner seeds the block subplan from
+	// the distinct outer `a` values and left-joins it back on `a`, so a MULTI-hop
+	// bound-variable expansion lowers like any other chain.
+	assert_eq!(
+		render("MATCH (a:person) OPTIONAL MATCH (a)-[k1]->(x)-[k2]->(b) RETURN a"),
+		"bindings: a:Node k1:Edge x:Node k2:Edge b:Node\n\
+		 MATCH (a:person)\n\
+		 OPTIONAL#0 MATCH (a)-[k1]->(x)-[k2]->(b)\n\
+		 RETURN a AS a"
 	);
 }
 
 #[test]
-fn rejects_optional_leading_quantified_bound_var_expansion() {
-	// Likewise a QUANTIFIED bound-variable expansion leading an OPTIONAL block: a
-	// quantified hop is a `PathExpand` (not the single-hop `OptionalExpand` fast
-	// path), and an unlabelled quantified start cannot self-root a standalone
-	// subplan — so it is rejected at lowering.
+fn optional_leading_quantified_bound_var_expansion_lowers() {
+	// Likewise a QUANTIFIED bound-variable expansion leading an OPTIONAL block: the
+	// `PathExpand` runs inside the seeded block subplan.
+	assert_eq!(
+		render("MATCH (a:person) OPTIONAL MATCH (a)-[:knows]->{1,3}(b:person) RETURN a"),
+		"bindings: a:Node __e0:EdgeGroup* b:Node\n\
+		 MATCH (a:person)\n\
+		 OPTIONAL#0 MATCH (a)-[__e0:knows]->{1,3}(b:person)\n\
+		 RETURN a AS a"
+	);
+}
+
+#[test]
+fn rejects_optional_leading_multi_hop_bound_var_expansion_after_write() {
+	// After a write stage the outer accumulator cannot be read a second time to
+	// seed a correlated block (the write would run twice), so a bound-variable
+	// anchor leading an OPTIONAL block is only realised by the single-hop
+	// `OptionalExpand`. A MULTI-hop expansion is rejected at lowering.
 	assert_rejects(
-		"MATCH (a:person) OPTIONAL MATCH (a)-[:knows]->{1,3}(b:person) RETURN a",
+		"MATCH (a:person) SET a.seen = true OPTIONAL MATCH (a)-[k1]->(x)-[k2]->(b) RETURN a",
 		"This MATCH pattern shape is not supported yet",
 		"(a)",
 	);
 }
 
+#[test]
+fn optional_leading_single_hop_bound_var_expansion_after_write_lowers() {
+	// The single-hop fast path needs no second read, so it stays available.
+	assert_eq!(
+		render("MATCH (a:person) SET a.seen = true OPTIONAL MATCH (a)-[k]->(b) RETURN a"),
+		"bindings: a:Node k:Edge b:Node\n\
+		 MATCH (a:person)\n\
+		 SET [a] seen = true\n\
+		 OPTIONAL#0 MATCH (a)-[k]->(b)\n\
+		 RETURN a AS a"
+	);
+}
+
+#[test]
+fn clause_leading_bound_var_expansion_lowers() {
+	// A later MANDATORY clause whose leading pattern is anchored only by the
+	// bound `b` extends the accumulator directly (the inner join on `b`, R1).
+	assert_eq!(
+		render("MATCH (a:person)-[:knows]->(b:person) MATCH (b)-[e]->(c)-[f]->(d) RETURN a"),
+		"bindings: a:Node __e0:Edge* b:Node e:Edge c:Node f:Edge d:Node\n\
+		 MATCH (a:person)-[__e0:knows]->(b:person)\n\
+		 MATCH (b)-[e]->(c)-[f]->(d)\n\
+		 RETURN a AS a"
+	);
+}
+
 #[test]
 fn optional_block_continuation_multi_hop_expansion_lowers() {
 	// The mirror of the rejection above: a MULTI-hop bound-variable expansion is
@@ -2210,3 +2565,76 @@ fn rejects_cross_variable_predicate_inside_quantified_edge() {
 		"k.since > a.age",
 	);
 }
+
+// ------------------------------------------------------------------------
+// Composite queries.
+// ------------------------------------------------------------------------
+
+#[test]
+fn union_lowers_each_operand_on_its_own() {
+	assert_eq!(
+		render(
+			"MATCH (n:person) RETURN n.name AS name UNION MATCH (n:city) WHERE n.size > 1 \
+			 RETURN n.name AS name"
+		),
+		"bindings: n:Node\n\
+		 MATCH (n:person)\n\
+		 RETURN n.name AS name\n\
+		 UNION\n\
+		 bindings: n:Node\n\
+		 MATCH (n:city)\n\
+		 \x20\x20WHERE [n] n.size != NONE AND n.size != NULL AND n.size > 1\n\
+		 RETURN n.name AS name"
+	);
+}
+
+#[test]
+fn composite_conjunctions_and_quantifiers() {
+	let rendered = render(
+		"MATCH (a:person) RETURN a.name AS name \
+		 EXCEPT ALL MATCH (b:person) RETURN b.name AS name \
+		 INTERSECT MATCH (c:person) RETURN c.name AS name \
+		 UNION DISTINCT MATCH (d:person) RETURN d.name AS name \
+		 OTHERWISE MATCH (e:person) RETURN e.name AS name",
+	);
+	let conjunctions: Vec<&str> = rendered
+		.lines()
+		.filter(|line| {
+			["UNION", "EXCEPT", "INTERSECT", "OTHERWISE"].iter().any(|kw| line.starts_with(kw))
+		})
+		.collect();
+	assert_eq!(conjunctions, ["EXCEPT ALL", "INTERSECT", "UNION", "OTHERWISE"], "{rendered}");
+}
+
+#[test]
+fn composite_operands_may_order_their_columns_differently() {
+	let rendered = render(
+		"MATCH (a:person) RETURN a.name AS name, a.age AS age \
+		 UNION ALL MATCH (b:person) RETURN b.age AS age, b.name AS name ORDER BY age LIMIT 1",
+	);
+	assert!(rendered.contains("UNION ALL\nbindings: b:Node"), "{rendered}");
+	assert!(rendered.ends_with("ORDER BY b.age ASC\nLIMIT 1"), "{rendered}");
+}
+
+#[test]
+fn rejects_composite_operands_with_different_columns() {
+	assert_rejects(
+		"MATCH (a:person) RETURN a.name AS name UNION MATCH (b:person) RETURN b.age AS age",
+		"The operands of a composite query must return the same columns",
+		"RETURN b.age AS age",
+	);
+}
+
+#[test]
+fn rejects_writing_or_returnless_composite_operands() {
+	assert_rejects(
+		"MATCH (a:person) SET a.seen = true RETURN a UNION MATCH (b:person) RETURN b AS a",
+		"The operands of a composite query cannot modify data",
+		"SET a.seen = true",
+	);
+	assert_rejects(
+		"MATCH (a:person) RETURN a UNION MATCH (b:person)",
+		"Each operand of a composite query must end with a RETURN clause",
+		"MATCH (b:person)",
+	);
+}
@@ -14,9 +14,12 @@
 
 use reblessive::Stk;
 
-use crate::gql::ast::{BinaryOp, GqlExpr, GqlLiteral, Ident, SetQuantifier, TruthValue, UnaryOp};
+use crate::gql::ast::{
+	BinaryOp, CaseWhen, GqlExpr, GqlLiteral, GqlType, GqlTypeKind, Ident, MatchItem, SetQuantifier,
+	TruthValue, UnaryOp,
+};
 use crate::gql::lexer::Lexer;
-use crate::gql::parser::mac::{enter_object_recursion, unexpected};
+use crate::gql::parser::mac::{enter_object_recursion, expected, unexpected};
 use crate::gql::parser::{ParseResult, Parser};
 use crate::gql::token::{Keyword, NumberKind, NumberSuffix, Span, Token, TokenKind, t};
 use crate::syn::error::{bail, syntax_error};
@@ -102,9 +105,12 @@ impl Parser<'_> {
 		Ok(left)
 	}
 
-	/// Level 3: the postfix `IS [NOT] TRUE|FALSE|UNKNOWN` boolean test
-	/// (`#isNotExprAlt`). All other `IS …` predicates which can syntactically
-	/// occur here are rejected with targeted errors.
+	/// Level 3: the postfix `IS [NOT] …` predicates which apply to a larger
+	/// operand: the `TRUE|FALSE|UNKNOWN` boolean test (`#isNotExprAlt`), the
+	/// `TYPED` value type predicate (19.6), the `LABELED` label predicate
+	/// (19.8) and the `SOURCE|DESTINATION OF` predicates (19.10). The
+	/// `NORMALIZED` and `DIRECTED` predicates are rejected with targeted
+	/// errors.
 	async fn parse_is_expr(&mut self, stk: &mut Stk) -> ParseResult<GqlExpr> {
 		let mut expr = self.parse_not_expr(stk).await?;
 		while self.peek_kind() == t!("IS") {
@@ -127,10 +133,39 @@ impl Parser<'_> {
 					);
 				}
 				t!("TYPED") => {
-					bail!(
-						"`IS [NOT] TYPED` type predicates are not supported yet",
-						@is_token.span.covers(token.span)
-					);
+					let ty = self.parse_value_type()?;
+					let span = expr.span().covers(ty.span);
+					expr = GqlExpr::IsTyped {
+						expr: Box::new(expr),
+						ty,
+						negated,
+						span,
+					};
+					continue;
+				}
+				t!("LABELED") => {
+					let label = stk.run(|stk| self.parse_label_expr(stk)).await?;
+					let span = expr.span().covers(label.span());
+					expr = GqlExpr::IsLabeled {
+						expr: Box::new(expr),
+						label,
+						negated,
+						span,
+					};
+					continue;
+				}
+				t!("SOURCE") | t!("DESTINATION") => {
+					expected!(self, t!("OF"));
+					let edge = self.parse_ident()?;
+					let span = expr.span().covers(edge.span);
+					expr = GqlExpr::IsEndpoint {
+						expr: Box::new(expr),
+						edge,
+						source: token.kind == t!("SOURCE"),
+						negated,
+						span,
+					};
+					continue;
 				}
 				t!("NORMALIZED") | t!("NFC") | t!("NFD") | t!("NFKC") | t!("NFKD") => {
 					bail!(
@@ -138,25 +173,17 @@ impl Parser<'_> {
 						@is_token.span.covers(token.span)
 					);
 				}
-				t!("LABELED") => {
-					bail!(
-						"`IS [NOT] LABELED` predicates are not supported yet",
-						@is_token.span.covers(token.span)
-					);
-				}
 				t!("DIRECTED") => {
 					bail!(
 						"`IS [NOT] DIRECTED` predicates are not supported yet",
 						@is_token.span.covers(token.span)
 					);
 				}
-				t!("SOURCE") | t!("DESTINATION") => {
-					bail!(
-						"`IS [NOT] SOURCE/DESTINATION OF` predicates are not supported yet",
-						@is_token.span.covers(token.span)
-					);
-				}
-				_ => unexpected!(self, token, "`TRUE`, `FALSE`, `UNKNOWN` or `NULL`"),
+				_ => unexpected!(
+					self,
+					token,
+					"`TRUE`, `FALSE`, `UNKNOWN`, `NULL`, `TYPED`, `LABELED`, `SOURCE` or `DESTINATION`"
+				),
 			};
 			let span = expr.span().covers(token.span);
 			expr = GqlExpr::IsBool {
@@ -553,14 +580,20 @@ impl Parser<'_> {
 				self.pop_peek();
 				Ok(GqlExpr::Literal(GqlLiteral::Null, token.span))
 			}
-			t!("EXISTS") => {
-				bail!("`EXISTS` predicates are not supported yet", @token.span);
-			}
-			t!("CASE") => {
-				bail!("`CASE` expressions are not supported yet", @token.span);
-			}
+			t!("EXISTS") => self.parse_exists(stk).await,
+			t!("CASE") => self.parse_case(stk).await,
 			t!("CAST") => {
-				bail!("`CAST` expressions are not supported yet", @token.span);
+				self.pop_peek();
+				let open = expected!(self, t!("(")).span;
+				let expr = stk.run(|stk| self.parse_expr(stk)).await?;
+				expected!(self, t!("AS"));
+				let ty = self.parse_value_type()?;
+				self.expect_closing_delimiter(t!(")"), open)?;
+				Ok(GqlExpr::Cast {
+					expr: Box::new(expr),
+					ty,
+					span: token.span.covers(self.last_span()),
+				})
 			}
 			TokenKind::Identifier => {
 				if self.peek1().kind == t!("(") {
@@ -623,6 +656,191 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parse a `CASE` expression (`caseSpecification`, GQL.g4:2493): the
+	/// simple form `CASE operand WHEN v, … THEN r … [ELSE r] END` or the
+	/// searched form `CASE WHEN cond THEN r … [ELSE r] END`.
+	///
+	/// The simple form's partial comparisons (`WHEN > 5`, `WHEN IS NULL`,
+	/// GQL.g4:2513) are rejected: only value equality is supported.
+	async fn parse_case(&mut self, stk: &mut Stk) -> ParseResult<GqlExpr> {
+		let start = expected!(self, t!("CASE")).span;
+		let operand = if self.peek_kind() == t!("WHEN") {
+			None
+		} else {
+			Some(Box::new(stk.run(|stk| self.parse_expr(stk)).await?))
+		};
+		let mut whens = Vec::new();
+		while self.eat(t!("WHEN")) {
+			let mut operands = Vec::new();
+			if operand.is_some() {
+				loop {
+					let token = self.peek();
+					if self.peek_comparison_op().is_some() || token.kind == t!("IS") {
+						bail!(
+							"Partial comparisons in simple `CASE` expressions are not supported yet",
+							@token.span => "compare against a value, or use the searched form `CASE WHEN …`"
+						);
+					}
+					operands.push(stk.run(|stk| self.parse_expr(stk)).await?);
+					if !self.eat(t!(",")) {
+						break;
+					}
+				}
+			} else {
+				operands.push(stk.run(|stk| self.parse_expr(stk)).await?);
+			}
+			expected!(self, t!("THEN"));
+			let result = stk.run(|stk| self.parse_expr(stk)).await?;
+			whens.push(CaseWhen {
+				operands,
+				result,
+			});
+		}
+		if whens.is_empty() {
+			let token = self.peek();
+			unexpected!(self, token, "`WHEN`");
+		}
+		let else_result = if self.eat(t!("ELSE")) {
+			Some(Box::new(stk.run(|stk| self.parse_expr(stk)).await?))
+		} else {
+			None
+		};
+		expected!(self, t!("END"));
+		Ok(GqlExpr::Case {
+			operand,
+			whens,
+			else_result,
+			span: start.covers(self.last_span()),
+		})
+	}
+
+	/// Parse an `EXISTS` predicate (`existsPredicate`, GQL.g4:2024): a graph
+	/// pattern or a `MATCH` statement block in braces or parentheses. The
+	/// nested query specification form is not supported.
+	async fn parse_exists(&mut self, stk: &mut Stk) -> ParseResult<GqlExpr> {
+		let start = expected!(self, t!("EXISTS")).span;
+		let token = self.peek();
+		let close = match token.kind {
+			t!("{") => t!("}"),
+			t!("(") => t!(")"),
+			_ => unexpected!(self, token, "`{` or `(`"),
+		};
+		let open = self.pop_peek().span;
+		let token = self.peek();
+		let items = match token.kind {
+			t!("MATCH") | t!("OPTIONAL") => {
+				stk.run(|stk| self.parse_match_statement_block(stk)).await?
+			}
+			_ => {
+				let clause = stk.run(|stk| self.parse_match_clause(stk, token.span)).await?;
+				vec![MatchItem::Match(clause)]
+			}
+		};
+		self.expect_closing_delimiter(close, open)?;
+		Ok(GqlExpr::Exists {
+			items,
+			span: start.covers(self.last_span()),
+		})
+	}
+
+	/// Parse a value type (`valueType`, GQL.g4:1340) in a `CAST` or
+	/// `IS TYPED` position. Only the predefined types with a SurrealDB
+	/// counterpart are accepted; the remaining predefined types, and the
+	/// parameterized and constructed forms (`STRING(10)`, `LIST<INT>`), are
+	/// rejected.
+	fn parse_value_type(&mut self) -> ParseResult<GqlType> {
+		let token = self.next();
+		let kind = match token.kind {
+			t!("BOOL") | t!("BOOLEAN") => GqlTypeKind::Bool,
+			t!("STRING") | t!("CHAR") | t!("VARCHAR") => GqlTypeKind::String,
+			t!("INT")
+			| t!("INTEGER")
+			| t!("INT8")
+			| t!("INTEGER8")
+			| t!("INT16")
+			| t!("INTEGER16")
+			| t!("INT32")
+			| t!("INTEGER32")
+			| t!("INT64")
+			| t!("INTEGER64")
+			| t!("INT128")
+			| t!("INTEGER128")
+			| t!("INT256")
+			| t!("INTEGER256")
+			| t!("SMALLINT")
+			| t!("BIGINT") => GqlTypeKind::Int,
+			t!("FLOAT")
+			| t!("FLOAT16")
+			| t!("FLOAT32")
+			| t!("FLOAT64")
+			| t!("FLOAT128")
+			| t!("FLOAT256")
+			| t!("REAL") => GqlTypeKind::Float,
+			t!("DOUBLE") => {
+				self.eat(t!("PRECISION"));
+				GqlTypeKind::Float
+			}
+			t!("DECIMAL") | t!("DEC") => GqlTypeKind::Decimal,
+			t!("ZONED") if self.peek_kind() == t!("DATETIME") => {
+				self.pop_peek();
+				GqlTypeKind::Datetime
+			}
+			t!("DATETIME") | t!("ZONED_DATETIME") | t!("TIMESTAMP") => GqlTypeKind::Datetime,
+			t!("DURATION") => GqlTypeKind::Duration,
+			t!("LIST") | t!("ARRAY") => GqlTypeKind::List,
+			t!("RECORD") => GqlTypeKind::Record,
+			TokenKind::Keyword(
+				Keyword::Uint
+				| Keyword::Uint8
+				| Keyword::Uint16
+				| Keyword::Uint32
+				| Keyword::Uint64
+				| Keyword::Uint128
+				| Keyword::Uint256
+				| Keyword::Ubigint
+				| Keyword::Usmallint
+				| Keyword::Unsigned
+				| Keyword::Signed
+				| Keyword::Small
+				| Keyword::Big
+				| Keyword::Date
+				| Keyword::Time
+				| Keyword::LocalDatetime
+				| Keyword::LocalTime
+				| Keyword::LocalTimestamp
+				| Keyword::ZonedTime
+				| Keyword::Zoned
+				| Keyword::Local
+				| Keyword::Bytes
+				| Keyword::Binary
+				| Keyword::Varbinary
+				| Keyword::Path
+				| Keyword::Any
+				| Keyword::Nothing
+				| Keyword::Null,
+			) => {
+				bail!(
+					"The `{}` value type is not supported yet",
+					self.span_str(token.span),
+					@token.span
+				);
+			}
+			_ => unexpected!(self, token, "a value type"),
+		};
+		let span = token.span.covers(self.last_span());
+		let next = self.peek();
+		if matches!(next.kind, t!("(") | t!("<")) {
+			bail!(
+				"Parameterized and constructed value types are not supported yet",
+				@span.covers(next.span)
+			);
+		}
+		Ok(GqlType {
+			kind,
+			span,
+		})
+	}
+
 	/// Parse a function call: a name followed by a parenthesized, comma
 	/// separated argument list. The caller must have checked that the next
 	/// two tokens are a name and `(`.
@@ -630,7 +848,7 @@ impl Parser<'_> {
 	/// The aggregate argument forms — a sole `*` (`count(*)`, GQL.g4:2381)
 	/// and a leading `DISTINCT`/`ALL` set quantifier (`generalSetFunction`,
 	/// GQL.g4:2387) — are parsed into the AST for any function name; lowering
-	/// validates the name and rejects aggregates with a targeted error.
+	/// validates the name and rejects them on a scalar function.
 	async fn parse_function_call(&mut self, stk: &mut Stk) -> ParseResult<GqlExpr> {
 		let name_token = self.pop_peek();
 		let name = Ident {
@@ -9,7 +9,7 @@
 //!
 //! The parser implements the grammar subset distilled in
 //! `doc/gql/REFERENCE.md` exactly, and recognises the surrounding grammar
-//! (write statements, composite queries, path search prefixes, …) only to
+//! (`LET`/`FOR` statements, `USE` graphs, typed temporal literals, …) only to
 //! reject it with precise spans and actionable messages.
 //!
 //! Token lookahead is at most three tokens (`peek`, `peek1` and `peek2`), the
@@ -21,7 +21,7 @@ use reblessive::Stk;
 
 use self::token_buffer::TokenBuffer;
 use crate::gql::GqlParserSettings;
-use crate::gql::ast::{GqlQuery, Ident};
+use crate::gql::ast::{CompositeOperand, GqlQuery, Ident};
 use crate::gql::lexer::Lexer;
 use crate::gql::token::{Span, Token, TokenKind};
 use crate::syn::error::{SyntaxError, bail};
@@ -176,14 +176,31 @@ impl<'a> Parser<'a> {
 	}
 
 	/// Parse a full query: the primary entry point of the parser.
+	///
+	/// A query is a linear query, optionally followed by further linear queries
+	/// each introduced by a query conjunction (`UNION`, `EXCEPT`, `INTERSECT`
+	/// or `OTHERWISE`).
 	pub async fn parse_query(&mut self, stk: &mut Stk) -> ParseResult<GqlQuery> {
 		let program = self.parse_statement(stk).await?;
+		let mut composite = Vec::new();
+		while let Some(conjunction) = self.parse_query_conjunction() {
+			let token = self.peek();
+			if token.is_eof() || Self::is_query_conjunction(token.kind) {
+				unexpected!(self, token, "a query after the query conjunction");
+			}
+			let query = self.parse_statement(stk).await?;
+			composite.push(CompositeOperand {
+				conjunction,
+				query,
+			});
+		}
 		let token = self.peek();
 		if !token.is_eof() {
 			unexpected!(self, token, "the query to end");
 		}
 		Ok(GqlQuery {
 			program,
+			composite,
 		})
 	}
 
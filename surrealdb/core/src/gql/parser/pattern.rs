@@ -513,7 +513,7 @@ impl Parser<'_> {
 
 	/// Parse a label expression (GQL.g4:1102-1109), with precedence
 	/// `!` > `&` > `|`.
-	async fn parse_label_expr(&mut self, stk: &mut Stk) -> ParseResult<LabelExpr> {
+	pub(super) async fn parse_label_expr(&mut self, stk: &mut Stk) -> ParseResult<LabelExpr> {
 		let mut left = self.parse_label_conjunction(stk).await?;
 		while self.eat(t!("|")) {
 			let right = self.parse_label_conjunction(stk).await?;
@@ -1,19 +1,20 @@
-//! Statement level parsing: the linear query statement, `MATCH` clauses and
-//! the `RETURN` clause with its trailing `ORDER BY`/`OFFSET`/`LIMIT` page
-//! statement.
+//! Statement level parsing: the linear query statement, the query
+//! conjunctions composing linear queries, `MATCH` clauses and the `RETURN`
+//! clause with its trailing `ORDER BY`/`OFFSET`/`LIMIT` page statement.
 //!
-//! From `ambientLinearQueryStatement` (GQL.g4:554), `matchStatement` (14.4,
-//! GQL.g4:578-599), `primitiveResultStatement`/`returnStatement` (14.10-14.11,
-//! GQL.g4:660-685) and `orderByAndPageStatement` (14.9, GQL.g4:652). All
+//! From `queryConjunction` (14.2, GQL.g4:509), `ambientLinearQueryStatement`
+//! (GQL.g4:554), `matchStatement` (14.4, GQL.g4:578-599),
+//! `primitiveResultStatement`/`returnStatement` (14.10-14.11, GQL.g4:660-685)
+//! and `orderByAndPageStatement` (14.9, GQL.g4:652). All
 //! constructs which share grammar real estate with this subset but are not
 //! supported are recognised and rejected with precise spans.
 
 use reblessive::Stk;
 
 use crate::gql::ast::{
-	DetachMode, GqlExpr, GqlGroupItem, GqlLiteral, GqlStep, LinearQuery, MatchClause, MatchItem,
-	MutationStatement, OptionalBlock, OrderItem, ReturnClause, ReturnItem, ReturnItems,
-	SetQuantifier,
+	ConjunctionKind, DetachMode, GqlExpr, GqlGroupItem, GqlLiteral, GqlStep, LinearQuery,
+	MatchClause, MatchItem, MutationStatement, OptionalBlock, OrderItem, QueryConjunction,
+	ReturnClause, ReturnItem, ReturnItems, SetQuantifier,
 };
 use crate::gql::parser::mac::{enter_object_recursion, expected, unexpected};
 use crate::gql::parser::{ParseResult, Parser};
@@ -82,7 +83,9 @@ impl Parser<'_> {
 		let token = self.peek();
 		let ret = match token.kind {
 			t!("RETURN") => Some(self.parse_return_clause(stk).await?),
-			_ if token.is_eof() => None,
+			// A composite query operand without a RETURN is rejected in the
+			// lowering, with the other operand rules.
+			_ if token.is_eof() || Self::is_query_conjunction(token.kind) => None,
 			t!("FINISH") => {
 				bail!(
 					"FINISH statements are not supported yet",
@@ -180,7 +183,10 @@ impl Parser<'_> {
 	/// Parse a `matchStatementBlock`: one or more `matchStatement`s
 	/// (GQL.g4:597), each a plain `MATCH` clause or a nested `OPTIONAL`
 	/// operand. At least one statement is required.
-	async fn parse_match_statement_block(&mut self, stk: &mut Stk) -> ParseResult<Vec<MatchItem>> {
+	pub(super) async fn parse_match_statement_block(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<Vec<MatchItem>> {
 		let mut items = Vec::new();
 		loop {
 			let token = self.peek();
@@ -205,7 +211,11 @@ impl Parser<'_> {
 	/// Parse a `MATCH` clause graph pattern: `matchMode? pathPatternList
 	/// keepClause? graphPatternWhereClause?` (GQL.g4:803). The `MATCH` keyword
 	/// must already be consumed; `start` is its span.
-	async fn parse_match_clause(&mut self, stk: &mut Stk, start: Span) -> ParseResult<MatchClause> {
+	pub(super) async fn parse_match_clause(
+		&mut self,
+		stk: &mut Stk,
+		start: Span,
+	) -> ParseResult<MatchClause> {
 		// `REPEATABLE`/`DIFFERENT` are non-reserved words, so they are only a
 		// match mode when followed by their element/edge synonym; otherwise
 		// they can begin a path pattern as a path variable.
@@ -454,8 +464,42 @@ impl Parser<'_> {
 		}
 	}
 
-	/// Rejects out-of-order, duplicated or composite trailing clauses with a
-	/// targeted error before the generic end-of-query check runs.
+	/// Parse a query conjunction (`queryConjunction`, GQL.g4:509) if the next
+	/// token starts one: `UNION`, `EXCEPT` or `INTERSECT` with an optional
+	/// `ALL`/`DISTINCT` quantifier, or `OTHERWISE`.
+	pub(super) fn parse_query_conjunction(&mut self) -> Option<QueryConjunction> {
+		let token = self.peek();
+		let kind = match token.kind {
+			t!("UNION") => ConjunctionKind::Union,
+			t!("EXCEPT") => ConjunctionKind::Except,
+			t!("INTERSECT") => ConjunctionKind::Intersect,
+			t!("OTHERWISE") => ConjunctionKind::Otherwise,
+			_ => return None,
+		};
+		self.pop_peek();
+		let quantifier = if kind == ConjunctionKind::Otherwise {
+			None
+		} else if self.eat(t!("DISTINCT")) {
+			Some(SetQuantifier::Distinct)
+		} else if self.eat(t!("ALL")) {
+			Some(SetQuantifier::All)
+		} else {
+			None
+		};
+		Some(QueryConjunction {
+			kind,
+			quantifier,
+			span: token.span.covers(self.last_span()),
+		})
+	}
+
+	/// Whether a token starts a query conjunction.
+	pub(super) fn is_query_conjunction(kind: TokenKind) -> bool {
+		matches!(kind, t!("UNION") | t!("EXCEPT") | t!("INTERSECT") | t!("OTHERWISE"))
+	}
+
+	/// Rejects out-of-order or duplicated trailing clauses with a targeted
+	/// error before the generic end-of-query check runs.
 	fn check_trailing_clauses(&mut self) -> ParseResult<()> {
 		let token = self.peek();
 		match token.kind {
@@ -466,12 +510,6 @@ impl Parser<'_> {
 					@token.span => "ORDER BY, OFFSET/SKIP and LIMIT may appear at most once each, in that order"
 				);
 			}
-			t!("UNION") | t!("EXCEPT") | t!("INTERSECT") | t!("OTHERWISE") => {
-				bail!(
-					"Composite queries (`UNION`, `EXCEPT`, `INTERSECT`, `OTHERWISE`) are not supported yet",
-					@token.span
-				);
-			}
 			t!("GROUP") => {
 				bail!(
 					"Unexpected `GROUP` clause",
@@ -152,24 +152,83 @@ fn boolean_tests(#[case] source: &str, #[case] expected: &str) {
 }
 
 #[rstest]
-#[case::typed("RETURN a IS TYPED INT", "`IS [NOT] TYPED`")]
-#[case::not_typed("RETURN a IS NOT TYPED INT", "`IS [NOT] TYPED`")]
+#[case::typed("a IS TYPED INT", "(a IS TYPED Int)")]
+#[case::not_typed("a IS NOT TYPED STRING", "(a IS NOT TYPED String)")]
+#[case::typed_double_precision("a IS TYPED DOUBLE PRECISION", "(a IS TYPED Float)")]
+#[case::typed_zoned_datetime("a IS TYPED ZONED DATETIME", "(a IS TYPED Datetime)")]
+#[case::typed_binds_above_and("a IS TYPED BOOL AND b", "((a IS TYPED Bool) AND b)")]
+#[case::labeled("a IS LABELED Person", "(a IS LABELED Person)")]
+#[case::not_labeled("a IS NOT LABELED !(A|B)", "(a IS NOT LABELED !((A|B)))")]
+#[case::source("a IS SOURCE OF k", "(a IS SOURCE OF k)")]
+#[case::not_destination("a IS NOT DESTINATION OF k", "(a IS NOT DESTINATION OF k)")]
+fn value_type_label_and_endpoint_predicates(#[case] source: &str, #[case] expected: &str) {
+	// `valueTypePredicate` (19.6), `labeledPredicate` (19.8) and
+	// `sourceDestinationPredicate` (19.10) share the `IS` test level.
+	assert_eq!(parse_expr_str(source), expected);
+}
+
+#[rstest]
 #[case::normalized("RETURN a IS NORMALIZED", "`IS [NOT] NORMALIZED`")]
 #[case::nfc_normalized("RETURN a IS NFC NORMALIZED", "`IS [NOT] NORMALIZED`")]
-#[case::labeled("RETURN a IS LABELED l", "`IS [NOT] LABELED`")]
 #[case::directed("RETURN a IS DIRECTED", "`IS [NOT] DIRECTED`")]
-#[case::source("RETURN a IS SOURCE OF k", "`IS [NOT] SOURCE/DESTINATION OF`")]
-#[case::destination("RETURN a IS DESTINATION OF k", "`IS [NOT] SOURCE/DESTINATION OF`")]
-fn other_is_predicates_rejected(#[case] source: &str, #[case] expected: &str) {
-	// The remaining `predicate` alternatives (19.2) parse-and-reject.
+#[case::source_without_of("RETURN a IS SOURCE k", "expected `OF`")]
+#[case::unsigned_type("RETURN a IS TYPED UINT8", "The `UINT8` value type is not supported yet")]
+#[case::date_type("RETURN a IS TYPED DATE", "The `DATE` value type is not supported yet")]
+#[case::sized_type("RETURN CAST(a AS STRING(10))", "Parameterized and constructed value types")]
+#[case::list_of_type("RETURN CAST(a AS LIST<INT>)", "Parameterized and constructed value types")]
+fn other_is_predicates_and_types_rejected(#[case] source: &str, #[case] expected: &str) {
+	// The remaining `predicate` alternatives (19.2) and value types
+	// parse-and-reject.
 	let error = parse_err(source);
 	assert!(error.contains(expected), "{error}");
 }
 
-#[test]
-fn cast_rejected() {
-	let error = parse_err("RETURN CAST(1 AS FLOAT)");
-	assert!(error.contains("`CAST` expressions are not supported yet"), "{error}");
+#[rstest]
+#[case::cast("CAST(a.x AS FLOAT)", "CAST(a.x AS Float)")]
+#[case::cast_expr("CAST(a + 1 AS STRING) || 'x'", "(CAST((a + 1) AS String) || 'x')")]
+#[case::searched("CASE WHEN a THEN 1 ELSE 2 END", "(CASE WHEN a THEN 1 ELSE 2 END)")]
+#[case::searched_no_else(
+	"CASE WHEN a > 1 THEN 'x' WHEN b THEN 'y' END",
+	"(CASE WHEN (a > 1) THEN 'x' WHEN b THEN 'y' END)"
+)]
+#[case::simple(
+	"CASE a.x WHEN 1, 2 THEN 'low' ELSE 'high' END",
+	"(CASE a.x WHEN 1, 2 THEN 'low' ELSE 'high' END)"
+)]
+#[case::nested(
+	"CASE WHEN a THEN CASE WHEN b THEN 1 END END",
+	"(CASE WHEN a THEN (CASE WHEN b THEN 1 END) END)"
+)]
+fn case_and_cast(#[case] source: &str, #[case] expected: &str) {
+	assert_eq!(parse_expr_str(source), expected);
+}
+
+#[rstest]
+#[case::no_when("RETURN CASE ELSE 1 END", "expected `WHEN`")]
+#[case::no_end("RETURN CASE WHEN a THEN 1", "expected `END`")]
+#[case::partial_comparison("RETURN CASE a WHEN > 1 THEN 2 END", "Partial comparisons")]
+#[case::partial_null_test("RETURN CASE a WHEN IS NULL THEN 2 END", "Partial comparisons")]
+#[case::cast_no_as("RETURN CAST(a FLOAT)", "expected `AS`")]
+fn case_and_cast_rejected(#[case] source: &str, #[case] expected: &str) {
+	let error = parse_err(source);
+	assert!(error.contains(expected), "{error}");
+}
+
+#[rstest]
+#[case::braced_pattern("EXISTS { (a)-[:KNOWS]->(b) }", 1)]
+#[case::paren_pattern_where("EXISTS ((a)-[:KNOWS]->(b) WHERE b.age > 1)", 1)]
+#[case::match_block("EXISTS { MATCH (a)-[:KNOWS]->(b) MATCH (b)-[:KNOWS]->(c) }", 2)]
+fn exists_predicates(#[case] source: &str, #[case] items: usize) {
+	// `existsPredicate` (19.4): a bare graph pattern is held as one `MATCH`.
+	let expr = parse_return_expr(source);
+	let GqlExpr::Exists {
+		items: parsed,
+		..
+	} = &expr
+	else {
+		panic!("expected an EXISTS predicate in {source:?}");
+	};
+	assert_eq!(parsed.len(), items);
 }
 
 #[rstest]
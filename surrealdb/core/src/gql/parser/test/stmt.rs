@@ -1,13 +1,16 @@
 //! Statement level tests: `MATCH`/`OPTIONAL MATCH` clauses, the `RETURN`
 //! clause with `DISTINCT`/`ALL`, `*` and aliases, the trailing `ORDER BY`,
-//! `OFFSET`/`SKIP` and `LIMIT` page clauses, and the targeted rejections of
-//! all statement forms outside the v1 subset.
+//! `OFFSET`/`SKIP` and `LIMIT` page clauses, the query conjunctions of
+//! composite queries, and the targeted rejections of all statement forms
+//! outside the v1 subset.
 
 use rstest::rstest;
 
 use super::{match_clauses, parse, parse_err, parse_return_items};
-use crate::gql::ast::{GqlExpr, GqlLiteral, MatchItem, ReturnItems, SetQuantifier};
-use crate::gql::{GqlParserSettings, parse_with_settings};
+use crate::gql::ast::{
+	ConjunctionKind, GqlExpr, GqlLiteral, MatchItem, ReturnItems, SetQuantifier,
+};
+use crate::gql::{GqlParserSettings, parse_str, parse_with_settings};
 
 /// Asserts that a count specification holds the given integer literal.
 #[track_caller]
@@ -277,14 +280,64 @@ fn finish_rejected() {
 }
 
 #[rstest]
-#[case::union("MATCH (a) RETURN a UNION MATCH (b) RETURN b")]
-#[case::except("MATCH (a) RETURN a EXCEPT MATCH (b) RETURN b")]
-#[case::intersect("MATCH (a) RETURN a INTERSECT MATCH (b) RETURN b")]
-#[case::otherwise("MATCH (a) RETURN a OTHERWISE MATCH (b) RETURN b")]
-fn composite_queries_rejected(#[case] source: &str) {
+#[case::union("MATCH (a) RETURN a UNION MATCH (b) RETURN b", ConjunctionKind::Union, None)]
+#[case::union_all(
+	"MATCH (a) RETURN a UNION ALL MATCH (b) RETURN b",
+	ConjunctionKind::Union,
+	Some(SetQuantifier::All)
+)]
+#[case::except_distinct(
+	"MATCH (a) RETURN a EXCEPT DISTINCT MATCH (b) RETURN b",
+	ConjunctionKind::Except,
+	Some(SetQuantifier::Distinct)
+)]
+#[case::intersect(
+	"MATCH (a) RETURN a INTERSECT MATCH (b) RETURN b",
+	ConjunctionKind::Intersect,
+	None
+)]
+#[case::otherwise(
+	"MATCH (a) RETURN a OTHERWISE MATCH (b) RETURN b",
+	ConjunctionKind::Otherwise,
+	None
+)]
+fn composite_queries_parse(
+	#[case] source: &str,
+	#[case] kind: ConjunctionKind,
+	#[case] quantifier: Option<SetQuantifier>,
+) {
 	// `compositeQueryExpression` (GQL.g4:504).
-	let error = parse_err(source);
-	assert!(error.contains("Composite queries"), "{error}");
+	let query = parse_str(source).expect("composite query parses");
+	assert_eq!(query.composite.len(), 1);
+	let operand = &query.composite[0];
+	assert_eq!(operand.conjunction.kind, kind);
+	assert_eq!(operand.conjunction.quantifier, quantifier);
+	assert!(operand.query.ret.is_some());
+	assert!(query.program.ret.is_some());
+}
+
+#[test]
+fn composite_query_chains_left_to_right() {
+	let query = parse_str(
+		"MATCH (a) RETURN a.x AS x UNION MATCH (b) RETURN b.x AS x ORDER BY x LIMIT 1 \
+		 UNION ALL MATCH (c) RETURN c.x AS x",
+	)
+	.expect("composite query parses");
+	assert_eq!(query.composite.len(), 2);
+	// A page clause belongs to the operand it follows.
+	assert!(query.composite[0].query.ret.as_ref().is_some_and(|ret| ret.limit.is_some()));
+	assert_eq!(query.composite[1].conjunction.quantifier, Some(SetQuantifier::All));
+}
+
+#[test]
+fn composite_query_needs_an_operand() {
+	let error = parse_err("MATCH (a) RETURN a UNION");
+	assert!(error.contains("expected a query after the query conjunction"), "{error}");
+	let error = parse_err("MATCH (a) RETURN a UNION OTHERWISE MATCH (b) RETURN b");
+	assert!(error.contains("expected a query after the query conjunction"), "{error}");
+	// `OTHERWISE` takes no set quantifier.
+	let error = parse_err("MATCH (a) RETURN a OTHERWISE ALL MATCH (b) RETURN b");
+	assert!(error.contains("Unexpected token"), "{error}");
 }
 
 #[rstest]
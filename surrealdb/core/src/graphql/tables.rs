@@ -359,6 +359,7 @@ fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectS
 		omit: vec![],
 		with: None,
 		cond: None,
+		fusion: None,
 		split: None,
 		group: None,
 		order: None,
@@ -392,6 +393,7 @@ fn select_field_from_record(
 		omit: vec![],
 		with: None,
 		cond: None,
+		fusion: None,
 		split: None,
 		group: None,
 		order: None,
@@ -420,6 +422,7 @@ fn select_all_from_table(
 		fields: Fields::all(),
 		order,
 		cond,
+		fusion: None,
 		limit,
 		start,
 		version: version_to_expr(version),
@@ -2499,9 +2502,10 @@ const PAGE_INFO_TYPE: &str = "PageInfo";
 /// unconditional avoids drift between the filter generator and the type
 /// registration step.
 pub(crate) fn register_filter_helper_types(types: &mut Vec<Type>) {
-	// `Minkowski(Number)` is intentionally omitted — it takes an extra param
-	// that doesn't fit a flat enum; use the `call` operator with
-	// `vector::distance::minkowski` if it's needed.
+	// `Minkowski(Number)` and `Mahalanobis(..)` are intentionally omitted —
+	// they take an extra param that doesn't fit a flat enum; use the `call`
+	// operator with `vector::distance::minkowski` or
+	// `vector::distance::mahalanobis` if they're needed.
 	types.push(Type::Enum(
 		Enum::new(VECTOR_DISTANCE_ENUM)
 			.description(
@@ -2514,7 +2518,8 @@ pub(crate) fn register_filter_helper_types(types: &mut Vec<Type>) {
 			.item("HAMMING")
 			.item("JACCARD")
 			.item("CHEBYSHEV")
-			.item("PEARSON"),
+			.item("PEARSON")
+			.item("SPEARMAN"),
 	));
 
 	types.push(Type::Enum(
@@ -2720,11 +2725,12 @@ fn distance_variant(name: &str) -> Option<crate::catalog::Distance> {
 		"JACCARD" => Some(Distance::Jaccard),
 		"CHEBYSHEV" => Some(Distance::Chebyshev),
 		"PEARSON" => Some(Distance::Pearson),
+		"SPEARMAN" => Some(Distance::Spearman),
 		_ => None,
 	}
 }
 
-/// Cosine/Jaccard/Pearson have dedicated *similarity* functions
+/// Cosine/Jaccard/Pearson/Spearman have dedicated *similarity* functions
 /// (higher = closer); the others fall back to the corresponding *distance*
 /// function (lower = closer).
 fn distance_function_name(name: &str) -> Option<&'static str> {
@@ -2732,6 +2738,7 @@ fn distance_function_name(name: &str) -> Option<&'static str> {
 		"COSINE" => Some("vector::similarity::cosine"),
 		"JACCARD" => Some("vector::similarity::jaccard"),
 		"PEARSON" => Some("vector::similarity::pearson"),
+		"SPEARMAN" => Some("vector::similarity::spearman"),
 		"EUCLIDEAN" => Some("vector::distance::euclidean"),
 		"MANHATTAN" => Some("vector::distance::manhattan"),
 		"HAMMING" => Some("vector::distance::hamming"),
@@ -3169,6 +3176,7 @@ fn make_table_aggregate_field(
 				what: vec![Expr::Table(tb_name)],
 				fields: Fields::Select(select_fields),
 				cond,
+				fusion: None,
 				group,
 				order: None,
 				limit: None,
@@ -3368,6 +3376,7 @@ async fn run_connection_total_count(
 			alias: Some(Idiom::field("count".to_string())),
 		})]),
 		cond: q.cond.clone(),
+		fusion: None,
 		group: Some(Groups(Vec::new())),
 		version: version_to_expr(&q.version),
 		timeout: Expr::Literal(Literal::None),
@@ -1,13 +1,22 @@
-use anyhow::Result;
+use std::sync::OnceLock;
+
+use anyhow::{Result, bail};
 use deunicode::deunicode;
 use rust_stemmers::{Algorithm, Stemmer};
+use surrealdb_types::ToSql;
 
-use crate::expr::filter::Filter as SqlFilter;
+use crate::ctx::FrozenContext;
+use crate::dbs::Options;
+use crate::err::Error;
+use crate::expr::filter::{Filter as SqlFilter, StopWords as SqlStopWords};
 use crate::expr::language::Language;
 use crate::idx::ft::Position;
 use crate::idx::ft::analyzer::mapper::Mapper;
+use crate::idx::ft::analyzer::stopwords::StopWords;
+use crate::idx::ft::analyzer::synonyms::Synonyms;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
 use crate::idx::trees::store::IndexStores;
+use crate::val::File;
 
 #[derive(Clone, Copy)]
 pub(in crate::idx::ft) enum FilteringStage {
@@ -22,6 +31,33 @@ pub(super) enum Filter {
 	Lowercase,
 	Uppercase,
 	Mapper(Mapper),
+	StopWords(WordList<StopWords>),
+	Synonyms(WordList<Synonyms>),
+}
+
+/// A list of words, built in or read from a bucket file. A file can only be
+/// read with the context of a query, so it is loaded on the first use of the
+/// filter (see [`Filter::load_word_lists`]).
+pub(super) struct WordList<T> {
+	/// The bucket file, `None` for a built-in list
+	file: Option<File>,
+	list: OnceLock<T>,
+}
+
+impl<T> WordList<T> {
+	fn get(&self) -> Result<&T> {
+		match self.list.get() {
+			Some(list) => Ok(list),
+			None => {
+				let list = match &self.file {
+					Some(file) => file.to_sql(),
+					// Built-in lists are loaded when the filter is created
+					None => "built-in".to_owned(),
+				};
+				bail!(Error::Internal(format!("The {list} word list is not loaded")))
+			}
+		}
+	}
 }
 
 impl Filter {
@@ -56,6 +92,18 @@ impl Filter {
 			}
 			SqlFilter::Uppercase => Filter::Uppercase,
 			SqlFilter::Mapper(path) => Filter::Mapper(ixs.mappers().get(path)?),
+			SqlFilter::StopWords(SqlStopWords::Language(l)) => Filter::StopWords(WordList {
+				file: None,
+				list: OnceLock::from(StopWords::language(*l)),
+			}),
+			SqlFilter::StopWords(SqlStopWords::File(file)) => Filter::StopWords(WordList {
+				file: Some(file.clone()),
+				list: OnceLock::new(),
+			}),
+			SqlFilter::Synonyms(file) => Filter::Synonyms(WordList {
+				file: Some(file.clone()),
+				list: OnceLock::new(),
+			}),
 		};
 		Ok(f)
 	}
@@ -79,10 +127,53 @@ impl Filter {
 		if let FilteringStage::Querying = stage {
 			!matches!(self, Filter::EdgeNgram(_, _) | Filter::Ngram(_, _))
 		} else {
-			true
+			// Synonyms are expanded in the queries, so they can change
+			// without reindexing
+			!matches!(self, Filter::Synonyms(_))
 		}
 	}
 
+	/// Reads the bucket files of the filters applied at a stage, unless
+	/// already loaded. Queries sharing the analyzer can load a list
+	/// concurrently, in which case the first list stored is kept.
+	pub(super) async fn load_word_lists(
+		ctx: &FrozenContext,
+		opt: &Options,
+		f: &Option<Vec<Filter>>,
+		stage: FilteringStage,
+	) -> Result<()> {
+		let Some(filters) = f else {
+			return Ok(());
+		};
+		for filter in filters {
+			if !filter.is_stage(stage) {
+				continue;
+			}
+			match filter {
+				Filter::StopWords(WordList {
+					file: Some(file),
+					list,
+				}) if list.get().is_none() => {
+					let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+					let ixs = ctx.get_index_stores();
+					let words = ixs.word_lists().get_stop_words(ctx, ns, db, file).await?;
+					list.get_or_init(|| words);
+				}
+				Filter::Synonyms(WordList {
+					file: Some(file),
+					list,
+				}) if list.get().is_none() => {
+					let (ns, db) = ctx.expect_ns_db_ids(opt).await?;
+					let ixs = ctx.get_index_stores();
+					let words = ixs.word_lists().get_synonyms(ctx, ns, db, file).await?;
+					list.get_or_init(|| words);
+				}
+				_ => {}
+			}
+		}
+		Ok(())
+	}
+
 	pub(super) fn apply_filters(
 		mut t: Tokens,
 		f: &Option<Vec<Filter>>,
@@ -98,8 +189,8 @@ impl Filter {
 		Ok(t)
 	}
 
-	pub(super) fn apply_filter(&self, c: &str) -> FilterResult {
-		match self {
+	pub(super) fn apply_filter(&self, c: &str) -> Result<FilterResult> {
+		let r = match self {
 			Filter::Ascii => Self::deunicode(c),
 			Filter::EdgeNgram(min, max) => Self::edgengram(c, *min, *max),
 			Filter::Lowercase => Self::lowercase(c),
@@ -107,7 +198,10 @@ impl Filter {
 			Filter::Stemmer(s) => Self::stem(s, c),
 			Filter::Uppercase => Self::uppercase(c),
 			Filter::Mapper(m) => m.map(c),
-		}
+			Filter::StopWords(l) => l.get()?.filter(c),
+			Filter::Synonyms(l) => l.get()?.expand(c),
+		};
+		Ok(r)
 	}
 
 	#[inline]
@@ -23,6 +23,8 @@ use crate::val::Value;
 
 pub(in crate::idx::ft) mod filter;
 pub(in crate::idx) mod mapper;
+pub(in crate::idx) mod stopwords;
+pub(in crate::idx) mod synonyms;
 pub(in crate::idx::ft) mod tokenizer;
 
 #[derive(Clone)]
@@ -123,6 +125,7 @@ impl Analyzer {
 		} else {
 			Tokenizer::tokenize(&[], input)
 		};
+		Filter::load_word_lists(ctx, opt, &self.filters, stage).await?;
 		Filter::apply_filters(tokens, &self.filters, stage)
 	}
 
@@ -165,10 +168,18 @@ impl Analyzer {
 		let mut dl = 0;
 		let mut tfos: HashMap<&str, Vec<Offset>> = HashMap::new();
 		for (i, tks) in inputs.iter().enumerate() {
+			// Tokens generated from the same original term share its position
+			let mut pos = 0;
+			let mut prev_start = None;
 			for tk in tks.list() {
 				dl += 1;
 				let s = tks.get_token_string(tk)?;
-				let o = tk.new_offset(i as u32);
+				let start = tk.get_start();
+				if prev_start.is_some_and(|prev| prev != start) {
+					pos += 1;
+				}
+				prev_start = Some(start);
+				let o = tk.new_offset(i as u32).with_pos(pos);
 				tfos.entry(s).or_default().push(o);
 			}
 		}
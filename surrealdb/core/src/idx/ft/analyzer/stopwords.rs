@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use ahash::HashSet;
+
+use crate::expr::language::Language;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+/// A list of words removed from the indexed content and from the queries.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct StopWords {
+	words: Arc<HashSet<String>>,
+}
+
+impl StopWords {
+	/// The built-in list of a language.
+	pub(in crate::idx) fn language(l: Language) -> Self {
+		let list = match l {
+			Language::Arabic => include_str!("stopwords/arabic.txt"),
+			Language::Danish => include_str!("stopwords/danish.txt"),
+			Language::Dutch => include_str!("stopwords/dutch.txt"),
+			Language::English => include_str!("stopwords/english.txt"),
+			Language::Finnish => include_str!("stopwords/finnish.txt"),
+			Language::French => include_str!("stopwords/french.txt"),
+			Language::German => include_str!("stopwords/german.txt"),
+			Language::Greek => include_str!("stopwords/greek.txt"),
+			Language::Hungarian => include_str!("stopwords/hungarian.txt"),
+			Language::Italian => include_str!("stopwords/italian.txt"),
+			Language::Norwegian => include_str!("stopwords/norwegian.txt"),
+			Language::Portuguese => include_str!("stopwords/portuguese.txt"),
+			Language::Romanian => include_str!("stopwords/romanian.txt"),
+			Language::Russian => include_str!("stopwords/russian.txt"),
+			Language::Spanish => include_str!("stopwords/spanish.txt"),
+			Language::Swedish => include_str!("stopwords/swedish.txt"),
+			Language::Tamil => include_str!("stopwords/tamil.txt"),
+			Language::Turkish => include_str!("stopwords/turkish.txt"),
+		};
+		Self::parse(list)
+	}
+
+	/// Parses a list with one word per line. Blank lines and lines starting
+	/// with `#` are ignored.
+	pub(in crate::idx) fn parse(content: &str) -> Self {
+		let words = content
+			.lines()
+			.map(str::trim)
+			.filter(|l| !l.is_empty() && !l.starts_with('#'))
+			.map(str::to_string)
+			.collect();
+		Self {
+			words: Arc::new(words),
+		}
+	}
+
+	pub(super) fn filter(&self, token: &str) -> FilterResult {
+		if self.words.contains(token) {
+			FilterResult::Ignore
+		} else {
+			FilterResult::Term(Term::Unchanged)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::StopWords;
+	use crate::expr::language::Language;
+	use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+	#[test]
+	fn test_parse() {
+		let sw = StopWords::parse("# Articles\nthe\n\n  a  \nan\n");
+		assert!(matches!(sw.filter("the"), FilterResult::Ignore));
+		assert!(matches!(sw.filter("a"), FilterResult::Ignore));
+		assert!(matches!(sw.filter("# Articles"), FilterResult::Term(Term::Unchanged)));
+		assert!(matches!(sw.filter("fox"), FilterResult::Term(Term::Unchanged)));
+	}
+
+	#[test]
+	fn test_languages() {
+		assert!(matches!(
+			StopWords::language(Language::English).filter("and"),
+			FilterResult::Ignore
+		));
+		assert!(matches!(
+			StopWords::language(Language::French).filter("les"),
+			FilterResult::Ignore
+		));
+		assert!(matches!(
+			StopWords::language(Language::German).filter("hund"),
+			FilterResult::Term(Term::Unchanged)
+		));
+	}
+}
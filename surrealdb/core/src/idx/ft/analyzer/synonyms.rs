@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use ahash::HashMap;
+use anyhow::{Result, ensure};
+
+use crate::err::Error;
+use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+/// The synonyms a query term is expanded to.
+#[derive(Clone, Default)]
+pub(in crate::idx) struct Synonyms {
+	terms: Arc<HashMap<String, Vec<String>>>,
+}
+
+impl Synonyms {
+	/// Parses a list of synonyms, one group per line:
+	/// - `car, automobile, auto`: each term is expanded to the other ones,
+	/// - `tv => television, telly`: the terms on the left are expanded to the
+	///   terms on the right.
+	///
+	/// Blank lines and lines starting with `#` are ignored.
+	pub(in crate::idx) fn parse(content: &str) -> Result<Self> {
+		let mut terms: HashMap<String, Vec<String>> = HashMap::default();
+		for (line_number, line) in content.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+			// Error messages must not echo the file content (see Mapper).
+			let (left, right) = match line.split_once("=>") {
+				Some((left, right)) => {
+					ensure!(
+						!right.contains("=>"),
+						Error::AnalyzerError(format!(
+							"Expected a single `=>` on line {line_number} of the synonyms"
+						))
+					);
+					(Self::split(left), Self::split(right))
+				}
+				None => {
+					let group = Self::split(line);
+					(group.clone(), group)
+				}
+			};
+			ensure!(
+				!left.is_empty() && !right.is_empty(),
+				Error::AnalyzerError(format!(
+					"Expected terms on line {line_number} of the synonyms"
+				))
+			);
+			for l in &left {
+				let synonyms = terms.entry(l.to_string()).or_default();
+				for r in &right {
+					if r != l && !synonyms.iter().any(|s| s == r) {
+						synonyms.push(r.to_string());
+					}
+				}
+			}
+		}
+		terms.retain(|_, s| !s.is_empty());
+		Ok(Self {
+			terms: Arc::new(terms),
+		})
+	}
+
+	fn split(terms: &str) -> Vec<&str> {
+		terms.split(',').map(str::trim).filter(|t| !t.is_empty()).collect()
+	}
+
+	/// Returns the term followed by its synonyms. They share the position of
+	/// the term, which is how the query tells them apart from other terms.
+	pub(super) fn expand(&self, token: &str) -> FilterResult {
+		match self.terms.get(token) {
+			Some(synonyms) => {
+				let mut terms = Vec::with_capacity(synonyms.len() + 1);
+				terms.push(Term::Unchanged);
+				terms.extend(synonyms.iter().map(|s| Term::NewTerm(s.clone(), 0)));
+				FilterResult::Terms(terms)
+			}
+			None => FilterResult::Term(Term::Unchanged),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Synonyms;
+	use crate::idx::ft::analyzer::filter::{FilterResult, Term};
+
+	fn expand(s: &Synonyms, token: &str) -> Vec<String> {
+		match s.expand(token) {
+			FilterResult::Terms(terms) => terms
+				.into_iter()
+				.map(|t| match t {
+					Term::Unchanged => token.to_string(),
+					Term::NewTerm(t, _) => t,
+				})
+				.collect(),
+			FilterResult::Term(Term::Unchanged) => vec![token.to_string()],
+			_ => panic!(),
+		}
+	}
+
+	#[test]
+	fn test_parse() {
+		let s = Synonyms::parse(
+			"# Vehicles\ncar, automobile , auto\n\ntv => television, telly\ncar => vehicle\n",
+		)
+		.unwrap();
+		assert_eq!(expand(&s, "car"), ["car", "automobile", "auto", "vehicle"]);
+		assert_eq!(expand(&s, "auto"), ["auto", "car", "automobile"]);
+		assert_eq!(expand(&s, "tv"), ["tv", "television", "telly"]);
+		assert_eq!(expand(&s, "television"), ["television"]);
+		assert_eq!(expand(&s, "bike"), ["bike"]);
+	}
+
+	/// A malformed line must not have its content echoed back in the error.
+	#[test]
+	fn test_parse_error_does_not_leak_file_content() {
+		const SECRET: &str = "super-secret-content";
+		let msg =
+			Synonyms::parse(&format!("car, auto\n{SECRET} => a => b")).err().unwrap().to_string();
+		assert!(!msg.contains(SECRET), "parser error leaked file content: {msg}");
+		assert!(
+			msg.contains("Expected a single `=>` on line 1 of the synonyms"),
+			"unexpected error message: {msg}"
+		);
+		let msg = Synonyms::parse(&format!("{SECRET} =>")).err().unwrap().to_string();
+		assert!(!msg.contains(SECRET), "parser error leaked file content: {msg}");
+		assert!(
+			msg.contains("Expected terms on line 0 of the synonyms"),
+			"unexpected error message: {msg}"
+		);
+	}
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use anyhow::{Result, bail};
 use surrealdb_strand::Strand;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::err::Error;
 use crate::expr::tokenizer::Tokenizer as SqlTokenizer;
@@ -36,7 +39,7 @@ impl Tokens {
 				continue;
 			}
 			let c = tk.get_str(&self.i)?;
-			match f.apply_filter(c) {
+			match f.apply_filter(c)? {
 				FilterResult::Term(t) => match t {
 					Term::Unchanged => tks.push(tk),
 					Term::NewTerm(t, s) => tks.push(tk.new_token(t, s)),
@@ -76,6 +79,28 @@ impl Tokens {
 		}
 		Ok(false)
 	}
+
+	/// Removes the tokens sharing the position of the previous token, which
+	/// the synonyms filter adds to a query term, and returns them by term.
+	pub(in crate::idx::ft) fn take_alternatives(&mut self) -> Result<HashMap<String, Vec<String>>> {
+		let mut alternatives: HashMap<String, Vec<String>> = HashMap::new();
+		let mut kept: Vec<Token> = Vec::with_capacity(self.t.len());
+		for t in std::mem::take(&mut self.t) {
+			match kept.last() {
+				Some(last) if last.get_start() == t.get_start() => {
+					let term = last.get_str(&self.i)?;
+					let alternative = t.get_str(&self.i)?;
+					let terms = alternatives.entry(term.to_owned()).or_default();
+					if alternative != term && !terms.iter().any(|a| a == alternative) {
+						terms.push(alternative.to_owned());
+					}
+				}
+				_ => kept.push(t),
+			}
+		}
+		self.t = kept;
+		Ok(alternatives)
+	}
 }
 
 impl TryFrom<Tokens> for Value {
@@ -158,6 +183,20 @@ impl Token {
 		}
 	}
 
+	/// The start position of the original term the token was generated from.
+	pub(in crate::idx::ft) fn get_start(&self) -> Position {
+		match self {
+			Token::Ref {
+				chars,
+				..
+			} => chars.0,
+			Token::String {
+				chars,
+				..
+			} => chars.0,
+		}
+	}
+
 	pub(in crate::idx::ft) fn get_char_len(&self) -> u32 {
 		match self {
 			Token::Ref {
@@ -197,12 +236,22 @@ impl Token {
 
 pub(super) struct Tokenizer {
 	splitters: Vec<Splitter>,
+	/// Split the tokens into Unicode words (UAX #29)
+	unicode: bool,
+	/// Split the CJK runs of the tokens into bigrams
+	cjk: bool,
 }
 
 impl Tokenizer {
 	pub(in crate::idx::ft) fn new(t: &[SqlTokenizer]) -> Self {
 		Self {
-			splitters: t.iter().map(|t| t.into()).collect(),
+			splitters: t
+				.iter()
+				.filter(|t| !matches!(t, SqlTokenizer::Unicode | SqlTokenizer::Cjk))
+				.map(|t| t.into())
+				.collect(),
+			unicode: t.contains(&SqlTokenizer::Unicode),
+			cjk: t.contains(&SqlTokenizer::Cjk),
 		}
 	}
 
@@ -234,6 +283,34 @@ impl Tokenizer {
 
 	pub(super) fn tokenize(t: &[SqlTokenizer], i: Strand) -> Tokens {
 		let mut w = Tokenizer::new(t);
+		if !w.unicode && !w.cjk {
+			return w.split(i);
+		}
+		// The segmenters apply to the tokens of the other tokenizers, if any,
+		// or to the whole input
+		let tokens = if w.splitters.is_empty() {
+			let len = i.chars().count() as Position;
+			let mut t = Vec::new();
+			if len > 0 {
+				t.push(Token::Ref {
+					chars: (0, 0, len),
+					bytes: (0, i.len() as Position),
+					len,
+				});
+			}
+			Tokens {
+				i,
+				t,
+			}
+		} else {
+			w.split(i)
+		};
+		w.segment(tokens)
+	}
+
+	/// Splits the input into tokens according to the character roles
+	/// assigned by the splitters.
+	fn split(&mut self, i: Strand) -> Tokens {
 		let mut last_char_pos = 0;
 		let mut last_byte_pos = 0;
 		let mut current_char_pos = 0;
@@ -242,7 +319,7 @@ impl Tokenizer {
 		let mut t = Vec::new();
 		for c in i.chars() {
 			let char_len = c.len_utf8() as Position;
-			let cr = w.character_role(c);
+			let cr = self.character_role(c);
 			// if the new character is not part of the current token,
 			if !matches!(cr, CharacterRole::PartOfCurrentToken)
 				|| matches!(previous_character_role, CharacterRole::IsolatedToken)
@@ -281,6 +358,123 @@ impl Tokenizer {
 			t,
 		}
 	}
+
+	/// Splits each token into CJK bigrams and, with the `UNICODE` tokenizer,
+	/// the rest into Unicode words, dropping the segments without any letter
+	/// or digit.
+	fn segment(&self, tokens: Tokens) -> Tokens {
+		let mut t = Vec::with_capacity(tokens.t.len());
+		for tk in &tokens.t {
+			let Token::Ref {
+				chars,
+				bytes,
+				..
+			} = tk
+			else {
+				t.push(tk.clone());
+				continue;
+			};
+			let s = &tokens.i[bytes.0 as usize..bytes.1 as usize];
+			for (cjk, char_pos, byte_pos, run) in Self::runs(s, self.cjk) {
+				let char_pos = chars.0 + char_pos;
+				let byte_pos = bytes.0 + byte_pos;
+				if cjk {
+					Self::push_bigrams(&mut t, char_pos, byte_pos, run);
+				} else if self.unicode {
+					let mut pos = char_pos;
+					for (b, word) in run.split_word_bound_indices() {
+						let len = word.chars().count() as Position;
+						if word.chars().any(char::is_alphanumeric) {
+							let b = byte_pos + b as Position;
+							t.push(Token::Ref {
+								chars: (pos, pos, pos + len),
+								bytes: (b, b + word.len() as Position),
+								len,
+							});
+						}
+						pos += len;
+					}
+				} else {
+					let len = run.chars().count() as Position;
+					t.push(Token::Ref {
+						chars: (char_pos, char_pos, char_pos + len),
+						bytes: (byte_pos, byte_pos + run.len() as Position),
+						len,
+					});
+				}
+			}
+		}
+		Tokens {
+			i: tokens.i,
+			t,
+		}
+	}
+
+	/// Splits a string into runs of CJK and other characters, with their
+	/// character and byte positions. Without `cjk`, the string is a single
+	/// run.
+	fn runs(s: &str, cjk: bool) -> Vec<(bool, Position, Position, &str)> {
+		if !cjk {
+			return vec![(false, 0, 0, s)];
+		}
+		let mut runs = Vec::new();
+		let mut start = (0, 0);
+		let mut current = None;
+		for (char_pos, (byte_pos, c)) in s.char_indices().enumerate() {
+			let is_cjk = is_cjk(c);
+			if current.is_some_and(|r| r != is_cjk) {
+				runs.push((!is_cjk, start.0, start.1 as Position, &s[start.1..byte_pos]));
+				start = (char_pos as Position, byte_pos);
+			}
+			current = Some(is_cjk);
+		}
+		if let Some(is_cjk) = current {
+			runs.push((is_cjk, start.0, start.1 as Position, &s[start.1..]));
+		}
+		runs
+	}
+
+	/// Pushes the overlapping bigrams of a run of CJK characters, or the
+	/// character itself for a run of one.
+	fn push_bigrams(t: &mut Vec<Token>, char_pos: Position, byte_pos: Position, run: &str) {
+		let chars: Vec<(usize, char)> = run.char_indices().collect();
+		if chars.len() == 1 {
+			t.push(Token::Ref {
+				chars: (char_pos, char_pos, char_pos + 1),
+				bytes: (byte_pos, byte_pos + run.len() as Position),
+				len: 1,
+			});
+			return;
+		}
+		for (i, w) in chars.windows(2).enumerate() {
+			let pos = char_pos + i as Position;
+			let b = byte_pos + w[0].0 as Position;
+			let end = w[1].0 + w[1].1.len_utf8();
+			t.push(Token::Ref {
+				chars: (pos, pos, pos + 2),
+				bytes: (b, byte_pos + end as Position),
+				len: 2,
+			});
+		}
+	}
+}
+
+/// Whether a character belongs to a script written without spaces between
+/// words: Han ideographs, Hiragana, Katakana, Hangul and Thai.
+fn is_cjk(c: char) -> bool {
+	matches!(c,
+		'\u{0E00}'..='\u{0E7F}' // Thai
+		| '\u{1100}'..='\u{11FF}' // Hangul Jamo
+		| '\u{3040}'..='\u{30FF}' // Hiragana, Katakana
+		| '\u{3130}'..='\u{318F}' // Hangul Compatibility Jamo
+		| '\u{31F0}'..='\u{31FF}' // Katakana Phonetic Extensions
+		| '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+		| '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+		| '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+		| '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+		| '\u{FF66}'..='\u{FF9F}' // Halfwidth Katakana
+		| '\u{20000}'..='\u{2FA1F}' // CJK Unified Ideographs Extensions B to F
+	)
 }
 
 struct Splitter {
@@ -352,6 +546,8 @@ impl Splitter {
 			SqlTokenizer::Camel => self.camel_role(cl),
 			SqlTokenizer::Class => self.class_role(cl),
 			SqlTokenizer::Punct => self.punct_role(cl),
+			// The segmenters run on the split tokens (see `Tokenizer::segment`)
+			SqlTokenizer::Unicode | SqlTokenizer::Cjk => CharacterRole::PartOfCurrentToken,
 		}
 	}
 
@@ -416,7 +612,8 @@ impl Splitter {
 
 #[cfg(test)]
 mod tests {
-	use crate::idx::ft::analyzer::tests::test_analyzer;
+	use crate::idx::ft::analyzer::tests::{test_analyzer, test_analyzer_tokens};
+	use crate::idx::ft::analyzer::tokenizer::Token;
 
 	#[tokio::test]
 	async fn test_tokenize_blank_class() {
@@ -488,4 +685,61 @@ static LANGUAGE: &str = "Rust";"#,
 		)
 		.await;
 	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode FILTERS lowercase",
+			"Hello, World! Don't stop: 3.14 — naïve café.",
+			&["hello", "world", "don't", "stop", "3.14", "naïve", "café"],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_cjk() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS cjk",
+			"我爱北京天安门",
+			&["我爱", "爱北", "北京", "京天", "天安", "安门"],
+		)
+		.await;
+		test_analyzer(
+			"ANALYZER test TOKENIZERS blank,cjk FILTERS lowercase",
+			"iPhone手机壳 東京タワー 한국어 ไทย 猫",
+			&[
+				"iphone", "手机", "机壳", "東京", "京タ", "タワ", "ワー", "한국", "국어", "ไท",
+				"ทย", "猫",
+			],
+		)
+		.await;
+	}
+
+	#[tokio::test]
+	async fn test_tokenize_unicode_cjk() {
+		test_analyzer(
+			"ANALYZER test TOKENIZERS unicode,cjk FILTERS lowercase",
+			"Rust是一种编程语言, fast!",
+			&["rust", "是一", "一种", "种编", "编程", "程语", "语言", "fast"],
+		)
+		.await;
+		// Bigrams keep the positions of their characters
+		test_analyzer_tokens(
+			"ANALYZER test TOKENIZERS unicode,cjk",
+			"Go 语言",
+			&[
+				Token::Ref {
+					chars: (0, 0, 2),
+					bytes: (0, 2),
+					len: 2,
+				},
+				Token::Ref {
+					chars: (3, 3, 5),
+					bytes: (3, 9),
+					len: 2,
+				},
+			],
+		)
+		.await;
+	}
 }
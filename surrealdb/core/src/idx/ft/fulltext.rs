@@ -1,9 +1,9 @@
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::Result;
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use revision::revisioned;
 use roaring::RoaringTreemap;
@@ -25,18 +25,23 @@ use crate::catalog::{FullTextParams, Scoring};
 /// - Compaction of index data
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
+use crate::err::Error;
 use crate::expr::Idiom;
 use crate::expr::operator::BooleanOperator;
 use crate::idx::ft::analyzer::Analyzer;
 use crate::idx::ft::analyzer::filter::FilteringStage;
 use crate::idx::ft::analyzer::tokenizer::Tokens;
+use crate::idx::ft::fuzzy::{self, Levenshtein, term_distance};
 use crate::idx::ft::highlighter::{HighlightParams, Highlighter, Offseter};
 use crate::idx::ft::offset::Offset;
-use crate::idx::ft::{DocLength, Score, TermFrequency};
+use crate::idx::ft::query::{Clause, QuerySyntax, near_matches, phrase_matches};
+use crate::idx::ft::{DocLength, Position, Score, TermFrequency};
 use crate::idx::planner::iterators::MatchesHitsIterator;
 use crate::idx::seqdocids::{DocId, SeqDocIds};
 use crate::idx::trees::store::IndexStores;
 use crate::idx::{IndexKeyBase, bump_compaction_generation, read_compaction_generation};
+use crate::key::index::td::{Td, TdRoot};
+use crate::key::index::tf::Tf;
 use crate::key::index::tt::Tt;
 use crate::kvs::{COUNT_BATCH_SIZE, Key, Transaction, impl_kv_value_revisioned};
 use crate::val::{RecordId, Value};
@@ -94,14 +99,32 @@ pub(crate) struct QueryTerms {
 	/// Indicates if any terms in the query are not found in the index
 	#[allow(dead_code)]
 	has_unknown_terms: bool,
+	/// The clauses of a query using the phrase, proximity, prefix or
+	/// exclusion syntax, `None` for a query made only of free text
+	syntax: Option<SyntaxTerms>,
+	/// The other indexed terms matched by the query, with their length in
+	/// characters and the documents containing them, for scoring and
+	/// highlighting: the terms of the phrase, proximity and prefix clauses,
+	/// and the fuzzy matches of the tokens
+	terms: Vec<(String, u32, Option<RoaringTreemap>)>,
+	/// The maximum edit distance of the tokens, for a fuzzy index
+	fuzzy: Option<u8>,
+	/// The synonyms of the tokens, expanded by the analyzer
+	synonyms: HashMap<String, Vec<String>>,
 }
 
 impl QueryTerms {
 	pub(crate) fn is_empty(&self) -> bool {
-		self.tokens.list().is_empty()
+		match &self.syntax {
+			Some(syntax) => syntax.include.is_empty(),
+			None => self.tokens.list().is_empty(),
+		}
 	}
 
 	pub(crate) fn contains_doc(&self, doc_id: DocId) -> bool {
+		if let Some(syntax) = &self.syntax {
+			return syntax.contains_doc(doc_id);
+		}
 		for d in self.docs.iter().flatten() {
 			if d.contains(doc_id) {
 				return true;
@@ -114,7 +137,7 @@ impl QueryTerms {
 		for t in self.tokens.list() {
 			let t = self.tokens.get_token_string(t)?;
 			for tokens in tks {
-				if tokens.try_contains(t)? {
+				if self.contains_term(tokens, t)? {
 					return Ok(true);
 				}
 			}
@@ -127,7 +150,7 @@ impl QueryTerms {
 			let t = self.tokens.get_token_string(t)?;
 			let mut found = false;
 			for tokens in tks {
-				if tokens.try_contains(t)? {
+				if self.contains_term(tokens, t)? {
 					found = true;
 					break;
 				}
@@ -138,8 +161,160 @@ impl QueryTerms {
 		}
 		Ok(true)
 	}
+
+	/// Whether the tokens of a value contain a query term or one of its
+	/// synonyms, or a term within their edit distance for a fuzzy index.
+	fn contains_term(&self, tokens: &Tokens, term: &str) -> Result<bool> {
+		// A term without synonyms only matches itself
+		let synonyms = self.synonyms.get(term).into_iter().flatten().map(String::as_str);
+		for term in std::iter::once(term).chain(synonyms) {
+			let found = match self.fuzzy {
+				None => tokens.try_contains(term)?,
+				Some(max) => {
+					let lev = Levenshtein::new(term, term_distance(term, max));
+					let mut found = false;
+					for t in tokens.list() {
+						if lev.matches(tokens.get_token_string(t)?) {
+							found = true;
+							break;
+						}
+					}
+					found
+				}
+			};
+			if found {
+				return Ok(true);
+			}
+		}
+		Ok(false)
+	}
+}
+
+/// A clause of a query string, with its analysed terms
+enum AnalyzedClause {
+	/// Terms matched individually, combined with the operator's AND / OR
+	Terms(Vec<String>),
+	/// Terms matched at consecutive positions
+	Phrase(Vec<String>),
+	/// Two terms matched at most `distance` positions apart
+	Near {
+		left: String,
+		right: String,
+		distance: u32,
+	},
+	/// The indexed terms starting with the prefix
+	Prefix {
+		prefix: String,
+		terms: Vec<String>,
+	},
+}
+
+impl AnalyzedClause {
+	fn terms(&self) -> Vec<&str> {
+		match self {
+			Self::Terms(terms)
+			| Self::Phrase(terms)
+			| Self::Prefix {
+				terms,
+				..
+			} => terms.iter().map(String::as_str).collect(),
+			Self::Near {
+				left,
+				right,
+				..
+			} => vec![left.as_str(), right.as_str()],
+		}
+	}
+
+	/// Pushes whether each operand of the clause matches a value, given the
+	/// sorted `(value index, position)` occurrences of the value's terms
+	fn match_value(&self, occurrences: &HashMap<&str, Vec<(u32, Position)>>, res: &mut Vec<bool>) {
+		let get = |term: &str| match occurrences.get(term) {
+			Some(occ) => occ.as_slice(),
+			None => &[],
+		};
+		match self {
+			Self::Terms(terms) => {
+				res.extend(terms.iter().map(|t| occurrences.contains_key(t.as_str())))
+			}
+			Self::Phrase(terms) => {
+				let occ: Vec<_> = terms.iter().map(|t| get(t).to_vec()).collect();
+				res.push(phrase_matches(&occ));
+			}
+			Self::Near {
+				left,
+				right,
+				distance,
+			} => res.push(near_matches(get(left), get(right), *distance)),
+			Self::Prefix {
+				prefix,
+				..
+			} => res.push(occurrences.keys().any(|t| t.starts_with(prefix.as_str()))),
+		}
+	}
+}
+
+/// The evaluation of a query using the syntax of [`QuerySyntax`]
+struct SyntaxTerms {
+	/// The clauses combined with the operator's AND / OR
+	include: Vec<AnalyzedClause>,
+	/// The clauses excluding documents
+	exclude: Vec<AnalyzedClause>,
+	/// The documents matching each operand of the included clauses
+	operands: Vec<Option<RoaringTreemap>>,
+	/// The documents matching any of the excluded clauses
+	excluded: RoaringTreemap,
+}
+
+impl SyntaxTerms {
+	/// Like a plain query, a document matches when it matches any operand:
+	/// the AND operator is only applied by the hits iterator.
+	fn contains_doc(&self, doc_id: DocId) -> bool {
+		!self.excluded.contains(doc_id)
+			&& self.operands.iter().flatten().any(|d| d.contains(doc_id))
+	}
+
+	fn hits(&self, bo: BooleanOperator) -> Option<RoaringTreemap> {
+		let mut hits = match bo {
+			BooleanOperator::And => FullTextIndex::intersection_operation(&self.operands),
+			BooleanOperator::Or => FullTextIndex::union_operation(&self.operands),
+		}?;
+		hits -= &self.excluded;
+		Some(hits)
+	}
+
+	fn matches_value(&self, bo: BooleanOperator, tks: &[Tokens]) -> Result<bool> {
+		let (_, offsets) = Analyzer::extract_offsets(tks)?;
+		let occurrences: HashMap<&str, Vec<(u32, Position)>> = offsets
+			.into_iter()
+			.map(|(t, o)| (t, o.into_iter().map(|o| (o.index, o.pos)).collect()))
+			.collect();
+		let mut res = Vec::new();
+		for c in &self.exclude {
+			c.match_value(&occurrences, &mut res);
+			let excluded = !res.is_empty() && res.iter().all(|m| *m);
+			res.clear();
+			if excluded {
+				return Ok(false);
+			}
+		}
+		for c in &self.include {
+			c.match_value(&occurrences, &mut res);
+		}
+		Ok(match bo {
+			BooleanOperator::And => !res.is_empty() && res.iter().all(|m| *m),
+			BooleanOperator::Or => res.iter().any(|m| *m),
+		})
+	}
 }
 
+/// The maximum number of indexed terms a `prefix*` clause expands to
+const MAX_PREFIX_TERMS: usize = 1024;
+
+/// The maximum number of uncompacted terms a fuzzy query matches its tokens
+/// against, on top of the term dictionary
+const MAX_PENDING_TERMS: usize = 1024;
+
 #[derive(Clone, Copy)]
 pub(crate) struct Bm25Params {
 	pub(in crate::idx) k1: f32,
@@ -159,6 +334,8 @@ pub(crate) struct FullTextIndex {
 	doc_ids: SeqDocIds,
 	/// BM25 scoring parameters, if scoring is enabled
 	bm25: Option<Bm25Params>,
+	/// The maximum edit distance of fuzzy term matching, if enabled
+	fuzzy: Option<u8>,
 }
 
 /// Snapshot gathered by the read phase of full-text compaction.
@@ -267,6 +444,7 @@ impl FullTextIndex {
 			doc_ids: SeqDocIds::new(ikb.clone()),
 			ikb,
 			bm25,
+			fuzzy: p.fuzzy,
 		})
 	}
 
@@ -437,8 +615,8 @@ impl FullTextIndex {
 	/// Extracts query terms from a search string
 	///
 	/// Tokenizes the query string, then retrieves the document bitmaps for each
-	/// unique term. The compacted bitmap fetches are batched via `tx.getm()` to
-	/// reduce KV round trips (one batch instead of N sequential gets).
+	/// unique term. A query using the phrase, proximity, prefix or exclusion
+	/// syntax (see [`QuerySyntax`]) is evaluated against the index here.
 	pub(crate) async fn extract_querying_terms(
 		&self,
 		stk: &mut Stk,
@@ -446,11 +624,159 @@ impl FullTextIndex {
 		opt: &Options,
 		query_string: String,
 	) -> Result<QueryTerms> {
-		let tokens = self
+		let syntax = QuerySyntax::parse(&query_string);
+		if !syntax.is_plain() {
+			return self.extract_syntax_terms(stk, ctx, opt, query_string, syntax).await;
+		}
+		let mut tokens = self
 			.analyzer
 			.generate_tokens(stk, ctx, opt, FilteringStage::Querying, query_string.into())
 			.await?;
+		let synonyms = tokens.take_alternatives()?;
+		if self.fuzzy.is_some() || !synonyms.is_empty() {
+			return self.extract_expanded_terms(&ctx.tx(), tokens, synonyms).await;
+		}
+		let (docs, has_unknown_terms) = self.get_tokens_docs(&ctx.tx(), &tokens).await?;
+		Ok(QueryTerms {
+			tokens,
+			docs,
+			has_unknown_terms,
+			syntax: None,
+			terms: Vec::new(),
+			fuzzy: None,
+			synonyms,
+		})
+	}
 
+	/// Matches each token of a plain query to its synonyms and, for a fuzzy
+	/// index, to the indexed terms within their edit distance (see
+	/// [`term_distance`]): the documents of a token are those of its matching
+	/// terms.
+	async fn extract_expanded_terms(
+		&self,
+		tx: &Transaction,
+		tokens: Tokens,
+		synonyms: HashMap<String, Vec<String>>,
+	) -> Result<QueryTerms> {
+		let (mut shards, pending) = match self.fuzzy {
+			Some(_) => (self.get_dictionary_shards(tx).await?, self.get_pending_terms(tx).await?),
+			None => (BTreeMap::new(), Vec::new()),
+		};
+		let mut unique_tokens = HashSet::new();
+		let mut matches = Vec::new();
+		for token in tokens.list() {
+			if !unique_tokens.insert(token) {
+				continue;
+			}
+			let term = tokens.get_token_string(token)?;
+			let mut terms = Vec::new();
+			let expanded = synonyms.get(term).into_iter().flatten().map(String::as_str);
+			for t in std::iter::once(term).chain(expanded) {
+				if let Some(max) = self.fuzzy {
+					let distance = term_distance(t, max);
+					let lev = Levenshtein::new(t, distance);
+					terms.extend(self.search_dictionary(tx, &mut shards, &lev, t, distance).await?);
+					terms.extend(pending.iter().filter(|p| lev.matches(p)).cloned());
+				}
+				// The term itself, which may only be known to the `!td` keys
+				terms.push(t.to_owned());
+			}
+			terms.sort_unstable();
+			terms.dedup();
+			matches.push((term, terms));
+		}
+		let mut docs = Vec::with_capacity(matches.len());
+		let mut extra_terms: Vec<(String, u32, Option<RoaringTreemap>)> = Vec::new();
+		for (term, terms) in matches {
+			let terms_ref: Vec<&str> = terms.iter().map(String::as_str).collect();
+			let terms_docs = self.get_terms_docs(tx, &terms_ref).await?;
+			docs.push(Self::union_operation(&terms_docs));
+			for (t, d) in terms.into_iter().zip(terms_docs) {
+				if t != term && d.is_some() && !extra_terms.iter().any(|(u, ..)| *u == t) {
+					let len = t.chars().count() as u32;
+					extra_terms.push((t, len, d));
+				}
+			}
+		}
+		let has_unknown_terms = docs.iter().any(Option::is_none);
+		Ok(QueryTerms {
+			tokens,
+			docs,
+			has_unknown_terms,
+			syntax: None,
+			terms: extra_terms,
+			fuzzy: self.fuzzy,
+			synonyms,
+		})
+	}
+
+	/// Lists the shards of the term dictionary, without fetching them yet.
+	async fn get_dictionary_shards(
+		&self,
+		tx: &Transaction,
+	) -> Result<BTreeMap<String, Option<Vec<u8>>>> {
+		let (beg, end) = self.ikb.new_tf_range()?;
+		let mut shards = BTreeMap::new();
+		for key in tx.keys(beg..end, u32::MAX, 0, None).await? {
+			shards.insert(Tf::decode_key(&key)?.shard.into_owned(), None);
+		}
+		Ok(shards)
+	}
+
+	/// Lists the terms of the term dictionary within the distance of a term,
+	/// fetching the shards the automaton can enter on first use.
+	async fn search_dictionary(
+		&self,
+		tx: &Transaction,
+		shards: &mut BTreeMap<String, Option<Vec<u8>>>,
+		lev: &Levenshtein,
+		term: &str,
+		distance: u32,
+	) -> Result<Vec<String>> {
+		let missing: Vec<String> = shards
+			.iter()
+			.filter(|(shard, dictionary)| dictionary.is_none() && lev.can_enter(shard))
+			.map(|(shard, _)| shard.clone())
+			.collect();
+		let keys: Vec<_> = missing.iter().map(|shard| self.ikb.new_tf_key(shard)).collect();
+		for (shard, dictionary) in missing.iter().zip(tx.getm(keys, None).await?) {
+			shards.insert(shard.clone(), dictionary);
+		}
+		let mut terms = Vec::new();
+		for (shard, dictionary) in shards.iter() {
+			if let Some(dictionary) = dictionary
+				&& lev.can_enter(shard)
+			{
+				terms.extend(fuzzy::search(dictionary, term, distance)?);
+			}
+		}
+		Ok(terms)
+	}
+
+	/// Lists the terms with `!tt` deltas that are not compacted yet, so not in
+	/// the term dictionary, skipping from one term to the next. At most
+	/// [`MAX_PENDING_TERMS`] are listed: the others are matched once compacted.
+	async fn get_pending_terms(&self, tx: &Transaction) -> Result<Vec<String>> {
+		let (mut beg, end) = self.ikb.new_tt_terms_range()?;
+		let mut terms = Vec::new();
+		while terms.len() < MAX_PENDING_TERMS {
+			let Some(key) = tx.keys(beg..end.clone(), 1, 0, None).await?.pop() else {
+				break;
+			};
+			let term = Tt::decode_key(&key)?.term.into_owned();
+			beg = self.ikb.new_tt_term_range(&term)?.1;
+			terms.push(term);
+		}
+		Ok(terms)
+	}
+
+	/// Retrieves the document bitmaps of each unique token, and whether any
+	/// token is unknown to the index.
+	async fn get_tokens_docs(
+		&self,
+		tx: &Transaction,
+		tokens: &Tokens,
+	) -> Result<(Vec<Option<RoaringTreemap>>, bool)> {
 		let mut unique_terms: Vec<&str> = Vec::new();
 		let mut unique_tokens = HashSet::new();
 		for token in tokens.list() {
@@ -458,12 +784,22 @@ impl FullTextIndex {
 				unique_terms.push(tokens.get_token_string(token)?);
 			}
 		}
+		let docs = self.get_terms_docs(tx, &unique_terms).await?;
+		let has_unknown_terms = docs.iter().any(Option::is_none);
+		Ok((docs, has_unknown_terms))
+	}
 
-		let tx = ctx.tx();
-
+	/// Retrieves the document bitmap of each term, `None` for a term found in
+	/// no document. The compacted bitmap fetches are batched via `tx.getm()`
+	/// to reduce KV round trips (one batch instead of N sequential gets).
+	async fn get_terms_docs(
+		&self,
+		tx: &Transaction,
+		terms: &[&str],
+	) -> Result<Vec<Option<RoaringTreemap>>> {
 		// Phase 1: Collect deltas for each term (sequential range scans)
-		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(unique_terms.len());
-		for term in &unique_terms {
+		let mut all_deltas: Vec<HashMap<DocId, i64>> = Vec::with_capacity(terms.len());
+		for term in terms {
 			let (beg, end) = self.ikb.new_tt_term_range(term)?;
 			let mut deltas: HashMap<DocId, i64> = HashMap::new();
 			for k in tx.keys(beg..end, u32::MAX, 0, None).await? {
@@ -479,13 +815,11 @@ impl FullTextIndex {
 		}
 
 		// Phase 2: Batch-fetch compacted bitmaps for all terms at once
-		let bitmap_keys: Vec<_> =
-			unique_terms.iter().map(|term| self.ikb.new_td_root(term)).collect();
+		let bitmap_keys: Vec<_> = terms.iter().map(|term| self.ikb.new_td_root(term)).collect();
 		let bitmaps: Vec<Option<RoaringTreemap>> = tx.getm(bitmap_keys, None).await?;
 
 		// Phase 3: Merge deltas into bitmaps
-		let mut docs = Vec::with_capacity(unique_terms.len());
-		let mut has_unknown_terms = false;
+		let mut docs = Vec::with_capacity(terms.len());
 		for (bitmap, deltas) in bitmaps.into_iter().zip(all_deltas.iter()) {
 			let mut doc_set = bitmap.unwrap_or_default();
 			for (doc_id, delta) in deltas {
@@ -500,22 +834,275 @@ impl FullTextIndex {
 				}
 			}
 			if doc_set.is_empty() {
-				if !has_unknown_terms {
-					has_unknown_terms = true;
-				}
 				docs.push(None);
 			} else {
 				docs.push(Some(doc_set));
 			}
 		}
+		Ok(docs)
+	}
 
+	/// Evaluates a query using the phrase, proximity, prefix or exclusion
+	/// syntax. Each clause is resolved to the documents matching it, checking
+	/// the positions of the candidate documents for phrases and proximity.
+	async fn extract_syntax_terms(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		query_string: String,
+		syntax: QuerySyntax,
+	) -> Result<QueryTerms> {
+		if syntax.has_positional_clauses() && !self.highlighting {
+			bail!(Error::FtPositionsRequired {
+				query: query_string,
+			});
+		}
+		let tx = ctx.tx();
+		// The free text is analysed as a whole, as for a plain query
+		let mut include = syntax.include.into_iter().peekable();
+		let mut tokens = match include.next_if(|c| matches!(c, Clause::Text(_))) {
+			Some(Clause::Text(text)) => {
+				self.analyzer
+					.generate_tokens(stk, ctx, opt, FilteringStage::Querying, text.into())
+					.await?
+			}
+			_ => Tokens::new(String::new().into()),
+		};
+		// The clauses are matched without synonyms
+		tokens.take_alternatives()?;
+		let (docs, has_unknown_terms) = self.get_tokens_docs(&tx, &tokens).await?;
+		let mut text_terms = Vec::new();
+		for t in tokens.list() {
+			let t = tokens.get_token_string(t)?;
+			if !text_terms.iter().any(|u| u == t) {
+				text_terms.push(t.to_owned());
+			}
+		}
+		let mut analyzed_include = Vec::new();
+		if !text_terms.is_empty() {
+			analyzed_include.push(AnalyzedClause::Terms(text_terms.clone()));
+		}
+		for c in include {
+			analyzed_include.push(self.analyze_clause(stk, ctx, opt, &tx, c).await?);
+		}
+		let mut analyzed_exclude = Vec::new();
+		for c in syntax.exclude {
+			analyzed_exclude.push(self.analyze_clause(stk, ctx, opt, &tx, c).await?);
+		}
+		// Fetch the documents of every term of the clauses
+		let mut all_terms: Vec<&str> = Vec::new();
+		for t in analyzed_include.iter().chain(&analyzed_exclude).flat_map(AnalyzedClause::terms) {
+			if !all_terms.contains(&t) {
+				all_terms.push(t);
+			}
+		}
+		let term_docs: HashMap<&str, Option<RoaringTreemap>> =
+			all_terms.iter().copied().zip(self.get_terms_docs(&tx, &all_terms).await?).collect();
+		// Resolve the documents matching each clause
+		let mut operands = Vec::new();
+		for c in &analyzed_include {
+			self.clause_docs(&tx, c, &term_docs, &mut operands).await?;
+		}
+		let mut excluded = RoaringTreemap::new();
+		for c in &analyzed_exclude {
+			let mut clause_operands = Vec::new();
+			self.clause_docs(&tx, c, &term_docs, &mut clause_operands).await?;
+			if let Some(d) = Self::intersection_operation(&clause_operands) {
+				excluded |= d;
+			}
+		}
+		// The remaining terms, for scoring and highlighting
+		let mut terms: Vec<(String, u32, Option<RoaringTreemap>)> = Vec::new();
+		for t in analyzed_include.iter().flat_map(AnalyzedClause::terms) {
+			if !text_terms.iter().any(|u| u == t) && !terms.iter().any(|(u, ..)| u == t) {
+				let docs = term_docs.get(t).cloned().flatten();
+				terms.push((t.to_owned(), t.chars().count() as u32, docs));
+			}
+		}
 		Ok(QueryTerms {
 			tokens,
 			docs,
 			has_unknown_terms,
+			syntax: Some(SyntaxTerms {
+				include: analyzed_include,
+				exclude: analyzed_exclude,
+				operands,
+				excluded,
+			}),
+			terms,
+			fuzzy: None,
+			synonyms: HashMap::new(),
 		})
 	}
 
+	/// Analyses the text of a clause with the querying filters. The clauses
+	/// keep a single term per position, without synonyms, and the single
+	/// word clauses their first term.
+	async fn analyze_clause(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		tx: &Transaction,
+		clause: Clause,
+	) -> Result<AnalyzedClause> {
+		Ok(match clause {
+			Clause::Text(text) => {
+				let mut tokens = self.analyze_terms(stk, ctx, opt, text).await?;
+				tokens.dedup_by_key(|(start, _)| *start);
+				AnalyzedClause::Terms(tokens.into_iter().map(|(_, t)| t).collect())
+			}
+			Clause::Phrase(phrase) => {
+				let mut tokens = self.analyze_terms(stk, ctx, opt, phrase).await?;
+				tokens.dedup_by_key(|(start, _)| *start);
+				AnalyzedClause::Phrase(tokens.into_iter().map(|(_, t)| t).collect())
+			}
+			Clause::Near {
+				left,
+				right,
+				distance,
+			} => AnalyzedClause::Near {
+				left: self.analyze_term(stk, ctx, opt, left).await?,
+				right: self.analyze_term(stk, ctx, opt, right).await?,
+				distance,
+			},
+			Clause::Prefix(prefix) => {
+				let prefix = self.analyze_term(stk, ctx, opt, prefix).await?;
+				let terms = self.expand_prefix(tx, &prefix).await?;
+				AnalyzedClause::Prefix {
+					prefix,
+					terms,
+				}
+			}
+		})
+	}
+
+	/// Analyses a text into its terms, with the start of their original term.
+	async fn analyze_terms(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		text: String,
+	) -> Result<Vec<(Position, String)>> {
+		let tokens = self
+			.analyzer
+			.generate_tokens(stk, ctx, opt, FilteringStage::Querying, text.into())
+			.await?;
+		tokens
+			.list()
+			.iter()
+			.map(|t| Ok((t.get_start(), tokens.get_token_string(t)?.to_owned())))
+			.collect()
+	}
+
+	/// Analyses a single word, keeping it as given if the analyzer drops it.
+	async fn analyze_term(
+		&self,
+		stk: &mut Stk,
+		ctx: &FrozenContext,
+		opt: &Options,
+		word: String,
+	) -> Result<String> {
+		let mut terms = self.analyze_terms(stk, ctx, opt, word.clone()).await?;
+		Ok(if terms.is_empty() {
+			word
+		} else {
+			terms.swap_remove(0).1
+		})
+	}
+
+	/// Lists the indexed terms starting with `prefix`, skipping from one term
+	/// to the next rather than scanning each term's documents.
+	async fn expand_prefix(&self, tx: &Transaction, prefix: &str) -> Result<Vec<String>> {
+		let (mut beg, end) = self.ikb.new_td_prefix_range(prefix)?;
+		let mut terms = Vec::new();
+		while terms.len() < MAX_PREFIX_TERMS {
+			let Some(key) = tx.keys(beg..end.clone(), 1, 0, None).await?.pop() else {
+				break;
+			};
+			// The range holds both the per-document keys and the compacted roots
+			let term = match Td::decode_key(&key) {
+				Ok(td) => td.term.into_owned(),
+				Err(_) => TdRoot::decode_key(&key)?.term.into_owned(),
+			};
+			beg = self.ikb.new_td_after_term(&term)?;
+			terms.push(term);
+		}
+		Ok(terms)
+	}
+
+	/// Pushes the documents matching each operand of a clause.
+	async fn clause_docs(
+		&self,
+		tx: &Transaction,
+		clause: &AnalyzedClause,
+		term_docs: &HashMap<&str, Option<RoaringTreemap>>,
+		operands: &mut Vec<Option<RoaringTreemap>>,
+	) -> Result<()> {
+		let docs = |t: &str| term_docs.get(t).cloned().flatten();
+		match clause {
+			AnalyzedClause::Terms(terms) => operands.extend(terms.iter().map(|t| docs(t.as_str()))),
+			AnalyzedClause::Prefix {
+				terms,
+				..
+			} => {
+				let terms: Vec<_> = terms.iter().map(|t| docs(t.as_str())).collect();
+				operands.push(Self::union_operation(&terms));
+			}
+			AnalyzedClause::Phrase(_)
+			| AnalyzedClause::Near {
+				..
+			} => {
+				let terms = clause.terms();
+				let candidates: Vec<_> = terms.iter().map(|t| docs(t)).collect();
+				let Some(candidates) = Self::intersection_operation(&candidates) else {
+					operands.push(None);
+					return Ok(());
+				};
+				let mut hits = RoaringTreemap::new();
+				for doc_id in candidates {
+					let mut occurrences = Vec::with_capacity(terms.len());
+					for t in &terms {
+						occurrences.push(self.get_term_positions(tx, doc_id, t).await?);
+					}
+					let matches = match clause {
+						AnalyzedClause::Near {
+							distance,
+							..
+						} => near_matches(&occurrences[0], &occurrences[1], *distance),
+						_ => phrase_matches(&occurrences),
+					};
+					if matches {
+						hits.insert(doc_id);
+					}
+				}
+				operands.push(Some(hits).filter(|h| !h.is_empty()));
+			}
+		}
+		Ok(())
+	}
+
+	/// The sorted `(value index, position)` occurrences of a term in a document
+	async fn get_term_positions(
+		&self,
+		tx: &Transaction,
+		doc_id: DocId,
+		term: &str,
+	) -> Result<Vec<(u32, Position)>> {
+		let mut positions: Vec<_> = match self.get_term_document(tx, doc_id, term).await? {
+			Some(td) => {
+				// Offsets indexed before positions were recorded can't be matched
+				ensure!(td.o.iter().all(Offset::has_pos), Error::FtPositionsOutdated);
+				td.o.iter().map(|o| (o.index, o.pos)).collect()
+			}
+			None => Vec::new(),
+		};
+		positions.sort_unstable();
+		Ok(positions)
+	}
+
 	pub(in crate::idx) async fn matches_value(
 		&self,
 		stk: &mut Stk,
@@ -527,6 +1114,9 @@ impl FullTextIndex {
 	) -> Result<bool> {
 		let mut tks = vec![];
 		self.analyzer.analyze_value(stk, ctx, opt, val, FilteringStage::Indexing, &mut tks).await?;
+		if let Some(syntax) = &qt.syntax {
+			return syntax.matches_value(bo, &tks);
+		}
 		match bo {
 			BooleanOperator::And => qt.matches_and(&tks),
 			BooleanOperator::Or => qt.matches_or(&tks),
@@ -562,20 +1152,23 @@ impl FullTextIndex {
 
 		Ok(docs)
 	}
+	/// Applies the deltas to the compacted documents of a term, returning
+	/// whether any document still contains the term.
 	async fn set_term_docs_delta(
 		&self,
 		tx: &Transaction,
 		term: &str,
 		deltas: &HashMap<DocId, i64>,
-	) -> Result<()> {
+	) -> Result<bool> {
 		let docs = self.append_term_docs_delta(tx, term, deltas).await?;
 		let td = self.ikb.new_td_root(term);
 		if docs.is_empty() {
 			tx.del(&td).await?;
+			Ok(false)
 		} else {
 			tx.set(&td, &docs).await?;
+			Ok(true)
 		}
-		Ok(())
 	}
 
 	/// Read phase for `!tt`: capture the generation, fold visible deltas by
@@ -656,17 +1249,52 @@ impl FullTextIndex {
 		tx: &Transaction,
 		plan: TermDocsCompactionPlan,
 	) -> Result<()> {
+		let mut added = BTreeSet::new();
+		let mut removed = BTreeSet::new();
 		for (term, deltas) in plan.deltas_by_term {
 			if !deltas.is_empty() {
-				self.set_term_docs_delta(tx, &term, &deltas).await?;
+				if self.set_term_docs_delta(tx, &term, &deltas).await? {
+					added.insert(term);
+				} else {
+					removed.insert(term);
+				}
 			}
 		}
+		if self.fuzzy.is_some() && !(added.is_empty() && removed.is_empty()) {
+			self.update_term_dictionary(tx, &added, &removed).await?;
+		}
 		for key in plan.delta_keys {
 			tx.del(&key).await?;
 		}
 		Ok(())
 	}
 
+	/// Adds and removes the compacted terms of a fuzzy index from its term
+	/// dictionary, rebuilding only the shards holding these terms.
+	async fn update_term_dictionary(
+		&self,
+		tx: &Transaction,
+		added: &BTreeSet<String>,
+		removed: &BTreeSet<String>,
+	) -> Result<()> {
+		let mut shards: BTreeMap<&str, (BTreeSet<String>, BTreeSet<String>)> = BTreeMap::new();
+		for term in added {
+			shards.entry(fuzzy::shard(term)).or_default().0.insert(term.clone());
+		}
+		for term in removed {
+			shards.entry(fuzzy::shard(term)).or_default().1.insert(term.clone());
+		}
+		for (shard, (added, removed)) in shards {
+			let key = self.ikb.new_tf_key(shard);
+			let dictionary: Option<Vec<u8>> = tx.get(&key, None).await?;
+			match fuzzy::update(dictionary.as_deref(), &added, &removed)? {
+				Some(dictionary) => tx.set(&key, &dictionary).await?,
+				None => tx.del(&key).await?,
+			}
+		}
+		Ok(())
+	}
+
 	/// Compacts term documents by consolidating deltas and removing logs.
 	#[cfg(test)]
 	async fn compact_term_docs(&self, tx: &Transaction) -> Result<bool> {
@@ -684,9 +1312,10 @@ impl FullTextIndex {
 		bo: BooleanOperator,
 	) -> Option<FullTextHitsIterator> {
 		// Execute the operation depending on the operator
-		let hits = match bo {
-			BooleanOperator::And => Self::intersection_operation(&qt.docs),
-			BooleanOperator::Or => Self::union_operation(&qt.docs),
+		let hits = match (&qt.syntax, bo) {
+			(Some(syntax), bo) => syntax.hits(bo),
+			(None, BooleanOperator::And) => Self::intersection_operation(&qt.docs),
+			(None, BooleanOperator::Or) => Self::union_operation(&qt.docs),
 		};
 
 		// Create and return an iterator if we have matching documents
@@ -1007,6 +1636,11 @@ impl FullTextIndex {
 					hl.highlight(tk.get_char_len(), td.o);
 				}
 			}
+			for (term, len, _) in &qt.terms {
+				if let Some(td) = self.get_term_document(tx, doc_id, term).await? {
+					hl.highlight(*len, td.o);
+				}
+			}
 			return hl.try_into();
 		}
 		Ok(Value::None)
@@ -1039,6 +1673,11 @@ impl FullTextIndex {
 					or.highlight(tk.get_char_len(), o.o);
 				}
 			}
+			for (term, len, _) in &qt.terms {
+				if let Some(o) = self.get_term_document(tx, doc_id, term).await? {
+					or.highlight(*len, o.o);
+				}
+			}
 			return Ok(or.into());
 		}
 		Ok(Value::None)
@@ -1152,6 +1791,16 @@ impl Scorer {
 				}
 			}
 		}
+		// The terms of the phrase, proximity and prefix clauses, and the
+		// fuzzy matches of the tokens
+		for (term, _, docs) in &qt.terms {
+			if let Some(docs) = docs
+				&& docs.contains(doc_id)
+				&& let Some(td) = fti.get_term_document(tx, doc_id, term).await?
+			{
+				sc += self.compute_bm25_score(td.f as f64, docs.len() as f64, doc_length)
+			}
+		}
 		Ok(sc as f32)
 	}
 
@@ -1295,6 +1944,7 @@ mod tests {
 				analyzer: az.name.clone(),
 				scoring: Default::default(),
 				highlight: true,
+				fuzzy: None,
 			});
 			let nid = Uuid::new_v4();
 			let ikb = IndexKeyBase::new(NamespaceId(1), DatabaseId(2), "t".into(), IndexId(3));
@@ -1396,30 +2046,35 @@ mod tests {
 							start: 44,
 							gen_start: 44,
 							end: 47,
+							pos: 6,
 						},
 						Offset {
 							index: 3,
 							start: 42,
 							gen_start: 42,
 							end: 45,
+							pos: 7,
 						},
 						Offset {
 							index: 16,
 							start: 4,
 							gen_start: 4,
 							end: 7,
+							pos: 1,
 						},
 						Offset {
 							index: 18,
 							start: 8,
 							gen_start: 8,
 							end: 11,
+							pos: 2,
 						},
 						Offset {
 							index: 19,
 							start: 59,
 							gen_start: 59,
 							end: 62,
+							pos: 12,
 						},
 					],
 				}
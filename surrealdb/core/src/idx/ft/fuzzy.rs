@@ -0,0 +1,215 @@
+//! Fuzzy (edit-distance) term matching for full-text indexes defined with
+//! `FUZZY`.
+//!
+//! The compacted terms of such an index are kept in FST sets (the `!tf`
+//! term dictionary), sharded by the first character of the terms. A
+//! Levenshtein automaton walks the shards it can enter to list the indexed
+//! terms within the edit distance of a query term, pruning every branch of
+//! the dictionary as soon as it can no longer match.
+
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use fst::{Automaton, IntoStreamer, Set, SetBuilder, Streamer};
+
+/// The edit distance allowed for a query term: like the `AUTO` fuzziness of
+/// Elasticsearch, terms of up to 2 characters match exactly, and terms of up
+/// to 5 characters within a single edit, bounded by the index's distance.
+pub(in crate::idx::ft) fn term_distance(term: &str, max: u8) -> u32 {
+	let distance = match term.chars().count() {
+		0..=2 => 0,
+		3..=5 => 1,
+		_ => 2,
+	};
+	distance.min(max as u32)
+}
+
+/// The shard of the term dictionary holding a term: its first character.
+pub(in crate::idx::ft) fn shard(term: &str) -> &str {
+	match term.char_indices().nth(1) {
+		Some((i, _)) => &term[..i],
+		None => term,
+	}
+}
+
+/// An automaton matching the strings within a Levenshtein distance of a
+/// term, counted in characters.
+pub(in crate::idx::ft) struct Levenshtein {
+	term: Vec<char>,
+	distance: u32,
+}
+
+/// The last row of the edit distance matrix between the term and the input
+/// read so far, with the bytes of an incomplete UTF-8 character. `None` once
+/// no continuation of the input can match.
+#[derive(Clone)]
+pub(in crate::idx::ft) struct LevenshteinState(Option<(Vec<u32>, Vec<u8>)>);
+
+impl Levenshtein {
+	pub(in crate::idx::ft) fn new(term: &str, distance: u32) -> Self {
+		Self {
+			term: term.chars().collect(),
+			distance,
+		}
+	}
+
+	/// Whether a string is within the distance of the term.
+	pub(in crate::idx::ft) fn matches(&self, s: &str) -> bool {
+		let state = s.bytes().fold(self.start(), |state, b| self.accept(&state, b));
+		self.is_match(&state)
+	}
+
+	/// Whether a string starting with `prefix` can be within the distance of
+	/// the term, to skip the shards of the dictionary it can not match.
+	pub(in crate::idx::ft) fn can_enter(&self, prefix: &str) -> bool {
+		let state = prefix.bytes().fold(self.start(), |state, b| self.accept(&state, b));
+		self.can_match(&state)
+	}
+
+	fn step(&self, row: &[u32], c: char) -> Vec<u32> {
+		let mut next = Vec::with_capacity(row.len());
+		next.push(row[0] + 1);
+		for (i, t) in self.term.iter().enumerate() {
+			let substitution = row[i] + u32::from(*t != c);
+			next.push(substitution.min(row[i + 1] + 1).min(next[i] + 1));
+		}
+		next
+	}
+}
+
+impl Automaton for Levenshtein {
+	type State = LevenshteinState;
+
+	fn start(&self) -> Self::State {
+		LevenshteinState(Some(((0..=self.term.len() as u32).collect(), Vec::new())))
+	}
+
+	fn is_match(&self, state: &Self::State) -> bool {
+		match &state.0 {
+			Some((row, partial)) => {
+				partial.is_empty() && row.last().is_some_and(|d| *d <= self.distance)
+			}
+			None => false,
+		}
+	}
+
+	fn can_match(&self, state: &Self::State) -> bool {
+		state.0.is_some()
+	}
+
+	fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+		let Some((row, partial)) = &state.0 else {
+			return LevenshteinState(None);
+		};
+		let mut partial = partial.clone();
+		partial.push(byte);
+		let c = match std::str::from_utf8(&partial) {
+			Ok(s) => s.chars().next(),
+			// An incomplete character, waiting for its next bytes
+			Err(e) if e.error_len().is_none() => {
+				return LevenshteinState(Some((row.clone(), partial)));
+			}
+			Err(_) => None,
+		};
+		let Some(c) = c else {
+			return LevenshteinState(None);
+		};
+		let row = self.step(row, c);
+		if row.iter().all(|d| *d > self.distance) {
+			return LevenshteinState(None);
+		}
+		LevenshteinState(Some((row, Vec::new())))
+	}
+}
+
+/// Lists the terms of a dictionary within the distance of a term.
+pub(in crate::idx::ft) fn search(
+	dictionary: &[u8],
+	term: &str,
+	distance: u32,
+) -> Result<Vec<String>> {
+	let set = Set::new(dictionary)?;
+	Ok(set.search(Levenshtein::new(term, distance)).into_stream().into_strs()?)
+}
+
+/// Builds a dictionary from an existing one, adding and removing terms.
+/// Returns `None` once no term is left.
+pub(in crate::idx::ft) fn update(
+	dictionary: Option<&[u8]>,
+	added: &BTreeSet<String>,
+	removed: &BTreeSet<String>,
+) -> Result<Option<Vec<u8>>> {
+	let current = match dictionary {
+		Some(dictionary) => Set::new(dictionary.to_vec())?,
+		None => Set::from_iter(std::iter::empty::<&str>())?,
+	};
+	let added = Set::from_iter(added)?;
+	let mut stream = current.op().add(&added).union();
+	let mut builder = SetBuilder::memory();
+	let mut empty = true;
+	while let Some(term) = stream.next() {
+		if !std::str::from_utf8(term).is_ok_and(|t| removed.contains(t)) {
+			builder.insert(term)?;
+			empty = false;
+		}
+	}
+	if empty {
+		return Ok(None);
+	}
+	Ok(Some(builder.into_inner()?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn levenshtein() {
+		let lev = Levenshtein::new("quick", 1);
+		assert!(lev.matches("quick"));
+		assert!(lev.matches("quik"));
+		assert!(lev.matches("quicks"));
+		assert!(lev.matches("quack"));
+		// A transposition is two edits
+		assert!(!lev.matches("qiuck"));
+		// Distances count characters, not bytes
+		let lev = Levenshtein::new("café", 1);
+		assert!(lev.matches("cafe"));
+		assert!(lev.matches("cafés"));
+		assert!(!lev.matches("cofe"));
+		// Shards are skipped once their first character exceeds the distance
+		let lev = Levenshtein::new("fox", 0);
+		assert!(lev.can_enter("f"));
+		assert!(!lev.can_enter("b"));
+		assert!(Levenshtein::new("fox", 1).can_enter("b"));
+	}
+
+	#[test]
+	fn shards() {
+		assert_eq!(shard("keyboard"), "k");
+		assert_eq!(shard("été"), "é");
+		assert_eq!(shard("a"), "a");
+	}
+
+	#[test]
+	fn term_distances() {
+		assert_eq!(term_distance("an", 2), 0);
+		assert_eq!(term_distance("fox", 2), 1);
+		assert_eq!(term_distance("brown", 2), 1);
+		assert_eq!(term_distance("keyboard", 2), 2);
+		assert_eq!(term_distance("keyboard", 1), 1);
+	}
+
+	#[test]
+	fn dictionary() {
+		let terms = |t: &[&str]| t.iter().map(|t| t.to_string()).collect::<BTreeSet<_>>();
+		let dict =
+			update(None, &terms(&["keyboard", "monitor", "mouse"]), &terms(&[])).unwrap().unwrap();
+		let dict = update(Some(&dict), &terms(&["house"]), &terms(&["monitor"])).unwrap().unwrap();
+		assert_eq!(search(&dict, "mouse", 1).unwrap(), vec!["house", "mouse"]);
+		assert_eq!(search(&dict, "keybaord", 2).unwrap(), vec!["keyboard"]);
+		assert!(search(&dict, "monitor", 2).unwrap().is_empty());
+		let all = terms(&["house", "keyboard", "mouse"]);
+		assert!(update(Some(&dict), &terms(&[]), &all).unwrap().is_none());
+	}
+}
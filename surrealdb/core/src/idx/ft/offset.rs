@@ -2,7 +2,7 @@ use revision::revisioned;
 
 use crate::idx::ft::Position;
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Offset {
 	pub(super) index: u32,
@@ -12,8 +12,16 @@ pub(crate) struct Offset {
 	pub(super) gen_start: Position,
 	// End position of the original term
 	pub(super) end: Position,
+	// Position of the term among the terms of its value, counting the terms
+	// generated from one original term (n-grams, ...) once. Offsets stored
+	// before revision 2 read as `UNKNOWN_POSITION`.
+	#[revision(start = 2, default_fn = "default_pos")]
+	pub(super) pos: Position,
 }
 
+/// The position of offsets stored before positions were recorded.
+const UNKNOWN_POSITION: Position = Position::MAX;
+
 impl Offset {
 	pub(crate) fn new(index: u32, start: Position, gen_start: Position, end: Position) -> Self {
 		Self {
@@ -21,6 +29,60 @@ impl Offset {
 			start,
 			gen_start,
 			end,
+			pos: 0,
 		}
 	}
+
+	pub(super) fn with_pos(mut self, pos: Position) -> Self {
+		self.pos = pos;
+		self
+	}
+
+	/// Whether the position of the term is known. Offsets stored before
+	/// revision 2 do not record it, until the index is rebuilt.
+	pub(super) fn has_pos(&self) -> bool {
+		self.pos != UNKNOWN_POSITION
+	}
+
+	fn default_pos(_revision: u16) -> Result<Position, revision::Error> {
+		Ok(UNKNOWN_POSITION)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use revision::{DeserializeRevisioned, SerializeRevisioned, revisioned};
+
+	use super::*;
+
+	/// The layout of an offset before positions were recorded.
+	#[revisioned(revision = 1)]
+	struct OffsetV1 {
+		index: u32,
+		start: Position,
+		gen_start: Position,
+		end: Position,
+	}
+
+	#[test]
+	fn revision_1_offsets_have_no_position() {
+		let mut buf = Vec::new();
+		let v1 = OffsetV1 {
+			index: 1,
+			start: 2,
+			gen_start: 3,
+			end: 4,
+		};
+		SerializeRevisioned::serialize_revisioned(&v1, &mut buf).unwrap();
+		let o: Offset = DeserializeRevisioned::deserialize_revisioned(&mut buf.as_slice()).unwrap();
+		assert_eq!((o.index, o.start, o.gen_start, o.end), (1, 2, 3, 4));
+		assert!(!o.has_pos());
+		// Offsets stored with a position keep it
+		let o = Offset::new(1, 2, 3, 4).with_pos(0);
+		let mut buf = Vec::new();
+		SerializeRevisioned::serialize_revisioned(&o, &mut buf).unwrap();
+		let o: Offset = DeserializeRevisioned::deserialize_revisioned(&mut buf.as_slice()).unwrap();
+		assert!(o.has_pos());
+		assert_eq!(o.pos, 0);
+	}
 }
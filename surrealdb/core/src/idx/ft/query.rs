@@ -0,0 +1,234 @@
+//! The query syntax accepted by the full-text `MATCHES` operator.
+//!
+//! On top of free text, which is analysed into terms combined with the
+//! operator's `AND` / `OR`, a query string can contain:
+//! - `"quoted phrases"`, matching their terms at consecutive positions;
+//! - `left NEAR/n right`, matching two terms at most `n` positions apart, in
+//!   either order;
+//! - `prefix*`, matching any indexed term starting with the prefix;
+//! - `-clause`, excluding the documents matching the clause.
+//!
+//! Phrase and proximity clauses are checked against the positions stored
+//! with each term occurrence, which the index only keeps when defined with
+//! `HIGHLIGHTS`. Indexes built before positions were stored must be rebuilt
+//! (`REBUILD INDEX`) for these clauses to match.
+
+use crate::idx::ft::Position;
+
+#[derive(Debug, PartialEq)]
+pub(in crate::idx::ft) enum Clause {
+	/// Free text, analysed into terms.
+	Text(String),
+	/// A quoted phrase.
+	Phrase(String),
+	/// Two terms at most `distance` positions apart.
+	Near {
+		left: String,
+		right: String,
+		distance: u32,
+	},
+	/// A term prefix, without its trailing `*`.
+	Prefix(String),
+}
+
+/// A parsed query string.
+#[derive(Debug, Default, PartialEq)]
+pub(in crate::idx::ft) struct QuerySyntax {
+	/// The clauses combined with the operator's `AND` / `OR`. The free text
+	/// of the query, if any, comes first as a single clause.
+	pub(in crate::idx::ft) include: Vec<Clause>,
+	/// The clauses excluding documents from the result.
+	pub(in crate::idx::ft) exclude: Vec<Clause>,
+}
+
+enum Item<'a> {
+	Word(&'a str),
+	Phrase(&'a str),
+}
+
+impl QuerySyntax {
+	pub(in crate::idx::ft) fn parse(query: &str) -> Self {
+		let items = Self::lex(query);
+		let mut syntax = Self::default();
+		let mut text = Vec::new();
+		let mut i = 0;
+		while i < items.len() {
+			let (negated, item) = &items[i];
+			// `left NEAR/n right`
+			if let Item::Word(left) = item
+				&& let Some((false, Item::Word(near))) = items.get(i + 1)
+				&& let Some(distance) = near.strip_prefix("NEAR/").and_then(|n| n.parse().ok())
+				&& let Some((false, Item::Word(right))) = items.get(i + 2)
+			{
+				let clause = Clause::Near {
+					left: (*left).to_owned(),
+					right: (*right).to_owned(),
+					distance,
+				};
+				syntax.push(*negated, clause);
+				i += 3;
+				continue;
+			}
+			let clause = match item {
+				Item::Phrase(phrase) => Clause::Phrase((*phrase).to_owned()),
+				Item::Word(word) => match word.strip_suffix('*') {
+					Some(prefix) if !prefix.is_empty() && !prefix.ends_with('*') => {
+						Clause::Prefix(prefix.to_owned())
+					}
+					_ if !negated => {
+						text.push(*word);
+						i += 1;
+						continue;
+					}
+					_ => Clause::Text((*word).to_owned()),
+				},
+			};
+			syntax.push(*negated, clause);
+			i += 1;
+		}
+		if !text.is_empty() {
+			syntax.include.insert(0, Clause::Text(text.join(" ")));
+		}
+		syntax
+	}
+
+	/// Whether the query is only free text, to be analysed as it was given.
+	pub(in crate::idx::ft) fn is_plain(&self) -> bool {
+		self.exclude.is_empty() && self.include.iter().all(|c| matches!(c, Clause::Text(_)))
+	}
+
+	/// Whether any clause needs the term positions.
+	pub(in crate::idx::ft) fn has_positional_clauses(&self) -> bool {
+		self.include
+			.iter()
+			.chain(&self.exclude)
+			.any(|c| matches!(c, Clause::Phrase(_) | Clause::Near { .. }))
+	}
+
+	fn push(&mut self, negated: bool, clause: Clause) {
+		if negated {
+			self.exclude.push(clause);
+		} else {
+			self.include.push(clause);
+		}
+	}
+
+	/// Split the query into words and quoted phrases, each flagged when
+	/// preceded by a `-`. An unterminated phrase runs to the end of the query.
+	fn lex(query: &str) -> Vec<(bool, Item<'_>)> {
+		let mut items = Vec::new();
+		let mut rest = query;
+		loop {
+			rest = rest.trim_start();
+			if rest.is_empty() {
+				break;
+			}
+			let negated = rest.len() > 1
+				&& rest.starts_with('-')
+				&& !rest[1..].starts_with(char::is_whitespace);
+			if negated {
+				rest = &rest[1..];
+			}
+			if let Some(phrase) = rest.strip_prefix('"') {
+				let (terms, after) = match phrase.find('"') {
+					Some(end) => (&phrase[..end], &phrase[end + 1..]),
+					// An unterminated phrase runs to the end of the query
+					None => (phrase, ""),
+				};
+				items.push((negated, Item::Phrase(terms)));
+				rest = after;
+			} else {
+				let end = rest.find(|c: char| c.is_whitespace() || c == '"').unwrap_or(rest.len());
+				items.push((negated, Item::Word(&rest[..end])));
+				rest = &rest[end..];
+			}
+		}
+		items
+	}
+}
+
+/// Whether the terms of a phrase occur at consecutive positions of a value.
+/// `occurrences[i]` lists the sorted `(value index, position)` of each
+/// occurrence of the phrase's i-th term.
+pub(in crate::idx::ft) fn phrase_matches(occurrences: &[Vec<(u32, Position)>]) -> bool {
+	let Some((first, rest)) = occurrences.split_first() else {
+		return false;
+	};
+	first.iter().any(|&(index, pos)| {
+		rest.iter()
+			.enumerate()
+			.all(|(i, occ)| occ.binary_search(&(index, pos + i as u32 + 1)).is_ok())
+	})
+}
+
+/// Whether two terms occur at most `distance` positions apart in a value.
+pub(in crate::idx::ft) fn near_matches(
+	left: &[(u32, Position)],
+	right: &[(u32, Position)],
+	distance: u32,
+) -> bool {
+	left.iter().any(|&(index, pos)| {
+		let from = (index, pos.saturating_sub(distance));
+		let to = (index, pos.saturating_add(distance));
+		let i = right.partition_point(|o| *o < from);
+		right.get(i).is_some_and(|o| *o <= to)
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn text(s: &str) -> Clause {
+		Clause::Text(s.to_owned())
+	}
+
+	#[test]
+	fn parse_plain_text() {
+		let q = QuerySyntax::parse("hello world");
+		assert_eq!(q.include, vec![text("hello world")]);
+		assert!(q.is_plain());
+		// Hyphens and stars within words are plain text
+		assert!(QuerySyntax::parse("state-of-the-art a*b - *").is_plain());
+	}
+
+	#[test]
+	fn parse_syntax() {
+		let q = QuerySyntax::parse(
+			r#"rust "memory safety" -unsafe compil* fast NEAR/3 code -"garbage collector""#,
+		);
+		assert_eq!(
+			q.include,
+			vec![
+				text("rust"),
+				Clause::Phrase("memory safety".to_owned()),
+				Clause::Prefix("compil".to_owned()),
+				Clause::Near {
+					left: "fast".to_owned(),
+					right: "code".to_owned(),
+					distance: 3,
+				},
+			]
+		);
+		assert_eq!(q.exclude, vec![text("unsafe"), Clause::Phrase("garbage collector".to_owned())]);
+		assert!(!q.is_plain());
+		assert!(q.has_positional_clauses());
+		// An invalid distance leaves `NEAR/` as a word, an unterminated phrase
+		// runs to the end
+		let q = QuerySyntax::parse(r#"a NEAR/x b "c d"#);
+		assert_eq!(q.include, vec![text("a NEAR/x b"), Clause::Phrase("c d".to_owned())]);
+	}
+
+	#[test]
+	fn positions() {
+		let brown = vec![(0, 1), (1, 4)];
+		let fox = vec![(0, 3), (1, 5)];
+		assert!(phrase_matches(&[brown.clone(), fox.clone()]));
+		assert!(!phrase_matches(&[fox.clone(), brown.clone()]));
+		assert!(!phrase_matches(&[vec![(0, 1)], vec![(1, 2)]]));
+		assert!(near_matches(&brown, &fox, 1));
+		assert!(near_matches(&fox, &brown, 1));
+		assert!(!near_matches(&[(0, 1)], &[(0, 3)], 1));
+		assert!(near_matches(&[(0, 1)], &[(0, 3)], 2));
+	}
+}
@@ -1,5 +1,5 @@
 //! This module applies index mutations for a single document across different
-//! index types (UNIQUE, regular, search, fulltext, Hnsw). Index keys are
+//! index types (UNIQUE, regular, search, fulltext, Hnsw, spatial). Index keys are
 //! constructed via key::index and field values are encoded using
 //! key::value::Array.
 //!
@@ -23,7 +23,7 @@ use uuid::Uuid;
 use crate::catalog::providers::TableProvider;
 use crate::catalog::{
 	DatabaseId, DiskAnnParams, FullTextParams, HnswParams, Index, IndexDefinition, NamespaceId,
-	TableId,
+	SpatialParams, TableId,
 };
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
@@ -32,6 +32,7 @@ use crate::expr::{Cond, Part};
 use crate::idx::IndexKeyBase;
 use crate::idx::ft::fulltext::{FullTextCompactionPlan, FullTextIndex};
 use crate::idx::planner::iterators::{IndexCountCompactionPlan, IndexCountThingIterator};
+use crate::idx::spatial::SpatialIndex;
 #[cfg(diskann)]
 use crate::idx::trees::diskann::index::{DiskAnnCompactionPlan, DiskAnnIndex};
 use crate::idx::trees::hnsw::index::{HnswCompactionPlan, HnswIndex};
@@ -128,6 +129,7 @@ impl<'a> IndexOperation<'a> {
 			Index::Hnsw(p) => self.index_hnsw(p, require_compaction).await,
 			Index::DiskAnn(p) => self.index_diskann(p, require_compaction).await,
 			Index::Count(c) => self.index_count(stk, c.as_ref(), require_compaction).await,
+			Index::Spatial(p) => self.index_spatial(p).await,
 		}
 	}
 
@@ -244,6 +246,11 @@ impl<'a> IndexOperation<'a> {
 		Ok(())
 	}
 
+	async fn index_spatial(&mut self, p: &SpatialParams) -> Result<()> {
+		let si = SpatialIndex::new(self.ikb.clone(), p);
+		si.index(&self.ctx.tx(), self.rid, self.o.take(), self.n.take()).await
+	}
+
 	async fn index_count(
 		&mut self,
 		_stk: &mut Stk,
@@ -2,6 +2,7 @@ pub(crate) mod ft;
 pub(crate) mod index;
 pub mod planner;
 pub(super) mod seqdocids;
+pub(crate) mod spatial;
 pub mod trees;
 
 use std::borrow::Cow;
@@ -63,6 +64,7 @@ use crate::key::index::ip::Ip;
 use crate::key::index::is::Is;
 use crate::key::index::iv::Iv;
 use crate::key::index::td::{Td, TdRoot};
+use crate::key::index::tf::Tf;
 use crate::key::index::tt::Tt;
 use crate::key::index::tv::Tv;
 use crate::key::root::ic::IndexCompactionKey;
@@ -157,6 +159,11 @@ impl IndexKeyBase {
 		He::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, element_id)
 	}
 
+	/// Range covering all HNSW element vectors.
+	fn new_he_range(&self) -> Result<Range<Key>> {
+		He::range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
 	fn new_hi_key<'a>(&'a self, id: &'a RecordIdKey) -> Hi<'a> {
 		Hi::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, id)
 	}
@@ -452,10 +459,27 @@ impl IndexKeyBase {
 		TdRoot::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term)
 	}
 
+	fn new_td_prefix_range(&self, prefix: &str) -> Result<(Key, Key)> {
+		TdRoot::prefix_range(self.0.ns, self.0.db, &self.0.tb, self.0.ix, prefix)
+	}
+
+	fn new_td_after_term(&self, term: &str) -> Result<Key> {
+		TdRoot::after_term(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term)
+	}
+
 	fn new_td<'a>(&'a self, term: &'a str, doc_id: DocId) -> Td<'a> {
 		Td::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, term, doc_id)
 	}
 
+	/// Shard of the term dictionary of a fuzzy full-text index.
+	fn new_tf_key<'a>(&'a self, shard: &'a str) -> Tf<'a> {
+		Tf::new(self.0.ns, self.0.db, &self.0.tb, self.0.ix, shard)
+	}
+
+	fn new_tf_range(&self) -> Result<(Key, Key)> {
+		Tf::range(self.0.ns, self.0.db, &self.0.tb, self.0.ix)
+	}
+
 	fn new_tt<'a>(
 		&'a self,
 		term: &'a str,
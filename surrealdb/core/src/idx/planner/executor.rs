@@ -7,7 +7,7 @@ use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
 use crate::catalog::providers::TableProvider;
-use crate::catalog::{DatabaseId, Distance, Index, IndexDefinition, NamespaceId};
+use crate::catalog::{DatabaseId, Distance, Index, IndexDefinition, NamespaceId, PreparedDistance};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::{CursorDoc, DocumentContext};
@@ -35,7 +35,7 @@ use crate::idx::trees::store::diskann::SharedDiskAnnIndex;
 use crate::idx::trees::store::hnsw::SharedHnswIndex;
 use crate::val::{Array, Number, Object, RecordId, TableName, Value};
 
-pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, Distance);
+pub(super) type KnnBruteForceEntry = (KnnPriorityList, Idiom, Arc<Vec<Number>>, PreparedDistance);
 
 pub(super) struct KnnBruteForceExpression {
 	k: u32,
@@ -334,7 +334,7 @@ impl InnerQueryExecutor {
 
 		let knn_bruteforce_len = kbtes.len();
 		for (exp, knn) in kbtes {
-			let ke = (KnnPriorityList::new(knn.k as usize), knn.id, knn.obj, knn.d);
+			let ke = (KnnPriorityList::new(knn.k as usize), knn.id, knn.obj, knn.d.prepare()?);
 			exp_entries.insert(exp, PerExpressionEntry::KnnBruteForce(ke));
 		}
 
@@ -474,6 +474,8 @@ impl QueryExecutor {
 			Index::DiskAnn(_) => Ok(self.new_diskann_index_ann_iterator(irf)),
 			#[cfg(not(diskann))]
 			Index::DiskAnn(_) => Err(anyhow::anyhow!("DISKANN indexes require a 64-bit, non-WASM platform")),
+			// Spatial indexes are only used by the streaming planner
+			Index::Spatial(_) => Ok(None),
 		}
 	}
 
@@ -5,9 +5,56 @@ use std::sync::Arc;
 use ahash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use tokio::sync::Mutex;
 
+use crate::cnf::{KNN_FILTER_BRUTE_FORCE_LIMIT, KNN_FILTER_MAX_EF};
 use crate::expr::Expr;
 use crate::val::{Number, RecordId, TableName};
 
+/// Selectivity of the condition above which a filtered KNN search traverses
+/// the graph with the requested search list size.
+const KNN_FILTER_IN_GRAPH_SELECTIVITY: f64 = 0.5;
+
+/// How an ANN search restricted by a non-vector condition is executed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum KnnFilterStrategy {
+	/// Traverse the graph, only admitting the candidates matching the condition.
+	InGraph,
+	/// Traverse the graph with a search list size expanded to compensate for
+	/// the candidates rejected by the condition.
+	AdaptiveEf(usize),
+	/// Compute the exact distances of the few records matching the condition.
+	BruteForce,
+}
+
+impl KnnFilterStrategy {
+	/// Chooses a strategy from the estimated selectivity of the condition.
+	///
+	/// `matching` is the number of records matching the condition, which is a
+	/// lower bound unless `exhausted` is true, and `indexed` is the number of
+	/// documents held by the vector index.
+	pub(crate) fn select(ef: usize, matching: usize, exhausted: bool, indexed: u64) -> Self {
+		if exhausted && matching <= *KNN_FILTER_BRUTE_FORCE_LIMIT {
+			return Self::BruteForce;
+		}
+		if indexed == 0 {
+			return Self::InGraph;
+		}
+		let selectivity = (matching as f64 / indexed as f64).min(1.0);
+		if selectivity >= KNN_FILTER_IN_GRAPH_SELECTIVITY {
+			return Self::InGraph;
+		}
+		let max_ef = (*KNN_FILTER_MAX_EF).max(ef);
+		Self::AdaptiveEf(((ef as f64 / selectivity).ceil() as usize).min(max_ef))
+	}
+
+	/// Returns the search list size the graph is traversed with.
+	pub(crate) fn ef(&self, ef: usize) -> usize {
+		match self {
+			Self::AdaptiveEf(ef) => *ef,
+			_ => ef,
+		}
+	}
+}
+
 pub(super) struct KnnPriorityList(Arc<Mutex<Inner>>);
 
 struct Inner {
@@ -154,3 +201,41 @@ impl KnnBruteForceResults {
 		None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::KnnFilterStrategy;
+
+	#[test]
+	fn test_knn_filter_strategy() {
+		// Few matching records: exact search over them
+		assert_eq!(
+			KnnFilterStrategy::select(40, 0, true, 1_000_000),
+			KnnFilterStrategy::BruteForce
+		);
+		assert_eq!(
+			KnnFilterStrategy::select(40, 1000, true, 1_000_000),
+			KnnFilterStrategy::BruteForce
+		);
+		// Not selective: plain in-graph filtering
+		assert_eq!(
+			KnnFilterStrategy::select(40, 10_000, false, 15_000),
+			KnnFilterStrategy::InGraph
+		);
+		assert_eq!(KnnFilterStrategy::select(40, 2000, true, 0), KnnFilterStrategy::InGraph);
+		// Selective: the search list grows with the rejected candidates
+		assert_eq!(
+			KnnFilterStrategy::select(40, 10_000, false, 100_000),
+			KnnFilterStrategy::AdaptiveEf(400)
+		);
+		// ... up to the configured bound
+		assert_eq!(
+			KnnFilterStrategy::select(40, 10_000, false, 100_000_000),
+			KnnFilterStrategy::AdaptiveEf(4096)
+		);
+		assert_eq!(
+			KnnFilterStrategy::select(5000, 10_000, false, 100_000_000),
+			KnnFilterStrategy::AdaptiveEf(5000)
+		);
+	}
+}
@@ -1,7 +1,7 @@
 pub(crate) mod count_exists_rewriter;
 pub(crate) mod executor;
 pub(crate) mod iterators;
-pub(in crate::idx) mod knn;
+pub(crate) mod knn;
 pub(crate) mod plan;
 pub(in crate::idx) mod rewriter;
 pub(in crate::idx) mod tree;
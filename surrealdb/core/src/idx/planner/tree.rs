@@ -11,6 +11,7 @@ use crate::catalog::providers::TableProvider;
 use crate::catalog::{self, DatabaseId, Index, IndexDefinition, IndexId, NamespaceId, Permission};
 use crate::expr::operator::NearestNeighbor;
 use crate::expr::order::{OrderList, Ordering};
+use crate::expr::statements::define::index_column_fields;
 use crate::expr::visit::MutVisitor;
 use crate::expr::{
 	BinaryOperator, Cond, Expr, FlowResultExt as _, Idiom, Kind, Literal, Order, Part, With,
@@ -51,6 +52,10 @@ impl Tree {
 		let mut b = TreeBuilder::new(stm_ctx, table);
 		if let Some(cond) = stm_ctx.cond {
 			b.eval_cond(stk, cond).await?;
+			// The schema is loaded once the condition reads a field
+			if let Some(schema) = b.schemas.get(table) {
+				cond.check_partial_indexes(&schema.indexes)?;
+			}
 		}
 		b.eval_order().await?;
 		b.eval_count(table).await?;
@@ -301,11 +306,14 @@ impl<'a> TreeBuilder<'a> {
 				| Literal::Decimal(_)
 				| Literal::Float(_),
 			)
-			| Expr::Param(_)
-			| Expr::FunctionCall(_) => {
+			| Expr::Param(_) => {
 				self.leaf_nodes_count += 1;
 				Ok(Node::Computable)
 			}
+			Expr::FunctionCall(_) => {
+				self.leaf_nodes_count += 1;
+				self.eval_expression(v).await
+			}
 			Expr::Literal(Literal::Array(a)) => self.eval_array(stk, a).await,
 			_ => Ok(Node::Unsupported(format!("Unsupported expression: {}", v.to_sql()))),
 		}
@@ -358,6 +366,28 @@ impl<'a> TreeBuilder<'a> {
 		Ok(n)
 	}
 
+	/// Resolves an expression matching an expression index column, like
+	/// `string::lowercase(email)`. Any other expression is computable.
+	async fn eval_expression(&mut self, v: &Expr) -> Result<Node> {
+		let i = Arc::new(Idiom(vec![Part::Start(v.clone())]));
+		if let Some(node) = self.resolved_idioms.get(&i).cloned() {
+			return Ok(node);
+		}
+		let tx = self.ctx.ctx.tx();
+		let schema = self.lazy_load_schema_resolver(&tx, self.table).await?;
+		if self.ctx.is_perm && Self::idiom_touches_restricted_field(&i, schema.fields.as_ref()) {
+			self.cond_touches_restricted_field = true;
+		}
+		let irs = self.resolve_indexes(self.table, &i, &schema);
+		let n = if irs.is_empty() {
+			Node::Computable
+		} else {
+			Node::IndexedField(Arc::clone(&i), irs)
+		};
+		self.resolved_idioms.insert(i, n.clone());
+		Ok(n)
+	}
+
 	async fn resolve_idiom(&mut self, i: &Idiom) -> Result<Node> {
 		let tx = self.ctx.ctx.tx();
 		let schema = self.lazy_load_schema_resolver(&tx, self.table).await?;
@@ -399,6 +429,10 @@ impl<'a> TreeBuilder<'a> {
 			if ix.prepare_remove {
 				continue;
 			}
+			// A partial index can only serve a condition on this table
+			if ix.cond.is_some() && (t != self.table.as_str() || !ix.serves(self.ctx.cond)) {
+				continue;
+			}
 			if let Some(idiom_index) = ix.cols.iter().position(|p| p.eq(i)) {
 				// SECURITY: when permissions are being enforced, refuse to use
 				// an index whose columns reference a field with a restrictive
@@ -445,6 +479,12 @@ impl<'a> TreeBuilder<'a> {
 	/// Returns true when the idiom (or any of its ancestor field paths) is
 	/// governed by a field definition with a non-`Full` SELECT permission.
 	fn idiom_touches_restricted_field(idiom: &Idiom, fields: &[catalog::FieldDefinition]) -> bool {
+		// An expression column touches every field the expression reads
+		if let Some(Part::Start(_)) = idiom.first() {
+			return index_column_fields(idiom)
+				.iter()
+				.any(|i| Self::idiom_touches_restricted_field(i, fields));
+		}
 		for field in fields {
 			if idiom.starts_with(field.name.0.as_slice())
 				&& !matches!(field.select_permission, Permission::Full)
@@ -0,0 +1,422 @@
+//! Geohash-cell based spatial index.
+//!
+//! Every indexed geometry is approximated by its bounding rectangle, which is
+//! then covered by a small set of geohash cells. The cover uses the finest
+//! level (up to the index precision) for which the number of cells stays
+//! below [`MAX_COVER_CELLS`], so that large geometries are stored in a few
+//! coarse cells and small geometries in a few fine cells.
+//!
+//! A query region is covered the same way. Because geohash cells are
+//! hierarchical, a stored cell overlaps a query cell only if one is a prefix
+//! of the other. The search therefore looks up the exact ancestors of each
+//! query cell, and scans the descendants of each query cell with a single
+//! key range. The result is a superset of the matching records: the caller
+//! is expected to re-check the original predicate on every candidate.
+//!
+//! Records whose value is a geometry other than a point are additionally
+//! stored under an empty cell. `geo::distance` returns `NONE` for those
+//! geometries, which compares lower than any distance, so distance searches
+//! must return them as well.
+
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::ops::Range;
+
+use anyhow::Result;
+use geo::{BoundingRect, Coord, Rect};
+
+use crate::catalog::SpatialParams;
+use crate::idx::IndexKeyBase;
+use crate::key::index::gh::Gh;
+use crate::kvs::Transaction;
+use crate::val::{Geometry, RecordId, Value};
+
+/// The maximum number of cells used to cover a single rectangle.
+const MAX_COVER_CELLS: usize = 32;
+
+/// The mean radius of the earth in meters, as used by `geo::distance`.
+const EARTH_RADIUS_METERS: f64 = 6_371_008.8;
+
+/// The number of keys fetched per batch when scanning cells.
+const SCAN_BATCH_SIZE: u32 = 1000;
+
+/// The cell marking records whose value is not a single point.
+const NON_POINT_CELL: &str = "";
+
+static BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+pub(crate) struct SpatialIndex {
+	ikb: IndexKeyBase,
+	precision: u8,
+}
+
+impl SpatialIndex {
+	pub(crate) fn new(ikb: IndexKeyBase, p: &SpatialParams) -> Self {
+		Self {
+			ikb,
+			precision: p.precision,
+		}
+	}
+
+	/// Updates the index entries of a record, removing the cells which are
+	/// no longer covered by the new values and adding the new ones.
+	pub(crate) async fn index(
+		&self,
+		tx: &Transaction,
+		rid: &RecordId,
+		old_values: Option<Vec<Value>>,
+		new_values: Option<Vec<Value>>,
+	) -> Result<()> {
+		let old_cells = self.cells_for_values(old_values);
+		let new_cells = self.cells_for_values(new_values);
+		for cell in old_cells.difference(&new_cells) {
+			let key = self.new_gh_key(cell, rid);
+			tx.del(&key).await?;
+		}
+		for cell in new_cells.difference(&old_cells) {
+			let key = self.new_gh_key(cell, rid);
+			tx.set(&key, rid).await?;
+		}
+		Ok(())
+	}
+
+	/// Returns a cursor over the records whose indexed geometries may overlap
+	/// the region.
+	///
+	/// When `non_points` is set, the records whose value is a geometry other
+	/// than a point are returned too, wherever they are located.
+	pub(crate) fn search(&self, region: &[Rect<f64>], non_points: bool) -> Result<SpatialSearch> {
+		// Collect the query cells, and the coarser cells containing them
+		let mut cells = BTreeSet::new();
+		for rect in region {
+			cells.extend(cover_rect(rect, self.precision));
+		}
+		let mut ancestors = BTreeSet::new();
+		for cell in &cells {
+			for len in 1..cell.len() {
+				ancestors.insert(&cell[..len]);
+			}
+		}
+		// Collect the key ranges to scan
+		let mut ranges = VecDeque::new();
+		for cell in &cells {
+			let (beg, end) = self.new_gh_descendants_range(cell)?;
+			ranges.push_back(beg..end);
+		}
+		for cell in ancestors {
+			// Ancestors which are query cells are already covered
+			if cells.contains(cell) {
+				continue;
+			}
+			let (beg, end) = self.new_gh_cell_range(cell)?;
+			ranges.push_back(beg..end);
+		}
+		if non_points {
+			let (beg, end) = self.new_gh_cell_range(NON_POINT_CELL)?;
+			ranges.push_back(beg..end);
+		}
+		Ok(SpatialSearch {
+			ranges,
+			seen: HashSet::new(),
+		})
+	}
+
+	fn cells_for_values(&self, values: Option<Vec<Value>>) -> BTreeSet<String> {
+		let mut cells = BTreeSet::new();
+		if let Some(values) = values {
+			for v in values {
+				if let Value::Geometry(g) = &v
+					&& !matches!(g, Geometry::Point(_))
+				{
+					cells.insert(NON_POINT_CELL.to_owned());
+				}
+				self.cells_for_value(&v, &mut cells);
+			}
+		}
+		cells
+	}
+
+	fn cells_for_value(&self, v: &Value, cells: &mut BTreeSet<String>) {
+		match v {
+			Value::Geometry(g) => {
+				if let Some(rect) = bounding_rect(g) {
+					cells.extend(cover_rect(&rect, self.precision));
+				}
+			}
+			// Arrays of geometries are indexed element by element
+			Value::Array(a) => {
+				for v in a.iter() {
+					self.cells_for_value(v, cells);
+				}
+			}
+			// Anything else is not a geometry and is not indexed
+			_ => {}
+		}
+	}
+
+	fn new_gh_key<'a>(&'a self, cell: &'a str, rid: &'a RecordId) -> Gh<'a> {
+		Gh::new(self.ikb.ns(), self.ikb.db(), self.ikb.table(), self.ikb.index(), cell, &rid.key)
+	}
+
+	fn new_gh_cell_range(&self, cell: &str) -> Result<(Vec<u8>, Vec<u8>)> {
+		Gh::cell_range(self.ikb.ns(), self.ikb.db(), self.ikb.table(), self.ikb.index(), cell)
+	}
+
+	fn new_gh_descendants_range(&self, cell: &str) -> Result<(Vec<u8>, Vec<u8>)> {
+		Gh::descendants_range(
+			self.ikb.ns(),
+			self.ikb.db(),
+			self.ikb.table(),
+			self.ikb.index(),
+			cell,
+		)
+	}
+}
+
+/// Scans the cell ranges of a spatial search one batch at a time.
+pub(crate) struct SpatialSearch {
+	/// The key ranges which remain to be scanned
+	ranges: VecDeque<Range<Vec<u8>>>,
+	/// The records already returned, as a record can be stored in several
+	/// of the scanned cells
+	seen: HashSet<RecordId>,
+}
+
+impl SpatialSearch {
+	/// Returns the next batch of candidate records, or `None` once every cell
+	/// range has been scanned.
+	pub(crate) async fn next_batch(
+		&mut self,
+		tx: &Transaction,
+		version: Option<u64>,
+	) -> Result<Option<Vec<RecordId>>> {
+		while let Some(rng) = self.ranges.pop_front() {
+			let batch = tx.batch_keys_vals(rng, SCAN_BATCH_SIZE, version).await?;
+			if let Some(next) = batch.next {
+				self.ranges.push_front(next);
+			}
+			let mut res = Vec::with_capacity(batch.result.len());
+			for (_, val) in batch.result {
+				let rid: RecordId = revision::from_slice(&val)?;
+				if !self.seen.contains(&rid) {
+					self.seen.insert(rid.clone());
+					res.push(rid);
+				}
+			}
+			if !res.is_empty() {
+				return Ok(Some(res));
+			}
+		}
+		Ok(None)
+	}
+}
+
+/// Returns the bounding rectangle of a geometry, if it is not empty.
+pub(crate) fn bounding_rect(g: &Geometry) -> Option<Rect<f64>> {
+	match g {
+		Geometry::Point(v) => Some(v.bounding_rect()),
+		Geometry::Line(v) => v.bounding_rect(),
+		Geometry::Polygon(v) => v.bounding_rect(),
+		Geometry::MultiPoint(v) => v.bounding_rect(),
+		Geometry::MultiLine(v) => v.bounding_rect(),
+		Geometry::MultiPolygon(v) => v.bounding_rect(),
+		Geometry::Collection(v) => v.iter().filter_map(bounding_rect).reduce(|a, b| {
+			Rect::new(
+				Coord {
+					x: a.min().x.min(b.min().x),
+					y: a.min().y.min(b.min().y),
+				},
+				Coord {
+					x: a.max().x.max(b.max().x),
+					y: a.max().y.max(b.max().y),
+				},
+			)
+		}),
+	}
+}
+
+/// Returns the region containing every point within `meters` of `centre`.
+///
+/// The region is slightly enlarged to absorb floating point error, and spans
+/// all longitudes when it reaches a pole or crosses the antimeridian.
+pub(crate) fn region_within_distance(centre: Coord<f64>, meters: f64) -> Rect<f64> {
+	// Enlarge the radius by 1% to stay on the safe side of rounding errors
+	let angle = (meters * 1.01 / EARTH_RADIUS_METERS).to_degrees();
+	let min_y = centre.y - angle;
+	let max_y = centre.y + angle;
+	if min_y <= -90.0 || max_y >= 90.0 {
+		return Rect::new(
+			Coord {
+				x: -180.0,
+				y: min_y.max(-90.0),
+			},
+			Coord {
+				x: 180.0,
+				y: max_y.min(90.0),
+			},
+		);
+	}
+	let lat = min_y.abs().max(max_y.abs()).to_radians();
+	let dx = angle / lat.cos();
+	let (min_x, max_x) = if centre.x - dx < -180.0 || centre.x + dx > 180.0 {
+		(-180.0, 180.0)
+	} else {
+		(centre.x - dx, centre.x + dx)
+	};
+	Rect::new(
+		Coord {
+			x: min_x,
+			y: min_y,
+		},
+		Coord {
+			x: max_x,
+			y: max_y,
+		},
+	)
+}
+
+/// Covers a rectangle with geohash cells.
+///
+/// Picks the finest level, up to `precision`, needing no more than
+/// [`MAX_COVER_CELLS`] cells. Coordinates lying exactly on a cell boundary
+/// select the cells on both sides of the boundary.
+fn cover_rect(rect: &Rect<f64>, precision: u8) -> Vec<String> {
+	let mut level = precision.max(1) as u32;
+	loop {
+		let (x_range, y_range) = cell_ranges(rect, level);
+		let count = (x_range.1 - x_range.0 + 1) * (y_range.1 - y_range.0 + 1);
+		if count as usize <= MAX_COVER_CELLS || level == 1 {
+			let mut cells = Vec::with_capacity(count as usize);
+			for x in x_range.0..=x_range.1 {
+				for y in y_range.0..=y_range.1 {
+					cells.push(encode_cell(x, y, level));
+				}
+			}
+			return cells;
+		}
+		level -= 1;
+	}
+}
+
+/// Returns the number of longitude and latitude bits of a geohash level.
+fn level_bits(level: u32) -> (u32, u32) {
+	let bits = level * 5;
+	(bits.div_ceil(2), bits / 2)
+}
+
+/// Returns the inclusive ranges of cell indexes overlapping a rectangle.
+fn cell_ranges(rect: &Rect<f64>, level: u32) -> ((u64, u64), (u64, u64)) {
+	let (x_bits, y_bits) = level_bits(level);
+	(
+		axis_range(rect.min().x, rect.max().x, -180.0, 360.0, x_bits),
+		axis_range(rect.min().y, rect.max().y, -90.0, 180.0, y_bits),
+	)
+}
+
+fn axis_range(min: f64, max: f64, origin: f64, span: f64, bits: u32) -> (u64, u64) {
+	let cells = 1u64 << bits;
+	let size = span / cells as f64;
+	let last = (cells - 1) as f64;
+	// A coordinate on a boundary belongs to the lower cell when encoding
+	// a point, so the lower bound also includes that cell.
+	let lo = (((min - origin) / size).ceil() - 1.0).clamp(0.0, last) as u64;
+	let hi = ((max - origin) / size).floor().clamp(0.0, last) as u64;
+	(lo, hi.max(lo))
+}
+
+/// Encodes a cell from its longitude and latitude indexes.
+fn encode_cell(x: u64, y: u64, level: u32) -> String {
+	let (x_bits, y_bits) = level_bits(level);
+	let mut out = String::with_capacity(level as usize);
+	let (mut xi, mut yi) = (x_bits, y_bits);
+	let mut hash = 0usize;
+	// Geohash interleaves bits, starting with the longitude
+	for i in 0..level * 5 {
+		let bit = if i % 2 == 0 {
+			xi -= 1;
+			(x >> xi) & 1
+		} else {
+			yi -= 1;
+			(y >> yi) & 1
+		};
+		hash = (hash << 1) | bit as usize;
+		if i % 5 == 4 {
+			out.push(BASE32[hash] as char);
+			hash = 0;
+		}
+	}
+	out
+}
+
+#[cfg(test)]
+mod tests {
+	use geo::Point;
+
+	use super::*;
+	use crate::fnc::util::geo::encode;
+
+	fn rect(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Rect<f64> {
+		Rect::new(
+			Coord {
+				x: min_x,
+				y: min_y,
+			},
+			Coord {
+				x: max_x,
+				y: max_y,
+			},
+		)
+	}
+
+	#[test]
+	fn point_cover_matches_geohash_encoding() {
+		for (x, y) in [(-0.1278, 51.5074), (2.3522, 48.8566), (139.6917, 35.6895), (-74.0, 40.7)] {
+			let point = Point::new(x, y);
+			for level in 1..=12 {
+				let cells = cover_rect(&rect(x, y, x, y), level);
+				assert_eq!(cells, vec![encode(point, level as usize)], "({x}, {y}) @ {level}");
+			}
+		}
+	}
+
+	#[test]
+	fn boundary_point_selects_both_cells() {
+		// The prime meridian is a cell boundary at every level
+		let cells = cover_rect(&rect(0.0, 10.0, 0.0, 10.0), 2);
+		assert_eq!(cells.len(), 2);
+		assert!(cells.contains(&encode(Point::new(0.0, 10.0), 2)));
+	}
+
+	#[test]
+	fn large_rect_uses_coarser_cells() {
+		let cells = cover_rect(&rect(-10.0, 40.0, 10.0, 60.0), 8);
+		assert!(cells.len() <= MAX_COVER_CELLS);
+		assert!(cells.iter().all(|c| c.len() < 8));
+		// Every corner falls within one of the cells
+		for (x, y) in [(-10.0, 40.0), (10.0, 60.0), (0.5, 50.5)] {
+			let hash = encode(Point::new(x, y), 8);
+			assert!(cells.iter().any(|c| hash.starts_with(c.as_str())), "({x}, {y})");
+		}
+	}
+
+	#[test]
+	fn distance_region_contains_circle() {
+		let centre = Coord {
+			x: -0.1278,
+			y: 51.5074,
+		};
+		let region = region_within_distance(centre, 1000.0);
+		// One kilometre is roughly 0.009 degrees of latitude
+		assert!(region.max().y - centre.y > 0.009);
+		assert!(region.max().x - centre.x > region.max().y - centre.y);
+		// Regions reaching a pole span all longitudes
+		let region = region_within_distance(
+			Coord {
+				x: 0.0,
+				y: 89.99,
+			},
+			5000.0,
+		);
+		assert_eq!(region.min().x, -180.0);
+		assert_eq!(region.max().x, 180.0);
+	}
+}
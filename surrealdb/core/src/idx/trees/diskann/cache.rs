@@ -13,12 +13,14 @@ use crate::catalog::{DatabaseId, IndexId, NamespaceId, TableId};
 use crate::idx::seqdocids::DocId;
 use crate::idx::trees::diskann::{DiskAnnElement, DiskAnnNode, DiskAnnState, ElementId};
 use crate::idx::trees::knn::Ids64;
+use crate::idx::trees::quantization::QuantizedVector;
 use crate::idx::trees::vector::SerializedVector;
 use crate::val::RecordIdKey;
 
 type IndexKey = (NamespaceId, DatabaseId, TableId, IndexId);
 type ElementCacheKey = (NamespaceId, DatabaseId, TableId, IndexId, ElementId);
 type NodeCacheKey = ElementCacheKey;
+type CodeCacheKey = ElementCacheKey;
 type DocSetCacheKey = ElementCacheKey;
 type DocIdCacheKey = (NamespaceId, DatabaseId, TableId, IndexId, DocId);
 
@@ -34,6 +36,7 @@ struct CachedDocId {
 enum DiskAnnCacheKey {
 	Element(ElementCacheKey),
 	Node(NodeCacheKey),
+	Code(CodeCacheKey),
 	DocSet(DocSetCacheKey),
 	DocId(DocIdCacheKey),
 	State(IndexKey),
@@ -43,6 +46,7 @@ enum DiskAnnCacheKey {
 enum DiskAnnCacheValue {
 	Element(Arc<DiskAnnElement>),
 	Node(Arc<DiskAnnNode>),
+	Code(Arc<QuantizedVector>),
 	DocSet(Ids64),
 	DocId(CachedDocId),
 	State(DiskAnnState),
@@ -65,6 +69,11 @@ impl Weighter<DiskAnnCacheKey, DiskAnnCacheValue> for DiskAnnCacheWeighter {
 					+ std::mem::size_of::<Arc<DiskAnnNode>>()
 					+ std::mem::size_of_val(key)) as u64
 			}
+			(DiskAnnCacheKey::Code(key), DiskAnnCacheValue::Code(val)) => {
+				(val.mem_size()
+					+ std::mem::size_of::<Arc<QuantizedVector>>()
+					+ std::mem::size_of_val(key)) as u64
+			}
 			(DiskAnnCacheKey::DocSet(key), DiskAnnCacheValue::DocSet(val)) => {
 				(val.iter().count() * std::mem::size_of::<u64>() + std::mem::size_of_val(key))
 					as u64
@@ -86,8 +95,8 @@ impl Weighter<DiskAnnCacheKey, DiskAnnCacheValue> for DiskAnnCacheWeighter {
 /// Shared weighted cache for one process' DiskANN graph data.
 ///
 /// The cache is scoped by namespace, database, table, and index id. Element vectors (`De` keys),
-/// adjacency lists (`Dn` keys), graph state (`Ds` key), and document mappings share one weighted
-/// budget keyed by the composite (`namespace`, `database`, `table`, `index`, `id`) tuples; index
+/// adjacency lists (`Dn` keys), graph state (`Ds` key), quantized element codes, and document
+/// mappings share one weighted budget keyed by the composite (`namespace`, `database`, `table`, `index`, `id`) tuples; index
 /// removal walks every shard with [`Cache::retain`] because per-key membership trackers cannot be
 /// kept reliably in sync with quick_cache's hot/cold/ghost eviction (see #7318).
 #[derive(Clone)]
@@ -157,6 +166,7 @@ impl DiskAnnCache {
 		element: DiskAnnElement,
 	) -> Arc<DiskAnnElement> {
 		let element = Arc::new(element);
+		self.remove_code(index, element_id);
 		self.0.cache.insert(
 			DiskAnnCacheKey::Element(Self::element_key(index, element_id)),
 			DiskAnnCacheValue::Element(Arc::clone(&element)),
@@ -167,6 +177,37 @@ impl DiskAnnCache {
 	/// Evicts one graph element from the cache.
 	pub(super) fn remove_element(&self, index: IndexKey, element_id: ElementId) {
 		self.0.cache.remove(&DiskAnnCacheKey::Element(Self::element_key(index, element_id)));
+		self.remove_code(index, element_id);
+	}
+
+	/// Returns the cached quantized code of one graph element.
+	pub(super) fn get_code(
+		&self,
+		index: IndexKey,
+		element_id: ElementId,
+	) -> Option<Arc<QuantizedVector>> {
+		match self.0.cache.get(&DiskAnnCacheKey::Code(Self::element_key(index, element_id))) {
+			Some(DiskAnnCacheValue::Code(code)) => Some(code),
+			_ => None,
+		}
+	}
+
+	/// Inserts the quantized code of one graph element.
+	pub(super) fn insert_code(
+		&self,
+		index: IndexKey,
+		element_id: ElementId,
+		code: Arc<QuantizedVector>,
+	) {
+		self.0.cache.insert(
+			DiskAnnCacheKey::Code(Self::element_key(index, element_id)),
+			DiskAnnCacheValue::Code(code),
+		);
+	}
+
+	/// Evicts the quantized code of one graph element.
+	fn remove_code(&self, index: IndexKey, element_id: ElementId) {
+		self.0.cache.remove(&DiskAnnCacheKey::Code(Self::element_key(index, element_id)));
 	}
 
 	/// Returns a shared cached adjacency-list payload.
@@ -284,6 +325,7 @@ impl DiskAnnCache {
 			DiskAnnCacheKey::State(k) => *k != index,
 			DiskAnnCacheKey::Element(k) => (k.0, k.1, k.2, k.3) != index,
 			DiskAnnCacheKey::Node(k) => (k.0, k.1, k.2, k.3) != index,
+			DiskAnnCacheKey::Code(k) => (k.0, k.1, k.2, k.3) != index,
 			DiskAnnCacheKey::DocSet(k) => (k.0, k.1, k.2, k.3) != index,
 			DiskAnnCacheKey::DocId(k) => (k.0, k.1, k.2, k.3) != index,
 		});
@@ -60,6 +60,11 @@ impl DiskAnnDocs {
 		})
 	}
 
+	/// Returns the number of documents holding a compact document ID.
+	pub(super) fn count(&self) -> u64 {
+		self.state.next_doc_id - self.state.available.len()
+	}
+
 	/// Looks up the compact document ID for a record key without allocating a new one.
 	pub(super) async fn get_doc_id(
 		ikb: &IndexKeyBase,
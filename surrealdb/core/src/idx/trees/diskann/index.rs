@@ -355,7 +355,15 @@ impl DiskAnnGraph {
 		);
 		builder.alpha(alpha);
 		let config = builder.build()?;
-		let provider = DiskAnnProvider::new(ikb, tb, cache, p.dimension as usize, metric);
+		let provider = DiskAnnProvider::new(
+			ikb,
+			tb,
+			cache,
+			p.dimension as usize,
+			metric,
+			&p.distance,
+			p.quantization,
+		);
 		Ok(Self {
 			index: RawDiskAnnIndex::new(config, provider, None),
 		})
@@ -466,7 +474,7 @@ impl DiskAnnGraph {
 		let mut ids = vec![0; limit];
 		let mut distances = vec![0.0; limit];
 		let mut output = IdDistance::new(&mut ids, &mut distances);
-		let strategy = DiskAnnStrategy::<T>::default();
+		let strategy = DiskAnnStrategy::<T>::quantized();
 		let stats =
 			self.index.search(params, &strategy, &ctx.provider_context, query, &mut output).await?;
 		let result_count = stats.result_count as usize;
@@ -477,6 +485,31 @@ impl DiskAnnGraph {
 			.map(|(id, distance)| (id, distance as f64))
 			.collect())
 	}
+
+	/// Re-ranks quantized search results with the exact distances to their full vectors.
+	///
+	/// Returns `None` when the graph search was not quantized and the raw distances are exact.
+	async fn rerank(
+		&self,
+		ctx: &DiskAnnContext<'_>,
+		distance: &Distance,
+		pt: &Vector,
+		results: &[DiskAnnSearchResult],
+	) -> Result<Option<Vec<DiskAnnSearchResult>>> {
+		let provider = self.index.provider();
+		if provider.quantizer().is_none() {
+			return Ok(None);
+		}
+		let ids: Vec<_> = results.iter().map(|(id, _)| *id).collect();
+		let vectors = provider.get_vectors(&ctx.provider_context, &ids).await?;
+		let mut reranked: Vec<_> = ids
+			.into_iter()
+			.zip(vectors)
+			.filter_map(|(id, v)| v.map(|v| (id, distance.calculate(&v, pt))))
+			.collect();
+		reranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+		Ok(Some(reranked))
+	}
 }
 
 /// Cancels `tx` and discards any error from a tx that is already closed.
@@ -1205,6 +1238,12 @@ impl DiskAnnIndex {
 		Ok(())
 	}
 
+	/// Returns the number of documents indexed in the graph. Documents only
+	/// held by pending updates are not counted, so this is an estimate.
+	pub(crate) async fn indexed_count(&self, tx: &Transaction) -> Result<u64> {
+		Ok(DiskAnnDocs::new(tx, self.ikb.clone()).await?.count())
+	}
+
 	/// Placeholder consistency hook matching the HNSW index-store interface.
 	pub(crate) async fn check_state(&self) -> Result<()> {
 		Ok(())
@@ -1242,6 +1281,8 @@ impl DiskAnnIndex {
 		let search = DiskAnnSearch::new(vector, k, ef)?;
 		let graph = self.graph.read().await;
 		let provider_context = graph.index.provider().context(ctx.tx());
+		// Train the product quantization codebook once enough vectors are indexed
+		graph.index.provider().train(ctx, &provider_context).await?;
 		let ctx = self.new_diskann_context(ctx, provider_context);
 		let mut builder = KnnResultBuilder::new(k);
 		// `pending_state` reflects only the sharded `!dy` guard; legacy `!dr` records are not
@@ -1319,10 +1360,18 @@ impl DiskAnnIndex {
 			state.graph.search(ctx, &state.search.query, state.search.k, state.search.l).await?;
 		// Keep the distances returned by graph search instead of re-reading each vector only to
 		// recompute the same score. The remaining vector reads are only needed to resolve
-		// vector-to-document keys.
+		// vector-to-document keys. Quantized distances are only approximate, so those candidates
+		// are re-ranked with their full vectors.
+		let results =
+			match state.graph.rerank(ctx, &self.distance, &state.search.pt, &results).await? {
+				Some(reranked) => reranked,
+				None => results
+					.into_iter()
+					.map(|(element_id, distance)| (element_id, self.graph_distance(distance)))
+					.collect(),
+			};
 		let candidates: Vec<_> = results
 			.into_iter()
-			.map(|(element_id, distance)| (element_id, self.graph_distance(distance)))
 			.filter(|(_, distance)| state.builder.check_add(*distance))
 			.collect();
 		if candidates.is_empty() {
@@ -1542,7 +1591,7 @@ mod tests {
 	use temp_dir::TempDir;
 
 	use super::*;
-	use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+	use crate::catalog::{DatabaseId, IndexId, NamespaceId, VectorQuantization};
 	use crate::idx::trees::diskann::cache::DiskAnnCache;
 	use crate::kvs::{Datastore, LockType, TransactionType};
 
@@ -1563,6 +1612,7 @@ mod tests {
 			l_build: 32,
 			alpha: 1.2.into(),
 			use_hashed_vector: false,
+			quantization: None,
 		}
 	}
 
@@ -3222,4 +3272,40 @@ mod tests {
 		assert_eq!(knn_len(&index, &ds, &v1).await?, 1);
 		Ok(())
 	}
+
+	/// Quantized searches traverse the graph with codes, but the results are re-ranked with the
+	/// exact distances to the full vectors.
+	#[tokio::test]
+	async fn diskann_quantized_knn_returns_exact_distances() -> Result<()> {
+		for quantization in [VectorQuantization::Scalar, VectorQuantization::Binary] {
+			let ds = Datastore::new("memory").await?;
+			let ikb = ikb();
+			let params = DiskAnnParams {
+				quantization: Some(quantization),
+				..params(VectorType::F32, Distance::Euclidean)
+			};
+			let index = DiskAnnIndex::new(ikb.clone(), TableId(4), &params, cache()).await?;
+			let vectors = [
+				[1.0_f32, 0.0, 0.0, 0.0],
+				[0.0, 1.0, 0.0, 0.0],
+				[0.0, 0.0, 1.0, 0.0],
+				[0.0, 0.0, 0.0, 1.0],
+				[0.5, 0.5, 0.5, 0.5],
+			];
+			for (id, v) in vectors.iter().enumerate() {
+				let ctx = new_ctx(&ds, TransactionType::Write).await;
+				index
+					.index(&ctx, &RecordIdKey::Number(id as i64), None, Some(f32_content(v)))
+					.await?;
+				ctx.tx().commit().await?;
+			}
+			assert!(compact_once(&index, &ds, &ikb).await?);
+			for v in &vectors {
+				assert_eq!(knn_nearest(&index, &ds, v).await?, Some(0.0), "{quantization:?}");
+			}
+			let nearest = knn_nearest(&index, &ds, &[0.9, 0.1, 0.0, 0.0]).await?.unwrap();
+			assert!((nearest - 0.02_f64.sqrt()).abs() < 1e-6, "{quantization:?}: {nearest}");
+		}
+		Ok(())
+	}
 }
@@ -27,8 +27,9 @@
 use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use diskann::graph::glue::{
 	CopyIds, DefaultPostProcessor, HybridPredicate, InsertStrategy, PruneAccessor, PruneStrategy,
 	SearchAccessor, SearchStrategy,
@@ -42,15 +43,17 @@ use diskann::utils::VectorRepr;
 use diskann::{ANNError, ANNResult, default_post_processor};
 use diskann_vector::distance::Metric;
 use diskann_vector::{Half, PreprocessedDistanceFunction};
+use parking_lot::RwLock;
 use tracing::warn;
 
-use crate::catalog::{DatabaseId, IndexId, NamespaceId, TableId};
+use crate::catalog::{DatabaseId, Distance, IndexId, NamespaceId, TableId, VectorQuantization};
+use crate::ctx::FrozenContext;
+use crate::err::Error;
 use crate::idx::IndexKeyBase;
 use crate::idx::trees::diskann::cache::DiskAnnCache;
 use crate::idx::trees::diskann::{DiskAnnElement, DiskAnnNode, DiskAnnState, ElementId};
-use crate::idx::trees::vector::SerializedVector;
-#[cfg(test)]
-use crate::idx::trees::vector::Vector;
+use crate::idx::trees::quantization::{Codebook, QuantizedVector, Quantizer};
+use crate::idx::trees::vector::{SerializedVector, Vector};
 use crate::kvs::{KVValue, Transaction};
 
 /// Provider execution context passed through the upstream DiskANN trait calls.
@@ -165,6 +168,14 @@ pub(super) struct DiskAnnProvider {
 	cache: DiskAnnCache,
 	dim: usize,
 	metric: Metric,
+	distance: Distance,
+	/// Configured vector quantization, if any.
+	quantization: Option<VectorQuantization>,
+	/// Quantizer scoring query searches. Product quantization only gets one once its codebook is
+	/// trained.
+	quantizer: RwLock<Option<Arc<Quantizer>>>,
+	/// Next element id at which the product quantization codebook training is retried.
+	next_training_at: AtomicU64,
 }
 
 impl DiskAnnProvider {
@@ -175,6 +186,8 @@ impl DiskAnnProvider {
 		cache: DiskAnnCache,
 		dim: usize,
 		metric: Metric,
+		distance: &Distance,
+		quantization: Option<VectorQuantization>,
 	) -> Self {
 		Self {
 			ikb,
@@ -182,9 +195,94 @@ impl DiskAnnProvider {
 			cache,
 			dim,
 			metric,
+			distance: distance.clone(),
+			quantization,
+			quantizer: RwLock::new(quantization.and_then(|q| Quantizer::new(distance, q))),
+			next_training_at: AtomicU64::new(*crate::cnf::VECTOR_PQ_TRAINING_SIZE as u64),
 		}
 	}
 
+	/// Returns the quantizer used to score query searches, if the index is quantized.
+	pub(super) fn quantizer(&self) -> Option<Arc<Quantizer>> {
+		self.quantizer.read().clone()
+	}
+
+	/// Trains the product quantization codebook once enough elements were inserted.
+	///
+	/// The codebook is held in memory only: each instance trains its own from the persisted
+	/// element vectors, and no code exists before it is set.
+	pub(super) async fn train(
+		&self,
+		ctx: &FrozenContext,
+		context: &DiskAnnProviderContext,
+	) -> Result<()> {
+		let Some(VectorQuantization::Product(subspaces)) = self.quantization else {
+			return Ok(());
+		};
+		if self.quantizer.read().is_some() {
+			return Ok(());
+		}
+		let state = self.read_state(context).await?;
+		if state.next_element_id < self.next_training_at.load(Ordering::Relaxed) {
+			return Ok(());
+		}
+		let size = *crate::cnf::VECTOR_PQ_TRAINING_SIZE;
+		let mut samples = Vec::with_capacity(size);
+		let rng = context.ikb.new_de_range()?;
+		let mut cursor = context
+			.tx
+			.open_vals_cursor(rng, crate::idx::planner::ScanDirection::Forward, 0, None)
+			.await?;
+		while samples.len() < size {
+			let batch = cursor.next_batch(crate::kvs::NORMAL_BATCH_SIZE).await?;
+			if batch.is_empty() {
+				break;
+			}
+			for (_, value) in &batch {
+				if ctx.is_done(Some(samples.len())).await? {
+					bail!(Error::QueryCancelled);
+				}
+				let element = DiskAnnElement::kv_decode_value(value, ())?;
+				if !element.deleted {
+					samples.push(Vector::from(element.vector));
+				}
+			}
+		}
+		drop(cursor);
+		if samples.len() < size {
+			// Elements were removed: wait for the missing ones to be inserted
+			self.next_training_at
+				.store(state.next_element_id + (size - samples.len()) as u64, Ordering::Relaxed);
+			return Ok(());
+		}
+		samples.truncate(size);
+		let codebook = Codebook::train(&samples, subspaces as usize)?;
+		*self.quantizer.write() = Some(Quantizer::with_codebook(&self.distance, codebook));
+		Ok(())
+	}
+
+	/// Returns the quantized code of one element, encoding it on a cache miss.
+	///
+	/// Like element vectors, codes are only published into the shared cache from read-only
+	/// transactions (see the module-level [cache coherency invariant](self)).
+	fn element_code(
+		&self,
+		context: &DiskAnnProviderContext,
+		quantizer: &Quantizer,
+		element_id: ElementId,
+		element: &DiskAnnElement,
+	) -> Arc<QuantizedVector> {
+		let index = self.cache_index();
+		if let Some(code) = self.cache.get_code(index, element_id) {
+			return code;
+		}
+		let code = Arc::new(quantizer.encode(&Vector::from(element.vector.clone())));
+		if !context.tx.writeable() {
+			self.cache.insert_code(index, element_id, Arc::clone(&code));
+		}
+		code
+	}
+
 	/// Returns the cache scope tuple for this provider's index.
 	fn cache_index(&self) -> (NamespaceId, DatabaseId, TableId, IndexId) {
 		(self.ikb.ns(), self.ikb.db(), self.table_id, self.ikb.index())
@@ -413,8 +511,7 @@ impl DiskAnnProvider {
 	///
 	/// Deleted and missing elements are reported as `None`, matching the pre-cache behavior used by
 	/// post-search result construction.
-	#[cfg(test)]
-	async fn get_vectors(
+	pub(super) async fn get_vectors(
 		&self,
 		context: &DiskAnnProviderContext,
 		element_ids: &[ElementId],
@@ -579,6 +676,8 @@ pub(super) struct DiskAnnSearchAccessor<'a, T: DiskAnnVectorElement> {
 	query: <T as VectorRepr>::QueryDistance,
 	/// Reusable scratch buffer for converting persisted vectors into typed slices.
 	buffer: Box<[T]>,
+	/// Quantizer and encoded query, when candidates are scored with quantized codes.
+	quantized: Option<(Arc<Quantizer>, QuantizedVector)>,
 }
 
 impl<'a, T> DiskAnnSearchAccessor<'a, T>
@@ -589,20 +688,32 @@ where
 		provider: &'a DiskAnnProvider,
 		context: &'a DiskAnnProviderContext,
 		query: &[T],
+		quantized: bool,
 	) -> Result<Self, ANNError> {
 		if query.len() != provider.dim {
 			return Err(vector_len_mismatch(query.len(), provider.dim));
 		}
+		let quantized = quantized.then(|| provider.quantizer()).flatten().map(|quantizer| {
+			let q = quantizer.encode(&Vector::from(T::serialized_from_slice(query)));
+			(quantizer, q)
+		});
 		Ok(Self {
 			provider,
 			context,
 			query: T::query_distance(query, provider.metric),
 			buffer: vec![T::default(); provider.dim].into_boxed_slice(),
+			quantized,
 		})
 	}
 
 	/// Decodes `element` into the scratch buffer and returns its distance to the query.
-	fn distance_to(&mut self, element: &DiskAnnElement) -> ANNResult<f32> {
+	///
+	/// When the search is quantized, the distance is instead approximated from the element code.
+	fn distance_to(&mut self, id: ElementId, element: &DiskAnnElement) -> ANNResult<f32> {
+		if let Some((quantizer, query)) = &self.quantized {
+			let code = self.provider.element_code(self.context, quantizer, id, element);
+			return Ok(quantizer.distance(query, &code) as f32);
+		}
 		T::copy_from_serialized(&element.vector, &mut self.buffer)?;
 		Ok(self.query.evaluate_similarity(&self.buffer))
 	}
@@ -636,7 +747,7 @@ where
 					"DiskANN start point {id} is missing"
 				)));
 			};
-			let distance = self.distance_to(&element)?;
+			let distance = self.distance_to(id, &element)?;
 			f(id, distance);
 		}
 		Ok(())
@@ -684,7 +795,7 @@ where
 			// A candidate whose element was compacted away mid-search is dropped: the
 			// `expand_beam` contract tolerates skipping candidates in exceptional cases.
 			if let Some(element) = element {
-				let distance = self.distance_to(&element)?;
+				let distance = self.distance_to(id, &element)?;
 				on_neighbors(id, distance);
 			}
 		}
@@ -873,17 +984,38 @@ impl NeighborAccessorMut for DiskAnnNeighborAccessor<'_> {
 
 /// DiskANN search/insert/prune strategy using SurrealDB's provider accessors.
 #[derive(Debug)]
-pub(super) struct DiskAnnStrategy<T>(PhantomData<fn() -> T>);
+pub(super) struct DiskAnnStrategy<T> {
+	/// Whether graph search scores candidates with the provider's quantized codes. Graph
+	/// construction always uses the full vectors.
+	quantized: bool,
+	_vector: PhantomData<fn() -> T>,
+}
+
+impl<T> DiskAnnStrategy<T> {
+	/// Returns a strategy scoring query searches with quantized codes when the index has any.
+	pub(super) fn quantized() -> Self {
+		Self {
+			quantized: true,
+			_vector: PhantomData,
+		}
+	}
+}
 
 impl<T> Default for DiskAnnStrategy<T> {
 	fn default() -> Self {
-		Self(PhantomData)
+		Self {
+			quantized: false,
+			_vector: PhantomData,
+		}
 	}
 }
 
 impl<T> Clone for DiskAnnStrategy<T> {
 	fn clone(&self) -> Self {
-		Self::default()
+		Self {
+			quantized: self.quantized,
+			_vector: PhantomData,
+		}
 	}
 }
 
@@ -900,7 +1032,7 @@ where
 		context: &'a DiskAnnProviderContext,
 		query: &'a [T],
 	) -> Result<DiskAnnSearchAccessor<'a, T>, ANNError> {
-		DiskAnnSearchAccessor::new(provider, context, query)
+		DiskAnnSearchAccessor::new(provider, context, query, self.quantized)
 	}
 }
 
@@ -955,8 +1087,15 @@ mod tests {
 		let ds = Datastore::new("memory").await?;
 		let tx = Arc::new(ds.transaction(TransactionType::Write, LockType::Optimistic).await?);
 		let ikb = ikb();
-		let provider =
-			DiskAnnProvider::new(ikb, TableId(4), DiskAnnCache::new(1024 * 1024), 2, Metric::L2);
+		let provider = DiskAnnProvider::new(
+			ikb,
+			TableId(4),
+			DiskAnnCache::new(1024 * 1024),
+			2,
+			Metric::L2,
+			&Distance::Euclidean,
+			None,
+		);
 		let context = provider.context(tx);
 		Ok((provider, context))
 	}
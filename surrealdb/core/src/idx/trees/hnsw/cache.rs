@@ -9,8 +9,8 @@ use roaring::RoaringTreemap;
 use crate::catalog::{DatabaseId, IndexId, NamespaceId, TableId};
 use crate::idx::seqdocids::DocId;
 use crate::idx::trees::hnsw::ElementId;
+use crate::idx::trees::hnsw::elements::ElementVector;
 use crate::idx::trees::knn::Ids64;
-use crate::idx::trees::vector::SharedVector;
 use crate::val::RecordIdKey;
 
 pub(super) type HnswCacheIndex = (NamespaceId, DatabaseId, TableId, IndexId);
@@ -36,7 +36,7 @@ enum HnswCacheKey {
 
 #[derive(Clone)]
 enum HnswCacheValue {
-	Vector(SharedVector),
+	Vector(ElementVector),
 	DocSet(Ids64),
 	DocId(CachedDocId),
 }
@@ -48,7 +48,7 @@ impl Weighter<HnswCacheKey, HnswCacheValue> for HnswCacheWeighter {
 	fn weight(&self, key: &HnswCacheKey, val: &HnswCacheValue) -> u64 {
 		match (key, val) {
 			(HnswCacheKey::Vector(key), HnswCacheValue::Vector(val)) => {
-				// Calculate total memory: vector (including Arc + hash) + TableId + IndexId.
+				// Calculate total memory: vector or code (including Arc) + TableId + IndexId.
 				(val.mem_size() + std::mem::size_of_val(&key.0) + std::mem::size_of_val(&key.1))
 					as u64
 			}
@@ -227,7 +227,7 @@ impl VectorCache {
 		table_id: TableId,
 		index_id: IndexId,
 		element_id: ElementId,
-		vector: SharedVector,
+		vector: ElementVector,
 	) {
 		// Update indexes tracking first, before inserting into cache.
 		// This prevents a race condition where eviction could occur immediately after
@@ -245,7 +245,7 @@ impl VectorCache {
 		table_id: TableId,
 		index_id: IndexId,
 		element_id: ElementId,
-	) -> Option<SharedVector> {
+	) -> Option<ElementVector> {
 		let key = HnswCacheKey::Vector((namespace_id, database_id, table_id, index_id, element_id));
 		match self.0.cache.get(&key) {
 			Some(HnswCacheValue::Vector(vector)) => Some(vector),
@@ -478,7 +478,7 @@ mod tests {
 	use ndarray::Array1;
 
 	use super::*;
-	use crate::idx::trees::vector::Vector;
+	use crate::idx::trees::vector::{SharedVector, Vector};
 
 	/// Test that cache eviction works correctly within an async runtime.
 	///
@@ -501,7 +501,14 @@ mod tests {
 		let vector = Vector::F32(Array1::from_vec(vec![1.0, 2.0, 3.0, 4.0]));
 
 		cache
-			.insert(namespace_id, database_id, table_id, index_id, 7, SharedVector::from(vector))
+			.insert(
+				namespace_id,
+				database_id,
+				table_id,
+				index_id,
+				7,
+				SharedVector::from(vector).into(),
+			)
 			.await;
 		cache.insert_doc_set(index, 7, Ids64::Vec2([11, 12])).await;
 		cache.insert_doc_id(index, 11, Some(1), RecordIdKey::Number(99)).await;
@@ -528,7 +535,7 @@ mod tests {
 			let vector = Vector::F32(Array1::from_vec(data));
 			let shared = SharedVector::from(vector);
 
-			cache.insert(namespace_id, database_id, table_id, index_id, i, shared).await;
+			cache.insert(namespace_id, database_id, table_id, index_id, i, shared.into()).await;
 		}
 
 		assert!(cache.len(namespace_id, database_id, table_id, index_id).await < 100);
@@ -551,7 +558,14 @@ mod tests {
 
 		// Insert
 		cache
-			.insert(namespace_id, database_id, table_id, index_id, element_id, shared.clone())
+			.insert(
+				namespace_id,
+				database_id,
+				table_id,
+				index_id,
+				element_id,
+				shared.clone().into(),
+			)
 			.await;
 		assert!(cache.contains(namespace_id, database_id, table_id, index_id, element_id).await);
 		assert_eq!(cache.len(namespace_id, database_id, table_id, index_id).await, 1);
@@ -559,7 +573,7 @@ mod tests {
 		// Get
 		let retrieved = cache.get(namespace_id, database_id, table_id, index_id, element_id).await;
 		assert!(retrieved.is_some());
-		assert_eq!(retrieved.unwrap(), shared);
+		assert_eq!(retrieved.unwrap(), shared.into());
 		cache.insert_doc_set(index, element_id, Ids64::Vec2([7, 8])).await;
 		assert_eq!(cache.get_doc_set(index, element_id).await, Some(Ids64::Vec2([7, 8])));
 		let id = cache.insert_doc_id(index, 7, Some(3), RecordIdKey::Number(99)).await;
@@ -595,7 +609,7 @@ mod tests {
 			let data: Vec<f32> = vec![i as f32; 4];
 			let vector = Vector::F32(Array1::from_vec(data));
 			let shared = SharedVector::from(vector);
-			cache.insert(namespace_id, database_id, table_id, index_id, i, shared).await;
+			cache.insert(namespace_id, database_id, table_id, index_id, i, shared.into()).await;
 			cache.insert_doc_set(index, i, Ids64::One(i)).await;
 			cache.insert_doc_id(index, i, Some(1), RecordIdKey::Number(i as i64)).await;
 		}
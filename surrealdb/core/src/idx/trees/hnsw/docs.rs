@@ -52,6 +52,11 @@ impl HnswDocs {
 		})
 	}
 
+	/// Returns the number of documents holding a compact document ID.
+	pub(super) fn count(&self) -> u64 {
+		self.state.next_doc_id - self.state.available.len()
+	}
+
 	/// Looks up the internal doc ID for a given record key, if it exists.
 	///
 	/// This is a static method that reads directly from the key-value store,
@@ -1,16 +1,47 @@
-use anyhow::Result;
+use std::sync::Arc;
 
-use crate::catalog::{Distance, IndexId, TableId};
+use anyhow::{Result, bail};
+
+use crate::catalog::{Distance, IndexId, TableId, VectorQuantization};
+use crate::ctx::FrozenContext;
+use crate::err::Error;
 use crate::idx::IndexKeyBase;
+use crate::idx::planner::ScanDirection;
 use crate::idx::trees::hnsw::ElementId;
 use crate::idx::trees::hnsw::cache::VectorCache;
+use crate::idx::trees::quantization::{Codebook, QuantizedVector, Quantizer};
 use crate::idx::trees::vector::{SerializedVector, SharedVector, Vector};
-use crate::kvs::Transaction;
+use crate::kvs::{KVValue, Transaction};
+
+/// The representation of an element used to traverse the graph: its full
+/// vector, or its code when the index is quantized.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ElementVector {
+	Full(SharedVector),
+	Quantized(Arc<QuantizedVector>),
+}
+
+impl ElementVector {
+	pub(super) fn mem_size(&self) -> usize {
+		match self {
+			Self::Full(v) => v.mem_size(),
+			Self::Quantized(q) => q.mem_size() + std::mem::size_of::<Self>() + 16,
+		}
+	}
+}
+
+impl From<SharedVector> for ElementVector {
+	fn from(v: SharedVector) -> Self {
+		Self::Full(v)
+	}
+}
 
 /// Manages storage and retrieval of element vectors in the HNSW graph.
 ///
 /// Vectors are stored in the key-value store and cached in-memory via
 /// [`VectorCache`] for fast distance computations during graph traversal.
+/// When the index is quantized, the cache holds the codes of the vectors, and
+/// the full vectors are only read to re-rank the search results.
 pub(super) struct HnswElements {
 	/// The table this index belongs to.
 	table_id: TableId,
@@ -24,6 +55,12 @@ pub(super) struct HnswElements {
 	next_element_id: ElementId,
 	/// Distance metric for similarity computations.
 	dist: Distance,
+	/// The quantization of the index, if any.
+	quantization: Option<VectorQuantization>,
+	/// Encodes the vectors, once the codebook is trained for product quantization.
+	quantizer: Option<Arc<Quantizer>>,
+	/// The element count from which the codebook training is attempted again.
+	next_training_at: ElementId,
 }
 
 impl HnswElements {
@@ -32,6 +69,7 @@ impl HnswElements {
 		table_id: TableId,
 		ikb: IndexKeyBase,
 		dist: Distance,
+		quantization: Option<VectorQuantization>,
 		vector_cache: VectorCache,
 	) -> Self {
 		Self {
@@ -40,7 +78,10 @@ impl HnswElements {
 			ikb,
 			vector_cache,
 			next_element_id: 0,
+			quantizer: quantization.and_then(|q| Quantizer::new(&dist, q)),
 			dist,
+			quantization,
+			next_training_at: *crate::cnf::VECTOR_PQ_TRAINING_SIZE as ElementId,
 		}
 	}
 
@@ -73,70 +114,167 @@ impl HnswElements {
 			.await
 	}
 
-	/// Stores a vector in the key-value store and caches it. Returns the shared vector.
+	/// Returns `true` if the graph is traversed with codes rather than full vectors.
+	pub(super) fn is_quantized(&self) -> bool {
+		self.quantizer.is_some()
+	}
+
+	/// Returns the representation of a vector used to traverse the graph.
+	pub(super) fn point(&self, v: &SharedVector) -> ElementVector {
+		match &self.quantizer {
+			Some(q) => ElementVector::Quantized(Arc::new(q.encode(v))),
+			None => ElementVector::Full(v.clone()),
+		}
+	}
+
+	/// Stores a vector in the key-value store and caches it. Returns the point
+	/// used to traverse the graph.
 	pub(super) async fn insert(
 		&mut self,
 		tx: &Transaction,
 		id: ElementId,
 		vec: Vector,
 		ser_vec: &SerializedVector,
-	) -> Result<SharedVector> {
+	) -> Result<ElementVector> {
 		let key = self.ikb.new_he_key(id);
 		tx.set(&key, ser_vec).await?;
-		let pt: SharedVector = vec.into();
+		let pt = self.point(&vec.into());
+		self.cache_point(id, pt.clone()).await;
+		Ok(pt)
+	}
+
+	async fn cache_point(&self, e_id: ElementId, pt: ElementVector) {
 		self.vector_cache
-			.insert(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, id, pt.clone())
+			.insert(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, e_id, pt)
 			.await;
-		Ok(pt)
 	}
 
-	/// Retrieves a vector by element ID, checking the cache first then the key-value store.
+	async fn load_vector(&self, tx: &Transaction, e_id: ElementId) -> Result<Option<SharedVector>> {
+		let key = self.ikb.new_he_key(e_id);
+		Ok(tx.get(&key, None).await?.map(|v| Vector::from(v).into()))
+	}
+
+	/// Retrieves the point of an element, checking the cache first then the
+	/// key-value store.
+	pub(super) async fn get_point(
+		&self,
+		tx: &Transaction,
+		e_id: &ElementId,
+	) -> Result<Option<ElementVector>> {
+		let cached = self
+			.vector_cache
+			.get(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, *e_id)
+			.await;
+		let vec = match (cached, &self.quantizer) {
+			(Some(ElementVector::Full(v)), None) => return Ok(Some(ElementVector::Full(v))),
+			(Some(pt @ ElementVector::Quantized(_)), Some(_)) => return Ok(Some(pt)),
+			// Cached before the codebook was trained
+			(Some(ElementVector::Full(v)), Some(_)) => v,
+			_ => match self.load_vector(tx, *e_id).await? {
+				Some(v) => v,
+				None => return Ok(None),
+			},
+		};
+		let pt = self.point(&vec);
+		self.cache_point(*e_id, pt.clone()).await;
+		Ok(Some(pt))
+	}
+
+	/// Retrieves the full vector of an element. When the index is quantized,
+	/// the vector is read from the key-value store and not cached.
 	pub(super) async fn get_vector(
 		&self,
 		tx: &Transaction,
 		e_id: &ElementId,
 	) -> Result<Option<SharedVector>> {
-		if let Some(v) = self
+		if let Some(ElementVector::Full(v)) = self
 			.vector_cache
 			.get(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id, *e_id)
 			.await
 		{
 			return Ok(Some(v));
 		}
-		let key = self.ikb.new_he_key(*e_id);
-		match tx.get(&key, None).await? {
-			None => Ok(None),
-			Some(vec) => {
-				let vec = Vector::from(vec);
-				let vec: SharedVector = vec.into();
-				self.vector_cache
-					.insert(
-						self.ikb.ns(),
-						self.ikb.db(),
-						self.table_id,
-						self.index_id,
-						*e_id,
-						vec.clone(),
-					)
-					.await;
-				Ok(Some(vec))
-			}
+		let vec = self.load_vector(tx, *e_id).await?;
+		if let Some(v) = &vec
+			&& self.quantizer.is_none()
+		{
+			self.cache_point(*e_id, ElementVector::Full(v.clone())).await;
+		}
+		Ok(vec)
+	}
+
+	/// Computes the distance between two points using the configured distance
+	/// metric, or its approximation when they are quantized.
+	pub(super) fn distance(&self, a: &ElementVector, b: &ElementVector) -> f64 {
+		match (a, b, &self.quantizer) {
+			(ElementVector::Full(a), ElementVector::Full(b), _) => self.dist.calculate(a, b),
+			(ElementVector::Quantized(a), ElementVector::Quantized(b), Some(q)) => q.distance(a, b),
+			(ElementVector::Quantized(a), ElementVector::Full(b), Some(q))
+			| (ElementVector::Full(b), ElementVector::Quantized(a), Some(q)) => q.distance(a, &q.encode(b)),
+			_ => f64::INFINITY,
 		}
 	}
 
-	/// Computes the distance between two vectors using the configured distance metric.
-	pub(super) fn distance(&self, a: &SharedVector, b: &SharedVector) -> f64 {
+	/// Computes the exact distance between two full vectors.
+	pub(super) fn exact_distance(&self, a: &Vector, b: &Vector) -> f64 {
 		self.dist.calculate(a, b)
 	}
 
-	/// Computes the distance between a query vector and an element's stored vector.
+	/// Computes the distance between a query point and an element's point.
 	pub(super) async fn get_distance(
 		&self,
 		tx: &Transaction,
-		q: &SharedVector,
+		q: &ElementVector,
 		e_id: &ElementId,
 	) -> Result<Option<f64>> {
-		Ok(self.get_vector(tx, e_id).await?.map(|r| self.dist.calculate(&r, q)))
+		Ok(self.get_point(tx, e_id).await?.map(|r| self.distance(&r, q)))
+	}
+
+	/// Returns `true` if the index uses product quantization, its codebook is
+	/// not trained yet, and enough elements may have been inserted to train it.
+	pub(super) fn needs_training(&self) -> bool {
+		matches!(self.quantization, Some(VectorQuantization::Product(_)))
+			&& self.quantizer.is_none()
+			&& self.next_element_id >= self.next_training_at
+	}
+
+	/// Trains the product quantization codebook on the first vectors of the
+	/// index. The codebook is held in memory only: each instance trains its
+	/// own, and the codes are derived from the stored full vectors.
+	pub(super) async fn train(&mut self, ctx: &FrozenContext, tx: &Transaction) -> Result<()> {
+		let Some(VectorQuantization::Product(subspaces)) = self.quantization else {
+			return Ok(());
+		};
+		let size = *crate::cnf::VECTOR_PQ_TRAINING_SIZE;
+		let mut samples = Vec::with_capacity(size);
+		let rng = self.ikb.new_he_range()?;
+		let mut cursor = tx.open_vals_cursor(rng, ScanDirection::Forward, 0, None).await?;
+		while samples.len() < size {
+			let batch = cursor.next_batch(crate::kvs::NORMAL_BATCH_SIZE).await?;
+			if batch.is_empty() {
+				break;
+			}
+			for (_, v) in &batch {
+				if ctx.is_done(Some(samples.len())).await? {
+					bail!(Error::QueryCancelled);
+				}
+				samples.push(Vector::from(SerializedVector::kv_decode_value(v, ())?));
+			}
+		}
+		drop(cursor);
+		if samples.len() < size {
+			// Elements were removed: wait for the missing ones to be inserted
+			self.next_training_at = self.next_element_id + (size - samples.len()) as ElementId;
+			return Ok(());
+		}
+		samples.truncate(size);
+		let codebook = Codebook::train(&samples, subspaces as usize)?;
+		self.quantizer = Some(Quantizer::with_codebook(&self.dist, codebook));
+		// Drop any code computed with the codebook of a previous instance
+		self.vector_cache
+			.remove_index(self.ikb.ns(), self.ikb.db(), self.table_id, self.index_id)
+			.await;
+		Ok(())
 	}
 
 	/// Removes an element's vector from both the cache and the key-value store.
@@ -2,10 +2,10 @@ use anyhow::Result;
 
 use crate::catalog::HnswParams;
 use crate::idx::trees::dynamicset::DynamicSet;
+use crate::idx::trees::hnsw::elements::ElementVector;
 use crate::idx::trees::hnsw::layer::HnswLayer;
 use crate::idx::trees::hnsw::{ElementId, HnswElements};
 use crate::idx::trees::knn::DoublePriorityQueue;
-use crate::idx::trees::vector::SharedVector;
 use crate::kvs::Transaction;
 
 #[derive(Debug)]
@@ -40,7 +40,7 @@ impl Heuristic {
 		elements: &HnswElements,
 		layer: &HnswLayer<S>,
 		q_id: ElementId,
-		q_pt: &SharedVector,
+		q_pt: &ElementVector,
 		c: DoublePriorityQueue,
 		ignore: Option<ElementId>,
 		res: &mut S,
@@ -120,7 +120,7 @@ impl Heuristic {
 		elements: &HnswElements,
 		layer: &HnswLayer<S>,
 		q_id: ElementId,
-		q_pt: &SharedVector,
+		q_pt: &ElementVector,
 		c: &mut DoublePriorityQueue,
 		ignore: Option<ElementId>,
 	) -> Result<()>
@@ -160,7 +160,7 @@ impl Heuristic {
 		elements: &HnswElements,
 		layer: &HnswLayer<S>,
 		q_id: ElementId,
-		q_pt: &SharedVector,
+		q_pt: &ElementVector,
 		mut c: DoublePriorityQueue,
 		ignore: Option<ElementId>,
 		res: &mut S,
@@ -178,7 +178,7 @@ impl Heuristic {
 		elements: &HnswElements,
 		layer: &HnswLayer<S>,
 		q_id: ElementId,
-		q_pt: &SharedVector,
+		q_pt: &ElementVector,
 		mut c: DoublePriorityQueue,
 		ignore: Option<ElementId>,
 		res: &mut S,
@@ -200,7 +200,7 @@ impl Heuristic {
 	where
 		S: DynamicSet,
 	{
-		if let Some(current_vec) = elements.get_vector(tx, &e_id).await? {
+		if let Some(current_vec) = elements.get_point(tx, &e_id).await? {
 			for r_id in r.iter() {
 				if let Some(r_dist) = elements.get_distance(tx, &current_vec, r_id).await?
 					&& e_dist > r_dist
@@ -546,6 +546,12 @@ impl HnswIndex {
 		Ok(())
 	}
 
+	/// Returns the number of documents indexed in the graph. Documents only
+	/// held by pending updates are not counted, so this is an estimate.
+	pub(crate) async fn indexed_count(&self, tx: &Transaction) -> Result<u64> {
+		Ok(HnswDocs::new(tx, self.ikb.clone()).await?.count())
+	}
+
 	/// Performs a k-nearest neighbor search, combining pending and committed results.
 	///
 	/// HNSW pending updates remain on the hot write path, so lookup scans them
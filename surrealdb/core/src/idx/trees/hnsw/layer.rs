@@ -11,12 +11,12 @@ use crate::idx::IndexKeyBase;
 use crate::idx::planner::ScanDirection;
 use crate::idx::trees::dynamicset::DynamicSet;
 use crate::idx::trees::graph::UndirectedGraph;
+use crate::idx::trees::hnsw::elements::ElementVector;
 use crate::idx::trees::hnsw::filter::HnswTruthyDocumentFilter;
 use crate::idx::trees::hnsw::heuristic::Heuristic;
 use crate::idx::trees::hnsw::index::HnswContext;
 use crate::idx::trees::hnsw::{ElementId, HnswElements, HnswSearch, VectorId};
 use crate::idx::trees::knn::{DoublePriorityQueue, Ids64};
-use crate::idx::trees::vector::SharedVector;
 use crate::key::index::hn::HnswNode;
 use crate::kvs::Transaction;
 
@@ -76,7 +76,7 @@ where
 		&self,
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
-		pt: &SharedVector,
+		pt: &ElementVector,
 		ep_dist: f64,
 		ep_id: ElementId,
 		ef: usize,
@@ -86,8 +86,9 @@ where
 		let candidates = DoublePriorityQueue::from(ep_dist, ep_id);
 		let w = if pending_docs.is_some() {
 			let mut w = DoublePriorityQueue::default();
-			if let Some(ep_pt) = elements.get_vector(&ctx.tx, &ep_id).await?
-				&& !Self::are_all_docs_in_pending(ctx, ep_id, &ep_pt, pending_docs).await?
+			if let Some(ep_pt) = elements.get_point(&ctx.tx, &ep_id).await?
+				&& !Self::are_all_docs_in_pending(ctx, elements, ep_id, &ep_pt, pending_docs)
+					.await?
 			{
 				w.push(ep_dist, ep_id);
 			}
@@ -102,7 +103,7 @@ where
 		&self,
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
-		pt: &SharedVector,
+		pt: &ElementVector,
 		ignore_id: ElementId,
 		ef: usize,
 	) -> Result<Option<ElementId>> {
@@ -123,6 +124,7 @@ where
 		stk: &mut Stk,
 		elements: &HnswElements,
 		search: &HnswSearch,
+		q: &ElementVector,
 		ep_dist: f64,
 		ep_id: ElementId,
 		filter: &mut HnswTruthyDocumentFilter<'_>,
@@ -134,9 +136,10 @@ where
 		Self::add_if_truthy(
 			ctx,
 			stk,
+			elements,
 			search.ef,
 			&mut w,
-			&search.pt,
+			&ElementVector::Full(search.pt.clone()),
 			ep_dist,
 			ep_id,
 			filter,
@@ -148,6 +151,7 @@ where
 			stk,
 			elements,
 			search,
+			q,
 			candidates,
 			visited,
 			w,
@@ -161,7 +165,7 @@ where
 		&self,
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
-		pt: &SharedVector,
+		pt: &ElementVector,
 		candidates: DoublePriorityQueue,
 		ef: usize,
 	) -> Result<DoublePriorityQueue> {
@@ -174,7 +178,7 @@ where
 		&self,
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
-		pt: &SharedVector,
+		pt: &ElementVector,
 		ignore_ids: Vec<ElementId>,
 		efc: usize,
 	) -> Result<DoublePriorityQueue> {
@@ -194,7 +198,7 @@ where
 		&self,
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
-		q: &SharedVector,
+		q: &ElementVector,
 		mut candidates: DoublePriorityQueue, // set of candidates
 		mut visited: HashSet<ElementId>,     // set of visited elements
 		mut w: DoublePriorityQueue,
@@ -212,10 +216,17 @@ where
 					if !visited.insert(e_id) {
 						continue;
 					}
-					if let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await? {
+					if let Some(e_pt) = elements.get_point(&ctx.tx, &e_id).await? {
 						let e_dist = elements.distance(&e_pt, q);
 						if e_dist < fq_dist || w.len() < ef {
-							if Self::are_all_docs_in_pending(ctx, e_id, &e_pt, pending_docs).await?
+							if Self::are_all_docs_in_pending(
+								ctx,
+								elements,
+								e_id,
+								&e_pt,
+								pending_docs,
+							)
+							.await?
 							{
 								continue;
 							}
@@ -240,6 +251,7 @@ where
 		stk: &mut Stk,
 		elements: &HnswElements,
 		search: &HnswSearch,
+		q: &ElementVector,
 		mut candidates: DoublePriorityQueue,
 		mut visited: HashSet<ElementId>,
 		mut w: DoublePriorityQueue,
@@ -262,6 +274,7 @@ where
 					ctx,
 					elements,
 					search,
+					q,
 					neighbourhood,
 					&visited,
 					w.len(),
@@ -275,13 +288,14 @@ where
 					if !visited.insert(e_id) {
 						continue;
 					}
-					if let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await? {
-						let e_dist = elements.distance(&e_pt, &search.pt);
+					if let Some(e_pt) = elements.get_point(&ctx.tx, &e_id).await? {
+						let e_dist = elements.distance(&e_pt, q);
 						if e_dist < f_dist || w.len() < search.ef {
 							candidates.push(e_dist, e_id);
 							if Self::add_if_truthy(
 								ctx,
 								stk,
+								elements,
 								search.ef,
 								&mut w,
 								&e_pt,
@@ -326,6 +340,7 @@ where
 		ctx: &HnswContext<'_>,
 		elements: &HnswElements,
 		search: &HnswSearch,
+		q: &ElementVector,
 		neighbourhood: &S,
 		visited: &HashSet<ElementId>,
 		w_len: usize,
@@ -338,17 +353,17 @@ where
 			if visited.contains(&e_id) {
 				continue;
 			}
-			let Some(e_pt) = elements.get_vector(&ctx.tx, &e_id).await? else {
+			let Some(e_pt) = elements.get_point(&ctx.tx, &e_id).await? else {
 				continue;
 			};
-			let e_dist = elements.distance(&e_pt, &search.pt);
+			let e_dist = elements.distance(&e_pt, q);
 			// Same gate as the evaluation loop, but against the f_dist / w.len()
 			// captured at the start of this neighbourhood — the loosest form (see
 			// the method doc), so the collected set is a superset of the loop's.
 			if !(e_dist < f_dist || w_len < search.ef) {
 				continue;
 			}
-			let Some(docs) = ctx.vec_docs.get_docs_by_element(&ctx.tx, e_id, &e_pt).await? else {
+			let Some(docs) = Self::get_docs(ctx, elements, e_id, &e_pt).await? else {
 				continue;
 			};
 			if let Some(pending_docs) = pending_docs
@@ -367,15 +382,16 @@ where
 	pub(super) async fn add_if_truthy(
 		ctx: &HnswContext<'_>,
 		stk: &mut Stk,
+		elements: &HnswElements,
 		efc: usize,
 		w: &mut DoublePriorityQueue,
-		e_pt: &SharedVector,
+		e_pt: &ElementVector,
 		e_dist: f64,
 		e_id: ElementId,
 		filter: &mut HnswTruthyDocumentFilter<'_>,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<bool> {
-		if let Some(docs) = ctx.vec_docs.get_docs_by_element(&ctx.tx, e_id, e_pt).await? {
+		if let Some(docs) = Self::get_docs(ctx, elements, e_id, e_pt).await? {
 			if let Some(pending_docs) = pending_docs
 				// Check all these docs are currently updated the pending
 				&& Self::check_all_docs_in_pending(&docs, pending_docs)
@@ -406,10 +422,33 @@ where
 		true
 	}
 
+	/// Returns the documents of an element. On a doc-set cache miss, the
+	/// lookup requires the full vector, which is read when the point is a code.
+	async fn get_docs(
+		ctx: &HnswContext<'_>,
+		elements: &HnswElements,
+		e_id: ElementId,
+		e_pt: &ElementVector,
+	) -> Result<Option<Ids64>> {
+		match e_pt {
+			ElementVector::Full(v) => ctx.vec_docs.get_docs_by_element(&ctx.tx, e_id, v).await,
+			ElementVector::Quantized(_) => {
+				if let Some(docs) = ctx.vec_docs.get_cached_doc_set(e_id).await {
+					return Ok(Some(docs));
+				}
+				match elements.get_vector(&ctx.tx, &e_id).await? {
+					Some(v) => ctx.vec_docs.get_docs_by_element(&ctx.tx, e_id, &v).await,
+					None => Ok(None),
+				}
+			}
+		}
+	}
+
 	async fn are_all_docs_in_pending(
 		search_ctx: &HnswContext<'_>,
+		elements: &HnswElements,
 		e_id: ElementId,
-		e_pt: &SharedVector,
+		e_pt: &ElementVector,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<bool> {
 		let Some(pending_docs) = pending_docs else {
@@ -418,9 +457,7 @@ where
 		if pending_docs.is_empty() {
 			return Ok(false);
 		}
-		if let Some(docs) =
-			search_ctx.vec_docs.get_docs_by_element(&search_ctx.tx, e_id, e_pt).await?
-		{
+		if let Some(docs) = Self::get_docs(search_ctx, elements, e_id, e_pt).await? {
 			for doc_id in docs.iter() {
 				if !pending_docs.contains(doc_id) {
 					return Ok(false);
@@ -438,7 +475,7 @@ where
 		elements: &HnswElements,
 		heuristic: &Heuristic,
 		efc: usize,
-		(q_id, q_pt): (ElementId, &SharedVector),
+		(q_id, q_pt): (ElementId, &ElementVector),
 		mut eps: DoublePriorityQueue,
 	) -> Result<DoublePriorityQueue> {
 		let w;
@@ -454,7 +491,7 @@ where
 		for e_id in &neighbors {
 			if let Some(e_conn) = self.graph.get_edges(*e_id) {
 				if e_conn.len() > self.m_max
-					&& let Some(e_pt) = elements.get_vector(&ctx.tx, e_id).await?
+					&& let Some(e_pt) = elements.get_point(&ctx.tx, e_id).await?
 				{
 					let e_c = self.build_priority_list(&ctx.tx, elements, *e_id, e_conn).await?;
 					let mut e_new_conn = self.graph.new_edges();
@@ -486,9 +523,9 @@ where
 		neighbors: &S,
 	) -> Result<DoublePriorityQueue> {
 		let mut w = DoublePriorityQueue::default();
-		if let Some(e_pt) = elements.get_vector(tx, &e_id).await? {
+		if let Some(e_pt) = elements.get_point(tx, &e_id).await? {
 			for n_id in neighbors.iter() {
-				if let Some(n_pt) = elements.get_vector(tx, n_id).await? {
+				if let Some(n_pt) = elements.get_point(tx, n_id).await? {
 					let dist = elements.distance(&e_pt, &n_pt);
 					w.push(dist, *n_id);
 				}
@@ -509,7 +546,7 @@ where
 		if let Some(f_ids) = self.graph.remove_node_and_bidirectional_edges(e_id) {
 			let mut changed_nodes = Vec::with_capacity(f_ids.len());
 			for &q_id in f_ids.iter() {
-				if let Some(q_pt) = elements.get_vector(&ctx.tx, &q_id).await? {
+				if let Some(q_pt) = elements.get_point(&ctx.tx, &q_id).await? {
 					let c = self
 						.search_multi_with_ignore(ctx, elements, &q_pt, vec![q_id, e_id], efc)
 						.await?;
@@ -23,7 +23,7 @@ use crate::idx::IndexKeyBase;
 use crate::idx::seqdocids::DocId;
 use crate::idx::trees::dynamicset::DynamicSet;
 use crate::idx::trees::hnsw::cache::VectorCache;
-use crate::idx::trees::hnsw::elements::HnswElements;
+use crate::idx::trees::hnsw::elements::{ElementVector, HnswElements};
 use crate::idx::trees::hnsw::filter::HnswTruthyDocumentFilter;
 use crate::idx::trees::hnsw::heuristic::Heuristic;
 use crate::idx::trees::hnsw::index::HnswContext;
@@ -191,7 +191,13 @@ where
 			ml: p.ml.to_float(),
 			layer0: HnswLayer::new(ikb.clone(), 0, m0),
 			layers: Vec::default(),
-			elements: HnswElements::new(table_id, ikb.clone(), p.distance.clone(), vector_cache),
+			elements: HnswElements::new(
+				table_id,
+				ikb.clone(),
+				p.distance.clone(),
+				p.quantization,
+				vector_cache,
+			),
 			// A fixed seed (via SURREAL_HNSW_BUILD_SEED) makes graph construction
 			// deterministic so search benchmarks are reproducible across runs;
 			// unset, the RNG is seeded from entropy as before.
@@ -235,6 +241,9 @@ where
 		if st.next_element_id != self.elements.next_element_id() {
 			return Ok(true);
 		}
+		if self.elements.needs_training() {
+			return Ok(true);
+		}
 		Ok(false)
 	}
 
@@ -279,6 +288,10 @@ where
 		if migrated {
 			self.save_state(&tx).await?;
 		}
+		// Train the product quantization codebook once enough vectors are indexed
+		if self.elements.needs_training() {
+			self.elements.train(ctx, &tx).await?;
+		}
 		Ok(())
 	}
 
@@ -357,7 +370,7 @@ where
 		&mut self,
 		ctx: &HnswContext<'_>,
 		q_id: ElementId,
-		q_pt: &SharedVector,
+		q_pt: &ElementVector,
 		q_level: usize,
 		mut ep_id: ElementId,
 		top_up_layers: usize,
@@ -458,7 +471,7 @@ where
 		let mut removed = false;
 
 		// Do we have the vector?
-		if let Some(e_pt) = self.elements.get_vector(&ctx.tx, &e_id).await? {
+		if let Some(e_pt) = self.elements.get_point(&ctx.tx, &e_id).await? {
 			// Check if we are deleted the current enter_point
 			let mut new_enter_point = if Some(e_id) == self.state.enter_point {
 				None
@@ -521,20 +534,13 @@ where
 		search: &HnswSearch,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<Vec<(f64, ElementId)>> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &search.pt, pending_docs).await? {
+		let q = self.elements.point(&search.pt);
+		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &q, pending_docs).await? {
 			let w = self
 				.layer0
-				.search_single(
-					ctx,
-					&self.elements,
-					&search.pt,
-					ep_dist,
-					ep_id,
-					search.ef,
-					pending_docs,
-				)
+				.search_single(ctx, &self.elements, &q, ep_dist, ep_id, search.ef, pending_docs)
 				.await?;
-			Ok(w.to_vec_limit(search.k))
+			self.rerank(&ctx.tx, search, w).await
 		} else {
 			Ok(vec![])
 		}
@@ -552,8 +558,9 @@ where
 		filter: &mut HnswTruthyDocumentFilter<'_>,
 		pending_docs: Option<&RoaringTreemap>,
 	) -> Result<Vec<(f64, ElementId)>> {
-		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &search.pt, pending_docs).await?
-			&& self.elements.get_vector(&ctx.tx, &ep_id).await?.is_some()
+		let q = self.elements.point(&search.pt);
+		if let Some((ep_dist, ep_id)) = self.search_ep(ctx, &q, pending_docs).await?
+			&& self.elements.get_point(&ctx.tx, &ep_id).await?.is_some()
 		{
 			let w = self
 				.layer0
@@ -562,17 +569,39 @@ where
 					stk,
 					&self.elements,
 					search,
+					&q,
 					ep_dist,
 					ep_id,
 					filter,
 					pending_docs,
 				)
 				.await?;
-			return Ok(w.to_vec_limit(search.k));
+			return self.rerank(&ctx.tx, search, w).await;
 		}
 		Ok(vec![])
 	}
 
+	/// Returns the `k` closest candidates. When the graph was traversed with
+	/// codes, the candidates are first re-ranked with the exact distances to
+	/// their full vectors.
+	async fn rerank(
+		&self,
+		tx: &Transaction,
+		search: &HnswSearch,
+		w: DoublePriorityQueue,
+	) -> Result<Vec<(f64, ElementId)>> {
+		if !self.elements.is_quantized() {
+			return Ok(w.to_vec_limit(search.k));
+		}
+		let mut res = DoublePriorityQueue::default();
+		for (_, e_id) in w.to_vec() {
+			if let Some(v) = self.elements.get_vector(tx, &e_id).await? {
+				res.push(self.elements.exact_distance(&v, &search.pt), e_id);
+			}
+		}
+		Ok(res.to_vec_limit(search.k))
+	}
+
 	/// Finds the best entry point for a search by traversing the upper layers.
 	///
 	/// Starting from the graph's entry point, greedily descends through the upper
@@ -580,7 +609,7 @@ where
 	async fn search_ep(
 		&self,
 		ctx: &HnswContext<'_>,
-		pt: &SharedVector,
+		pt: &ElementVector,
 		pending_doc: Option<&RoaringTreemap>,
 	) -> Result<Option<(f64, ElementId)>> {
 		if let Some(mut ep_id) = self.state.enter_point {
@@ -644,7 +673,7 @@ mod tests {
 	use crate::catalog::providers::{CatalogProvider, TableProvider};
 	use crate::catalog::{
 		DatabaseId, Distance, HnswParams, IndexId, NamespaceId, TableDefinition, TableId,
-		VectorType,
+		VectorQuantization, VectorType,
 	};
 	use crate::ctx::{Context, FrozenContext};
 	use crate::dbs::Session;
@@ -800,6 +829,7 @@ mod tests {
 			extend_candidates,
 			keep_pruned_connections,
 			use_hashed_vector,
+			quantization: None,
 		}
 	}
 
@@ -863,6 +893,20 @@ mod tests {
 		Ok(())
 	}
 
+	#[test(tokio::test(flavor = "multi_thread"))]
+	async fn test_hnsw_quantized() -> Result<()> {
+		for q in [VectorQuantization::Scalar, VectorQuantization::Binary] {
+			for dist in [Distance::Euclidean, Distance::Cosine] {
+				for vt in [VectorType::F32, VectorType::I16] {
+					let mut p = new_params(8, vt, dist.clone(), 24, 500, false, false, false);
+					p.quantization = Some(q);
+					test_hnsw(30, p).await;
+				}
+			}
+		}
+		Ok(())
+	}
+
 	#[test(tokio::test(flavor = "multi_thread"))]
 	async fn test_hnsw_inner_product_smoke() -> Result<()> {
 		let ds = Datastore::new("memory").await?;
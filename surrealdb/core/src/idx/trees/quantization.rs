@@ -0,0 +1,343 @@
+//! Compressed vector representations used to traverse ANN graphs.
+//!
+//! A [`Quantizer`] encodes full vectors into [`QuantizedVector`]s, which are
+//! smaller to cache and cheaper to compare. Distances between codes are only
+//! approximations: the indexes re-rank their candidates with the full vectors
+//! before returning results.
+//!
+//! - Scalar quantization stores one signed byte per dimension, scaled by the
+//!   largest absolute component of the vector.
+//! - Binary quantization stores the sign of each dimension as one bit, compared
+//!   with the Hamming distance.
+//! - Product quantization splits the vector into subspaces and stores, for each
+//!   of them, the index of the nearest centroid of a trained [`Codebook`].
+
+use std::sync::Arc;
+
+use anyhow::{Result, ensure};
+use ndarray::Array1;
+
+use crate::catalog::{Distance, VectorQuantization};
+use crate::err::Error;
+use crate::idx::trees::vector::Vector;
+
+/// Maximum number of centroids per subspace, so a code fits in a byte.
+const PQ_CENTROIDS: usize = 256;
+/// Number of k-means iterations used to train a codebook.
+const PQ_TRAINING_ITERATIONS: usize = 10;
+
+/// The encoded form of a vector.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum QuantizedVector {
+	/// One signed byte per dimension; the component `i` is `codes[i] * scale`.
+	Scalar {
+		scale: f32,
+		codes: Box<[i8]>,
+	},
+	/// One bit per dimension, set when the component is positive.
+	Binary(Box<[u64]>),
+	/// One centroid index per subspace.
+	Product(Box<[u8]>),
+}
+
+impl QuantizedVector {
+	pub(crate) fn mem_size(&self) -> usize {
+		let s = match self {
+			Self::Scalar {
+				codes,
+				..
+			} => codes.len(),
+			Self::Binary(bits) => bits.len() * std::mem::size_of::<u64>(),
+			Self::Product(codes) => codes.len(),
+		};
+		std::mem::size_of::<Self>() + s
+	}
+}
+
+#[derive(Debug)]
+enum QuantizerKind {
+	Scalar,
+	Binary,
+	Product(Codebook),
+}
+
+/// Encodes vectors and computes the distance between their codes.
+#[derive(Debug)]
+pub(crate) struct Quantizer {
+	dist: Distance,
+	kind: QuantizerKind,
+}
+
+impl Quantizer {
+	/// Returns the quantizer of an index, or `None` when it requires a
+	/// codebook (product quantization) that is not trained yet.
+	pub(crate) fn new(dist: &Distance, q: VectorQuantization) -> Option<Arc<Self>> {
+		let kind = match q {
+			VectorQuantization::Scalar => QuantizerKind::Scalar,
+			VectorQuantization::Binary => QuantizerKind::Binary,
+			VectorQuantization::Product(_) => return None,
+		};
+		Some(Arc::new(Self {
+			dist: dist.clone(),
+			kind,
+		}))
+	}
+
+	/// Returns a product quantizer using a trained codebook.
+	pub(crate) fn with_codebook(dist: &Distance, codebook: Codebook) -> Arc<Self> {
+		Arc::new(Self {
+			dist: dist.clone(),
+			kind: QuantizerKind::Product(codebook),
+		})
+	}
+
+	pub(crate) fn encode(&self, v: &Vector) -> QuantizedVector {
+		let values = to_f32(v);
+		match &self.kind {
+			QuantizerKind::Scalar => {
+				let max = values.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+				let scale = if max > 0.0 {
+					max / i8::MAX as f32
+				} else {
+					1.0
+				};
+				let codes = values.iter().map(|x| (x / scale).round() as i8).collect();
+				QuantizedVector::Scalar {
+					scale,
+					codes,
+				}
+			}
+			QuantizerKind::Binary => {
+				let mut bits = vec![0u64; values.len().div_ceil(64)];
+				for (i, x) in values.iter().enumerate() {
+					if *x > 0.0 {
+						bits[i / 64] |= 1 << (i % 64);
+					}
+				}
+				QuantizedVector::Binary(bits.into())
+			}
+			QuantizerKind::Product(codebook) => QuantizedVector::Product(codebook.encode(&values)),
+		}
+	}
+
+	/// Approximates the distance between two vectors from their codes.
+	pub(crate) fn distance(&self, a: &QuantizedVector, b: &QuantizedVector) -> f64 {
+		match (a, b) {
+			(QuantizedVector::Binary(a), QuantizedVector::Binary(b)) => {
+				a.iter().zip(b.iter()).map(|(a, b)| (a ^ b).count_ones()).sum::<u32>() as f64
+			}
+			(
+				QuantizedVector::Scalar {
+					scale: sa,
+					codes: a,
+				},
+				QuantizedVector::Scalar {
+					scale: sb,
+					codes: b,
+				},
+			) => self.decoded_distance(
+				a.iter().map(|x| *x as f32 * sa),
+				b.iter().map(|x| *x as f32 * sb),
+			),
+			(QuantizedVector::Product(a), QuantizedVector::Product(b)) => {
+				if let QuantizerKind::Product(codebook) = &self.kind {
+					self.decoded_distance(codebook.decode(a), codebook.decode(b))
+				} else {
+					f64::INFINITY
+				}
+			}
+			_ => f64::INFINITY,
+		}
+	}
+
+	/// Computes the distance between two decoded vectors. The usual ANN
+	/// metrics are computed on the fly, the other ones on materialized vectors.
+	fn decoded_distance(&self, a: impl Iterator<Item = f32>, b: impl Iterator<Item = f32>) -> f64 {
+		let pairs = a.zip(b).map(|(a, b)| (a as f64, b as f64));
+		match &self.dist {
+			Distance::Euclidean => pairs.map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt(),
+			Distance::Manhattan => pairs.map(|(a, b)| (a - b).abs()).sum(),
+			Distance::Chebyshev => pairs.map(|(a, b)| (a - b).abs()).fold(0.0, f64::max),
+			Distance::InnerProduct => -pairs.map(|(a, b)| a * b).sum::<f64>(),
+			Distance::CosineNormalized => 1.0 - pairs.map(|(a, b)| a * b).sum::<f64>(),
+			Distance::Cosine => {
+				let (dot, na, nb) = pairs.fold((0.0, 0.0, 0.0), |(d, na, nb), (a, b)| {
+					(d + a * b, na + a * a, nb + b * b)
+				});
+				1.0 - dot / (na.sqrt() * nb.sqrt())
+			}
+			dist => {
+				let (a, b): (Vec<f32>, Vec<f32>) = pairs.map(|(a, b)| (a as f32, b as f32)).unzip();
+				dist.calculate(&Vector::F32(Array1::from_vec(a)), &Vector::F32(Array1::from_vec(b)))
+			}
+		}
+	}
+}
+
+/// The centroids of a product quantizer, trained with k-means on a sample of
+/// the indexed vectors.
+#[derive(Debug)]
+pub(crate) struct Codebook {
+	/// Number of dimensions of a subspace.
+	sub_dim: usize,
+	/// Number of centroids of each subspace.
+	centroids: usize,
+	/// For each subspace, its centroids laid out contiguously.
+	subspaces: Vec<Box<[f32]>>,
+}
+
+impl Codebook {
+	/// Trains a codebook with `subspaces` subspaces on the given vectors.
+	pub(crate) fn train(samples: &[Vector], subspaces: usize) -> Result<Self> {
+		ensure!(!samples.is_empty(), Error::unreachable("No vectors to train the codebook"));
+		let samples: Vec<Vec<f32>> = samples.iter().map(to_f32).collect();
+		let dim = samples[0].len();
+		ensure!(
+			subspaces > 0 && dim.is_multiple_of(subspaces),
+			Error::unreachable("The number of subspaces must divide the dimension")
+		);
+		for s in &samples {
+			Vector::check_expected_dimension(s.len(), dim)?;
+		}
+		let sub_dim = dim / subspaces;
+		let centroids = samples.len().min(PQ_CENTROIDS);
+		let subspaces = (0..subspaces)
+			.map(|s| {
+				let points: Vec<&[f32]> =
+					samples.iter().map(|v| &v[s * sub_dim..(s + 1) * sub_dim]).collect();
+				Self::kmeans(&points, sub_dim, centroids)
+			})
+			.collect();
+		Ok(Self {
+			sub_dim,
+			centroids,
+			subspaces,
+		})
+	}
+
+	/// Lloyd's algorithm, seeded with evenly spaced samples so the training is
+	/// deterministic. A centroid left without points keeps its position.
+	fn kmeans(points: &[&[f32]], sub_dim: usize, k: usize) -> Box<[f32]> {
+		let step = points.len() / k;
+		let mut centroids: Vec<f32> =
+			(0..k).flat_map(|i| points[i * step].iter().copied()).collect();
+		let mut sums = vec![0.0f64; k * sub_dim];
+		let mut counts = vec![0usize; k];
+		for _ in 0..PQ_TRAINING_ITERATIONS {
+			sums.fill(0.0);
+			counts.fill(0);
+			for p in points {
+				let c = Self::nearest(&centroids, sub_dim, p);
+				counts[c] += 1;
+				for (s, x) in sums[c * sub_dim..(c + 1) * sub_dim].iter_mut().zip(p.iter()) {
+					*s += *x as f64;
+				}
+			}
+			for (c, count) in counts.iter().enumerate() {
+				if *count > 0 {
+					for d in c * sub_dim..(c + 1) * sub_dim {
+						centroids[d] = (sums[d] / *count as f64) as f32;
+					}
+				}
+			}
+		}
+		centroids.into()
+	}
+
+	/// Returns the index of the centroid closest to `p` (squared L2).
+	fn nearest(centroids: &[f32], sub_dim: usize, p: &[f32]) -> usize {
+		let mut best = (0, f32::INFINITY);
+		for (i, c) in centroids.chunks_exact(sub_dim).enumerate() {
+			let d: f32 = c.iter().zip(p.iter()).map(|(c, p)| (c - p) * (c - p)).sum();
+			if d < best.1 {
+				best = (i, d);
+			}
+		}
+		best.0
+	}
+
+	fn encode(&self, values: &[f32]) -> Box<[u8]> {
+		self.subspaces
+			.iter()
+			.zip(values.chunks_exact(self.sub_dim))
+			.map(|(centroids, p)| Self::nearest(centroids, self.sub_dim, p) as u8)
+			.collect()
+	}
+
+	fn decode<'a>(&'a self, codes: &'a [u8]) -> impl Iterator<Item = f32> + 'a {
+		self.subspaces.iter().zip(codes.iter()).flat_map(|(centroids, c)| {
+			let c = (*c as usize).min(self.centroids - 1);
+			centroids[c * self.sub_dim..(c + 1) * self.sub_dim].iter().copied()
+		})
+	}
+}
+
+fn to_f32(v: &Vector) -> Vec<f32> {
+	match v {
+		Vector::F64(a) => a.iter().map(|x| *x as f32).collect(),
+		Vector::F16(a) => a.iter().map(|x| x.to_f32()).collect(),
+		Vector::F32(a) => a.to_vec(),
+		Vector::I64(a) => a.iter().map(|x| *x as f32).collect(),
+		Vector::I32(a) => a.iter().map(|x| *x as f32).collect(),
+		Vector::I16(a) => a.iter().map(|x| *x as f32).collect(),
+		Vector::I8(a) => a.iter().map(|x| *x as f32).collect(),
+		Vector::U8(a) => a.iter().map(|x| *x as f32).collect(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ndarray::Array1;
+
+	use super::{Codebook, QuantizedVector, Quantizer};
+	use crate::catalog::{Distance, VectorQuantization};
+	use crate::idx::trees::vector::Vector;
+
+	fn vec(v: &[f32]) -> Vector {
+		Vector::F32(Array1::from_vec(v.to_vec()))
+	}
+
+	#[test]
+	fn test_scalar() {
+		let q = Quantizer::new(&Distance::Euclidean, VectorQuantization::Scalar).unwrap();
+		let a = q.encode(&vec(&[1.0, -2.0, 0.5, 0.0]));
+		let QuantizedVector::Scalar {
+			codes,
+			..
+		} = &a
+		else {
+			panic!()
+		};
+		assert_eq!(codes.as_ref(), &[64, -127, 32, 0]);
+		let b = q.encode(&vec(&[1.0, -2.0, 0.5, 3.0]));
+		assert!((q.distance(&a, &b) - 3.0).abs() < 0.05);
+		assert_eq!(q.distance(&a, &a), 0.0);
+	}
+
+	#[test]
+	fn test_binary() {
+		let q = Quantizer::new(&Distance::Cosine, VectorQuantization::Binary).unwrap();
+		let a = q.encode(&vec(&[1.0, -2.0, 0.5, 0.0]));
+		assert_eq!(a, QuantizedVector::Binary([0b101].into()));
+		let b = q.encode(&vec(&[-1.0, -2.0, 0.5, 3.0]));
+		assert_eq!(q.distance(&a, &b), 2.0);
+	}
+
+	#[test]
+	fn test_product() {
+		assert!(Quantizer::new(&Distance::Euclidean, VectorQuantization::Product(2)).is_none());
+		let samples: Vec<Vector> =
+			(0..20).map(|i| vec(&[(i % 2) as f32, 0.0, 10.0 * (i % 3) as f32, 1.0])).collect();
+		let codebook = Codebook::train(&samples, 2).unwrap();
+		assert_eq!(codebook.centroids, 20);
+		let q = Quantizer::with_codebook(&Distance::Euclidean, codebook);
+		// Every sample matches a centroid exactly
+		for s in &samples {
+			assert_eq!(q.distance(&q.encode(s), &q.encode(s)), 0.0);
+		}
+		let a = q.encode(&vec(&[0.0, 0.0, 0.0, 1.0]));
+		let b = q.encode(&vec(&[1.0, 0.0, 20.0, 1.0]));
+		assert!((q.distance(&a, &b) - 401f64.sqrt()).abs() < 1e-6);
+		assert!(Codebook::train(&samples, 3).is_err());
+	}
+}
@@ -2,6 +2,7 @@
 pub(crate) mod diskann;
 pub(crate) mod hnsw;
 mod mapper;
+mod wordlist;
 
 use std::sync::Arc;
 
@@ -21,6 +22,7 @@ use crate::idx::trees::hnsw::cache::VectorCache;
 use crate::idx::trees::store::diskann::{DiskAnnIndexes, SharedDiskAnnIndex};
 use crate::idx::trees::store::hnsw::{HnswIndexes, SharedHnswIndex};
 use crate::idx::trees::store::mapper::Mappers;
+use crate::idx::trees::store::wordlist::WordLists;
 
 #[derive(Clone)]
 pub struct IndexStores(Arc<Inner>);
@@ -33,6 +35,7 @@ struct Inner {
 	diskann_cache: DiskAnnCache,
 	hnsw_indexes: HnswIndexes,
 	mappers: Mappers,
+	word_lists: WordLists,
 	vector_cache: VectorCache,
 }
 
@@ -47,6 +50,7 @@ impl IndexStores {
 			diskann_cache: DiskAnnCache::new(diskann_cache_size),
 			hnsw_indexes: HnswIndexes::default(),
 			mappers: Mappers::default(),
+			word_lists: WordLists::default(),
 			vector_cache: VectorCache::new(hnsw_cache_size),
 		}))
 	}
@@ -131,6 +135,10 @@ impl IndexStores {
 		&self.0.mappers
 	}
 
+	pub(crate) fn word_lists(&self) -> &WordLists {
+		&self.0.word_lists
+	}
+
 	pub(crate) fn vector_cache(&self) -> &VectorCache {
 		&self.0.vector_cache
 	}
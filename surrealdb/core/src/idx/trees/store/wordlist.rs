@@ -0,0 +1,151 @@
+use ahash::HashSet;
+use anyhow::{Result, bail};
+use dashmap::DashMap;
+use surrealdb_types::ToSql;
+
+use crate::buc::store::ObjectKey;
+use crate::catalog::{self, DatabaseId, NamespaceId};
+use crate::ctx::FrozenContext;
+use crate::err::Error;
+use crate::expr::Filter;
+use crate::expr::filter::StopWords as StopWordsFilter;
+use crate::idx::ft::analyzer::stopwords::StopWords;
+use crate::idx::ft::analyzer::synonyms::Synonyms;
+use crate::val::File;
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+struct WordListKey {
+	ns: NamespaceId,
+	db: DatabaseId,
+	file: File,
+}
+
+impl WordListKey {
+	fn new(ns: NamespaceId, db: DatabaseId, file: &File) -> Self {
+		Self {
+			ns,
+			db,
+			file: file.clone(),
+		}
+	}
+}
+
+/// The stop words and synonyms files of the analyzers, read from their
+/// buckets and kept in memory.
+#[derive(Default)]
+pub(crate) struct WordLists {
+	stop_words: DashMap<WordListKey, StopWords>,
+	synonyms: DashMap<WordListKey, Synonyms>,
+}
+
+impl WordLists {
+	/// Loads the files of an analyzer in memory, replacing those already
+	/// loaded: a file is only read again when its analyzer is (re)defined.
+	pub(crate) async fn load(
+		&self,
+		ctx: &FrozenContext,
+		ns: NamespaceId,
+		db: DatabaseId,
+		az: &catalog::AnalyzerDefinition,
+	) -> Result<()> {
+		for f in az.filters.iter().flatten() {
+			match f {
+				Filter::StopWords(StopWordsFilter::File(file)) => {
+					let stop_words = StopWords::parse(&Self::read(ctx, ns, db, file).await?);
+					self.stop_words.insert(WordListKey::new(ns, db, file), stop_words);
+				}
+				Filter::Synonyms(file) => {
+					let synonyms = Synonyms::parse(&Self::read(ctx, ns, db, file).await?)?;
+					self.synonyms.insert(WordListKey::new(ns, db, file), synonyms);
+				}
+				_ => {}
+			}
+		}
+		Ok(())
+	}
+
+	/// Returns the stop words of a file, reading it if not yet in memory.
+	pub(in crate::idx) async fn get_stop_words(
+		&self,
+		ctx: &FrozenContext,
+		ns: NamespaceId,
+		db: DatabaseId,
+		file: &File,
+	) -> Result<StopWords> {
+		let key = WordListKey::new(ns, db, file);
+		if let Some(e) = self.stop_words.get(&key) {
+			return Ok(e.value().clone());
+		}
+		let stop_words = StopWords::parse(&Self::read(ctx, ns, db, file).await?);
+		self.stop_words.insert(key, stop_words.clone());
+		Ok(stop_words)
+	}
+
+	/// Returns the synonyms of a file, reading it if not yet in memory.
+	pub(in crate::idx) async fn get_synonyms(
+		&self,
+		ctx: &FrozenContext,
+		ns: NamespaceId,
+		db: DatabaseId,
+		file: &File,
+	) -> Result<Synonyms> {
+		let key = WordListKey::new(ns, db, file);
+		if let Some(e) = self.synonyms.get(&key) {
+			return Ok(e.value().clone());
+		}
+		let synonyms = Synonyms::parse(&Self::read(ctx, ns, db, file).await?)?;
+		self.synonyms.insert(key, synonyms.clone());
+		Ok(synonyms)
+	}
+
+	/// Reads a file from the bucket store. The bucket permissions are not
+	/// checked: the file is read on behalf of the analyzer, whose definition
+	/// requires editing the database.
+	async fn read(
+		ctx: &FrozenContext,
+		ns: NamespaceId,
+		db: DatabaseId,
+		file: &File,
+	) -> Result<String> {
+		let store = ctx.get_bucket_store(ns, db, &file.bucket).await?;
+		let Some(bytes) = store
+			.get(&ObjectKey::new(file.key.clone()))
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(file.bucket.clone(), e))?
+		else {
+			bail!(Error::AnalyzerError(format!("The file {} does not exist", file.to_sql())));
+		};
+		match String::from_utf8(bytes.to_vec()) {
+			Ok(s) => Ok(s),
+			Err(_) => {
+				bail!(Error::AnalyzerError(format!(
+					"The file {} is not valid UTF-8",
+					file.to_sql()
+				)))
+			}
+		}
+	}
+
+	/// Removes the files of a database no longer used by its analyzers.
+	pub(crate) fn cleanup(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		azs: &[catalog::AnalyzerDefinition],
+	) {
+		let mut used = HashSet::default();
+		for az in azs {
+			for f in az.filters.iter().flatten() {
+				match f {
+					Filter::StopWords(StopWordsFilter::File(file)) | Filter::Synonyms(file) => {
+						used.insert(WordListKey::new(ns, db, file));
+					}
+					_ => {}
+				}
+			}
+		}
+		let unused = |k: &WordListKey| k.ns == ns && k.db == db && !used.contains(k);
+		self.stop_words.retain(|k, _| !unused(k));
+		self.synonyms.retain(|k, _| !unused(k));
+	}
+}
@@ -24,6 +24,7 @@ use storekey::{BorrowDecode, BorrowReader, DecodeError, Encode, EncodeError, Wri
 use crate::catalog::{Distance, VectorType};
 use crate::err::Error;
 use crate::fnc::util::math::ToFloat;
+use crate::fnc::util::math::vector::spearman;
 use crate::kvs::KVValue;
 use crate::val::{Number, Value};
 
@@ -697,6 +698,31 @@ impl Vector {
 		}
 	}
 
+	#[inline]
+	fn spearman<T>(a: &Array1<T>, b: &Array1<T>) -> f64
+	where
+		T: ToFloat,
+	{
+		spearman(a.iter().map(ToFloat::to_float), b.iter().map(ToFloat::to_float))
+	}
+
+	fn spearman_similarity(&self, other: &Self) -> f64 {
+		if self.len() != other.len() {
+			return f64::NAN;
+		}
+		match (self, other) {
+			(Self::F64(a), Self::F64(b)) => Self::spearman(a, b),
+			(Self::F16(a), Self::F16(b)) => Self::spearman(a, b),
+			(Self::F32(a), Self::F32(b)) => Self::spearman(a, b),
+			(Self::I64(a), Self::I64(b)) => Self::spearman(a, b),
+			(Self::I32(a), Self::I32(b)) => Self::spearman(a, b),
+			(Self::I16(a), Self::I16(b)) => Self::spearman(a, b),
+			(Self::I8(a), Self::I8(b)) => Self::spearman(a, b),
+			(Self::U8(a), Self::U8(b)) => Self::spearman(a, b),
+			_ => f64::NAN,
+		}
+	}
+
 	fn inner_product_distance(&self, other: &Self) -> f64 {
 		match (self, other) {
 			(Self::F64(a), Self::F64(b)) => -Self::dot_product(a, b),
@@ -991,6 +1017,10 @@ impl Distance {
 			Distance::Manhattan => a.manhattan_distance(b),
 			Distance::Minkowski(order) => a.minkowski_distance(b, order.to_float()),
 			Distance::Pearson => a.pearson_similarity(b),
+			Distance::Spearman => a.spearman_similarity(b),
+			// HNSW and DiskANN indexes reject MAHALANOBIS, which is only computed
+			// by brute-force KNN through a `PreparedDistance`
+			Distance::Mahalanobis(_) => f64::NAN,
 		}
 	}
 }
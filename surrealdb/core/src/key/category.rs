@@ -37,6 +37,8 @@ pub enum Category {
 	IndexCompaction,
 	/// crate::key::root::rc                 /!rc{kind}{ns}{db}{tb}{ix}{expunge}{uuid}
 	Reclaim,
+	/// crate::key::root::re                 /!re
+	Reencryption,
 	/// crate::key::root::eq                 /!eq{ns}{db}{tb}{ev}{ts}{nid}
 	EventQueue,
 	///
@@ -116,6 +118,14 @@ pub enum Category {
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::cg                /*{ns}*{db}*{tb}!cg{cs}
+	TableConsumerGroup,
+	/// crate::key::table::cm                /*{ns}*{db}*{tb}!cm{cs}{member}
+	TableConsumerMember,
+	/// crate::key::table::co                /*{ns}*{db}*{tb}!co{cs}{partition}
+	TableConsumerOffset,
+	/// crate::key::table::cs                /*{ns}*{db}*{tb}!cs{cs}
+	TableConsumer,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
@@ -126,6 +136,14 @@ pub enum Category {
 	IndexDefinition,
 	/// crate::key::table::lq                /*{ns}*{db}*{tb}!lq{lq}
 	TableLiveQuery,
+	/// crate::key::table::sc                /*{ns}*{db}*{tb}!sc{sk}
+	TableSinkCursor,
+	/// crate::key::table::sk                /*{ns}*{db}*{tb}!sk{sk}
+	TableSink,
+	/// crate::key::table::sp                /*{ns}*{db}*{tb}!sp{sk}{vs}
+	TableSinkParked,
+	/// crate::key::table::te                /*{ns}*{db}*{tb}!te{secs}{nanos}{id}
+	TableExpiry,
 	///
 	/// ------------------------------
 	///
@@ -156,6 +174,8 @@ pub enum Category {
 	/// crate::key::index::tt
 	/// /*{ns}*{db}*{tb}+{ix}!td{term}{uuid}{uuid}
 	IndexTermDocuments,
+	/// crate::key::index::tf                /*{ns}*{db}*{tb}+{ix}!tf
+	IndexTermDictionary,
 	/// crate::key::index::he                /*{ns}*{db}*{tb}+{ix}!he{id}
 	IndexHnswElements,
 	/// crate::key::index::hd                /*{ns}*{db}*{tb}+{ix}!hd{id}
@@ -166,6 +186,8 @@ pub enum Category {
 	IndexHnswVec,
 	/// crate::key::index::hh                /*{ns}*{db}*{tb}+{ix}!hh{hash}
 	IndexHnswHashedVec,
+	/// crate::key::index::gh                /*{ns}*{db}*{tb}+{ix}!gh{cell}{id}
+	IndexSpatialCell,
 	/// crate::key::index::ia                /*{ns}*{db}*{tb}+{ix}!ia{id} (Previously - discarded by
 	/// #6856) crate::key::index::ig                /*{ns}*{db}*{tb}+{ix}!ig{id}
 	IndexAppendings,
@@ -260,11 +282,19 @@ impl Display for Category {
 			Self::DatabaseSequence => "DatabaseSequence",
 			Self::DatabaseConfig => "DatabaseConfig",
 			Self::TableRoot => "TableRoot",
+			Self::TableConsumerGroup => "TableConsumerGroup",
+			Self::TableConsumerMember => "TableConsumerMember",
+			Self::TableConsumerOffset => "TableConsumerOffset",
+			Self::TableConsumer => "TableConsumer",
 			Self::TableEvent => "TableEvent",
 			Self::TableField => "TableField",
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableSinkCursor => "TableSinkCursor",
+			Self::TableSink => "TableSink",
+			Self::TableSinkParked => "TableSinkParked",
+			Self::TableExpiry => "TableExpiry",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -281,6 +311,7 @@ impl Display for Category {
 			Self::IndexHnswThings => "IndexHnswThings",
 			Self::IndexHnswVec => "IndexHnswVec",
 			Self::IndexHnswHashedVec => "IndexHnswHashedVec",
+			Self::IndexSpatialCell => "IndexSpatialCell",
 			Self::IndexAppendings => "IndexAppendings",
 			Self::IndexPrimaryAppending => "IndexPrimaryAppending",
 			Self::Index => "Index",
@@ -297,8 +328,10 @@ impl Display for Category {
 			Self::IndexFullTextDocIdsSequenceState => "IndexFullTextDocIdsSequenceState",
 			Self::IndexFullTextDocCountAndLength => "IndexFullTextDocCountAndLength",
 			Self::IndexTermDocuments => "IndexTermDocuments",
+			Self::IndexTermDictionary => "IndexTermDictionary",
 			Self::IndexCompaction => "IndexCompaction",
 			Self::Reclaim => "Reclaim",
+			Self::Reencryption => "Reencryption",
 			Self::IndexCountState => "IndexCountState",
 			Self::IndexBuildState => "IndexBuildState",
 			Self::IndexBuildReservation => "IndexBuildReservation",
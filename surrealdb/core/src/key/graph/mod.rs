@@ -240,21 +240,52 @@ impl Graph<'_> {
 			table: g.ft.into_owned(),
 			key: g.fk.into_owned(),
 		};
-		let target = if reader.is_empty() {
-			None
-		} else {
-			let tt = <Cow<TableName> as BorrowDecode>::borrow_decode(&mut reader)?;
-			let tk = <Cow<RecordIdKey> as BorrowDecode>::borrow_decode(&mut reader)?;
-			Some(RecordId {
-				table: tt.into_owned(),
-				key: tk.into_owned(),
-			})
-		};
+		let target = Self::decode_target(&mut reader)?;
 		Ok(DecodedGraph {
 			edge,
 			target,
 		})
 	}
+
+	/// Decode a graph adjacency key together with the record the key is
+	/// stored under and the direction of the adjacency.
+	///
+	/// Accepts the same layouts as [`Self::decode_key`]. Used by offline
+	/// consistency checks, which need to know which record owns each key.
+	pub fn decode_key_with_origin(k: &[u8]) -> Result<(RecordId, Dir, DecodedGraph)> {
+		let mut reader = BorrowReader::new(k);
+		let g = <Graph as BorrowDecode>::borrow_decode(&mut reader)?;
+		let origin = RecordId {
+			table: g.tb.into_owned(),
+			key: g.id,
+		};
+		let edge = RecordId {
+			table: g.ft.into_owned(),
+			key: g.fk.into_owned(),
+		};
+		let target = Self::decode_target(&mut reader)?;
+		Ok((
+			origin,
+			g.eg,
+			DecodedGraph {
+				edge,
+				target,
+			},
+		))
+	}
+
+	/// Decode the optional pointer-key tail `(tt, tk)`.
+	fn decode_target(reader: &mut BorrowReader<'_>) -> Result<Option<RecordId>> {
+		if reader.is_empty() {
+			return Ok(None);
+		}
+		let tt = <Cow<TableName> as BorrowDecode>::borrow_decode(reader)?;
+		let tk = <Cow<RecordIdKey> as BorrowDecode>::borrow_decode(reader)?;
+		Ok(Some(RecordId {
+			table: tt.into_owned(),
+			key: tk.into_owned(),
+		}))
+	}
 }
 
 pub fn new<'a>(
@@ -0,0 +1,202 @@
+//! Stores the geohash cells of a spatial index
+//!
+//! Each indexed geometry is covered by a small set of geohash cells. Every
+//! cell of the cover produces one key, so that a record can be found from any
+//! of the cells its geometry overlaps.
+//!
+//! The key structure includes:
+//! - Namespace, database, table, and index identifiers
+//! - The geohash cell (a base32 string whose length is the cell level)
+//! - The record identifier
+//!
+//! Because geohash cells are hierarchical, every descendant of a cell shares
+//! the cell's string as a prefix. This lets a query scan all the finer cells
+//! of a region with a single range, see [`Gh::descendants_range`].
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{IndexFormat, RecordId, RecordIdKey, TableName};
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "IndexFormat")]
+pub(crate) struct Gh<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: Cow<'a, str>,
+	pub id: Cow<'a, RecordIdKey>,
+}
+
+impl KVKey for Gh<'_> {
+	type ValueType = RecordId;
+
+	fn encode_key(&self) -> Result<Vec<u8>> {
+		Ok(storekey::encode_vec_format::<IndexFormat, _>(self)
+			.map_err(|_| crate::err::Error::Unencodable)?)
+	}
+
+	fn value_context(&self) {}
+}
+
+impl Categorise for Gh<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexSpatialCell
+	}
+}
+
+impl<'a> Gh<'a> {
+	/// Creates the key linking a geohash cell to a record.
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+		id: &'a RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'h',
+			cell: Cow::Borrowed(cell),
+			id: Cow::Borrowed(id),
+		}
+	}
+
+	/// Creates a key range covering every record stored exactly under `cell`.
+	pub(crate) fn cell_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let prefix = GhCellPrefix::new(ns, db, tb, ix, cell).encode_key()?;
+		let mut beg = prefix.clone();
+		beg.push(0);
+		let mut end = prefix;
+		end.push(255);
+		Ok((beg, end))
+	}
+
+	/// Creates a key range covering every record stored under `cell` or under
+	/// any finer cell contained in `cell`.
+	pub(crate) fn descendants_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		cell: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = GhCellPrefix::new(ns, db, tb, ix, cell).encode_key()?;
+		// Remove the string terminator so that longer cells sharing this
+		// prefix fall within the range.
+		beg.pop();
+		let mut end = beg.clone();
+		end.push(255);
+		Ok((beg, end))
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct GhCellPrefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub cell: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(GhCellPrefix<'_> => RecordId);
+
+impl<'a> GhCellPrefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, ix: IndexId, cell: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b'g',
+			_g: b'h',
+			cell: Cow::Borrowed(cell),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_strand::Strand;
+
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String(Strand::new_static("testid"));
+		let val = Gh::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "u4pr", &id);
+		let enc = Gh::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!ghu4pr\0\x03testid\0",
+			"{}",
+			String::from_utf8_lossy(&enc)
+		);
+	}
+
+	#[test]
+	fn ranges() {
+		let tb = TableName::from("testtb");
+		let id = RecordIdKey::String(Strand::new_static("testid"));
+		let (ns, db, ix) = (NamespaceId(1), DatabaseId(2), IndexId(3));
+		let exact = Gh::new(ns, db, &tb, ix, "u4pr", &id).encode_key().unwrap();
+		let child = Gh::new(ns, db, &tb, ix, "u4pru", &id).encode_key().unwrap();
+		let sibling = Gh::new(ns, db, &tb, ix, "u4ps", &id).encode_key().unwrap();
+		// The exact-cell range only contains the cell itself
+		let (beg, end) = Gh::cell_range(ns, db, &tb, ix, "u4pr").unwrap();
+		assert!(beg <= exact && exact < end);
+		assert!(!(beg <= child && child < end));
+		// The descendants range contains the cell and its finer cells
+		let (beg, end) = Gh::descendants_range(ns, db, &tb, ix, "u4pr").unwrap();
+		assert!(beg <= exact && exact < end);
+		assert!(beg <= child && child < end);
+		assert!(!(beg <= sibling && sibling < end));
+	}
+}
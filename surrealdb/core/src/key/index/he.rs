@@ -1,12 +1,14 @@
 //! Stores Vector of an HNSW index
 use std::borrow::Cow;
+use std::ops::Range;
 
+use anyhow::Result;
 use storekey::{BorrowDecode, Encode};
 
 use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::idx::trees::hnsw::ElementId;
 use crate::idx::trees::vector::SerializedVector;
-use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::{KVKey, Key, impl_kv_key_storekey};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -53,12 +55,23 @@ impl<'a> He<'a> {
 			element_id,
 		}
 	}
+
+	/// Returns the range covering all element vectors of one HNSW index.
+	pub(crate) fn range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+	) -> Result<Range<Key>> {
+		let beg = Self::new(ns, db, tb, ix, 0).encode_key()?;
+		let end = Self::new(ns, db, tb, ix, u64::MAX).encode_key()?;
+		Ok(beg..end)
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::kvs::KVKey;
 
 	#[test]
 	fn key() {
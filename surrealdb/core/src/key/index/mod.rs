@@ -44,6 +44,7 @@ pub mod dv;
 pub mod dw;
 #[cfg(diskann)]
 pub mod dy;
+pub mod gh;
 pub mod hd;
 pub mod he;
 pub mod hg;
@@ -64,6 +65,7 @@ pub mod is;
 pub mod iu;
 pub mod iv;
 pub mod td;
+pub mod tf;
 pub mod tt;
 pub mod tv;
 
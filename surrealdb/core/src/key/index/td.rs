@@ -16,6 +16,7 @@
 
 use std::borrow::Cow;
 
+use anyhow::Result;
 use roaring::RoaringTreemap;
 use storekey::{BorrowDecode, Encode};
 
@@ -23,7 +24,7 @@ use crate::catalog::{DatabaseId, IndexId, NamespaceId};
 use crate::idx::ft::fulltext::TermDocument;
 use crate::idx::seqdocids::DocId;
 use crate::key::category::{Categorise, Category};
-use crate::kvs::impl_kv_key_storekey;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
 use crate::val::TableName;
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -76,6 +77,42 @@ impl<'a> TdRoot<'a> {
 			term: Cow::Borrowed(term),
 		}
 	}
+
+	/// Creates a key range covering the keys of every term starting with
+	/// `prefix`, both the compacted roots and the per-document keys.
+	pub(crate) fn prefix_range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		prefix: &'a str,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = Self::new(ns, db, tb, ix, prefix).encode_key()?;
+		// Drop the string terminator
+		beg.pop();
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	/// Creates the first key following every key of `term`, to skip to the
+	/// next term of a range.
+	pub(crate) fn after_term(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		term: &'a str,
+	) -> Result<Vec<u8>> {
+		let mut key = Self::new(ns, db, tb, ix, term).encode_key()?;
+		// Past the largest document id
+		key.extend([0xff; 9]);
+		Ok(key)
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<TdRoot<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
@@ -144,6 +181,10 @@ impl<'a> Td<'a> {
 			id,
 		}
 	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Td<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
 }
 
 #[cfg(test)]
@@ -159,6 +200,22 @@ mod tests {
 		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdterm\0");
 	}
 
+	#[test]
+	fn prefix_range() {
+		let tb = TableName::from("testtb");
+		let (beg, end) =
+			TdRoot::prefix_range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "te").unwrap();
+		assert_eq!(beg, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte");
+		assert_eq!(end, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\0\0\0\x03!tdte\xff");
+		let key = Td::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term", 129);
+		let key = Td::encode_key(&key).unwrap();
+		assert!(beg < key && key < end);
+		let after =
+			TdRoot::after_term(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "term").unwrap();
+		assert!(key < after && after < end);
+		assert_eq!(Td::decode_key(&key).unwrap().term, "term");
+	}
+
 	#[test]
 	fn key() {
 		let tb = TableName::from("testtb");
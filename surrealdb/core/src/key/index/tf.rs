@@ -0,0 +1,103 @@
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, IndexId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+/// A shard of the full-text term dictionary of a fuzzy index.
+///
+/// The value is an FST set of the compacted terms of the index starting with
+/// the shard, the first character of the terms. The `!tt` compaction only
+/// rebuilds the shards of the terms it adds or removes, and fuzzy queries run
+/// a Levenshtein automaton over the shards it can enter rather than looking up
+/// each query term exactly.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Tf<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	pub ix: IndexId,
+	_e: u8,
+	_f: u8,
+	_g: u8,
+	pub shard: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Tf<'_> => Vec<u8>);
+
+impl Categorise for Tf<'_> {
+	fn categorise(&self) -> Category {
+		Category::IndexTermDictionary
+	}
+}
+
+impl<'a> Tf<'a> {
+	pub(crate) fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+		shard: &'a str,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'+',
+			ix,
+			_e: b'!',
+			_f: b't',
+			_g: b'f',
+			shard: Cow::Borrowed(shard),
+		}
+	}
+
+	/// Creates the key range of every shard of the term dictionary.
+	pub(crate) fn range(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		ix: IndexId,
+	) -> Result<(Vec<u8>, Vec<u8>)> {
+		let mut beg = Self::new(ns, db, tb, ix, "").encode_key()?;
+		// Drop the string terminator
+		beg.pop();
+		let mut end = beg.clone();
+		end.push(0xff);
+		Ok((beg, end))
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Tf<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Tf::new(NamespaceId(1), DatabaseId(2), &tb, IndexId(3), "k");
+		let enc = Tf::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0+\x00\x00\x00\x03!tfk\0");
+		let (beg, end) = Tf::range(NamespaceId(1), DatabaseId(2), &tb, IndexId(3)).unwrap();
+		assert!(beg < enc && enc < end);
+		assert_eq!(Tf::decode_key(&enc).unwrap().shard, "k");
+	}
+}
@@ -27,6 +27,7 @@
 //! crate::key::root::ns                 /!ns{ns} -> NamespaceDefinition
 //! crate::key::root::us                 /!us{us}
 //! crate::key::root::tl                 /!tl{tl}
+//! crate::key::root::re                 /!re
 //! crate::key::root::cg                 /!cg{ty}
 //!
 //! crate::key::node::all                /${nd}
@@ -65,12 +66,19 @@
 //! crate::key::database::access::gr     /*{ns}*{db}&{ac}!gr{gr}
 //!
 //! crate::key::table::all               /*{ns}*{db}*{tb_name}
+//! crate::key::table::cm                /*{ns}*{db}*{tb_name}!cm{cs}{member} -> ConsumerMember
+//! crate::key::table::co                /*{ns}*{db}*{tb_name}!co{cs}{partition} -> ConsumerOffset
+//! crate::key::table::cs                /*{ns}*{db}*{tb_name}!cs{cs} -> ConsumerDefinition
 //! crate::key::table::ev                /*{ns}*{db}*{tb_name}!ev{ev}
 //! crate::key::table::fd                /*{ns}*{db}*{tb_name}!fd{fd}
 //! crate::key::table::ft                /*{ns}*{db}*{tb_name}!ft{ft}
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!il{ix} -> ix_name
 //! crate::key::table::ix                /*{ns}*{db}*{tb_name}!ix{ix_name} -> IndexDefinition
 //! crate::key::table::lq                /*{ns}*{db}*{tb_name}!lq{lq}
+//! crate::key::table::sc                /*{ns}*{db}*{tb_name}!sc{sk} -> SinkCursor
+//! crate::key::table::sk                /*{ns}*{db}*{tb_name}!sk{sk} -> SinkDefinition
+//! crate::key::table::sp                /*{ns}*{db}*{tb_name}!sp{sk}{vs} -> SinkParked
+//! crate::key::table::te                /*{ns}*{db}*{tb_name}!te{secs}{nanos}{id}
 //!
 //! crate::key::index::all               /*{ns}*{db}*{tb_name}+{ix}
 //! crate::key::index::bc                /*{ns}*{db}*{tb_name}+{ix}!bc{id}
@@ -85,6 +93,7 @@
 //! crate::key::index::bt                /*{ns}*{db}*{tb_name}+{ix}!bt{id}
 //! crate::key::index::bu                /*{ns}*{db}*{tb_name}+{ix}!bu{id}
 //! crate::key::index::dl                /*{ns}*{db}*{tb_name}+{ix}!dl{id}
+//! crate::key::index::gh                /*{ns}*{db}*{tb_name}+{ix}!gh{cell}{id}
 //! crate::key::index::tf                /*{ns}*{db}*{tb_name}+{ix}!tf{term}{id}
 //! crate::key::index                    /*{ns}*{db}*{tb_name}+{ix}*{fd}{id}
 //!
@@ -8,6 +8,7 @@ pub mod nh;
 pub mod ni;
 pub mod ns;
 pub mod rc;
+pub mod re;
 pub mod root_config;
 pub mod tl;
 pub mod us;
@@ -0,0 +1,63 @@
+//! Stores the progress of the re-encryption of values sealed with a retired
+//! encryption key
+//!
+//! The state lives in the keyspace, so that a pass resumes from where it
+//! stopped on any node after a restart or a lease takeover, and so that a
+//! completed pass is not repeated until the active encryption key changes.
+use revision::revisioned;
+use serde::{Deserialize, Serialize};
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{Key, impl_kv_key_storekey, impl_kv_value_revisioned};
+
+/// The progress of a re-encryption pass, stored as the value of a [`Re`].
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ReencryptionState {
+	/// The active encryption key the values are re-encrypted with
+	pub key: u32,
+	/// The key the next batch resumes from, or `None` once the pass completed
+	pub next: Option<Key>,
+}
+
+impl_kv_value_revisioned!(ReencryptionState);
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Re {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+}
+
+impl_kv_key_storekey!(Re => ReencryptionState);
+
+impl Categorise for Re {
+	fn categorise(&self) -> Category {
+		Category::Reencryption
+	}
+}
+
+impl Re {
+	pub(crate) fn new() -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'r',
+			_c: b'e',
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let enc = Re::new().encode_key().unwrap();
+		assert_eq!(enc, b"/!re");
+	}
+}
@@ -31,6 +31,9 @@ impl Tl {
 			TaskLeaseType::EventProcessing => 3,
 			TaskLeaseType::ReclaimTombstones => 4,
 			TaskLeaseType::IndexBuildResume => 5,
+			TaskLeaseType::SinkDelivery => 6,
+			TaskLeaseType::RecordExpiry => 7,
+			TaskLeaseType::Reencryption => 8,
 		};
 		Self {
 			__: b'/',
@@ -0,0 +1,69 @@
+//! Stores the membership generation of the group of a DEFINE CONSUMER
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerGroup, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Cg<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Cg<'_> => ConsumerGroup);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Cg<'a> {
+	Cg::new(ns, db, tb, cs)
+}
+
+impl Categorise for Cg<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableConsumerGroup
+	}
+}
+
+impl<'a> Cg<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'g',
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Cg::new(NamespaceId(1), DatabaseId(2), &tb, "testcs");
+		let enc = Cg::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cgtestcs\0");
+	}
+}
@@ -0,0 +1,147 @@
+//! Stores a member of the group of a DEFINE CONSUMER
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerMember, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Cm<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+	pub member: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Cm<'_> => ConsumerMember);
+
+#[derive(Clone, Debug, Eq, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Prefix<'_> => Vec<u8>);
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	cs: &'a str,
+	member: &'a str,
+) -> Cm<'a> {
+	Cm::new(ns, db, tb, cs, member)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName, cs: &str) -> Result<Vec<u8>> {
+	Prefix::new(ns, db, tb, cs).encode_key()
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName, cs: &str) -> Result<Vec<u8>> {
+	let mut k = Prefix::new(ns, db, tb, cs).encode_key()?;
+	k.extend_from_slice(&[0xff]);
+	Ok(k)
+}
+
+impl Categorise for Cm<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableConsumerMember
+	}
+}
+
+impl<'a> Cm<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		cs: &'a str,
+		member: &'a str,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'm',
+			cs: Cow::Borrowed(cs),
+			member: Cow::Borrowed(member),
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Cm<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'm',
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Cm::new(NamespaceId(1), DatabaseId(2), &tb, "testcs", "worker");
+		let enc = Cm::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cmtestcs\0worker\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let tb = TableName::from("testtb");
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), &tb, "testcs").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cmtestcs\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let tb = TableName::from("testtb");
+		let val = super::suffix(NamespaceId(1), DatabaseId(2), &tb, "testcs").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cmtestcs\0\xff");
+	}
+}
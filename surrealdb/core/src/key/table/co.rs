@@ -0,0 +1,147 @@
+//! Stores the acknowledged offset of a partition of a DEFINE CONSUMER
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerOffset, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Co<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+	pub partition: u16,
+}
+
+impl_kv_key_storekey!(Co<'_> => ConsumerOffset);
+
+#[derive(Clone, Debug, Eq, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Prefix<'_> => Vec<u8>);
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	cs: &'a str,
+	partition: u16,
+) -> Co<'a> {
+	Co::new(ns, db, tb, cs, partition)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName, cs: &str) -> Result<Vec<u8>> {
+	Prefix::new(ns, db, tb, cs).encode_key()
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName, cs: &str) -> Result<Vec<u8>> {
+	let mut k = Prefix::new(ns, db, tb, cs).encode_key()?;
+	k.extend_from_slice(&[0xff]);
+	Ok(k)
+}
+
+impl Categorise for Co<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableConsumerOffset
+	}
+}
+
+impl<'a> Co<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		cs: &'a str,
+		partition: u16,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'o',
+			cs: Cow::Borrowed(cs),
+			partition,
+		}
+	}
+
+	pub(crate) fn decode_key(k: &[u8]) -> Result<Co<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'o',
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Co::new(NamespaceId(1), DatabaseId(2), &tb, "testcs", 3);
+		let enc = Co::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cotestcs\0\x00\x03");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let tb = TableName::from("testtb");
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), &tb, "testcs").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cotestcs\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let tb = TableName::from("testtb");
+		let val = super::suffix(NamespaceId(1), DatabaseId(2), &tb, "testcs").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cotestcs\0\xff");
+	}
+}
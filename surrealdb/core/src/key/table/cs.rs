@@ -0,0 +1,95 @@
+//! Stores a DEFINE CONSUMER config definition
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerDefinition, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Cs<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub cs: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Cs<'_> => ConsumerDefinition);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Cs<'a> {
+	Cs::new(ns, db, tb, cs)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!cs\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!cs\xff");
+	Ok(k)
+}
+
+impl Categorise for Cs<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableConsumer
+	}
+}
+
+impl<'a> Cs<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, cs: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b's',
+			cs: Cow::Borrowed(cs),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Cs::new(NamespaceId(1), DatabaseId(2), &tb, "testcs");
+		let enc = Cs::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cstestcs\0");
+	}
+
+	#[test]
+	fn test_prefix() {
+		let tb = TableName::from("testtb");
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cs\0");
+	}
+
+	#[test]
+	fn test_suffix() {
+		let tb = TableName::from("testtb");
+		let val = super::suffix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cs\xff");
+	}
+}
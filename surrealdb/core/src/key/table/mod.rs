@@ -3,6 +3,10 @@ pub mod bg;
 pub mod bp;
 pub mod br;
 pub mod bs;
+pub mod cg;
+pub mod cm;
+pub mod co;
+pub mod cs;
 pub mod ev;
 pub mod fd;
 pub mod ft;
@@ -10,3 +14,7 @@ pub mod ih;
 pub mod is;
 pub mod ix;
 pub mod lq;
+pub mod sc;
+pub mod sk;
+pub mod sp;
+pub mod te;
@@ -0,0 +1,69 @@
+//! Stores the delivery cursor of a DEFINE SINK
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, SinkCursor};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Sc<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub sk: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Sc<'_> => SinkCursor);
+
+pub fn new<'a>(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, sk: &'a str) -> Sc<'a> {
+	Sc::new(ns, db, tb, sk)
+}
+
+impl Categorise for Sc<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableSinkCursor
+	}
+}
+
+impl<'a> Sc<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, sk: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b's',
+			_f: b'c',
+			sk: Cow::Borrowed(sk),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Sc::new(NamespaceId(1), DatabaseId(2), &tb, "testsk");
+		let enc = Sc::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!sctestsk\0");
+	}
+}
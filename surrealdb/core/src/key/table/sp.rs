@@ -0,0 +1,133 @@
+//! Stores a change set which a DEFINE SINK failed to deliver, and parked
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId, SinkParked};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Sp<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub sk: Cow<'a, str>,
+	pub vs: u128,
+}
+
+impl_kv_key_storekey!(Sp<'_> => SinkParked);
+
+#[derive(Clone, Debug, Eq, PartialEq, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+struct Prefix<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub sk: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Prefix<'_> => Vec<u8>);
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	sk: &'a str,
+	vs: u128,
+) -> Sp<'a> {
+	Sp::new(ns, db, tb, sk, vs)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName, sk: &str) -> Result<Vec<u8>> {
+	Prefix::new(ns, db, tb, sk).encode_key()
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName, sk: &str) -> Result<Vec<u8>> {
+	let mut k = Prefix::new(ns, db, tb, sk).encode_key()?;
+	k.extend_from_slice(&[0xff]);
+	Ok(k)
+}
+
+impl Categorise for Sp<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableSinkParked
+	}
+}
+
+impl<'a> Sp<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, sk: &'a str, vs: u128) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b's',
+			_f: b'p',
+			sk: Cow::Borrowed(sk),
+			vs,
+		}
+	}
+}
+
+impl<'a> Prefix<'a> {
+	fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, sk: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b's',
+			_f: b'p',
+			sk: Cow::Borrowed(sk),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = Sp::new(NamespaceId(1), DatabaseId(2), &tb, "testsk", 3);
+		let enc = Sp::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!sptestsk\0\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00\x03"
+		);
+	}
+
+	#[test]
+	fn test_prefix() {
+		let tb = TableName::from("testtb");
+		let val = super::prefix(NamespaceId(1), DatabaseId(2), &tb, "testsk").unwrap();
+		assert_eq!(val, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!sptestsk\0");
+	}
+}
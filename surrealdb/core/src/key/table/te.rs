@@ -0,0 +1,139 @@
+//! Stores the expiry entry of a record in a table with a TTL
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::{Datetime, RecordIdKey, TableName};
+
+/// The entries are ordered by the datetime the TTL of the record is counted
+/// from, encoded as seconds and nanoseconds so that the byte order of the
+/// keys matches the order of the datetimes.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct Te<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	pub tb: Cow<'a, TableName>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub secs: i64,
+	pub nanos: u32,
+	pub id: RecordIdKey,
+}
+
+impl_kv_key_storekey!(Te<'_> => ());
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	dt: &Datetime,
+	id: &RecordIdKey,
+) -> Te<'a> {
+	Te::new(ns, db, tb, dt, id.to_owned())
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!te");
+	Ok(k)
+}
+
+/// The end of the range of the entries whose datetime is at or before `dt`.
+pub fn until(ns: NamespaceId, db: DatabaseId, tb: &TableName, dt: &Datetime) -> Result<Vec<u8>> {
+	let mut k = prefix(ns, db, tb)?;
+	// Keep the encoded datetime, and sort after every record id following it
+	let key = Te::new(ns, db, tb, dt, RecordIdKey::Number(0)).encode_key()?;
+	k.extend_from_slice(&key[k.len()..k.len() + 12]);
+	k.push(0xff);
+	Ok(k)
+}
+
+impl Categorise for Te<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableExpiry
+	}
+}
+
+impl<'a> Te<'a> {
+	pub fn new(
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		dt: &Datetime,
+		id: RecordIdKey,
+	) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'*',
+			tb: Cow::Borrowed(tb),
+			_d: b'!',
+			_e: b't',
+			_f: b'e',
+			secs: dt.timestamp(),
+			nanos: dt.timestamp_subsec_nanos(),
+			id,
+		}
+	}
+
+	pub fn decode_key(k: &[u8]) -> Result<Te<'_>> {
+		Ok(storekey::decode_borrow(k)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::DateTime;
+
+	use super::*;
+
+	fn datetime(secs: i64, nanos: u32) -> Datetime {
+		Datetime(DateTime::from_timestamp(secs, nanos).unwrap())
+	}
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val =
+			Te::new(NamespaceId(1), DatabaseId(2), &tb, &datetime(1, 2), RecordIdKey::Number(3));
+		let enc = Te::encode_key(&val).unwrap();
+		assert_eq!(
+			enc,
+			b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!te\x80\x00\x00\x00\x00\x00\x00\x01\x00\x00\x00\x02\x02\x80\x00\x00\x00\x00\x00\x00\x03"
+		);
+		let dec = Te::decode_key(&enc).unwrap();
+		assert_eq!(dec, val);
+	}
+
+	#[test]
+	fn ordered_by_datetime() {
+		let tb = TableName::from("testtb");
+		let key = |secs, nanos, id| {
+			let dt = datetime(secs, nanos);
+			Te::new(NamespaceId(1), DatabaseId(2), &tb, &dt, RecordIdKey::Number(id))
+				.encode_key()
+				.unwrap()
+		};
+		assert!(key(-1, 0, 9) < key(0, 0, 1));
+		assert!(key(0, 5, 9) < key(0, 6, 1));
+		assert!(key(0, 5, 9) < key(1, 0, 1));
+		// The range up to a datetime includes every record at that datetime
+		let until = until(NamespaceId(1), DatabaseId(2), &tb, &datetime(0, 5)).unwrap();
+		assert!(key(0, 5, i64::MAX) < until);
+		assert!(until < key(0, 6, 0));
+		assert!(prefix(NamespaceId(1), DatabaseId(2), &tb).unwrap() < key(i64::MIN / 2, 0, 0));
+	}
+}
@@ -1079,4 +1079,21 @@ pub trait Transactable: requirements::TransactionRequirements {
 	fn compact(&self, _range: Option<Range<Key>>) -> BoxFut<'_, anyhow::Result<()>> {
 		Box::pin(async move { bail!(Error::CompactionNotSupported) })
 	}
+
+	/// Re-encrypt, with the active encryption key, the values sealed with a
+	/// retired key, or left in plaintext, among the next `batch` values of the
+	/// range.
+	///
+	/// Returns the rest of the range, or `None` once the range is done. The
+	/// default impl has nothing to re-encrypt: only transactions with
+	/// encryption at rest enabled override this.
+	fn reencrypt(&self, _rng: Range<Key>, _batch: u32) -> BoxFut<'_, Result<Option<Range<Key>>>> {
+		Box::pin(async move { Ok(None) })
+	}
+
+	/// The id of the active encryption key, or `None` when values are not
+	/// encrypted at rest.
+	fn encryption_key(&self) -> Option<u32> {
+		None
+	}
 }
@@ -0,0 +1,563 @@
+//! Binary, snapshot-consistent database backups.
+//!
+//! A backup file starts with [`MAGIC`], followed by a length-prefixed
+//! [`BackupHeader`] and a sequence of length-prefixed [`BackupEntry`] frames,
+//! terminated by [`BackupEntry::End`].
+//!
+//! A full backup copies the raw key-value range of a single database, read in
+//! one read transaction. An incremental backup instead ships the changefeed
+//! entries recorded since the `until` versionstamp of a previous backup, and
+//! therefore requires a changefeed on the database or its tables.
+//!
+//! Restoring a full backup creates the database afresh: the namespace and
+//! database identifiers embedded in the keys are rewritten to the identifiers
+//! allocated in the target datastore. The keys are written into a staging
+//! database, which only takes the name of the target database once the whole
+//! backup has been restored. Restoring an incremental backup replays the
+//! recorded changes against the existing database in import mode, so that
+//! the recorded values are written as they are, indexes stay consistent, and
+//! table events do not fire a second time.
+
+use std::ops::Range;
+
+use anyhow::{Result, bail, ensure};
+use async_channel::Sender;
+use revision::revisioned;
+use surrealdb_types::ToSql;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::{KVKey, KVValue, Key, Transaction, Val};
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseDefinition, DatabaseId, NamespaceId, TableDefinition};
+use crate::cf::{ChangeSet, TableMutation, TableMutations};
+use crate::err::Error;
+use crate::expr::paths::{IN, OUT};
+use crate::expr::statements::show::ShowSince;
+use crate::sql::statements::define::DefineKind;
+use crate::val::Value;
+
+/// The bytes every backup file starts with.
+pub const MAGIC: &[u8; 16] = b"SURREALDB-BACKUP";
+
+/// The maximum number of changefeed entries read per scan.
+const CHANGE_BATCH_SIZE: u32 = 1000;
+
+/// The size above which a batch of key-value pairs is split across frames.
+const PAIRS_FRAME_SIZE: usize = 4 * 1024 * 1024;
+
+/// The largest frame which is written to, or read from, a backup file.
+const MAX_FRAME_SIZE: usize = 256 * 1024 * 1024;
+
+/// Whether a backup contains a full copy of a database or only the changes
+/// made since a previous backup.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BackupKind {
+	Full,
+	/// Contains the changes with a versionstamp in `since..until`.
+	Incremental {
+		since: u128,
+	},
+}
+
+/// Describes the contents of a backup file.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BackupHeader {
+	/// The namespace the backup was taken from.
+	pub namespace: String,
+	/// The database the backup was taken from.
+	pub database: String,
+	pub kind: BackupKind,
+	/// Every change with a lower versionstamp is contained in this backup or
+	/// in the backups it builds upon. An incremental backup taken from this
+	/// one starts here.
+	pub until: u128,
+}
+
+impl BackupHeader {
+	/// Read the magic bytes and header at the start of a backup file.
+	pub async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Self> {
+		let mut magic = [0u8; MAGIC.len()];
+		reader
+			.read_exact(&mut magic)
+			.await
+			.map_err(|_| invalid("the file is not a SurrealDB backup"))?;
+		ensure!(&magic == MAGIC, invalid("the file is not a SurrealDB backup"));
+		let Some(bytes) = read_frame(reader).await? else {
+			bail!(invalid("the backup header is missing"));
+		};
+		Ok(revision::from_slice(&bytes)?)
+	}
+
+	/// Checks whether `next` can be restored directly after this backup.
+	pub fn precedes(&self, next: &BackupHeader) -> bool {
+		self.namespace == next.namespace
+			&& self.database == next.database
+			&& next.kind
+				== BackupKind::Incremental {
+					since: self.until,
+				}
+	}
+}
+
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct BackupPair {
+	pub(crate) key: Key,
+	pub(crate) val: Val,
+}
+
+/// A single frame of a backup file.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum BackupEntry {
+	/// The encoded [`DatabaseDefinition`], first in a full backup.
+	Database(Val),
+	/// A batch of raw key-value pairs from the database key range.
+	Pairs(Vec<BackupPair>),
+	/// The changes committed at a single versionstamp.
+	Changes(ChangeSet),
+	/// Marks the end of the backup, with the number of preceding entries.
+	End {
+		entries: u64,
+	},
+}
+
+/// Encode a value and prefix it with its length.
+fn frame<T: revision::SerializeRevisioned>(value: &T) -> Result<Vec<u8>> {
+	let bytes = revision::to_vec(value)?;
+	ensure!(
+		bytes.len() <= MAX_FRAME_SIZE,
+		invalid("a backup entry exceeds the maximum frame size")
+	);
+	let mut out = Vec::with_capacity(bytes.len() + 4);
+	out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+	out.extend_from_slice(&bytes);
+	Ok(out)
+}
+
+/// Read a single length-prefixed frame, returning `None` at the end of the
+/// input.
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+	let mut len = [0u8; 4];
+	match reader.read_exact(&mut len).await {
+		Ok(_) => {}
+		Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e.into()),
+	}
+	let len = u32::from_be_bytes(len) as usize;
+	ensure!(len <= MAX_FRAME_SIZE, invalid("a backup entry exceeds the maximum frame size"));
+	let mut buf = vec![0u8; len];
+	reader.read_exact(&mut buf).await?;
+	Ok(Some(buf))
+}
+
+/// Read the next entry of a backup file.
+pub(crate) async fn read_entry<R: AsyncRead + Unpin>(reader: &mut R) -> Result<BackupEntry> {
+	let Some(bytes) = read_frame(reader).await? else {
+		bail!(invalid("the backup is truncated"));
+	};
+	Ok(revision::from_slice(&bytes)?)
+}
+
+pub(crate) fn invalid(message: &str) -> Error {
+	Error::InvalidBackup {
+		message: message.to_owned(),
+	}
+}
+
+/// Encode the frame which ends a backup file.
+pub(crate) fn end_bytes(entries: u64) -> Result<Vec<u8>> {
+	frame(&BackupEntry::End {
+		entries,
+	})
+}
+
+/// Encode the magic bytes and header which start a backup file.
+pub(crate) fn header_bytes(header: &BackupHeader) -> Result<Vec<u8>> {
+	let mut out = MAGIC.to_vec();
+	out.extend(frame(header)?);
+	Ok(out)
+}
+
+impl Transaction {
+	/// Writes every key of the database, apart from changefeed entries and
+	/// live query registrations, as raw key-value pairs.
+	pub(crate) async fn backup_full(
+		&self,
+		db: &DatabaseDefinition,
+		batch_size: u32,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<u64> {
+		let (ns, db_id) = (db.namespace_id, db.database_id);
+		chn.send(frame(&BackupEntry::Database(db.kv_encode_value()?))?).await?;
+		let mut entries = 1;
+
+		// Live query registrations belong to the nodes of this datastore
+		let mut skip = Vec::new();
+		for tb in self.all_tb(ns, db_id, None).await?.iter() {
+			skip.push(
+				crate::key::table::lq::prefix(ns, db_id, &tb.name)?
+					..crate::key::table::lq::suffix(ns, db_id, &tb.name)?,
+			);
+		}
+
+		let root = crate::key::database::all::new(ns, db_id).encode_key()?;
+		let mut beg = root.clone();
+		beg.push(0x00);
+		let mut end = root.clone();
+		end.push(0xff);
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys_vals(rng, batch_size, None).await?;
+			next = batch.next;
+			let mut pairs = Vec::new();
+			let mut size = 0;
+			for (key, val) in batch.result {
+				// Changefeed (`#`) and live query event (`%`) entries are not data
+				if matches!(key.get(root.len()), Some(b'#' | b'%'))
+					|| skip.iter().any(|r| r.contains(&key))
+				{
+					continue;
+				}
+				size += key.len() + val.len();
+				pairs.push(BackupPair {
+					key,
+					val,
+				});
+				// Keep frames small, whatever the size of the values
+				if size >= PAIRS_FRAME_SIZE {
+					chn.send(frame(&BackupEntry::Pairs(std::mem::take(&mut pairs)))?).await?;
+					entries += 1;
+					size = 0;
+				}
+			}
+			if !pairs.is_empty() {
+				chn.send(frame(&BackupEntry::Pairs(pairs))?).await?;
+				entries += 1;
+			}
+		}
+		Ok(entries)
+	}
+
+	/// Writes the changefeed entries with a versionstamp in `since..until`.
+	pub(crate) async fn backup_changes(
+		&self,
+		db: &DatabaseDefinition,
+		since: u128,
+		until: u128,
+		chn: &Sender<Vec<u8>>,
+	) -> Result<u64> {
+		let (ns, db_id) = (db.namespace_id, db.database_id);
+		if db.changefeed.is_none()
+			&& !self.all_tb(ns, db_id, None).await?.iter().any(|tb| tb.changefeed.is_some())
+		{
+			bail!(invalid(
+				"incremental backups require a CHANGEFEED on the database or its tables"
+			));
+		}
+
+		let mut entries = 0;
+		let mut cursor = since;
+		loop {
+			let mut changes = crate::cf::read(
+				self,
+				ns,
+				db_id,
+				None,
+				ShowSince::Versionstamp(cursor as u64),
+				Some(CHANGE_BATCH_SIZE),
+			)
+			.await?;
+			let read: usize = changes.iter().map(|cs| cs.1.0.len()).sum();
+			let complete = read < CHANGE_BATCH_SIZE as usize;
+			// The last change set of a full batch may continue past the end
+			// of the scan, so it is read again by the next iteration.
+			if !complete && changes.len() > 1 {
+				changes.pop();
+			}
+			let Some(last) = changes.last() else {
+				break;
+			};
+			cursor = last.0 + 1;
+			for cs in changes {
+				if cs.0 >= until {
+					return Ok(entries);
+				}
+				chn.send(frame(&BackupEntry::Changes(cs))?).await?;
+				entries += 1;
+			}
+			if complete {
+				break;
+			}
+		}
+		Ok(entries)
+	}
+
+	/// Creates the staging database a full backup of `db` is restored into,
+	/// returning the restore state.
+	pub(crate) async fn restore_database(
+		&self,
+		ns: &str,
+		db: &str,
+		staging: &str,
+		def: &[u8],
+	) -> Result<RestoreTarget> {
+		if self.get_db_by_name(ns, db, None).await?.is_some() {
+			bail!(Error::DbAlreadyExists {
+				name: db.to_owned(),
+			});
+		}
+		let source = DatabaseDefinition::kv_decode_value(def, ())?;
+		let ns_def = self.get_or_add_ns(None, ns).await?;
+		let db_def = DatabaseDefinition {
+			namespace_id: ns_def.namespace_id,
+			database_id: self.get_next_db_id(None, ns_def.namespace_id).await?,
+			name: staging.into(),
+			..source.clone()
+		};
+		let db_def = self.put_db(ns, db_def).await?;
+		RestoreTarget::new(&source, &db_def)
+	}
+
+	/// Gives a fully restored staging database the name of the database the
+	/// backup is restored into.
+	pub(crate) async fn restore_swap(&self, ns: &str, db: &str, staging: &str) -> Result<()> {
+		if self.get_db_by_name(ns, db, None).await?.is_some() {
+			bail!(Error::DbAlreadyExists {
+				name: db.to_owned(),
+			});
+		}
+		let Some(def) = self.get_db_by_name(ns, staging, None).await? else {
+			bail!(Error::DbNotFound {
+				name: staging.to_owned(),
+			});
+		};
+		// The keys of the database contents only refer to its identifier
+		self.del(&crate::key::namespace::db::new(def.namespace_id, staging)).await?;
+		self.put_db(
+			ns,
+			DatabaseDefinition {
+				name: db.into(),
+				..def.as_ref().clone()
+			},
+		)
+		.await?;
+		Ok(())
+	}
+
+	/// Writes a batch of raw key-value pairs into the restored database.
+	pub(crate) async fn restore_pairs(
+		&self,
+		target: &mut RestoreTarget,
+		pairs: Vec<BackupPair>,
+	) -> Result<()> {
+		for BackupPair {
+			key,
+			val,
+		} in pairs
+		{
+			let (key, val) = target.rebase(&key, val)?;
+			self.set(&key, &val).await?;
+		}
+		Ok(())
+	}
+}
+
+/// Maps keys from the database a backup was taken from onto the database it
+/// is restored into.
+pub(crate) struct RestoreTarget {
+	source: (NamespaceId, DatabaseId),
+	target: (NamespaceId, DatabaseId),
+	source_root: Key,
+	target_root: Key,
+	tables: Range<Key>,
+	views: Vec<Range<Key>>,
+}
+
+impl RestoreTarget {
+	fn new(source: &DatabaseDefinition, target: &DatabaseDefinition) -> Result<Self> {
+		let (ns, db) = (source.namespace_id, source.database_id);
+		Ok(Self {
+			source: (ns, db),
+			target: (target.namespace_id, target.database_id),
+			source_root: crate::key::database::all::new(ns, db).encode_key()?,
+			target_root: crate::key::database::all::new(target.namespace_id, target.database_id)
+				.encode_key()?,
+			tables: crate::key::database::tb::prefix(ns, db)?
+				..crate::key::database::tb::suffix(ns, db)?,
+			views: Vec::new(),
+		})
+	}
+
+	/// Rewrite the key prefix, and the identifiers within table definitions,
+	/// to point at the target database.
+	fn rebase(&mut self, key: &[u8], val: Val) -> Result<(Key, Val)> {
+		ensure!(key.starts_with(&self.source_root), invalid("a key lies outside the database"));
+		let mut out = self.target_root.clone();
+		out.extend_from_slice(&key[self.source_root.len()..]);
+
+		let is_table = within(&self.tables, key);
+		if !is_table && !self.views.iter().any(|r| within(r, key)) {
+			return Ok((out, val));
+		}
+		let mut def = TableDefinition::kv_decode_value(&val, ())?;
+		if is_table {
+			// Table definitions sort before the table contents, so the view
+			// definitions of every table are known before they are reached.
+			let (ns, db) = self.source;
+			self.views.push(
+				crate::key::table::ft::prefix(ns, db, &def.name)?
+					..crate::key::table::ft::suffix(ns, db, &def.name)?,
+			);
+		}
+		(def.namespace_id, def.database_id) = self.target;
+		Ok((out, def.kv_encode_value()?))
+	}
+}
+
+/// Check whether a key lies within a range of keys.
+fn within(rng: &Range<Key>, key: &[u8]) -> bool {
+	rng.start.as_slice() <= key && key < rng.end.as_slice()
+}
+
+/// Render a change set as a transaction which replays it in import mode.
+pub(crate) fn changes_script(changes: ChangeSet) -> String {
+	// Events already fired, and fields were already computed, when the
+	// changes were first made
+	let mut sql = String::from("OPTION IMPORT;\nBEGIN;\n");
+	for TableMutations(_, mutations) in (changes.1).0 {
+		for mutation in mutations {
+			match mutation {
+				TableMutation::Set(id, value) | TableMutation::SetWithDiff(id, value, _) => {
+					if is_edge(&value) {
+						// Relations can not be upserted, so the edge is recreated
+						sql.push_str(&format!("DELETE {} RETURN NONE;\n", id.to_sql()));
+						sql.push_str(&format!("INSERT RELATION {} RETURN NONE;\n", value.to_sql()));
+					} else {
+						sql.push_str(&format!(
+							"UPSERT {} CONTENT {} RETURN NONE;\n",
+							id.to_sql(),
+							value.to_sql()
+						));
+					}
+				}
+				TableMutation::Del(id) | TableMutation::DelWithOriginal(id, _) => {
+					sql.push_str(&format!("DELETE {} RETURN NONE;\n", id.to_sql()));
+				}
+				TableMutation::Def(def) => {
+					let mut stmt = def.to_sql_definition();
+					stmt.kind = DefineKind::Overwrite;
+					sql.push_str(&format!("{};\n", stmt.to_sql()));
+				}
+			}
+		}
+	}
+	sql.push_str("COMMIT;");
+	sql
+}
+
+fn is_edge(value: &Value) -> bool {
+	matches!(value.pick(&IN), Value::RecordId(_)) && matches!(value.pick(&OUT), Value::RecordId(_))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::val::{RecordIdKey, TableName};
+
+	#[tokio::test]
+	async fn header_round_trip() {
+		let header = BackupHeader {
+			namespace: "test".to_owned(),
+			database: "test".to_owned(),
+			kind: BackupKind::Incremental {
+				since: 42,
+			},
+			until: 84,
+		};
+		let bytes = header_bytes(&header).unwrap();
+		assert_eq!(BackupHeader::read(&mut bytes.as_slice()).await.unwrap(), header);
+		assert!(BackupHeader::read(&mut &b"SURREALDB-EXPORT"[..]).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn truncated_backup() {
+		let entry = frame(&BackupEntry::End {
+			entries: 3,
+		})
+		.unwrap();
+		assert_eq!(
+			read_entry(&mut entry.as_slice()).await.unwrap(),
+			BackupEntry::End {
+				entries: 3
+			}
+		);
+		assert!(read_entry(&mut &entry[..entry.len() - 1]).await.is_err());
+		assert!(read_entry(&mut &[][..]).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn oversized_frame() {
+		let mut bytes = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes().to_vec();
+		bytes.extend_from_slice(&[0; 16]);
+		assert!(read_entry(&mut bytes.as_slice()).await.is_err());
+	}
+
+	#[test]
+	fn header_chain() {
+		let full = BackupHeader {
+			namespace: "ns".to_owned(),
+			database: "db".to_owned(),
+			kind: BackupKind::Full,
+			until: 10,
+		};
+		let next = BackupHeader {
+			kind: BackupKind::Incremental {
+				since: 10,
+			},
+			until: 20,
+			..full.clone()
+		};
+		assert!(full.precedes(&next));
+		assert!(!next.precedes(&full));
+		assert!(!full.precedes(&BackupHeader {
+			database: "other".to_owned(),
+			..next.clone()
+		}));
+	}
+
+	#[test]
+	fn rebase_keys() {
+		let source = DatabaseDefinition {
+			namespace_id: NamespaceId(1),
+			database_id: DatabaseId(2),
+			name: "db".into(),
+			comment: None,
+			changefeed: None,
+			strict: false,
+		};
+		let target = DatabaseDefinition {
+			namespace_id: NamespaceId(3),
+			database_id: DatabaseId(4),
+			..source.clone()
+		};
+		let mut restore = RestoreTarget::new(&source, &target).unwrap();
+		let tb = TableName::new("person".to_owned());
+		let key =
+			crate::key::record::new(NamespaceId(1), DatabaseId(2), &tb, &RecordIdKey::Number(1))
+				.encode_key()
+				.unwrap();
+		let (rebased, _) = restore.rebase(&key, vec![1, 2, 3]).unwrap();
+		assert_eq!(
+			rebased,
+			crate::key::record::new(NamespaceId(3), DatabaseId(4), &tb, &RecordIdKey::Number(1))
+				.encode_key()
+				.unwrap()
+		);
+		let outside =
+			crate::key::database::all::new(NamespaceId(1), DatabaseId(9)).encode_key().unwrap();
+		assert!(restore.rebase(&outside, vec![]).is_err());
+	}
+}
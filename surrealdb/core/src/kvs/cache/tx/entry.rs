@@ -66,6 +66,10 @@ pub(crate) enum Entry {
 	Ixs(Arc<[catalog::IndexDefinition]>),
 	/// A slice of LiveStatement specified on a table.
 	Lvs(Arc<[catalog::SubscriptionDefinition]>),
+	/// A slice of DefineSinkStatement specified on a table.
+	Sks(Arc<[catalog::SinkDefinition]>),
+	/// A slice of DefineConsumerStatement specified on a table.
+	Css(Arc<[catalog::ConsumerDefinition]>),
 }
 
 impl Entry {
@@ -300,6 +304,22 @@ impl Entry {
 			_ => fail!("Unable to convert type into Entry::Lvs"),
 		}
 	}
+	/// Converts this cache entry into a slice of [`DefineSinkStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Sks`].
+	pub(crate) fn try_into_sks(self) -> Result<Arc<[catalog::SinkDefinition]>> {
+		match self {
+			Entry::Sks(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Sks"),
+		}
+	}
+	/// Converts this cache entry into a slice of [`DefineConsumerStatement`].
+	/// This panics if called on a cache entry that is not an [`Entry::Css`].
+	pub(crate) fn try_into_css(self) -> Result<Arc<[catalog::ConsumerDefinition]>> {
+		match self {
+			Entry::Css(v) => Ok(v),
+			_ => fail!("Unable to convert type into Entry::Css"),
+		}
+	}
 	/// Converts this cache entry into a single [`Record`].
 	/// This panics if called on a cache entry that is not an [`Entry::Val`].
 	pub(crate) fn try_into_record(self) -> Result<Arc<Record>> {
@@ -63,6 +63,10 @@ pub(crate) enum Key {
 	Ixs(NamespaceId, DatabaseId, String),
 	/// A cache key for live queries (on a table)
 	Lvs(NamespaceId, DatabaseId, String),
+	/// A cache key for sinks (on a table)
+	Sks(NamespaceId, DatabaseId, String),
+	/// A cache key for consumers (on a table)
+	Css(NamespaceId, DatabaseId, String),
 	/// A cache key for a node
 	Nd(Uuid),
 	/// A cache key for a root config
@@ -117,6 +121,10 @@ pub(crate) enum Key {
 	Fd(NamespaceId, DatabaseId, String, String),
 	/// A cache key for an index (on a table)
 	Ix(NamespaceId, DatabaseId, String, String),
+	/// A cache key for a sink (on a table)
+	Sk(NamespaceId, DatabaseId, String, String),
+	/// A cache key for a consumer (on a table)
+	Cs(NamespaceId, DatabaseId, String, String),
 	/// A cache key for a record
 	Record(NamespaceId, DatabaseId, String, RecordIdKey),
 }
@@ -154,6 +162,8 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Fts(a, b, c) => Key::Fts(a, b, c.to_string()),
 			Lookup::Ixs(a, b, c) => Key::Ixs(a, b, c.to_string()),
 			Lookup::Lvs(a, b, c) => Key::Lvs(a, b, c.to_string()),
+			Lookup::Sks(a, b, c) => Key::Sks(a, b, c.to_string()),
+			Lookup::Css(a, b, c) => Key::Css(a, b, c.to_string()),
 			//
 			Lookup::Nd(a) => Key::Nd(a),
 			Lookup::Rcg(a) => Key::Rcg(a.to_string()),
@@ -182,6 +192,8 @@ impl<'a> From<Lookup<'a>> for Key {
 			Lookup::Ev(a, b, c, d) => Key::Ev(a, b, c.to_string(), d.to_string()),
 			Lookup::Fd(a, b, c, d) => Key::Fd(a, b, c.to_string(), d.to_string()),
 			Lookup::Ix(a, b, c, d) => Key::Ix(a, b, c.to_string(), d.to_string()),
+			Lookup::Sk(a, b, c, d) => Key::Sk(a, b, c.to_string(), d.to_string()),
+			Lookup::Cs(a, b, c, d) => Key::Cs(a, b, c.to_string(), d.to_string()),
 			Lookup::Record(a, b, c, d) => Key::Record(a, b, c.to_string(), d.to_owned()),
 		}
 	}
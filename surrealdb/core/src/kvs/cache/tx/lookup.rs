@@ -64,6 +64,10 @@ pub(crate) enum Lookup<'a> {
 	Ixs(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for live queries (on a table)
 	Lvs(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for sinks (on a table)
+	Sks(NamespaceId, DatabaseId, &'a str),
+	/// A cache key for consumers (on a table)
+	Css(NamespaceId, DatabaseId, &'a str),
 	/// A cache key for a node
 	Nd(Uuid),
 	/// A cache key for root config
@@ -118,6 +122,10 @@ pub(crate) enum Lookup<'a> {
 	Fd(NamespaceId, DatabaseId, &'a str, &'a str),
 	/// A cache key for an index (on a table)
 	Ix(NamespaceId, DatabaseId, &'a str, &'a str),
+	/// A cache key for a sink (on a table)
+	Sk(NamespaceId, DatabaseId, &'a str, &'a str),
+	/// A cache key for a consumer (on a table)
+	Cs(NamespaceId, DatabaseId, &'a str, &'a str),
 	/// A cache key for a record
 	Record(NamespaceId, DatabaseId, &'a str, &'a RecordIdKey),
 }
@@ -155,6 +163,8 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Fts(la, lb, lc), Key::Fts(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Ixs(la, lb, lc), Key::Ixs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			(Self::Lvs(la, lb, lc), Key::Lvs(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Sks(la, lb, lc), Key::Sks(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
+			(Self::Css(la, lb, lc), Key::Css(ka, kb, kc)) => la == ka && lb == kb && lc == kc,
 			//
 			(Self::Nd(la), Key::Nd(ka)) => la == ka,
 			(Self::Rcg(la), Key::Rcg(ka)) => la == ka,
@@ -183,6 +193,8 @@ impl Equivalent<Key> for Lookup<'_> {
 			(Self::Ev(la, lb, lc, ld), Key::Ev(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Fd(la, lb, lc, ld), Key::Fd(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Ix(la, lb, lc, ld), Key::Ix(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
+			(Self::Sk(la, lb, lc, ld), Key::Sk(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
+			(Self::Cs(la, lb, lc, ld), Key::Cs(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && ld == kd,
 			(Self::Record(la, lb, lc, ld), Key::Record(ka, kb, kc, kd)) => la == ka && lb == kb && lc == kc && *ld == kd,
 			//
 			_ => false,
@@ -267,6 +279,8 @@ mod tests {
 	#[case(Lookup::Fts(NamespaceId(1), DatabaseId(1), "test"), Key::Fts(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Ixs(NamespaceId(1), DatabaseId(1), "test"), Key::Ixs(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Lvs(NamespaceId(1), DatabaseId(1), "test"), Key::Lvs(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Sks(NamespaceId(1), DatabaseId(1), "test"), Key::Sks(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
+	#[case(Lookup::Css(NamespaceId(1), DatabaseId(1), "test"), Key::Css(NamespaceId(1), DatabaseId(1), "test".to_string()), true)]
 	#[case(Lookup::Nd(Uuid::from_u128(1)), Key::Nd(Uuid::from_u128(1)), true)]
 	#[case(Lookup::Ru("test"), Key::Ru("test".to_string()), true)]
 	#[case(Lookup::Ra("test"), Key::Ra("test".to_string()), true)]
@@ -292,6 +306,8 @@ mod tests {
 	#[case(Lookup::Ev(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ev(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Fd(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Fd(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Ix(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Ix(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
+	#[case(Lookup::Sk(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Sk(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
+	#[case(Lookup::Cs(NamespaceId(1), DatabaseId(1), "test", "test"), Key::Cs(NamespaceId(1), DatabaseId(1), "test".to_string(), "test".to_string()), true)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(1), "test".to_string(), RecordIdKey::Number(1)), true)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(1), "test".to_string(), RecordIdKey::Number(2)), false)]
 	#[case(Lookup::Record(NamespaceId(1), DatabaseId(1), "test", &RecordIdKey::Number(1)), Key::Record(NamespaceId(1), DatabaseId(2), "test".to_string(), RecordIdKey::Number(1)), false)]
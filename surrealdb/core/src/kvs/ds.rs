@@ -34,7 +34,7 @@ use super::api::{BoxFut, Transactable};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
-use super::{Key, Val, export};
+use super::{Key, Val, backup, export, fix};
 use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
 use crate::api::request::ApiRequest;
@@ -75,6 +75,7 @@ use crate::key::root::ic::IndexCompactionKey;
 use crate::key::root::rc::{
 	RECLAIM_DATABASE, RECLAIM_INDEX, RECLAIM_NAMESPACE, ReclaimKey, ReclaimState,
 };
+use crate::key::root::re::{Re, ReencryptionState};
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
 use crate::kvs::cache::ds::DatastoreCache;
@@ -564,6 +565,8 @@ pub enum DatastoreFlavor {
 	TiKV(super::tikv::Datastore),
 	#[cfg(feature = "kv-surrealkv")]
 	SurrealKV(super::surrealkv::Datastore),
+	#[cfg(feature = "kv-redb")]
+	Redb(super::redb::Datastore),
 }
 
 impl TransactionBuilderFactoryRequirements for CommunityComposer {}
@@ -693,6 +696,24 @@ impl TransactionBuilderFactory for CommunityComposer {
 				#[cfg(not(feature = "kv-surrealkv"))]
 				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `surrealkv` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
 			}
+			// Initiate a redb database
+			(flavour @ "redb", path) => {
+				#[cfg(feature = "kv-redb")]
+				{
+					// Parse redb-specific configuration from query parameters
+					let config = config.load();
+					// Initialise the storage engine
+					let v = super::redb::Datastore::new(&path, config)
+						.await
+						.map(DatastoreFlavor::Redb)?;
+					info!(target: TARGET, "Started {flavour} kvs store");
+					Ok(TransactionBuilderParts::without_router_state(Box::<DatastoreFlavor>::new(
+						v,
+					)))
+				}
+				#[cfg(not(feature = "kv-redb"))]
+				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `redb` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
+			}
 			// Initiate an IndxDB database
 			(flavour @ "indxdb", path) => {
 				#[cfg(feature = "kv-indxdb")]
@@ -744,6 +765,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 			v_s if v_s.starts_with("file:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("rocksdb:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("surrealkv:") => Ok(v.to_string()),
+			v_s if v_s.starts_with("redb:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("mem:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("tikv:") => Ok(v.to_string()),
 			_ => bail!("Provide a valid database path parameter"),
@@ -792,6 +814,11 @@ impl TransactionBuilder for DatastoreFlavor {
 					let tx = v.transaction(write, lock).await?;
 					(tx, true)
 				}
+				#[cfg(feature = "kv-redb")]
+				Self::Redb(v) => {
+					let tx = v.transaction(write, lock).await?;
+					(tx, true)
+				}
 				_ => unreachable!(),
 			})
 		})
@@ -832,6 +859,8 @@ impl TransactionBuilder for DatastoreFlavor {
 				Self::TiKV(v) => Ok(v.shutdown().await?),
 				#[cfg(feature = "kv-surrealkv")]
 				Self::SurrealKV(v) => Ok(v.shutdown().await?),
+				#[cfg(feature = "kv-redb")]
+				Self::Redb(v) => Ok(v.shutdown().await?),
 				#[allow(unreachable_patterns)]
 				_ => unreachable!(),
 			}
@@ -866,6 +895,8 @@ impl Display for DatastoreFlavor {
 			Self::TiKV(_) => write!(f, "tikv"),
 			#[cfg(feature = "kv-surrealkv")]
 			Self::SurrealKV(_) => write!(f, "surrealkv"),
+			#[cfg(feature = "kv-redb")]
+			Self::Redb(_) => write!(f, "redb"),
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
@@ -2113,6 +2144,151 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Delivers changefeed mutations to sinks as a background task.
+	///
+	/// This method pushes the pending change sets of every `DEFINE SINK`
+	/// definition to its external endpoint, advancing the durable cursor of
+	/// each sink once its changes have been delivered. It uses a distributed
+	/// task lease so that only a single node delivers changes at any one time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between delivery runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn sink_delivery(&self, interval: &Duration) -> Result<()> {
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::SinkDelivery,
+			*interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running sink delivery");
+		// Deliver the pending changes of each sink
+		crate::cf::sink::process(self, &lh).await
+	}
+
+	/// Deletes expired records as a background task.
+	///
+	/// This method deletes the records of every table defined with a `TTL`
+	/// once they have outlived it, running a regular `DELETE` so that table
+	/// events and live queries are triggered as normal. It uses a distributed
+	/// task lease so that only a single node deletes records at any one time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between expiry runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn record_expiry(&self, interval: &Duration) -> Result<()> {
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::RecordExpiry,
+			*interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running record expiry");
+		// Delete the expired records of each table
+		crate::kvs::expiry::process(self, &lh).await
+	}
+
+	/// Re-encrypts the values sealed with a retired encryption key as a
+	/// background task.
+	///
+	/// When encryption at rest is enabled with retired keys in the key ring,
+	/// this method walks the keyspace in batches of [`NORMAL_BATCH_SIZE`]
+	/// values, each re-encrypted and committed in its own short transaction,
+	/// so that it never conflicts with or holds up user transactions for
+	/// long. It uses a distributed task lease so that only a single node
+	/// re-encrypts at any one time, and stops at the end of a batch when the
+	/// lease can not be maintained. The progress of the pass is committed with
+	/// each batch, so the next run resumes from that point on any node, even
+	/// after a restart. Once the whole keyspace has been walked the pass is
+	/// marked as complete for the active key, and a message is logged to say
+	/// that the retired keys can be removed from the key ring. The task is then
+	/// a no-op until the active key changes.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between re-encryption runs, to calculate the lease duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn reencryption(&self, interval: &Duration) -> Result<()> {
+		// Check if values are encrypted at rest
+		let txn = self.transaction(Read, Optimistic).await?;
+		let key = txn.encryption_key();
+		txn.cancel().await?;
+		let Some(key) = key else {
+			return Ok(());
+		};
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::Reencryption,
+			*interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Fetch the progress of the pass for the active key
+		let txn = self.transaction(Read, Optimistic).await?;
+		let state = catch!(txn, txn.get(&Re::new(), None).await);
+		txn.cancel().await?;
+		let beg = match state {
+			// The pass for the active key is already complete
+			Some(ReencryptionState {
+				key: k,
+				next: None,
+			}) if k == key => return Ok(()),
+			// Resume the pass for the active key
+			Some(ReencryptionState {
+				key: k,
+				next: Some(next),
+			}) if k == key => next,
+			// Start a new pass, as the active key changed
+			_ => Vec::new(),
+		};
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running re-encryption");
+		let mut next = Some(beg..vec![0xff]);
+		while let Some(rng) = next {
+			// Re-encrypt the next batch in its own transaction
+			let txn = self.transaction(Write, Optimistic).await?;
+			next = catch!(txn, txn.reencrypt(rng, NORMAL_BATCH_SIZE).await);
+			// Store where the next run resumes from, along with the batch
+			let state = ReencryptionState {
+				key,
+				next: next.as_ref().map(|rng| rng.start.clone()),
+			};
+			catch!(txn, txn.set(&Re::new(), &state).await);
+			catch!(txn, txn.commit().await);
+			// Stop if the lease was taken over by another node
+			if next.is_some() && !lh.try_maintain_lease().await? {
+				return Ok(());
+			}
+			// Pause and yield execution
+			yield_now!();
+		}
+		// Let operators know that the retired keys are no longer needed
+		info!(
+			target: TARGET,
+			"Every value is sealed with encryption key {key}, so any retired encryption keys can now be removed from the key ring"
+		);
+		// Everything was successful
+		Ok(())
+	}
+
 	/// Run one live-query router pass.
 	///
 	/// Under the [`LiveQueryEngine::Router`] engine this tails the dedicated
@@ -4051,6 +4227,248 @@ impl Datastore {
 		})
 	}
 
+	/// Performs a binary backup of the session database
+	///
+	/// Without `since` the whole database is copied from a single snapshot.
+	/// With `since`, taken from the `until` of a previous backup header, only
+	/// the changefeed entries recorded from that versionstamp are included.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn backup(
+		&self,
+		sess: &Session,
+		since: Option<u128>,
+		chn: Sender<Vec<u8>>,
+	) -> Result<impl Future<Output = Result<backup::BackupHeader>> + 'static> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Every change below the watermark is visible to a snapshot taken
+		// afterwards, and later changes are left to the next incremental backup
+		let txn = self.transaction(Read, Optimistic).await?;
+		let until = catch!(txn, txn.safe_timestamp().await).as_versionstamp();
+		txn.cancel().await?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		let batch_size = self.config.export_batch_size;
+		// Return an async backup job
+		Ok(async move {
+			let res = async {
+				let def =
+					txn.get_db_by_name(&ns, &db, None).await?.ok_or_else(|| Error::DbNotFound {
+						name: db.clone(),
+					})?;
+				let header = backup::BackupHeader {
+					namespace: ns,
+					database: db,
+					kind: match since {
+						None => backup::BackupKind::Full,
+						Some(since) => backup::BackupKind::Incremental {
+							since,
+						},
+					},
+					until,
+				};
+				chn.send(backup::header_bytes(&header)?).await?;
+				let entries = match since {
+					None => txn.backup_full(&def, batch_size, &chn).await?,
+					Some(since) => txn.backup_changes(&def, since, until, &chn).await?,
+				};
+				chn.send(backup::end_bytes(entries)?).await?;
+				Ok(header)
+			}
+			.await;
+			txn.cancel().await?;
+			res
+		})
+	}
+
+	/// Restores a binary backup into the session database
+	///
+	/// The header must already have been read from `reader` with
+	/// [`backup::BackupHeader::read`]. A full backup creates the database,
+	/// which must not exist yet, while an incremental backup replays its
+	/// changes on top of an existing database.
+	///
+	/// A full backup is restored into a staging database, which is removed
+	/// if the restore fails, and which is only renamed to the session
+	/// database once every entry has been restored.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip_all)]
+	pub async fn restore<R: tokio::io::AsyncRead + Unpin + Send>(
+		&self,
+		sess: &Session,
+		header: &backup::BackupHeader,
+		mut reader: R,
+	) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		if header.kind != backup::BackupKind::Full {
+			let txn = self.transaction(Read, Optimistic).await?;
+			let exists = catch!(txn, txn.get_db_by_name(&ns, &db, None).await).is_some();
+			txn.cancel().await?;
+			ensure!(
+				exists,
+				Error::DbNotFound {
+					name: db.clone(),
+				}
+			);
+			return self.restore_changes(&ns, &db, &mut reader).await;
+		}
+		// A leftover staging database never blocks a later restore
+		let staging = format!("__restore_{db}_{}", Uuid::now_v7().simple());
+		let res = self.restore_full(&ns, &db, &staging, &mut reader).await;
+		if res.is_err() {
+			// Remove whatever was restored, so that the restore can be retried
+			let cleanup = async {
+				let txn = self.transaction(Write, Optimistic).await?;
+				catch!(txn, txn.del_db_deferred(&ns, &staging, false).await);
+				txn.commit().await
+			};
+			if let Err(e) = cleanup.await {
+				warn!(target: TARGET, "Failed to remove the staging database '{staging}': {e}");
+			}
+		}
+		res
+	}
+
+	/// Restores the entries of a full backup into a staging database, and
+	/// renames it once complete.
+	async fn restore_full<R: tokio::io::AsyncRead + Unpin + Send>(
+		&self,
+		ns: &str,
+		db: &str,
+		staging: &str,
+		reader: &mut R,
+	) -> Result<()> {
+		let mut target = None;
+		let mut entries = 0;
+		loop {
+			match backup::read_entry(reader).await? {
+				backup::BackupEntry::Database(def) => {
+					ensure!(target.is_none(), backup::invalid("unexpected database definition"));
+					let txn = self.transaction(Write, Optimistic).await?;
+					target = Some(catch!(txn, txn.restore_database(ns, db, staging, &def).await));
+					txn.commit().await?;
+				}
+				backup::BackupEntry::Pairs(pairs) => {
+					let Some(target) = target.as_mut() else {
+						bail!(backup::invalid("records precede the database definition"));
+					};
+					let txn = self.transaction(Write, Optimistic).await?;
+					catch!(txn, txn.restore_pairs(target, pairs).await);
+					txn.commit().await?;
+				}
+				backup::BackupEntry::Changes(_) => {
+					bail!(backup::invalid("unexpected changes in a full backup"));
+				}
+				backup::BackupEntry::End {
+					entries: expected,
+				} => {
+					ensure!(
+						target.is_some() && entries == expected,
+						backup::invalid("the backup is incomplete")
+					);
+					let txn = self.transaction(Write, Optimistic).await?;
+					catch!(txn, txn.restore_swap(ns, db, staging).await);
+					return txn.commit().await;
+				}
+			}
+			entries += 1;
+		}
+	}
+
+	/// Replays the changes of an incremental backup on top of an existing
+	/// database.
+	async fn restore_changes<R: tokio::io::AsyncRead + Unpin + Send>(
+		&self,
+		ns: &str,
+		db: &str,
+		reader: &mut R,
+	) -> Result<()> {
+		let sess = Session::owner().with_ns(ns).with_db(db);
+		let mut entries = 0;
+		loop {
+			match backup::read_entry(reader).await? {
+				backup::BackupEntry::Changes(changes) => {
+					let sql = backup::changes_script(changes);
+					for res in
+						self.execute(&sql, &sess, None).await.map_err(|e| anyhow::anyhow!(e))?
+					{
+						res.result.map_err(|e| anyhow::anyhow!(e))?;
+					}
+				}
+				backup::BackupEntry::End {
+					entries: expected,
+				} => {
+					ensure!(entries == expected, backup::invalid("the backup is incomplete"));
+					return Ok(());
+				}
+				_ => bail!(backup::invalid("unexpected entry in an incremental backup")),
+			}
+			entries += 1;
+		}
+	}
+
+	/// Checks every table in the datastore for keys which no longer belong
+	/// to a record or definition, and optionally deletes them
+	///
+	/// This is intended to run against a datastore which is not in use, as
+	/// each table is checked in its own read transaction. See [`fix`] for the
+	/// inconsistencies which are detected.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn fix(&self, repair: bool) -> Result<fix::FixReport> {
+		let mut report = fix::FixReport::default();
+		// Fetch all namespaces
+		let nss = {
+			let txn = self.transaction(Read, Optimistic).await?;
+			let res = catch!(txn, txn.all_ns(None).await);
+			txn.cancel().await?;
+			res
+		};
+		// Loop over all namespaces
+		for ns in nss.iter() {
+			// Fetch all databases
+			let dbs = {
+				let txn = self.transaction(Read, Optimistic).await?;
+				let res = catch!(txn, txn.all_db(ns.namespace_id, None).await);
+				txn.cancel().await?;
+				res
+			};
+			// Loop over all databases
+			for db in dbs.iter() {
+				// Fetch all tables
+				let tbs = {
+					let txn = self.transaction(Read, Optimistic).await?;
+					let res = catch!(txn, txn.all_tb(ns.namespace_id, db.database_id, None).await);
+					txn.cancel().await?;
+					res
+				};
+				// Loop over all tables
+				for tb in tbs.iter() {
+					trace!(target: TARGET, "Checking table {}/{}/{}", ns.name, db.name, tb.name);
+					let txn = self.transaction(Read, Optimistic).await?;
+					let issues = catch!(txn, txn.check_table(ns, db, tb).await);
+					txn.cancel().await?;
+					report.issues.extend(issues);
+				}
+			}
+		}
+		// Delete the inconsistent keys in batches
+		if repair {
+			for issues in report.issues.chunks(NORMAL_BATCH_SIZE as usize) {
+				let txn = self.transaction(Write, Optimistic).await?;
+				for issue in issues {
+					catch!(txn, issue.repair(&txn).await);
+				}
+				txn.commit().await?;
+			}
+			report.repaired = true;
+		}
+		Ok(report)
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	#[allow(clippy::needless_pass_by_value)] // Public API: ergonomic for callers passing `ResourceKind::X.on_db(ns, db)` inline.
@@ -0,0 +1,720 @@
+//! Encryption at rest for the on-disk storage engines.
+//!
+//! When a key is configured, every value written through a RocksDB or
+//! SurrealKV transaction is sealed with AES-256-GCM before it reaches the
+//! storage engine, and opened again when it is read back. Keys are left in
+//! plaintext: range scans rely on the byte ordering of the stored keys, which
+//! an encryption scheme would destroy.
+//!
+//! Each stored value is laid out as
+//!
+//! ```text
+//! [version: u8][key id: u32 BE][nonce: 12 bytes][ciphertext + tag]
+//! ```
+//!
+//! The storage key is used as the additional authenticated data, so an
+//! encrypted value can not be moved under a different key without failing
+//! authentication.
+//!
+//! Keys are supplied by a [`KeyProvider`], read from a file, from an
+//! environment variable, or from a provider registered by the embedding
+//! application (for instance one backed by a KMS). A key ring holds one or
+//! more keys: the last one is the active key used to encrypt new values, the
+//! others are retired keys which are only used to decrypt existing values.
+//! Values written under a retired key are re-encrypted with the active key
+//! by a background task ([`Datastore::reencryption`]), in batches of short
+//! transactions. The progress of the task is stored in the keyspace, and once
+//! it completes a message is logged to say that the retired keys can be
+//! removed.
+//!
+//! Encryption must be enabled on a new datastore: existing plaintext values
+//! can not be read through an encrypted transaction. Any value found in
+//! plaintext during a re-encryption pass is sealed with the active key rather
+//! than failing the pass.
+//!
+//! [`Datastore::reencryption`]: crate::kvs::Datastore::reencryption
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use ring::aead::{AES_256_GCM, Aad, LessSafeKey, NONCE_LEN, Nonce, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use super::api::{BoxFut, GetMultiResult, KeysResult, ScanCursorKeys, ScanResult, Transactable};
+use super::err::{Error, Result};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Direction, Key, Val};
+use crate::cnf::{Config, ConfigMap};
+
+const TARGET: &str = "surrealdb::core::kvs::encryption";
+
+/// The version of the encrypted value layout.
+const VERSION: u8 = 1;
+
+/// The length of the header prepended to each encrypted value.
+const HEADER_LEN: usize = 1 + 4 + NONCE_LEN;
+
+/// The length in bytes of an AES-256 key.
+const KEY_LEN: usize = 32;
+
+// --------------------------------------------------
+// Configuration
+// --------------------------------------------------
+
+/// Encryption at rest configuration, shared by the on-disk storage engines.
+///
+/// At most one key source can be configured. When none is configured,
+/// values are stored in plaintext.
+#[derive(Debug, Clone, Default)]
+pub struct EncryptionConfig {
+	/// A file containing the encryption keys
+	pub key_file: Option<PathBuf>,
+	/// The name of an environment variable containing the encryption keys
+	pub key_env: Option<String>,
+	/// The name of a key provider registered with [`register_key_provider`]
+	pub key_provider: Option<String>,
+}
+
+impl Config for EncryptionConfig {
+	fn parse(&mut self, map: &ConfigMap) {
+		map.parse_key_option("datastore_encryption_key_file", &mut self.key_file)
+			.parse_key_option("datastore_encryption_key_env", &mut self.key_env)
+			.parse_key_option("datastore_encryption_key_provider", &mut self.key_provider);
+	}
+}
+
+impl EncryptionConfig {
+	/// Load the key ring from the configured key source, if any.
+	pub(crate) fn key_ring(&self) -> Result<Option<Arc<KeyRing>>> {
+		let provider: Arc<dyn KeyProvider> =
+			match (&self.key_file, &self.key_env, &self.key_provider) {
+				(None, None, None) => return Ok(None),
+				(Some(path), None, None) => Arc::new(FileKeyProvider::new(path.clone())),
+				(None, Some(var), None) => Arc::new(EnvKeyProvider::new(var.clone())),
+				(None, None, Some(name)) => key_provider(name).ok_or_else(|| {
+					Error::Encryption(format!("The key provider '{name}' is not registered"))
+				})?,
+				_ => {
+					return Err(Error::Encryption(
+						"Only one of an encryption key file, environment variable, or provider can be configured"
+							.to_owned(),
+					));
+				}
+			};
+		let keys = provider.load()?;
+		info!(target: TARGET, "Encryption at rest enabled with {keys:?}");
+		Ok(Some(Arc::new(keys)))
+	}
+}
+
+// --------------------------------------------------
+// Key providers
+// --------------------------------------------------
+
+/// A source of encryption keys.
+///
+/// Implement this trait to fetch the keys from an external key management
+/// service, and register the implementation with [`register_key_provider`].
+pub trait KeyProvider: Send + Sync {
+	/// Load the key ring used to encrypt and decrypt values.
+	fn load(&self) -> Result<KeyRing>;
+}
+
+/// The key providers registered by the embedding application.
+static KEY_PROVIDERS: LazyLock<RwLock<HashMap<String, Arc<dyn KeyProvider>>>> =
+	LazyLock::new(Default::default);
+
+/// Register a key provider, which a datastore selects with the
+/// `encryption_key_provider` option.
+pub fn register_key_provider(name: &str, provider: Arc<dyn KeyProvider>) {
+	KEY_PROVIDERS.write().unwrap_or_else(|e| e.into_inner()).insert(name.to_owned(), provider);
+}
+
+/// Get a registered key provider.
+fn key_provider(name: &str) -> Option<Arc<dyn KeyProvider>> {
+	KEY_PROVIDERS.read().unwrap_or_else(|e| e.into_inner()).get(name).cloned()
+}
+
+/// Reads the key ring from a file, in the format described in
+/// [`KeyRing::parse`].
+struct FileKeyProvider {
+	path: PathBuf,
+}
+
+impl FileKeyProvider {
+	fn new(path: PathBuf) -> Self {
+		Self {
+			path,
+		}
+	}
+}
+
+impl KeyProvider for FileKeyProvider {
+	fn load(&self) -> Result<KeyRing> {
+		let keys = std::fs::read_to_string(&self.path).map_err(|e| {
+			Error::Encryption(format!(
+				"Unable to read the encryption key file '{}': {e}",
+				self.path.display()
+			))
+		})?;
+		KeyRing::parse(&keys)
+	}
+}
+
+/// Reads the key ring from an environment variable, in the format described
+/// in [`KeyRing::parse`].
+struct EnvKeyProvider {
+	var: String,
+}
+
+impl EnvKeyProvider {
+	fn new(var: String) -> Self {
+		Self {
+			var,
+		}
+	}
+}
+
+impl KeyProvider for EnvKeyProvider {
+	fn load(&self) -> Result<KeyRing> {
+		let keys = std::env::var(&self.var).map_err(|e| {
+			Error::Encryption(format!(
+				"Unable to read the encryption keys from the environment variable '{}': {e}",
+				self.var
+			))
+		})?;
+		KeyRing::parse(&keys)
+	}
+}
+
+// --------------------------------------------------
+// Key ring
+// --------------------------------------------------
+
+/// A set of AES-256 keys, identified by a numeric id.
+pub struct KeyRing {
+	/// The id of the key used to encrypt new values
+	active: u32,
+	/// All the keys, including the active one
+	keys: HashMap<u32, LessSafeKey>,
+	/// The source of the random nonces
+	rng: SystemRandom,
+}
+
+impl fmt::Debug for KeyRing {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut ids: Vec<_> = self.keys.keys().collect();
+		ids.sort_unstable();
+		f.debug_struct("KeyRing").field("active", &self.active).field("keys", &ids).finish()
+	}
+}
+
+impl KeyRing {
+	/// Create a key ring from a list of keys. The last key is the active one.
+	pub fn new(keys: Vec<(u32, [u8; KEY_LEN])>) -> Result<Self> {
+		let Some((active, _)) = keys.last() else {
+			return Err(Error::Encryption("No encryption key was provided".to_owned()));
+		};
+		let active = *active;
+		let mut ring = HashMap::with_capacity(keys.len());
+		for (id, key) in keys {
+			let key = UnboundKey::new(&AES_256_GCM, &key)
+				.map_err(|_| Error::Encryption(format!("The encryption key {id} is invalid")))?;
+			if ring.insert(id, LessSafeKey::new(key)).is_some() {
+				return Err(Error::Encryption(format!("The encryption key {id} is defined twice")));
+			}
+		}
+		Ok(Self {
+			active,
+			keys: ring,
+			rng: SystemRandom::new(),
+		})
+	}
+
+	/// Parse a key ring from a list of hex encoded 256-bit keys, separated by
+	/// new lines or commas. Each key can be prefixed with its id, as in
+	/// `2:<hex>`, and otherwise takes its position in the list as id. The
+	/// last key is the active one.
+	pub fn parse(s: &str) -> Result<Self> {
+		let mut keys = Vec::new();
+		let entries = s.split([',', '\n']).map(str::trim).filter(|x| !x.is_empty());
+		for (pos, entry) in entries.enumerate() {
+			let (id, hex) = match entry.split_once(':') {
+				Some((id, hex)) => {
+					let id = id.trim().parse().map_err(|_| {
+						Error::Encryption(format!("The encryption key id '{id}' is invalid"))
+					})?;
+					(id, hex.trim())
+				}
+				None => (pos as u32, entry),
+			};
+			let mut key = [0u8; KEY_LEN];
+			hex::decode_to_slice(hex, &mut key).map_err(|_| {
+				Error::Encryption(format!(
+					"The encryption key {id} must be {KEY_LEN} bytes encoded as hexadecimal"
+				))
+			})?;
+			keys.push((id, key));
+		}
+		Self::new(keys)
+	}
+
+	/// The id of the key used to encrypt new values.
+	pub fn active(&self) -> u32 {
+		self.active
+	}
+
+	/// Encrypt a value stored under the given key, with the active key.
+	pub(crate) fn encrypt(&self, key: &[u8], val: &[u8]) -> Result<Val> {
+		let mut nonce = [0u8; NONCE_LEN];
+		self.rng
+			.fill(&mut nonce)
+			.map_err(|_| Error::Encryption("Unable to generate a nonce".to_owned()))?;
+		let mut out = Vec::with_capacity(HEADER_LEN + val.len() + AES_256_GCM.tag_len());
+		out.push(VERSION);
+		out.extend_from_slice(&self.active.to_be_bytes());
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(val);
+		let tag = self.keys[&self.active]
+			.seal_in_place_separate_tag(
+				Nonce::assume_unique_for_key(nonce),
+				Aad::from(key),
+				&mut out[HEADER_LEN..],
+			)
+			.map_err(|_| Error::Encryption("Unable to encrypt a value".to_owned()))?;
+		out.extend_from_slice(tag.as_ref());
+		Ok(out)
+	}
+
+	/// Decrypt a value stored under the given key.
+	pub(crate) fn decrypt(&self, key: &[u8], val: &[u8]) -> Result<Val> {
+		let id = Self::key_id(val)?;
+		let cipher = self.keys.get(&id).ok_or_else(|| {
+			Error::Encryption(format!("The encryption key {id} is not in the key ring"))
+		})?;
+		let mut nonce = [0u8; NONCE_LEN];
+		nonce.copy_from_slice(&val[5..HEADER_LEN]);
+		let mut buf = val[HEADER_LEN..].to_vec();
+		let len = cipher
+			.open_in_place(Nonce::assume_unique_for_key(nonce), Aad::from(key), &mut buf)
+			.map_err(|_| Error::Encryption("Unable to decrypt a value".to_owned()))?
+			.len();
+		buf.truncate(len);
+		Ok(buf)
+	}
+
+	/// Get the id of the key an encrypted value was sealed with.
+	fn key_id(val: &[u8]) -> Result<u32> {
+		match val {
+			[VERSION, a, b, c, d, ..] if val.len() >= HEADER_LEN + AES_256_GCM.tag_len() => {
+				Ok(u32::from_be_bytes([*a, *b, *c, *d]))
+			}
+			_ => Err(Error::Encryption("The stored value is not encrypted".to_owned())),
+		}
+	}
+
+	/// Check whether a stored value has the layout of an encrypted value.
+	fn is_encrypted(val: &[u8]) -> bool {
+		Self::key_id(val).is_ok()
+	}
+
+	/// Check whether an encrypted value was sealed with a retired key.
+	fn is_retired(&self, val: &[u8]) -> Result<bool> {
+		Ok(Self::key_id(val)? != self.active)
+	}
+
+	/// Check whether the key ring holds any retired key.
+	fn has_retired(&self) -> bool {
+		self.keys.len() > 1
+	}
+}
+
+// --------------------------------------------------
+// Transaction
+// --------------------------------------------------
+
+/// A transaction which encrypts the values written to, and decrypts the
+/// values read from, an inner storage engine transaction.
+pub(crate) struct EncryptedTransaction {
+	/// The storage engine transaction
+	inner: Box<dyn Transactable>,
+	/// The keys used to encrypt and decrypt values
+	keys: Arc<KeyRing>,
+}
+
+impl EncryptedTransaction {
+	/// Wrap a transaction when encryption at rest is enabled.
+	pub(crate) fn wrap(
+		inner: Box<dyn Transactable>,
+		keys: Option<&Arc<KeyRing>>,
+	) -> Box<dyn Transactable> {
+		match keys {
+			Some(keys) => Box::new(Self {
+				inner,
+				keys: Arc::clone(keys),
+			}),
+			None => inner,
+		}
+	}
+
+	/// Decrypt the values of a range scan.
+	fn decrypt_scan(&self, res: ScanResult) -> Result<ScanResult> {
+		let mut value_bytes = 0u64;
+		let mut values = Vec::with_capacity(res.values.len());
+		for (k, v) in res.values {
+			let v = self.keys.decrypt(&k, &v)?;
+			value_bytes += v.len() as u64;
+			values.push((k, v));
+		}
+		Ok(ScanResult {
+			values,
+			key_bytes: res.key_bytes,
+			value_bytes,
+		})
+	}
+
+	/// Get the stored value of a key, if it matches the expected plaintext.
+	///
+	/// Encryption is not deterministic, so a conditional write can not pass
+	/// the expected plaintext down to the storage engine. The stored
+	/// ciphertext is checked here instead, and passed down as the condition,
+	/// which keeps the check atomic within the storage engine.
+	async fn check(&self, key: &Key, chk: Option<Val>) -> Result<Option<Val>> {
+		let Some(chk) = chk else {
+			return Ok(None);
+		};
+		match self.inner.get(key.clone(), None).await? {
+			Some(val) if self.keys.decrypt(key, &val)? == chk => Ok(Some(val)),
+			_ => Err(Error::TransactionConditionNotMet),
+		}
+	}
+}
+
+impl Transactable for EncryptedTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	fn cancel(&self) -> BoxFut<'_, Result<()>> {
+		self.inner.cancel()
+	}
+
+	fn commit(&self) -> BoxFut<'_, Result<()>> {
+		self.inner.commit()
+	}
+
+	fn exists(&self, key: Key, version: Option<u64>) -> BoxFut<'_, Result<bool>> {
+		self.inner.exists(key, version)
+	}
+
+	fn get(&self, key: Key, version: Option<u64>) -> BoxFut<'_, Result<Option<Val>>> {
+		Box::pin(async move {
+			match self.inner.get(key.clone(), version).await? {
+				Some(val) => self.keys.decrypt(&key, &val).map(Some),
+				None => Ok(None),
+			}
+		})
+	}
+
+	fn set(&self, key: Key, val: Val) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let val = self.keys.encrypt(&key, &val)?;
+			self.inner.set(key, val).await
+		})
+	}
+
+	fn put(&self, key: Key, val: Val) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let val = self.keys.encrypt(&key, &val)?;
+			self.inner.put(key, val).await
+		})
+	}
+
+	fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let chk = self.check(&key, chk).await?;
+			let val = self.keys.encrypt(&key, &val)?;
+			self.inner.putc(key, val, chk).await
+		})
+	}
+
+	fn del(&self, key: Key) -> BoxFut<'_, Result<()>> {
+		self.inner.del(key)
+	}
+
+	fn delc(&self, key: Key, chk: Option<Val>) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let chk = self.check(&key, chk).await?;
+			self.inner.delc(key, chk).await
+		})
+	}
+
+	fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<KeysResult>> {
+		self.inner.keys(rng, limit, skip, version)
+	}
+
+	fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<KeysResult>> {
+		self.inner.keysr(rng, limit, skip, version)
+	}
+
+	fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<ScanResult>> {
+		Box::pin(async move {
+			let res = self.inner.scan(rng, limit, skip, version).await?;
+			self.decrypt_scan(res)
+		})
+	}
+
+	fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<ScanResult>> {
+		Box::pin(async move {
+			let res = self.inner.scanr(rng, limit, skip, version).await?;
+			self.decrypt_scan(res)
+		})
+	}
+
+	// Key scans are unaffected by encryption, so they can use the native
+	// cursor of the storage engine. Value scans use the default cursor,
+	// which reads through `scan` and `scanr` above.
+	fn open_keys_cursor<'a>(
+		&'a self,
+		rng: Range<Key>,
+		dir: Direction,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'a, Result<Box<dyn ScanCursorKeys + 'a>>> {
+		self.inner.open_keys_cursor(rng, dir, skip, version)
+	}
+
+	fn replace(&self, key: Key, val: Val) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let val = self.keys.encrypt(&key, &val)?;
+			self.inner.replace(key, val).await
+		})
+	}
+
+	fn clr(&self, key: Key) -> BoxFut<'_, Result<()>> {
+		self.inner.clr(key)
+	}
+
+	fn clrc(&self, key: Key, chk: Option<Val>) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let chk = self.check(&key, chk).await?;
+			self.inner.clrc(key, chk).await
+		})
+	}
+
+	fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> BoxFut<'_, Result<GetMultiResult>> {
+		Box::pin(async move {
+			let res = self.inner.getm(keys.clone(), version).await?;
+			let mut value_bytes = 0u64;
+			let mut values = Vec::with_capacity(res.values.len());
+			for (key, val) in keys.iter().zip(res.values) {
+				let val = val.map(|v| self.keys.decrypt(key, &v)).transpose()?;
+				value_bytes += val.as_ref().map_or(0, |v| v.len() as u64);
+				values.push(val);
+			}
+			Ok(GetMultiResult {
+				values,
+				records: res.records,
+				value_bytes,
+			})
+		})
+	}
+
+	fn delp(&self, key: Key) -> BoxFut<'_, Result<()>> {
+		self.inner.delp(key)
+	}
+
+	fn delr(&self, rng: Range<Key>) -> BoxFut<'_, Result<()>> {
+		self.inner.delr(rng)
+	}
+
+	fn clrp(&self, key: Key) -> BoxFut<'_, Result<()>> {
+		self.inner.clrp(key)
+	}
+
+	fn clrr(&self, rng: Range<Key>) -> BoxFut<'_, Result<()>> {
+		self.inner.clrr(rng)
+	}
+
+	fn count(&self, rng: Range<Key>, version: Option<u64>) -> BoxFut<'_, Result<usize>> {
+		self.inner.count(rng, version)
+	}
+
+	fn new_save_point(&self) -> BoxFut<'_, Result<()>> {
+		self.inner.new_save_point()
+	}
+
+	fn release_last_save_point(&self) -> BoxFut<'_, Result<()>> {
+		self.inner.release_last_save_point()
+	}
+
+	fn rollback_to_save_point(&self) -> BoxFut<'_, Result<()>> {
+		self.inner.rollback_to_save_point()
+	}
+
+	fn timestamp(&self) -> BoxFut<'_, Result<BoxTimeStamp>> {
+		self.inner.timestamp()
+	}
+
+	fn safe_timestamp(&self) -> BoxFut<'_, Result<BoxTimeStamp>> {
+		self.inner.safe_timestamp()
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	fn compact(&self, range: Option<Range<Key>>) -> BoxFut<'_, anyhow::Result<()>> {
+		self.inner.compact(range)
+	}
+
+	/// Reads the stored ciphertext of the batch, so that only the values
+	/// sealed with a retired key are decrypted and written again. Values which
+	/// were left in plaintext are sealed with the active key.
+	fn reencrypt(&self, rng: Range<Key>, batch: u32) -> BoxFut<'_, Result<Option<Range<Key>>>> {
+		Box::pin(async move {
+			// Every value is sealed with the active key when no key is retired
+			if !self.keys.has_retired() {
+				return Ok(None);
+			}
+			let res = self.inner.batch_keys_vals(rng, batch, None).await?;
+			let mut count = 0usize;
+			let mut plaintext = 0usize;
+			for (k, v) in res.result {
+				let v = if !KeyRing::is_encrypted(&v) {
+					plaintext += 1;
+					v
+				} else if self.keys.is_retired(&v)? {
+					self.keys.decrypt(&k, &v)?
+				} else {
+					continue;
+				};
+				let v = self.keys.encrypt(&k, &v)?;
+				self.inner.set(k, v).await?;
+				count += 1;
+			}
+			if plaintext > 0 {
+				warn!(target: TARGET, "Encrypted {plaintext} values which were stored in plaintext");
+			}
+			if count > 0 {
+				debug!(target: TARGET, "Re-encrypted {count} values with encryption key {}", self.keys.active);
+			}
+			Ok(res.next)
+		})
+	}
+
+	fn encryption_key(&self) -> Option<u32> {
+		Some(self.keys.active)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key_ring(keys: &[(u32, u8)]) -> KeyRing {
+		KeyRing::new(keys.iter().map(|(id, b)| (*id, [*b; KEY_LEN])).collect()).unwrap()
+	}
+
+	#[test]
+	fn encrypt_round_trip() {
+		let keys = key_ring(&[(1, 7)]);
+		let val = keys.encrypt(b"key", b"value").unwrap();
+		assert_ne!(&val[HEADER_LEN..], b"value");
+		assert_eq!(KeyRing::key_id(&val).unwrap(), 1);
+		assert_eq!(keys.decrypt(b"key", &val).unwrap(), b"value");
+		// Encryption is not deterministic
+		assert_ne!(keys.encrypt(b"key", b"value").unwrap(), val);
+	}
+
+	#[test]
+	fn decrypt_is_bound_to_the_key() {
+		let keys = key_ring(&[(1, 7)]);
+		let val = keys.encrypt(b"key", b"value").unwrap();
+		assert!(matches!(keys.decrypt(b"other", &val), Err(Error::Encryption(_))));
+		assert!(matches!(keys.decrypt(b"key", b"value"), Err(Error::Encryption(_))));
+	}
+
+	#[test]
+	fn plaintext_is_told_apart() {
+		let keys = key_ring(&[(1, 7)]);
+		assert!(KeyRing::is_encrypted(&keys.encrypt(b"key", b"value").unwrap()));
+		assert!(!KeyRing::is_encrypted(b"value"));
+		assert!(!KeyRing::is_encrypted(&[VERSION, 0, 0, 0, 1]));
+	}
+
+	#[test]
+	fn rotation_keeps_retired_keys_readable() {
+		let old = key_ring(&[(1, 7)]);
+		let val = old.encrypt(b"key", b"value").unwrap();
+		let new = key_ring(&[(1, 7), (2, 9)]);
+		assert_eq!(new.active(), 2);
+		assert!(new.is_retired(&val).unwrap());
+		assert_eq!(new.decrypt(b"key", &val).unwrap(), b"value");
+		let val = new.encrypt(b"key", b"value").unwrap();
+		assert!(!new.is_retired(&val).unwrap());
+		// A ring without the key can not read the value
+		assert!(matches!(old.decrypt(b"key", &val), Err(Error::Encryption(_))));
+	}
+
+	#[test]
+	fn parse_key_ring() {
+		let a = "00".repeat(KEY_LEN);
+		let b = "ff".repeat(KEY_LEN);
+		assert_eq!(KeyRing::parse(&format!("{a}\n{b}\n")).unwrap().active(), 1);
+		assert_eq!(KeyRing::parse(&format!("3:{a},7:{b}")).unwrap().active(), 7);
+		assert!(KeyRing::parse("").is_err());
+		assert!(KeyRing::parse("abcd").is_err());
+		assert!(KeyRing::parse(&format!("1:{a},1:{b}")).is_err());
+	}
+
+	#[test]
+	fn parse_config() {
+		let config = ConfigMap::from_config_string("encryption_key_env=DB_KEYS")
+			.map_keys(|x| format!("datastore_{x}"))
+			.load::<EncryptionConfig>();
+		assert_eq!(config.key_env.as_deref(), Some("DB_KEYS"));
+		assert!(config.key_file.is_none());
+		let config = EncryptionConfig {
+			key_file: Some("keys".into()),
+			key_env: Some("DB_KEYS".into()),
+			key_provider: None,
+		};
+		assert!(config.key_ring().is_err());
+		assert!(EncryptionConfig::default().key_ring().unwrap().is_none());
+	}
+}
@@ -78,6 +78,10 @@ pub enum Error {
 
 	#[error("The storage layer does not support compaction requests.")]
 	CompactionNotSupported,
+
+	/// A value could not be encrypted or decrypted
+	#[error("There was a problem with encryption at rest: {0}")]
+	Encryption(String),
 }
 
 impl Error {
@@ -238,6 +242,36 @@ impl From<tikv::Error> for Error {
 	}
 }
 
+#[cfg(feature = "kv-redb")]
+impl From<redb::Error> for Error {
+	fn from(e: redb::Error) -> Error {
+		match e {
+			redb::Error::DatabaseAlreadyOpen => Error::Datastore(e.to_string()),
+			redb::Error::Corrupted(_) => Error::Datastore(e.to_string()),
+			redb::Error::UpgradeRequired(_) => Error::Datastore(e.to_string()),
+			redb::Error::Io(_) => Error::Datastore(e.to_string()),
+			_ => Error::Transaction(e.to_string()),
+		}
+	}
+}
+
+// The specific redb error types all convert into the top-level `redb::Error`
+#[cfg(feature = "kv-redb")]
+macro_rules! impl_from_redb_error {
+	($($err:ident),* $(,)?) => {
+		$(
+			impl From<redb::$err> for Error {
+				fn from(e: redb::$err) -> Error {
+					Error::from(redb::Error::from(e))
+				}
+			}
+		)*
+	};
+}
+
+#[cfg(feature = "kv-redb")]
+impl_from_redb_error!(DatabaseError, TransactionError, TableError, StorageError, CommitError);
+
 // Conversion from anyhow::Error for compatibility with existing code
 impl From<anyhow::Error> for Error {
 	fn from(e: anyhow::Error) -> Self {
@@ -0,0 +1,219 @@
+//! Deletion of records which have outlived the TTL of their table.
+//!
+//! A table defined with `TTL <duration> ON <field>` expires each record once
+//! the duration has passed since the datetime stored in the field. Expired
+//! records are removed with a regular `DELETE` statement, so table events,
+//! live queries, and change feeds observe the deletion like any other.
+//!
+//! Expiry runs as a lease-guarded background task, so only a single node in
+//! the cluster deletes expired records at any one time
+//! ([`Datastore::record_expiry`]). Every record which holds a datetime in the
+//! TTL field has an expiry entry ([`crate::key::table::te`]), ordered by that
+//! datetime, so each pass only reads the entries which are due, in bounded
+//! batches, resuming each batch from the last key read. The entries are
+//! written alongside the records, and rebuilt when the TTL field of a table
+//! changes ([`rebuild`]).
+
+use anyhow::Result;
+use surrealdb_strand::Strand;
+use surrealdb_types::ToSql;
+
+use crate::catalog::providers::{DatabaseProvider, NamespaceProvider, TableProvider};
+use crate::catalog::{DatabaseId, NamespaceId, Record};
+use crate::dbs::Session;
+use crate::expr::Ttl;
+use crate::key::record;
+use crate::key::table::te;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::{Read, Write};
+use crate::kvs::tasklease::LeaseHandler;
+use crate::kvs::{Datastore, KVValue, Key, Transaction};
+use crate::types::PublicVariables;
+use crate::val::{Array, Datetime, RecordId, TableName, Value};
+
+const TARGET: &str = "surrealdb::core::kvs::expiry";
+
+/// The maximum number of entries scanned from a table in one batch.
+const BATCH_SIZE: u32 = 1000;
+
+/// A table which has a TTL defined.
+struct Expiring {
+	/// The id of the namespace
+	ns_id: NamespaceId,
+	/// The id of the database
+	db_id: DatabaseId,
+	/// The name of the namespace
+	ns: Strand,
+	/// The name of the database
+	db: Strand,
+	/// The name of the table
+	tb: TableName,
+	/// The time to live of the table records
+	ttl: Ttl,
+}
+
+/// Run one expiry pass over every table which has a TTL defined.
+///
+/// Each table is processed independently: a failure to delete the expired
+/// records of one table is logged, and retried on the next pass.
+pub(crate) async fn process(ds: &Datastore, lh: &LeaseHandler) -> Result<()> {
+	// Collect all expiring tables in a short-lived read transaction
+	let txn = ds.transaction(Read, Optimistic).await?;
+	let mut tables = Vec::new();
+	for ns in txn.all_ns(None).await?.iter() {
+		for db in txn.all_db(ns.namespace_id, None).await?.iter() {
+			for tb in txn.all_tb(db.namespace_id, db.database_id, None).await?.iter() {
+				if let Some(ttl) = &tb.ttl {
+					tables.push(Expiring {
+						ns_id: db.namespace_id,
+						db_id: db.database_id,
+						ns: ns.name.clone(),
+						db: db.name.clone(),
+						tb: tb.name.clone(),
+						ttl: ttl.clone(),
+					});
+				}
+			}
+		}
+	}
+	txn.cancel().await?;
+	// Delete the expired records of each table
+	for tb in tables {
+		if let Err(e) = process_table(ds, lh, &tb).await {
+			warn!(target: TARGET, "Failed to delete expired records on {}:{}:{}: {e}", tb.ns, tb.db, tb.tb);
+		}
+		// Possibly renew the lease
+		lh.try_maintain_lease().await?;
+		// Yield execution
+		yield_now!();
+	}
+	Ok(())
+}
+
+/// Delete the expired records of a single table, in batches.
+///
+/// Each batch reads the next range of due expiry entries in a short-lived
+/// read transaction, and deletes the records they point to with a regular
+/// `DELETE` statement. The statement checks the field again, so a record which
+/// was updated since it was read is not deleted. Deleting a record removes its
+/// expiry entry, and any entry of the batch which is left behind no longer
+/// matches its record, so it is removed to let the next pass move on.
+async fn process_table(ds: &Datastore, lh: &LeaseHandler, tb: &Expiring) -> Result<()> {
+	// Records whose field is at or before the cutoff have expired
+	let Ok(ttl) = chrono::Duration::from_std(tb.ttl.duration) else {
+		return Ok(());
+	};
+	let Some(cutoff) = Datetime::now().0.checked_sub_signed(ttl) else {
+		return Ok(());
+	};
+	let cutoff = Datetime(cutoff);
+	// Only datetimes can expire, as other values sort before any datetime
+	let field = tb.ttl.field.to_sql();
+	let sql =
+		format!("DELETE $ids WHERE type::is_datetime({field}) AND {field} <= $cutoff RETURN NONE");
+	let sess = Session::owner().with_ns(tb.ns.as_str()).with_db(tb.db.as_str());
+	// Scan the due expiry entries, resuming each batch after the last key read
+	let beg = te::prefix(tb.ns_id, tb.db_id, &tb.tb)?;
+	let end = te::until(tb.ns_id, tb.db_id, &tb.tb, &cutoff)?;
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		// Find the expired records in this batch
+		let txn = ds.transaction(Read, Optimistic).await?;
+		let batch = txn.batch_keys(rng, BATCH_SIZE, None).await;
+		txn.cancel().await?;
+		let batch = batch?;
+		next = batch.next;
+		if batch.result.is_empty() {
+			break;
+		}
+		let ids = batch
+			.result
+			.iter()
+			.map(|k| {
+				let k = te::Te::decode_key(k)?;
+				Ok(Value::RecordId(RecordId {
+					table: k.tb.into_owned(),
+					key: k.id,
+				}))
+			})
+			.collect::<Result<Vec<_>>>()?;
+		// Delete the expired records
+		let mut vars = PublicVariables::new();
+		vars.insert("ids", crate::val::convert_value_to_public_value(Value::Array(Array(ids)))?);
+		vars.insert("cutoff", crate::val::convert_value_to_public_value(Value::Datetime(cutoff))?);
+		for r in ds.execute(&sql, &sess, Some(vars)).await.map_err(anyhow::Error::new)? {
+			r.result.map_err(anyhow::Error::new)?;
+		}
+		// Remove the entries which no longer match their record
+		let txn = ds.transaction(Write, Optimistic).await?;
+		run!(txn, remove_stale(&txn, tb, &batch.result).await)?;
+		// Possibly renew the lease
+		lh.try_maintain_lease().await?;
+		// Yield execution
+		yield_now!();
+	}
+	Ok(())
+}
+
+/// Remove the expiry entries whose record is gone, or whose field no longer
+/// holds the datetime the entry was stored under.
+async fn remove_stale(txn: &Transaction, tb: &Expiring, keys: &[Key]) -> Result<()> {
+	for k in keys {
+		if !txn.exists(k, None).await? {
+			continue;
+		}
+		let entry = te::Te::decode_key(k)?;
+		let rec = txn.get_record(tb.ns_id, tb.db_id, &tb.tb, &entry.id, None).await?;
+		let current = match rec.data.pick(&tb.ttl.field) {
+			Value::Datetime(v) => Some((v.timestamp(), v.timestamp_subsec_nanos())),
+			_ => None,
+		};
+		if current != Some((entry.secs, entry.nanos)) {
+			txn.del(k).await?;
+		}
+	}
+	Ok(())
+}
+
+/// Rebuild the expiry entries of a table, after its TTL field changed.
+///
+/// The existing entries are removed, and when the table has a TTL an entry is
+/// written for every record which holds a datetime in the TTL field.
+pub(crate) async fn rebuild(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	ttl: Option<&Ttl>,
+) -> Result<()> {
+	// Remove the existing entries
+	let beg = te::prefix(ns, db, tb)?;
+	let mut end = beg.clone();
+	end.push(0xff);
+	txn.delr(beg..end).await?;
+	// Write an entry for each record of the table
+	let Some(ttl) = ttl else {
+		return Ok(());
+	};
+	let beg = record::prefix(ns, db, tb)?;
+	let end = record::suffix(ns, db, tb)?;
+	let mut next = Some(beg..end);
+	while let Some(rng) = next {
+		let batch = txn.batch_keys_vals(rng, BATCH_SIZE, None).await?;
+		next = batch.next;
+		for (k, v) in batch.result {
+			let k = record::RecordKey::decode_key(&k)?;
+			let rid = RecordId {
+				table: k.tb.into_owned(),
+				key: k.id,
+			};
+			let rec = Record::kv_decode_value(&v, rid.clone())?;
+			if let Value::Datetime(dt) = rec.data.pick(&ttl.field) {
+				txn.set(&te::new(ns, db, tb, &dt, &rid.key), &()).await?;
+			}
+		}
+		// Yield execution
+		yield_now!();
+	}
+	Ok(())
+}
@@ -371,6 +371,24 @@ impl Transaction {
 			chn.send(bytes!(format!("{};", event.to_sql()))).await?;
 		}
 		chn.send(bytes!("")).await?;
+		// Export all table sink definitions for this table
+		let sinks = self.all_tb_sinks(ns, db, &table.name, None).await?;
+		if !sinks.is_empty() {
+			for sink in sinks.iter() {
+				// The exported URL keeps its credentials, so that the sink can deliver once imported
+				let sink = sink.to_export_definition();
+				chn.send(bytes!(format!("{};", sink.to_sql()))).await?;
+			}
+			chn.send(bytes!("")).await?;
+		}
+		// Export all table consumer definitions for this table
+		let consumers = self.all_tb_consumers(ns, db, &table.name, None).await?;
+		if !consumers.is_empty() {
+			for consumer in consumers.iter() {
+				chn.send(bytes!(format!("{};", consumer.to_sql()))).await?;
+			}
+			chn.send(bytes!("")).await?;
+		}
 		// Everything ok
 		Ok(())
 	}
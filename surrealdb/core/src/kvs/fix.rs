@@ -0,0 +1,490 @@
+//! Offline consistency checks and repairs for the storage layer.
+//!
+//! Records, their index entries, their graph adjacency keys and their
+//! reference keys are normally written and removed together. Keys written by
+//! older versions, or left behind by a crash, can however outlive the record
+//! or definition they belong to. [`Transaction::check_table`] scans the keys
+//! of a single table and reports every key which no longer belongs to
+//! anything:
+//!
+//! - entries of unique, non-unique and spatial indexes pointing at a missing record,
+//! - index data stored under an index id which is no longer defined,
+//! - graph adjacency keys which are not part of an existing edge,
+//! - reference keys whose referencing record no longer exists,
+//! - index build state (`!bs`, `!bg`, `!bp`, `!br`) for an index which is no longer defined.
+//!
+//! Repairing an issue only ever deletes the keys which caused it. Index
+//! entries which are missing for an existing record are not detected here, as
+//! `REBUILD INDEX` already recreates them. The data of count, full-text and
+//! vector indexes does not point at records directly, so it is only checked
+//! for indexes which are no longer defined; `REBUILD INDEX` recreates it.
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+use anyhow::Result;
+use surrealdb_types::ToSql;
+
+use super::{KVKey, KVValue, Key, Transaction};
+use crate::catalog::providers::TableProvider;
+use crate::catalog::{
+	DatabaseDefinition, DatabaseId, Index, IndexId, NamespaceDefinition, NamespaceId, Record,
+	TableDefinition,
+};
+use crate::expr::dir::Dir;
+use crate::expr::paths::{IN, OUT};
+use crate::key::graph::{DecodedGraph, Graph};
+use crate::key::index::gh::Gh;
+use crate::key::r#ref::Ref;
+use crate::kvs::index::retire_durable_index;
+use crate::val::{RecordId, TableName, Value};
+
+/// The maximum number of keys read per scan.
+const SCAN_BATCH_SIZE: u32 = 1000;
+
+/// The kind of inconsistency found in the datastore.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IssueKind {
+	/// A unique, non-unique or spatial index entry points at a missing record.
+	OrphanedIndexEntry,
+	/// Index data is stored for an index which is not defined.
+	OrphanedIndexData,
+	/// A graph adjacency key is not part of an existing edge.
+	DanglingEdge,
+	/// A reference key is kept for a referencing record which does not exist.
+	DanglingReference,
+	/// Index build state is stored for an index which is not defined.
+	StaleBuildState,
+}
+
+impl Display for IssueKind {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		match self {
+			Self::OrphanedIndexEntry => f.write_str("orphaned index entry"),
+			Self::OrphanedIndexData => f.write_str("orphaned index data"),
+			Self::DanglingEdge => f.write_str("dangling edge"),
+			Self::DanglingReference => f.write_str("dangling reference"),
+			Self::StaleBuildState => f.write_str("stale index build state"),
+		}
+	}
+}
+
+/// An inconsistency found in a table, together with the keys which repair it.
+#[derive(Clone, Debug)]
+pub struct Issue {
+	pub kind: IssueKind,
+	pub namespace: String,
+	pub database: String,
+	pub table: String,
+	pub detail: String,
+	repair: Repair,
+}
+
+impl Display for Issue {
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{}/{} table `{}`: {}: {}",
+			self.namespace, self.database, self.table, self.kind, self.detail
+		)
+	}
+}
+
+/// The keys which are deleted to repair an issue.
+#[derive(Clone, Debug)]
+enum Repair {
+	Key(Key),
+	Range(Range<Key>),
+	RetireIndex {
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: TableName,
+		ix: IndexId,
+	},
+}
+
+impl Issue {
+	/// Deletes the keys which caused this issue.
+	pub(crate) async fn repair(&self, txn: &Transaction) -> Result<()> {
+		match &self.repair {
+			Repair::Key(key) => txn.del(key).await,
+			Repair::Range(rng) => txn.delr(rng.clone()).await,
+			Repair::RetireIndex {
+				ns,
+				db,
+				tb,
+				ix,
+			} => retire_durable_index(txn, *ns, *db, tb, *ix).await,
+		}
+	}
+}
+
+/// The result of checking a datastore.
+#[derive(Debug, Default)]
+pub struct FixReport {
+	/// Every inconsistency which was found.
+	pub issues: Vec<Issue>,
+	/// Whether the issues have been repaired.
+	pub repaired: bool,
+}
+
+/// The table being checked, used to attribute issues.
+struct Scope<'a> {
+	ns: &'a NamespaceDefinition,
+	db: &'a DatabaseDefinition,
+	tb: &'a TableDefinition,
+}
+
+impl Scope<'_> {
+	fn issue(&self, kind: IssueKind, detail: String, repair: Repair) -> Issue {
+		Issue {
+			kind,
+			namespace: self.ns.name.to_string(),
+			database: self.db.name.to_string(),
+			table: self.tb.name.to_string(),
+			detail,
+			repair,
+		}
+	}
+}
+
+impl Transaction {
+	/// Checks the keys stored under a single table.
+	pub(crate) async fn check_table(
+		&self,
+		ns: &NamespaceDefinition,
+		db: &DatabaseDefinition,
+		tb: &TableDefinition,
+	) -> Result<Vec<Issue>> {
+		let scope = Scope {
+			ns,
+			db,
+			tb,
+		};
+		let mut issues = Vec::new();
+		self.check_indexes(&scope, &mut issues).await?;
+		self.check_graph(&scope, &mut issues).await?;
+		self.check_references(&scope, &mut issues).await?;
+		Ok(issues)
+	}
+
+	async fn check_indexes(&self, scope: &Scope<'_>, issues: &mut Vec<Issue>) -> Result<()> {
+		let (ns, db, tb) = (scope.tb.namespace_id, scope.tb.database_id, &scope.tb.name);
+		let indexes = self.all_tb_indexes(ns, db, tb, None).await?;
+		let defined: BTreeSet<IndexId> = indexes.iter().map(|ix| ix.index_id).collect();
+		let root = crate::key::table::all::new(ns, db, tb).encode_key()?;
+
+		// Entries of unique, non-unique and spatial indexes point at their record
+		for ix in indexes.iter() {
+			let (beg, end) = match ix.index {
+				Index::Idx | Index::Uniq => (
+					crate::key::index::Index::prefix_beg(ns, db, tb, ix.index_id)?,
+					crate::key::index::Index::prefix_end(ns, db, tb, ix.index_id)?,
+				),
+				// The empty cell prefixes every geohash cell
+				Index::Spatial(_) => Gh::descendants_range(ns, db, tb, ix.index_id, "")?,
+				_ => continue,
+			};
+			let mut next = Some(beg..end);
+			while let Some(rng) = next {
+				let batch = self.batch_keys_vals(rng, SCAN_BATCH_SIZE, None).await?;
+				next = batch.next;
+				for (key, val) in batch.result {
+					let rid = RecordId::kv_decode_value(&val, ())?;
+					if !self
+						.exists(&crate::key::record::new(ns, db, &rid.table, &rid.key), None)
+						.await?
+					{
+						issues.push(scope.issue(
+							IssueKind::OrphanedIndexEntry,
+							format!(
+								"index `{}` refers to missing record {}",
+								ix.name,
+								rid.to_sql()
+							),
+							Repair::Key(key),
+						));
+					}
+				}
+			}
+		}
+
+		// Index data lives under `+{ix}`
+		let mut beg = root.clone();
+		beg.push(b'+');
+		let mut end = root.clone();
+		end.push(b'+' + 1);
+		for (ix, rng) in self.undefined_indexes(beg..end, root.len() + 1, &defined).await? {
+			issues.push(scope.issue(
+				IssueKind::OrphanedIndexData,
+				format!("data is stored for index id {} which is not defined", ix.0),
+				Repair::Range(rng),
+			));
+		}
+
+		// Build state lives under `!bs{ix}`, `!bg{ix}`, `!bp{ix}` and `!br{ix}`
+		let mut stale = BTreeSet::new();
+		for family in [b'g', b'p', b'r', b's'] {
+			let mut beg = root.clone();
+			beg.extend_from_slice(&[b'!', b'b', family]);
+			let mut end = root.clone();
+			end.extend_from_slice(&[b'!', b'b', family + 1]);
+			for (ix, _) in self.undefined_indexes(beg..end, root.len() + 3, &defined).await? {
+				stale.insert(ix);
+			}
+		}
+		for ix in stale {
+			issues.push(scope.issue(
+				IssueKind::StaleBuildState,
+				format!("build state is stored for index id {} which is not defined", ix.0),
+				Repair::RetireIndex {
+					ns,
+					db,
+					tb: tb.clone(),
+					ix,
+				},
+			));
+		}
+		Ok(())
+	}
+
+	/// Finds the index ids stored at `offset` in the keys of `rng` which are
+	/// not defined, together with the range of keys stored for each of them.
+	///
+	/// The scan reads a single key per index id, and then skips to the next.
+	async fn undefined_indexes(
+		&self,
+		rng: Range<Key>,
+		offset: usize,
+		defined: &BTreeSet<IndexId>,
+	) -> Result<Vec<(IndexId, Range<Key>)>> {
+		let mut found = Vec::new();
+		let mut beg = rng.start;
+		loop {
+			let keys = self.keys(beg..rng.end.clone(), 1, 0, None).await?;
+			let Some(key) = keys.first() else {
+				break;
+			};
+			let Some(bytes) = key.get(offset..offset + 4) else {
+				break;
+			};
+			let ix = IndexId(u32::from_be_bytes(bytes.try_into()?));
+			let prefix = key[..offset + 4].to_vec();
+			let mut next = prefix.clone();
+			next.push(0xff);
+			if !defined.contains(&ix) {
+				found.push((ix, prefix..next.clone()));
+			}
+			beg = next;
+		}
+		Ok(found)
+	}
+
+	async fn check_graph(&self, scope: &Scope<'_>, issues: &mut Vec<Issue>) -> Result<()> {
+		let (ns, db, tb) = (scope.tb.namespace_id, scope.tb.database_id, &scope.tb.name);
+		let root = crate::key::table::all::new(ns, db, tb).encode_key()?;
+		let mut beg = root.clone();
+		beg.push(b'~');
+		let mut end = root;
+		end.push(b'~' + 1);
+		// Keys are grouped by the record they are stored under
+		let mut origin: Option<(RecordId, Option<Record>)> = None;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys(rng, SCAN_BATCH_SIZE, None).await?;
+			next = batch.next;
+			for key in batch.result {
+				let (rid, dir, decoded) = Graph::decode_key_with_origin(&key)?;
+				if origin.as_ref().is_none_or(|(cached, _)| *cached != rid) {
+					let record = self.get_raw_record(ns, db, &rid).await?;
+					origin = Some((rid.clone(), record));
+				}
+				let record = origin.as_ref().and_then(|(_, record)| record.as_ref());
+				if !self.adjacency_is_valid(ns, db, &rid, record, dir, &decoded).await? {
+					let arrow = match dir {
+						Dir::In => "<-",
+						Dir::Out => "->",
+						Dir::Both => "<->",
+					};
+					issues.push(scope.issue(
+						IssueKind::DanglingEdge,
+						format!(
+							"{}{arrow}{} is not part of an existing edge",
+							rid.to_sql(),
+							decoded.edge.to_sql()
+						),
+						Repair::Key(key),
+					));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Checks whether a graph adjacency key belongs to an existing edge.
+	///
+	/// Inner keys are stored under the edge and name one of its endpoints,
+	/// while pointer keys are stored under an endpoint and name the edge. See
+	/// [`crate::key::graph`] for the layout. Endpoints themselves do not need
+	/// to exist, as only enforced relations require them to.
+	async fn adjacency_is_valid(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		origin: &RecordId,
+		record: Option<&Record>,
+		dir: Dir,
+		decoded: &DecodedGraph,
+	) -> Result<bool> {
+		// An inner key on the edge itself
+		if record.is_some_and(|edge| links(edge, dir, &decoded.edge)) {
+			return Ok(true);
+		}
+		// A pointer key on one of the endpoints of the edge
+		let Some(edge) = self.get_raw_record(ns, db, &decoded.edge).await? else {
+			return Ok(false);
+		};
+		let reverse = match dir {
+			Dir::In => Dir::Out,
+			Dir::Out => Dir::In,
+			Dir::Both => return Ok(false),
+		};
+		Ok(links(&edge, reverse, origin)
+			&& decoded.target.as_ref().is_none_or(|target| links(&edge, dir, target)))
+	}
+
+	async fn check_references(&self, scope: &Scope<'_>, issues: &mut Vec<Issue>) -> Result<()> {
+		let (ns, db, tb) = (scope.tb.namespace_id, scope.tb.database_id, &scope.tb.name);
+		let beg = crate::key::r#ref::prefix_tb(ns, db, tb)?;
+		let end = crate::key::r#ref::suffix_tb(ns, db, tb)?;
+		let mut next = Some(beg..end);
+		while let Some(rng) = next {
+			let batch = self.batch_keys(rng, SCAN_BATCH_SIZE, None).await?;
+			next = batch.next;
+			for key in batch.result {
+				let r = Ref::decode_key(&key)?;
+				if !self.exists(&crate::key::record::new(ns, db, &r.ft, &r.fk), None).await? {
+					let target = RecordId {
+						table: r.tb.into_owned(),
+						key: r.id.into_owned(),
+					};
+					let source = RecordId {
+						table: r.ft.into_owned(),
+						key: r.fk.into_owned(),
+					};
+					issues.push(scope.issue(
+						IssueKind::DanglingReference,
+						format!(
+							"{} is referenced by missing record {} through `{}`",
+							target.to_sql(),
+							source.to_sql(),
+							r.ff
+						),
+						Repair::Key(key),
+					));
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Reads a record without going through the transaction cache.
+	async fn get_raw_record(
+		&self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		rid: &RecordId,
+	) -> Result<Option<Record>> {
+		self.get(&crate::key::record::new(ns, db, &rid.table, &rid.key), None).await
+	}
+}
+
+/// Checks whether `edge` is an edge record whose `in` (for [`Dir::In`]) or
+/// `out` (for [`Dir::Out`]) is `vertex`.
+fn links(edge: &Record, dir: Dir, vertex: &RecordId) -> bool {
+	if !edge.is_edge() {
+		return false;
+	}
+	let field = match dir {
+		Dir::In => &IN,
+		Dir::Out => &OUT,
+		Dir::Both => return false,
+	};
+	matches!(edge.data.pick(field), Value::RecordId(ref rid) if rid == vertex)
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use surrealdb_strand::Strand;
+
+	use super::*;
+	use crate::catalog::providers::DatabaseProvider;
+	use crate::dbs::Session;
+	use crate::kvs::LockType::Optimistic;
+	use crate::kvs::{Datastore, TransactionType};
+	use crate::val::RecordIdKey;
+
+	async fn execute(ds: &Datastore, sql: &str) -> Result<()> {
+		let sess = Session::owner().with_ns("test").with_db("test");
+		for res in ds.execute(sql, &sess, None).await? {
+			res.result?;
+		}
+		Ok(())
+	}
+
+	fn count(report: &FixReport, kind: IssueKind) -> usize {
+		report.issues.iter().filter(|issue| issue.kind == kind).count()
+	}
+
+	#[tokio::test]
+	async fn fix_detects_and_repairs_orphaned_keys() -> Result<()> {
+		let ds = Datastore::new("memory").await?;
+		execute(
+			&ds,
+			"DEFINE INDEX name ON person FIELDS name;
+			DEFINE INDEX location ON person FIELDS location SPATIAL;
+			CREATE person:1 SET name = 'Tobie', location = (-0.118092, 51.509865);
+			CREATE person:2 SET name = 'Jaime';
+			RELATE person:1->knows:k->person:2;
+			RELATE person:1->knows:j->nobody:1;",
+		)
+		.await?;
+		// A consistent datastore has nothing to report
+		assert!(ds.fix(false).await?.issues.is_empty());
+
+		// Remove records and add keys behind the back of the document layer
+		let tx = ds.transaction(TransactionType::Write, Optimistic).await?;
+		let def = tx.get_db_by_name("test", "test", None).await?.unwrap();
+		let (ns, db) = (def.namespace_id, def.database_id);
+		let person = TableName::new("person");
+		let knows = TableName::new("knows");
+		tx.del(&crate::key::record::new(ns, db, &person, &RecordIdKey::Number(1))).await?;
+		tx.del(&crate::key::record::new(ns, db, &knows, &RecordIdKey::String(Strand::new("k"))))
+			.await?;
+		let mut key = crate::key::index::all::new(ns, db, &person, IndexId(999)).encode_key()?;
+		key.extend_from_slice(b"*data");
+		tx.set(&key, &Vec::new()).await?;
+		let key = crate::key::table::bs::Bs::new(ns, db, &person, IndexId(998)).encode_key()?;
+		tx.set(&key, &Vec::new()).await?;
+		tx.commit().await?;
+
+		let report = ds.fix(false).await?;
+		assert!(!report.repaired);
+		// The name and location index entries of the removed record
+		assert_eq!(count(&report, IssueKind::OrphanedIndexEntry), 2);
+		assert_eq!(count(&report, IssueKind::OrphanedIndexData), 1);
+		assert_eq!(count(&report, IssueKind::StaleBuildState), 1);
+		// The inner and pointer keys of the removed edge
+		assert_eq!(count(&report, IssueKind::DanglingEdge), 4);
+		assert_eq!(report.issues.len(), 8);
+		// A check leaves the datastore untouched
+		assert_eq!(ds.fix(false).await?.issues.len(), 8);
+
+		let report = ds.fix(true).await?;
+		assert!(report.repaired);
+		assert_eq!(report.issues.len(), 8);
+		assert!(ds.fix(false).await?.issues.is_empty());
+		Ok(())
+	}
+}
@@ -1658,6 +1658,7 @@ async fn count_index_duplicate_initial_build_does_not_overcount() -> Result<()>
 			index: Index::Count(None),
 			comment: None,
 			prepare_remove: false,
+			cond: None,
 		});
 		tx.put_tb_index(ns.namespace_id, db.database_id, &table.name, &index).await?;
 		tx.commit().await?;
@@ -13,9 +13,11 @@
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value
 //!   database
 //! - `mem`: in-memory database
+//! - `redb`: [redb](https://github.com/cberner/redb) an embeddable single-file key-value store
 
 pub mod config;
 pub mod export;
+pub mod fix;
 
 mod api;
 mod batch;
@@ -24,6 +26,8 @@ mod consts;
 mod cursor;
 mod direction;
 mod ds;
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv", feature = "kv-redb"))]
+mod encryption;
 mod err;
 mod into;
 mod key;
@@ -35,6 +39,7 @@ mod util;
 
 mod indxdb;
 mod mem;
+mod redb;
 mod rocksdb;
 mod surrealkv;
 mod tikv;
@@ -42,7 +47,9 @@ mod tikv;
 #[cfg(test)]
 mod tests;
 
+pub(crate) mod backup;
 pub(crate) mod cache;
+pub(crate) mod expiry;
 pub(crate) mod index;
 pub(crate) mod sequences;
 pub(crate) mod slowlog;
@@ -52,6 +59,7 @@ pub(crate) mod version;
 pub use api::{
 	GetMultiResult, KeysResult, ScanCursorKeys, ScanCursorVals, ScanResult, Transactable,
 };
+pub use backup::{BackupHeader, BackupKind};
 pub use consts::{
 	COUNT_BATCH_SIZE, ESTIMATED_BYTES_PER_KEY, ESTIMATED_BYTES_PER_KV, INDEXING_BATCH_SIZE,
 	NORMAL_BATCH_SIZE,
@@ -63,6 +71,8 @@ pub use ds::{
 	Builder, Datastore, DatastoreFlavor, Metric, Metrics, TransactionBuilder,
 	TransactionBuilderFactory, TransactionBuilderParts,
 };
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv", feature = "kv-redb"))]
+pub use encryption::{EncryptionConfig, KeyProvider, KeyRing, register_key_provider};
 pub use err::{Error, Result};
 pub use into::IntoBytes;
 pub(crate) use key::{KVKey, KVValue, impl_kv_key_storekey, impl_kv_value_revisioned};
@@ -0,0 +1,73 @@
+use crate::cnf::Config;
+use crate::kvs::EncryptionConfig;
+use crate::kvs::config::SyncMode;
+use crate::sys::TOTAL_SYSTEM_MEMORY;
+
+/// Configuration for the redb storage engine, parsed from query parameters.
+#[derive(Debug, Clone)]
+pub struct RedbConfig {
+	/// Disk sync mode.
+	pub sync_mode: SyncMode,
+	/// The page cache size in bytes (default: dynamic based on memory)
+	pub cache_size: usize,
+	/// Encryption at rest of the stored values (default: disabled)
+	pub encryption: EncryptionConfig,
+}
+
+const MIB: u64 = 1024 * 1024;
+const GIB: u64 = 1024 * MIB;
+
+fn default_cache_size() -> usize {
+	// Edge deployments are typically memory constrained, so the
+	// cache is kept to a small fraction of the available memory.
+	let mem = *TOTAL_SYSTEM_MEMORY;
+	(mem / 8).clamp(16 * MIB, GIB) as usize
+}
+
+impl Default for RedbConfig {
+	fn default() -> Self {
+		Self {
+			sync_mode: SyncMode::Every,
+			cache_size: default_cache_size(),
+			encryption: EncryptionConfig::default(),
+		}
+	}
+}
+
+impl Config for RedbConfig {
+	fn parse(&mut self, map: &crate::cnf::ConfigMap) {
+		map.parse_key("redb_cache_size", &mut self.cache_size);
+
+		if map.has_key("datastore_sync") {
+			map.parse_key("datastore_sync", &mut self.sync_mode);
+		} else {
+			map.parse_key("datastore_sync_data", &mut self.sync_mode);
+		}
+
+		self.encryption.parse(map);
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use crate::cnf::ConfigMap;
+	use crate::kvs::config::SyncMode;
+	use crate::kvs::redb::cnf::RedbConfig;
+
+	#[test]
+	fn test_redb_config_defaults() {
+		let map = ConfigMap::empty();
+
+		let config = map.load::<RedbConfig>();
+		assert_eq!(config.sync_mode, SyncMode::Every);
+		assert!(config.cache_size > 0);
+	}
+
+	#[test]
+	fn test_redb_config_from_params() {
+		let map =
+			ConfigMap::from_config_string("sync=never").map_keys(|x| format!("datastore_{x}"));
+		let config = map.load::<RedbConfig>();
+		assert_eq!(config.sync_mode, SyncMode::Never);
+	}
+}
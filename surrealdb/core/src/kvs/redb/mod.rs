@@ -0,0 +1,926 @@
+//! An embedded, single-file storage engine backed by redb.
+//!
+//! The engine is aimed at small edge deployments: it runs no background
+//! threads, and hands no work to a thread pool. Every call into redb runs on
+//! the task which makes it.
+//!
+//! Transactions run under snapshot isolation, which is weaker than the
+//! serializable isolation of the other storage engines: a commit only
+//! conflicts with another transaction which wrote one of the same keys since
+//! the snapshot was taken. Two transactions which each read a key the other
+//! writes can both commit (write skew).
+
+#![cfg(feature = "kv-redb")]
+
+mod cnf;
+
+use std::cmp::Ordering as KeyOrdering;
+use std::collections::BTreeMap;
+use std::ops::{Bound, ControlFlow, Range};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub use cnf::RedbConfig;
+use redb::{
+	AccessGuard, Database, Durability, ReadOnlyTable, ReadableTable, StorageError, TableDefinition,
+	WriteTransaction,
+};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::sync::{Mutex, MutexGuard};
+use tokio::task::block_in_place;
+use tracing::{info, warn};
+
+use super::api::{
+	BoxFut, KeySpan, KeyValSpan, KeyVisitor, KeysBatch, KeysResult, ScanChunkStats, ScanCursorKeys,
+	ScanCursorVals, ScanResult, ValVisitor, ValsBatch,
+};
+use super::config::SyncMode;
+use super::direction::Direction;
+use super::encryption::{EncryptedTransaction, KeyRing};
+use super::err::{Error, Result};
+use super::util::update_range;
+use crate::key::debug::Sprintable;
+use crate::kvs::api::Transactable;
+use crate::kvs::{Key, Val};
+
+const TARGET: &str = "surrealdb::core::kvs::redb";
+
+/// All keys are stored in a single ordered table within the database file.
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("surrealdb");
+
+/// The snapshot of the table a transaction reads from.
+type Snapshot = ReadOnlyTable<&'static [u8], &'static [u8]>;
+
+/// Run a blocking call into redb on the calling task.
+///
+/// On a multi-threaded runtime the worker hands its other tasks over to the
+/// rest of the runtime while the call blocks. On a current-thread runtime
+/// there is nowhere to hand them to, and the runtime waits for the call.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+	match Handle::try_current() {
+		Ok(rt) if rt.runtime_flavor() == RuntimeFlavor::MultiThread => block_in_place(f),
+		_ => f(),
+	}
+}
+
+pub struct Datastore {
+	/// The underlying single-file database
+	db: Arc<Database>,
+	/// The durability of each committed write transaction
+	durability: Durability,
+	/// The keys used for encryption at rest, if enabled
+	encryption: Option<Arc<KeyRing>>,
+}
+
+/// A redb transaction.
+///
+/// redb only allows a single write transaction at a time, and the datastore
+/// may open further write transactions (e.g. for sequences) while another is
+/// still in progress. Transactions therefore read from a consistent snapshot
+/// of the database and buffer their writes in memory. The buffered writes are
+/// applied in a short-lived redb write transaction on commit, failing with a
+/// retryable conflict if any of the written keys was changed since the
+/// snapshot was taken. Keys which were only read are not checked, so this is
+/// snapshot isolation, not serializable isolation.
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: AtomicBool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// The snapshot and buffered writes of this transaction
+	inner: Mutex<Inner>,
+	/// The underlying database, used to apply the writes on commit
+	db: Arc<Database>,
+	/// The durability of the commit
+	durability: Durability,
+}
+
+struct Inner {
+	/// The table as it was when the transaction started
+	snapshot: Snapshot,
+	/// The buffered writes, where `None` marks a deleted key
+	writes: BTreeMap<Key, Option<Val>>,
+	/// The undo log of each open save point
+	save_points: Vec<Vec<(Key, Option<Option<Val>>)>>,
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str, config: RedbConfig) -> Result<Datastore> {
+		// Load the encryption keys, if encryption at rest is enabled
+		let encryption = config.encryption.key_ring()?;
+		// Map the sync mode onto the commit durability
+		info!(target: TARGET, "Sync mode: {}", config.sync_mode);
+		let durability = match config.sync_mode {
+			SyncMode::Every => Durability::Immediate,
+			SyncMode::Never => Durability::Eventual,
+			SyncMode::Interval(_) => {
+				warn!(
+					target: TARGET,
+					"Interval sync is not supported by the redb storage engine, syncing on every commit instead"
+				);
+				Durability::Immediate
+			}
+		};
+		info!(target: TARGET, "Cache size: {}", config.cache_size);
+		let db = blocking(|| -> Result<Database> {
+			// Ensure the parent directory of the database file exists
+			if let Some(parent) = Path::new(path).parent() {
+				if !parent.as_os_str().is_empty() {
+					std::fs::create_dir_all(parent).map_err(|e| Error::Datastore(e.to_string()))?;
+				}
+			}
+			// Open or create the database file
+			let db = Database::builder().set_cache_size(config.cache_size).create(path)?;
+			// Ensure the table exists, so that snapshots can always open it
+			let txn = db.begin_write()?;
+			txn.open_table(TABLE)?;
+			txn.commit()?;
+			Ok(db)
+		})?;
+		// Return the datastore
+		Ok(Datastore {
+			db: Arc::new(db),
+			durability,
+			encryption,
+		})
+	}
+
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<()> {
+		// The database file is closed once it is dropped
+		Ok(())
+	}
+
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
+		// Take a consistent snapshot of the table
+		let snapshot =
+			blocking(|| -> Result<Snapshot> { Ok(self.db.begin_read()?.open_table(TABLE)?) })?;
+		// Create a new transaction
+		let tx = Box::new(Transaction {
+			done: AtomicBool::new(false),
+			write,
+			inner: Mutex::new(Inner {
+				snapshot,
+				writes: BTreeMap::new(),
+				save_points: Vec::new(),
+			}),
+			db: self.db.clone(),
+			durability: self.durability,
+		});
+		// Encrypt the stored values, if enabled
+		Ok(EncryptedTransaction::wrap(tx, self.encryption.as_ref()))
+	}
+}
+
+impl Transaction {
+	fn ensure_open(&self, version: Option<u64>) -> Result<()> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		Ok(())
+	}
+
+	fn ensure_writeable(&self) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		Ok(())
+	}
+
+	/// Apply the buffered writes in a single redb write transaction. This
+	/// blocks on the database write lock, and on syncing to disk, so it is
+	/// only called through [`blocking`].
+	fn commit_blocking(&self, inner: MutexGuard<'_, Inner>) -> Result<()> {
+		let mut txn = self.db.begin_write()?;
+		txn.set_durability(self.durability);
+		match inner.apply(&txn) {
+			Ok(()) => Ok(txn.commit()?),
+			Err(e) => {
+				txn.abort()?;
+				Err(e)
+			}
+		}
+	}
+}
+
+impl Inner {
+	/// Fetch a key as seen by this transaction
+	fn get(&self, key: &[u8]) -> Result<Option<Val>> {
+		match self.writes.get(key) {
+			Some(val) => Ok(val.clone()),
+			None => Ok(self.snapshot.get(key)?.map(|v| v.value().to_vec())),
+		}
+	}
+
+	/// Buffer a write, recording the previous state in the last save point
+	fn write(&mut self, key: Key, val: Option<Val>) {
+		match self.save_points.last_mut() {
+			Some(log) => {
+				let prev = self.writes.insert(key.clone(), val);
+				log.push((key, prev));
+			}
+			None => {
+				self.writes.insert(key, val);
+			}
+		}
+	}
+
+	/// Check that a key currently matches the expected value
+	fn check(&self, key: &[u8], chk: Option<Val>) -> Result<()> {
+		match (self.get(key)?, chk) {
+			(Some(v), Some(w)) if v == w => Ok(()),
+			(None, None) => Ok(()),
+			_ => Err(Error::TransactionConditionNotMet),
+		}
+	}
+
+	/// Apply the buffered writes, failing if any of the written keys has been
+	/// changed by another transaction since the snapshot was taken
+	fn apply(&self, txn: &WriteTransaction) -> Result<()> {
+		let mut table = txn.open_table(TABLE)?;
+		for (key, val) in self.writes.iter() {
+			let changed = {
+				let current = table.get(key.as_slice())?;
+				let snapshot = self.snapshot.get(key.as_slice())?;
+				current.as_ref().map(|v| v.value()) != snapshot.as_ref().map(|v| v.value())
+			};
+			if changed {
+				return Err(Error::TransactionConflict(format!(
+					"Key write conflict on {}",
+					key.sprint()
+				)));
+			}
+			match val {
+				Some(val) => {
+					table.insert(key.as_slice(), val.as_slice())?;
+				}
+				None => {
+					table.remove(key.as_slice())?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Visit the entries in a range as seen by this transaction, merging the
+	/// buffered writes over the snapshot. The first `skip` entries are passed
+	/// over, and at most `limit` entries are handed to the visitor.
+	fn visit(
+		&self,
+		rng: &Range<Key>,
+		dir: Direction,
+		skip: &mut u32,
+		limit: u32,
+		f: &mut dyn ValVisitor,
+	) -> Result<Visit> {
+		// Empty ranges can not be passed to the range iterators
+		if limit == 0 || rng.start >= rng.end {
+			return Ok(Visit {
+				exhausted: rng.start >= rng.end,
+				..Visit::default()
+			});
+		}
+		let stored = self.snapshot.range::<&[u8]>(rng.start.as_slice()..rng.end.as_slice())?;
+		let pending = self.writes.range::<[u8], _>((
+			Bound::Included(rng.start.as_slice()),
+			Bound::Excluded(rng.end.as_slice()),
+		));
+		match dir {
+			Direction::Forward => merge(stored, pending, false, skip, limit, f),
+			Direction::Backward => merge(stored.rev(), pending.rev(), true, skip, limit, f),
+		}
+	}
+}
+
+/// The outcome of visiting a range of entries.
+#[derive(Default)]
+struct Visit {
+	/// The row and byte counts of the visited entries
+	stats: ScanChunkStats,
+	/// The last visited key, valid if any rows were visited
+	last: Key,
+	/// Whether the end of the range was reached
+	exhausted: bool,
+}
+
+impl Visit {
+	/// The last visited key, if any
+	fn last(&self) -> Option<&[u8]> {
+		(self.stats.rows > 0).then_some(self.last.as_slice())
+	}
+
+	/// Hand an entry to the visitor, returning whether the scan should stop
+	fn row(
+		&mut self,
+		key: &[u8],
+		val: &[u8],
+		skip: &mut u32,
+		limit: u32,
+		f: &mut dyn ValVisitor,
+	) -> Result<bool> {
+		if *skip > 0 {
+			*skip -= 1;
+			return Ok(false);
+		}
+		// Count only after the visitor accepts the row
+		let flow = f(key, val)?;
+		self.stats.rows += 1;
+		self.stats.key_bytes += key.len() as u64;
+		self.stats.value_bytes += val.len() as u64;
+		self.last.clear();
+		self.last.extend_from_slice(key);
+		Ok(flow.is_break() || self.stats.rows >= limit as u64)
+	}
+}
+
+/// Merge the buffered writes over the snapshot entries, both iterated in the
+/// same direction. Buffered writes shadow stored entries with the same key,
+/// and deleted keys are passed over.
+fn merge<'a, 'g, S, P>(
+	stored: S,
+	pending: P,
+	reverse: bool,
+	skip: &mut u32,
+	limit: u32,
+	f: &mut dyn ValVisitor,
+) -> Result<Visit>
+where
+	S: Iterator<
+		Item = std::result::Result<
+			(AccessGuard<'g, &'static [u8]>, AccessGuard<'g, &'static [u8]>),
+			StorageError,
+		>,
+	>,
+	P: Iterator<Item = (&'a Key, &'a Option<Val>)>,
+{
+	let mut visit = Visit::default();
+	let mut stored = stored.peekable();
+	let mut pending = pending.peekable();
+	loop {
+		// Order the next buffered write against the next stored entry
+		let ord = match (pending.peek(), stored.peek()) {
+			(None, None) => {
+				visit.exhausted = true;
+				break;
+			}
+			(Some(_), None) => KeyOrdering::Less,
+			(None, Some(_)) | (Some(_), Some(Err(_))) => KeyOrdering::Greater,
+			(Some((p, _)), Some(Ok((k, _)))) if reverse => k.value().cmp(p.as_slice()),
+			(Some((p, _)), Some(Ok((k, _)))) => p.as_slice().cmp(k.value()),
+		};
+		// The buffered write shadows a stored entry with the same key
+		if ord.is_eq() {
+			stored.next();
+		}
+		let stop = if ord.is_le() {
+			match pending.next() {
+				Some((k, Some(v))) => visit.row(k, v, skip, limit, f)?,
+				// Deleted keys are passed over
+				_ => false,
+			}
+		} else {
+			match stored.next() {
+				Some(entry) => {
+					let (k, v) = entry?;
+					visit.row(k.value(), v.value(), skip, limit, f)?
+				}
+				None => false,
+			}
+		};
+		if stop {
+			break;
+		}
+	}
+	Ok(visit)
+}
+
+impl Transactable for Transaction {
+	fn kind(&self) -> &'static str {
+		"redb"
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done.load(Ordering::Relaxed)
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancel a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	fn cancel(&self) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			// Atomically mark transaction as done and check if it was already closed
+			if self.done.swap(true, Ordering::AcqRel) {
+				return Err(Error::TransactionFinished);
+			}
+			// Discard the buffered writes
+			let mut inner = self.inner.lock().await;
+			inner.writes.clear();
+			inner.save_points.clear();
+			// Continue
+			Ok(())
+		})
+	}
+
+	/// Commit a transaction
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	fn commit(&self) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			// Atomically mark transaction as done and check if it was already closed
+			if self.done.swap(true, Ordering::AcqRel) {
+				return Err(Error::TransactionFinished);
+			}
+			// Check to see if transaction is writable
+			if !self.writeable() {
+				return Err(Error::TransactionReadonly);
+			}
+			// Load the inner transaction
+			let inner = self.inner.lock().await;
+			// There is nothing to do if nothing was written
+			if inner.writes.is_empty() {
+				return Ok(());
+			}
+			// Apply the writes
+			blocking(|| self.commit_blocking(inner))
+		})
+	}
+
+	/// Check if a key exists
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn exists(&self, key: Key, version: Option<u64>) -> BoxFut<'_, Result<bool>> {
+		Box::pin(async move {
+			self.ensure_open(version)?;
+			// Load the inner transaction
+			let inner = self.inner.lock().await;
+			// Check the key
+			let res = match inner.writes.get(&key) {
+				Some(val) => val.is_some(),
+				None => blocking(|| inner.snapshot.get(key.as_slice()))?.is_some(),
+			};
+			// Return result
+			Ok(res)
+		})
+	}
+
+	/// Fetch a key from the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn get(&self, key: Key, version: Option<u64>) -> BoxFut<'_, Result<Option<Val>>> {
+		Box::pin(async move {
+			self.ensure_open(version)?;
+			// Load the inner transaction
+			let inner = self.inner.lock().await;
+			// Get the key
+			blocking(|| inner.get(&key))
+		})
+	}
+
+	/// Insert or update a key in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn set(&self, key: Key, val: Val) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.ensure_writeable()?;
+			// Load the inner transaction
+			let mut inner = self.inner.lock().await;
+			// Set the key
+			inner.write(key, Some(val));
+			// Return result
+			Ok(())
+		})
+	}
+
+	/// Insert a key if it doesn't exist in the database
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn put(&self, key: Key, val: Val) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.ensure_writeable()?;
+			// Load the inner transaction
+			let mut inner = self.inner.lock().await;
+			// Check the key does not already exist
+			if blocking(|| inner.get(&key))?.is_some() {
+				return Err(Error::TransactionKeyAlreadyExists);
+			}
+			// Set the key
+			inner.write(key, Some(val));
+			// Return result
+			Ok(())
+		})
+	}
+
+	/// Insert a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.ensure_writeable()?;
+			// Load the inner transaction
+			let mut inner = self.inner.lock().await;
+			// Check the current value
+			blocking(|| inner.check(&key, chk))?;
+			// Set the key
+			inner.write(key, Some(val));
+			// Return result
+			Ok(())
+		})
+	}
+
+	/// Delete a key
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn del(&self, key: Key) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.ensure_writeable()?;
+			// Load the inner transaction
+			let mut inner = self.inner.lock().await;
+			// Remove the key
+			inner.write(key, None);
+			// Return result
+			Ok(())
+		})
+	}
+
+	/// Delete a key if the current value matches a condition
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	fn delc(&self, key: Key, chk: Option<Val>) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.ensure_writeable()?;
+			// Load the inner transaction
+			let mut inner = self.inner.lock().await;
+			// Check the current value
+			blocking(|| inner.check(&key, chk))?;
+			// Remove the key
+			inner.write(key, None);
+			// Return result
+			Ok(())
+		})
+	}
+
+	/// Retrieve a range of keys
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<KeysResult>> {
+		Box::pin(
+			async move { self.collect_keys(rng, Direction::Forward, limit, skip, version).await },
+		)
+	}
+
+	/// Retrieve a range of keys, in reverse
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<KeysResult>> {
+		Box::pin(
+			async move { self.collect_keys(rng, Direction::Backward, limit, skip, version).await },
+		)
+	}
+
+	/// Retrieve a range of key-value pairs
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<ScanResult>> {
+		Box::pin(
+			async move { self.collect_vals(rng, Direction::Forward, limit, skip, version).await },
+		)
+	}
+
+	/// Retrieve a range of key-value pairs, in reverse
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: u32,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'_, Result<ScanResult>> {
+		Box::pin(
+			async move { self.collect_vals(rng, Direction::Backward, limit, skip, version).await },
+		)
+	}
+
+	/// Open a stateful keys scan cursor, which re-seeks the snapshot and the
+	/// buffered writes from the current range bound on each call.
+	fn open_keys_cursor<'a>(
+		&'a self,
+		rng: Range<Key>,
+		dir: Direction,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'a, Result<Box<dyn ScanCursorKeys + 'a>>> {
+		Box::pin(async move {
+			self.ensure_open(version)?;
+			Ok(Box::new(RedbKeysCursor {
+				tx: self,
+				rng,
+				dir,
+				skip,
+				exhausted: false,
+				buf: Vec::new(),
+				spans: Vec::new(),
+			}) as Box<dyn ScanCursorKeys + 'a>)
+		})
+	}
+
+	/// Open a stateful key+value scan cursor, which re-seeks the snapshot and
+	/// the buffered writes from the current range bound on each call.
+	fn open_vals_cursor<'a>(
+		&'a self,
+		rng: Range<Key>,
+		dir: Direction,
+		skip: u32,
+		version: Option<u64>,
+	) -> BoxFut<'a, Result<Box<dyn ScanCursorVals + 'a>>> {
+		Box::pin(async move {
+			self.ensure_open(version)?;
+			Ok(Box::new(RedbValsCursor {
+				tx: self,
+				rng,
+				dir,
+				skip,
+				exhausted: false,
+				key_buf: Vec::new(),
+				val_buf: Vec::new(),
+				spans: Vec::new(),
+			}) as Box<dyn ScanCursorVals + 'a>)
+		})
+	}
+
+	/// Set a new save point on the transaction.
+	fn new_save_point(&self) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			self.inner.lock().await.save_points.push(Vec::new());
+			Ok(())
+		})
+	}
+
+	/// Rollback to the last save point.
+	fn rollback_to_save_point(&self) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let mut inner = self.inner.lock().await;
+			let Some(log) = inner.save_points.pop() else {
+				return Err(Error::Transaction("There is no save point to rollback to".into()));
+			};
+			// Undo the writes made since the save point, most recent first
+			for (key, prev) in log.into_iter().rev() {
+				match prev {
+					Some(prev) => inner.writes.insert(key, prev),
+					None => inner.writes.remove(&key),
+				};
+			}
+			Ok(())
+		})
+	}
+
+	/// Release the last save point.
+	fn release_last_save_point(&self) -> BoxFut<'_, Result<()>> {
+		Box::pin(async move {
+			let mut inner = self.inner.lock().await;
+			// Keep the undo log for any enclosing save point
+			if let Some(log) = inner.save_points.pop() {
+				if let Some(parent) = inner.save_points.last_mut() {
+					parent.extend(log);
+				}
+			}
+			Ok(())
+		})
+	}
+}
+
+impl Transaction {
+	async fn collect_keys(
+		&self,
+		rng: Range<Key>,
+		dir: Direction,
+		limit: u32,
+		mut skip: u32,
+		version: Option<u64>,
+	) -> Result<KeysResult> {
+		self.ensure_open(version)?;
+		// Load the inner transaction
+		let inner = self.inner.lock().await;
+		// Create the result set
+		let mut keys = Vec::with_capacity(limit.min(4096) as usize);
+		let visit = blocking(|| {
+			inner.visit(&rng, dir, &mut skip, limit, &mut |k, _| {
+				keys.push(k.to_vec());
+				Ok(ControlFlow::Continue(()))
+			})
+		})?;
+		Ok(KeysResult {
+			keys,
+			key_bytes: visit.stats.key_bytes,
+		})
+	}
+
+	async fn collect_vals(
+		&self,
+		rng: Range<Key>,
+		dir: Direction,
+		limit: u32,
+		mut skip: u32,
+		version: Option<u64>,
+	) -> Result<ScanResult> {
+		self.ensure_open(version)?;
+		// Load the inner transaction
+		let inner = self.inner.lock().await;
+		// Create the result set
+		let mut values = Vec::with_capacity(limit.min(4096) as usize);
+		let visit = blocking(|| {
+			inner.visit(&rng, dir, &mut skip, limit, &mut |k, v| {
+				values.push((k.to_vec(), v.to_vec()));
+				Ok(ControlFlow::Continue(()))
+			})
+		})?;
+		Ok(ScanResult {
+			values,
+			key_bytes: visit.stats.key_bytes,
+			value_bytes: visit.stats.value_bytes,
+		})
+	}
+}
+
+/// Stateful, resume-by-bound keys scan cursor for the redb engine.
+///
+/// The merged view over the snapshot and the buffered writes borrows the
+/// transaction's lock, so the cursor re-seeks both from the current range
+/// bound on each call, and advances the bound past the last visited key.
+struct RedbKeysCursor<'a> {
+	/// The parent transaction
+	tx: &'a Transaction,
+	/// Remaining range to scan; advanced past the last visited key each call.
+	rng: Range<Key>,
+	/// Fixed scan direction.
+	dir: Direction,
+	/// Entries still to skip before the first visited row (burned once).
+	skip: u32,
+	/// Set once the range is exhausted; further calls are cheap no-ops.
+	exhausted: bool,
+	/// Reusable concatenated key buffer for the `next_batch` path.
+	buf: Vec<u8>,
+	/// Reusable per-key spans for the `next_batch` path.
+	spans: Vec<KeySpan>,
+}
+
+impl RedbKeysCursor<'_> {
+	/// Visit up to `limit` keys from the current range bound
+	async fn visit(&mut self, limit: u32, f: &mut dyn ValVisitor) -> Result<ScanChunkStats> {
+		if self.tx.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// A zero-budget call leaves the cursor state intact
+		if limit == 0 || self.exhausted {
+			return Ok(ScanChunkStats::default());
+		}
+		let inner = self.tx.inner.lock().await;
+		let visit = blocking(|| inner.visit(&self.rng, self.dir, &mut self.skip, limit, f))?;
+		update_range(&mut self.rng, self.dir, visit.last());
+		self.exhausted = visit.exhausted;
+		Ok(ScanChunkStats {
+			value_bytes: 0,
+			..visit.stats
+		})
+	}
+}
+
+impl ScanCursorKeys for RedbKeysCursor<'_> {
+	fn next_batch<'s>(&'s mut self, limit: u32) -> BoxFut<'s, Result<KeysBatch<'s>>> {
+		Box::pin(async move {
+			let mut buf = std::mem::take(&mut self.buf);
+			let mut spans = std::mem::take(&mut self.spans);
+			buf.clear();
+			spans.clear();
+			let stats = self
+				.visit(limit, &mut |k, _| {
+					spans.push(KeySpan {
+						offset: buf.len(),
+						len: k.len(),
+					});
+					buf.extend_from_slice(k);
+					Ok(ControlFlow::Continue(()))
+				})
+				.await;
+			self.buf = buf;
+			self.spans = spans;
+			Ok(KeysBatch::from_parts(&self.buf, &self.spans, stats?.key_bytes))
+		})
+	}
+
+	fn for_each<'s>(
+		&'s mut self,
+		limit: u32,
+		f: &'s mut dyn KeyVisitor,
+	) -> BoxFut<'s, Result<ScanChunkStats>> {
+		Box::pin(async move { self.visit(limit, &mut |k, _| f(k)).await })
+	}
+}
+
+/// Stateful, resume-by-bound key+value scan cursor for the redb engine. See
+/// [`RedbKeysCursor`] for how the cursor resumes between calls.
+struct RedbValsCursor<'a> {
+	/// The parent transaction
+	tx: &'a Transaction,
+	/// Remaining range to scan; advanced past the last visited key each call.
+	rng: Range<Key>,
+	/// Fixed scan direction.
+	dir: Direction,
+	/// Entries still to skip before the first visited row (burned once).
+	skip: u32,
+	/// Set once the range is exhausted; further calls are cheap no-ops.
+	exhausted: bool,
+	/// Reusable concatenated key buffer for the `next_batch` path.
+	key_buf: Vec<u8>,
+	/// Reusable concatenated value buffer for the `next_batch` path.
+	val_buf: Vec<u8>,
+	/// Reusable per-pair spans for the `next_batch` path.
+	spans: Vec<KeyValSpan>,
+}
+
+impl RedbValsCursor<'_> {
+	/// Visit up to `limit` key-value pairs from the current range bound
+	async fn visit(&mut self, limit: u32, f: &mut dyn ValVisitor) -> Result<ScanChunkStats> {
+		if self.tx.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// A zero-budget call leaves the cursor state intact
+		if limit == 0 || self.exhausted {
+			return Ok(ScanChunkStats::default());
+		}
+		let inner = self.tx.inner.lock().await;
+		let visit = blocking(|| inner.visit(&self.rng, self.dir, &mut self.skip, limit, f))?;
+		update_range(&mut self.rng, self.dir, visit.last());
+		self.exhausted = visit.exhausted;
+		Ok(visit.stats)
+	}
+}
+
+impl ScanCursorVals for RedbValsCursor<'_> {
+	fn next_batch<'s>(&'s mut self, limit: u32) -> BoxFut<'s, Result<ValsBatch<'s>>> {
+		Box::pin(async move {
+			let mut key_buf = std::mem::take(&mut self.key_buf);
+			let mut val_buf = std::mem::take(&mut self.val_buf);
+			let mut spans = std::mem::take(&mut self.spans);
+			key_buf.clear();
+			val_buf.clear();
+			spans.clear();
+			let stats = self
+				.visit(limit, &mut |k, v| {
+					spans.push(KeyValSpan {
+						key_offset: key_buf.len(),
+						key_len: k.len(),
+						val_offset: val_buf.len(),
+						val_len: v.len(),
+					});
+					key_buf.extend_from_slice(k);
+					val_buf.extend_from_slice(v);
+					Ok(ControlFlow::Continue(()))
+				})
+				.await;
+			self.key_buf = key_buf;
+			self.val_buf = val_buf;
+			self.spans = spans;
+			let stats = stats?;
+			Ok(ValsBatch::from_parts(
+				&self.key_buf,
+				&self.val_buf,
+				&self.spans,
+				stats.key_bytes,
+				stats.value_bytes,
+			))
+		})
+	}
+
+	fn for_each<'s>(
+		&'s mut self,
+		limit: u32,
+		f: &'s mut dyn ValVisitor,
+	) -> BoxFut<'s, Result<ScanChunkStats>> {
+		Box::pin(async move { self.visit(limit, &mut |k, v| f(k, v)).await })
+	}
+}
@@ -2,6 +2,7 @@ use std::str::FromStr;
 use std::time::Duration;
 
 use crate::cnf::Config;
+use crate::kvs::EncryptionConfig;
 use crate::kvs::config::{SyncMode, parse_duration};
 use crate::sys::TOTAL_SYSTEM_MEMORY;
 
@@ -587,6 +588,11 @@ pub struct RocksDbConfig {
 	/// workloads
 	/// (default: `2`).
 	pub runtime_reserve: usize,
+
+	/// Encryption at rest of the stored values. Configured with the shared
+	/// `encryption_key_file`, `encryption_key_env`, or
+	/// `encryption_key_provider` options (default: disabled).
+	pub encryption: EncryptionConfig,
 }
 
 impl Default for RocksDbConfig {
@@ -649,6 +655,7 @@ impl Default for RocksDbConfig {
 			shutdown_wait_for_compact_seconds: default_shutdown_wait_for_compact_seconds(),
 			runtime_worker_threads: default_runtime_worker_threads(),
 			runtime_reserve: 2,
+			encryption: EncryptionConfig::default(),
 		}
 	}
 }
@@ -760,6 +767,8 @@ impl Config for RocksDbConfig {
 		} else {
 			map.parse_key("datastore_sync_data", &mut self.sync_mode);
 		}
+
+		self.encryption.parse(map);
 	}
 }
 
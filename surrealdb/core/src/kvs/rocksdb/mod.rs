@@ -50,6 +50,7 @@ use super::api::{
 	ScanCursorKeys, ScanCursorVals, ScanResult, ValVisitor, ValsBatch,
 };
 use super::config::SyncMode;
+use super::encryption::{EncryptedTransaction, KeyRing};
 use super::err::{Error, Result};
 use super::{Direction, ESTIMATED_BYTES_PER_KEY, ESTIMATED_BYTES_PER_KV};
 use crate::key::debug::Sprintable;
@@ -96,6 +97,9 @@ pub struct Datastore {
 	/// `Arc`) into every `Transaction` so the dispatch helpers can
 	/// probe / divert without going through global state.
 	inline_guard: Arc<InlineGuard>,
+	/// The keys used to encrypt the stored values, when encryption at rest
+	/// is enabled
+	encryption: Option<Arc<KeyRing>>,
 }
 
 pub struct Transaction {
@@ -347,6 +351,8 @@ fn apply_cf_level_options(target: &mut Options, config: &RocksDbConfig) {
 impl Datastore {
 	/// Open a new database
 	pub(crate) async fn new(path: &str, config: RocksDbConfig) -> Result<Datastore> {
+		// Load the encryption keys, if encryption at rest is enabled
+		let encryption = config.encryption.key_ring()?;
 		// Configure custom options
 		let mut opts = Options::default();
 		// Ensure we use fdatasync
@@ -523,6 +529,7 @@ impl Datastore {
 			compact_on_shutdown: config.compact_on_shutdown,
 			shutdown_wait_for_compact_seconds: config.shutdown_wait_for_compact_seconds,
 			inline_guard,
+			encryption,
 		})
 	}
 
@@ -844,7 +851,7 @@ impl Datastore {
 			ro.set_timestamp(u64::MAX.to_le_bytes().to_vec());
 		}
 		// Create a new transaction
-		Ok(Box::new(Transaction {
+		let tx = Box::new(Transaction {
 			cursors_alive: AtomicUsize::new(0),
 			done: AtomicBool::new(false),
 			write,
@@ -861,7 +868,9 @@ impl Datastore {
 			prefix_extractor_enabled: self.prefix_extractor_enabled,
 			scan_verify_checksums: self.scan_verify_checksums,
 			inline_guard: Arc::clone(&self.inline_guard),
-		}))
+		});
+		// Encrypt the stored values, if enabled
+		Ok(EncryptedTransaction::wrap(tx, self.encryption.as_ref()))
 	}
 }
 
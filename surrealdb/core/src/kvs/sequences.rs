@@ -730,6 +730,7 @@ mod tests {
 				index: Index::Idx,
 				comment: None,
 				prepare_remove: false,
+				cond: None,
 			},
 		)
 		.await
@@ -1,6 +1,7 @@
 use std::time::Duration;
 
 use crate::cnf::Config;
+use crate::kvs::EncryptionConfig;
 use crate::kvs::config::{SyncMode, parse_duration};
 use crate::sys::TOTAL_SYSTEM_MEMORY;
 
@@ -46,6 +47,8 @@ pub struct SurrealKvConfig {
 	/// If a single transaction needs to store larger than this size, this value should be
 	/// increased.
 	pub max_memtable_size: usize,
+	/// Encryption at rest of the stored values (default: disabled)
+	pub encryption: EncryptionConfig,
 }
 
 const KIB: u64 = 1024;
@@ -101,6 +104,7 @@ impl Default for SurrealKvConfig {
 			grouped_commit_wait_threshold: 12,
 			grouped_commit_max_batch_size: 4096,
 			max_memtable_size: default_max_memtable_size(),
+			encryption: EncryptionConfig::default(),
 		}
 	}
 }
@@ -131,6 +135,8 @@ impl Config for SurrealKvConfig {
 		} else {
 			map.parse_key("datastore_sync_data", &mut self.sync_mode);
 		}
+
+		self.encryption.parse(map);
 	}
 }
 
@@ -167,4 +173,13 @@ mod test {
 		let config = map.load::<SurrealKvConfig>();
 		assert_eq!(config.sync_mode, SyncMode::Interval(Duration::from_secs(5)));
 	}
+
+	#[test]
+	fn test_surrealkv_config_encryption() {
+		let map = ConfigMap::from_config_string("encryption_key_file=/etc/surreal/keys")
+			.map_keys(|x| format!("datastore_{x}"));
+		let config = map.load::<SurrealKvConfig>();
+		assert_eq!(config.encryption.key_file, Some("/etc/surreal/keys".into()));
+		assert!(config.encryption.key_env.is_none());
+	}
 }
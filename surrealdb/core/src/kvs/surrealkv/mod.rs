@@ -21,6 +21,7 @@ use tokio::sync::RwLock;
 use super::Direction;
 use super::api::{BoxFut, KeysResult, ScanResult};
 use super::config::SyncMode;
+use super::encryption::{EncryptedTransaction, KeyRing};
 use super::err::{Error, Result};
 use crate::key::debug::Sprintable;
 use crate::kvs::api::Transactable;
@@ -39,6 +40,8 @@ pub struct Datastore {
 	commit_coordinator: Option<Arc<CommitCoordinator>>,
 	/// Background flusher for periodically flushing WAL when sync=<interval>
 	background_flusher: Option<Arc<BackgroundFlusher>>,
+	/// The keys used to encrypt the stored values, when encryption at rest is enabled
+	encryption: Option<Arc<KeyRing>>,
 }
 
 pub struct Transaction {
@@ -66,6 +69,8 @@ impl Transaction {
 impl Datastore {
 	/// Open a new database
 	pub(crate) async fn new(path: &str, config: SurrealKvConfig) -> Result<Datastore> {
+		// Load the encryption keys, if encryption at rest is enabled
+		let encryption = config.encryption.key_ring()?;
 		// Configure custom options
 		let builder = TreeBuilder::new();
 
@@ -132,6 +137,7 @@ impl Datastore {
 			versioned: config.versioned,
 			commit_coordinator,
 			background_flusher,
+			encryption,
 		})
 	}
 
@@ -167,14 +173,16 @@ impl Datastore {
 		// For sync=every mode, use Eventual durability and let coordinator handle fsync
 		// For sync=never/interval modes, also use Eventual (OS or background thread handles sync)
 		txn.set_durability(Durability::Eventual);
-		// Return the new transaction
-		Ok(Box::new(Transaction {
+		// Create the new transaction
+		let tx = Box::new(Transaction {
 			done: AtomicBool::new(false),
 			write,
 			versioned: self.versioned,
 			inner: RwLock::new(txn),
 			commit_coordinator: self.commit_coordinator.clone(),
-		}))
+		});
+		// Encrypt the stored values, if enabled
+		Ok(EncryptedTransaction::wrap(tx, self.encryption.as_ref()))
 	}
 }
 
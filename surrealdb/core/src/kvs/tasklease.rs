@@ -32,6 +32,12 @@ pub(crate) enum TaskLeaseType {
 	EventProcessing,
 	/// Background reclaim of tombstoned namespace/database/index data
 	ReclaimTombstones,
+	/// Delivery of changefeed mutations to sinks
+	SinkDelivery,
+	/// Deletion of records which have outlived the TTL of their table
+	RecordExpiry,
+	/// Re-encryption of the values sealed with a retired encryption key
+	Reencryption,
 }
 
 /// Represents a distributed task lease stored in the datastore.
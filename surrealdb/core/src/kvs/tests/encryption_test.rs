@@ -0,0 +1,107 @@
+//! Tests for encryption at rest on the on-disk storage engines.
+//!
+//! Each test opens an encrypted datastore, checks that records round-trip,
+//! and that none of the stored files contain the plaintext values. It then
+//! rotates the key, runs the background re-encryption task
+//! [`Datastore::reencryption`] directly, and checks that the pass is stored as
+//! complete, and that the data can be read once the retired key is removed.
+
+use std::path::Path;
+use std::time::Duration;
+
+use surrealdb_types::Value;
+use temp_dir::TempDir;
+
+use crate::dbs::Session;
+use crate::key::root::re::{Re, ReencryptionState};
+use crate::kvs::Datastore;
+use crate::kvs::LockType::Optimistic;
+use crate::kvs::TransactionType::Read;
+
+/// A value which is only ever written inside a record.
+const MARKER: &str = "plaintext-marker-7d1f9c";
+
+async fn open(flavour: &str, dir: &Path, keys: &str) -> Datastore {
+	let file = dir.join("keys");
+	std::fs::write(&file, keys).unwrap();
+	let path = format!(
+		"{flavour}://{}?encryption_key_file={}",
+		dir.join("data").display(),
+		file.display()
+	);
+	Datastore::builder().build_with_path(&path).await.unwrap()
+}
+
+/// Run a query, check that every statement succeeded, and return the result
+/// of the last statement.
+async fn query(ds: &Datastore, sql: &str) -> Value {
+	let ses = Session::owner().with_ns("test").with_db("test");
+	let res = ds.execute(sql, &ses, None).await.unwrap();
+	res.into_iter().map(|r| r.result.unwrap()).last().unwrap()
+}
+
+/// Check whether any file below `dir` contains `needle`.
+fn contains(dir: &Path, needle: &[u8]) -> bool {
+	std::fs::read_dir(dir).unwrap().any(|entry| {
+		let path = entry.unwrap().path();
+		if path.is_dir() {
+			contains(&path, needle)
+		} else {
+			std::fs::read(&path).unwrap().windows(needle.len()).any(|w| w == needle)
+		}
+	})
+}
+
+async fn encryption_at_rest(flavour: &str) {
+	let dir = TempDir::new().unwrap();
+	let k1 = format!("1:{}", "07".repeat(32));
+	let k2 = format!("2:{}", "09".repeat(32));
+	// Write a record with the first key
+	let ds = open(flavour, dir.path(), &k1).await;
+	query(
+		&ds,
+		&format!(
+			"DEFINE NAMESPACE test; DEFINE DATABASE test; CREATE person:1 SET name = '{MARKER}'"
+		),
+	)
+	.await;
+	let val = query(&ds, "RETURN person:1.name").await;
+	assert_eq!(val, Value::String(MARKER.to_owned()));
+	ds.shutdown().await.unwrap();
+	drop(ds);
+	// The stored values are encrypted
+	assert!(!contains(&dir.path().join("data"), MARKER.as_bytes()));
+	// Rotate to the second key, and re-encrypt the existing values
+	let ds = open(flavour, dir.path(), &format!("{k1},{k2}")).await;
+	ds.reencryption(&Duration::from_secs(10)).await.unwrap();
+	// The pass is stored as complete for the active key
+	let txn = ds.transaction(Read, Optimistic).await.unwrap();
+	let state = txn.get(&Re::new(), None).await.unwrap();
+	txn.cancel().await.unwrap();
+	assert_eq!(
+		state,
+		Some(ReencryptionState {
+			key: 2,
+			next: None,
+		})
+	);
+	ds.shutdown().await.unwrap();
+	drop(ds);
+	// The retired key is no longer needed to read the values
+	let ds = open(flavour, dir.path(), &k2).await;
+	let val = query(&ds, "RETURN person:1.name").await;
+	assert_eq!(val, Value::String(MARKER.to_owned()));
+	ds.shutdown().await.unwrap();
+}
+
+#[cfg(feature = "kv-rocksdb")]
+#[tokio::test]
+async fn rocksdb_encryption_at_rest() {
+	encryption_at_rest("rocksdb").await;
+}
+
+#[cfg(feature = "kv-surrealkv")]
+#[tokio::test]
+async fn surrealkv_encryption_at_rest() {
+	encryption_at_rest("surrealkv").await;
+}
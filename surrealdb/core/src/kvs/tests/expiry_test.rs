@@ -0,0 +1,153 @@
+//! Tests for the deletion of records which have outlived the TTL of their table.
+//!
+//! Records are only deleted by the background expiry task
+//! [`Datastore::record_expiry`], so these tests run it directly and verify which
+//! records remain, and that the deletions trigger table events.
+
+use std::time::Duration;
+
+use surrealdb_types::{Number, Value};
+
+use crate::dbs::{Capabilities, Session};
+use crate::kvs::Datastore;
+
+async fn mem_ds() -> Datastore {
+	Datastore::builder()
+		.with_capabilities(Capabilities::all())
+		.build_with_path("memory")
+		.await
+		.unwrap()
+}
+
+/// Run a query, check that every statement succeeded, and return the result
+/// of the last statement.
+async fn query(ds: &Datastore, ses: &Session, sql: &str) -> Value {
+	let res = ds.execute(sql, ses, None).await.unwrap();
+	res.into_iter().map(|r| r.result.unwrap()).last().unwrap()
+}
+
+#[tokio::test]
+async fn record_expiry_deletes_expired_records() {
+	let ds = mem_ds().await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+
+	// Define an expiring table, and record each deletion through an event
+	query(
+		&ds,
+		&ses,
+		r#"
+		DEFINE NAMESPACE test;
+		DEFINE DATABASE test;
+		DEFINE TABLE session TTL 1h ON created_at;
+		DEFINE EVENT log ON session WHEN $event = "DELETE" THEN {
+			CREATE expired SET record = $before.id;
+		};
+		CREATE |session:1200| SET created_at = time::now() - 2h;
+		CREATE session:fresh SET created_at = time::now();
+		CREATE session:number SET created_at = 0;
+		CREATE session:missing;
+		"#,
+	)
+	.await;
+
+	// Nothing expires until the expiry task runs
+	let val = query(&ds, &ses, "RETURN count(SELECT id FROM session)").await;
+	assert_eq!(val, Value::Number(Number::Int(1203)));
+
+	// Delete the expired records, across more than one batch
+	ds.record_expiry(&Duration::from_secs(10)).await.unwrap();
+
+	// Only records with a recent datetime, or no datetime, are kept
+	let val = query(
+		&ds,
+		&ses,
+		"RETURN (SELECT VALUE record::id(id) FROM session ORDER BY id) == ['fresh', 'missing', 'number']",
+	)
+	.await;
+	assert_eq!(val, Value::Bool(true));
+
+	// The deletions triggered the table event
+	let val = query(&ds, &ses, "RETURN count(SELECT id FROM expired)").await;
+	assert_eq!(val, Value::Number(Number::Int(1200)));
+}
+
+#[tokio::test]
+async fn record_expiry_ignores_tables_without_ttl() {
+	let ds = mem_ds().await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+
+	query(
+		&ds,
+		&ses,
+		r#"
+		DEFINE NAMESPACE test;
+		DEFINE DATABASE test;
+		DEFINE TABLE session TTL 1h ON created_at;
+		CREATE session:old SET created_at = time::now() - 2h;
+		ALTER TABLE session DROP TTL;
+		CREATE other:old SET created_at = time::now() - 2h;
+		"#,
+	)
+	.await;
+
+	ds.record_expiry(&Duration::from_secs(10)).await.unwrap();
+
+	let val = query(&ds, &ses, "RETURN count(SELECT id FROM session, other)").await;
+	assert_eq!(val, Value::Number(Number::Int(2)));
+}
+
+#[tokio::test]
+async fn record_expiry_follows_updates_and_later_ttls() {
+	let ds = mem_ds().await;
+	let ses = Session::owner().with_ns("test").with_db("test");
+
+	// Records which exist before the TTL is defined expire too, and updates
+	// to the field move the record expiry
+	query(
+		&ds,
+		&ses,
+		r#"
+		DEFINE NAMESPACE test;
+		DEFINE DATABASE test;
+		CREATE session:before SET created_at = time::now() - 2h;
+		CREATE session:renewed SET created_at = time::now() - 2h;
+		CREATE session:aged SET created_at = time::now();
+		CREATE session:kept SET created_at = time::now();
+		DEFINE TABLE session TTL 1h ON created_at;
+		UPDATE session:renewed SET created_at = time::now();
+		UPDATE session:aged SET created_at = time::now() - 2h;
+		"#,
+	)
+	.await;
+
+	ds.record_expiry(&Duration::from_secs(10)).await.unwrap();
+
+	let val = query(
+		&ds,
+		&ses,
+		"RETURN (SELECT VALUE record::id(id) FROM session ORDER BY id) == ['kept', 'renewed']",
+	)
+	.await;
+	assert_eq!(val, Value::Bool(true));
+
+	// Changing the TTL field expires records by the new field
+	query(
+		&ds,
+		&ses,
+		r#"
+		UPDATE session:kept SET seen_at = time::now() - 2h;
+		ALTER TABLE session TTL 1h ON seen_at;
+		"#,
+	)
+	.await;
+
+	ds.record_expiry(&Duration::from_secs(10)).await.unwrap();
+
+	let val = query(
+		&ds,
+		&ses,
+		"RETURN (SELECT VALUE record::id(id) FROM session ORDER BY id) == ['renewed']",
+	)
+	.await;
+	assert_eq!(val, Value::Bool(true));
+}
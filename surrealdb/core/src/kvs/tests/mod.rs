@@ -4,6 +4,7 @@
 	feature = "kv-indxdb",
 	feature = "kv-tikv",
 	feature = "kv-surrealkv",
+	feature = "kv-redb",
 ))]
 
 use std::future::Future;
@@ -23,6 +24,10 @@ macro_rules! include_tests {
 #[cfg(feature = "kv-rocksdb")]
 mod metrics;
 
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod encryption_test;
+#[cfg(feature = "kv-mem")]
+mod expiry_test;
 mod multireader;
 mod multiwriter_different_keys;
 mod multiwriter_same_keys_allow;
@@ -44,6 +49,8 @@ pub(crate) enum Kvs {
 	Tikv,
 	#[cfg_attr(not(feature = "kv-surrealkv"), expect(dead_code))]
 	SurrealKV,
+	#[cfg_attr(not(feature = "kv-redb"), expect(dead_code))]
+	Redb,
 }
 
 trait CreateDs {
@@ -155,6 +162,38 @@ mod surrealkv {
 	);
 }
 
+#[cfg(feature = "kv-redb")]
+mod redb {
+	use temp_dir::TempDir;
+	use uuid::Uuid;
+
+	use super::Kvs;
+	use crate::CommunityComposer;
+	use crate::kvs::Datastore;
+
+	async fn new_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the temporary data storage path
+		let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
+		let path = format!("redb:{path}/data.redb");
+		// Setup the redb datastore
+		let ds = Datastore::builder()
+			.with_id(id)
+			.build_with_factory_path(&path, CommunityComposer())
+			.await
+			.unwrap();
+		// Return the datastore
+		(ds, Kvs::Redb)
+	}
+
+	include_tests!(new_ds =>
+		raw,
+		snapshot,
+		multireader,
+		multiwriter_different_keys,
+		multiwriter_same_keys_conflict,
+	);
+}
+
 #[cfg(feature = "kv-tikv")]
 mod tikv {
 	use uuid::Uuid;
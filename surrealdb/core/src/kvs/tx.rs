@@ -2158,6 +2158,23 @@ impl Transaction {
 		self.tr.inner.compact(rng).await
 	}
 
+	/// Re-encrypts the values sealed with a retired encryption key among the
+	/// next `batch` values of the range, returning the rest of the range.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub(crate) async fn reencrypt(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+	) -> Result<Option<Range<Key>>> {
+		Ok(self.tr.inner.reencrypt(rng, batch).await.map_err(Error::from)?)
+	}
+
+	/// The id of the active encryption key, or `None` when values are not
+	/// encrypted at rest.
+	pub(crate) fn encryption_key(&self) -> Option<u32> {
+		self.tr.inner.encryption_key()
+	}
+
 	/// Mark this transaction to wake the async event processor after commit.
 	pub(crate) fn trigger_async_event(&self) {
 		self.trigger_async_event.store(true, Ordering::Relaxed);
@@ -3538,6 +3555,74 @@ impl TableProvider for Transaction {
 		)
 	}
 
+	/// Retrieve all sink definitions for a specific table.
+	fn all_tb_sinks<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<[catalog::SinkDefinition]>>> {
+		Box::pin(
+			async move {
+				if version.is_some() {
+					let beg = crate::key::table::sk::prefix(ns, db, tb)?;
+					let end = crate::key::table::sk::suffix(ns, db, tb)?;
+					let val = self.getr(beg..end, version).await?;
+					return util::deserialize_cache(val.iter().map(|x| x.1.as_slice()));
+				}
+				let qey = cache::tx::Lookup::Sks(ns, db, tb);
+				match self.cache.get(&qey) {
+					Some(val) => val.try_into_sks(),
+					None => {
+						let beg = crate::key::table::sk::prefix(ns, db, tb)?;
+						let end = crate::key::table::sk::suffix(ns, db, tb)?;
+						let val = self.getr(beg..end, None).await?;
+						let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+						let entry = cache::tx::Entry::Sks(Arc::clone(&val));
+						self.cache.insert(qey, entry);
+						Ok(val)
+					}
+				}
+			}
+			.instrument(trace_span!(target: "surrealdb::core::kvs::tx", "all_tb_sinks")),
+		)
+	}
+
+	/// Retrieve all consumer definitions for a specific table.
+	fn all_tb_consumers<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<[catalog::ConsumerDefinition]>>> {
+		Box::pin(
+			async move {
+				if version.is_some() {
+					let beg = crate::key::table::cs::prefix(ns, db, tb)?;
+					let end = crate::key::table::cs::suffix(ns, db, tb)?;
+					let val = self.getr(beg..end, version).await?;
+					return util::deserialize_cache(val.iter().map(|x| x.1.as_slice()));
+				}
+				let qey = cache::tx::Lookup::Css(ns, db, tb);
+				match self.cache.get(&qey) {
+					Some(val) => val.try_into_css(),
+					None => {
+						let beg = crate::key::table::cs::prefix(ns, db, tb)?;
+						let end = crate::key::table::cs::suffix(ns, db, tb)?;
+						let val = self.getr(beg..end, None).await?;
+						let val = util::deserialize_cache(val.iter().map(|x| x.1.as_slice()))?;
+						let entry = cache::tx::Entry::Css(Arc::clone(&val));
+						self.cache.insert(qey, entry);
+						Ok(val)
+					}
+				}
+			}
+			.instrument(trace_span!(target: "surrealdb::core::kvs::tx", "all_tb_consumers")),
+		)
+	}
+
 	/// Retrieve a specific table definition.
 	fn get_tb<'a>(
 		&'a self,
@@ -3611,6 +3696,80 @@ impl TableProvider for Transaction {
 		)
 	}
 
+	/// Retrieve a sink for a table.
+	fn get_tb_sink<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		sk: &'a str,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<catalog::SinkDefinition>>> {
+		Box::pin(
+			async move {
+				if version.is_some() {
+					let key = crate::key::table::sk::new(ns, db, tb, sk);
+					let val = self.get(&key, version).await?.ok_or_else(|| Error::SkNotFound {
+						name: sk.to_owned(),
+					})?;
+					return Ok(Arc::new(val));
+				}
+				let qey = cache::tx::Lookup::Sk(ns, db, tb, sk);
+				match self.cache.get(&qey) {
+					Some(val) => val.try_into_type(),
+					None => {
+						let key = crate::key::table::sk::new(ns, db, tb, sk);
+						let val = self.get(&key, None).await?.ok_or_else(|| Error::SkNotFound {
+							name: sk.to_owned(),
+						})?;
+						let val = Arc::new(val);
+						let entry = cache::tx::Entry::Any(val.clone());
+						self.cache.insert(qey, entry);
+						Ok(val)
+					}
+				}
+			}
+			.instrument(trace_span!(target: "surrealdb::core::kvs::tx", "get_tb_sink")),
+		)
+	}
+
+	/// Retrieve a consumer for a table.
+	fn get_tb_consumer<'a>(
+		&'a self,
+		ns: NamespaceId,
+		db: DatabaseId,
+		tb: &'a TableName,
+		cs: &'a str,
+		version: Option<u64>,
+	) -> BoxProviderFut<'a, Result<Arc<catalog::ConsumerDefinition>>> {
+		Box::pin(
+			async move {
+				if version.is_some() {
+					let key = crate::key::table::cs::new(ns, db, tb, cs);
+					let val = self.get(&key, version).await?.ok_or_else(|| Error::CsNotFound {
+						name: cs.to_owned(),
+					})?;
+					return Ok(Arc::new(val));
+				}
+				let qey = cache::tx::Lookup::Cs(ns, db, tb, cs);
+				match self.cache.get(&qey) {
+					Some(val) => val.try_into_type(),
+					None => {
+						let key = crate::key::table::cs::new(ns, db, tb, cs);
+						let val = self.get(&key, None).await?.ok_or_else(|| Error::CsNotFound {
+							name: cs.to_owned(),
+						})?;
+						let val = Arc::new(val);
+						let entry = cache::tx::Entry::Any(val.clone());
+						self.cache.insert(qey, entry);
+						Ok(val)
+					}
+				}
+			}
+			.instrument(trace_span!(target: "surrealdb::core::kvs::tx", "get_tb_consumer")),
+		)
+	}
+
 	/// Retrieve a field for a table.
 	fn get_tb_field<'a>(
 		&'a self,
@@ -80,6 +80,7 @@ pub enum StatementType {
 	Use,
 	Option,
 	Show,
+	Ack,
 	Break,
 	Continue,
 	Throw,
@@ -118,6 +119,7 @@ impl StatementType {
 			Self::Use => "use",
 			Self::Option => "option",
 			Self::Show => "show",
+			Self::Ack => "ack",
 			Self::Break => "break",
 			Self::Continue => "continue",
 			Self::Throw => "throw",
@@ -138,6 +140,7 @@ impl StatementType {
 			TopLevelExpr::Option(_) => Self::Option,
 			TopLevelExpr::Use(_) => Self::Use,
 			TopLevelExpr::Show(_) => Self::Show,
+			TopLevelExpr::Ack(_) => Self::Ack,
 			TopLevelExpr::Expr(expr) => Self::from_expr(expr),
 		}
 	}
@@ -189,6 +192,7 @@ impl StatementType {
 				..
 			}
 			| Expr::FunctionCall(_)
+			| Expr::Window(_)
 			| Expr::Closure(_) => Self::Other,
 			// GQL MATCH is not a SurrealQL statement-shaped expression.
 			#[cfg(feature = "gql")]
@@ -1209,6 +1213,7 @@ mod tests {
 			StatementType::Use.as_label(),
 			StatementType::Option.as_label(),
 			StatementType::Show.as_label(),
+			StatementType::Ack.as_label(),
 			StatementType::Break.as_label(),
 			StatementType::Continue.as_label(),
 			StatementType::Throw.as_label(),
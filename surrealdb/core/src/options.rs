@@ -51,6 +51,21 @@ pub struct EngineOptions {
 	///
 	/// Default: 100 milliseconds
 	pub live_query_router_interval: Duration,
+	/// Interval at which pending changefeed mutations are delivered to the
+	/// external endpoints of `DEFINE SINK` definitions.
+	///
+	/// Default: 1 second
+	pub sink_delivery_interval: Duration,
+	/// Interval at which records which have outlived the `TTL` of their
+	/// table are deleted.
+	///
+	/// Default: 10 seconds
+	pub record_expiry_interval: Duration,
+	/// Interval at which the values sealed with a retired encryption key are
+	/// re-encrypted with the active key, when encryption at rest is enabled.
+	///
+	/// Default: 60 seconds
+	pub reencryption_interval: Duration,
 	/// Interval for the background reclaim of tombstoned namespace/database/
 	/// index data.
 	///
@@ -118,6 +133,9 @@ impl Default for EngineOptions {
 			index_build_resume_interval: Duration::from_secs(30),
 			event_processing_interval: Duration::from_secs(5),
 			live_query_router_interval: Duration::from_millis(100),
+			sink_delivery_interval: Duration::from_secs(1),
+			record_expiry_interval: Duration::from_secs(10),
+			reencryption_interval: Duration::from_secs(60),
 			reclaim_interval: Duration::from_secs(60),
 			reclaim_grace: Duration::from_secs(600),
 			tikv_gc_interval: Duration::from_secs(600),
@@ -165,6 +183,21 @@ impl EngineOptions {
 		self
 	}
 
+	pub fn with_sink_delivery_interval(mut self, interval: Duration) -> Self {
+		self.sink_delivery_interval = interval;
+		self
+	}
+
+	pub fn with_record_expiry_interval(mut self, interval: Duration) -> Self {
+		self.record_expiry_interval = interval;
+		self
+	}
+
+	pub fn with_reencryption_interval(mut self, interval: Duration) -> Self {
+		self.reencryption_interval = interval;
+		self
+	}
+
 	pub fn with_reclaim_interval(mut self, interval: Duration) -> Self {
 		self.reclaim_interval = interval;
 		self
@@ -1023,6 +1023,7 @@ pub trait RpcProtocol {
 			what: vec![what],
 			with: None,
 			cond: None,
+			fusion: None,
 			omit: vec![],
 			split: None,
 			group: None,
@@ -13,6 +13,7 @@ pub(crate) use utils::*;
 
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::statements::SleepStatement;
+use crate::sql::ttl::Ttl;
 use crate::val::Bytes;
 
 impl<'a> Arbitrary<'a> for ChangeFeed {
@@ -24,6 +25,15 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 	}
 }
 
+impl<'a> Arbitrary<'a> for Ttl {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(Self {
+			duration: u.arbitrary()?,
+			field: plain_idiom(u)?,
+		})
+	}
+}
+
 impl<'a> Arbitrary<'a> for SleepStatement {
 	fn arbitrary(_u: &mut Unstructured<'a>) -> Result<Self> {
 		Ok(Self {
@@ -11,6 +11,7 @@ use crate::sql::field::Selector;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::access::Subject;
 use crate::sql::statements::define::config::api::Middleware;
+use crate::sql::window::{WindowBound, WindowCall, WindowFrame, WindowSpec};
 use crate::sql::{
 	Closure, Data, Expr, Fetch, Field, Fields, Function, FunctionCall, Group, Groups, Idiom, Kind,
 	Literal, Lookup, Model, Order, Part, RecordIdKeyLit, RecordIdKeyRangeLit, RecordIdLit, Scoring,
@@ -229,6 +230,65 @@ impl<'a> Arbitrary<'a> for Selector {
 	}
 }
 
+impl<'a> Arbitrary<'a> for WindowCall {
+	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+		// Only builtin function calls accept an `OVER` clause.
+		let receiver = match u.arbitrary()? {
+			Function::Normal(x) => Function::Normal(x),
+			_ => Function::Normal("count".to_string()),
+		};
+		let call = FunctionCall {
+			receiver,
+			arguments: u.arbitrary()?,
+		};
+
+		let mut partition = Vec::new();
+		for _ in 0..u.arbitrary_len::<Idiom>()? {
+			partition.push(Expr::Idiom(basic_idiom(u)?));
+		}
+		let order = if u.arbitrary()? {
+			Some(u.arbitrary()?)
+		} else {
+			None
+		};
+		let frame = if u.arbitrary()? {
+			Some(arb_window_frame(u)?)
+		} else {
+			None
+		};
+
+		Ok(WindowCall {
+			call,
+			spec: WindowSpec {
+				partition,
+				order,
+				frame,
+			},
+		})
+	}
+}
+
+/// Generates a frame the parser accepts: the start may not be `UNBOUNDED
+/// FOLLOWING`, the end may not be `UNBOUNDED PRECEDING`, and the start may not
+/// lie after the end.
+fn arb_window_frame(u: &mut arbitrary::Unstructured<'_>) -> arbitrary::Result<WindowFrame> {
+	fn bound(u: &mut arbitrary::Unstructured<'_>, rank: u8) -> arbitrary::Result<WindowBound> {
+		Ok(match rank {
+			0 => WindowBound::UnboundedPreceding,
+			1 => WindowBound::Preceding(u.arbitrary()?),
+			2 => WindowBound::CurrentRow,
+			3 => WindowBound::Following(u.arbitrary()?),
+			_ => WindowBound::UnboundedFollowing,
+		})
+	}
+	let start_rank = u.int_in_range(0u8..=3)?;
+	let end_rank = u.int_in_range(start_rank.max(1)..=4)?;
+	Ok(WindowFrame {
+		start: bound(u, start_rank)?,
+		end: bound(u, end_rank)?,
+	})
+}
+
 impl<'a> Arbitrary<'a> for Lookup {
 	fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
 		let r = match u.int_in_range(0u8..=2)? {
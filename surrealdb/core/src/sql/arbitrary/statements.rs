@@ -126,9 +126,16 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 				}
 				cols
 			}
-			Index::Hnsw(_) | Index::DiskAnn(_) | Index::FullText(_) => vec![u.arbitrary()?],
+			Index::Hnsw(_) | Index::DiskAnn(_) | Index::FullText(_) | Index::Spatial(_) => {
+				vec![u.arbitrary()?]
+			}
 			Index::Count(_) => Vec::new(),
 		};
+		// The condition of a COUNT index is part of the index itself
+		let cond = match index {
+			Index::Count(_) => None,
+			_ => u.arbitrary()?,
+		};
 
 		Ok(DefineIndexStatement {
 			kind,
@@ -136,6 +143,7 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineIndexStatement {
 			what,
 			cols,
 			index,
+			cond,
 			comment,
 			concurrently,
 		})
@@ -198,6 +206,7 @@ impl<'a> arbitrary::Arbitrary<'a> for SelectStatement {
 			what: arb_vec1(u, Expr::arbitrary)?,
 			with: u.arbitrary()?,
 			cond: u.arbitrary()?,
+			fusion: u.arbitrary()?,
 			split,
 			group,
 			order,
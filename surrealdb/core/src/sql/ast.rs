@@ -5,7 +5,8 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use crate::expr;
 use crate::fmt::Fmt;
 use crate::sql::statements::{
-	AccessStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement, UseStatement,
+	AccessStatement, AckStatement, KillStatement, LiveStatement, OptionStatement, ShowStatement,
+	UseStatement,
 };
 use crate::sql::{Expr, Literal, Param};
 
@@ -161,6 +162,7 @@ pub(crate) enum TopLevelExpr {
 	Option(OptionStatement),
 	Use(UseStatement),
 	Show(ShowStatement),
+	Ack(AckStatement),
 	Expr(Expr),
 }
 
@@ -188,6 +190,9 @@ impl From<TopLevelExpr> for crate::expr::TopLevelExpr {
 			TopLevelExpr::Show(show_statement) => {
 				crate::expr::TopLevelExpr::Show(show_statement.into())
 			}
+			TopLevelExpr::Ack(ack_statement) => {
+				crate::expr::TopLevelExpr::Ack(ack_statement.into())
+			}
 			TopLevelExpr::Expr(expr) => crate::expr::TopLevelExpr::Expr(expr.into()),
 		}
 	}
@@ -217,6 +222,9 @@ impl From<crate::expr::TopLevelExpr> for TopLevelExpr {
 			crate::expr::TopLevelExpr::Show(show_statement) => {
 				TopLevelExpr::Show(show_statement.into())
 			}
+			crate::expr::TopLevelExpr::Ack(ack_statement) => {
+				TopLevelExpr::Ack(ack_statement.into())
+			}
 			crate::expr::TopLevelExpr::Expr(expr) => TopLevelExpr::Expr(expr.into()),
 		}
 	}
@@ -244,6 +252,7 @@ impl ToSql for TopLevelExpr {
 			TopLevelExpr::Option(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Use(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Show(s) => s.fmt_sql(f, fmt),
+			TopLevelExpr::Ack(s) => s.fmt_sql(f, fmt),
 			TopLevelExpr::Expr(e) => e.fmt_sql(f, fmt),
 		}
 	}
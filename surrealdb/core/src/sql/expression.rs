@@ -15,7 +15,7 @@ use crate::sql::statements::{
 };
 use crate::sql::{
 	BinaryOperator, Block, Closure, Constant, Dir, FunctionCall, Idiom, Literal, Mock, Param, Part,
-	PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit,
+	PostfixOperator, PrefixOperator, RecordIdKeyLit, RecordIdLit, WindowCall,
 };
 use crate::types::{PublicFile, PublicNumber, PublicRecordId, PublicValue};
 use crate::val::TableName;
@@ -46,6 +46,8 @@ pub(crate) enum Expr {
 	},
 	// TODO: Factor out the call from the function expression.
 	FunctionCall(Box<FunctionCall>),
+	/// A function call evaluated over a window, `math::sum(x) OVER (...)`.
+	Window(Box<WindowCall>),
 	Closure(Box<Closure>),
 
 	Break,
@@ -82,6 +84,7 @@ impl Expr {
 			Expr::Idiom(i) => i.simplify(),
 			Expr::Param(i) => Idiom::field(i.clone().into_strand()),
 			Expr::FunctionCall(x) => x.receiver.to_idiom(),
+			Expr::Window(x) => x.call.receiver.to_idiom(),
 			Expr::Literal(l) => match l {
 				Literal::String(s) => Idiom::field(s.clone()),
 				Literal::Datetime(d) => Idiom::field(d.to_string()),
@@ -188,7 +191,8 @@ impl Expr {
 			| Expr::Binary {
 				..
 			}
-			| Expr::FunctionCall(_) => false,
+			| Expr::FunctionCall(_)
+			| Expr::Window(_) => false,
 		}
 	}
 
@@ -527,6 +531,7 @@ impl ToSql for Expr {
 				}
 			}
 			Expr::FunctionCall(function_call) => function_call.fmt_sql(f, fmt),
+			Expr::Window(window) => window.fmt_sql(f, fmt),
 			Expr::Closure(closure) => closure.fmt_sql(f, fmt),
 			Expr::Break => f.push_str("BREAK"),
 			Expr::Continue => f.push_str("CONTINUE"),
@@ -603,6 +608,7 @@ impl From<Expr> for crate::expr::Expr {
 				right: Box::new((*right).into()),
 			},
 			Expr::FunctionCall(f) => crate::expr::Expr::FunctionCall(Box::new((*f).into())),
+			Expr::Window(w) => crate::expr::Expr::Window(Box::new((*w).into())),
 			Expr::Closure(s) => crate::expr::Expr::Closure(Box::new((*s).into())),
 			Expr::Break => crate::expr::Expr::Break,
 			Expr::Continue => crate::expr::Expr::Continue,
@@ -672,6 +678,7 @@ impl From<crate::expr::Expr> for Expr {
 				right: Box::new((*right).into()),
 			},
 			crate::expr::Expr::FunctionCall(f) => Expr::FunctionCall(Box::new((*f).into())),
+			crate::expr::Expr::Window(w) => Expr::Window(Box::new((*w).into())),
 			crate::expr::Expr::Closure(s) => Expr::Closure(Box::new((*s).into())),
 			crate::expr::Expr::Break => Expr::Break,
 			crate::expr::Expr::Continue => Expr::Continue,
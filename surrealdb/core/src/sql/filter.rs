@@ -2,6 +2,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::fmt::QuoteStr;
 use crate::sql::language::Language;
+use crate::types::PublicFile;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -13,6 +14,15 @@ pub enum Filter {
 	Snowball(Language),
 	Uppercase,
 	Mapper(String),
+	StopWords(StopWords),
+	Synonyms(PublicFile),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum StopWords {
+	Language(Language),
+	File(PublicFile),
 }
 
 impl ToSql for Filter {
@@ -25,6 +35,9 @@ impl ToSql for Filter {
 			Self::Snowball(lang) => write_sql!(f, fmt, "SNOWBALL({lang})"),
 			Self::Uppercase => f.push_str("UPPERCASE"),
 			Self::Mapper(path) => write_sql!(f, fmt, "MAPPER({})", QuoteStr(path)),
+			Self::StopWords(StopWords::Language(lang)) => write_sql!(f, fmt, "STOPWORDS({lang})"),
+			Self::StopWords(StopWords::File(file)) => write_sql!(f, fmt, "STOPWORDS({file})"),
+			Self::Synonyms(file) => write_sql!(f, fmt, "SYNONYMS({file})"),
 		}
 	}
 }
@@ -39,6 +52,13 @@ impl From<Filter> for crate::expr::Filter {
 			Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			Filter::Uppercase => Self::Uppercase,
 			Filter::Mapper(path) => Self::Mapper(path),
+			Filter::StopWords(StopWords::Language(lang)) => {
+				Self::StopWords(crate::expr::filter::StopWords::Language(lang.into()))
+			}
+			Filter::StopWords(StopWords::File(file)) => {
+				Self::StopWords(crate::expr::filter::StopWords::File(file.into()))
+			}
+			Filter::Synonyms(file) => Self::Synonyms(file.into()),
 		}
 	}
 }
@@ -53,6 +73,13 @@ impl From<crate::expr::Filter> for Filter {
 			crate::expr::Filter::Snowball(lang) => Self::Snowball(lang.into()),
 			crate::expr::Filter::Uppercase => Self::Uppercase,
 			crate::expr::Filter::Mapper(path) => Self::Mapper(path),
+			crate::expr::Filter::StopWords(crate::expr::filter::StopWords::Language(lang)) => {
+				Self::StopWords(StopWords::Language(lang.into()))
+			}
+			crate::expr::Filter::StopWords(crate::expr::filter::StopWords::File(file)) => {
+				Self::StopWords(StopWords::File(file.into()))
+			}
+			crate::expr::Filter::Synonyms(file) => Self::Synonyms(file.into()),
 		}
 	}
 }
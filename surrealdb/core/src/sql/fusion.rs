@@ -0,0 +1,108 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::types::PublicNumber;
+
+/// The FUSION clause of a hybrid full-text and vector search.
+///
+/// The `FUSION ...` part in
+/// `SELECT * FROM doc WHERE text @@ 'rust' OR emb <|10,40|> $v FUSION RRF`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Fusion {
+	/// Reciprocal rank fusion with the given rank constant.
+	Rrf(u64),
+	/// Weighted linear combination of the normalized scores.
+	Linear {
+		fulltext: PublicNumber,
+		vector: PublicNumber,
+		norm: FusionNorm,
+	},
+}
+
+/// How the scores of each ranking are normalized before a linear fusion.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FusionNorm {
+	#[default]
+	MinMax,
+	ZScore,
+}
+
+impl Fusion {
+	/// The rank constant used by `FUSION RRF` without arguments.
+	pub const DEFAULT_RRF_CONSTANT: u64 = 60;
+}
+
+impl ToSql for Fusion {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Rrf(constant) => write_sql!(f, fmt, "FUSION RRF({constant})"),
+			Self::Linear {
+				fulltext,
+				vector,
+				norm,
+			} => write_sql!(f, fmt, "FUSION LINEAR({fulltext}, {vector}, {norm})"),
+		}
+	}
+}
+
+impl ToSql for FusionNorm {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		match self {
+			Self::MinMax => f.push_str("MINMAX"),
+			Self::ZScore => f.push_str("ZSCORE"),
+		}
+	}
+}
+
+impl From<Fusion> for crate::expr::Fusion {
+	fn from(v: Fusion) -> Self {
+		match v {
+			Fusion::Rrf(constant) => Self::Rrf(constant),
+			Fusion::Linear {
+				fulltext,
+				vector,
+				norm,
+			} => Self::Linear {
+				fulltext: fulltext.into(),
+				vector: vector.into(),
+				norm: norm.into(),
+			},
+		}
+	}
+}
+
+impl From<crate::expr::Fusion> for Fusion {
+	fn from(v: crate::expr::Fusion) -> Self {
+		match v {
+			crate::expr::Fusion::Rrf(constant) => Self::Rrf(constant),
+			crate::expr::Fusion::Linear {
+				fulltext,
+				vector,
+				norm,
+			} => Self::Linear {
+				fulltext: fulltext.into(),
+				vector: vector.into(),
+				norm: norm.into(),
+			},
+		}
+	}
+}
+
+impl From<FusionNorm> for crate::expr::FusionNorm {
+	fn from(v: FusionNorm) -> Self {
+		match v {
+			FusionNorm::MinMax => Self::MinMax,
+			FusionNorm::ZScore => Self::ZScore,
+		}
+	}
+}
+
+impl From<crate::expr::FusionNorm> for FusionNorm {
+	fn from(v: crate::expr::FusionNorm) -> Self {
+		match v {
+			crate::expr::FusionNorm::MinMax => Self::MinMax,
+			crate::expr::FusionNorm::ZScore => Self::ZScore,
+		}
+	}
+}
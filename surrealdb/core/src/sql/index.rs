@@ -1,7 +1,7 @@
 use surrealdb_strand::Strand;
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::EscapeKwFreeIdent;
+use crate::fmt::{EscapeKwFreeIdent, Fmt};
 use crate::sql::Cond;
 use crate::sql::scoring::Scoring;
 use crate::types::PublicNumber;
@@ -21,6 +21,8 @@ pub(crate) enum Index {
 	FullText(FullTextParams),
 	/// Count index
 	Count(Option<Cond>),
+	/// Geohash-cell based spatial index for geometry fields
+	Spatial(SpatialParams),
 }
 
 impl From<Index> for crate::catalog::Index {
@@ -32,6 +34,7 @@ impl From<Index> for crate::catalog::Index {
 			Index::DiskAnn(p) => Self::DiskAnn(p.into()),
 			Index::FullText(p) => Self::FullText(p.into()),
 			Index::Count(c) => Self::Count(c.map(Into::into)),
+			Index::Spatial(p) => Self::Spatial(p.into()),
 		}
 	}
 }
@@ -45,6 +48,7 @@ impl From<crate::catalog::Index> for Index {
 			crate::catalog::Index::DiskAnn(p) => Self::DiskAnn(p.into()),
 			crate::catalog::Index::FullText(p) => Self::FullText(p.into()),
 			crate::catalog::Index::Count(c) => Self::Count(c.map(Into::into)),
+			crate::catalog::Index::Spatial(p) => Self::Spatial(p.into()),
 		}
 	}
 }
@@ -55,6 +59,7 @@ pub struct FullTextParams {
 	pub az: Strand,
 	pub hl: bool,
 	pub sc: Scoring,
+	pub fz: Option<u8>,
 }
 
 impl From<FullTextParams> for crate::catalog::FullTextParams {
@@ -63,6 +68,7 @@ impl From<FullTextParams> for crate::catalog::FullTextParams {
 			analyzer: v.az.clone(),
 			highlight: v.hl,
 			scoring: v.sc.into(),
+			fuzzy: v.fz,
 		}
 	}
 }
@@ -72,6 +78,7 @@ impl From<crate::catalog::FullTextParams> for FullTextParams {
 			az: v.analyzer.clone(),
 			hl: v.highlight,
 			sc: v.scoring.into(),
+			fz: v.fuzzy,
 		}
 	}
 }
@@ -89,6 +96,7 @@ pub(crate) struct HnswParams {
 	pub keep_pruned_connections: bool,
 	pub ml: PublicNumber,
 	pub use_hashed_vector: bool,
+	pub quantization: Option<VectorQuantization>,
 }
 
 impl From<HnswParams> for crate::catalog::HnswParams {
@@ -104,6 +112,7 @@ impl From<HnswParams> for crate::catalog::HnswParams {
 			extend_candidates: v.extend_candidates,
 			keep_pruned_connections: v.keep_pruned_connections,
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
 		}
 	}
 }
@@ -121,6 +130,7 @@ impl From<crate::catalog::HnswParams> for HnswParams {
 			extend_candidates: v.extend_candidates,
 			keep_pruned_connections: v.keep_pruned_connections,
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
 		}
 	}
 }
@@ -142,6 +152,8 @@ pub(crate) struct DiskAnnParams {
 	pub alpha: PublicNumber,
 	/// Whether vector-document mappings are keyed by vector hash.
 	pub use_hashed_vector: bool,
+	/// The quantization of the vectors traversed by the graph.
+	pub quantization: Option<VectorQuantization>,
 }
 
 impl From<DiskAnnParams> for crate::catalog::DiskAnnParams {
@@ -154,6 +166,7 @@ impl From<DiskAnnParams> for crate::catalog::DiskAnnParams {
 			l_build: v.l_build,
 			alpha: v.alpha.into(),
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
 		}
 	}
 }
@@ -168,6 +181,71 @@ impl From<crate::catalog::DiskAnnParams> for DiskAnnParams {
 			l_build: v.l_build,
 			alpha: v.alpha.into(),
 			use_hashed_vector: v.use_hashed_vector,
+			quantization: v.quantization.map(Into::into),
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) enum VectorQuantization {
+	/// Scalar quantization: one signed byte per dimension.
+	Scalar,
+	/// Binary quantization: one bit per dimension.
+	Binary,
+	/// Product quantization with the given number of subspaces.
+	Product(u16),
+}
+
+impl ToSql for VectorQuantization {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			Self::Scalar => f.push_str("SCALAR"),
+			Self::Binary => f.push_str("BINARY"),
+			Self::Product(subspaces) => write_sql!(f, fmt, "PQ {subspaces}"),
+		}
+	}
+}
+
+impl From<VectorQuantization> for crate::catalog::VectorQuantization {
+	fn from(v: VectorQuantization) -> Self {
+		match v {
+			VectorQuantization::Scalar => Self::Scalar,
+			VectorQuantization::Binary => Self::Binary,
+			VectorQuantization::Product(subspaces) => Self::Product(subspaces),
+		}
+	}
+}
+
+impl From<crate::catalog::VectorQuantization> for VectorQuantization {
+	fn from(v: crate::catalog::VectorQuantization) -> Self {
+		match v {
+			crate::catalog::VectorQuantization::Scalar => Self::Scalar,
+			crate::catalog::VectorQuantization::Binary => Self::Binary,
+			crate::catalog::VectorQuantization::Product(subspaces) => Self::Product(subspaces),
+		}
+	}
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct SpatialParams {
+	/// The finest geohash precision used to cover indexed geometries.
+	pub precision: u8,
+}
+
+impl From<SpatialParams> for crate::catalog::SpatialParams {
+	fn from(v: SpatialParams) -> Self {
+		crate::catalog::SpatialParams {
+			precision: v.precision,
+		}
+	}
+}
+
+impl From<crate::catalog::SpatialParams> for SpatialParams {
+	fn from(v: crate::catalog::SpatialParams) -> Self {
+		Self {
+			precision: v.precision,
 		}
 	}
 }
@@ -196,6 +274,10 @@ pub(crate) enum Distance {
 	CosineNormalized,
 	/// Inner product similarity, transformed into a distance score.
 	InnerProduct,
+	/// Spearman rank correlation.
+	Spearman,
+	/// Mahalanobis distance with the supplied covariance matrix.
+	Mahalanobis(Vec<Vec<PublicNumber>>),
 }
 
 impl ToSql for Distance {
@@ -211,6 +293,15 @@ impl ToSql for Distance {
 			Self::Manhattan => f.push_str("MANHATTAN"),
 			Self::Minkowski(order) => write_sql!(f, fmt, "MINKOWSKI {}", order),
 			Self::Pearson => f.push_str("PEARSON"),
+			Self::Spearman => f.push_str("SPEARMAN"),
+			Self::Mahalanobis(covariance) => {
+				let rows = covariance.iter().map(|row| {
+					Fmt::new(row, |row: &Vec<PublicNumber>, f: &mut String, fmt: SqlFormat| {
+						write_sql!(f, fmt, "[{}]", Fmt::comma_separated(row))
+					})
+				});
+				write_sql!(f, fmt, "MAHALANOBIS [{}]", Fmt::comma_separated(rows))
+			}
 		}
 	}
 }
@@ -228,6 +319,10 @@ impl From<Distance> for crate::catalog::Distance {
 			Distance::Manhattan => crate::catalog::Distance::Manhattan,
 			Distance::Minkowski(n) => crate::catalog::Distance::Minkowski(n.into()),
 			Distance::Pearson => crate::catalog::Distance::Pearson,
+			Distance::Spearman => crate::catalog::Distance::Spearman,
+			Distance::Mahalanobis(c) => crate::catalog::Distance::Mahalanobis(
+				c.into_iter().map(|row| row.into_iter().map(Into::into).collect()).collect(),
+			),
 		}
 	}
 }
@@ -245,6 +340,10 @@ impl From<crate::catalog::Distance> for Distance {
 			crate::catalog::Distance::Manhattan => Self::Manhattan,
 			crate::catalog::Distance::Minkowski(n) => Self::Minkowski(n.into()),
 			crate::catalog::Distance::Pearson => Self::Pearson,
+			crate::catalog::Distance::Spearman => Self::Spearman,
+			crate::catalog::Distance::Mahalanobis(c) => Self::Mahalanobis(
+				c.into_iter().map(|row| row.into_iter().map(Into::into).collect()).collect(),
+			),
 		}
 	}
 }
@@ -302,6 +401,9 @@ impl ToSql for Index {
 				if p.hl {
 					f.push_str(" HIGHLIGHTS")
 				}
+				if let Some(fz) = p.fz {
+					write_sql!(f, fmt, " FUZZY {fz}")
+				}
 			}
 			Self::Hnsw(p) => {
 				write_sql!(
@@ -325,6 +427,9 @@ impl ToSql for Index {
 				if p.use_hashed_vector {
 					f.push_str(" HASHED_VECTOR")
 				}
+				if let Some(q) = &p.quantization {
+					write_sql!(f, fmt, " QUANTIZATION {}", q)
+				}
 			}
 			Self::DiskAnn(p) => {
 				write_sql!(
@@ -341,7 +446,11 @@ impl ToSql for Index {
 				if p.use_hashed_vector {
 					f.push_str(" HASHED_VECTOR")
 				}
+				if let Some(q) = &p.quantization {
+					write_sql!(f, fmt, " QUANTIZATION {}", q)
+				}
 			}
+			Self::Spatial(p) => write_sql!(f, fmt, "SPATIAL PRECISION {}", p.precision),
 		}
 	}
 }
@@ -20,6 +20,7 @@ pub(crate) mod field;
 pub(crate) mod file;
 pub(crate) mod filter;
 pub(crate) mod function;
+pub(crate) mod fusion;
 pub(crate) mod group;
 pub(crate) mod idiom;
 pub(crate) mod kind;
@@ -46,8 +47,10 @@ pub(crate) mod table_type;
 #[cfg(test)]
 mod test_to_sql;
 pub(crate) mod tokenizer;
+pub(crate) mod ttl;
 pub(crate) mod user;
 pub(crate) mod view;
+pub(crate) mod window;
 pub(crate) mod with;
 
 pub mod index;
@@ -76,6 +79,7 @@ pub(crate) use self::expression::Expr;
 pub(crate) use self::fetch::{Fetch, Fetchs};
 pub(crate) use self::field::{Field, Fields};
 pub(crate) use self::function::{Function, FunctionCall};
+pub(crate) use self::fusion::{Fusion, FusionNorm};
 pub(crate) use self::group::{Group, Groups};
 pub(crate) use self::idiom::Idiom;
 pub(crate) use self::index::Index;
@@ -101,11 +105,13 @@ pub(crate) use self::script::Script;
 pub(crate) use self::split::{Split, Splits};
 pub(crate) use self::start::Start;
 pub(crate) use self::statements::{
-	CreateStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
-	DefineIndexStatement, DefineModelStatement, DefineModuleStatement, DeleteStatement,
-	InsertStatement, KillStatement, LiveStatement, RelateStatement, SelectStatement,
-	UpdateStatement, UpsertStatement,
+	CreateStatement, DefineConsumerStatement, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineModelStatement, DefineModuleStatement,
+	DefineSinkStatement, DeleteStatement, InsertStatement, KillStatement, LiveStatement,
+	RelateStatement, SelectStatement, UpdateStatement, UpsertStatement,
 };
 pub(crate) use self::table_type::TableType;
+pub(crate) use self::ttl::Ttl;
 pub(crate) use self::view::View;
+pub(crate) use self::window::WindowCall;
 pub(crate) use self::with::With;
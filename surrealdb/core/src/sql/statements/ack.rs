@@ -0,0 +1,53 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::{CoverStmts, EscapeKwFreeIdent, QuoteStr};
+use crate::sql::Expr;
+use crate::val::TableName;
+
+/// An ACK statement for acknowledging the changes read by a consumer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AckStatement {
+	pub consumer: String,
+	pub table: TableName,
+	pub member: Option<String>,
+	pub versionstamp: Expr,
+}
+
+impl ToSql for AckStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(
+			f,
+			fmt,
+			"ACK {} ON {}",
+			EscapeKwFreeIdent(&self.consumer),
+			EscapeKwFreeIdent(self.table.as_str())
+		);
+		if let Some(ref member) = self.member {
+			write_sql!(f, fmt, " MEMBER {}", QuoteStr(member));
+		}
+		write_sql!(f, fmt, " AT {}", CoverStmts(&self.versionstamp));
+	}
+}
+
+impl From<AckStatement> for crate::expr::statements::AckStatement {
+	fn from(v: AckStatement) -> Self {
+		crate::expr::statements::AckStatement {
+			consumer: v.consumer,
+			table: v.table,
+			member: v.member,
+			versionstamp: v.versionstamp.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::AckStatement> for AckStatement {
+	fn from(v: crate::expr::statements::AckStatement) -> Self {
+		AckStatement {
+			consumer: v.consumer,
+			table: v.table,
+			member: v.member,
+			versionstamp: v.versionstamp.into(),
+		}
+	}
+}
@@ -83,7 +83,7 @@ pub enum AlterStatement {
 	System(AlterSystemStatement),
 	Namespace(AlterNamespaceStatement),
 	Database(AlterDatabaseStatement),
-	Table(AlterTableStatement),
+	Table(Box<AlterTableStatement>),
 	Api(AlterApiStatement),
 	Event(AlterEventStatement),
 	Index(AlterIndexStatement),
@@ -129,7 +129,7 @@ impl From<AlterStatement> for crate::expr::statements::AlterStatement {
 			AlterStatement::System(v) => Self::System(v.into()),
 			AlterStatement::Namespace(v) => Self::Namespace(v.into()),
 			AlterStatement::Database(v) => Self::Database(v.into()),
-			AlterStatement::Table(v) => Self::Table(v.into()),
+			AlterStatement::Table(v) => Self::Table(Box::new((*v).into())),
 			AlterStatement::Api(v) => Self::Api(v.into()),
 			AlterStatement::Event(v) => Self::Event(v.into()),
 			AlterStatement::Index(v) => Self::Index(v.into()),
@@ -153,7 +153,7 @@ impl From<crate::expr::statements::AlterStatement> for AlterStatement {
 			crate::expr::statements::AlterStatement::System(v) => Self::System(v.into()),
 			crate::expr::statements::AlterStatement::Namespace(v) => Self::Namespace(v.into()),
 			crate::expr::statements::AlterStatement::Database(v) => Self::Database(v.into()),
-			crate::expr::statements::AlterStatement::Table(v) => Self::Table(v.into()),
+			crate::expr::statements::AlterStatement::Table(v) => Self::Table(Box::new((*v).into())),
 			crate::expr::statements::AlterStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::AlterStatement::Event(v) => Self::Event(v.into()),
 			crate::expr::statements::AlterStatement::Index(v) => Self::Index(v.into()),
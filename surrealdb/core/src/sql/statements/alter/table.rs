@@ -2,7 +2,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::AlterKind;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent, QuoteStr};
-use crate::sql::{ChangeFeed, Expr, Literal, Permissions, TableType};
+use crate::sql::{ChangeFeed, Expr, Literal, Permissions, TableType, Ttl};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -13,6 +13,7 @@ use crate::sql::{ChangeFeed, Expr, Literal, Permissions, TableType};
 /// - `SCHEMAFULL` / `SCHEMALESS`
 /// - `PERMISSIONS ...`
 /// - `CHANGEFEED ...` / `DROP CHANGEFEED`
+/// - `TTL <duration> ON <field>` / `DROP TTL`
 /// - `COMMENT <string>` / `DROP COMMENT`
 /// - `COMPACT` (request table keyspace compaction)
 ///
@@ -24,6 +25,7 @@ pub struct AlterTableStatement {
 	pub schemafull: AlterKind<()>,
 	pub permissions: Option<Permissions>,
 	pub changefeed: AlterKind<ChangeFeed>,
+	pub ttl: AlterKind<Ttl>,
 	pub comment: AlterKind<String>,
 	pub kind: Option<TableType>,
 	/// Request table‑level compaction when true.
@@ -38,6 +40,7 @@ impl Default for AlterTableStatement {
 			schemafull: AlterKind::None,
 			permissions: None,
 			changefeed: AlterKind::None,
+			ttl: AlterKind::None,
 			comment: AlterKind::None,
 			kind: None,
 			compact: false,
@@ -102,6 +105,12 @@ impl ToSql for AlterTableStatement {
 			AlterKind::Drop => f.push_str(" DROP CHANGEFEED"),
 			AlterKind::None => {}
 		}
+
+		match self.ttl {
+			AlterKind::Set(ref ttl) => write_sql!(f, fmt, " {}", ttl),
+			AlterKind::Drop => f.push_str(" DROP TTL"),
+			AlterKind::None => {}
+		}
 		if let Some(permissions) = &self.permissions {
 			write_sql!(f, fmt, " {permissions}");
 		}
@@ -120,6 +129,7 @@ impl From<AlterTableStatement> for crate::expr::statements::alter::AlterTableSta
 			schemafull: v.schemafull.into(),
 			permissions: v.permissions.map(Into::into),
 			changefeed: v.changefeed.into(),
+			ttl: v.ttl.into(),
 			comment: v.comment.into(),
 			kind: v.kind.map(Into::into),
 			compact: v.compact,
@@ -135,6 +145,7 @@ impl From<crate::expr::statements::alter::AlterTableStatement> for AlterTableSta
 			schemafull: v.schemafull.into(),
 			permissions: v.permissions.map(Into::into),
 			changefeed: v.changefeed.into(),
+			ttl: v.ttl.into(),
 			comment: v.comment.into(),
 			kind: v.kind.map(Into::into),
 			compact: v.compact,
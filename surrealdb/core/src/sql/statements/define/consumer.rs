@@ -0,0 +1,67 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineConsumerStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub partitions: u16,
+	pub timeout: Expr,
+	pub comment: Expr,
+}
+
+impl ToSql for DefineConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE CONSUMER");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(
+			f,
+			fmt,
+			" {} ON {} PARTITIONS {}",
+			CoverStmts(&self.name),
+			CoverStmts(&self.target_table),
+			self.partitions
+		);
+		if !matches!(self.timeout, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " TIMEOUT {}", CoverStmts(&self.timeout));
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineConsumerStatement> for crate::expr::statements::DefineConsumerStatement {
+	fn from(v: DefineConsumerStatement) -> Self {
+		crate::expr::statements::DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			partitions: v.partitions,
+			timeout: v.timeout.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::DefineConsumerStatement> for DefineConsumerStatement {
+	fn from(v: crate::expr::statements::DefineConsumerStatement) -> Self {
+		DefineConsumerStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			partitions: v.partitions,
+			timeout: v.timeout.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
@@ -3,6 +3,7 @@ mod analyzer;
 mod api;
 mod bucket;
 pub mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
@@ -13,6 +14,7 @@ mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 pub mod user;
 
@@ -21,6 +23,7 @@ pub(crate) use analyzer::DefineAnalyzerStatement;
 pub(crate) use api::{ApiAction, DefineApiStatement};
 pub(crate) use bucket::DefineBucketStatement;
 pub(crate) use config::DefineConfigStatement;
+pub(crate) use consumer::DefineConsumerStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
 pub(crate) use field::{DefineDefault, DefineFieldStatement};
@@ -31,6 +34,7 @@ pub(crate) use module::DefineModuleStatement;
 pub(crate) use namespace::DefineNamespaceStatement;
 pub(crate) use param::DefineParamStatement;
 pub(crate) use sequence::DefineSequenceStatement;
+pub(crate) use sink::DefineSinkStatement;
 use surrealdb_types::{SqlFormat, ToSql};
 pub(crate) use table::DefineTableStatement;
 pub(crate) use user::DefineUserStatement;
@@ -84,6 +88,8 @@ pub(crate) enum DefineStatement {
 	Api(DefineApiStatement),
 	Bucket(DefineBucketStatement),
 	Sequence(DefineSequenceStatement),
+	Sink(DefineSinkStatement),
+	Consumer(DefineConsumerStatement),
 	#[cfg_attr(feature = "arbitrary", arbitrary(skip))]
 	Module(DefineModuleStatement),
 }
@@ -107,6 +113,8 @@ impl ToSql for DefineStatement {
 			Self::Api(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 		}
 	}
@@ -131,6 +139,8 @@ impl From<DefineStatement> for crate::expr::statements::DefineStatement {
 			DefineStatement::Api(v) => Self::Api(v.into()),
 			DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			DefineStatement::Sink(v) => Self::Sink(v.into()),
+			DefineStatement::Consumer(v) => Self::Consumer(v.into()),
 			DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
@@ -155,6 +165,8 @@ impl From<crate::expr::statements::DefineStatement> for DefineStatement {
 			crate::expr::statements::DefineStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::DefineStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::DefineStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::DefineStatement::Sink(v) => Self::Sink(v.into()),
+			crate::expr::statements::DefineStatement::Consumer(v) => Self::Consumer(v.into()),
 			crate::expr::statements::DefineStatement::Module(v) => Self::Module(v.into()),
 		}
 	}
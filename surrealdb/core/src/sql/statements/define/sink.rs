@@ -0,0 +1,88 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use super::DefineKind;
+use crate::catalog::SinkKind;
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct DefineSinkStatement {
+	pub kind: DefineKind,
+	pub name: Expr,
+	pub target_table: Expr,
+	pub sink_kind: SinkKind,
+	pub url: Expr,
+	pub topic: Expr,
+	pub retry: u16,
+	pub backoff: Expr,
+	pub dead_letter: Expr,
+	pub comment: Expr,
+}
+
+impl ToSql for DefineSinkStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		f.push_str("DEFINE SINK");
+		match self.kind {
+			DefineKind::Default => {}
+			DefineKind::Overwrite => f.push_str(" OVERWRITE"),
+			DefineKind::IfNotExists => f.push_str(" IF NOT EXISTS"),
+		}
+		write_sql!(
+			f,
+			fmt,
+			" {} ON {} TO {} {}",
+			CoverStmts(&self.name),
+			CoverStmts(&self.target_table),
+			self.sink_kind.as_str(),
+			CoverStmts(&self.url)
+		);
+		if !matches!(self.topic, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " TOPIC {}", CoverStmts(&self.topic));
+		}
+		write_sql!(f, fmt, " RETRY {}", self.retry);
+		if !matches!(self.backoff, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " BACKOFF {}", CoverStmts(&self.backoff));
+		}
+		if !matches!(self.dead_letter, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " DEADLETTER {}", CoverStmts(&self.dead_letter));
+		}
+		if !matches!(self.comment, Expr::Literal(Literal::None)) {
+			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
+		}
+	}
+}
+
+impl From<DefineSinkStatement> for crate::expr::statements::DefineSinkStatement {
+	fn from(v: DefineSinkStatement) -> Self {
+		crate::expr::statements::DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			sink_kind: v.sink_kind,
+			url: v.url.into(),
+			topic: v.topic.into(),
+			retry: v.retry,
+			backoff: v.backoff.into(),
+			dead_letter: v.dead_letter.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
+
+impl From<crate::expr::statements::DefineSinkStatement> for DefineSinkStatement {
+	fn from(v: crate::expr::statements::DefineSinkStatement) -> Self {
+		DefineSinkStatement {
+			kind: v.kind.into(),
+			name: v.name.into(),
+			target_table: v.target_table.into(),
+			sink_kind: v.sink_kind,
+			url: v.url.into(),
+			topic: v.topic.into(),
+			retry: v.retry,
+			backoff: v.backoff.into(),
+			dead_letter: v.dead_letter.into(),
+			comment: v.comment.into(),
+		}
+	}
+}
@@ -1,4 +1,5 @@
 pub(crate) mod access;
+pub(crate) mod ack;
 pub(crate) mod alter;
 pub(crate) mod create;
 pub(crate) mod define;
@@ -23,12 +24,13 @@ pub(crate) mod upsert;
 pub(crate) mod r#use;
 
 pub(crate) use self::access::AccessStatement;
+pub(crate) use self::ack::AckStatement;
 pub(crate) use self::alter::{AlterStatement, AlterTableStatement};
 pub(crate) use self::create::CreateStatement;
 pub(crate) use self::define::{
-	DefineApiStatement, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
-	DefineIndexStatement, DefineModelStatement, DefineModuleStatement, DefineNamespaceStatement,
-	DefineStatement, DefineTableStatement,
+	DefineApiStatement, DefineConsumerStatement, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineModelStatement, DefineModuleStatement,
+	DefineNamespaceStatement, DefineSinkStatement, DefineStatement, DefineTableStatement,
 };
 pub(crate) use self::delete::DeleteStatement;
 pub(crate) use self::foreach::ForeachStatement;
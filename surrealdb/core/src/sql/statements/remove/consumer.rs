@@ -0,0 +1,52 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::CoverStmts;
+use crate::sql::{Expr, Literal};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct RemoveConsumerStatement {
+	pub name: Expr,
+	pub what: Expr,
+	pub if_exists: bool,
+}
+
+impl Default for RemoveConsumerStatement {
+	fn default() -> Self {
+		Self {
+			name: Expr::Literal(Literal::None),
+			what: Expr::Literal(Literal::None),
+			if_exists: false,
+		}
+	}
+}
+
+impl ToSql for RemoveConsumerStatement {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "REMOVE CONSUMER");
+		if self.if_exists {
+			write_sql!(f, fmt, " IF EXISTS");
+		}
+		write_sql!(f, fmt, " {} ON {}", CoverStmts(&self.name), CoverStmts(&self.what));
+	}
+}
+
+impl From<RemoveConsumerStatement> for crate::expr::statements::RemoveConsumerStatement {
+	fn from(v: RemoveConsumerStatement) -> Self {
+		crate::expr::statements::RemoveConsumerStatement {
+			name: v.name.into(),
+			table_name: v.what.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
+
+impl From<crate::expr::statements::RemoveConsumerStatement> for RemoveConsumerStatement {
+	fn from(v: crate::expr::statements::RemoveConsumerStatement) -> Self {
+		RemoveConsumerStatement {
+			name: v.name.into(),
+			what: v.table_name.into(),
+			if_exists: v.if_exists,
+		}
+	}
+}
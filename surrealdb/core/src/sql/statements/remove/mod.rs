@@ -4,6 +4,7 @@ mod analyzer;
 mod api;
 mod bucket;
 mod config;
+mod consumer;
 mod database;
 mod event;
 mod field;
@@ -14,6 +15,7 @@ mod module;
 mod namespace;
 mod param;
 mod sequence;
+mod sink;
 mod table;
 mod user;
 
@@ -22,6 +24,7 @@ pub(crate) use analyzer::RemoveAnalyzerStatement;
 pub(crate) use api::RemoveApiStatement;
 pub(crate) use bucket::RemoveBucketStatement;
 pub(crate) use config::{RemoveConfigKind, RemoveConfigStatement};
+pub(crate) use consumer::RemoveConsumerStatement;
 pub(crate) use database::RemoveDatabaseStatement;
 pub(crate) use event::RemoveEventStatement;
 pub(crate) use field::RemoveFieldStatement;
@@ -32,6 +35,7 @@ pub(crate) use module::RemoveModuleStatement;
 pub(crate) use namespace::RemoveNamespaceStatement;
 pub(crate) use param::RemoveParamStatement;
 pub(crate) use sequence::RemoveSequenceStatement;
+pub(crate) use sink::RemoveSinkStatement;
 pub(crate) use table::RemoveTableStatement;
 pub(crate) use user::RemoveUserStatement;
 
@@ -54,6 +58,8 @@ pub(crate) enum RemoveStatement {
 	Api(RemoveApiStatement),
 	Bucket(RemoveBucketStatement),
 	Sequence(RemoveSequenceStatement),
+	Sink(RemoveSinkStatement),
+	Consumer(RemoveConsumerStatement),
 	Module(RemoveModuleStatement),
 	Config(RemoveConfigStatement),
 }
@@ -76,6 +82,8 @@ impl ToSql for RemoveStatement {
 			Self::Api(v) => v.fmt_sql(f, fmt),
 			Self::Bucket(v) => v.fmt_sql(f, fmt),
 			Self::Sequence(v) => v.fmt_sql(f, fmt),
+			Self::Sink(v) => v.fmt_sql(f, fmt),
+			Self::Consumer(v) => v.fmt_sql(f, fmt),
 			Self::Module(v) => v.fmt_sql(f, fmt),
 			Self::Config(v) => v.fmt_sql(f, fmt),
 		}
@@ -100,6 +108,8 @@ impl From<RemoveStatement> for crate::expr::statements::RemoveStatement {
 			RemoveStatement::Api(v) => Self::Api(v.into()),
 			RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			RemoveStatement::Sink(v) => Self::Sink(v.into()),
+			RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
 			RemoveStatement::Module(v) => Self::Module(v.into()),
 			RemoveStatement::Config(v) => Self::Config(v.into()),
 		}
@@ -124,6 +134,8 @@ impl From<crate::expr::statements::RemoveStatement> for RemoveStatement {
 			crate::expr::statements::RemoveStatement::Api(v) => Self::Api(v.into()),
 			crate::expr::statements::RemoveStatement::Bucket(v) => Self::Bucket(v.into()),
 			crate::expr::statements::RemoveStatement::Sequence(v) => Self::Sequence(v.into()),
+			crate::expr::statements::RemoveStatement::Sink(v) => Self::Sink(v.into()),
+			crate::expr::statements::RemoveStatement::Consumer(v) => Self::Consumer(v.into()),
 			crate::expr::statements::RemoveStatement::Module(v) => Self::Module(v.into()),
 			crate::expr::statements::RemoveStatement::Config(v) => Self::Config(v.into()),
 		}
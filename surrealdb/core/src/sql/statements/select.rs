@@ -3,7 +3,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use crate::fmt::{CoverStmts, Fmt};
 use crate::sql::order::Ordering;
 use crate::sql::{
-	Cond, Explain, Expr, Fetchs, Fields, Groups, Limit, Literal, Splits, Start, With,
+	Cond, Explain, Expr, Fetchs, Fields, Fusion, Groups, Limit, Literal, Splits, Start, With,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -16,6 +16,7 @@ pub struct SelectStatement {
 	pub what: Vec<Expr>,
 	pub with: Option<With>,
 	pub cond: Option<Cond>,
+	pub fusion: Option<Fusion>,
 	pub split: Option<Splits>,
 	pub group: Option<Groups>,
 	pub order: Option<Ordering>,
@@ -45,6 +46,9 @@ impl ToSql for SelectStatement {
 		if let Some(ref v) = self.cond {
 			write_sql!(f, fmt, " {v}");
 		}
+		if let Some(ref v) = self.fusion {
+			write_sql!(f, fmt, " {v}");
+		}
 		if let Some(ref v) = self.split {
 			write_sql!(f, fmt, " {v}");
 		}
@@ -84,6 +88,7 @@ impl From<SelectStatement> for crate::expr::statements::SelectStatement {
 			what: v.what.into_iter().map(From::from).collect(),
 			with: v.with.map(Into::into),
 			cond: v.cond.map(Into::into),
+			fusion: v.fusion.map(Into::into),
 			split: v.split.map(Into::into),
 			group: v.group.map(Into::into),
 			order: v.order.map(Into::into),
@@ -107,6 +112,7 @@ impl From<crate::expr::statements::SelectStatement> for SelectStatement {
 			what: v.what.into_iter().map(From::from).collect(),
 			with: v.with.map(Into::into),
 			cond: v.cond.map(Into::into),
+			fusion: v.fusion.map(Into::into),
 			split: v.split.map(Into::into),
 			group: v.group.map(Into::into),
 			order: v.order.map(Into::into),
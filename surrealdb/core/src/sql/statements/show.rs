@@ -1,6 +1,6 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::EscapeKwFreeIdent;
+use crate::fmt::{EscapeKwFreeIdent, QuoteStr};
 use crate::types::PublicDatetime;
 use crate::val::TableName;
 
@@ -9,6 +9,10 @@ use crate::val::TableName;
 pub enum ShowSince {
 	Timestamp(PublicDatetime),
 	Versionstamp(u64),
+	Consumer {
+		name: String,
+		member: Option<String>,
+	},
 }
 
 impl From<ShowSince> for crate::expr::statements::show::ShowSince {
@@ -16,6 +20,13 @@ impl From<ShowSince> for crate::expr::statements::show::ShowSince {
 		match v {
 			ShowSince::Timestamp(v) => Self::Timestamp(v.into()),
 			ShowSince::Versionstamp(v) => Self::Versionstamp(v),
+			ShowSince::Consumer {
+				name,
+				member,
+			} => Self::Consumer {
+				name,
+				member,
+			},
 		}
 	}
 }
@@ -27,6 +38,13 @@ impl From<crate::expr::statements::show::ShowSince> for ShowSince {
 				ShowSince::Timestamp(v.into())
 			}
 			crate::expr::statements::show::ShowSince::Versionstamp(v) => ShowSince::Versionstamp(v),
+			crate::expr::statements::show::ShowSince::Consumer {
+				name,
+				member,
+			} => ShowSince::Consumer {
+				name,
+				member,
+			},
 		}
 	}
 }
@@ -50,6 +68,15 @@ impl ToSql for ShowStatement {
 		match self.since {
 			ShowSince::Timestamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
 			ShowSince::Versionstamp(ref v) => write_sql!(f, fmt, " SINCE {}", v),
+			ShowSince::Consumer {
+				ref name,
+				ref member,
+			} => {
+				write_sql!(f, fmt, " CONSUMER {}", EscapeKwFreeIdent(name));
+				if let Some(member) = member {
+					write_sql!(f, fmt, " MEMBER {}", QuoteStr(member));
+				}
+			}
 		}
 		if let Some(ref v) = self.limit {
 			write_sql!(f, fmt, " LIMIT {}", v)
@@ -10,6 +10,8 @@ pub enum Tokenizer {
 	Camel,
 	Class,
 	Punct,
+	Unicode,
+	Cjk,
 }
 
 impl Display for Tokenizer {
@@ -19,6 +21,8 @@ impl Display for Tokenizer {
 			Self::Camel => "CAMEL",
 			Self::Class => "CLASS",
 			Self::Punct => "PUNCT",
+			Self::Unicode => "UNICODE",
+			Self::Cjk => "CJK",
 		})
 	}
 }
@@ -34,6 +38,8 @@ impl ToSql for Tokenizer {
 				Self::Camel => "CAMEL",
 				Self::Class => "CLASS",
 				Self::Punct => "PUNCT",
+				Self::Unicode => "UNICODE",
+				Self::Cjk => "CJK",
 			}
 		)
 	}
@@ -59,6 +65,8 @@ impl From<Tokenizer> for crate::expr::Tokenizer {
 			Tokenizer::Camel => Self::Camel,
 			Tokenizer::Class => Self::Class,
 			Tokenizer::Punct => Self::Punct,
+			Tokenizer::Unicode => Self::Unicode,
+			Tokenizer::Cjk => Self::Cjk,
 		}
 	}
 }
@@ -70,6 +78,8 @@ impl From<crate::expr::Tokenizer> for Tokenizer {
 			crate::expr::Tokenizer::Camel => Self::Camel,
 			crate::expr::Tokenizer::Class => Self::Class,
 			crate::expr::Tokenizer::Punct => Self::Punct,
+			crate::expr::Tokenizer::Unicode => Self::Unicode,
+			crate::expr::Tokenizer::Cjk => Self::Cjk,
 		}
 	}
 }
@@ -0,0 +1,34 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::sql::Idiom;
+use crate::types::PublicDuration;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ttl {
+	pub duration: PublicDuration,
+	pub field: Idiom,
+}
+
+impl ToSql for Ttl {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "TTL {} ON {}", self.duration, self.field);
+	}
+}
+
+impl From<Ttl> for crate::expr::Ttl {
+	fn from(v: Ttl) -> Self {
+		crate::expr::Ttl {
+			duration: v.duration.into(),
+			field: v.field.into(),
+		}
+	}
+}
+
+impl From<crate::expr::Ttl> for Ttl {
+	fn from(v: crate::expr::Ttl) -> Self {
+		Ttl {
+			duration: v.duration.into(),
+			field: v.field.into(),
+		}
+	}
+}
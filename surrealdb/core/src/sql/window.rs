@@ -0,0 +1,190 @@
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
+
+use crate::fmt::{CoverStmts, Fmt};
+use crate::sql::order::OrderList;
+use crate::sql::{Expr, FunctionCall};
+
+/// A function call evaluated over a window of rows, e.g.
+/// `math::sum(amount) OVER (PARTITION BY customer ORDER BY time)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct WindowCall {
+	/// The windowed function: a framed aggregate such as `math::sum(amount)`
+	/// or a `window::*` ranking / offset function.
+	pub call: FunctionCall,
+	/// The `OVER (...)` clause.
+	pub spec: WindowSpec,
+}
+
+impl ToSql for WindowCall {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "{} OVER ({})", self.call, self.spec);
+	}
+}
+
+impl From<WindowCall> for crate::expr::WindowCall {
+	fn from(v: WindowCall) -> Self {
+		Self {
+			call: v.call.into(),
+			spec: v.spec.into(),
+		}
+	}
+}
+
+impl From<crate::expr::WindowCall> for WindowCall {
+	fn from(v: crate::expr::WindowCall) -> Self {
+		Self {
+			call: v.call.into(),
+			spec: v.spec.into(),
+		}
+	}
+}
+
+/// The contents of an `OVER (...)` clause.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct WindowSpec {
+	/// The `PARTITION BY a, b` expressions.
+	pub partition: Vec<Expr>,
+	/// The `ORDER BY c DESC` list, ordering rows within a partition.
+	pub order: Option<OrderList>,
+	/// The `ROWS BETWEEN ... AND ...` frame.
+	pub frame: Option<WindowFrame>,
+}
+
+impl ToSql for WindowSpec {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		let mut first = true;
+		let mut sep = |f: &mut String| {
+			if !std::mem::take(&mut first) {
+				f.push(' ');
+			}
+		};
+		if !self.partition.is_empty() {
+			sep(f);
+			write_sql!(
+				f,
+				fmt,
+				"PARTITION BY {}",
+				Fmt::comma_separated(self.partition.iter().map(CoverStmts))
+			);
+		}
+		if let Some(order) = &self.order {
+			sep(f);
+			write_sql!(f, fmt, "ORDER BY {order}");
+		}
+		if let Some(frame) = &self.frame {
+			sep(f);
+			frame.fmt_sql(f, fmt);
+		}
+	}
+}
+
+impl From<WindowSpec> for crate::expr::WindowSpec {
+	fn from(v: WindowSpec) -> Self {
+		Self {
+			partition: v.partition.into_iter().map(Into::into).collect(),
+			order: v.order.map(Into::into),
+			frame: v.frame.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::WindowSpec> for WindowSpec {
+	fn from(v: crate::expr::WindowSpec) -> Self {
+		Self {
+			partition: v.partition.into_iter().map(Into::into).collect(),
+			order: v.order.map(Into::into),
+			frame: v.frame.map(Into::into),
+		}
+	}
+}
+
+/// A `ROWS BETWEEN <start> AND <end>` frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct WindowFrame {
+	pub start: WindowBound,
+	pub end: WindowBound,
+}
+
+impl ToSql for WindowFrame {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		write_sql!(f, fmt, "ROWS BETWEEN {} AND {}", self.start, self.end);
+	}
+}
+
+impl From<WindowFrame> for crate::expr::WindowFrame {
+	fn from(v: WindowFrame) -> Self {
+		Self {
+			start: v.start.into(),
+			end: v.end.into(),
+		}
+	}
+}
+
+impl From<crate::expr::WindowFrame> for WindowFrame {
+	fn from(v: crate::expr::WindowFrame) -> Self {
+		Self {
+			start: v.start.into(),
+			end: v.end.into(),
+		}
+	}
+}
+
+/// One end of a [`WindowFrame`], relative to the current row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum WindowBound {
+	UnboundedPreceding,
+	Preceding(u64),
+	CurrentRow,
+	Following(u64),
+	UnboundedFollowing,
+}
+
+impl WindowBound {
+	/// The position of this bound kind relative to the current row, used to
+	/// reject frames whose start lies after their end.
+	pub(crate) fn rank(&self) -> u8 {
+		match self {
+			WindowBound::UnboundedPreceding => 0,
+			WindowBound::Preceding(_) => 1,
+			WindowBound::CurrentRow => 2,
+			WindowBound::Following(_) => 3,
+			WindowBound::UnboundedFollowing => 4,
+		}
+	}
+}
+
+impl ToSql for WindowBound {
+	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
+		match self {
+			WindowBound::UnboundedPreceding => f.push_str("UNBOUNDED PRECEDING"),
+			WindowBound::Preceding(n) => write_sql!(f, fmt, "{n} PRECEDING"),
+			WindowBound::CurrentRow => f.push_str("CURRENT ROW"),
+			WindowBound::Following(n) => write_sql!(f, fmt, "{n} FOLLOWING"),
+			WindowBound::UnboundedFollowing => f.push_str("UNBOUNDED FOLLOWING"),
+		}
+	}
+}
+
+impl From<WindowBound> for crate::expr::WindowBound {
+	fn from(v: WindowBound) -> Self {
+		match v {
+			WindowBound::UnboundedPreceding => Self::UnboundedPreceding,
+			WindowBound::Preceding(n) => Self::Preceding(n),
+			WindowBound::CurrentRow => Self::CurrentRow,
+			WindowBound::Following(n) => Self::Following(n),
+			WindowBound::UnboundedFollowing => Self::UnboundedFollowing,
+		}
+	}
+}
+
+impl From<crate::expr::WindowBound> for WindowBound {
+	fn from(v: crate::expr::WindowBound) -> Self {
+		match v {
+			crate::expr::WindowBound::UnboundedPreceding => Self::UnboundedPreceding,
+			crate::expr::WindowBound::Preceding(n) => Self::Preceding(n),
+			crate::expr::WindowBound::CurrentRow => Self::CurrentRow,
+			crate::expr::WindowBound::Following(n) => Self::Following(n),
+			crate::expr::WindowBound::UnboundedFollowing => Self::UnboundedFollowing,
+		}
+	}
+}
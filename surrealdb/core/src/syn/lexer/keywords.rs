@@ -62,6 +62,7 @@ pub fn could_be_reserved(s: &str) -> bool {
 pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map! {
 	// Keywords
 	UniCase::ascii("ACCESS") => TokenKind::Keyword(Keyword::Access),
+	UniCase::ascii("ACK") => TokenKind::Keyword(Keyword::Ack),
 	UniCase::ascii("AFTER") => TokenKind::Keyword(Keyword::After),
 	UniCase::ascii("ALGORITHM") => TokenKind::Keyword(Keyword::Algorithm),
 	UniCase::ascii("ALL") => TokenKind::Keyword(Keyword::All),
@@ -278,6 +279,7 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("TOKENIZERS") => TokenKind::Keyword(Keyword::Tokenizers),
 	UniCase::ascii("TRANSACTION") => TokenKind::Keyword(Keyword::Transaction),
 	UniCase::ascii("true") => TokenKind::Keyword(Keyword::True),
+	UniCase::ascii("TTL") => TokenKind::Keyword(Keyword::Ttl),
 	UniCase::ascii("TYPE") => TokenKind::Keyword(Keyword::Type),
 	UniCase::ascii("UNIQUE") => TokenKind::Keyword(Keyword::Unique),
 	UniCase::ascii("UNSET") => TokenKind::Keyword(Keyword::Unset),
@@ -406,9 +408,11 @@ pub(crate) static KEYWORDS: phf::Map<UniCase<&'static str>, TokenKind> = phf_map
 	UniCase::ascii("HAMMING") => TokenKind::Distance(DistanceKind::Hamming),
 	UniCase::ascii("INNER_PRODUCT") => TokenKind::Distance(DistanceKind::InnerProduct),
 	UniCase::ascii("JACCARD") => TokenKind::Distance(DistanceKind::Jaccard),
+	UniCase::ascii("MAHALANOBIS") => TokenKind::Distance(DistanceKind::Mahalanobis),
 	UniCase::ascii("MANHATTAN") => TokenKind::Distance(DistanceKind::Manhattan),
 	UniCase::ascii("MINKOWSKI") => TokenKind::Distance(DistanceKind::Minkowski),
 	UniCase::ascii("PEARSON") => TokenKind::Distance(DistanceKind::Pearson),
+	UniCase::ascii("SPEARMAN") => TokenKind::Distance(DistanceKind::Spearman),
 
 	// VectorTypes
 	UniCase::ascii("F16") => TokenKind::VectorType(VectorTypeKind::F16),
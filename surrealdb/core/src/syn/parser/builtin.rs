@@ -486,6 +486,14 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("vector::similarity::jaccard") => (PathKind::Function, None),
 		UniCase::ascii("vector::similarity::pearson") => (PathKind::Function, None),
 		UniCase::ascii("vector::similarity::spearman") => (PathKind::Function, None),
+		//
+		UniCase::ascii("window::dense_rank") => (PathKind::Function, None),
+		UniCase::ascii("window::first_value") => (PathKind::Function, None),
+		UniCase::ascii("window::lag") => (PathKind::Function, None),
+		UniCase::ascii("window::last_value") => (PathKind::Function, None),
+		UniCase::ascii("window::lead") => (PathKind::Function, None),
+		UniCase::ascii("window::rank") => (PathKind::Function, None),
+		UniCase::ascii("window::row_number") => (PathKind::Function, None),
 		// constants
 		UniCase::ascii("math::E") => (PathKind::Constant(Constant::MathE), None),
 		UniCase::ascii("math::FRAC_1_PI") => (PathKind::Constant(Constant::MathFrac1Pi), None),
@@ -638,10 +646,10 @@ impl Parser<'_> {
 
 		match PATHS.get_entry(&UniCase::ascii(&buffer)) {
 			Some((_, (PathKind::Constant(x), _))) => Ok(Expr::Constant(x.clone())),
-			Some((_, (PathKind::Function, _))) => stk
-				.run(|ctx| self.parse_builtin_function(ctx, buffer))
-				.await
-				.map(|x| Expr::FunctionCall(Box::new(x))),
+			Some((_, (PathKind::Function, _))) => {
+				let call = stk.run(|ctx| self.parse_builtin_function(ctx, buffer)).await?;
+				stk.run(|ctx| self.try_parse_window(ctx, call)).await
+			}
 			None => {
 				if let Some(suggest) = find_suggestion(&buffer) {
 					Err(SyntaxError::new(format_args!(
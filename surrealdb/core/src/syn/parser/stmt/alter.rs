@@ -1,5 +1,6 @@
 use reblessive::Stk;
 
+use super::define::is_identifier_token;
 use crate::catalog::{ApiMethod, EventDefinition, EventKind};
 use crate::sql::TableType;
 use crate::sql::filter::Filter;
@@ -26,7 +27,9 @@ impl Parser<'_> {
 			t!("SYSTEM") => self.parse_alter_system(stk).await.map(AlterStatement::System),
 			t!("NAMESPACE") => self.parse_alter_namespace().await.map(AlterStatement::Namespace),
 			t!("DATABASE") => self.parse_alter_database().await.map(AlterStatement::Database),
-			t!("TABLE") => self.parse_alter_table(stk).await.map(AlterStatement::Table),
+			t!("TABLE") => {
+				self.parse_alter_table(stk).await.map(|s| AlterStatement::Table(Box::new(s)))
+			}
 			t!("EVENT") => self.parse_alter_event(stk).await.map(AlterStatement::Event),
 			t!("INDEX") => self.parse_alter_index(stk).await.map(AlterStatement::Index),
 			t!("FIELD") => {
@@ -146,8 +149,12 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.changefeed = AlterKind::Drop;
 						}
+						t!("TTL") => {
+							self.pop_peek();
+							res.ttl = AlterKind::Drop;
+						}
 						_ => {
-							unexpected!(self, peek, "`COMMENT` or `CHANGEFEED`")
+							unexpected!(self, peek, "`COMMENT`, `CHANGEFEED` or `TTL`")
 						}
 					}
 				}
@@ -194,6 +201,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = AlterKind::Set(self.parse_changefeed()?)
 				}
+				t!("TTL") => {
+					self.pop_peek();
+					res.ttl = AlterKind::Set(self.parse_ttl(stk).await?)
+				}
 				_ => break,
 			}
 		}
@@ -655,6 +666,8 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							_ if is_identifier_token(self, next, "UNICODE") => Tokenizer::Unicode,
+							_ if is_identifier_token(self, next, "CJK") => Tokenizer::Cjk,
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -701,6 +714,18 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path));
 							}
+							_ if is_identifier_token(self, next, "STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let stop_words = self.parse_stop_words()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::StopWords(stop_words));
+							}
+							_ if is_identifier_token(self, next, "SYNONYMS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let file = self.parse_filter_file()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Synonyms(file));
+							}
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
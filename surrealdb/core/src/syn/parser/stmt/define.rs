@@ -1,12 +1,16 @@
 use reblessive::Stk;
 use surrealdb_strand::Strand;
 
-use crate::catalog::{ApiMethod, EventDefinition, EventKind};
+use crate::catalog::{
+	ApiMethod, ConsumerDefinition, EventDefinition, EventKind, SinkDefinition, SinkKind,
+};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::JwtAccessVerify;
 use crate::sql::base::Base;
-use crate::sql::filter::Filter;
-use crate::sql::index::{DiskAnnParams, Distance, HnswParams, VectorType};
+use crate::sql::filter::{Filter, StopWords};
+use crate::sql::index::{
+	DiskAnnParams, Distance, HnswParams, SpatialParams, VectorQuantization, VectorType,
+};
 use crate::sql::kind::KindLiteral;
 use crate::sql::statements::define::config::api::{ApiConfig, Middleware};
 use crate::sql::statements::define::config::defaults::DefaultConfig;
@@ -15,10 +19,11 @@ use crate::sql::statements::define::config::{ConfigInner, graphql};
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
 	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
-	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	DefineBucketStatement, DefineConfigStatement, DefineConsumerStatement, DefineDatabaseStatement,
+	DefineDefault, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
+	DefineIndexStatement, DefineKind, DefineNamespaceStatement, DefineParamStatement,
+	DefineSequenceStatement, DefineSinkStatement, DefineStatement, DefineTableStatement,
+	DefineUserStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -31,14 +36,13 @@ use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Token, TokenKind, t};
-#[cfg(feature = "surrealism")]
 use crate::types::PublicFile;
 
 /// Returns whether an identifier token matches a keyword-like option name.
 ///
 /// DiskANN options such as `DEGREE`, `L_BUILD`, and `ALPHA` are parsed as identifiers in this
 /// parser, so matching them case-insensitively keeps the syntax aligned with regular keywords.
-fn is_identifier_token(parser: &Parser<'_>, token: Token, ident: &str) -> bool {
+pub(super) fn is_identifier_token(parser: &Parser<'_>, token: Token, ident: &str) -> bool {
 	token.kind == TokenKind::Identifier && parser.span_str(token.span).eq_ignore_ascii_case(ident)
 }
 
@@ -74,6 +78,12 @@ impl Parser<'_> {
 			t!("BUCKET") => self.parse_define_bucket(stk, next).await.map(DefineStatement::Bucket),
 			t!("SEQUENCE") => self.parse_define_sequence(stk).await.map(DefineStatement::Sequence),
 			t!("MODULE") => self.parse_define_module(stk).await.map(DefineStatement::Module),
+			_ if is_identifier_token(self, next, "SINK") => {
+				stk.run(|stk| self.parse_define_sink(stk)).await.map(DefineStatement::Sink)
+			}
+			_ if is_identifier_token(self, next, "CONSUMER") => {
+				stk.run(|stk| self.parse_define_consumer(stk)).await.map(DefineStatement::Consumer)
+			}
 			_ => unexpected!(self, next, "a define statement keyword"),
 		}
 	}
@@ -708,6 +718,10 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				t!("TTL") => {
+					self.pop_peek();
+					res.ttl = Some(self.parse_ttl(stk).await?);
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -923,6 +937,125 @@ impl Parser<'_> {
 		}
 		Ok(res)
 	}
+
+	pub(crate) async fn parse_define_sink(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineSinkStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+		expected!(self, t!("TO"));
+		let next = self.next();
+		let sink_kind = match next.kind {
+			_ if is_identifier_token(self, next, "WEBHOOK") => SinkKind::Webhook,
+			_ if is_identifier_token(self, next, "NATS") => SinkKind::Nats,
+			_ if is_identifier_token(self, next, "KAFKA") => SinkKind::Kafka,
+			_ => unexpected!(self, next, "a sink kind, WEBHOOK, NATS, or KAFKA"),
+		};
+		let url = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+
+		let mut res = DefineSinkStatement {
+			kind,
+			name,
+			target_table: what,
+			sink_kind,
+			url,
+			topic: Expr::Literal(Literal::None),
+			retry: SinkDefinition::DEFAULT_RETRY,
+			backoff: Expr::Literal(Literal::None),
+			dead_letter: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		};
+
+		loop {
+			let peek = self.peek();
+			match peek.kind {
+				t!("RETRY") => {
+					self.pop_peek();
+					res.retry = self.next_token_value()?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ if is_identifier_token(self, peek, "TOPIC") => {
+					self.pop_peek();
+					res.topic = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ if is_identifier_token(self, peek, "BACKOFF") => {
+					self.pop_peek();
+					res.backoff = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ if is_identifier_token(self, peek, "DEADLETTER") => {
+					self.pop_peek();
+					res.dead_letter = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
+
+	pub(crate) async fn parse_define_consumer(
+		&mut self,
+		stk: &mut Stk,
+	) -> ParseResult<DefineConsumerStatement> {
+		let kind = if self.eat(t!("IF")) {
+			expected!(self, t!("NOT"));
+			expected!(self, t!("EXISTS"));
+			DefineKind::IfNotExists
+		} else if self.eat(t!("OVERWRITE")) {
+			DefineKind::Overwrite
+		} else {
+			DefineKind::Default
+		};
+
+		let name = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let what = stk.run(|ctx| self.parse_expr_table(ctx)).await?;
+
+		let mut res = DefineConsumerStatement {
+			kind,
+			name,
+			target_table: what,
+			partitions: ConsumerDefinition::DEFAULT_PARTITIONS,
+			timeout: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		};
+
+		loop {
+			let peek = self.peek();
+			match peek.kind {
+				t!("TIMEOUT") => {
+					self.pop_peek();
+					res.timeout = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				t!("COMMENT") => {
+					self.pop_peek();
+					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				}
+				_ if is_identifier_token(self, peek, "PARTITIONS") => {
+					self.pop_peek();
+					res.partitions = self.next_token_value()?;
+				}
+				_ => break,
+			}
+		}
+		Ok(res)
+	}
 	pub(crate) async fn parse_define_field(
 		&mut self,
 		stk: &mut Stk,
@@ -1064,11 +1197,13 @@ impl Parser<'_> {
 			kind,
 			cols: Vec::new(),
 			index: Index::Idx,
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		};
 
 		let mut field_span = None;
+		let mut cond_span = None;
 
 		loop {
 			match self.peek_kind() {
@@ -1095,8 +1230,10 @@ impl Parser<'_> {
 					let mut analyzer: Option<String> = None;
 					let mut scoring = None;
 					let mut hl = false;
+					let mut fz = None;
 					loop {
-						match self.peek_kind() {
+						let token = self.peek();
+						match token.kind {
 							t!("ANALYZER") => {
 								self.pop_peek();
 								analyzer = Some(self.parse_ident()?.into_string());
@@ -1121,6 +1258,17 @@ impl Parser<'_> {
 								self.pop_peek();
 								hl = true;
 							}
+							_ if is_identifier_token(self, token, "FUZZY") => {
+								self.pop_peek();
+								let mut distance = 1;
+								if self.peek_kind() == TokenKind::Digits {
+									distance = self.next_token_value()?;
+									if !(1..=2).contains(&distance) {
+										bail!("Invalid value for FULLTEXT parameter `FUZZY`", @self.recent_span() => "`FUZZY` must be 1 or 2")
+									}
+								}
+								fz = Some(distance);
+							}
 							_ => break,
 						}
 					}
@@ -1128,6 +1276,7 @@ impl Parser<'_> {
 						az: analyzer.unwrap_or_else(|| "like".to_owned()).into(),
 						sc: scoring.unwrap_or_else(Default::default),
 						hl,
+						fz,
 					});
 				}
 				t!("HNSW") => {
@@ -1143,8 +1292,10 @@ impl Parser<'_> {
 					let mut extend_candidates = false;
 					let mut keep_pruned_connections = false;
 					let mut use_hashed_vector = false;
+					let mut quantization = None;
 					loop {
-						match self.peek_kind() {
+						let peek = self.peek();
+						match peek.kind {
 							t!("DISTANCE") => {
 								self.pop_peek();
 								distance = self.parse_distance()?;
@@ -1183,12 +1334,27 @@ impl Parser<'_> {
 								self.pop_peek();
 								use_hashed_vector = true;
 							}
+							kind if is_identifier_token(
+								self,
+								Token {
+									kind,
+									span: peek.span,
+								},
+								"QUANTIZATION",
+							) =>
+							{
+								self.pop_peek();
+								quantization = Some(self.parse_vector_quantization(dimension)?);
+							}
 							_ => {
 								break;
 							}
 						}
 					}
 
+					if matches!(distance, Distance::Mahalanobis(_)) {
+						bail!("Invalid DISTANCE for HNSW index", @self.recent_span() => "MAHALANOBIS is only supported by brute-force KNN")
+					}
 					let m = if let Some((m, span)) = m {
 						if m > 127 {
 							bail!("Invalid value for HNSW parameter `M`", @span => "`M` cannot be larger then 127")
@@ -1211,6 +1377,7 @@ impl Parser<'_> {
 						extend_candidates,
 						keep_pruned_connections,
 						use_hashed_vector,
+						quantization,
 					});
 				}
 				token
@@ -1235,6 +1402,7 @@ impl Parser<'_> {
 					let mut l_build = 100;
 					let mut alpha = 1.2.into();
 					let mut use_hashed_vector = false;
+					let mut quantization = None;
 					loop {
 						let peek = self.peek();
 						match peek.kind {
@@ -1286,6 +1454,18 @@ impl Parser<'_> {
 								self.pop_peek();
 								use_hashed_vector = true;
 							}
+							kind if is_identifier_token(
+								self,
+								Token {
+									kind,
+									span: peek.span,
+								},
+								"QUANTIZATION",
+							) =>
+							{
+								self.pop_peek();
+								quantization = Some(self.parse_vector_quantization(dimension)?);
+							}
 							_ => {
 								break;
 							}
@@ -1324,8 +1504,41 @@ impl Parser<'_> {
 						l_build,
 						alpha,
 						use_hashed_vector,
+						quantization,
+					});
+				}
+				token
+					if {
+						let span = self.peek().span;
+						is_identifier_token(
+							self,
+							Token {
+								kind: token,
+								span,
+							},
+							"SPATIAL",
+						)
+					} =>
+				{
+					self.pop_peek();
+					let mut precision = 8;
+					let peek = self.peek();
+					if is_identifier_token(self, peek, "PRECISION") {
+						self.pop_peek();
+						precision = self.next_token_value()?;
+						if !(1..=12).contains(&precision) {
+							bail!("Invalid value for SPATIAL parameter `PRECISION`", @self.recent_span() => "`PRECISION` must be between 1 and 12")
+						}
+					}
+					res.index = Index::Spatial(SpatialParams {
+						precision,
 					});
 				}
+				t!("WHERE") => {
+					let before = self.peek().span;
+					res.cond = self.try_parse_condition(stk).await?;
+					cond_span = Some(before.covers(self.last_span()));
+				}
 				t!("CONCURRENTLY") => {
 					self.pop_peek();
 					res.concurrently = true;
@@ -1337,13 +1550,26 @@ impl Parser<'_> {
 				_ => break,
 			}
 		}
+		// The condition of a COUNT index is stored with the index itself
+		if let Index::Count(count_cond) = &mut res.index
+			&& let Some(cond) = res.cond.take()
+		{
+			if count_cond.is_some() {
+				let span = cond_span.unwrap_or_else(|| self.recent_span());
+				bail!("Cannot specify a condition twice for a count index", @span);
+			}
+			*count_cond = Some(cond);
+		}
 		match (field_span, &res.index) {
 			(Some(field_span), Index::Count(_)) => {
 				if !res.cols.is_empty() {
 					bail!("Cannot create a count index with fields", @field_span);
 				}
 			}
-			(field_span, Index::FullText(_) | Index::Hnsw(_) | Index::DiskAnn(_)) => {
+			(
+				field_span,
+				Index::FullText(_) | Index::Hnsw(_) | Index::DiskAnn(_) | Index::Spatial(_),
+			) => {
 				if res.cols.len() != 1 {
 					if let Some(field_span) = field_span {
 						bail!("Expected one column, found {}", res.cols.len(), @field_span);
@@ -1362,6 +1588,27 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the argument of a vector index `QUANTIZATION` clause: `SCALAR`,
+	/// `BINARY`, or `PQ` followed by a number of subspaces dividing the
+	/// dimension.
+	fn parse_vector_quantization(&mut self, dimension: u16) -> ParseResult<VectorQuantization> {
+		let next = self.next();
+		if is_identifier_token(self, next, "SCALAR") {
+			return Ok(VectorQuantization::Scalar);
+		}
+		if is_identifier_token(self, next, "BINARY") {
+			return Ok(VectorQuantization::Binary);
+		}
+		if !is_identifier_token(self, next, "PQ") {
+			unexpected!(self, next, "SCALAR, BINARY, or PQ");
+		}
+		let subspaces: u16 = self.next_token_value()?;
+		if subspaces == 0 || subspaces > dimension || !dimension.is_multiple_of(subspaces) {
+			bail!("Invalid number of subspaces for `PQ` quantization", @self.last_span() => "The number of subspaces must divide the dimension")
+		}
+		Ok(VectorQuantization::Product(subspaces))
+	}
+
 	pub(crate) async fn parse_define_analyzer(
 		&mut self,
 		stk: &mut Stk,
@@ -1431,6 +1678,18 @@ impl Parser<'_> {
 								self.expect_closing_delimiter(t!(")"), open_span)?;
 								filters.push(Filter::Mapper(path))
 							}
+							_ if is_identifier_token(self, next, "STOPWORDS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let stop_words = self.parse_stop_words()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::StopWords(stop_words))
+							}
+							_ if is_identifier_token(self, next, "SYNONYMS") => {
+								let open_span = expected!(self, t!("(")).span;
+								let file = self.parse_filter_file()?;
+								self.expect_closing_delimiter(t!(")"), open_span)?;
+								filters.push(Filter::Synonyms(file))
+							}
 							_ => unexpected!(self, next, "a filter"),
 						}
 						if !self.eat(t!(",")) {
@@ -1450,6 +1709,8 @@ impl Parser<'_> {
 							t!("CAMEL") => Tokenizer::Camel,
 							t!("CLASS") => Tokenizer::Class,
 							t!("PUNCT") => Tokenizer::Punct,
+							_ if is_identifier_token(self, next, "UNICODE") => Tokenizer::Unicode,
+							_ if is_identifier_token(self, next, "CJK") => Tokenizer::Cjk,
 							_ => unexpected!(self, next, "a tokenizer"),
 						};
 						tokenizers.push(tokenizer);
@@ -1474,6 +1735,27 @@ impl Parser<'_> {
 		Ok(res)
 	}
 
+	/// Parses the argument of a `STOPWORDS` filter: a language, or a file.
+	pub(super) fn parse_stop_words(&mut self) -> ParseResult<StopWords> {
+		if matches!(self.peek_kind(), t!("f\"") | t!("f'")) {
+			Ok(StopWords::File(self.parse_filter_file()?))
+		} else {
+			Ok(StopWords::Language(self.next_token_value()?))
+		}
+	}
+
+	/// Parses the bucket file of an analyzer filter.
+	pub(super) fn parse_filter_file(&mut self) -> ParseResult<PublicFile> {
+		let peek = self.peek();
+		if !matches!(peek.kind, t!("f\"") | t!("f'")) {
+			unexpected!(self, peek, "a file");
+		}
+		if !self.settings.files_enabled {
+			unexpected!(self, peek, "the experimental files feature to be enabled");
+		}
+		self.next_token_value()
+	}
+
 	pub(crate) async fn parse_define_bucket(
 		&mut self,
 		stk: &mut Stk,
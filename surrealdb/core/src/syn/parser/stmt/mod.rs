@@ -1,5 +1,6 @@
 use reblessive::Stk;
 
+use self::define::is_identifier_token;
 use super::mac::expected;
 use super::{ParseResult, Parser};
 use crate::sql::data::Assignment;
@@ -11,7 +12,7 @@ use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::rebuild::RebuildIndexStatement;
 use crate::sql::statements::show::ShowSince;
 use crate::sql::statements::{
-	ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
+	AckStatement, ForeachStatement, InfoStatement, KillStatement, LiveStatement, OptionStatement,
 	OutputStatement, RebuildStatement, SetStatement, ShowStatement, SleepStatement, UseStatement,
 };
 use crate::sql::{AssignOperator, ExplainFormat, Expr, Literal, Param, TopLevelExpr};
@@ -113,6 +114,10 @@ impl Parser<'_> {
 				self.pop_peek();
 				self.parse_show_stmt().map(TopLevelExpr::Show)
 			}
+			t!("ACK") => {
+				self.pop_peek();
+				self.parse_ack_stmt(stk).await.map(TopLevelExpr::Ack)
+			}
 			_ => {
 				let covered = self.peek_kind() == t!("(");
 				let expr = self.parse_expr_start(stk).await?;
@@ -628,17 +633,38 @@ impl Parser<'_> {
 			_ => unexpected!(self, next, "`TABLE` or `DATABASE`"),
 		};
 
-		expected!(self, t!("SINCE"));
-
 		let next = self.peek();
-		let since = match next.kind {
-			TokenKind::Digits => {
+		let since = if is_identifier_token(self, next, "CONSUMER") {
+			self.pop_peek();
+			// Consumers are always defined on a single table
+			if table.is_none() {
+				unexpected!(self, next, "`SINCE`", => "consumers can only be read for a table");
+			}
+			let name = self.parse_ident_str()?.to_owned();
+			let peek = self.peek();
+			let member = if is_identifier_token(self, peek, "MEMBER") {
 				self.pop_peek();
-				let int = self.lex_compound(next, compound::integer)?.value;
-				ShowSince::Versionstamp(int)
+				Some(self.parse_string_lit()?)
+			} else {
+				None
+			};
+			ShowSince::Consumer {
+				name,
+				member,
+			}
+		} else {
+			expected!(self, t!("SINCE"));
+
+			let next = self.peek();
+			match next.kind {
+				TokenKind::Digits => {
+					self.pop_peek();
+					let int = self.lex_compound(next, compound::integer)?.value;
+					ShowSince::Versionstamp(int)
+				}
+				t!("d\"") | t!("d'") => ShowSince::Timestamp(self.next_token_value()?),
+				_ => unexpected!(self, next, "a version stamp or a date-time"),
 			}
-			t!("d\"") | t!("d'") => ShowSince::Timestamp(self.next_token_value()?),
-			_ => unexpected!(self, next, "a version stamp or a date-time"),
 		};
 
 		let limit = self.eat(t!("LIMIT")).then(|| self.next_token_value()).transpose()?;
@@ -650,6 +676,32 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parsers an ACK statement.
+	///
+	/// # Parser State
+	/// Expects `ACK` to already be consumed.
+	pub(super) async fn parse_ack_stmt(&mut self, stk: &mut Stk) -> ParseResult<AckStatement> {
+		let consumer = self.parse_ident_str()?.to_owned();
+		expected!(self, t!("ON"));
+		self.eat(t!("TABLE"));
+		let table = self.parse_ident_str()?.into();
+		let peek = self.peek();
+		let member = if is_identifier_token(self, peek, "MEMBER") {
+			self.pop_peek();
+			Some(self.parse_string_lit()?)
+		} else {
+			None
+		};
+		expected!(self, t!("AT"));
+		let versionstamp = stk.run(|stk| self.parse_expr_field(stk)).await?;
+		Ok(AckStatement {
+			consumer,
+			table,
+			member,
+			versionstamp,
+		})
+	}
+
 	/// Parsers a SLEEP statement
 	///
 	/// # Parser State
@@ -7,6 +7,7 @@ use surrealdb_types::ToSql;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::index::{Distance, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
+use crate::sql::ttl::Ttl;
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Literal,
 	Output, Permission, Permissions, View, With,
@@ -472,6 +473,21 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a ttl production
+	///
+	/// # Parser State
+	/// Expects the parser to have already eating the `TTL` keyword
+	pub(crate) async fn parse_ttl(&mut self, stk: &mut Stk) -> ParseResult<Ttl> {
+		let duration = self.next_token_value::<PublicDuration>()?;
+		expected!(self, t!("ON"));
+		let field = self.parse_plain_idiom(stk).await?;
+
+		Ok(Ttl {
+			duration,
+			field,
+		})
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -547,6 +563,10 @@ impl Parser<'_> {
 						Distance::Minkowski(distance)
 					}
 					DistanceKind::Pearson => Distance::Pearson,
+					DistanceKind::Spearman => Distance::Spearman,
+					DistanceKind::Mahalanobis => {
+						Distance::Mahalanobis(self.parse_covariance_matrix()?)
+					}
 				};
 				Ok(dist)
 			}
@@ -554,6 +574,34 @@ impl Parser<'_> {
 		}
 	}
 
+	/// Parses the covariance matrix of a `MAHALANOBIS` distance: a square
+	/// array of arrays of numbers.
+	fn parse_covariance_matrix(&mut self) -> ParseResult<Vec<Vec<surrealdb_types::Number>>> {
+		let start = expected!(self, t!("[")).span;
+		let mut rows = Vec::new();
+		while !self.eat(t!("]")) {
+			let row_start = expected!(self, t!("[")).span;
+			let mut row = Vec::new();
+			while !self.eat(t!("]")) {
+				row.push(self.next_token_value()?);
+				if !self.eat(t!(",")) {
+					self.expect_closing_delimiter(t!("]"), row_start)?;
+					break;
+				}
+			}
+			rows.push(row);
+			if !self.eat(t!(",")) {
+				self.expect_closing_delimiter(t!("]"), start)?;
+				break;
+			}
+		}
+		if rows.is_empty() || rows.iter().any(|row| row.len() != rows.len()) {
+			bail!("Invalid covariance matrix",
+				@start.covers(self.recent_span()) => "The covariance matrix must be a non-empty square matrix")
+		}
+		Ok(rows)
+	}
+
 	pub fn parse_vector_type(&mut self) -> ParseResult<VectorType> {
 		let next = self.next();
 		match next.kind {
@@ -1,8 +1,10 @@
 use reblessive::Stk;
 
+use super::define::is_identifier_token;
 use crate::sql::statements::remove::{
 	RemoveAnalyzerStatement, RemoveApiStatement, RemoveBucketStatement, RemoveConfigKind,
-	RemoveConfigStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveConfigStatement, RemoveConsumerStatement, RemoveModuleStatement, RemoveSequenceStatement,
+	RemoveSinkStatement,
 };
 use crate::sql::statements::{
 	RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement, RemoveFieldStatement,
@@ -336,6 +338,42 @@ impl Parser<'_> {
 					if_exists,
 				})
 			}
+			_ if is_identifier_token(self, next, "SINK") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let what = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Sink(RemoveSinkStatement {
+					name,
+					what,
+					if_exists,
+				})
+			}
+			_ if is_identifier_token(self, next, "CONSUMER") => {
+				let if_exists = if self.eat(t!("IF")) {
+					expected!(self, t!("EXISTS"));
+					true
+				} else {
+					false
+				};
+				let name = stk.run(|stk| self.parse_expr_field(stk)).await?;
+				expected!(self, t!("ON"));
+				self.eat(t!("TABLE"));
+				let what = stk.run(|stk| self.parse_expr_field(stk)).await?;
+
+				RemoveStatement::Consumer(RemoveConsumerStatement {
+					name,
+					what,
+					if_exists,
+				})
+			}
 			_ => unexpected!(self, next, "a remove statement keyword"),
 		};
 		Ok(res)
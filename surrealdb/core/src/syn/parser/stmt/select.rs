@@ -1,12 +1,18 @@
 use reblessive::Stk;
 
+use super::define::is_identifier_token;
 use super::parts::MissingKind;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::SelectStatement;
-use crate::sql::{Expr, Fields, Limit, Literal, Order, Split, Splits, Start};
-use crate::syn::parser::mac::expected;
+use crate::sql::window::{WindowBound, WindowCall, WindowFrame, WindowSpec};
+use crate::sql::{
+	Expr, Fields, FunctionCall, Fusion, FusionNorm, Limit, Literal, Order, Split, Splits, Start,
+};
+use crate::syn::error::bail;
+use crate::syn::parser::mac::{expected, unexpected};
 use crate::syn::parser::{ParseResult, Parser};
 use crate::syn::token::{Span, t};
+use crate::types::PublicNumber;
 
 impl Parser<'_> {
 	/// expects `select` to be eaten.
@@ -43,6 +49,7 @@ impl Parser<'_> {
 
 		let with = self.try_parse_with()?;
 		let cond = self.try_parse_condition(stk).await?;
+		let fusion = self.try_parse_fusion()?;
 
 		let split_before = self.peek().span;
 		let split = self.try_parse_split(&fields, fields_span)?;
@@ -75,6 +82,7 @@ impl Parser<'_> {
 			what,
 			with,
 			cond,
+			fusion,
 			split,
 			group,
 			order,
@@ -88,6 +96,56 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses the FUSION clause of a hybrid search:
+	/// `FUSION RRF[(constant)]` or `FUSION LINEAR[(fulltext, vector[, MINMAX | ZSCORE])]`.
+	pub(crate) fn try_parse_fusion(&mut self) -> ParseResult<Option<Fusion>> {
+		let token = self.peek();
+		if !is_identifier_token(self, token, "FUSION") {
+			return Ok(None);
+		}
+		self.pop_peek();
+
+		let next = self.next();
+		if is_identifier_token(self, next, "RRF") {
+			let mut constant = Fusion::DEFAULT_RRF_CONSTANT;
+			if self.eat(t!("(")) {
+				let open = self.last_span();
+				constant = self.next_token_value()?;
+				self.expect_closing_delimiter(t!(")"), open)?;
+			}
+			return Ok(Some(Fusion::Rrf(constant)));
+		}
+		if is_identifier_token(self, next, "LINEAR") {
+			let mut fulltext = PublicNumber::Int(1);
+			let mut vector = PublicNumber::Int(1);
+			let mut norm = FusionNorm::default();
+			if self.eat(t!("(")) {
+				let open = self.last_span();
+				fulltext = self.next_token_value()?;
+				expected!(self, t!(","));
+				vector = self.next_token_value()?;
+				if self.eat(t!(",")) {
+					let peek = self.peek();
+					if is_identifier_token(self, peek, "MINMAX") {
+						norm = FusionNorm::MinMax;
+					} else if is_identifier_token(self, peek, "ZSCORE") {
+						norm = FusionNorm::ZScore;
+					} else {
+						unexpected!(self, peek, "MINMAX or ZSCORE");
+					}
+					self.pop_peek();
+				}
+				self.expect_closing_delimiter(t!(")"), open)?;
+			}
+			return Ok(Some(Fusion::Linear {
+				fulltext,
+				vector,
+				norm,
+			}));
+		}
+		unexpected!(self, next, "RRF or LINEAR")
+	}
+
 	pub(crate) fn try_parse_split(
 		&mut self,
 		fields: &Fields,
@@ -191,6 +249,118 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses an optional `OVER (...)` clause following a builtin function
+	/// call, turning the call into a window call.
+	pub(crate) async fn try_parse_window(
+		&mut self,
+		stk: &mut Stk,
+		call: FunctionCall,
+	) -> ParseResult<Expr> {
+		let peek = self.peek();
+		if !is_identifier_token(self, peek, "OVER") || self.peek1().kind != t!("(") {
+			return Ok(Expr::FunctionCall(Box::new(call)));
+		}
+		self.pop_peek();
+		let start = expected!(self, t!("(")).span;
+
+		let mut spec = WindowSpec::default();
+		let peek = self.peek();
+		if is_identifier_token(self, peek, "PARTITION") {
+			self.pop_peek();
+			expected!(self, t!("BY"));
+			loop {
+				let expr = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+				spec.partition.push(expr);
+				if !self.eat(t!(",")) {
+					break;
+				}
+			}
+		}
+
+		if self.eat(t!("ORDER")) {
+			expected!(self, t!("BY"));
+			let mut orders = vec![self.parse_order()?];
+			while self.eat(t!(",")) {
+				orders.push(self.parse_order()?);
+			}
+			spec.order = Some(OrderList(orders));
+		}
+
+		let peek = self.peek();
+		if is_identifier_token(self, peek, "ROWS") {
+			self.pop_peek();
+			let frame_start = self.recent_span();
+			let peek = self.peek();
+			let frame = if is_identifier_token(self, peek, "BETWEEN") {
+				self.pop_peek();
+				let start = self.parse_window_bound()?;
+				expected!(self, t!("AND"));
+				let end = self.parse_window_bound()?;
+				WindowFrame {
+					start,
+					end,
+				}
+			} else {
+				// `ROWS <bound>` is shorthand for `ROWS BETWEEN <bound> AND CURRENT ROW`.
+				WindowFrame {
+					start: self.parse_window_bound()?,
+					end: WindowBound::CurrentRow,
+				}
+			};
+			let frame_span = frame_start.covers(self.last_span());
+			if frame.start == WindowBound::UnboundedFollowing {
+				bail!("A window frame can not start at `UNBOUNDED FOLLOWING`", @frame_span);
+			}
+			if frame.end == WindowBound::UnboundedPreceding {
+				bail!("A window frame can not end at `UNBOUNDED PRECEDING`", @frame_span);
+			}
+			if frame.start.rank() > frame.end.rank() {
+				bail!("A window frame can not start after it ends", @frame_span);
+			}
+			spec.frame = Some(frame);
+		}
+
+		self.expect_closing_delimiter(t!(")"), start)?;
+
+		Ok(Expr::Window(Box::new(WindowCall {
+			call,
+			spec,
+		})))
+	}
+
+	/// Parses one end of a `ROWS` frame.
+	fn parse_window_bound(&mut self) -> ParseResult<WindowBound> {
+		let peek = self.peek();
+		if is_identifier_token(self, peek, "UNBOUNDED") {
+			self.pop_peek();
+			let next = self.next();
+			if is_identifier_token(self, next, "PRECEDING") {
+				return Ok(WindowBound::UnboundedPreceding);
+			}
+			if is_identifier_token(self, next, "FOLLOWING") {
+				return Ok(WindowBound::UnboundedFollowing);
+			}
+			unexpected!(self, next, "`PRECEDING` or `FOLLOWING`")
+		}
+		if is_identifier_token(self, peek, "CURRENT") {
+			self.pop_peek();
+			let next = self.next();
+			if !is_identifier_token(self, next, "ROW") {
+				unexpected!(self, next, "`ROW`")
+			}
+			return Ok(WindowBound::CurrentRow);
+		}
+		let offset = self.next_token_value::<u64>()?;
+		let next = self.next();
+		if is_identifier_token(self, next, "PRECEDING") {
+			return Ok(WindowBound::Preceding(offset));
+		}
+		if is_identifier_token(self, next, "FOLLOWING") {
+			return Ok(WindowBound::Following(offset));
+		}
+		unexpected!(self, next, "`PRECEDING` or `FOLLOWING`")
+	}
+
 	pub(crate) async fn try_parse_limit(&mut self, stk: &mut Stk) -> ParseResult<Option<Limit>> {
 		if !self.eat(t!("LIMIT")) {
 			return Ok(None);
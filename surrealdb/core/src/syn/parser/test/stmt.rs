@@ -2,7 +2,7 @@ use chrono::offset::TimeZone;
 use chrono::{NaiveDate, Offset, Utc};
 use surrealdb_strand::Strand;
 
-use crate::catalog::EventKind;
+use crate::catalog::{EventKind, SinkKind};
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::{
 	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess, JwtAccessIssue,
@@ -11,8 +11,11 @@ use crate::sql::access_type::{
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::data::Assignment;
 use crate::sql::field::Selector;
-use crate::sql::filter::Filter;
-use crate::sql::index::{DiskAnnParams, Distance, FullTextParams, HnswParams, VectorType};
+use crate::sql::filter::{Filter, StopWords};
+use crate::sql::index::{
+	DiskAnnParams, Distance, FullTextParams, HnswParams, SpatialParams, VectorQuantization,
+	VectorType,
+};
 use crate::sql::language::Language;
 use crate::sql::literal::ObjectEntry;
 use crate::sql::lookup::{LookupKind, LookupSubject};
@@ -22,35 +25,36 @@ use crate::sql::statements::access::{
 };
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	DefineAccessStatement, DefineAnalyzerStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineStatement,
-	DefineTableStatement,
+	DefineAccessStatement, DefineAnalyzerStatement, DefineConsumerStatement,
+	DefineDatabaseStatement, DefineDefault, DefineEventStatement, DefineFieldStatement,
+	DefineFunctionStatement, DefineIndexStatement, DefineKind, DefineNamespaceStatement,
+	DefineParamStatement, DefineSinkStatement, DefineStatement, DefineTableStatement,
 };
 use crate::sql::statements::live::LiveFields;
 use crate::sql::statements::remove::{
-	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement,
+	RemoveAnalyzerStatement, RemoveConfigKind, RemoveConfigStatement, RemoveConsumerStatement,
+	RemoveSinkStatement,
 };
 use crate::sql::statements::show::{ShowSince, ShowStatement};
 use crate::sql::statements::sleep::SleepStatement;
 use crate::sql::statements::{
-	AccessStatement, CreateStatement, DeleteStatement, ForeachStatement, IfelseStatement,
-	InfoStatement, InsertStatement, KillStatement, OptionStatement, OutputStatement,
-	RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement, RemoveEventStatement,
-	RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement, RemoveNamespaceStatement,
-	RemoveParamStatement, RemoveStatement, RemoveTableStatement, RemoveUserStatement,
-	SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
+	AccessStatement, AckStatement, CreateStatement, DeleteStatement, ForeachStatement,
+	IfelseStatement, InfoStatement, InsertStatement, KillStatement, OptionStatement,
+	OutputStatement, RelateStatement, RemoveAccessStatement, RemoveDatabaseStatement,
+	RemoveEventStatement, RemoveFieldStatement, RemoveFunctionStatement, RemoveIndexStatement,
+	RemoveNamespaceStatement, RemoveParamStatement, RemoveStatement, RemoveTableStatement,
+	RemoveUserStatement, SelectStatement, UpdateStatement, UpsertStatement, UseStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
 	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Explain, Expr, Fetch,
 	Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Literal, Lookup, Mock, Output, Param,
-	Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, TableType, TopLevelExpr,
-	With,
+	Part, Permission, Permissions, PrefixOperator, RecordIdKeyLit, RecordIdLit, Scoring, TableType,
+	TopLevelExpr, Ttl, With,
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
-use crate::types::{PublicDatetime, PublicDuration, PublicUuid};
+use crate::types::{PublicDatetime, PublicDuration, PublicFile, PublicUuid};
 use crate::val::range::TypedRange;
 
 fn ident_field(name: &str) -> Expr {
@@ -1592,6 +1596,7 @@ fn parse_define_table() {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: true,
 			}),
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -1601,6 +1606,27 @@ fn parse_define_table() {
 	);
 }
 
+#[test]
+fn parse_define_table_ttl() {
+	let res = syn::parse_with(
+		r#"DEFINE TABLE session TTL 1h ON created_at"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Table(DefineTableStatement {
+			name: Expr::Table("session".into()),
+			ttl: Some(Ttl {
+				duration: PublicDuration::from_secs(3600),
+				field: Idiom::field("created_at".to_string()),
+			}),
+			..Default::default()
+		})))
+	);
+}
+
 #[test]
 fn parse_define_event() {
 	let res = syn::parse_with(
@@ -1627,6 +1653,101 @@ fn parse_define_event() {
 	)
 }
 
+#[test]
+fn parse_define_sink() {
+	let res = syn::parse_with(
+		r#"DEFINE SINK orders ON TABLE purchase TO KAFKA "kafka://localhost:9092" TOPIC "orders" RETRY 5 BACKOFF 2s DEADLETTER failed COMMENT "test""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("orders".to_string())),
+			target_table: Expr::Table("purchase".into()),
+			sink_kind: SinkKind::Kafka,
+			url: Expr::Literal(Literal::String(Strand::new_static("kafka://localhost:9092"))),
+			topic: Expr::Literal(Literal::String(Strand::new_static("orders"))),
+			retry: 5,
+			backoff: Expr::Literal(Literal::Duration(PublicDuration::from_secs(2))),
+			dead_letter: Expr::Table("failed".into()),
+			comment: Expr::Literal(Literal::String(Strand::new_static("test"))),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE SINK IF NOT EXISTS hook ON person TO WEBHOOK "https://example.com/hook""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Sink(DefineSinkStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("hook".to_string())),
+			target_table: Expr::Table("person".into()),
+			sink_kind: SinkKind::Webhook,
+			url: Expr::Literal(Literal::String(Strand::new_static("https://example.com/hook"))),
+			topic: Expr::Literal(Literal::None),
+			retry: 3,
+			backoff: Expr::Literal(Literal::None),
+			dead_letter: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		})))
+	);
+
+	syn::parse_with(
+		r#"DEFINE SINK hook ON person TO KINESIS "https://example.com""#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap_err();
+}
+
+#[test]
+fn parse_define_consumer() {
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER indexer ON TABLE purchase PARTITIONS 4 TIMEOUT 1m COMMENT "test""#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("indexer".to_string())),
+			target_table: Expr::Table("purchase".into()),
+			partitions: 4,
+			timeout: Expr::Literal(Literal::Duration(PublicDuration::from_secs(60))),
+			comment: Expr::Literal(Literal::String(Strand::new_static("test"))),
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE CONSUMER IF NOT EXISTS indexer ON person"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Consumer(DefineConsumerStatement {
+			kind: DefineKind::IfNotExists,
+			name: Expr::Idiom(Idiom::field("indexer".to_string())),
+			target_table: Expr::Table("person".into()),
+			partitions: 1,
+			timeout: Expr::Literal(Literal::None),
+			comment: Expr::Literal(Literal::None),
+		})))
+	);
+}
+
 #[test]
 fn parse_define_field() {
 	// General
@@ -1701,12 +1822,35 @@ fn parse_define_index() {
 					k1: 0.1,
 					b: 0.2
 				},
+				fz: None,
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
 
+	let fuzzy = |sql: &str| {
+		let res = syn::parse_with(sql.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})?;
+		let Expr::Define(stmt) = res else {
+			panic!("expected a define statement");
+		};
+		let DefineStatement::Index(DefineIndexStatement {
+			index: Index::FullText(params),
+			..
+		}) = *stmt
+		else {
+			panic!("expected a full-text index");
+		};
+		Ok::<_, anyhow::Error>(params.fz)
+	};
+	let sql = "DEFINE INDEX index ON TABLE table FIELDS a FULLTEXT ANALYZER ana";
+	assert_eq!(fuzzy(&format!("{sql} FUZZY HIGHLIGHTS")).unwrap(), Some(1));
+	assert_eq!(fuzzy(&format!("{sql} BM25 FUZZY 2")).unwrap(), Some(2));
+	fuzzy(&format!("{sql} FUZZY 3")).unwrap_err();
+
 	let res = syn::parse_with(
 		r#"DEFINE INDEX index ON TABLE table FIELDS a UNIQUE"#.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
@@ -1721,13 +1865,14 @@ fn parse_define_index() {
 			what: Expr::Table("table".into()),
 			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
 			index: Index::Uniq,
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
 
 	let res =
-		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 EFC 250 TYPE F32 DISTANCE MANHATTAN M 6 M0 12 LM 0.5 EXTEND_CANDIDATES KEEP_PRUNED_CONNECTIONS HASHED_VECTOR"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
+		syn::parse_with( r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 EFC 250 TYPE F32 DISTANCE MANHATTAN M 6 M0 12 LM 0.5 EXTEND_CANDIDATES KEEP_PRUNED_CONNECTIONS HASHED_VECTOR QUANTIZATION BINARY"#.as_bytes(),async |parser,stk| parser.parse_expr_inherit(stk).await).unwrap();
 	assert_eq!(
 		res,
 		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
@@ -1746,7 +1891,9 @@ fn parse_define_index() {
 				keep_pruned_connections: true,
 				ml: 0.5.into(),
 				use_hashed_vector: true,
+				quantization: Some(VectorQuantization::Binary),
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
@@ -1772,14 +1919,16 @@ fn parse_define_index() {
 				l_build: 100,
 				alpha: 1.2.into(),
 				use_hashed_vector: false,
+				quantization: None,
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
 
 	let res = syn::parse_with(
-		r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 128 DEGREE 32 L_BUILD 88 ALPHA 1.4 TYPE F32 DISTANCE COSINE HASHED_VECTOR"#.as_bytes(),
+		r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 128 DEGREE 32 L_BUILD 88 ALPHA 1.4 TYPE F32 DISTANCE COSINE HASHED_VECTOR QUANTIZATION PQ 16"#.as_bytes(),
 		async |parser, stk| parser.parse_expr_inherit(stk).await,
 	)
 	.unwrap();
@@ -1798,7 +1947,9 @@ fn parse_define_index() {
 				l_build: 88,
 				alpha: 1.4.into(),
 				use_hashed_vector: true,
+				quantization: Some(VectorQuantization::Product(16)),
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
@@ -1824,7 +1975,9 @@ fn parse_define_index() {
 				l_build: 100,
 				alpha: 1.2.into(),
 				use_hashed_vector: false,
+				quantization: None,
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
@@ -1850,12 +2003,118 @@ fn parse_define_index() {
 				l_build: 100,
 				alpha: 1.2.into(),
 				use_hashed_vector: false,
+				quantization: None,
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false
 		})))
 	);
 
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 4 QUANTIZATION SCALAR"#
+			.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::DiskAnn(DiskAnnParams {
+				dimension: 4,
+				distance: Distance::Euclidean,
+				vector_type: VectorType::F32,
+				degree: 64,
+				l_build: 100,
+				alpha: 1.2.into(),
+				use_hashed_vector: false,
+				quantization: Some(VectorQuantization::Scalar),
+			}),
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	for sql in [
+		"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 128 QUANTIZATION PQ 3",
+		"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 4 QUANTIZATION PQ 8",
+		"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 4 QUANTIZATION PQ 0",
+		"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 4 QUANTIZATION INT8",
+	] {
+		syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+			.unwrap_err();
+	}
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Spatial(SpatialParams {
+				precision: 8,
+			}),
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL PRECISION 5"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Spatial(SpatialParams {
+				precision: 5,
+			}),
+			cond: None,
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+
+	assert!(
+		syn::parse_with(
+			r#"DEFINE INDEX index ON TABLE table FIELDS a SPATIAL PRECISION 0"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.is_err()
+	);
+	assert!(
+		syn::parse_with(
+			r#"DEFINE INDEX index ON TABLE table FIELDS a, b SPATIAL"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.is_err()
+	);
+
+	assert!(
+		syn::parse_with(
+			r#"DEFINE INDEX index ON TABLE table FIELDS a HNSW DIMENSION 2 DISTANCE MAHALANOBIS [[1, 0], [0, 1]]"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.is_err()
+	);
 	assert!(
 		syn::parse_with(
 			r#"DEFINE INDEX index ON TABLE table FIELDS a DISKANN DIMENSION 128 TYPE I64"#
@@ -1889,6 +2148,57 @@ fn parse_define_index() {
 	);
 }
 
+#[test]
+fn parse_define_partial_index() {
+	use surrealdb_types::ToSql;
+
+	let parse = |sql: &str| {
+		syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+	};
+
+	let res = parse("DEFINE INDEX index ON TABLE table FIELDS a UNIQUE WHERE !deleted").unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Index(DefineIndexStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("index".to_string())),
+			what: Expr::Table("table".into()),
+			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
+			index: Index::Uniq,
+			cond: Some(Cond(Expr::Prefix {
+				op: PrefixOperator::Not,
+				expr: Box::new(Expr::Idiom(Idiom::field("deleted".to_string()))),
+			})),
+			comment: Expr::Literal(Literal::None),
+			concurrently: false
+		})))
+	);
+	assert_eq!(res.to_sql(), "DEFINE INDEX index ON table FIELDS a UNIQUE WHERE !deleted");
+
+	// The condition can precede the index type, and is printed after it
+	let res = parse("DEFINE INDEX index ON TABLE table FIELDS a WHERE a > 1 FULLTEXT ANALYZER ana")
+		.unwrap();
+	assert_eq!(
+		res.to_sql(),
+		"DEFINE INDEX index ON table FIELDS a FULLTEXT ANALYZER ana BM25(1.2,0.75) WHERE a > 1"
+	);
+
+	// The condition of a COUNT index is stored with the index
+	let res = parse("DEFINE INDEX index ON TABLE table WHERE active COUNT").unwrap();
+	let Expr::Define(stmt) = res else {
+		panic!("expected a define statement");
+	};
+	let DefineStatement::Index(stmt) = *stmt else {
+		panic!("expected a define index statement");
+	};
+	assert_eq!(
+		stmt.index,
+		Index::Count(Some(Cond(Expr::Idiom(Idiom::field("active".to_string())))))
+	);
+	assert_eq!(stmt.cond, None);
+	parse("DEFINE INDEX index ON TABLE table COUNT WHERE active WHERE enabled").unwrap_err();
+}
+
 #[test]
 fn parse_define_analyzer() {
 	let res = syn::parse_with(r#"DEFINE ANALYZER ana FILTERS ASCII, EDGENGRAM(1,2), NGRAM(3,4), LOWERCASE, SNOWBALL(NLD), UPPERCASE TOKENIZERS BLANK, CAMEL, CLASS, PUNCT FUNCTION fn::foo::bar"#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
@@ -1951,6 +2261,54 @@ fn parse_define_analyzer_module_function() {
 	assert!(err.to_string().contains("Experimental capability `surrealism` is not enabled"));
 }
 
+#[test]
+fn parse_define_analyzer_stop_words_synonyms() {
+	let sql = r#"DEFINE ANALYZER ana TOKENIZERS blank FILTERS lowercase, stopwords(english), stopwords(f"lists:/stop.txt"), synonyms(f"lists:/synonyms.txt")"#;
+	let res = syn::parse_with_settings(
+		sql.as_bytes(),
+		ParserSettings {
+			files_enabled: true,
+			..Default::default()
+		},
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Analyzer(DefineAnalyzerStatement {
+			kind: DefineKind::Default,
+			name: Expr::Idiom(Idiom::field("ana".to_string())),
+			tokenizers: Some(vec![Tokenizer::Blank]),
+			filters: Some(vec![
+				Filter::Lowercase,
+				Filter::StopWords(StopWords::Language(Language::English)),
+				Filter::StopWords(StopWords::File(PublicFile::new("lists", "/stop.txt"))),
+				Filter::Synonyms(PublicFile::new("lists", "/synonyms.txt")),
+			]),
+			comment: Expr::Literal(Literal::None),
+			function: None,
+		}))),
+	);
+
+	// The files require the experimental files feature
+	let err =
+		syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+			.unwrap_err();
+	assert!(err.to_string().contains("the experimental files feature to be enabled"));
+	// Synonyms are only read from a file
+	assert!(
+		syn::parse_with_settings(
+			r#"DEFINE ANALYZER ana FILTERS synonyms("synonyms.txt")"#.as_bytes(),
+			ParserSettings {
+				files_enabled: true,
+				..Default::default()
+			},
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.is_err()
+	);
+}
+
 #[test]
 fn parse_define_analyzer_rejects_function_calls() {
 	let err = syn::parse_with(
@@ -2046,6 +2404,7 @@ pub fn parse_for() {
 					only: false,
 					with: None,
 					cond: None,
+					fusion: None,
 					split: None,
 					group: None,
 					order: None,
@@ -2183,6 +2542,86 @@ fn parse_info() {
 	);
 }
 
+#[test]
+fn parse_select_window() {
+	use surrealdb_types::ToSql;
+
+	let sql = "SELECT math::sum(x) OVER (PARTITION BY a ORDER BY b DESC ROWS BETWEEN 2 PRECEDING AND CURRENT ROW) AS total FROM t";
+	let res = syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+		.unwrap();
+	assert_eq!(res.to_sql(), sql);
+
+	// `ROWS <bound>` is shorthand for a frame ending at the current row.
+	let res = syn::parse_with(
+		"SELECT window::row_number() OVER (ORDER BY b ROWS UNBOUNDED PRECEDING) FROM t".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res.to_sql(),
+		"SELECT window::row_number() OVER (ORDER BY b ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) FROM t"
+	);
+
+	// Without a parenthesized window, `over` is still a plain field name.
+	let res = syn::parse_with("SELECT count() AS over FROM t".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res.to_sql(), "SELECT count() AS over FROM t");
+
+	for invalid in [
+		"SELECT math::sum(x) OVER (ROWS BETWEEN UNBOUNDED FOLLOWING AND CURRENT ROW) FROM t",
+		"SELECT math::sum(x) OVER (ROWS BETWEEN CURRENT ROW AND UNBOUNDED PRECEDING) FROM t",
+		"SELECT math::sum(x) OVER (ROWS BETWEEN 1 FOLLOWING AND 1 PRECEDING) FROM t",
+		"SELECT math::sum(x) OVER (ROWS BETWEEN 1 AND 2) FROM t",
+		"SELECT math::sum(x) OVER (PARTITION a) FROM t",
+	] {
+		assert!(
+			syn::parse_with(invalid.as_bytes(), async |parser, stk| parser
+				.parse_expr_inherit(stk)
+				.await)
+			.is_err(),
+			"{invalid} should not parse"
+		);
+	}
+}
+
+#[test]
+fn parse_select_fusion() {
+	use surrealdb_types::ToSql;
+
+	let parse = |sql: &str| {
+		syn::parse_with(sql.as_bytes(), async |parser, stk| parser.parse_expr_inherit(stk).await)
+	};
+
+	let sql = "SELECT * FROM doc WHERE text @1@ 'rust' OR emb <|2,40|> $v FUSION RRF(60) LIMIT 5";
+	assert_eq!(parse(sql).unwrap().to_sql(), sql);
+	let sql = "SELECT * FROM doc WHERE text @1@ 'rust' OR emb <|2,40|> $v FUSION LINEAR(0.7f, 0.3f, ZSCORE)";
+	assert_eq!(parse(sql).unwrap().to_sql(), sql);
+
+	// Arguments default to an RRF constant of 60 and equal MINMAX weights.
+	let res = parse("SELECT * FROM doc WHERE text @@ 'rust' OR emb <|2,40|> $v FUSION rrf").unwrap();
+	assert_eq!(
+		res.to_sql(),
+		"SELECT * FROM doc WHERE text @@ 'rust' OR emb <|2,40|> $v FUSION RRF(60)"
+	);
+	let res = parse("SELECT * FROM doc WHERE text @@ 'rust' OR emb <|2,40|> $v FUSION LINEAR").unwrap();
+	assert_eq!(
+		res.to_sql(),
+		"SELECT * FROM doc WHERE text @@ 'rust' OR emb <|2,40|> $v FUSION LINEAR(1, 1, MINMAX)"
+	);
+
+	for invalid in [
+		"SELECT * FROM doc WHERE a @@ 'b' FUSION",
+		"SELECT * FROM doc WHERE a @@ 'b' FUSION SUM",
+		"SELECT * FROM doc WHERE a @@ 'b' FUSION RRF(",
+		"SELECT * FROM doc WHERE a @@ 'b' FUSION LINEAR(1)",
+		"SELECT * FROM doc WHERE a @@ 'b' FUSION LINEAR(1, 1, MAX)",
+	] {
+		assert!(parse(invalid).is_err(), "{invalid} should not parse");
+	}
+}
+
 #[test]
 fn parse_show() {
 	let res = syn::parse_with(
@@ -2227,7 +2666,29 @@ fn parse_show() {
 			since: ShowSince::Timestamp(PublicDatetime::from(expected_datetime)),
 			limit: None
 		})
+	);
+
+	let res = syn::parse_with(
+		r#"SHOW CHANGES FOR TABLE foo CONSUMER bar MEMBER "worker-1" LIMIT 10"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
 	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Show(ShowStatement {
+			table: Some("foo".into()),
+			since: ShowSince::Consumer {
+				name: "bar".to_owned(),
+				member: Some("worker-1".to_owned()),
+			},
+			limit: Some(10)
+		})
+	);
+
+	syn::parse_with(r#"SHOW CHANGES FOR DATABASE CONSUMER bar"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap_err();
 }
 
 #[test]
@@ -2408,6 +2869,7 @@ fn parse_insert_select() {
 				what: vec![Expr::Table("baz".into())],
 				with: None,
 				cond: None,
+				fusion: None,
 				split: None,
 				group: None,
 				order: None,
@@ -2428,6 +2890,38 @@ fn parse_insert_select() {
 	)
 }
 
+#[test]
+fn parse_ack() {
+	let res = syn::parse_with(
+		r#"ACK bar ON TABLE foo MEMBER "worker-1" AT 10"#.as_bytes(),
+		async |parser, stk| parser.parse_top_level_expr(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Ack(AckStatement {
+			consumer: "bar".to_owned(),
+			table: "foo".into(),
+			member: Some("worker-1".to_owned()),
+			versionstamp: Expr::Literal(Literal::Integer(10)),
+		})
+	);
+
+	let res = syn::parse_with(r#"ACK bar ON foo AT $vs"#.as_bytes(), async |parser, stk| {
+		parser.parse_top_level_expr(stk).await
+	})
+	.unwrap();
+	assert_eq!(
+		res,
+		TopLevelExpr::Ack(AckStatement {
+			consumer: "bar".to_owned(),
+			table: "foo".into(),
+			member: None,
+			versionstamp: Expr::Param(Param::new("vs".to_owned())),
+		})
+	);
+}
+
 #[test]
 fn parse_kill() {
 	let res = syn::parse_with(r#"KILL $param"#.as_bytes(), async |parser, stk| {
@@ -2706,6 +3200,34 @@ fn parse_remove() {
 		})))
 	);
 
+	let res =
+		syn::parse_with(r#"REMOVE SINK IF EXISTS foo ON bar"#.as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Sink(RemoveSinkStatement {
+			name: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("foo"))])),
+			what: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("bar"))])),
+			if_exists: true,
+		})))
+	);
+
+	let res = syn::parse_with(
+		r#"REMOVE CONSUMER IF EXISTS foo ON TABLE bar"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Remove(Box::new(RemoveStatement::Consumer(RemoveConsumerStatement {
+			name: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("foo"))])),
+			what: Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("bar"))])),
+			if_exists: true,
+		})))
+	);
+
 	let res =
 		syn::parse_with(r#"REMOVE FIELD foo.bar[10] ON bar"#.as_bytes(), async |parser, stk| {
 			parser.parse_expr_inherit(stk).await
@@ -288,6 +288,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: false,
 			}),
+			ttl: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -351,7 +352,9 @@ fn statements() -> Vec<TopLevelExpr> {
 					k1: 0.1,
 					b: 0.2,
 				},
+				fz: None,
 			}),
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		})))),
@@ -361,6 +364,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			what: Expr::Table("table".into()),
 			cols: vec![Expr::Idiom(Idiom(vec![Part::Field(Strand::new_static("a"))]))],
 			index: Index::Uniq,
+			cond: None,
 			comment: Expr::Literal(Literal::None),
 			concurrently: false,
 		})))),
@@ -428,6 +432,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					only: false,
 					with: None,
 					cond: None,
+					fusion: None,
 					split: None,
 					group: None,
 					order: None,
@@ -488,6 +493,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			what: vec![Expr::Table("a".into())],
 			with: Some(With::Index(vec!["index".to_owned(), "index_2".to_owned()])),
 			cond: Some(Cond(Expr::Literal(Literal::Bool(true)))),
+			fusion: None,
 			split: None,
 			group: Some(Groups(vec![
 				Group(Idiom(vec![Part::Field(Strand::new_static("foo"))])),
@@ -529,6 +535,7 @@ fn statements() -> Vec<TopLevelExpr> {
 			what: vec![Expr::Table("a".into())],
 			with: None,
 			cond: None,
+			fusion: None,
 			split: Some(Splits(vec![
 				Split(Idiom(vec![Part::Field(Strand::new_static("foo"))])),
 				Split(Idiom(vec![Part::Field(Strand::new_static("bar"))])),
@@ -7,7 +7,7 @@ impl Parser<'_> {
 		matches!(
 			kind,
 			t!("ACCESS")
-				| t!("ALTER")
+				| t!("ACK") | t!("ALTER")
 				| t!("BEGIN")
 				| t!("BREAK")
 				| t!("CANCEL")
@@ -120,7 +120,8 @@ impl Parser<'_> {
 	pub(super) fn starts_disallowed_subquery_statement(kind: TokenKind) -> bool {
 		matches!(
 			kind,
-			t!("BEGIN")
+			t!("ACK")
+				| t!("BEGIN")
 				| t!("BREAK")
 				| t!("CANCEL")
 				| t!("COMMIT")
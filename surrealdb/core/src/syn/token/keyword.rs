@@ -25,6 +25,7 @@ macro_rules! keyword {
 
 keyword! {
 	Access => "ACCESS",
+	Ack => "ACK",
 	After => "AFTER",
 	Algorithm => "ALGORITHM",
 	All => "ALL",
@@ -210,6 +211,7 @@ keyword! {
 	To => "TO",
 	Transaction => "TRANSACTION",
 	True => "true",
+	Ttl => "TTL",
 	Type => "TYPE",
 	Unique => "UNIQUE",
 	Unset => "UNSET",
@@ -329,6 +329,9 @@ macro_rules! t {
 	("PEARSON") => {
 		$crate::syn::token::TokenKind::Distance($crate::syn::token::DistanceKind::Pearson)
 	};
+	("SPEARMAN") => {
+		$crate::syn::token::TokenKind::Distance($crate::syn::token::DistanceKind::Spearman)
+	};
 
 	// VectorType
 	("F64") => {
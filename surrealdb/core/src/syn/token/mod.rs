@@ -237,9 +237,11 @@ pub enum DistanceKind {
 	Hamming,
 	InnerProduct,
 	Jaccard,
+	Mahalanobis,
 	Manhattan,
 	Minkowski,
 	Pearson,
+	Spearman,
 }
 
 impl DistanceKind {
@@ -252,9 +254,11 @@ impl DistanceKind {
 			DistanceKind::Hamming => "HAMMING",
 			DistanceKind::InnerProduct => "INNER_PRODUCT",
 			DistanceKind::Jaccard => "JACCARD",
+			DistanceKind::Mahalanobis => "MAHALANOBIS",
 			DistanceKind::Manhattan => "MANHATTAN",
 			DistanceKind::Minkowski => "MINKOWSKI",
 			DistanceKind::Pearson => "PEARSON",
+			DistanceKind::Spearman => "SPEARMAN",
 		}
 	}
 }
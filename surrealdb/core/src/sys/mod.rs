@@ -7,7 +7,10 @@ use sysinfo::{Pid, System};
 /// Falls back to cgroup limits when running inside a container, and
 /// uses a conservative 1 GiB default when `/proc` is inaccessible
 /// (e.g. systemd `ProcSubset=pid` hardening).
-#[cfg_attr(not(any(feature = "kv-rocksdb", feature = "kv-surrealkv")), allow(dead_code))]
+#[cfg_attr(
+	not(any(feature = "kv-rocksdb", feature = "kv-surrealkv", feature = "kv-redb")),
+	allow(dead_code)
+)]
 pub(crate) static TOTAL_SYSTEM_MEMORY: LazyLock<u64> = LazyLock::new(|| {
 	// Load the system attributes
 	let mut system = System::new();
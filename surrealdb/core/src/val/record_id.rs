@@ -442,6 +442,7 @@ impl RecordId {
 			only: false,
 			with: None,
 			cond: None,
+			fusion: None,
 			split: None,
 			group: None,
 			order: None,
@@ -54,6 +54,7 @@ impl Value {
 							fields: g.expr.clone().unwrap_or(Fields::all()),
 							what: vec![what],
 							cond: g.cond.clone(),
+							fusion: None,
 							limit: g.limit.clone(),
 							order: g.order.clone(),
 							split: g.split.clone(),
@@ -260,6 +261,7 @@ impl Value {
 					only: false,
 					with: None,
 					cond: None,
+					fusion: None,
 					split: None,
 					group: None,
 					order: None,
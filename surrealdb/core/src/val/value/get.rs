@@ -590,6 +590,7 @@ impl Value {
 								fields,
 								what: vec![what],
 								cond: g.cond.clone(),
+								fusion: None,
 								limit: g.limit.clone(),
 								order: g.order.clone(),
 								split: g.split.clone(),
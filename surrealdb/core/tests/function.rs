@@ -3525,6 +3525,54 @@ async fn function_vector_similarity_pearson() -> Result<()> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn function_vector_similarity_spearman() -> Result<()> {
+	test_queries(
+		r#"
+		RETURN vector::similarity::spearman([1, 2, 3, 4, 5], [5, 6, 7, 8, 7]);
+		RETURN vector::similarity::spearman([NaN, 1, 2, 3], [NaN, 1, 2, 3]);
+		RETURN vector::similarity::spearman([1, 2, 3], [30, 20, 10]);
+	"#,
+		&["0.8207826816681233", "NaN", "-1f"],
+	)
+	.await?;
+
+	check_test_is_error(
+		r"RETURN vector::similarity::spearman([1, 2, 3], [4, 5]);
+		RETURN vector::similarity::spearman([1, 2], [4, 5, 5]);",
+		&[
+			"Incorrect arguments for function vector::similarity::spearman(). The two vectors must be of the same dimension.",
+			"Incorrect arguments for function vector::similarity::spearman(). The two vectors must be of the same dimension."
+		]).await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn function_vector_distance_mahalanobis() -> Result<()> {
+	test_queries(
+		r#"
+		RETURN vector::distance::mahalanobis([1, 2], [3, 4], [[1, 0], [0, 1]]);
+		RETURN vector::distance::mahalanobis([1, 0], [0, 0], [[2, 0], [0, 1]]);
+		RETURN vector::distance::mahalanobis([1, 2, 3], [1, 2, 3], [[2, 1, 0], [1, 2, 1], [0, 1, 2]]);
+	"#,
+		&["2.8284271247461903", "0.7071067811865476", "0f"],
+	)
+	.await?;
+
+	check_test_is_error(
+		r"RETURN vector::distance::mahalanobis([1, 2], [4, 5, 6], [[1, 0], [0, 1]]);
+		RETURN vector::distance::mahalanobis([1, 2], [4, 5], [[1, 0, 0], [0, 1, 0]]);
+		RETURN vector::distance::mahalanobis([1, 2], [4, 5], [[1, 2], [2, 4]]);
+		RETURN vector::distance::mahalanobis([1, 2], [4, 5], [[1, 0], [1, 1]]);",
+		&[
+			"Incorrect arguments for function vector::distance::mahalanobis(). The two vectors must be of the same dimension.",
+			"Incorrect arguments for function vector::distance::mahalanobis(). The covariance matrix must be a square matrix of the same dimension as the vectors.",
+			"Incorrect arguments for function vector::distance::mahalanobis(). The covariance matrix must be symmetric and positive definite.",
+			"Incorrect arguments for function vector::distance::mahalanobis(). The covariance matrix must be symmetric and positive definite."
+		]).await?;
+	Ok(())
+}
+
 #[tokio::test]
 async fn function_vector_distance_euclidean() -> Result<()> {
 	test_queries(
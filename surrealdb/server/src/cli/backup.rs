@@ -0,0 +1,184 @@
+use anyhow::{Result, ensure};
+use clap::{Args, Subcommand};
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::kvs::{BackupHeader, BackupKind};
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
+use tokio::fs::File;
+use tokio::io::{AsyncWriteExt, BufReader};
+
+use crate::cli::abstraction::{DatabaseSelectionArguments, LevelSelectionArguments};
+
+#[derive(Debug, Subcommand)]
+pub enum BackupCommand {
+	#[command(about = "Write a binary backup of a database to a file")]
+	Create(CreateCommandArguments),
+	#[command(about = "Restore a database from one or more backup files")]
+	Restore(RestoreCommandArguments),
+}
+
+#[derive(Args, Debug)]
+pub struct CreateCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "Path to the backup file to write")]
+	#[arg(index = 2)]
+	file: String,
+	#[command(flatten)]
+	sel: DatabaseSelectionArguments,
+	#[arg(help = "Only back up the changes made since this earlier backup of the same database")]
+	#[arg(long = "incremental", value_name = "BACKUP")]
+	incremental: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct RestoreCommandArguments {
+	#[arg(help = "Database path used for storing data")]
+	#[arg(env = "SURREAL_PATH", index = 1)]
+	path: String,
+	#[arg(help = "The full backup to restore, followed by any incremental backups in order")]
+	#[arg(index = 2, required = true, num_args = 1..)]
+	files: Vec<String>,
+	#[command(flatten)]
+	sel: LevelSelectionArguments,
+}
+
+/// Back up or restore a database in the datastore at the given path.
+///
+/// These commands work offline: the datastore is opened directly rather than
+/// through a running server, so a datastore stored on disk must not be in use
+/// by a server while a backup is taken or restored. Each backup holds a
+/// single database; a namespace is backed up by backing up each of its
+/// databases in turn.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	command: BackupCommand,
+) -> Result<()> {
+	match command {
+		BackupCommand::Create(args) => create(composer, args).await,
+		BackupCommand::Restore(args) => restore(composer, args).await,
+	}
+}
+
+async fn open<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	path: &str,
+) -> Result<Datastore> {
+	C::path_valid(path)?;
+	let ds = Datastore::builder().build_with_factory_path(path, composer).await?;
+	ds.check_version().await?;
+	Ok(ds)
+}
+
+async fn create<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	CreateCommandArguments {
+		path,
+		file,
+		sel: DatabaseSelectionArguments {
+			namespace,
+			database,
+		},
+		incremental,
+	}: CreateCommandArguments,
+) -> Result<()> {
+	// An incremental backup continues where the previous backup ended
+	let since = match incremental {
+		Some(previous) => {
+			let header =
+				BackupHeader::read(&mut BufReader::new(File::open(&previous).await?)).await?;
+			ensure!(
+				header.namespace == namespace && header.database == database,
+				"The backup '{previous}' was taken from {}/{}, not {namespace}/{database}",
+				header.namespace,
+				header.database,
+			);
+			Some(header.until)
+		}
+		None => None,
+	};
+
+	let ds = open(composer, &path).await?;
+	let sess = Session::owner().with_ns(&namespace).with_db(&database);
+	let (snd, rcv) = surrealdb_core::channel::bounded(16);
+	let job = ds.backup(&sess, since, snd).await?;
+	// Write to a temporary file so that a failed backup never leaves a
+	// truncated file behind under the final name
+	let partial = format!("{file}.partial");
+	let writer = async {
+		let mut out = tokio::fs::File::create(&partial).await?;
+		while let Ok(bytes) = rcv.recv().await {
+			out.write_all(&bytes).await?;
+		}
+		out.sync_all().await?;
+		Ok(())
+	};
+	let res = tokio::try_join!(job, writer);
+	ds.shutdown().await?;
+	let (header, ()) = match res {
+		Ok(v) => v,
+		Err(e) => {
+			let _ = tokio::fs::remove_file(&partial).await;
+			return Err(e);
+		}
+	};
+	tokio::fs::rename(&partial, &file).await?;
+
+	match header.kind {
+		BackupKind::Full => info!("The backup of {namespace}/{database} was written to '{file}'"),
+		BackupKind::Incremental {
+			..
+		} => info!("The incremental backup of {namespace}/{database} was written to '{file}'"),
+	}
+	Ok(())
+}
+
+async fn restore<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	RestoreCommandArguments {
+		path,
+		files,
+		sel: LevelSelectionArguments {
+			namespace,
+			database,
+		},
+	}: RestoreCommandArguments,
+) -> Result<()> {
+	// Check that the backups form a single chain before touching the datastore
+	let mut headers: Vec<BackupHeader> = Vec::with_capacity(files.len());
+	for file in &files {
+		let header = BackupHeader::read(&mut BufReader::new(File::open(file).await?)).await?;
+		match headers.last() {
+			None => ensure!(
+				header.kind == BackupKind::Full,
+				"The first backup to restore must be a full backup, but '{file}' is incremental"
+			),
+			Some(previous) => ensure!(
+				previous.precedes(&header),
+				"The backup '{file}' does not continue from the backup before it"
+			),
+		}
+		headers.push(header);
+	}
+
+	// Restore into the database the backups were taken from, unless specified
+	let namespace = namespace.unwrap_or_else(|| headers[0].namespace.clone());
+	let database = database.unwrap_or_else(|| headers[0].database.clone());
+
+	let ds = open(composer, &path).await?;
+	let sess = Session::owner().with_ns(&namespace).with_db(&database);
+	let mut res = Ok(());
+	for (file, header) in files.iter().zip(&headers) {
+		let mut reader = BufReader::new(File::open(file).await?);
+		// Skip over the header which was already checked
+		BackupHeader::read(&mut reader).await?;
+		res = ds.restore(&sess, header, reader).await;
+		if res.is_err() {
+			break;
+		}
+		info!("Restored '{file}' into {namespace}/{database}");
+	}
+	ds.shutdown().await?;
+	res
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 use clap::Args;
-use surrealdb_core::kvs::TransactionBuilderFactory;
+use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
 
 #[derive(Args, Debug)]
 pub struct FixCommandArguments {
@@ -8,14 +9,37 @@ pub struct FixCommandArguments {
 	#[arg(env = "SURREAL_PATH", index = 1)]
 	#[arg(default_value = "memory")]
 	path: String,
+	#[arg(help = "Repair the inconsistencies which were found, instead of only reporting them")]
+	#[arg(long = "repair")]
+	repair: bool,
 }
 
-/// Validate the datastore path for the `fix` subcommand.
+/// Check the datastore at the given path for inconsistent keys, and repair
+/// them when `--repair` is set.
 ///
-/// Only the `TransactionBuilderFactory` bound is required here because this
-/// command does not need to start the HTTP server or build routes.
-pub async fn init<F: TransactionBuilderFactory>(args: FixCommandArguments) -> Result<()> {
-	// All ok
-	F::path_valid(&args.path)?;
-	Err(anyhow::anyhow!("Fix is not implemented"))
+/// The datastore is opened directly, so it must not be in use by a running
+/// server when it is stored on disk.
+pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
+	composer: C,
+	FixCommandArguments {
+		path,
+		repair,
+	}: FixCommandArguments,
+) -> Result<()> {
+	// Check the path is valid
+	C::path_valid(&path)?;
+	let ds = Datastore::builder().build_with_factory_path(&path, composer).await?;
+	ds.check_version().await?;
+	let res = ds.fix(repair).await;
+	ds.shutdown().await?;
+	let report = res?;
+	for issue in &report.issues {
+		warn!("{issue}");
+	}
+	match (report.issues.len(), report.repaired) {
+		(0, _) => info!("No inconsistencies were found"),
+		(n, true) => info!("Repaired {n} inconsistencies"),
+		(n, false) => info!("Found {n} inconsistencies, run with --repair to repair them"),
+	}
+	Ok(())
 }
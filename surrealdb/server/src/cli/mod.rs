@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 pub(crate) mod abstraction;
+mod backup;
 mod config;
 mod export;
 mod fix;
@@ -28,6 +29,7 @@ use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::Result;
+use backup::BackupCommand;
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Config, ConfigCheck, ConfigCheckRequirements};
 use export::ExportCommandArguments;
@@ -165,10 +167,8 @@ struct Cli {
 enum Commands {
 	#[command(about = "Start the database server")]
 	Start(StartCommandArguments),
-	/* Not implemented yet
-	#[command(about = "Backup data to or from an existing database")]
-	Backup(BackupCommandArguments),
-	*/
+	#[command(subcommand, about = "Back up or restore a database in an offline datastore")]
+	Backup(BackupCommand),
 	#[command(about = "Import a SurrealQL script into an existing database")]
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
@@ -194,7 +194,10 @@ enum Commands {
 	IsReady(IsReadyCommandArguments),
 	#[command(about = "Validate SurrealQL query files")]
 	Validate(ValidateCommandArguments),
-	#[command(about = "Fix database storage issues")]
+	#[command(
+		about = "Check database storage for orphaned keys, and optionally repair them",
+		long_about = "Check database storage for orphaned keys, and optionally repair them.\n\nOrphaned entries are detected for standard, unique and spatial indexes, graph edges and record references. The data of count, full-text and vector indexes is only checked for indexes which are no longer defined: use REBUILD INDEX to recreate it."
+	)]
 	Fix(FixCommandArguments),
 	#[command(about = "Run commands in version 2 of the database for backwards compatibility")]
 	V2(V2Commands),
@@ -313,6 +316,7 @@ pub async fn init<
 	// After version warning we can run the respective command
 	let output = match args.command {
 		Commands::Start(args) => start::init::<C>(composer, args, runtime.clone()).await,
+		Commands::Backup(args) => backup::init::<C>(composer, args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
 		Commands::Version(args) => version::init(args).await,
@@ -326,7 +330,7 @@ pub async fn init<
 		Commands::Module(args) => module::init(args).await,
 		Commands::IsReady(args) => isready::init(args).await,
 		Commands::Validate(args) => validate::init(args).await,
-		Commands::Fix(args) => fix::init::<C>(args).await,
+		Commands::Fix(args) => fix::init::<C>(composer, args).await,
 		Commands::V2(args) => v2::init(args).await,
 	};
 	// Flush every provider's batch processor so audit / slow-query
@@ -82,6 +82,27 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(
+		help = "The interval at which changefeed mutations are delivered to sinks",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_SINK_DELIVERY_INTERVAL", long = "sink-delivery-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "1s")]
+	sink_delivery_interval: Duration,
+	#[arg(
+		help = "The interval at which records which have outlived their table TTL are deleted",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_RECORD_EXPIRY_INTERVAL", long = "record-expiry-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "10s")]
+	record_expiry_interval: Duration,
+	#[arg(
+		help = "The interval at which values sealed with a retired encryption key are re-encrypted",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_REENCRYPTION_INTERVAL", long = "reencryption-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	reencryption_interval: Duration,
 	#[arg(env = "SURREAL_RECLAIM_INTERVAL", long = "reclaim-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "60s")]
 	reclaim_interval: Duration,
@@ -166,6 +187,14 @@ pub struct StartCommandArguments {
 	#[arg(value_delimiter = ',', value_parser = super::validator::cors_origin)]
 	allow_origin: Vec<String>,
 	//
+	// PostgreSQL server
+	#[arg(
+		help = "The hostname or IP address to listen for PostgreSQL wire protocol connections on. Clients must connect over TLS, using the --web-crt and --web-key certificate, unless the address is a loopback address",
+		help_heading = "PostgreSQL server"
+	)]
+	#[arg(env = "SURREAL_PG_BIND", long = "pg-bind")]
+	pg_bind: Option<SocketAddr>,
+	//
 	// Database options
 	#[command(flatten)]
 	#[command(next_help_heading = "Database")]
@@ -232,6 +261,9 @@ pub async fn init<
 		index_compaction_interval,
 		index_build_resume_interval,
 		event_processing_interval,
+		sink_delivery_interval,
+		record_expiry_interval,
+		reencryption_interval,
 		reclaim_interval,
 		reclaim_grace,
 		tikv_gc_interval,
@@ -240,6 +272,7 @@ pub async fn init<
 		no_banner,
 		no_identification_headers,
 		allow_origin,
+		pg_bind,
 		..
 	}: StartCommandArguments,
 	runtime: ObservabilityRuntime,
@@ -272,6 +305,9 @@ pub async fn init<
 		.with_index_compaction_interval(index_compaction_interval)
 		.with_index_build_resume_interval(index_build_resume_interval)
 		.with_event_processing_interval(event_processing_interval)
+		.with_sink_delivery_interval(sink_delivery_interval)
+		.with_record_expiry_interval(record_expiry_interval)
+		.with_reencryption_interval(reencryption_interval)
 		.with_reclaim_interval(reclaim_interval)
 		.with_reclaim_grace(reclaim_grace)
 		.with_tikv_gc_interval(tikv_gc_interval)
@@ -289,6 +325,7 @@ pub async fn init<
 		pass,
 		no_identification_headers,
 		allow_origin,
+		pg_bind,
 		engine,
 		crt,
 		key,
@@ -392,6 +429,16 @@ pub async fn init<
 		// node-membership refresh task keeps the heartbeat current.
 		max_heartbeat_age: Some(max_heartbeat_age),
 	};
+	// Start the PostgreSQL wire protocol listener, if enabled
+	if let Some(bind) = config.pg_bind {
+		// TLS is negotiated with the certificate of the web server
+		let tls = match (&config.crt, &config.key) {
+			(Some(crt), Some(key)) => Some(ntw::pg::tls_acceptor(crt, key).await?),
+			_ => None,
+		};
+		ntw::pg::init(bind, tls, Arc::clone(&datastore), Arc::clone(&ready), canceller.clone())
+			.await?;
+	}
 	// Build and run the HTTP server using the provided RouterFactory implementation
 	ntw::init_with_metrics::<C>(
 		&config,
@@ -51,7 +51,7 @@ pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 	}
 	// Validate the scheme
 	match split_endpoint(v).0 {
-		"http" | "https" | "ws" | "wss" | "mem" | "rocksdb" | "surrealkv" | "tikv" => {
+		"http" | "https" | "ws" | "wss" | "mem" | "rocksdb" | "surrealkv" | "redb" | "tikv" => {
 			Ok(v.to_string())
 		}
 		_ => Err(String::from("Provide a valid database connection string")),
@@ -104,6 +104,30 @@ pub static HTTP_MAX_IMPORT_BODY_SIZE: LazyLock<usize> =
 pub static HTTP_MAX_MCP_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_MCP_BODY_SIZE", usize, 4 << 20);
 
+/// The maximum size of a single PostgreSQL wire protocol message accepted by
+/// the `--pg-bind` listener (default: 16 MiB). The length prefix is checked
+/// before the message body is read, so an oversized message closes the
+/// connection without buffering it.
+pub static PG_MAX_MESSAGE_SIZE: LazyLock<usize> =
+	lazy_env_parse!(bytes, "SURREAL_PG_MAX_MESSAGE_SIZE", usize, 16 << 20);
+
+/// The maximum number of concurrent connections to the `--pg-bind` listener
+/// (default: 256). Connections beyond the limit are refused with a
+/// `too_many_connections` error.
+pub static PG_MAX_CONNECTIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PG_MAX_CONNECTIONS", usize, 256);
+
+/// The maximum number of named prepared statements, and of named portals, a
+/// single PostgreSQL connection may hold at once (default: 1024).
+pub static PG_MAX_STATEMENTS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_PG_MAX_STATEMENTS", usize, 1024);
+
+/// The number of seconds a PostgreSQL client has to complete the startup,
+/// TLS handshake and authentication (default: 60). Connections which have
+/// not authenticated by then are closed, releasing their connection slot.
+pub static PG_AUTHENTICATION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_PG_AUTHENTICATION_TIMEOUT", u64, 60);
+
 /// Specifies the frequency with which ping messages are sent to the client
 pub const WEBSOCKET_PING_FREQUENCY: Duration = Duration::from_secs(5);
 
@@ -17,6 +17,7 @@ pub mod mcp;
 pub mod ml;
 pub(crate) mod output;
 mod params;
+pub mod pg;
 mod ready;
 pub mod rpc;
 mod signals;
@@ -0,0 +1,841 @@
+//! A single client connection: the startup, encryption and authentication
+//! phase, then the simple and extended query sub-protocols.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use anyhow::Result;
+use bytes::Bytes;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::iam::signin::signin;
+use surrealdb_core::kvs::Datastore;
+use surrealdb_types::{Error as TypesError, Value, Variables};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, ReadHalf, WriteHalf};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsAcceptor;
+use uuid::Uuid;
+
+use super::LOG;
+use super::protocol::{
+	Backend, FrontendMessage, PgError, StartupRequest, Target, read_message, read_startup, sqlstate,
+};
+use super::query::{MAX_PARAMS, Query};
+use super::result::ResultSet;
+use super::types::{self, Format, Oid, TEXT};
+use crate::cnf::{PG_AUTHENTICATION_TIMEOUT, PG_MAX_MESSAGE_SIZE, PG_MAX_STATEMENTS};
+
+/// The run-time parameters reported to the client after authentication, and
+/// the initial values of the settings `SHOW` reads.
+const SERVER_PARAMETERS: &[(&str, &str)] = &[
+	("server_version", "16.0"),
+	("server_encoding", "UTF8"),
+	("client_encoding", "UTF8"),
+	("DateStyle", "ISO, MDY"),
+	("IntervalStyle", "postgres"),
+	("TimeZone", "UTC"),
+	("integer_datetimes", "on"),
+	("standard_conforming_strings", "on"),
+	("transaction_isolation", "serializable"),
+];
+
+/// A client stream, in the clear or over TLS.
+trait ClientStream: AsyncRead + AsyncWrite + Unpin + Send {}
+
+impl<S: AsyncRead + AsyncWrite + Unpin + Send> ClientStream for S {}
+
+type Reader = BufReader<ReadHalf<Box<dyn ClientStream>>>;
+type Writer = WriteHalf<Box<dyn ClientStream>>;
+
+/// A statement prepared with `Parse`.
+struct Statement {
+	query: Query,
+	/// The declared type of each parameter, `0` where the client left it
+	/// unspecified.
+	param_types: Vec<Oid>,
+}
+
+/// A statement bound to its parameters with `Bind`.
+struct Portal {
+	query: Query,
+	vars: Variables,
+	result_formats: Vec<Format>,
+	state: PortalState,
+}
+
+enum PortalState {
+	/// Not run yet: a portal runs on its first `Describe` or `Execute`.
+	Pending,
+	/// Run, with the rows sent so far by `Execute`.
+	Executed {
+		result: Option<ResultSet>,
+		sent: usize,
+	},
+}
+
+/// The parameters of the startup message which the connection acts on.
+struct StartupParams {
+	user: String,
+	namespace: Option<String>,
+	database: Option<String>,
+	auth_ns: Option<String>,
+	auth_db: Option<String>,
+	/// Other run-time parameters, including `-c name=value` options.
+	settings: Vec<(String, String)>,
+}
+
+impl StartupParams {
+	fn new(params: Vec<(String, String)>) -> Result<Self, PgError> {
+		let mut user = None;
+		let mut target = None;
+		let mut settings = Vec::new();
+		for (name, value) in params {
+			match name.as_str() {
+				"user" => user = Some(value),
+				"database" => target = Some(value),
+				"options" => settings.extend(parse_options(&value)),
+				_ => settings.push((name, value)),
+			}
+		}
+		let Some(user) = user.filter(|user| !user.is_empty()) else {
+			return Err(PgError::fatal(
+				sqlstate::INVALID_AUTHORIZATION,
+				"no PostgreSQL user name specified in startup packet",
+			));
+		};
+		// `database` selects `namespace/database`, or just a namespace
+		let (namespace, database) = match target {
+			Some(target) => match target.split_once('/') {
+				Some((ns, db)) => (Some(ns.to_owned()), Some(db.to_owned())),
+				None => (Some(target), None),
+			},
+			None => (None, None),
+		};
+		let mut take = |name: &str| {
+			let i = settings.iter().position(|(n, _)| n == name)?;
+			Some(settings.remove(i).1)
+		};
+		let auth_ns = take("surreal.auth_ns");
+		let auth_db = take("surreal.auth_db");
+		Ok(Self {
+			user,
+			namespace: namespace.filter(|ns| !ns.is_empty()),
+			database: database.filter(|db| !db.is_empty()),
+			auth_ns,
+			auth_db,
+			settings,
+		})
+	}
+}
+
+/// Parse the command-line style `options` startup parameter, keeping the
+/// `-c name=value` and `--name=value` settings.
+fn parse_options(options: &str) -> Vec<(String, String)> {
+	let mut settings = Vec::new();
+	let mut words = options.split_whitespace();
+	while let Some(word) = words.next() {
+		let setting = if word == "-c" {
+			words.next()
+		} else {
+			word.strip_prefix("-c").or_else(|| word.strip_prefix("--"))
+		};
+		if let Some((name, value)) = setting.and_then(|s| s.split_once('=')) {
+			settings.push((name.to_owned(), value.to_owned()));
+		}
+	}
+	settings
+}
+
+/// The SQLSTATE reported for a statement error.
+fn error_code(err: &TypesError) -> &'static str {
+	match err.kind_str() {
+		"Validation" => sqlstate::INVALID_PARAMETER_VALUE,
+		"NotAllowed" => sqlstate::INSUFFICIENT_PRIVILEGE,
+		"NotFound" => sqlstate::UNDEFINED_OBJECT,
+		"AlreadyExists" => sqlstate::DUPLICATE_OBJECT,
+		"Thrown" => sqlstate::RAISE_EXCEPTION,
+		_ => sqlstate::INTERNAL_ERROR,
+	}
+}
+
+fn statement_error(err: &TypesError) -> PgError {
+	PgError::new(error_code(err), err.message())
+}
+
+pub(super) struct Connection {
+	ds: Arc<Datastore>,
+	ready: Arc<AtomicBool>,
+	/// Whether the listener is bound to a loopback address, where passwords
+	/// may be sent without TLS.
+	loopback: bool,
+	session: Session,
+	/// The run-time parameters, changed with `SET` and read with `SHOW`.
+	settings: BTreeMap<String, String>,
+	statements: HashMap<String, Statement>,
+	portals: HashMap<String, Portal>,
+	/// Set when a message of the extended query protocol failed: the
+	/// following messages are discarded up to the next `Sync`.
+	failed: bool,
+	out: Backend,
+}
+
+impl Connection {
+	pub fn new(
+		ds: Arc<Datastore>,
+		ready: Arc<AtomicBool>,
+		peer: SocketAddr,
+		loopback: bool,
+	) -> Self {
+		Self {
+			ds,
+			ready,
+			loopback,
+			session: Session {
+				ip: Some(peer.ip().to_string()),
+				id: Some(Uuid::new_v4()),
+				..Session::default()
+			},
+			settings: BTreeMap::new(),
+			statements: HashMap::new(),
+			portals: HashMap::new(),
+			failed: false,
+			out: Backend::default(),
+		}
+	}
+
+	/// Serve the connection until the client terminates it. Fatal errors are
+	/// reported to the client before the connection is closed; only I/O
+	/// errors are returned.
+	pub async fn run(mut self, stream: TcpStream, tls: Option<TlsAcceptor>) -> Result<()> {
+		// Startup, the TLS handshake and authentication share one timeout, so
+		// that idle clients can not hold on to a connection slot
+		let timeout = Duration::from_secs(*PG_AUTHENTICATION_TIMEOUT);
+		let (rd, wr) = match tokio::time::timeout(timeout, self.startup(stream, tls)).await {
+			Ok(res) => match res? {
+				Some(stream) => stream,
+				None => return Ok(()),
+			},
+			Err(_) => {
+				debug!(target: LOG, "Closing PostgreSQL connection: authentication timed out");
+				return Ok(());
+			}
+		};
+		self.serve(rd, wr).await
+	}
+
+	/// Negotiate encryption and authenticate the client, returning the
+	/// stream to serve queries on, or `None` when the connection ended.
+	async fn startup(
+		&mut self,
+		mut stream: TcpStream,
+		tls: Option<TlsAcceptor>,
+	) -> Result<Option<(Reader, Writer)>> {
+		let max_size = *PG_MAX_MESSAGE_SIZE;
+		// Encryption is negotiated on the raw stream, before any bytes are
+		// buffered, so no plaintext can be read as if encrypted
+		let params = loop {
+			let body = read_startup(&mut stream, max_size).await?;
+			match StartupRequest::decode(&body) {
+				Ok(StartupRequest::Ssl) => match &tls {
+					Some(tls) => {
+						stream.write_all(b"S").await?;
+						let stream = tls.accept(stream).await?;
+						return self.authenticate(Box::new(stream), true, None).await;
+					}
+					None => stream.write_all(b"N").await?,
+				},
+				Ok(StartupRequest::GssEnc) => stream.write_all(b"N").await?,
+				// Query cancellation is not supported
+				Ok(StartupRequest::Cancel) => return Ok(None),
+				Ok(StartupRequest::Startup(params)) => break params,
+				Err(err) => return self.fail(&mut stream, err).await.map(|_| None),
+			}
+		};
+		self.authenticate(Box::new(stream), false, Some(params)).await
+	}
+
+	/// Authenticate the client once encryption is settled. The startup
+	/// packet is read from the stream when it was not read before
+	/// negotiating TLS.
+	async fn authenticate(
+		&mut self,
+		stream: Box<dyn ClientStream>,
+		encrypted: bool,
+		params: Option<Vec<(String, String)>>,
+	) -> Result<Option<(Reader, Writer)>> {
+		let (rd, mut wr) = tokio::io::split(stream);
+		let mut rd = BufReader::new(rd);
+		let max_size = *PG_MAX_MESSAGE_SIZE;
+		let params = match params {
+			Some(params) => params,
+			None => match StartupRequest::decode(&read_startup(&mut rd, max_size).await?) {
+				Ok(StartupRequest::Startup(params)) => params,
+				Ok(_) => {
+					let err = PgError::fatal(
+						sqlstate::PROTOCOL_VIOLATION,
+						"unexpected request after the TLS handshake",
+					);
+					return self.fail(&mut wr, err).await.map(|_| None);
+				}
+				Err(err) => return self.fail(&mut wr, err).await.map(|_| None),
+			},
+		};
+		let params = match StartupParams::new(params) {
+			Ok(params) => params,
+			Err(err) => return self.fail(&mut wr, err).await.map(|_| None),
+		};
+		// Gate connections until the deferred startup work has completed
+		if !self.ready.load(Ordering::SeqCst) {
+			let err =
+				PgError::fatal(sqlstate::CANNOT_CONNECT_NOW, "the database system is starting up");
+			return self.fail(&mut wr, err).await.map(|_| None);
+		}
+		// The password is sent in the clear, so it must not cross the network
+		// unencrypted
+		if !encrypted && !self.loopback {
+			let err = PgError::fatal(
+				sqlstate::INVALID_AUTHORIZATION,
+				"password authentication requires an SSL connection",
+			);
+			return self.fail(&mut wr, err).await.map(|_| None);
+		}
+		self.out.authentication_cleartext_password();
+		self.flush(&mut wr).await?;
+		let password = match read_message(&mut rd, max_size).await? {
+			Some((tag, body)) => match FrontendMessage::decode(tag, body) {
+				Ok(FrontendMessage::Password(password)) => password,
+				Ok(_) => {
+					let err = PgError::fatal(
+						sqlstate::PROTOCOL_VIOLATION,
+						"expected a password response",
+					);
+					return self.fail(&mut wr, err).await.map(|_| None);
+				}
+				Err(err) => return self.fail(&mut wr, err).await.map(|_| None),
+			},
+			None => return Ok(None),
+		};
+		if let Err(err) = self.signin(&params, password).await {
+			return self.fail(&mut wr, err).await.map(|_| None);
+		}
+		self.greet(params);
+		self.flush(&mut wr).await?;
+		Ok(Some((rd, wr)))
+	}
+
+	/// Serve the queries of an authenticated client.
+	async fn serve(&mut self, mut rd: Reader, mut wr: Writer) -> Result<()> {
+		let max_size = *PG_MAX_MESSAGE_SIZE;
+		loop {
+			let Some((tag, body)) = read_message(&mut rd, max_size).await? else {
+				return Ok(());
+			};
+			let msg = match FrontendMessage::decode(tag, body) {
+				Ok(msg) => msg,
+				Err(err) => return self.fail(&mut wr, err).await,
+			};
+			let res = match msg {
+				FrontendMessage::Query(text) => {
+					self.simple_query(&text).await;
+					self.out.ready_for_query();
+					self.flush(&mut wr).await?;
+					continue;
+				}
+				FrontendMessage::Sync => {
+					// The end of the implicit transaction closes its portals
+					self.failed = false;
+					self.portals.clear();
+					self.out.ready_for_query();
+					self.flush(&mut wr).await?;
+					continue;
+				}
+				FrontendMessage::Flush => {
+					self.flush(&mut wr).await?;
+					continue;
+				}
+				FrontendMessage::Terminate => return Ok(()),
+				_ if self.failed => continue,
+				FrontendMessage::Parse {
+					name,
+					query,
+					param_types,
+				} => self.parse(name, &query, param_types),
+				FrontendMessage::Bind {
+					portal,
+					statement,
+					param_formats,
+					params,
+					result_formats,
+				} => self.bind(portal, &statement, &param_formats, &params, result_formats),
+				FrontendMessage::Describe {
+					target: Target::Statement,
+					name,
+				} => self.describe_statement(&name),
+				FrontendMessage::Describe {
+					target: Target::Portal,
+					name,
+				} => self.describe_portal(&name).await,
+				FrontendMessage::Execute {
+					portal,
+					max_rows,
+				} => self.execute(&portal, max_rows).await,
+				FrontendMessage::Close {
+					target,
+					name,
+				} => {
+					// Closing a statement or portal which does not exist is not an error
+					match target {
+						Target::Statement => {
+							self.statements.remove(&name);
+						}
+						Target::Portal => {
+							self.portals.remove(&name);
+						}
+					}
+					self.out.close_complete();
+					Ok(())
+				}
+				FrontendMessage::Password(_) => Err(PgError::fatal(
+					sqlstate::PROTOCOL_VIOLATION,
+					"unexpected password response",
+				)),
+			};
+			if let Err(err) = res {
+				if err.severity == "FATAL" {
+					return self.fail(&mut wr, err).await;
+				}
+				self.out.error_response(&err);
+				self.failed = true;
+			}
+		}
+	}
+
+	async fn flush<W: AsyncWrite + Unpin>(&mut self, wr: &mut W) -> Result<()> {
+		wr.write_all(&self.out.take()).await?;
+		wr.flush().await?;
+		Ok(())
+	}
+
+	/// Report a fatal error and end the connection.
+	async fn fail<W: AsyncWrite + Unpin>(&mut self, wr: &mut W, err: PgError) -> Result<()> {
+		debug!(target: LOG, "Closing PostgreSQL connection: {err}");
+		self.out.error_response(&err);
+		self.flush(wr).await
+	}
+
+	/// Verify the credentials with `iam::signin`: root credentials by default,
+	/// or namespace or database credentials when the `surreal.auth_ns` and
+	/// `surreal.auth_db` options name the level to sign in at.
+	async fn signin(&mut self, params: &StartupParams, password: String) -> Result<(), PgError> {
+		let mut vars = Variables::new();
+		vars.insert("user", params.user.clone());
+		vars.insert("pass", password);
+		if let Some(ns) = &params.auth_ns {
+			vars.insert("NS", ns.clone());
+		}
+		if let Some(db) = &params.auth_db {
+			vars.insert("DB", db.clone());
+		}
+		if let Err(err) = signin(&self.ds, &mut self.session, vars).await {
+			debug!(target: LOG, "PostgreSQL authentication failed for user '{}': {err}", params.user);
+			return Err(PgError::fatal(
+				sqlstate::INVALID_PASSWORD,
+				format!("password authentication failed for user \"{}\"", params.user),
+			));
+		}
+		if params.namespace.is_some() {
+			self.session.ns = params.namespace.clone();
+			self.session.db = params.database.clone();
+		}
+		if !self.ds.allows_query_by_subject(self.session.au.as_ref()) {
+			return Err(PgError::fatal(
+				sqlstate::INSUFFICIENT_PRIVILEGE,
+				format!("user \"{}\" is not allowed to run queries", params.user),
+			));
+		}
+		Ok(())
+	}
+
+	/// Complete the startup phase once authenticated.
+	fn greet(&mut self, params: StartupParams) {
+		self.out.authentication_ok();
+		for (name, value) in SERVER_PARAMETERS {
+			self.out.parameter_status(name, value);
+			self.settings.insert(name.to_ascii_lowercase(), (*value).to_owned());
+		}
+		for (name, value) in params.settings {
+			if name == "application_name" {
+				self.out.parameter_status(&name, &value);
+			}
+			self.settings.insert(name.to_ascii_lowercase(), value);
+		}
+		// Query cancellation is not supported, so the key is never checked
+		self.out.backend_key_data(rand::random(), rand::random());
+		self.out.ready_for_query();
+	}
+
+	/// Run a `Query` message. Each statement's result is sent in turn,
+	/// including those following a failed statement, which SurrealQL still
+	/// runs.
+	async fn simple_query(&mut self, text: &str) {
+		self.portals.clear();
+		let query = Query::classify(text);
+		let Query::Surreal(text) = &query else {
+			match self.command(&query) {
+				Ok(result) => self.send_result(&query, result.as_ref(), &[]),
+				Err(err) => self.out.error_response(&err),
+			}
+			return;
+		};
+		match self.ds.execute(text, &self.session, None).await {
+			Ok(results) => {
+				for res in results {
+					match res.result {
+						Ok(value) => {
+							let result = ResultSet::from_value(value);
+							self.send_result(&query, result.as_ref(), &[]);
+						}
+						Err(err) => self.out.error_response(&statement_error(&err)),
+					}
+				}
+			}
+			Err(err) => {
+				self.out.error_response(&PgError::new(sqlstate::SYNTAX_ERROR, err.message()))
+			}
+		}
+	}
+
+	/// Run a `SET`, `SHOW` or empty query against the connection itself.
+	fn command(&mut self, query: &Query) -> Result<Option<ResultSet>, PgError> {
+		match query {
+			Query::Set {
+				name,
+				value,
+			} => {
+				self.settings.insert(name.clone(), value.clone());
+				Ok(None)
+			}
+			Query::Show(name) => match self.settings.get(name) {
+				Some(value) => Ok(Some(ResultSet::text(name, value))),
+				None => Err(PgError::new(
+					sqlstate::UNDEFINED_OBJECT,
+					format!("unrecognized configuration parameter \"{name}\""),
+				)),
+			},
+			Query::Empty | Query::Surreal(_) => Ok(None),
+		}
+	}
+
+	/// Send the whole of a result in reply to a `Query` message.
+	fn send_result(&mut self, query: &Query, result: Option<&ResultSet>, formats: &[Format]) {
+		if *query == Query::Empty {
+			self.out.empty_query_response();
+			return;
+		}
+		if let Some(result) = result {
+			match result.fields(formats) {
+				Ok(fields) => self.out.row_description(&fields),
+				Err(err) => return self.out.error_response(&err),
+			}
+		}
+		if let Err(err) = self.send_rows(query, result, 0, 0, formats) {
+			self.out.error_response(&err);
+		}
+	}
+
+	/// Send up to `max_rows` rows (all of them when `0`) from `from`
+	/// onwards, followed by `CommandComplete`, or by `PortalSuspended` when
+	/// rows remain. Returns the number of rows sent.
+	fn send_rows(
+		&mut self,
+		query: &Query,
+		result: Option<&ResultSet>,
+		from: usize,
+		max_rows: usize,
+		formats: &[Format],
+	) -> Result<usize, PgError> {
+		let Some(result) = result else {
+			self.out.command_complete(&command_tag(query, 0));
+			return Ok(0);
+		};
+		let rows = &result.rows[from.min(result.rows.len())..];
+		let count = match max_rows {
+			0 => rows.len(),
+			max => max.min(rows.len()),
+		};
+		for row in &rows[..count] {
+			let cells = result.encode_row(row, formats)?;
+			self.out.data_row(&cells);
+		}
+		if count < rows.len() {
+			self.out.portal_suspended();
+		} else {
+			self.out.command_complete(&command_tag(query, count));
+		}
+		Ok(count)
+	}
+
+	fn parse(&mut self, name: String, text: &str, param_types: Vec<Oid>) -> Result<(), PgError> {
+		if !name.is_empty() && self.statements.contains_key(&name) {
+			return Err(PgError::new(
+				sqlstate::DUPLICATE_PREPARED_STATEMENT,
+				format!("prepared statement \"{name}\" already exists"),
+			));
+		}
+		// The unnamed statement is replaced, so it is always allowed
+		if !name.is_empty() && self.statements.len() >= *PG_MAX_STATEMENTS {
+			return Err(PgError::new(
+				sqlstate::PROGRAM_LIMIT_EXCEEDED,
+				format!("too many prepared statements, the limit is {}", *PG_MAX_STATEMENTS),
+			));
+		}
+		let query = Query::classify(text);
+		if let Query::Surreal(text) = &query {
+			let ast = surrealdb_core::syn::parse(text)
+				.map_err(|err| PgError::new(sqlstate::SYNTAX_ERROR, err.to_string()))?;
+			if ast.num_statements() > 1 {
+				return Err(PgError::new(
+					sqlstate::SYNTAX_ERROR,
+					"cannot insert multiple commands into a prepared statement",
+				));
+			}
+		}
+		// Checked before any parameter is allocated
+		let count = query.param_count().max(param_types.len());
+		if count > MAX_PARAMS {
+			return Err(PgError::new(
+				sqlstate::PROGRAM_LIMIT_EXCEEDED,
+				format!("too many parameters, the limit is {MAX_PARAMS}"),
+			));
+		}
+		// Parameters the client left undeclared are sent as text
+		let mut param_types = param_types;
+		param_types.resize(count, 0);
+		for oid in &mut param_types {
+			if *oid == 0 {
+				*oid = TEXT;
+			}
+		}
+		self.statements.insert(
+			name,
+			Statement {
+				query,
+				param_types,
+			},
+		);
+		self.out.parse_complete();
+		Ok(())
+	}
+
+	fn bind(
+		&mut self,
+		portal: String,
+		statement: &str,
+		param_formats: &[Format],
+		params: &[Option<Bytes>],
+		result_formats: Vec<Format>,
+	) -> Result<(), PgError> {
+		if !portal.is_empty() && self.portals.contains_key(&portal) {
+			return Err(PgError::new(
+				sqlstate::DUPLICATE_CURSOR,
+				format!("portal \"{portal}\" already exists"),
+			));
+		}
+		// The unnamed portal is replaced, so it is always allowed
+		if !portal.is_empty() && self.portals.len() >= *PG_MAX_STATEMENTS {
+			return Err(PgError::new(
+				sqlstate::PROGRAM_LIMIT_EXCEEDED,
+				format!("too many portals, the limit is {}", *PG_MAX_STATEMENTS),
+			));
+		}
+		let stmt = self.statement(statement)?;
+		if params.len() != stmt.param_types.len() {
+			return Err(PgError::new(
+				sqlstate::PROTOCOL_VIOLATION,
+				format!(
+					"bind message supplies {} parameters, but prepared statement \"{statement}\" requires {}",
+					params.len(),
+					stmt.param_types.len()
+				),
+			));
+		}
+		// Positional parameters are bound as `$1`, `$2`, ...
+		let mut vars = Variables::new();
+		let formats = Format::expand(param_formats, params.len())?;
+		for (i, ((param, oid), format)) in
+			params.iter().zip(&stmt.param_types).zip(formats).enumerate()
+		{
+			let value = match param {
+				Some(bytes) => types::decode(bytes, *oid, format)?,
+				None => Value::Null,
+			};
+			vars.insert((i + 1).to_string(), value);
+		}
+		let query = stmt.query.clone();
+		self.portals.insert(
+			portal,
+			Portal {
+				query,
+				vars,
+				result_formats,
+				state: PortalState::Pending,
+			},
+		);
+		self.out.bind_complete();
+		Ok(())
+	}
+
+	/// Describe a prepared statement. The columns of a SurrealQL statement
+	/// are only known once it has run, so only `SHOW` reports a row
+	/// description here; clients describe the portal to learn the others.
+	fn describe_statement(&mut self, name: &str) -> Result<(), PgError> {
+		let stmt = self.statement(name)?;
+		let show = match &stmt.query {
+			Query::Show(name) => Some(ResultSet::text(name, "")),
+			_ => None,
+		};
+		let param_types = stmt.param_types.clone();
+		self.out.parameter_description(&param_types);
+		match show {
+			Some(result) => self.out.row_description(&result.fields(&[])?),
+			None => self.out.no_data(),
+		}
+		Ok(())
+	}
+
+	/// Describe a portal, running it if it has not run yet.
+	async fn describe_portal(&mut self, name: &str) -> Result<(), PgError> {
+		self.run_portal(name).await?;
+		let portal = self.portal(name)?;
+		let fields = match &portal.state {
+			PortalState::Executed {
+				result: Some(result),
+				..
+			} => Some(result.fields(&portal.result_formats)?),
+			_ => None,
+		};
+		match fields {
+			Some(fields) => self.out.row_description(&fields),
+			None => self.out.no_data(),
+		}
+		Ok(())
+	}
+
+	async fn execute(&mut self, name: &str, max_rows: usize) -> Result<(), PgError> {
+		self.run_portal(name).await?;
+		let mut portal = self.portals.remove(name).ok_or_else(|| missing_portal(name))?;
+		let res = match &mut portal.state {
+			_ if portal.query == Query::Empty => {
+				self.out.empty_query_response();
+				Ok(())
+			}
+			PortalState::Executed {
+				result,
+				sent,
+			} => self
+				.send_rows(&portal.query, result.as_ref(), *sent, max_rows, &portal.result_formats)
+				.map(|count| *sent += count),
+			PortalState::Pending => Ok(()),
+		};
+		self.portals.insert(name.to_owned(), portal);
+		res
+	}
+
+	/// Run a portal's query on its first `Describe` or `Execute`, keeping the
+	/// result for the `Execute` messages which fetch its rows.
+	async fn run_portal(&mut self, name: &str) -> Result<(), PgError> {
+		let portal = self.portal(name)?;
+		if !matches!(portal.state, PortalState::Pending) {
+			return Ok(());
+		}
+		let query = portal.query.clone();
+		let result = match &query {
+			Query::Surreal(text) => {
+				let vars = portal.vars.clone();
+				let mut results = self
+					.ds
+					.execute(text, &self.session, Some(vars))
+					.await
+					.map_err(|err| PgError::new(sqlstate::SYNTAX_ERROR, err.message()))?;
+				match results.pop().map(|res| res.result) {
+					Some(Ok(value)) => ResultSet::from_value(value),
+					Some(Err(err)) => return Err(statement_error(&err)),
+					None => None,
+				}
+			}
+			query => self.command(query)?,
+		};
+		if let Some(portal) = self.portals.get_mut(name) {
+			portal.state = PortalState::Executed {
+				result,
+				sent: 0,
+			};
+		}
+		Ok(())
+	}
+
+	fn statement(&self, name: &str) -> Result<&Statement, PgError> {
+		self.statements.get(name).ok_or_else(|| {
+			PgError::new(
+				sqlstate::INVALID_SQL_STATEMENT_NAME,
+				format!("prepared statement \"{name}\" does not exist"),
+			)
+		})
+	}
+
+	fn portal(&self, name: &str) -> Result<&Portal, PgError> {
+		self.portals.get(name).ok_or_else(|| missing_portal(name))
+	}
+}
+
+fn missing_portal(name: &str) -> PgError {
+	PgError::new(sqlstate::INVALID_CURSOR_NAME, format!("portal \"{name}\" does not exist"))
+}
+
+/// The `CommandComplete` tag for a statement which sent `rows` rows.
+fn command_tag(query: &Query, rows: usize) -> String {
+	match query {
+		Query::Set {
+			..
+		} => "SET".to_owned(),
+		Query::Show(_) => "SHOW".to_owned(),
+		_ => format!("SELECT {rows}"),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn params(params: &[(&str, &str)]) -> Vec<(String, String)> {
+		params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+	}
+
+	#[test]
+	fn startup_parameters_select_the_namespace_and_database() {
+		let p = StartupParams::new(params(&[
+			("user", "root"),
+			("database", "shop/orders"),
+			("options", "-c surreal.auth_ns=shop --search_path=public"),
+			("application_name", "psql"),
+		]))
+		.unwrap();
+		assert_eq!(p.user, "root");
+		assert_eq!(p.namespace.as_deref(), Some("shop"));
+		assert_eq!(p.database.as_deref(), Some("orders"));
+		assert_eq!(p.auth_ns.as_deref(), Some("shop"));
+		assert_eq!(p.auth_db, None);
+		assert_eq!(p.settings, params(&[("search_path", "public"), ("application_name", "psql")]));
+		// A bare database name selects only the namespace
+		let p = StartupParams::new(params(&[("user", "root"), ("database", "shop")])).unwrap();
+		assert_eq!((p.namespace.as_deref(), p.database), (Some("shop"), None));
+	}
+
+	#[test]
+	fn startup_requires_a_user() {
+		let err = StartupParams::new(params(&[("database", "shop/orders")])).err().unwrap();
+		assert_eq!((err.severity, err.code), ("FATAL", sqlstate::INVALID_AUTHORIZATION));
+	}
+}
@@ -0,0 +1,130 @@
+//! A PostgreSQL wire protocol (v3) listener, enabled with `--pg-bind`.
+//!
+//! Clients connect as they would to PostgreSQL and send SurrealQL over the
+//! simple and extended query protocols, so that existing drivers, BI tools
+//! and `psql` can query a SurrealDB instance:
+//!
+//! - Connections are authenticated with a cleartext password, checked with
+//!   `iam::signin` against the stored (hashed) credentials, which rules out
+//!   SCRAM. The password is only accepted over TLS, negotiated with the
+//!   `--web-crt` and `--web-key` certificate, unless the listener is bound to
+//!   a loopback address. Root users sign in by default; the `surreal.auth_ns` and
+//!   `surreal.auth_db` options (`options='-c surreal.auth_ns=shop'`) sign in
+//!   a namespace or database user instead.
+//! - The `database` parameter selects `namespace/database`, or just a
+//!   namespace.
+//! - `SET` and `SHOW` read and write the connection's run-time parameters,
+//!   as drivers expect while setting up a connection.
+//! - Results are shaped into rows and columns (see [`result`]), with column
+//!   types inferred from the values' `surrealdb_types::Kind`.
+//! - Positional parameters (`$1`, `$2`, ...) bound with `Bind` are passed to
+//!   the query as variables of the same name.
+//!
+//! The number of connections, and of prepared statements and portals per
+//! connection, are bounded (see [`PG_MAX_CONNECTIONS`] and
+//! [`PG_MAX_STATEMENTS`]), and clients which do not authenticate within
+//! [`PG_AUTHENTICATION_TIMEOUT`] are disconnected. Query cancellation is not
+//! supported, and each query runs in a transaction of its own, as it does
+//! over HTTP.
+//!
+//! [`PG_MAX_CONNECTIONS`]: crate::cnf::PG_MAX_CONNECTIONS
+//! [`PG_MAX_STATEMENTS`]: crate::cnf::PG_MAX_STATEMENTS
+//! [`PG_AUTHENTICATION_TIMEOUT`]: crate::cnf::PG_AUTHENTICATION_TIMEOUT
+
+mod conn;
+mod protocol;
+mod query;
+mod result;
+mod types;
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Duration;
+
+use anyhow::Result;
+use axum_server::tls_rustls::RustlsConfig;
+use rustls::ServerConfig;
+use surrealdb_core::kvs::Datastore;
+use tokio::net::TcpListener;
+use tokio::sync::Semaphore;
+use tokio::task::JoinHandle;
+use tokio_rustls::TlsAcceptor;
+use tokio_util::sync::CancellationToken;
+
+use self::conn::Connection;
+use self::protocol::{Backend, PgError, sqlstate};
+use crate::cnf::PG_MAX_CONNECTIONS;
+
+const LOG: &str = "surrealdb::net::pg";
+
+/// Load the certificate and private key TLS connections are negotiated with.
+pub async fn tls_acceptor(crt: &Path, key: &Path) -> Result<TlsAcceptor> {
+	let config = RustlsConfig::from_pem_file(crt, key).await?;
+	let mut config = ServerConfig::clone(&config.get_inner());
+	// The protocol clients name when negotiating TLS directly
+	config.alpn_protocols = vec![b"postgresql".to_vec()];
+	Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Bind the listener and serve connections in the background until the
+/// server shuts down.
+pub async fn init(
+	bind: SocketAddr,
+	tls: Option<TlsAcceptor>,
+	ds: Arc<Datastore>,
+	ready: Arc<AtomicBool>,
+	ct: CancellationToken,
+) -> Result<JoinHandle<()>> {
+	let listener = TcpListener::bind(bind).await?;
+	info!(target: LOG, "Started PostgreSQL server on {bind}");
+	// Passwords may only be sent in the clear when they can not leave the host
+	let loopback = bind.ip().is_loopback();
+	let limit = Arc::new(Semaphore::new(*PG_MAX_CONNECTIONS));
+	Ok(tokio::spawn(async move {
+		loop {
+			let (stream, peer) = tokio::select! {
+				_ = ct.cancelled() => break,
+				res = listener.accept() => match res {
+					Ok(conn) => conn,
+					Err(err) => {
+						// Back off briefly, e.g. when out of file descriptors
+						warn!(target: LOG, "Failed to accept PostgreSQL connection: {err}");
+						tokio::time::sleep(Duration::from_millis(100)).await;
+						continue;
+					}
+				},
+			};
+			let Ok(permit) = Arc::clone(&limit).try_acquire_owned() else {
+				warn!(target: LOG, "Refused PostgreSQL connection from {peer}: too many connections");
+				// The client reads the error in reply to its startup packet
+				let mut out = Backend::default();
+				out.error_response(&PgError::fatal(
+					sqlstate::TOO_MANY_CONNECTIONS,
+					"sorry, too many clients already",
+				));
+				if let Err(err) = stream.try_write(&out.take()) {
+					debug!(target: LOG, "Failed to refuse PostgreSQL connection: {err}");
+				}
+				continue;
+			};
+			if let Err(err) = stream.set_nodelay(true) {
+				debug!(target: LOG, "Failed to set TCP_NODELAY: {err}");
+			}
+			let conn = Connection::new(Arc::clone(&ds), Arc::clone(&ready), peer, loopback);
+			let tls = tls.clone();
+			let ct = ct.clone();
+			tokio::spawn(async move {
+				// The connection counts towards the limit until it ends
+				let _permit = permit;
+				tokio::select! {
+					_ = ct.cancelled() => {}
+					res = conn.run(stream, tls) => if let Err(err) = res {
+						debug!(target: LOG, "PostgreSQL connection from {peer} failed: {err}");
+					},
+				}
+			});
+		}
+	}))
+}
@@ -0,0 +1,624 @@
+//! Message framing for the PostgreSQL frontend/backend protocol, version 3.0.
+//!
+//! Frontend messages are read off the socket as `(tag, body)` frames and
+//! decoded into [`FrontendMessage`]; backend messages are appended to an
+//! output buffer by [`Backend`], which the connection flushes at the points
+//! the protocol requires (after `Sync`, `Flush` and a simple `Query`).
+
+use bytes::{BufMut, Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::types::{Format, Oid, type_len};
+
+/// The protocol version number sent by 3.0 clients in the startup packet.
+const PROTOCOL_VERSION: i32 = 196608;
+/// The "version" of an `SSLRequest` packet.
+const SSL_REQUEST: i32 = 80877103;
+/// The "version" of a `GSSENCRequest` packet.
+const GSSENC_REQUEST: i32 = 80877104;
+/// The "version" of a `CancelRequest` packet.
+const CANCEL_REQUEST: i32 = 80877102;
+
+/// The SQLSTATE codes the listener reports.
+pub(super) mod sqlstate {
+	pub const PROTOCOL_VIOLATION: &str = "08P01";
+	pub const FEATURE_NOT_SUPPORTED: &str = "0A000";
+	pub const INVALID_AUTHORIZATION: &str = "28000";
+	pub const INVALID_PASSWORD: &str = "28P01";
+	pub const INSUFFICIENT_PRIVILEGE: &str = "42501";
+	pub const SYNTAX_ERROR: &str = "42601";
+	pub const INVALID_TEXT_REPRESENTATION: &str = "22P02";
+	pub const CHARACTER_NOT_IN_REPERTOIRE: &str = "22021";
+	pub const DUPLICATE_PREPARED_STATEMENT: &str = "42P05";
+	pub const DUPLICATE_CURSOR: &str = "42P03";
+	pub const INVALID_SQL_STATEMENT_NAME: &str = "26000";
+	pub const INVALID_CURSOR_NAME: &str = "34000";
+	pub const UNDEFINED_OBJECT: &str = "42704";
+	pub const DUPLICATE_OBJECT: &str = "42710";
+	pub const INVALID_PARAMETER_VALUE: &str = "22023";
+	pub const RAISE_EXCEPTION: &str = "P0001";
+	pub const CANNOT_CONNECT_NOW: &str = "57P03";
+	pub const TOO_MANY_CONNECTIONS: &str = "53300";
+	pub const PROGRAM_LIMIT_EXCEEDED: &str = "54000";
+	pub const INTERNAL_ERROR: &str = "XX000";
+}
+
+/// An error reported to the client as an `ErrorResponse`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct PgError {
+	/// `ERROR` for errors which end the current query, `FATAL` for errors which
+	/// end the connection.
+	pub severity: &'static str,
+	/// The SQLSTATE code, see [`sqlstate`].
+	pub code: &'static str,
+	pub message: String,
+}
+
+impl PgError {
+	pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+		Self {
+			severity: "ERROR",
+			code,
+			message: message.into(),
+		}
+	}
+
+	pub fn fatal(code: &'static str, message: impl Into<String>) -> Self {
+		Self {
+			severity: "FATAL",
+			code,
+			message: message.into(),
+		}
+	}
+
+	fn protocol(message: impl Into<String>) -> Self {
+		Self::fatal(sqlstate::PROTOCOL_VIOLATION, message)
+	}
+}
+
+impl std::fmt::Display for PgError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} ({}): {}", self.severity, self.code, self.message)
+	}
+}
+
+impl std::error::Error for PgError {}
+
+/// The first packet of a connection, which carries no message tag.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum StartupRequest {
+	/// The client asks to upgrade to TLS.
+	Ssl,
+	/// The client asks to upgrade to GSSAPI encryption.
+	GssEnc,
+	/// The client asks to cancel a query running on another connection.
+	Cancel,
+	/// A startup message with its `name`/`value` parameters.
+	Startup(Vec<(String, String)>),
+}
+
+impl StartupRequest {
+	pub fn decode(body: &[u8]) -> Result<Self, PgError> {
+		let mut body = Reader::new(body);
+		match body.i32()? {
+			SSL_REQUEST => Ok(Self::Ssl),
+			GSSENC_REQUEST => Ok(Self::GssEnc),
+			CANCEL_REQUEST => Ok(Self::Cancel),
+			PROTOCOL_VERSION => {
+				let mut params = Vec::new();
+				loop {
+					let name = body.cstr()?;
+					if name.is_empty() {
+						break;
+					}
+					params.push((name, body.cstr()?));
+				}
+				Ok(Self::Startup(params))
+			}
+			version => Err(PgError::fatal(
+				sqlstate::FEATURE_NOT_SUPPORTED,
+				format!(
+					"unsupported frontend protocol {}.{}: server supports 3.0",
+					version >> 16,
+					version & 0xffff
+				),
+			)),
+		}
+	}
+}
+
+/// Whether a `Describe` or `Close` refers to a prepared statement or a portal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Target {
+	Statement,
+	Portal,
+}
+
+/// A message sent by the client after the startup phase.
+#[derive(Debug, PartialEq)]
+pub(super) enum FrontendMessage {
+	Query(String),
+	Parse {
+		name: String,
+		query: String,
+		param_types: Vec<Oid>,
+	},
+	Bind {
+		portal: String,
+		statement: String,
+		param_formats: Vec<Format>,
+		params: Vec<Option<Bytes>>,
+		result_formats: Vec<Format>,
+	},
+	Describe {
+		target: Target,
+		name: String,
+	},
+	Execute {
+		portal: String,
+		max_rows: usize,
+	},
+	Close {
+		target: Target,
+		name: String,
+	},
+	Password(String),
+	Sync,
+	Flush,
+	Terminate,
+}
+
+impl FrontendMessage {
+	pub fn decode(tag: u8, body: Bytes) -> Result<Self, PgError> {
+		let mut r = Reader::new(&body);
+		let msg = match tag {
+			b'Q' => Self::Query(r.cstr()?),
+			b'P' => {
+				let name = r.cstr()?;
+				let query = r.cstr()?;
+				let count = r.count()?;
+				let mut param_types = Vec::with_capacity(count);
+				for _ in 0..count {
+					param_types.push(r.i32()? as Oid);
+				}
+				Self::Parse {
+					name,
+					query,
+					param_types,
+				}
+			}
+			b'B' => {
+				let portal = r.cstr()?;
+				let statement = r.cstr()?;
+				let param_formats = r.formats()?;
+				let count = r.count()?;
+				let mut params = Vec::with_capacity(count);
+				for _ in 0..count {
+					params.push(match r.i32()? {
+						-1 => None,
+						len if len < 0 => {
+							return Err(PgError::protocol("invalid parameter length"));
+						}
+						len => Some(body.slice_ref(r.bytes(len as usize)?)),
+					});
+				}
+				let result_formats = r.formats()?;
+				Self::Bind {
+					portal,
+					statement,
+					param_formats,
+					params,
+					result_formats,
+				}
+			}
+			b'D' => Self::Describe {
+				target: r.target()?,
+				name: r.cstr()?,
+			},
+			b'E' => Self::Execute {
+				portal: r.cstr()?,
+				// Zero (or a negative count) means no limit.
+				max_rows: usize::try_from(r.i32()?).unwrap_or(0),
+			},
+			b'C' => Self::Close {
+				target: r.target()?,
+				name: r.cstr()?,
+			},
+			b'p' => Self::Password(r.cstr()?),
+			b'S' => Self::Sync,
+			b'H' => Self::Flush,
+			b'X' => Self::Terminate,
+			b'd' | b'c' | b'f' => {
+				return Err(PgError::fatal(
+					sqlstate::FEATURE_NOT_SUPPORTED,
+					"COPY is not supported",
+				));
+			}
+			b'F' => {
+				return Err(PgError::fatal(
+					sqlstate::FEATURE_NOT_SUPPORTED,
+					"the function call sub-protocol is not supported",
+				));
+			}
+			tag => {
+				return Err(PgError::protocol(format!(
+					"invalid frontend message type {}",
+					char::from(tag).escape_default()
+				)));
+			}
+		};
+		Ok(msg)
+	}
+}
+
+/// Read the untagged startup packet.
+pub(super) async fn read_startup<R: AsyncRead + Unpin>(
+	rd: &mut R,
+	max_size: usize,
+) -> std::io::Result<Bytes> {
+	let len = rd.read_i32().await?;
+	read_body(rd, len, max_size).await
+}
+
+/// Read the next tagged message, or `None` when the client closed the
+/// connection between messages.
+pub(super) async fn read_message<R: AsyncRead + Unpin>(
+	rd: &mut R,
+	max_size: usize,
+) -> std::io::Result<Option<(u8, Bytes)>> {
+	let tag = match rd.read_u8().await {
+		Ok(tag) => tag,
+		Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+		Err(e) => return Err(e),
+	};
+	let len = rd.read_i32().await?;
+	Ok(Some((tag, read_body(rd, len, max_size).await?)))
+}
+
+/// Read a message body given its length prefix, which counts itself.
+async fn read_body<R: AsyncRead + Unpin>(
+	rd: &mut R,
+	len: i32,
+	max_size: usize,
+) -> std::io::Result<Bytes> {
+	let len = usize::try_from(len)
+		.ok()
+		.and_then(|len| len.checked_sub(4))
+		.ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "invalid length"))?;
+	if len > max_size {
+		return Err(std::io::Error::new(
+			std::io::ErrorKind::InvalidData,
+			format!("message of {len} bytes exceeds the maximum of {max_size} bytes"),
+		));
+	}
+	let mut body = vec![0; len];
+	rd.read_exact(&mut body).await?;
+	Ok(body.into())
+}
+
+/// A cursor over a message body.
+struct Reader<'a> {
+	buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+	fn new(buf: &'a [u8]) -> Self {
+		Self {
+			buf,
+		}
+	}
+
+	fn bytes(&mut self, len: usize) -> Result<&'a [u8], PgError> {
+		if self.buf.len() < len {
+			return Err(PgError::protocol("message is shorter than its contents"));
+		}
+		let (head, tail) = self.buf.split_at(len);
+		self.buf = tail;
+		Ok(head)
+	}
+
+	fn u8(&mut self) -> Result<u8, PgError> {
+		Ok(self.bytes(1)?[0])
+	}
+
+	fn i16(&mut self) -> Result<i16, PgError> {
+		let b = self.bytes(2)?;
+		Ok(i16::from_be_bytes([b[0], b[1]]))
+	}
+
+	fn i32(&mut self) -> Result<i32, PgError> {
+		let b = self.bytes(4)?;
+		Ok(i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+	}
+
+	/// A non-negative `Int16` element count.
+	fn count(&mut self) -> Result<usize, PgError> {
+		usize::try_from(self.i16()?).map_err(|_| PgError::protocol("negative element count"))
+	}
+
+	fn cstr(&mut self) -> Result<String, PgError> {
+		let Some(end) = self.buf.iter().position(|b| *b == 0) else {
+			return Err(PgError::protocol("unterminated string in message"));
+		};
+		let s = std::str::from_utf8(&self.buf[..end]).map_err(|_| {
+			PgError::new(sqlstate::CHARACTER_NOT_IN_REPERTOIRE, "invalid UTF-8 in message")
+		})?;
+		self.buf = &self.buf[end + 1..];
+		Ok(s.to_owned())
+	}
+
+	fn formats(&mut self) -> Result<Vec<Format>, PgError> {
+		let count = self.count()?;
+		(0..count).map(|_| Format::from_code(self.i16()?)).collect()
+	}
+
+	fn target(&mut self) -> Result<Target, PgError> {
+		match self.u8()? {
+			b'S' => Ok(Target::Statement),
+			b'P' => Ok(Target::Portal),
+			_ => Err(PgError::protocol("invalid Describe/Close target")),
+		}
+	}
+}
+
+/// A column of a `RowDescription`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) struct FieldDescription {
+	pub name: String,
+	pub oid: Oid,
+	pub format: Format,
+}
+
+/// The buffer of backend messages waiting to be flushed.
+#[derive(Default)]
+pub(super) struct Backend {
+	buf: BytesMut,
+}
+
+impl Backend {
+	/// Take the buffered messages, leaving the buffer empty.
+	pub fn take(&mut self) -> BytesMut {
+		self.buf.split()
+	}
+
+	/// Append one message, filling in its length once `body` has written it.
+	fn message(&mut self, tag: u8, body: impl FnOnce(&mut BytesMut)) {
+		let start = self.buf.len();
+		self.buf.put_u8(tag);
+		self.buf.put_i32(0);
+		body(&mut self.buf);
+		let len = (self.buf.len() - start - 1) as i32;
+		self.buf[start + 1..start + 5].copy_from_slice(&len.to_be_bytes());
+	}
+
+	pub fn authentication_ok(&mut self) {
+		self.message(b'R', |b| b.put_i32(0));
+	}
+
+	pub fn authentication_cleartext_password(&mut self) {
+		self.message(b'R', |b| b.put_i32(3));
+	}
+
+	pub fn parameter_status(&mut self, name: &str, value: &str) {
+		self.message(b'S', |b| {
+			put_cstr(b, name);
+			put_cstr(b, value);
+		});
+	}
+
+	pub fn backend_key_data(&mut self, pid: i32, secret: i32) {
+		self.message(b'K', |b| {
+			b.put_i32(pid);
+			b.put_i32(secret);
+		});
+	}
+
+	/// `ReadyForQuery`. Every query runs in its own transaction, so the
+	/// session is always idle between queries.
+	pub fn ready_for_query(&mut self) {
+		self.message(b'Z', |b| b.put_u8(b'I'));
+	}
+
+	pub fn row_description(&mut self, fields: &[FieldDescription]) {
+		self.message(b'T', |b| {
+			b.put_i16(fields.len() as i16);
+			for field in fields {
+				put_cstr(b, &field.name);
+				// No source table or column
+				b.put_i32(0);
+				b.put_i16(0);
+				b.put_u32(field.oid);
+				b.put_i16(type_len(field.oid));
+				// No type modifier
+				b.put_i32(-1);
+				b.put_i16(field.format.code());
+			}
+		});
+	}
+
+	pub fn data_row(&mut self, cells: &[Option<Bytes>]) {
+		self.message(b'D', |b| {
+			b.put_i16(cells.len() as i16);
+			for cell in cells {
+				match cell {
+					Some(cell) => {
+						b.put_i32(cell.len() as i32);
+						b.put_slice(cell);
+					}
+					None => b.put_i32(-1),
+				}
+			}
+		});
+	}
+
+	pub fn command_complete(&mut self, tag: &str) {
+		self.message(b'C', |b| put_cstr(b, tag));
+	}
+
+	pub fn empty_query_response(&mut self) {
+		self.message(b'I', |_| {});
+	}
+
+	pub fn error_response(&mut self, err: &PgError) {
+		self.message(b'E', |b| {
+			b.put_u8(b'S');
+			put_cstr(b, err.severity);
+			b.put_u8(b'V');
+			put_cstr(b, err.severity);
+			b.put_u8(b'C');
+			put_cstr(b, err.code);
+			b.put_u8(b'M');
+			put_cstr(b, &err.message);
+			b.put_u8(0);
+		});
+	}
+
+	pub fn parse_complete(&mut self) {
+		self.message(b'1', |_| {});
+	}
+
+	pub fn bind_complete(&mut self) {
+		self.message(b'2', |_| {});
+	}
+
+	pub fn close_complete(&mut self) {
+		self.message(b'3', |_| {});
+	}
+
+	pub fn no_data(&mut self) {
+		self.message(b'n', |_| {});
+	}
+
+	pub fn portal_suspended(&mut self) {
+		self.message(b's', |_| {});
+	}
+
+	pub fn parameter_description(&mut self, oids: &[Oid]) {
+		self.message(b't', |b| {
+			b.put_i16(oids.len() as i16);
+			for oid in oids {
+				b.put_u32(*oid);
+			}
+		});
+	}
+}
+
+/// Append a NUL-terminated string. Embedded NULs cannot be represented, so
+/// they are dropped.
+fn put_cstr(buf: &mut BytesMut, s: &str) {
+	buf.extend(s.bytes().filter(|b| *b != 0));
+	buf.put_u8(0);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::super::types::{INT8, TEXT};
+	use super::*;
+
+	fn cstr(s: &str) -> Vec<u8> {
+		let mut v = s.as_bytes().to_vec();
+		v.push(0);
+		v
+	}
+
+	#[test]
+	fn decodes_startup_parameters() {
+		let mut body = PROTOCOL_VERSION.to_be_bytes().to_vec();
+		for s in ["user", "root", "database", "test/test", ""] {
+			body.extend(cstr(s));
+		}
+		assert_eq!(
+			StartupRequest::decode(&body).unwrap(),
+			StartupRequest::Startup(vec![
+				("user".into(), "root".into()),
+				("database".into(), "test/test".into()),
+			])
+		);
+		assert_eq!(
+			StartupRequest::decode(&SSL_REQUEST.to_be_bytes()).unwrap(),
+			StartupRequest::Ssl
+		);
+		let err = StartupRequest::decode(&(2 << 16).to_be_bytes()).unwrap_err();
+		assert_eq!(err.code, sqlstate::FEATURE_NOT_SUPPORTED);
+		assert!(err.message.contains("2.0"), "{}", err.message);
+	}
+
+	#[test]
+	fn decodes_bind() {
+		let mut body = Vec::new();
+		body.extend(cstr("p1"));
+		body.extend(cstr("s1"));
+		// One format code for all parameters: binary
+		body.extend(1i16.to_be_bytes());
+		body.extend(1i16.to_be_bytes());
+		// Two parameters: an int8 and a NULL
+		body.extend(2i16.to_be_bytes());
+		body.extend(8i32.to_be_bytes());
+		body.extend(42i64.to_be_bytes());
+		body.extend((-1i32).to_be_bytes());
+		// No result format codes
+		body.extend(0i16.to_be_bytes());
+		let msg = FrontendMessage::decode(b'B', body.into()).unwrap();
+		assert_eq!(
+			msg,
+			FrontendMessage::Bind {
+				portal: "p1".into(),
+				statement: "s1".into(),
+				param_formats: vec![Format::Binary],
+				params: vec![Some(Bytes::copy_from_slice(&42i64.to_be_bytes())), None],
+				result_formats: vec![],
+			}
+		);
+	}
+
+	#[test]
+	fn rejects_truncated_and_unknown_messages() {
+		let mut body = cstr("s1");
+		body.extend(cstr("SELECT * FROM person"));
+		// Declares two parameter types but carries one
+		body.extend(2i16.to_be_bytes());
+		body.extend((TEXT as i32).to_be_bytes());
+		let err = FrontendMessage::decode(b'P', body.into()).unwrap_err();
+		assert_eq!(err.code, sqlstate::PROTOCOL_VIOLATION);
+		let err = FrontendMessage::decode(b'd', Bytes::new()).unwrap_err();
+		assert_eq!(err.code, sqlstate::FEATURE_NOT_SUPPORTED);
+		let err = FrontendMessage::decode(b'?', Bytes::new()).unwrap_err();
+		assert_eq!(err.code, sqlstate::PROTOCOL_VIOLATION);
+	}
+
+	#[test]
+	fn encodes_length_prefixed_messages() {
+		let mut out = Backend::default();
+		out.row_description(&[FieldDescription {
+			name: "age".into(),
+			oid: INT8,
+			format: Format::Text,
+		}]);
+		out.data_row(&[Some(Bytes::from_static(b"30")), None]);
+		out.ready_for_query();
+		let buf = out.take();
+		// RowDescription: tag, length, field count, then one 22 byte field
+		assert_eq!(buf[0], b'T');
+		assert_eq!(i32::from_be_bytes(buf[1..5].try_into().unwrap()), 4 + 2 + 22);
+		let row = &buf[1 + 28..];
+		assert_eq!(row[0], b'D');
+		assert_eq!(i32::from_be_bytes(row[1..5].try_into().unwrap()), 4 + 2 + 4 + 2 + 4);
+		assert_eq!(&row[11..13], b"30");
+		assert_eq!(i32::from_be_bytes(row[13..17].try_into().unwrap()), -1);
+		assert_eq!(&row[17..], &[b'Z', 0, 0, 0, 5, b'I']);
+		assert!(out.take().is_empty());
+	}
+
+	#[tokio::test]
+	async fn reads_frames_and_enforces_the_size_limit() {
+		let mut input: &[u8] = &[b'Q', 0, 0, 0, 6, b'x', 0, b'S', 0, 0, 0, 4];
+		assert_eq!(
+			read_message(&mut input, 64).await.unwrap(),
+			Some((b'Q', Bytes::from_static(b"x\0")))
+		);
+		assert_eq!(read_message(&mut input, 64).await.unwrap(), Some((b'S', Bytes::new())));
+		assert_eq!(read_message(&mut input, 64).await.unwrap(), None);
+		let mut input: &[u8] = &[b'Q', 0, 0, 1, 0];
+		assert!(read_message(&mut input, 64).await.is_err());
+	}
+}
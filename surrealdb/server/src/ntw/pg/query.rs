@@ -0,0 +1,227 @@
+//! Classification of the query strings sent by clients.
+//!
+//! Queries are SurrealQL. The exceptions are the `SET` and `SHOW` commands
+//! which drivers send while setting up a connection (`SET extra_float_digits
+//! = 3`, `SHOW transaction_isolation`, ...): they read and write the
+//! connection's run-time parameters instead of reaching the datastore.
+
+/// What a query string asks the connection to do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(super) enum Query {
+	/// An empty query string (or one made only of whitespace and `;`).
+	Empty,
+	/// `SET [SESSION | LOCAL] name { TO | = } value`.
+	Set {
+		name: String,
+		value: String,
+	},
+	/// `SHOW name`.
+	Show(String),
+	/// Anything else, run as SurrealQL.
+	Surreal(String),
+}
+
+impl Query {
+	pub fn classify(text: &str) -> Self {
+		let trimmed = text.trim().trim_end_matches(';').trim_end();
+		if trimmed.is_empty() {
+			return Self::Empty;
+		}
+		let mut words = trimmed.split_whitespace();
+		let keyword = words.next().unwrap_or_default();
+		if keyword.eq_ignore_ascii_case("SET") {
+			if let Some((name, value)) = parse_set(trimmed[keyword.len()..].trim_start()) {
+				return Self::Set {
+					name,
+					value,
+				};
+			}
+		} else if keyword.eq_ignore_ascii_case("SHOW") {
+			// `SHOW CHANGES FOR ...` is SurrealQL, and is never a single word
+			let rest = words.collect::<Vec<_>>();
+			match rest.as_slice() {
+				[name] if is_parameter_name(name) => return Self::Show(name.to_ascii_lowercase()),
+				[time, zone]
+					if time.eq_ignore_ascii_case("TIME") && zone.eq_ignore_ascii_case("ZONE") =>
+				{
+					return Self::Show("timezone".to_owned());
+				}
+				_ => {}
+			}
+		}
+		Self::Surreal(text.to_owned())
+	}
+
+	/// The number of positional parameters (`$1`, `$2`, ...) the query uses:
+	/// the highest parameter number outside of strings and comments. Numbers
+	/// above [`MAX_PARAMS`] are counted as `MAX_PARAMS + 1`, so the caller
+	/// can reject them without allocating for each parameter.
+	pub fn param_count(&self) -> usize {
+		match self {
+			Self::Surreal(text) => max_positional_param(text),
+			_ => 0,
+		}
+	}
+}
+
+/// Parse the tail of a `SET` command.
+fn parse_set(rest: &str) -> Option<(String, String)> {
+	let mut rest = rest;
+	for scope in ["SESSION", "LOCAL"] {
+		if let Some(head) = rest.get(..scope.len())
+			&& head.eq_ignore_ascii_case(scope)
+			&& rest[scope.len()..].starts_with(char::is_whitespace)
+		{
+			rest = rest[scope.len()..].trim_start();
+		}
+	}
+	// `SET TIME ZONE value`
+	let time_zone = "TIME ZONE";
+	if let Some(head) = rest.get(..time_zone.len())
+		&& head.eq_ignore_ascii_case(time_zone)
+	{
+		return Some(("timezone".to_owned(), parse_set_value(&rest[time_zone.len()..])?));
+	}
+	let end = rest.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(rest.len());
+	let (name, rest) = rest.split_at(end);
+	if !is_parameter_name(name) {
+		return None;
+	}
+	let rest = rest.trim_start();
+	let value = if let Some(value) = rest.strip_prefix('=') {
+		value
+	} else if rest.get(..2).is_some_and(|to| to.eq_ignore_ascii_case("TO")) {
+		rest[2..].strip_prefix(char::is_whitespace)?
+	} else {
+		return None;
+	};
+	Some((name.to_ascii_lowercase(), parse_set_value(value)?))
+}
+
+/// A `SET` value: a quoted string, or a bare word or number. `DEFAULT`
+/// resets the parameter to an empty value.
+fn parse_set_value(value: &str) -> Option<String> {
+	let value = value.trim();
+	if let Some(quoted) = value.strip_prefix('\'') {
+		return Some(quoted.strip_suffix('\'')?.replace("''", "'"));
+	}
+	if value.is_empty() || value.contains(char::is_whitespace) {
+		return None;
+	}
+	if value.eq_ignore_ascii_case("DEFAULT") {
+		return Some(String::new());
+	}
+	Some(value.to_owned())
+}
+
+fn is_parameter_name(name: &str) -> bool {
+	!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// The maximum number of positional parameters of a statement, as in
+/// PostgreSQL, where the `Bind` message counts them with an `i16`.
+pub const MAX_PARAMS: usize = u16::MAX as usize;
+
+/// Scan for `$<digits>` outside of quoted strings and comments.
+fn max_positional_param(text: &str) -> usize {
+	let bytes = text.as_bytes();
+	let mut max = 0;
+	let mut i = 0;
+	while i < bytes.len() {
+		match bytes[i] {
+			quote @ (b'\'' | b'"' | b'`') => {
+				i += 1;
+				while i < bytes.len() && bytes[i] != quote {
+					// Skip escaped characters
+					if bytes[i] == b'\\' {
+						i += 1;
+					}
+					i += 1;
+				}
+			}
+			b'-' if bytes.get(i + 1) == Some(&b'-') => {
+				i = line_end(bytes, i);
+			}
+			b'/' if bytes.get(i + 1) == Some(&b'/') => {
+				i = line_end(bytes, i);
+			}
+			b'#' => {
+				i = line_end(bytes, i);
+			}
+			b'/' if bytes.get(i + 1) == Some(&b'*') => {
+				i = text[i + 2..].find("*/").map(|end| i + 2 + end + 1).unwrap_or(bytes.len());
+			}
+			b'$' => {
+				let start = i + 1;
+				let mut end = start;
+				while end < bytes.len() && bytes[end].is_ascii_digit() {
+					end += 1;
+				}
+				// `$1abc` is a named parameter, not a positional one
+				let named =
+					end < bytes.len() && (bytes[end].is_ascii_alphabetic() || bytes[end] == b'_');
+				if end > start && !named {
+					// Numbers too large to parse are beyond the limit too
+					let n = text[start..end].parse::<usize>().unwrap_or(usize::MAX);
+					max = max.max(n.min(MAX_PARAMS + 1));
+				}
+				i = end - 1;
+			}
+			_ => {}
+		}
+		i += 1;
+	}
+	max
+}
+
+fn line_end(bytes: &[u8], from: usize) -> usize {
+	bytes[from..].iter().position(|b| *b == b'\n').map(|n| from + n).unwrap_or(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn set(name: &str, value: &str) -> Query {
+		Query::Set {
+			name: name.to_owned(),
+			value: value.to_owned(),
+		}
+	}
+
+	#[test]
+	fn recognises_set_and_show() {
+		assert_eq!(Query::classify("SET extra_float_digits = 3"), set("extra_float_digits", "3"));
+		assert_eq!(
+			Query::classify("set application_name to 'PostgreSQL JDBC Driver';"),
+			set("application_name", "PostgreSQL JDBC Driver")
+		);
+		assert_eq!(Query::classify("SET SESSION TIME ZONE 'UTC'"), set("timezone", "UTC"));
+		assert_eq!(Query::classify("SHOW TimeZone"), Query::Show("timezone".into()));
+		assert_eq!(Query::classify("SHOW TIME ZONE"), Query::Show("timezone".into()));
+		assert_eq!(Query::classify(" ;"), Query::Empty);
+	}
+
+	#[test]
+	fn leaves_surrealql_alone() {
+		for text in [
+			"SHOW CHANGES FOR TABLE person SINCE 1",
+			"SELECT * FROM person",
+			"SET x",
+			"SETTINGS = 1",
+		] {
+			assert_eq!(Query::classify(text), Query::Surreal(text.into()), "{text}");
+		}
+	}
+
+	#[test]
+	fn counts_positional_parameters() {
+		let count = |text: &str| Query::classify(text).param_count();
+		assert_eq!(count("SELECT * FROM person WHERE age > $2 AND name = $1"), 2);
+		assert_eq!(count("SELECT * FROM person WHERE name = '$3' -- $4\nAND age = $1"), 1);
+		assert_eq!(count("RETURN $1abc /* $9 */ + $10"), 10);
+		assert_eq!(count("SELECT * FROM person WHERE name = $name"), 0);
+		assert_eq!(count("RETURN $4000000000"), MAX_PARAMS + 1);
+		assert_eq!(count("RETURN $99999999999999999999999"), MAX_PARAMS + 1);
+	}
+}
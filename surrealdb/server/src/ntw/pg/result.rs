@@ -0,0 +1,182 @@
+//! Shaping statement results into rows and columns.
+//!
+//! A statement returning an array (or set) of objects becomes one row per
+//! object, with a column per field; fields missing from a row are `NULL`.
+//! Any other result becomes a single `?column?` column, one row per array
+//! element, or a single row for a scalar. A statement returning `NONE`
+//! (`LET`, `DEFINE`, ...) produces no rows at all.
+
+use bytes::Bytes;
+use surrealdb_types::Value;
+
+use super::protocol::{FieldDescription, PgError};
+use super::types::{self, Format, Oid, TEXT};
+
+/// The column name PostgreSQL gives to an unnamed result column.
+const UNNAMED_COLUMN: &str = "?column?";
+
+#[derive(Debug, PartialEq)]
+pub(super) struct Column {
+	pub name: String,
+	pub oid: Oid,
+}
+
+/// The rows returned by one statement.
+#[derive(Debug, PartialEq)]
+pub(super) struct ResultSet {
+	pub columns: Vec<Column>,
+	pub rows: Vec<Vec<Value>>,
+}
+
+impl ResultSet {
+	/// Shape a statement result, or `None` when the statement returned
+	/// `NONE`.
+	pub fn from_value(value: Value) -> Option<Self> {
+		let items: Vec<Value> = match value {
+			Value::None => return None,
+			Value::Array(items) => items.into_iter().collect(),
+			Value::Set(items) => items.into_iter().collect(),
+			value => vec![value],
+		};
+		let objects = !items.is_empty() && items.iter().all(|v| matches!(v, Value::Object(_)));
+		let (names, rows) = if objects {
+			let objects = items
+				.into_iter()
+				.filter_map(|item| match item {
+					Value::Object(object) => Some(object),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			let mut names: Vec<String> = Vec::new();
+			for key in objects.iter().flat_map(|object| object.keys()) {
+				if !names.contains(key) {
+					names.push(key.clone());
+				}
+			}
+			let rows = objects
+				.into_iter()
+				.map(|mut object| {
+					names.iter().map(|name| object.remove(name).unwrap_or(Value::None)).collect()
+				})
+				.collect();
+			(names, rows)
+		} else if items.is_empty() {
+			(Vec::new(), Vec::new())
+		} else {
+			(vec![UNNAMED_COLUMN.to_owned()], items.into_iter().map(|item| vec![item]).collect())
+		};
+		let columns = names
+			.into_iter()
+			.enumerate()
+			.map(|(i, name)| Column {
+				name,
+				oid: types::kind_oid(&types::column_kind(rows.iter().map(|row| &row[i]))),
+			})
+			.collect();
+		Some(Self {
+			columns,
+			rows,
+		})
+	}
+
+	/// A single text cell, the result of `SHOW`.
+	pub fn text(name: &str, value: &str) -> Self {
+		Self {
+			columns: vec![Column {
+				name: name.to_owned(),
+				oid: TEXT,
+			}],
+			rows: vec![vec![Value::String(value.to_owned())]],
+		}
+	}
+
+	/// The `RowDescription` fields, in the result formats given at `Bind`.
+	pub fn fields(&self, formats: &[Format]) -> Result<Vec<FieldDescription>, PgError> {
+		let formats = Format::expand(formats, self.columns.len())?;
+		Ok(self
+			.columns
+			.iter()
+			.zip(formats)
+			.map(|(column, format)| FieldDescription {
+				name: column.name.clone(),
+				oid: column.oid,
+				format,
+			})
+			.collect())
+	}
+
+	/// Encode one row as `DataRow` cells.
+	pub fn encode_row(
+		&self,
+		row: &[Value],
+		formats: &[Format],
+	) -> Result<Vec<Option<Bytes>>, PgError> {
+		let formats = Format::expand(formats, self.columns.len())?;
+		row.iter()
+			.zip(&self.columns)
+			.zip(formats)
+			.map(|((value, column), format)| types::encode(value, column.oid, format))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::{Number, Object};
+
+	use super::super::types::{INT8, JSONB};
+	use super::*;
+
+	fn object(fields: &[(&str, Value)]) -> Value {
+		Value::Object(fields.iter().map(|(k, v)| (k.to_string(), v.clone())).collect::<Object>())
+	}
+
+	#[test]
+	fn objects_become_rows() {
+		let int = |n| Value::Number(Number::Int(n));
+		let name = |s: &str| Value::String(s.to_owned());
+		let value = Value::Array(
+			vec![
+				object(&[("age", int(30)), ("name", name("A"))]),
+				object(&[("name", name("C")), ("tags", Value::Array(Vec::<Value>::new().into()))]),
+			]
+			.into(),
+		);
+		let set = ResultSet::from_value(value).unwrap();
+		let columns = set.columns.iter().map(|c| (c.name.as_str(), c.oid)).collect::<Vec<_>>();
+		assert_eq!(columns, [("age", INT8), ("name", TEXT), ("tags", JSONB)]);
+		assert_eq!(set.rows[1][0], Value::None);
+		let cells = set.encode_row(&set.rows[1], &[]).unwrap();
+		assert_eq!(cells, [None, Some(Bytes::from_static(b"C")), Some(Bytes::from_static(b"[]"))]);
+	}
+
+	#[test]
+	fn other_results_use_one_unnamed_column() {
+		let set = ResultSet::from_value(Value::Number(Number::Int(1))).unwrap();
+		assert_eq!(
+			set.columns,
+			[Column {
+				name: "?column?".into(),
+				oid: INT8,
+			}]
+		);
+		assert_eq!(set.rows.len(), 1);
+		// A mixed array is one text column
+		let value = Value::Array(vec![object(&[]), Value::Bool(true)].into());
+		let set = ResultSet::from_value(value).unwrap();
+		assert_eq!(set.columns[0].oid, TEXT);
+		assert_eq!(set.rows.len(), 2);
+		// An empty array has no columns, NONE has no result at all
+		let set = ResultSet::from_value(Value::Array(Vec::<Value>::new().into())).unwrap();
+		assert!(set.columns.is_empty() && set.rows.is_empty());
+		assert_eq!(ResultSet::from_value(Value::None), None);
+	}
+
+	#[test]
+	fn result_formats_must_match_the_columns() {
+		let set = ResultSet::text("timezone", "UTC");
+		let fields = set.fields(&[Format::Binary]).unwrap();
+		assert_eq!(fields[0].format, Format::Binary);
+		assert!(set.fields(&[Format::Text, Format::Text]).is_err());
+	}
+}
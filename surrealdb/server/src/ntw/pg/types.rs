@@ -0,0 +1,482 @@
+//! Mapping between SurrealDB values and PostgreSQL types.
+//!
+//! A result column is typed by the [`Kind`] of its values: the kinds of the
+//! non-null cells are merged with [`Kind::either`] and the merged kind picks
+//! the type OID ([`kind_oid`]). Cells are then encoded for that OID in the
+//! text or binary format the client asked for. Bound parameters go the other
+//! way, decoded from the type the client declared for them at `Parse`.
+
+use std::str::FromStr;
+
+use bytes::{BufMut, Bytes, BytesMut};
+use surrealdb_types::{Datetime, Decimal, Kind, KindLiteral, Number, ToSql, Uuid, Value};
+
+use super::protocol::{PgError, sqlstate};
+
+/// A PostgreSQL type OID.
+pub(super) type Oid = u32;
+
+pub(super) const BOOL: Oid = 16;
+pub(super) const BYTEA: Oid = 17;
+pub(super) const INT8: Oid = 20;
+pub(super) const INT2: Oid = 21;
+pub(super) const INT4: Oid = 23;
+pub(super) const TEXT: Oid = 25;
+pub(super) const JSON: Oid = 114;
+pub(super) const FLOAT4: Oid = 700;
+pub(super) const FLOAT8: Oid = 701;
+pub(super) const UNKNOWN: Oid = 705;
+pub(super) const VARCHAR: Oid = 1043;
+pub(super) const TIMESTAMP: Oid = 1114;
+pub(super) const TIMESTAMPTZ: Oid = 1184;
+pub(super) const NUMERIC: Oid = 1700;
+pub(super) const UUID: Oid = 2950;
+pub(super) const JSONB: Oid = 3802;
+
+/// Microseconds between the Unix epoch and the PostgreSQL epoch (2000-01-01).
+const PG_EPOCH_MICROS: i64 = 946_684_800_000_000;
+
+/// The wire format of a parameter or result column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(super) enum Format {
+	#[default]
+	Text,
+	Binary,
+}
+
+impl Format {
+	pub fn from_code(code: i16) -> Result<Self, PgError> {
+		match code {
+			0 => Ok(Self::Text),
+			1 => Ok(Self::Binary),
+			code => Err(PgError::fatal(
+				sqlstate::PROTOCOL_VIOLATION,
+				format!("invalid format code {code}"),
+			)),
+		}
+	}
+
+	pub fn code(self) -> i16 {
+		match self {
+			Self::Text => 0,
+			Self::Binary => 1,
+		}
+	}
+
+	/// Expand the format codes of a `Bind` message to one per item: none
+	/// means all text, a single code applies to every item.
+	pub fn expand(formats: &[Format], count: usize) -> Result<Vec<Format>, PgError> {
+		match formats {
+			[] => Ok(vec![Format::Text; count]),
+			[format] => Ok(vec![*format; count]),
+			formats if formats.len() == count => Ok(formats.to_vec()),
+			formats => Err(PgError::new(
+				sqlstate::PROTOCOL_VIOLATION,
+				format!("{} format codes given for {count} items", formats.len()),
+			)),
+		}
+	}
+}
+
+/// The `typlen` reported in a `RowDescription`: the fixed width of the type,
+/// or -1 for variable-width types.
+pub(super) fn type_len(oid: Oid) -> i16 {
+	match oid {
+		BOOL => 1,
+		INT2 => 2,
+		INT4 | FLOAT4 => 4,
+		INT8 | FLOAT8 | TIMESTAMP | TIMESTAMPTZ => 8,
+		UUID => 16,
+		_ => -1,
+	}
+}
+
+/// The kind of a single value, with numbers narrowed to `int`, `float` or
+/// `decimal`.
+pub(super) fn value_kind(value: &Value) -> Kind {
+	match value {
+		Value::Number(n) => n.kind(),
+		v => v.kind(),
+	}
+}
+
+/// The merged kind of a column's values. `NONE` and `NULL` cells are sent as
+/// SQL `NULL` and so do not constrain the column type.
+pub(super) fn column_kind<'a>(values: impl IntoIterator<Item = &'a Value>) -> Kind {
+	Kind::either(
+		values
+			.into_iter()
+			.filter(|v| !matches!(v, Value::None | Value::Null))
+			.map(value_kind)
+			.collect(),
+	)
+}
+
+/// The PostgreSQL type used to send values of `kind`.
+pub(super) fn kind_oid(kind: &Kind) -> Oid {
+	match kind {
+		Kind::Bool => BOOL,
+		Kind::Int => INT8,
+		Kind::Float => FLOAT8,
+		Kind::Decimal | Kind::Number => NUMERIC,
+		Kind::String => TEXT,
+		Kind::Bytes => BYTEA,
+		Kind::Datetime => TIMESTAMPTZ,
+		Kind::Uuid => UUID,
+		Kind::Object | Kind::Array(..) | Kind::Set(..) | Kind::Geometry(_) => JSONB,
+		Kind::Literal(literal) => match literal {
+			KindLiteral::String(_) | KindLiteral::Duration(_) => TEXT,
+			KindLiteral::Integer(_) => INT8,
+			KindLiteral::Float(_) => FLOAT8,
+			KindLiteral::Decimal(_) => NUMERIC,
+			KindLiteral::Bool(_) => BOOL,
+			KindLiteral::Array(_) | KindLiteral::Object(_) => JSONB,
+		},
+		Kind::Either(kinds) => {
+			let mut oids = kinds
+				.iter()
+				.filter(|k| !matches!(k, Kind::None | Kind::Null))
+				.map(kind_oid)
+				.collect::<Vec<_>>();
+			oids.dedup();
+			match oids.as_slice() {
+				[] => TEXT,
+				[oid] => *oid,
+				// Mixed integers, floats and decimals widen to numeric
+				oids if oids.iter().all(|o| matches!(*o, INT8 | FLOAT8 | NUMERIC)) => NUMERIC,
+				oids if oids.iter().all(|o| *o == oids[0]) => oids[0],
+				_ => TEXT,
+			}
+		}
+		// Durations, record ids, tables, ranges, regexes, files and values of
+		// no particular kind are sent in their SurrealQL text form.
+		_ => TEXT,
+	}
+}
+
+/// Encode one cell of a column of type `oid`. `NONE` and `NULL` become SQL
+/// `NULL`.
+pub(super) fn encode(value: &Value, oid: Oid, format: Format) -> Result<Option<Bytes>, PgError> {
+	if matches!(value, Value::None | Value::Null) {
+		return Ok(None);
+	}
+	let bytes = match format {
+		Format::Text => Bytes::from(encode_text(value, oid)),
+		Format::Binary => encode_binary(value, oid)?,
+	};
+	Ok(Some(bytes))
+}
+
+fn encode_text(value: &Value, oid: Oid) -> String {
+	if matches!(oid, JSON | JSONB) {
+		return json_text(value);
+	}
+	match value {
+		Value::Bool(true) => "t".to_owned(),
+		Value::Bool(false) => "f".to_owned(),
+		Value::Number(Number::Int(v)) => v.to_string(),
+		Value::Number(Number::Float(v)) => float_text(*v),
+		Value::Number(Number::Decimal(v)) => v.to_string(),
+		Value::String(v) => v.clone(),
+		Value::Bytes(v) => {
+			let mut out = String::with_capacity(2 + v.len() * 2);
+			out.push_str("\\x");
+			for b in v.iter() {
+				out.push_str(&format!("{b:02x}"));
+			}
+			out
+		}
+		Value::Datetime(v) => v.format("%Y-%m-%d %H:%M:%S%.f+00").to_string(),
+		Value::Uuid(v) => v.to_string(),
+		Value::Object(_) | Value::Array(_) | Value::Set(_) | Value::Geometry(_) => json_text(value),
+		v => v.to_sql(),
+	}
+}
+
+fn encode_binary(value: &Value, oid: Oid) -> Result<Bytes, PgError> {
+	let mut buf = BytesMut::new();
+	match (oid, value) {
+		(TEXT, v) => buf.put_slice(encode_text(v, TEXT).as_bytes()),
+		(JSON, v) => buf.put_slice(json_text(v).as_bytes()),
+		(JSONB, v) => {
+			// The jsonb binary format is a version byte followed by the text
+			buf.put_u8(1);
+			buf.put_slice(json_text(v).as_bytes());
+		}
+		(BOOL, Value::Bool(v)) => buf.put_u8(u8::from(*v)),
+		(INT8, Value::Number(Number::Int(v))) => buf.put_i64(*v),
+		(FLOAT8, Value::Number(Number::Float(v))) => buf.put_f64(*v),
+		(BYTEA, Value::Bytes(v)) => buf.put_slice(v),
+		(UUID, Value::Uuid(v)) => buf.put_slice(v.as_bytes()),
+		(TIMESTAMPTZ, Value::Datetime(v)) => buf.put_i64(v.timestamp_micros() - PG_EPOCH_MICROS),
+		(oid, _) => {
+			return Err(PgError::new(
+				sqlstate::FEATURE_NOT_SUPPORTED,
+				format!("the binary format is not supported for type {}", type_name(oid)),
+			));
+		}
+	}
+	Ok(buf.freeze())
+}
+
+fn json_text(value: &Value) -> String {
+	value.clone().into_json_value().to_string()
+}
+
+fn float_text(v: f64) -> String {
+	if v.is_nan() {
+		"NaN".to_owned()
+	} else if v.is_infinite() {
+		if v > 0.0 {
+			"Infinity".to_owned()
+		} else {
+			"-Infinity".to_owned()
+		}
+	} else {
+		v.to_string()
+	}
+}
+
+/// Decode a bound parameter sent for a parameter declared as `oid`. An
+/// undeclared (`0`) or unknown type is taken as text.
+pub(super) fn decode(bytes: &[u8], oid: Oid, format: Format) -> Result<Value, PgError> {
+	match format {
+		Format::Text => {
+			let text = std::str::from_utf8(bytes).map_err(|_| {
+				PgError::new(sqlstate::CHARACTER_NOT_IN_REPERTOIRE, "invalid UTF-8 in parameter")
+			})?;
+			decode_text(text, oid)
+		}
+		Format::Binary => decode_binary(bytes, oid),
+	}
+}
+
+fn decode_text(text: &str, oid: Oid) -> Result<Value, PgError> {
+	let invalid = || {
+		PgError::new(
+			sqlstate::INVALID_TEXT_REPRESENTATION,
+			format!("invalid input syntax for type {}: \"{text}\"", type_name(oid)),
+		)
+	};
+	let value = match oid {
+		BOOL => match text.trim().to_ascii_lowercase().as_str() {
+			"t" | "true" | "y" | "yes" | "on" | "1" => Value::Bool(true),
+			"f" | "false" | "n" | "no" | "off" | "0" => Value::Bool(false),
+			_ => return Err(invalid()),
+		},
+		INT2 | INT4 | INT8 => {
+			Value::Number(Number::Int(text.trim().parse().map_err(|_| invalid())?))
+		}
+		FLOAT4 | FLOAT8 => {
+			Value::Number(Number::Float(text.trim().parse().map_err(|_| invalid())?))
+		}
+		NUMERIC => {
+			let text = text.trim();
+			let v = Decimal::from_str(text)
+				.or_else(|_| Decimal::from_scientific(text))
+				.map_err(|_| invalid())?;
+			Value::Number(Number::Decimal(v))
+		}
+		UUID => Value::Uuid(Uuid::from_str(text.trim()).map_err(|_| invalid())?),
+		TIMESTAMP | TIMESTAMPTZ => {
+			Value::Datetime(parse_timestamp(text.trim()).ok_or_else(invalid)?)
+		}
+		JSON | JSONB => surrealdb_core::syn::json(text).map_err(|_| invalid())?,
+		BYTEA => {
+			let hex = text.strip_prefix("\\x").ok_or_else(invalid)?;
+			Value::Bytes(decode_hex(hex).ok_or_else(invalid)?.into())
+		}
+		_ => Value::String(text.to_owned()),
+	};
+	Ok(value)
+}
+
+fn decode_binary(bytes: &[u8], oid: Oid) -> Result<Value, PgError> {
+	let invalid = || {
+		PgError::new(
+			sqlstate::PROTOCOL_VIOLATION,
+			format!("incorrect binary data format for type {}", type_name(oid)),
+		)
+	};
+	let value = match oid {
+		BOOL => match bytes {
+			[b] => Value::Bool(*b != 0),
+			_ => return Err(invalid()),
+		},
+		INT2 => Value::Number(Number::Int(
+			i16::from_be_bytes(bytes.try_into().map_err(|_| invalid())?).into(),
+		)),
+		INT4 => Value::Number(Number::Int(
+			i32::from_be_bytes(bytes.try_into().map_err(|_| invalid())?).into(),
+		)),
+		INT8 => {
+			Value::Number(Number::Int(i64::from_be_bytes(bytes.try_into().map_err(|_| invalid())?)))
+		}
+		FLOAT4 => Value::Number(Number::Float(
+			f32::from_be_bytes(bytes.try_into().map_err(|_| invalid())?).into(),
+		)),
+		FLOAT8 => Value::Number(Number::Float(f64::from_be_bytes(
+			bytes.try_into().map_err(|_| invalid())?,
+		))),
+		UUID => Value::Uuid(Uuid::from(::uuid::Uuid::from_bytes(
+			bytes.try_into().map_err(|_| invalid())?,
+		))),
+		TIMESTAMP | TIMESTAMPTZ => {
+			let micros = i64::from_be_bytes(bytes.try_into().map_err(|_| invalid())?)
+				.checked_add(PG_EPOCH_MICROS)
+				.ok_or_else(invalid)?;
+			let datetime = Datetime::from_timestamp(
+				micros.div_euclid(1_000_000),
+				(micros.rem_euclid(1_000_000) * 1_000) as u32,
+			)
+			.ok_or_else(invalid)?;
+			Value::Datetime(datetime)
+		}
+		BYTEA => Value::Bytes(bytes.to_vec().into()),
+		JSONB => match bytes.split_first() {
+			Some((1, json)) => decode_text(std::str::from_utf8(json).map_err(|_| invalid())?, oid)?,
+			_ => return Err(invalid()),
+		},
+		0 | TEXT | VARCHAR | UNKNOWN | JSON => {
+			let text = std::str::from_utf8(bytes).map_err(|_| invalid())?;
+			decode_text(text, oid)?
+		}
+		oid => {
+			return Err(PgError::new(
+				sqlstate::FEATURE_NOT_SUPPORTED,
+				format!("the binary format is not supported for type {}", type_name(oid)),
+			));
+		}
+	};
+	Ok(value)
+}
+
+/// Parse a timestamp in RFC 3339 form or in PostgreSQL's output form, where
+/// the date and time are separated by a space and the offset may be hours
+/// only (`2024-01-02 03:04:05+00`). A timestamp without an offset is UTC.
+fn parse_timestamp(text: &str) -> Option<Datetime> {
+	if let Ok(v) = Datetime::from_str(text) {
+		return Some(v);
+	}
+	let mut text = text.replacen(' ', "T", 1);
+	let offset = text.rfind(['+', '-']).filter(|i| *i > 10);
+	match offset {
+		// An hours-only offset
+		Some(i) if text.len() - i == 3 => text.push_str(":00"),
+		Some(_) => {}
+		None if !text.ends_with(['Z', 'z']) => text.push('Z'),
+		None => {}
+	}
+	Datetime::from_str(&text).ok()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+	if !hex.len().is_multiple_of(2) {
+		return None;
+	}
+	(0..hex.len()).step_by(2).map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok()).collect()
+}
+
+/// The PostgreSQL name of a type, for error messages.
+pub(super) fn type_name(oid: Oid) -> &'static str {
+	match oid {
+		BOOL => "boolean",
+		BYTEA => "bytea",
+		INT8 => "bigint",
+		INT2 => "smallint",
+		INT4 => "integer",
+		TEXT => "text",
+		JSON => "json",
+		FLOAT4 => "real",
+		FLOAT8 => "double precision",
+		VARCHAR => "character varying",
+		TIMESTAMP => "timestamp without time zone",
+		TIMESTAMPTZ => "timestamp with time zone",
+		NUMERIC => "numeric",
+		UUID => "uuid",
+		JSONB => "jsonb",
+		_ => "unknown",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::{Array, Object};
+
+	use super::*;
+
+	fn text(value: Value, oid: Oid) -> Option<String> {
+		encode(&value, oid, Format::Text).unwrap().map(|b| String::from_utf8(b.to_vec()).unwrap())
+	}
+
+	#[test]
+	fn column_types_follow_the_merged_kind() {
+		let int = Value::Number(Number::Int(1));
+		let float = Value::Number(Number::Float(1.5));
+		let string = Value::String("a".into());
+		assert_eq!(kind_oid(&column_kind([&int, &Value::None, &int])), INT8);
+		assert_eq!(kind_oid(&column_kind([&int, &float])), NUMERIC);
+		assert_eq!(kind_oid(&column_kind([&int, &string])), TEXT);
+		assert_eq!(kind_oid(&column_kind([&Value::Null])), TEXT);
+		let object = Value::Object(Object::new());
+		let array = Value::Array(Array::new());
+		assert_eq!(kind_oid(&column_kind([&object, &array])), JSONB);
+		// Declared kinds map the same way, ignoring optionality
+		assert_eq!(kind_oid(&Kind::option(Kind::Datetime)), TIMESTAMPTZ);
+		assert_eq!(kind_oid(&Kind::Record(Vec::new())), TEXT);
+		assert_eq!(kind_oid(&Kind::Duration), TEXT);
+	}
+
+	#[test]
+	fn encodes_text_cells() {
+		assert_eq!(text(Value::Bool(true), BOOL).as_deref(), Some("t"));
+		assert_eq!(text(Value::Number(Number::Float(f64::NAN)), FLOAT8).as_deref(), Some("NaN"));
+		// Integers in a numeric column keep their own text form
+		assert_eq!(text(Value::Number(Number::Int(7)), NUMERIC).as_deref(), Some("7"));
+		assert_eq!(text(Value::Bytes(vec![0xde, 0xad].into()), BYTEA).as_deref(), Some("\\xdead"));
+		let datetime = Datetime::from_timestamp(1_700_000_000, 500_000_000).unwrap();
+		assert_eq!(
+			text(Value::Datetime(datetime), TIMESTAMPTZ).as_deref(),
+			Some("2023-11-14 22:13:20.500+00")
+		);
+		// A string in a jsonb column (a mixed object/string column is text,
+		// but a jsonb column is always valid JSON)
+		assert_eq!(text(Value::String("a".into()), JSONB).as_deref(), Some("\"a\""));
+		assert_eq!(text(Value::None, TEXT), None);
+	}
+
+	#[test]
+	fn encodes_binary_cells() {
+		let cell = |v: Value, oid| encode(&v, oid, Format::Binary).unwrap().unwrap().to_vec();
+		assert_eq!(cell(Value::Number(Number::Int(258)), INT8), 258i64.to_be_bytes());
+		assert_eq!(cell(Value::Object(Object::new()), JSONB), b"\x01{}");
+		let epoch = Datetime::from_timestamp(946_684_801, 0).unwrap();
+		assert_eq!(cell(Value::Datetime(epoch), TIMESTAMPTZ), 1_000_000i64.to_be_bytes());
+		let err = encode(&Value::Number(Number::Int(1)), NUMERIC, Format::Binary).unwrap_err();
+		assert_eq!(err.code, sqlstate::FEATURE_NOT_SUPPORTED);
+	}
+
+	#[test]
+	fn decodes_parameters() {
+		assert_eq!(decode(b"42", INT4, Format::Text).unwrap(), Value::Number(Number::Int(42)));
+		assert_eq!(decode(b"on", BOOL, Format::Text).unwrap(), Value::Bool(true));
+		assert_eq!(decode(b"42", 0, Format::Text).unwrap(), Value::String("42".into()));
+		assert_eq!(
+			decode(&7i32.to_be_bytes(), INT4, Format::Binary).unwrap(),
+			Value::Number(Number::Int(7))
+		);
+		assert_eq!(
+			decode(b"2024-01-02 03:04:05+00", TIMESTAMPTZ, Format::Text).unwrap(),
+			decode(b"2024-01-02T03:04:05Z", TIMESTAMPTZ, Format::Text).unwrap(),
+		);
+		assert_eq!(
+			decode(b"2024-01-02 03:04:05", TIMESTAMP, Format::Text).unwrap(),
+			decode(b"2024-01-02T03:04:05Z", TIMESTAMP, Format::Text).unwrap(),
+		);
+		let err = decode(b"forty", INT8, Format::Text).unwrap_err();
+		assert_eq!(err.code, sqlstate::INVALID_TEXT_REPRESENTATION);
+		assert!(err.message.contains("bigint"), "{}", err.message);
+		let err = decode(&[0; 3], INT4, Format::Binary).unwrap_err();
+		assert_eq!(err.code, sqlstate::PROTOCOL_VIOLATION);
+	}
+}
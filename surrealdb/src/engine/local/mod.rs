@@ -395,6 +395,47 @@ pub struct TiKv;
 #[derive(Debug)]
 pub struct SurrealKv;
 
+/// redb database, stored in a single file
+///
+/// The storage engine runs no background threads. Its transactions run under
+/// snapshot isolation rather than serializable isolation: a transaction only
+/// conflicts with another which wrote one of the same keys, so concurrent
+/// transactions which each read what the other writes can both commit.
+///
+/// # Examples
+///
+/// Instantiating a redb-backed instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Redb;
+///
+/// let db = Surreal::new::<Redb>("path/to/database.redb").await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Instantiating a redb-backed strict instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::opt::Config;
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Redb;
+///
+/// let config = Config::default().strict();
+/// let db = Surreal::new::<Redb>(("path/to/database.redb", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "kv-redb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kv-redb")))]
+#[derive(Debug)]
+pub struct Redb;
+
 /// An embedded database
 #[derive(Debug, Clone)]
 pub struct Db(());
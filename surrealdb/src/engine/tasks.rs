@@ -67,8 +67,14 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task8 = spawn_task_tikv_lock_cleanup(Arc::clone(&dbs), canceller.clone(), opts);
 	let task9 = spawn_task_reclaim_tombstones(Arc::clone(&dbs), canceller.clone(), opts);
 	let task10 = spawn_task_resume_index_builds(Arc::clone(&dbs), canceller.clone(), opts);
-	let task11 = spawn_task_live_query_router(dbs, canceller, opts);
-	Tasks(vec![task1, task2, task3, task4, task5, task6, task7, task8, task9, task10, task11])
+	let task11 = spawn_task_live_query_router(Arc::clone(&dbs), canceller.clone(), opts);
+	let task12 = spawn_task_sink_delivery(Arc::clone(&dbs), canceller.clone(), opts);
+	let task13 = spawn_task_record_expiry(Arc::clone(&dbs), canceller.clone(), opts);
+	let task14 = spawn_task_reencryption(dbs, canceller, opts);
+	Tasks(vec![
+		task1, task2, task3, task4, task5, task6, task7, task8, task9, task10, task11, task12,
+		task13, task14,
+	])
 }
 
 /// Spawns the per-node live-query router task.
@@ -232,6 +238,116 @@ fn spawn_task_changefeed_cleanup(
 	}))
 }
 
+/// Spawns a background task for delivering changefeed mutations to sinks
+///
+/// The task runs at the interval specified by `opts.sink_delivery_interval`,
+/// and is coordinated across the cluster by a task lease, so only a single
+/// node delivers the changes of each sink at any one time.
+fn spawn_task_sink_delivery(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.sink_delivery_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running sink delivery every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.sink_delivery(&interval).await {
+						error!("Error running sink delivery: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running sink delivery");
+	}))
+}
+
+/// Spawns a background task for deleting expired records
+///
+/// The task runs at the interval specified by `opts.record_expiry_interval`,
+/// and is coordinated across the cluster by a task lease, so only a single
+/// node deletes the records which have outlived their table TTL at any one
+/// time.
+fn spawn_task_record_expiry(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.record_expiry_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running record expiry every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.record_expiry(&interval).await {
+						error!("Error running record expiry: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running record expiry");
+	}))
+}
+
+/// Spawns a background task for re-encrypting values sealed with a retired key
+///
+/// The task runs at the interval specified by `opts.reencryption_interval`,
+/// and is coordinated across the cluster by a task lease, so only a single
+/// node re-encrypts the values at any one time. Each run resumes where the
+/// previous one stopped.
+fn spawn_task_reencryption(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.reencryption_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running re-encryption every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.reencryption(&interval).await {
+						error!("Error running re-encryption: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running re-encryption");
+	}))
+}
+
 /// Spawns a background task for index compaction
 ///
 /// This function creates a background task that periodically runs the index
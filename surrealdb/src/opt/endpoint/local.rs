@@ -65,9 +65,10 @@ impl<R> Connect<Db, R> {
 	/// Set the disk sync mode.
 	///
 	/// Controls how and when data is flushed to disk. Supported by `SurrealKv`,
-	/// `RocksDb`, and `Mem` engines (the `Mem` engine requires a persist path,
-	/// e.g. `Surreal::new::<Mem>("/tmp/data")` or `connect("mem:///tmp/data")`,
-	/// for sync to take effect).
+	/// `RocksDb`, `Redb`, and `Mem` engines (the `Mem` engine requires a persist
+	/// path, e.g. `Surreal::new::<Mem>("/tmp/data")` or `connect("mem:///tmp/data")`,
+	/// for sync to take effect). The `Redb` engine does not support interval
+	/// syncing, and syncs on every commit instead.
 	///
 	/// The `mode` argument can be any type that implements `Display`. The
 	/// canonical type is `SyncMode`:
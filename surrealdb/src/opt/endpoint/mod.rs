@@ -7,6 +7,8 @@ mod ws;
 mod indxdb;
 #[cfg(feature = "kv-mem")]
 mod mem;
+#[cfg(feature = "kv-redb")]
+mod redb;
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb;
 #[cfg(feature = "kv-surrealkv")]
@@ -14,7 +16,12 @@ mod surrealkv;
 #[cfg(feature = "kv-tikv")]
 mod tikv;
 
-#[cfg(any(feature = "kv-mem", feature = "kv-surrealkv", feature = "kv-rocksdb"))]
+#[cfg(any(
+	feature = "kv-mem",
+	feature = "kv-surrealkv",
+	feature = "kv-rocksdb",
+	feature = "kv-redb"
+))]
 mod local;
 
 use url::Url;
@@ -54,7 +61,12 @@ impl Endpoint {
 	/// Append a query parameter to the endpoint path string.
 	/// Only used when a local engine (e.g. `kv-mem`, `kv-rocksdb`) is enabled.
 	#[cfg_attr(
-		not(any(feature = "kv-mem", feature = "kv-surrealkv", feature = "kv-rocksdb")),
+		not(any(
+			feature = "kv-mem",
+			feature = "kv-surrealkv",
+			feature = "kv-rocksdb",
+			feature = "kv-redb"
+		)),
 		allow(dead_code)
 	)]
 	pub(crate) fn append_query_param(&mut self, key: &str, value: &str) {
@@ -154,6 +166,7 @@ pub enum EndpointKind {
 	TiKv,
 	Unsupported(String),
 	SurrealKv,
+	Redb,
 }
 
 impl From<&str> for EndpointKind {
@@ -169,6 +182,7 @@ impl From<&str> for EndpointKind {
 			"rocksdb" => Self::RocksDb,
 			"tikv" => Self::TiKv,
 			"surrealkv" => Self::SurrealKv,
+			"redb" => Self::Redb,
 			_ => Self::Unsupported(s.to_owned()),
 		}
 	}
@@ -0,0 +1,145 @@
+// RUST_LOG=warn cargo make ci-pg-integration
+mod common;
+
+mod pg_integration {
+	use std::collections::HashMap;
+	use std::time::Duration;
+
+	use rand::Rng;
+	use test_log::test;
+	use tokio::io::{AsyncReadExt, AsyncWriteExt};
+	use tokio::net::TcpStream;
+	use tokio_postgres::error::SqlState;
+	use tokio_postgres::{Client, NoTls, SimpleQueryMessage};
+
+	use super::common::{self, Child, PASS, StartServerArguments, USER};
+
+	/// Start a server listening for PostgreSQL connections on `host`, returning
+	/// the address of the PostgreSQL listener.
+	async fn start(
+		host: &str,
+		tls: bool,
+		vars: Option<HashMap<String, String>>,
+	) -> (String, Child) {
+		let port: u16 = rand::rng().random_range(24000..30000);
+		let (_, server) = common::start_server(StartServerArguments {
+			tls,
+			// The self signed certificate can not be checked for readiness
+			wait_is_ready: !tls,
+			args: format!("--pg-bind {host}:{port}"),
+			vars,
+			..Default::default()
+		})
+		.await
+		.unwrap();
+		let addr = format!("127.0.0.1:{port}");
+		// Otherwise wait for the listener to accept connections
+		if tls {
+			for _ in 0..20 {
+				if TcpStream::connect(&addr).await.is_ok() {
+					break;
+				}
+				tokio::time::sleep(Duration::from_millis(500)).await;
+			}
+		}
+		(addr, server)
+	}
+
+	async fn connect(addr: &str, password: &str) -> Result<Client, tokio_postgres::Error> {
+		let (host, port) = addr.split_once(':').unwrap();
+		let config = format!(
+			"host={host} port={port} user={USER} password={password} dbname=test/test sslmode=disable"
+		);
+		let (client, connection) = tokio_postgres::connect(&config, NoTls).await?;
+		tokio::spawn(connection);
+		Ok(client)
+	}
+
+	fn values(messages: &[SimpleQueryMessage], column: &str) -> Vec<String> {
+		messages
+			.iter()
+			.filter_map(|msg| match msg {
+				SimpleQueryMessage::Row(row) => row.get(column).map(str::to_owned),
+				_ => None,
+			})
+			.collect()
+	}
+
+	#[test(tokio::test)]
+	async fn simple_and_extended_queries() {
+		let (addr, _server) = start("127.0.0.1", false, None).await;
+		let client = connect(&addr, PASS).await.unwrap();
+		// Simple query protocol
+		client.simple_query("CREATE person:tobie SET name = 'Tobie'").await.unwrap();
+		// Extended query protocol, with a positional parameter
+		let rows = client.execute("CREATE person:jaime SET name = $1", &[&"Jaime"]).await.unwrap();
+		assert_eq!(rows, 1);
+		let res = client.simple_query("SELECT name FROM person ORDER BY name").await.unwrap();
+		assert_eq!(values(&res, "name"), vec!["Jaime", "Tobie"]);
+		// Run-time parameters
+		let res = client.simple_query("SHOW server_encoding").await.unwrap();
+		assert_eq!(values(&res, "server_encoding"), vec!["UTF8"]);
+	}
+
+	#[test(tokio::test)]
+	async fn rejects_a_wrong_password() {
+		let (addr, _server) = start("127.0.0.1", false, None).await;
+		let err = connect(&addr, "wrong").await.err().unwrap();
+		assert_eq!(err.code(), Some(&SqlState::INVALID_PASSWORD));
+	}
+
+	#[test(tokio::test)]
+	async fn refuses_cleartext_passwords_off_loopback() {
+		let (addr, _server) = start("0.0.0.0", false, None).await;
+		let err = connect(&addr, PASS).await.err().unwrap();
+		assert_eq!(err.code(), Some(&SqlState::INVALID_AUTHORIZATION_SPECIFICATION));
+	}
+
+	#[test(tokio::test)]
+	async fn negotiates_tls_with_the_web_certificate() {
+		let (addr, _server) = start("0.0.0.0", true, None).await;
+		// An `SSLRequest` packet is accepted once a certificate is configured
+		let mut stream = TcpStream::connect(&addr).await.unwrap();
+		stream.write_all(&[0, 0, 0, 8, 4, 210, 22, 47]).await.unwrap();
+		assert_eq!(stream.read_u8().await.unwrap(), b'S');
+	}
+
+	#[test(tokio::test)]
+	async fn bounds_connections_and_prepared_statements() {
+		let vars = HashMap::from([
+			("SURREAL_PG_MAX_CONNECTIONS".to_owned(), "1".to_owned()),
+			("SURREAL_PG_MAX_STATEMENTS".to_owned(), "2".to_owned()),
+		]);
+		let (addr, _server) = start("127.0.0.1", false, Some(vars)).await;
+		let client = connect(&addr, PASS).await.unwrap();
+		// Connections beyond the limit are refused
+		let err = connect(&addr, PASS).await.err().unwrap();
+		assert_eq!(err.code(), Some(&SqlState::TOO_MANY_CONNECTIONS));
+		// Named statements beyond the limit are refused
+		client.prepare("RETURN 1").await.unwrap();
+		client.prepare("RETURN 2").await.unwrap();
+		let err = client.prepare("RETURN 3").await.err().unwrap();
+		assert_eq!(err.code(), Some(&SqlState::PROGRAM_LIMIT_EXCEEDED));
+		// Positional parameters beyond the PostgreSQL limit are refused
+		let err = client.execute("RETURN $4000000000", &[]).await.err().unwrap();
+		assert_eq!(err.code(), Some(&SqlState::PROGRAM_LIMIT_EXCEEDED));
+		// The connection is still usable
+		client.simple_query("RETURN 4").await.unwrap();
+	}
+
+	#[test(tokio::test)]
+	async fn closes_connections_which_do_not_authenticate() {
+		let vars = HashMap::from([
+			("SURREAL_PG_MAX_CONNECTIONS".to_owned(), "1".to_owned()),
+			("SURREAL_PG_AUTHENTICATION_TIMEOUT".to_owned(), "1".to_owned()),
+		]);
+		let (addr, _server) = start("127.0.0.1", false, Some(vars)).await;
+		// An idle client is disconnected once the timeout expires
+		let mut stream = TcpStream::connect(&addr).await.unwrap();
+		let mut buf = Vec::new();
+		let read = tokio::time::timeout(Duration::from_secs(10), stream.read_to_end(&mut buf));
+		assert_eq!(read.await.unwrap().unwrap(), 0);
+		// Which releases its connection slot
+		connect(&addr, PASS).await.unwrap();
+	}
+}